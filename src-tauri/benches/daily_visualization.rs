@@ -0,0 +1,89 @@
+// Benchmark daily visualization generation on busy days (20+ workblocks)
+// Run with: cargo bench --bench daily_visualization
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use log15_lib::db::{create_schema, query_daily_visualization_data, VisualizationSource};
+use rusqlite::{params, Connection};
+use std::hint::black_box;
+
+const DATE: &str = "2024-05-06";
+const INTERVALS_PER_WORKBLOCK: i64 = 4;
+const ACTIVITIES: [&str; 6] = ["coding", "code review", "meeting", "emails", "design", "Away from workspace"];
+
+/// A day of back-to-back one-hour workblocks with answered, split and attached intervals
+fn busy_day(workblocks: i64) -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let start = chrono::DateTime::parse_from_rfc3339(&format!("{}T00:00:00+00:00", DATE)).unwrap();
+    for block in 0..workblocks {
+        let block_start = start + chrono::Duration::minutes(block * 60);
+        let status = if block % 7 == 6 { "cancelled" } else { "completed" };
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, end_time, duration_minutes, status, title, project)
+             VALUES (?1, ?2, ?3, 60, ?4, ?5, 'Client X')",
+            params![
+                DATE,
+                block_start.to_rfc3339(),
+                (block_start + chrono::Duration::minutes(60)).to_rfc3339(),
+                status,
+                format!("Block {}", block),
+            ],
+        )
+        .unwrap();
+        let workblock_id = conn.last_insert_rowid();
+
+        for number in 0..INTERVALS_PER_WORKBLOCK {
+            let interval_start = block_start + chrono::Duration::minutes(number * 15);
+            let words = ACTIVITIES[((block + number) as usize) % ACTIVITIES.len()];
+            conn.execute(
+                "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status, energy)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'recorded', ?6)",
+                params![
+                    workblock_id,
+                    number + 1,
+                    interval_start.to_rfc3339(),
+                    (interval_start + chrono::Duration::minutes(15)).to_rfc3339(),
+                    words,
+                    (number % 5) + 1,
+                ],
+            )
+            .unwrap();
+            let interval_id = conn.last_insert_rowid();
+
+            if number == 1 {
+                conn.execute(
+                    "INSERT INTO interval_activities (interval_id, position, words, percentage)
+                     VALUES (?1, 0, 'coding', 60), (?1, 1, 'meeting', 40)",
+                    params![interval_id],
+                )
+                .unwrap();
+            }
+            if number == 2 {
+                conn.execute(
+                    "INSERT INTO interval_attachments (interval_id, file_name, stored_path, size_bytes)
+                     VALUES (?1, 'notes.png', '/tmp/notes.png', 1024)",
+                    params![interval_id],
+                )
+                .unwrap();
+            }
+        }
+    }
+    conn
+}
+
+fn bench_daily_visualization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("daily_visualization");
+    for workblocks in [20, 40] {
+        let conn = busy_day(workblocks);
+        group.bench_with_input(BenchmarkId::new("full_day", workblocks), &conn, |b, conn| {
+            b.iter(|| query_daily_visualization_data(conn, black_box(DATE)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("load_day", workblocks), &conn, |b, conn| {
+            b.iter(|| VisualizationSource::load_day(conn, black_box(DATE)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_daily_visualization);
+criterion_main!(benches);
@@ -0,0 +1,108 @@
+// Acknowledged delivery for lifecycle-critical events. Tauri events are fire-and-forget, so an
+// interval-complete emitted while the webview reloads is simply lost. These events carry a
+// sequence number and stay pending until the frontend calls ack_event; whatever is still pending
+// is replayed when the main window regains focus.
+
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// Oldest pending events are dropped beyond this many
+pub const MAX_PENDING_EVENTS: usize = 100;
+
+/// Pending events older than this aren't replayed any more (one interval)
+pub const PENDING_EVENT_TTL_MINUTES: i64 = 15;
+
+/// Payload of an acknowledged event: the original payload plus the sequence number to ack
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AckedEvent {
+    pub seq: u64,
+    pub payload: Value,
+}
+
+#[derive(Debug, Clone)]
+struct PendingEvent {
+    event: String,
+    emitted_at: DateTime<Local>,
+    acked: AckedEvent,
+}
+
+#[derive(Debug, Default)]
+pub struct PendingEvents {
+    next_seq: u64,
+    pending: VecDeque<PendingEvent>,
+}
+
+impl PendingEvents {
+    pub const fn new() -> Self {
+        PendingEvents { next_seq: 0, pending: VecDeque::new() }
+    }
+
+    /// Register an event about to be emitted and return its sequenced payload
+    pub fn push(&mut self, event: &str, payload: Value, now: DateTime<Local>) -> AckedEvent {
+        self.next_seq += 1;
+        let acked = AckedEvent { seq: self.next_seq, payload };
+        self.pending.push_back(PendingEvent { event: event.to_string(), emitted_at: now, acked: acked.clone() });
+        while self.pending.len() > MAX_PENDING_EVENTS {
+            self.pending.pop_front();
+        }
+        acked
+    }
+
+    /// Mark an event delivered. Returns false when `seq` isn't pending (already acked or dropped).
+    pub fn ack(&mut self, seq: u64) -> bool {
+        let before = self.pending.len();
+        self.pending.retain(|p| p.acked.seq != seq);
+        self.pending.len() != before
+    }
+
+    /// Forget every pending event; returns how many there were. Sequence numbers keep counting up.
+    pub fn clear(&mut self) -> usize {
+        let cleared = self.pending.len();
+        self.pending.clear();
+        cleared
+    }
+
+    /// Events to re-emit, oldest first. Expired events are dropped.
+    pub fn replayable(&mut self, now: DateTime<Local>) -> Vec<(String, AckedEvent)> {
+        let cutoff = now - Duration::minutes(PENDING_EVENT_TTL_MINUTES);
+        self.pending.retain(|p| p.emitted_at >= cutoff);
+        self.pending.iter().map(|p| (p.event.clone(), p.acked.clone())).collect()
+    }
+}
+
+static PENDING: Mutex<PendingEvents> = Mutex::new(PendingEvents::new());
+
+/// Emit `event` with acknowledged delivery; listeners receive an AckedEvent
+pub fn emit_acked<T: Serialize>(app: &AppHandle, event: &str, payload: T) {
+    let payload = match serde_json::to_value(payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            eprintln!("[EVENTS] Failed to serialize {} payload: {}", event, e);
+            return;
+        }
+    };
+    let acked = PENDING.lock().unwrap_or_else(|e| e.into_inner()).push(event, payload, Local::now());
+    let _ = app.emit(event, acked);
+}
+
+pub fn ack_event(seq: u64) -> bool {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner()).ack(seq)
+}
+
+/// Drop every unacknowledged event, e.g. once the data they refer to was wiped
+pub fn clear_pending() -> usize {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner()).clear()
+}
+
+/// Re-emit every unacknowledged event; listeners skip sequence numbers they already handled
+pub fn replay_unacked(app: &AppHandle) {
+    let replay = PENDING.lock().unwrap_or_else(|e| e.into_inner()).replayable(Local::now());
+    for (event, acked) in replay {
+        println!("[EVENTS] Replaying unacknowledged {} (seq {})", event, acked.seq);
+        let _ = app.emit(&event, acked);
+    }
+}
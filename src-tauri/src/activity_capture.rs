@@ -0,0 +1,301 @@
+// Opt-in sampling of the focused application (and optionally its window title) during workblocks
+
+use crate::db::{get_active_workblock, get_current_interval, get_db_connection, get_today_date};
+use crate::idle::is_user_idle;
+use crate::settings::get_activity_capture_settings;
+use rusqlite::{params, Connection, Result};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// The application in front when a sample was taken
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusedWindow {
+    pub app_name: String,
+    pub title: Option<String>,
+}
+
+/// Time one application (and window title, if captured) was in front during an interval
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IntervalAppUsage {
+    pub app_name: String,
+    pub window_title: Option<String>,
+    pub seconds: i64,
+}
+
+/// Time per application across a workblock ("apps used")
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppUsage {
+    pub app_name: String,
+    pub seconds: i64,
+    pub percentage: f64,
+}
+
+/// Add `seconds` of `app_name` being in front to an interval's breakdown
+pub fn record_app_sample(
+    conn: &Connection,
+    interval_id: i64,
+    app_name: &str,
+    window_title: Option<&str>,
+    seconds: i64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO interval_app_usage (interval_id, app_name, window_title, seconds)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(interval_id, app_name, window_title) DO UPDATE SET seconds = seconds + excluded.seconds",
+        params![interval_id, app_name.trim(), window_title.map(str::trim).unwrap_or(""), seconds],
+    )?;
+    Ok(())
+}
+
+/// Breakdown for one interval, longest first
+pub fn query_interval_app_usage(conn: &Connection, interval_id: i64) -> Result<Vec<IntervalAppUsage>> {
+    let mut stmt = conn.prepare(
+        "SELECT app_name, window_title, seconds
+         FROM interval_app_usage
+         WHERE interval_id = ?1
+         ORDER BY seconds DESC, app_name, window_title",
+    )?;
+    let rows = stmt.query_map(params![interval_id], |row| {
+        let title: String = row.get(1)?;
+        Ok(IntervalAppUsage {
+            app_name: row.get(0)?,
+            window_title: (!title.is_empty()).then_some(title),
+            seconds: row.get(2)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Time per application over all intervals of a workblock, longest first
+pub fn query_workblock_app_usage(conn: &Connection, workblock_id: i64) -> Result<Vec<AppUsage>> {
+    let mut stmt = conn.prepare(
+        "SELECT u.app_name, SUM(u.seconds) AS total
+         FROM interval_app_usage u
+         JOIN intervals i ON i.id = u.interval_id
+         WHERE i.workblock_id = ?1
+         GROUP BY u.app_name
+         ORDER BY total DESC, u.app_name",
+    )?;
+    let rows = stmt
+        .query_map(params![workblock_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+        .collect::<Result<Vec<_>>>()?;
+    let total: i64 = rows.iter().map(|(_, seconds)| seconds).sum();
+    Ok(rows
+        .into_iter()
+        .map(|(app_name, seconds)| AppUsage {
+            app_name,
+            seconds,
+            percentage: if total > 0 { seconds as f64 / total as f64 * 100.0 } else { 0.0 },
+        })
+        .collect())
+}
+
+/// Add `seconds` of the user being active at the computer on `date`, inside or outside a workblock.
+/// Only totals are kept here; applications are recorded per interval during workblocks only.
+pub fn record_computer_activity(conn: &Connection, date: &str, seconds: i64, in_workblock: bool) -> Result<()> {
+    let (tracked, untracked) = if in_workblock { (seconds, 0) } else { (0, seconds) };
+    conn.execute(
+        "INSERT INTO computer_activity (date, tracked_seconds, untracked_seconds)
+         VALUES (?1, ?2, ?3)
+         ON CONFLICT(date) DO UPDATE SET
+             tracked_seconds = tracked_seconds + excluded.tracked_seconds,
+             untracked_seconds = untracked_seconds + excluded.untracked_seconds",
+        params![date, tracked, untracked],
+    )?;
+    Ok(())
+}
+
+/// Seconds active at the computer on `date` as (during workblocks, outside workblocks); None if nothing was captured
+pub fn query_computer_activity(conn: &Connection, date: &str) -> Result<Option<(i64, i64)>> {
+    let mut stmt = conn.prepare("SELECT tracked_seconds, untracked_seconds FROM computer_activity WHERE date = ?1")?;
+    let mut rows = stmt.query_map(params![date], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.next().transpose()
+}
+
+/// Delete everything captured so far; returns the number of rows removed
+pub fn clear_app_usage(conn: &Connection) -> Result<usize> {
+    let apps = conn.execute("DELETE FROM interval_app_usage", [])?;
+    let days = conn.execute("DELETE FROM computer_activity", [])?;
+    Ok(apps + days)
+}
+
+pub fn get_interval_app_usage(app: &AppHandle, interval_id: i64) -> Result<Vec<IntervalAppUsage>> {
+    let conn = get_db_connection(app)?;
+    query_interval_app_usage(&conn, interval_id)
+}
+
+pub fn clear_activity_capture(app: &AppHandle) -> Result<usize> {
+    let conn = get_db_connection(app)?;
+    clear_app_usage(&conn)
+}
+
+/// Sample the focused application in the background while capture is enabled.
+/// Active time is counted all day for the time audit; applications are only sampled for the
+/// pending interval of an active workblock. Nothing is recorded while the user is idle.
+pub fn spawn_activity_capture(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let settings = get_activity_capture_settings(&app);
+            tokio::time::sleep(tokio::time::Duration::from_secs(settings.sample_seconds)).await;
+            if !settings.enabled || is_user_idle(&app) {
+                continue;
+            }
+            let interval_id = current_interval_id(&app);
+            let result = get_db_connection(&app).and_then(|conn| {
+                Ok(record_computer_activity(&conn, &get_today_date(), settings.sample_seconds as i64, interval_id.is_some())?)
+            });
+            if let Err(e) = result {
+                eprintln!("[CAPTURE] Failed to record activity: {}", e);
+            }
+            let Some(interval_id) = interval_id else {
+                continue;
+            };
+            let Some(window) = tauri::async_runtime::spawn_blocking(focused_window).await.ok().flatten() else {
+                continue;
+            };
+            let title = window.title.as_deref().filter(|_| settings.window_titles);
+            let result = get_db_connection(&app).and_then(|conn| {
+                Ok(record_app_sample(&conn, interval_id, &window.app_name, title, settings.sample_seconds as i64)?)
+            });
+            if let Err(e) = result {
+                eprintln!("[CAPTURE] Failed to record sample: {}", e);
+            }
+        }
+    });
+}
+
+fn current_interval_id(app: &AppHandle) -> Option<i64> {
+    let workblock = get_active_workblock(app).ok().flatten()?;
+    get_current_interval(app, workblock.id?).ok().flatten()?.id
+}
+
+/// The application in front right now, if the platform exposes it
+pub fn focused_window() -> Option<FocusedWindow> {
+    platform_focused_window().filter(|w| !w.app_name.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn platform_focused_window() -> Option<FocusedWindow> {
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e", "tell application \"System Events\" to set p to first application process whose frontmost is true",
+            "-e", "set n to name of p",
+            "-e", "try",
+            "-e", "set t to name of front window of p",
+            "-e", "on error",
+            "-e", "set t to \"\"",
+            "-e", "end try",
+            "-e", "return n & linefeed & t",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_osascript_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_focused_window() -> Option<FocusedWindow> {
+    use std::ffi::c_void;
+
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetForegroundWindow() -> *mut c_void;
+        fn GetWindowTextW(hwnd: *mut c_void, text: *mut u16, max_count: i32) -> i32;
+        fn GetWindowThreadProcessId(hwnd: *mut c_void, process_id: *mut u32) -> u32;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn OpenProcess(desired_access: u32, inherit_handle: i32, process_id: u32) -> *mut c_void;
+        fn QueryFullProcessImageNameW(process: *mut c_void, flags: u32, name: *mut u16, size: *mut u32) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    // SAFETY: no preconditions; a null handle means no window has focus
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_null() {
+        return None;
+    }
+
+    let mut title = [0u16; 512];
+    // SAFETY: the buffer holds `title.len()` UTF-16 units
+    let title_len = unsafe { GetWindowTextW(hwnd, title.as_mut_ptr(), title.len() as i32) };
+    let title = String::from_utf16_lossy(&title[..title_len.max(0) as usize]);
+
+    let mut pid = 0u32;
+    // SAFETY: `hwnd` is a window handle and `pid` a valid out pointer
+    unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+    // SAFETY: opening with limited query rights; the handle is closed below
+    let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if process.is_null() {
+        return None;
+    }
+    let mut path = [0u16; 1024];
+    let mut path_len = path.len() as u32;
+    // SAFETY: `path_len` is the capacity of `path` and receives the written length
+    let ok = unsafe { QueryFullProcessImageNameW(process, 0, path.as_mut_ptr(), &mut path_len) };
+    // SAFETY: `process` was opened above
+    unsafe { CloseHandle(process) };
+    if ok == 0 {
+        return None;
+    }
+
+    Some(FocusedWindow {
+        app_name: app_name_from_path(&String::from_utf16_lossy(&path[..path_len as usize])),
+        title: (!title.trim().is_empty()).then(|| title.trim().to_string()),
+    })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn platform_focused_window() -> Option<FocusedWindow> {
+    // X11 only, and only if xdotool is installed
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowpid", "getwindowname"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let (pid, title) = parse_xdotool_output(&String::from_utf8_lossy(&output.stdout))?;
+    let app_name = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    Some(FocusedWindow {
+        app_name: app_name.trim().to_string(),
+        title,
+    })
+}
+
+/// Parse "<app name>\n<window title>" printed by the macOS capture script
+pub fn parse_osascript_output(output: &str) -> Option<FocusedWindow> {
+    let mut lines = output.lines();
+    let app_name = lines.next()?.trim();
+    if app_name.is_empty() {
+        return None;
+    }
+    let title = lines.next().map(str::trim).filter(|t| !t.is_empty());
+    Some(FocusedWindow {
+        app_name: app_name.to_string(),
+        title: title.map(str::to_string),
+    })
+}
+
+/// Parse `xdotool getactivewindow getwindowpid getwindowname` output: the pid, then the title
+pub fn parse_xdotool_output(output: &str) -> Option<(u32, Option<String>)> {
+    let mut lines = output.lines();
+    let pid = lines.next()?.trim().parse::<u32>().ok()?;
+    let title = lines.next().map(str::trim).filter(|t| !t.is_empty()).map(str::to_string);
+    Some((pid, title))
+}
+
+/// Application name from an executable path, e.g. "C:\\...\\Code.exe" -> "Code"
+pub fn app_name_from_path(path: &str) -> String {
+    let file = path.rsplit(['\\', '/']).next().unwrap_or(path);
+    file.strip_suffix(".exe")
+        .or_else(|| file.strip_suffix(".EXE"))
+        .unwrap_or(file)
+        .to_string()
+}
@@ -0,0 +1,127 @@
+// End-of-day summaries from a language model. Only the words of the day's recorded intervals are
+// sent (no times, projects, notes or titles), to the OpenAI-compatible endpoint in the AI settings.
+// The summary is stored with the day's archive and shown in the summary window.
+
+use crate::db::{get_db_connection, query_archived_day};
+use crate::settings::{get_ai_settings, AiSettings};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Models can take a while on a long day
+const REQUEST_TIMEOUT_SECONDS: u64 = 60;
+
+const SYSTEM_PROMPT: &str = "You summarize a person's workday from short notes they wrote every \
+15 minutes, in order. Write two to four sentences in the second person about what they \
+accomplished and where their time went. Do not invent details that are not in the notes.";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AiSummary {
+    pub date: String,
+    pub summary: String,
+}
+
+/// Words of the recorded intervals on `date`, in the order they were written
+pub fn query_day_words(conn: &Connection, date: &str) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT i.words FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE w.date = ?1 AND i.status = 'recorded' AND TRIM(COALESCE(i.words, '')) != ''
+         ORDER BY i.start_time, i.id",
+    )?;
+    let words = stmt.query_map(params![date], |row| row.get::<_, String>(0))?.collect();
+    words
+}
+
+/// Save the summary with the day's archive. Returns false if the day has no archive.
+pub fn store_ai_summary(conn: &Connection, date: &str, summary: &str) -> rusqlite::Result<bool> {
+    let updated = conn.execute("UPDATE daily_archives SET ai_summary = ?1 WHERE date = ?2", params![summary, date])?;
+    Ok(updated > 0)
+}
+
+/// The chat completions URL under an endpoint's base URL
+pub fn chat_completions_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    if endpoint.ends_with("/chat/completions") {
+        endpoint.to_string()
+    } else {
+        format!("{}/chat/completions", endpoint)
+    }
+}
+
+/// Request body asking `model` to summarize `words`
+pub fn build_summary_request(model: &str, words: &[String]) -> Value {
+    let notes: Vec<String> = words.iter().map(|w| format!("- {}", w.trim())).collect();
+    json!({
+        "model": model,
+        "messages": [
+            { "role": "system", "content": SYSTEM_PROMPT },
+            { "role": "user", "content": notes.join("\n") },
+        ],
+    })
+}
+
+/// The summary text of a chat completions response
+pub fn parse_summary_response(response: &Value) -> Result<String, String> {
+    if let Some(message) = response["error"]["message"].as_str() {
+        return Err(format!("The model returned an error: {}", message));
+    }
+    response["choices"][0]["message"]["content"]
+        .as_str()
+        .map(str::trim)
+        .filter(|summary| !summary.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| "The model returned no summary".to_string())
+}
+
+fn request_summary(ai: &AiSettings, body: &Value) -> Result<String, String> {
+    let mut request = ureq::post(&chat_completions_url(&ai.endpoint))
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
+        .set("Content-Type", "application/json");
+    if !ai.api_key.is_empty() {
+        request = request.set("Authorization", &format!("Bearer {}", ai.api_key));
+    }
+    let response: Value = match request.send_json(body) {
+        Ok(response) => response.into_json().map_err(|e| format!("Unreadable response: {}", e))?,
+        // Error bodies usually say what went wrong (bad key, unknown model)
+        Err(ureq::Error::Status(status, response)) => response
+            .into_json()
+            .unwrap_or_else(|_| json!({ "error": { "message": format!("HTTP {}", status) } })),
+        Err(ureq::Error::Transport(e)) => return Err(format!("Failed to reach {}: {}", ai.endpoint, e)),
+    };
+    parse_summary_response(&response)
+}
+
+/// Summarize `date` with the configured model and store the result with the day's archive
+pub async fn generate_ai_summary(app: &AppHandle, date: &str) -> Result<AiSummary, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("Invalid date '{}': {}", date, e))?;
+    let ai = get_ai_settings(app);
+    if !ai.enabled {
+        return Err("AI summaries are turned off in settings".to_string());
+    }
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    // The summary is kept with the archive, so don't ask the model for one that would be lost
+    if query_archived_day(&conn, date).map_err(|e| e.to_string())?.is_none() {
+        return Err(format!("Archive {} first to summarize it", date));
+    }
+    let words = query_day_words(&conn, date).map_err(|e| e.to_string())?;
+    drop(conn);
+    if words.is_empty() {
+        return Err(format!("Nothing was recorded on {}", date));
+    }
+
+    let body = build_summary_request(&ai.model, &words);
+    let summary = tauri::async_runtime::spawn_blocking(move || request_summary(&ai, &body))
+        .await
+        .map_err(|e| e.to_string())??;
+    let stored = get_db_connection(app)
+        .and_then(|conn| Ok(store_ai_summary(&conn, date, &summary)?))
+        .map_err(|e| e.to_string())?;
+    if !stored {
+        return Err(format!("The archive of {} went away before its summary could be stored", date));
+    }
+    Ok(AiSummary { date: date.to_string(), summary })
+}
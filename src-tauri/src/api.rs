@@ -0,0 +1,131 @@
+// Versioned command surface: the frontend calls "v1/<name>" through its facade, which resolves the
+// name to the registered command and tags the invoke with the API version it was written against.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+use tauri::ipc::InvokeMessage;
+use tauri::Runtime;
+
+/// Current version of the command API
+pub const API_VERSION: u32 = 1;
+
+/// Request header carrying the API version of a facade call
+pub const API_VERSION_HEADER: &str = "log15-api-version";
+
+/// v1 names of commands still registered under an older name (typically a `_cmd` suffix)
+const V1_RENAMES: &[(&str, &str)] = &[
+    ("cancel_workblock", "cancel_workblock_cmd"),
+    ("get_active_workblock", "get_active_workblock_cmd"),
+    ("get_workblocks_by_date", "get_workblocks_by_date_cmd"),
+    ("set_workblock_project", "set_workblock_project_cmd"),
+    ("set_interval_project", "set_interval_project_cmd"),
+    ("get_reclassify_offer", "get_reclassify_offer_cmd"),
+    ("reclassify_interval", "reclassify_interval_cmd"),
+    ("pin_activity", "pin_activity_cmd"),
+    ("unpin_activity", "unpin_activity_cmd"),
+    ("get_pinned_activities", "get_pinned_activities_cmd"),
+    ("set_activity_alias", "set_activity_alias_cmd"),
+    ("remove_activity_alias", "remove_activity_alias_cmd"),
+    ("get_activity_aliases", "get_activity_aliases_cmd"),
+    ("get_intervals_by_workblock", "get_intervals_by_workblock_cmd"),
+    ("get_current_interval", "get_current_interval_cmd"),
+    ("check_and_reset_daily", "check_and_reset_daily_cmd"),
+    ("regenerate_archive", "regenerate_archive_cmd"),
+    ("get_today_date", "get_today_date_cmd"),
+    ("get_archived_day", "get_archived_day_cmd"),
+    ("get_all_archived_dates", "get_all_archived_dates_cmd"),
+    ("get_daily_aggregate", "get_daily_aggregate_cmd"),
+    ("get_daily_visualization_data", "get_daily_visualization_data_cmd"),
+    ("get_monthly_summary", "get_monthly_summary_cmd"),
+    ("show_prompt_window", "show_prompt_window_cmd"),
+    ("hide_prompt_window", "hide_prompt_window_cmd"),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeprecatedCommand {
+    pub command: String,
+    pub replacement: Option<String>,  // Versioned name to use instead, if any
+}
+
+/// Commands on their way out, whichever way they are invoked
+fn deprecated_commands() -> Vec<DeprecatedCommand> {
+    vec![
+        DeprecatedCommand {
+            command: "get_interval_time_remaining".to_string(),
+            replacement: Some("v1/start_timer_ticker".to_string()),  // Listen for "timer-tick" instead of polling
+        },
+        DeprecatedCommand {
+            command: "greet".to_string(),
+            replacement: None,
+        },
+    ]
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiManifest {
+    pub version: u32,
+    pub commands: BTreeMap<String, String>,  // Versioned name -> registered command, where they differ
+    pub deprecated: Vec<DeprecatedCommand>,
+}
+
+pub fn api_manifest() -> ApiManifest {
+    ApiManifest {
+        version: API_VERSION,
+        commands: V1_RENAMES
+            .iter()
+            .map(|(name, command)| (name.to_string(), command.to_string()))
+            .collect(),
+        deprecated: deprecated_commands(),
+    }
+}
+
+/// Registered command for a versioned path like "v1/get_today_date"
+pub fn resolve_command(path: &str) -> Result<&str, String> {
+    let (version, name) = path
+        .split_once('/')
+        .ok_or_else(|| format!("Unversioned command path '{}'", path))?;
+    if version != format!("v{}", API_VERSION) {
+        return Err(format!("Unsupported API version '{}'", version));
+    }
+    Ok(V1_RENAMES
+        .iter()
+        .find(|(versioned, _)| *versioned == name)
+        .map_or(name, |(_, command)| command))
+}
+
+/// Deprecation warning for invoking `command`, if any. `api_version` is None for direct calls
+/// that bypass the facade; those are only flagged when they use a name v1 has replaced.
+pub fn deprecation_warning(command: &str, api_version: Option<u32>) -> Option<String> {
+    if let Some(deprecated) = deprecated_commands().into_iter().find(|d| d.command == command) {
+        return Some(match deprecated.replacement {
+            Some(replacement) => format!("'{}' is deprecated; use '{}'", command, replacement),
+            None => format!("'{}' is deprecated and will be removed", command),
+        });
+    }
+    if api_version.is_none() {
+        if let Some((versioned, _)) = V1_RENAMES.iter().find(|(_, registered)| *registered == command) {
+            return Some(format!("'{}' is a legacy name; use 'v{}/{}'", command, API_VERSION, versioned));
+        }
+    }
+    None
+}
+
+/// Commands already warned about, so each warning is logged once per run
+static WARNED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// Log a deprecation warning the first time a deprecated command is invoked
+pub fn log_deprecated_invoke<R: Runtime>(message: &InvokeMessage<R>) {
+    let api_version = message
+        .headers()
+        .get(API_VERSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u32>().ok());
+    let Some(warning) = deprecation_warning(message.command(), api_version) else {
+        return;
+    };
+    let mut warned = WARNED.lock().unwrap_or_else(|e| e.into_inner());
+    if warned.get_or_insert_with(HashSet::new).insert(message.command().to_string()) {
+        eprintln!("[API] {}", warning);
+    }
+}
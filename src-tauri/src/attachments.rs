@@ -0,0 +1,51 @@
+// Managed storage for files attached to intervals (whiteboard photos, screenshots, ...)
+
+use crate::db::{add_interval_attachment, get_interval_by_id, IntervalAttachment};
+use chrono::Local;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Directory (inside app data) that holds copies of attached files
+pub fn get_attachments_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let dir = app_data_dir.join("attachments");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create attachments directory: {}", e))?;
+    Ok(dir)
+}
+
+/// Copy a user-chosen file into managed storage and link it to the interval.
+/// The original file is left untouched; the copy survives if the original is moved or deleted.
+pub fn attach_file(app: &AppHandle, interval_id: i64, source: &Path) -> Result<IntervalAttachment, String> {
+    // Make sure the interval exists before copying anything
+    get_interval_by_id(app, interval_id).map_err(|e| format!("Interval {} not found: {}", interval_id, e))?;
+
+    if !source.is_file() {
+        return Err(format!("Not a file: {}", source.display()));
+    }
+    let file_name = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Invalid file name: {}", source.display()))?
+        .to_string();
+
+    // Prefix with interval ID and timestamp so repeated attachments never collide
+    let stored_name = format!(
+        "{}_{}_{}",
+        interval_id,
+        Local::now().timestamp_millis(),
+        file_name
+    );
+    let destination = get_attachments_dir(app)?.join(stored_name);
+    let size_bytes = std::fs::copy(source, &destination)
+        .map_err(|e| format!("Failed to copy attachment: {}", e))?;
+
+    let stored_path = destination.to_string_lossy().to_string();
+    add_interval_attachment(app, interval_id, &file_name, &stored_path, size_bytes as i64).map_err(|e| {
+        // Don't leave an orphaned copy behind if the DB insert fails
+        std::fs::remove_file(&destination).ok();
+        format!("Failed to save attachment: {}", e)
+    })
+}
@@ -0,0 +1,231 @@
+// Database snapshots in app data (backups/), taken with SQLite's online backup API so they are
+// consistent even while the app writes. Rotated to the newest MAX_BACKUPS files.
+
+use crate::db::{get_db_connection, get_db_path, query_schema_version, to_timestamp, SCHEMA_VERSION};
+use crate::timer::TimerManager;
+use crate::tray::TrayManager;
+use crate::window_manager::WindowManager;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rusqlite::{Connection, DatabaseName};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+/// Backups kept; older ones are deleted after each new snapshot
+pub const MAX_BACKUPS: usize = 10;
+
+const BACKUP_PREFIX: &str = "log15-";
+const BACKUP_EXTENSION: &str = "db";
+const BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S-%3f";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupReason {
+    Startup,
+    Migration,    // Taken right before schema migrations run
+    Manual,
+    PreRestore,   // The database as it was before a restore replaced it
+    PreImport,    // The database as it was before an import replaced or merged into it
+}
+
+impl BackupReason {
+    pub fn as_str(&self) -> &str {
+        match self {
+            BackupReason::Startup => "startup",
+            BackupReason::Migration => "migration",
+            BackupReason::Manual => "manual",
+            BackupReason::PreRestore => "pre_restore",
+            BackupReason::PreImport => "pre_import",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "startup" => Some(BackupReason::Startup),
+            "migration" => Some(BackupReason::Migration),
+            "manual" => Some(BackupReason::Manual),
+            "pre_restore" => Some(BackupReason::PreRestore),
+            "pre_import" => Some(BackupReason::PreImport),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BackupInfo {
+    pub path: String,
+    pub file_name: String,
+    pub created_at: String,  // UTC RFC 3339, from the file name
+    pub reason: BackupReason,
+    pub size_bytes: u64,
+}
+
+/// File name of a backup, e.g. log15-20240501-070000-123-startup.db (sorts chronologically)
+pub fn backup_file_name(created_at: DateTime<Utc>, reason: BackupReason) -> String {
+    format!("{}{}-{}.{}", BACKUP_PREFIX, created_at.format(BACKUP_TIME_FORMAT), reason.as_str(), BACKUP_EXTENSION)
+}
+
+/// Creation time and reason of a backup file name; None for anything else in the directory
+pub fn parse_backup_file_name(file_name: &str) -> Option<(DateTime<Utc>, BackupReason)> {
+    let stem = file_name.strip_prefix(BACKUP_PREFIX)?.strip_suffix(&format!(".{}", BACKUP_EXTENSION))?;
+    // The timestamp has a fixed width: YYYYMMDD-HHMMSS-mmm
+    let (time, reason) = stem.split_at_checked(19)?;
+    let created_at = NaiveDateTime::parse_from_str(time, BACKUP_TIME_FORMAT).ok()?.and_utc();
+    Some((created_at, BackupReason::from_str(reason.strip_prefix('-')?)?))
+}
+
+fn backup_info(path: &Path) -> Option<BackupInfo> {
+    let file_name = path.file_name()?.to_str()?.to_string();
+    let (created_at, reason) = parse_backup_file_name(&file_name)?;
+    Some(BackupInfo {
+        path: path.to_string_lossy().into_owned(),
+        file_name,
+        created_at: to_timestamp(created_at),
+        reason,
+        size_bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+/// Snapshot `conn` into `dir` and prune old backups
+pub fn create_backup_in(conn: &Connection, dir: &Path, reason: BackupReason, now: DateTime<Utc>) -> Result<BackupInfo, String> {
+    let path = snapshot_into(conn, dir, reason, now)?;
+    prune_backups(dir, MAX_BACKUPS)?;
+    backup_info(&path).ok_or_else(|| "Backup was pruned right away".to_string())
+}
+
+/// Snapshot `conn` into `dir` without pruning, so no existing backup goes away
+fn snapshot_into(conn: &Connection, dir: &Path, reason: BackupReason, now: DateTime<Utc>) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create backup directory: {}", e))?;
+    let path = dir.join(backup_file_name(now, reason));
+    conn.backup(DatabaseName::Main, &path, None).map_err(|e| format!("Backup failed: {}", e))?;
+    Ok(path)
+}
+
+/// Backups in `dir`, newest first
+pub fn list_backups_in(dir: &Path) -> Result<Vec<BackupInfo>, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read backup directory: {}", e)),
+    };
+    let mut backups: Vec<BackupInfo> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| backup_info(&entry.path()))
+        .collect();
+    backups.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+    Ok(backups)
+}
+
+/// Delete all but the newest `keep` backups; returns how many were deleted
+pub fn prune_backups(dir: &Path, keep: usize) -> Result<usize, String> {
+    let backups = list_backups_in(dir)?;
+    let mut deleted = 0;
+    for backup in backups.iter().skip(keep) {
+        std::fs::remove_file(&backup.path).map_err(|e| format!("Failed to delete {}: {}", backup.file_name, e))?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+/// Replace the contents of `conn` with the backup at `backup_path`, after checking it is a log15 database
+pub fn restore_backup_into(conn: &mut Connection, backup_path: &Path) -> Result<(), String> {
+    let backup = Connection::open_with_flags(backup_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open backup: {}", e))?;
+    let has_workblocks: bool = backup
+        .query_row("SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'workblocks')", [], |row| row.get(0))
+        .map_err(|e| format!("Not a log15 backup: {}", e))?;
+    if !has_workblocks {
+        return Err("Not a log15 backup: no workblocks table".to_string());
+    }
+    drop(backup);
+    conn.restore(DatabaseName::Main, backup_path, None::<fn(rusqlite::backup::Progress)>)
+        .map_err(|e| format!("Restore failed: {}", e))
+}
+
+pub fn get_backup_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("Failed to get app data directory")
+        .join("backups")
+}
+
+/// Snapshot an existing database before the schema is created or migrated (errors are only logged)
+pub fn backup_on_startup(app: &AppHandle, conn: &Connection) {
+    let has_tables: bool = conn
+        .query_row("SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table')", [], |row| row.get(0))
+        .unwrap_or(false);
+    if !has_tables {
+        return; // First launch: nothing to back up
+    }
+    let reason = match query_schema_version(conn) {
+        Ok(version) if version < SCHEMA_VERSION => BackupReason::Migration,
+        _ => BackupReason::Startup,
+    };
+    match create_backup_in(conn, &get_backup_dir(app), reason, Utc::now()) {
+        Ok(backup) => println!("[BACKUP] Saved {}", backup.file_name),
+        Err(e) => eprintln!("[BACKUP] {}", e),
+    }
+}
+
+pub fn create_backup_now(app: &AppHandle) -> Result<BackupInfo, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    create_backup_in(&conn, &get_backup_dir(app), BackupReason::Manual, Utc::now())
+}
+
+pub fn list_backups(app: &AppHandle) -> Result<Vec<BackupInfo>, String> {
+    list_backups_in(&get_backup_dir(app))
+}
+
+/// Back up `conn` into `dir`, then replace it with the backup at `path` and bring that up to the
+/// current schema. Pruning waits until the restore is done: it may be restoring the oldest backup.
+pub fn restore_backup_in(conn: &mut Connection, dir: &Path, path: &Path, now: DateTime<Utc>) -> Result<BackupInfo, String> {
+    let safety_path = snapshot_into(conn, dir, BackupReason::PreRestore, now)?;
+    restore_backup_into(conn, path)?;
+    crate::db::create_schema(conn).map_err(|e| e.to_string())?;
+    prune_backups(dir, MAX_BACKUPS)?;
+    backup_info(&safety_path).ok_or_else(|| "Pre-restore backup was pruned right away".to_string())
+}
+
+/// Restore a backup from the backup directory; the current database is backed up first.
+/// Returns that pre-restore backup so the restore can be undone. A running timer is stopped
+/// first and the restored database's active workblock, if any, picked up as on launch.
+pub async fn restore_backup(app: &AppHandle, path: &Path) -> Result<BackupInfo, String> {
+    let dir = get_backup_dir(app);
+    let path = path.canonicalize().map_err(|e| format!("Backup not found: {}", e))?;
+    let dir = dir.canonicalize().map_err(|e| format!("No backups yet: {}", e))?;
+    if path.parent() != Some(dir.as_path()) || backup_info(&path).is_none() {
+        return Err("Only backups from the backup directory can be restored".to_string());
+    }
+    
+    if let Some(timer_manager) = app.try_state::<Arc<Mutex<TimerManager>>>() {
+        timer_manager.lock().await.reset().await;
+    }
+    if let Some(window_manager) = app.try_state::<Arc<Mutex<WindowManager>>>() {
+        let window_mgr = window_manager.lock().await;
+        window_mgr.clear_prompt_queue().await;
+        let _ = window_mgr.hide_prompt_window().await;
+    }
+    
+    let mut conn = Connection::open(get_db_path(app)).map_err(|e| e.to_string())?;
+    let safety = restore_backup_in(&mut conn, &dir, &path, Utc::now())?;
+    // Refresh cached settings
+    crate::settings::load_day_start_hour(&conn);
+    crate::cache::clear();
+    drop(conn);
+    println!("[BACKUP] Restored {}", path.display());
+    
+    if let Some(tray_manager) = app.try_state::<Arc<Mutex<TrayManager>>>() {
+        let mut tray_mgr = tray_manager.lock().await;
+        tray_mgr.reset().await;
+        tray_mgr.refresh_today_totals();
+    }
+    if let Some(timer_manager) = app.try_state::<Arc<Mutex<TimerManager>>>() {
+        if let Err(e) = timer_manager.lock().await.restore_active_workblock().await {
+            eprintln!("[BACKUP] Failed to resume the restored workblock: {}", e);
+        }
+    }
+    Ok(safety)
+}
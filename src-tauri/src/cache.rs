@@ -0,0 +1,226 @@
+// In-memory daily aggregates, keyed by date, so the dashboard doesn't rebuild a whole day after
+// every answer. Writes to a day's workblocks or intervals rebuild that day's entry (when one is
+// cached) and emit "daily-aggregate-updated" with what changed; the frontend patches its copy.
+// Changes that reach every day (aliases, colors, imports, a wipe) just drop all entries.
+
+use crate::db::{
+    generate_daily_aggregate, get_db_connection, ActivityData, AggregateTimelineData, AwayCauseCount, DailyAggregate,
+    DeepWorkSession, EnergyPoint, ProjectData, WordFrequency, WorkblockBoundary,
+};
+use crate::error::{Log15Error, Result};
+use crate::events::{emit_event, AppEvent};
+use crate::settings::{WELLBEING_ENABLED_KEY, WELLBEING_LIMIT_KEY};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// Days kept at once; the oldest date is dropped first
+pub const MAX_CACHED_DAYS: usize = 7;
+
+static AGGREGATES: Mutex<Option<HashMap<String, DailyAggregate>>> = Mutex::new(None);
+
+/// Held while a day is rebuilt, so deltas go out in the order the writes happened
+static REFRESHING: Mutex<()> = Mutex::new(());
+
+/// Where a timeline entry came from
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TimelineEntryKey {
+    pub workblock_id: i64,
+    pub interval_number: i32,
+}
+
+/// Payload of "daily-aggregate-updated": how a date's aggregate changed.
+/// Totals are always sent; lists that are replaced whole are None when unchanged.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyAggregateDelta {
+    pub date: String,
+    pub total_workblocks: i32,
+    pub total_minutes: i32,
+    pub over_daily_limit: bool,
+    pub after_the_fact_minutes: i32,
+    pub timeline_upserted: Vec<AggregateTimelineData>,  // New or changed entries
+    pub timeline_removed: Vec<TimelineEntryKey>,
+    pub activities_upserted: Vec<ActivityData>,         // New or changed, including shifted percentages
+    pub activities_removed: Vec<String>,
+    pub word_frequency: Option<Vec<WordFrequency>>,
+    pub workblock_boundaries: Option<Vec<WorkblockBoundary>>,
+    pub project_data: Option<Vec<ProjectData>>,
+    pub deep_work_sessions: Option<Vec<DeepWorkSession>>,
+    pub energy_timeline: Option<Vec<EnergyPoint>>,
+    pub away_causes: Option<Vec<AwayCauseCount>>,
+}
+
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// `new` when it differs from `old`
+fn replaced<T: Serialize + Clone>(old: &T, new: &T) -> Option<T> {
+    (!same(old, new)).then(|| new.clone())
+}
+
+fn entry_key(entry: &AggregateTimelineData) -> TimelineEntryKey {
+    TimelineEntryKey { workblock_id: entry.workblock_id, interval_number: entry.interval_number }
+}
+
+/// What turns `old` into `new`; None when nothing changed
+pub fn aggregate_delta(date: &str, old: &DailyAggregate, new: &DailyAggregate) -> Option<DailyAggregateDelta> {
+    if same(old, new) {
+        return None;
+    }
+
+    let old_entries: HashMap<(i64, i32), &AggregateTimelineData> =
+        old.timeline_data.iter().map(|e| ((e.workblock_id, e.interval_number), e)).collect();
+    let new_keys: std::collections::HashSet<(i64, i32)> =
+        new.timeline_data.iter().map(|e| (e.workblock_id, e.interval_number)).collect();
+    let timeline_upserted = new
+        .timeline_data
+        .iter()
+        .filter(|e| old_entries.get(&(e.workblock_id, e.interval_number)).is_none_or(|old| !same(*old, *e)))
+        .cloned()
+        .collect();
+    let timeline_removed = old
+        .timeline_data
+        .iter()
+        .filter(|e| !new_keys.contains(&(e.workblock_id, e.interval_number)))
+        .map(entry_key)
+        .collect();
+
+    let old_activities: HashMap<&str, &ActivityData> = old.activity_data.iter().map(|a| (a.words.as_str(), a)).collect();
+    let activities_upserted = new
+        .activity_data
+        .iter()
+        .filter(|a| old_activities.get(a.words.as_str()).is_none_or(|old| !same(*old, *a)))
+        .cloned()
+        .collect();
+    let activities_removed = old
+        .activity_data
+        .iter()
+        .filter(|a| !new.activity_data.iter().any(|n| n.words == a.words))
+        .map(|a| a.words.clone())
+        .collect();
+
+    Some(DailyAggregateDelta {
+        date: date.to_string(),
+        total_workblocks: new.total_workblocks,
+        total_minutes: new.total_minutes,
+        over_daily_limit: new.over_daily_limit,
+        after_the_fact_minutes: new.after_the_fact_minutes,
+        timeline_upserted,
+        timeline_removed,
+        activities_upserted,
+        activities_removed,
+        word_frequency: replaced(&old.word_frequency, &new.word_frequency),
+        workblock_boundaries: replaced(&old.workblock_boundaries, &new.workblock_boundaries),
+        project_data: replaced(&old.project_data, &new.project_data),
+        deep_work_sessions: replaced(&old.deep_work_sessions, &new.deep_work_sessions),
+        energy_timeline: replaced(&old.energy_timeline, &new.energy_timeline),
+        away_causes: replaced(&old.away_causes, &new.away_causes),
+    })
+}
+
+fn cached(date: &str) -> Option<DailyAggregate> {
+    AGGREGATES.lock().unwrap_or_else(|e| e.into_inner()).as_ref()?.get(date).cloned()
+}
+
+/// Keep `aggregate` as the current one for `date`
+pub fn remember(date: &str, aggregate: &DailyAggregate) {
+    let mut guard = AGGREGATES.lock().unwrap_or_else(|e| e.into_inner());
+    let entries = guard.get_or_insert_with(HashMap::new);
+    entries.insert(date.to_string(), aggregate.clone());
+    while entries.len() > MAX_CACHED_DAYS {
+        let Some(oldest) = entries.keys().min().cloned() else { break };
+        entries.remove(&oldest);
+    }
+}
+
+/// Drop every cached day
+pub fn clear() {
+    *AGGREGATES.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// The aggregate of `date`, built only if it isn't cached yet
+pub fn daily_aggregate(app: &AppHandle, date: &str) -> Result<DailyAggregate> {
+    if let Some(aggregate) = cached(date) {
+        return Ok(aggregate);
+    }
+    let aggregate = generate_daily_aggregate(app, date)?;
+    remember(date, &aggregate);
+    Ok(aggregate)
+}
+
+/// Rebuild every cached day (after a setting the aggregates depend on changed)
+pub fn refresh_all(app: &AppHandle) {
+    let dates: Vec<String> = AGGREGATES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|entries| entries.keys().cloned().collect())
+        .unwrap_or_default();
+    for date in dates {
+        refresh_date(app, &date);
+    }
+}
+
+/// Follow the wellbeing limit, which decides `over_daily_limit`
+pub fn spawn_settings_listener(app: &AppHandle) {
+    crate::settings::spawn_settings_listener("cache", app, |app, change| async move {
+        if change.key.is_empty() || change.key == WELLBEING_ENABLED_KEY || change.key == WELLBEING_LIMIT_KEY {
+            refresh_all(&app);
+        }
+    });
+}
+
+/// Rebuild a cached day after a write and tell the frontend what changed.
+/// Days nobody asked for stay uncached.
+pub fn refresh_date(app: &AppHandle, date: &str) {
+    let _refreshing = REFRESHING.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(old) = cached(date) else { return };
+    let new = match generate_daily_aggregate(app, date) {
+        Ok(aggregate) => aggregate,
+        Err(e) => {
+            // Dropped rather than left stale; the next read rebuilds it
+            eprintln!("[CACHE] Failed to rebuild aggregate for {}: {}", date, e);
+            if let Some(entries) = AGGREGATES.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+                entries.remove(date);
+            }
+            return;
+        }
+    };
+    remember(date, &new);
+    if let Some(delta) = aggregate_delta(date, &old, &new) {
+        let _ = emit_event(app, AppEvent::DailyAggregateUpdated(delta));
+    }
+}
+
+fn refresh_date_of(app: &AppHandle, sql: &str, id: i64) {
+    let date = get_db_connection(app).and_then(|conn| {
+        conn.query_row(sql, params![id], |row| row.get::<_, String>(0))
+            .optional()
+            .map_err(Log15Error::from)
+    });
+    match date {
+        Ok(Some(date)) => refresh_date(app, &date),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("[CACHE] Failed to find the date of a write, dropping all aggregates: {}", e);
+            clear();
+        }
+    }
+}
+
+/// A workblock (or its intervals as a whole) was written
+pub fn workblock_written(app: &AppHandle, workblock_id: i64) {
+    refresh_date_of(app, "SELECT date FROM workblocks WHERE id = ?1", workblock_id);
+}
+
+/// An interval, its splits or its attachments were written
+pub fn interval_written(app: &AppHandle, interval_id: i64) {
+    refresh_date_of(
+        app,
+        "SELECT w.date FROM intervals i JOIN workblocks w ON w.id = i.workblock_id WHERE i.id = ?1",
+        interval_id,
+    );
+}
@@ -0,0 +1,131 @@
+// Incremental export: workblocks, intervals and archives created, modified or deleted after a
+// timestamp, so a sync engine or dashboard can follow the data without full exports.
+// Triggers stamp workblocks.updated_at and intervals.updated_at on every write and leave a
+// tombstone in deleted_rows for every delete.
+
+use crate::db::{
+    get_db_connection, parse_timestamp, query_archived_day, query_interval_by_id, query_workblock_by_id,
+    to_timestamp, DailyArchive, Interval, Workblock,
+};
+use crate::error::Log15Error;
+use chrono::Utc;
+use rusqlite::{params, Connection, Result};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Tables followed by get_changes_since
+pub const TRACKED_TABLES: [&str; 3] = ["workblocks", "intervals", "daily_archives"];
+
+/// A row deleted after the `since` of a change set
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeletedRow {
+    pub table_name: String,  // One of TRACKED_TABLES
+    pub row_id: i64,
+    pub deleted_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangeSet {
+    pub since: String,
+    pub until: String,  // Pass as `since` next time; changes stamped exactly then are sent again rather than missed
+    pub workblocks: Vec<Workblock>,
+    pub intervals: Vec<Interval>,
+    pub archives: Vec<DailyArchive>,
+    pub deleted: Vec<DeletedRow>,
+}
+
+/// Create the triggers that stamp updated_at and record deletions (idempotent)
+pub fn create_change_triggers(conn: &Connection) -> Result<()> {
+    // Millisecond stamps, so they sort after the second-precision timestamps of the same second
+    let now = "strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')";
+    for table in ["workblocks", "intervals"] {
+        // Recursive triggers are off, so the inner UPDATE doesn't fire the update trigger again
+        for event in ["INSERT", "UPDATE"] {
+            conn.execute(
+                &format!(
+                    "CREATE TRIGGER IF NOT EXISTS changes_{table}_{lower} AFTER {event} ON {table}
+                     BEGIN UPDATE {table} SET updated_at = {now} WHERE id = NEW.id; END",
+                    lower = event.to_lowercase(),
+                ),
+                [],
+            )?;
+        }
+    }
+    for table in TRACKED_TABLES {
+        conn.execute(
+            &format!(
+                "CREATE TRIGGER IF NOT EXISTS changes_{table}_delete AFTER DELETE ON {table}
+                 BEGIN INSERT INTO deleted_rows (table_name, row_id, deleted_at) VALUES ('{table}', OLD.id, {now}); END"
+            ),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Normalize `since` to a stored UTC timestamp, so it compares correctly as a string
+fn normalize_since(since: &str) -> crate::error::Result<String> {
+    parse_timestamp(since.trim())
+        .map(to_timestamp)
+        .ok_or_else(|| Log15Error::InvalidInput(format!("Invalid timestamp '{}': expected RFC 3339", since)))
+}
+
+fn query_ids(conn: &Connection, sql: &str, since: &str) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare(sql)?;
+    let ids = stmt.query_map(params![since], |row| row.get(0))?.collect();
+    ids
+}
+
+/// Everything created, modified or deleted since `since` (RFC 3339). Rows written before change
+/// tracking existed count as changed when they were created.
+pub fn query_changes_since(conn: &Connection, since: &str) -> crate::error::Result<ChangeSet> {
+    let since = normalize_since(since)?;
+    // Same format as the trigger stamps; `>=` below sends a change made in this very millisecond again next time
+    let until = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3f+00:00").to_string();
+
+    let workblocks = query_ids(
+        conn,
+        "SELECT id FROM workblocks WHERE COALESCE(updated_at, created_at, start_time) >= ?1 ORDER BY id",
+        &since,
+    )?
+    .into_iter()
+    .map(|id| query_workblock_by_id(conn, id))
+    .collect::<crate::error::Result<Vec<_>>>()?;
+
+    let intervals = query_ids(
+        conn,
+        "SELECT id FROM intervals WHERE COALESCE(updated_at, recorded_at, start_time) >= ?1 ORDER BY id",
+        &since,
+    )?
+    .into_iter()
+    .map(|id| query_interval_by_id(conn, id))
+    .collect::<crate::error::Result<Vec<_>>>()?;
+
+    // Archives are replaced as a whole when regenerated, which stamps a new archived_at
+    let mut stmt = conn.prepare("SELECT date FROM daily_archives WHERE archived_at >= ?1 ORDER BY date")?;
+    let dates = stmt.query_map(params![since], |row| row.get::<_, String>(0))?.collect::<Result<Vec<_>>>()?;
+    let mut archives = Vec::new();
+    for date in dates {
+        archives.extend(query_archived_day(conn, &date)?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT table_name, row_id, deleted_at FROM deleted_rows WHERE deleted_at >= ?1 ORDER BY deleted_at, id",
+    )?;
+    let deleted = stmt
+        .query_map(params![since], |row| {
+            Ok(DeletedRow {
+                table_name: row.get(0)?,
+                row_id: row.get(1)?,
+                deleted_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ChangeSet { since, until, workblocks, intervals, archives, deleted })
+}
+
+pub fn get_changes_since(app: &AppHandle, since: &str) -> crate::error::Result<ChangeSet> {
+    let conn = get_db_connection(app)?;
+    query_changes_since(&conn, since)
+}
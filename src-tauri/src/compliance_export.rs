@@ -0,0 +1,144 @@
+// Working-time record for an employer: workblock times and daily totals only, never the words,
+// notes, titles or projects. The CSV ends with a SHA-256 line over everything above it, so a
+// recipient can tell whether the file was edited after export.
+
+use crate::db::{get_db_connection, parse_timestamp};
+use crate::format::Formatter;
+use chrono::{Local, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Prefix of the last line, followed by the hex SHA-256 of everything before it
+pub const SIGNATURE_PREFIX: &str = "# SHA-256: ";
+
+/// A finished workblock as it appears in the record
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ComplianceWorkblock {
+    pub date: String,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub duration_minutes: i32,
+    pub status: String,  // completed or cancelled
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ComplianceExportSummary {
+    pub path: String,
+    pub workblocks: usize,
+    pub days: usize,
+    pub total_minutes: i32,
+    pub sha256: String,
+}
+
+/// Completed and cancelled workblocks dated `start_date..=end_date`, in order
+pub fn query_compliance_workblocks(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> rusqlite::Result<Vec<ComplianceWorkblock>> {
+    let mut stmt = conn.prepare(
+        "SELECT date, start_time, end_time, COALESCE(duration_minutes, 0), status
+         FROM workblocks
+         WHERE date >= ?1 AND date <= ?2 AND status IN ('completed', 'cancelled')
+         ORDER BY date ASC, start_time ASC",
+    )?;
+    let rows = stmt.query_map(params![start_date, end_date], |row| {
+        Ok(ComplianceWorkblock {
+            date: row.get(0)?,
+            start_time: row.get(1)?,
+            end_time: row.get(2)?,
+            duration_minutes: row.get(3)?,
+            status: row.get(4)?,
+        })
+    })?;
+    rows.collect()
+}
+
+fn sha256_hex(content: &str) -> String {
+    Sha256::digest(content.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Render the record: one row per workblock, a total row after each day and a grand total
+pub fn render_compliance_csv(workblocks: &[ComplianceWorkblock], fmt: &Formatter) -> String {
+    let sep = fmt.csv_delimiter().to_string();
+    let time = |timestamp: Option<&str>| {
+        timestamp
+            .and_then(parse_timestamp)
+            .map(|t| fmt.time(t.with_timezone(&Local).time()))
+            .unwrap_or_default()
+    };
+    let mut lines = vec![["Date", "Start", "End", "Hours", "Status"].join(&sep)];
+    let mut total_minutes = 0;
+    
+    for day in workblocks.chunk_by(|a, b| a.date == b.date) {
+        let date = fmt.date_str(&day[0].date);
+        for workblock in day {
+            lines.push(
+                [
+                    date.clone(),
+                    time(Some(&workblock.start_time)),
+                    time(workblock.end_time.as_deref()),
+                    fmt.hours(workblock.duration_minutes),
+                    workblock.status.clone(),
+                ]
+                .join(&sep),
+            );
+        }
+        let day_minutes: i32 = day.iter().map(|w| w.duration_minutes).sum();
+        total_minutes += day_minutes;
+        lines.push([date, String::new(), String::new(), fmt.hours(day_minutes), "day total".to_string()].join(&sep));
+    }
+    lines.push(["Total", "", "", &fmt.hours(total_minutes), ""].join(&sep));
+    
+    let mut csv = lines.join("\n");
+    csv.push('\n');
+    csv
+}
+
+/// Append the signature line to a rendered record
+pub fn sign_csv(body: &str) -> String {
+    format!("{}{}{}\n", body, SIGNATURE_PREFIX, sha256_hex(body))
+}
+
+/// Whether a signed record is unchanged since export
+pub fn verify_compliance_csv(content: &str) -> bool {
+    let content = content.strip_suffix('\n').unwrap_or(content);
+    let Some(split) = content.rfind(SIGNATURE_PREFIX) else {
+        return false;
+    };
+    let (body, signature) = content.split_at(split);
+    signature[SIGNATURE_PREFIX.len()..].trim() == sha256_hex(body)
+}
+
+pub fn export_compliance_csv(
+    app: &AppHandle,
+    start_date: &str,
+    end_date: &str,
+    path: &Path,
+) -> Result<ComplianceExportSummary, String> {
+    for date in [start_date, end_date] {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("Invalid date '{}': {}", date, e))?;
+    }
+    if start_date > end_date {
+        return Err("Start date must not be after end date".to_string());
+    }
+    
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let workblocks = query_compliance_workblocks(&conn, start_date, end_date).map_err(|e| e.to_string())?;
+    let body = render_compliance_csv(&workblocks, &Formatter::from_settings(app));
+    let sha256 = sha256_hex(&body);
+    std::fs::write(path, sign_csv(&body)).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    
+    let mut days: Vec<&str> = workblocks.iter().map(|w| w.date.as_str()).collect();
+    days.dedup();
+    Ok(ComplianceExportSummary {
+        path: path.to_string_lossy().into_owned(),
+        workblocks: workblocks.len(),
+        days: days.len(),
+        total_minutes: workblocks.iter().map(|w| w.duration_minutes).sum(),
+        sha256,
+    })
+}
@@ -0,0 +1,130 @@
+// Memoized derived data (monthly heatmaps, weekly reviews, ...) in the computed_cache table.
+// Entries are JSON under "<kind>:<period>" keys. They expire after their TTL, and triggers drop
+// all of them whenever the data they are derived from is written.
+
+use crate::db::{get_db_connection, parse_timestamp, to_timestamp};
+use crate::settings::LAYOUT_SETTING_KEYS;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// How long an entry is served before it is computed again
+pub const DEFAULT_CACHE_TTL_SECONDS: i64 = 600;
+
+pub const MONTHLY_SUMMARY_PREFIX: &str = "monthly_summary:";
+pub const WEEKLY_REVIEW_PREFIX: &str = "weekly_review:";
+
+/// Tables whose writes make every cached entry stale
+pub const INVALIDATING_TABLES: [&str; 6] =
+    ["workblocks", "intervals", "interval_activities", "activity_aliases", "activity_colors", "settings"];
+
+/// Create the triggers that empty the cache on writes to INVALIDATING_TABLES (idempotent).
+/// Window layout settings don't count as writes to settings.
+pub fn create_cache_triggers(conn: &Connection) -> Result<()> {
+    let layout_keys = LAYOUT_SETTING_KEYS.map(|key| format!("'{}'", key)).join(", ");
+    for table in INVALIDATING_TABLES {
+        for event in ["INSERT", "UPDATE", "DELETE"] {
+            let name = format!("computed_cache_{}_{}", table, event.to_lowercase());
+            let condition = if table == "settings" {
+                // Recreated, since databases from before the condition have the trigger without it
+                conn.execute(&format!("DROP TRIGGER IF EXISTS {}", name), [])?;
+                let row = if event == "DELETE" { "OLD" } else { "NEW" };
+                format!("WHEN {}.key NOT IN ({})", row, layout_keys)
+            } else {
+                String::new()
+            };
+            conn.execute(
+                &format!(
+                    "CREATE TRIGGER IF NOT EXISTS {} AFTER {} ON {} {}
+                     BEGIN DELETE FROM computed_cache; END",
+                    name,
+                    event,
+                    table,
+                    condition
+                ),
+                [],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// The entry under `key`, unless it is missing, expired at `now` or no longer deserializes as T
+pub fn query_cached<T: DeserializeOwned>(conn: &Connection, key: &str, now: DateTime<Utc>) -> Result<Option<T>> {
+    let entry: Option<(String, String, Option<i64>)> = conn
+        .query_row(
+            "SELECT value, computed_at, ttl_seconds FROM computed_cache WHERE key = ?1",
+            params![key],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+    let Some((value, computed_at, ttl_seconds)) = entry else {
+        return Ok(None);
+    };
+    let expired = match (ttl_seconds, parse_timestamp(&computed_at)) {
+        (None, _) => false,
+        (Some(ttl), Some(computed_at)) => computed_at + chrono::Duration::seconds(ttl) <= now,
+        (Some(_), None) => true,
+    };
+    if expired {
+        return Ok(None);
+    }
+    match serde_json::from_str(&value) {
+        Ok(value) => Ok(Some(value)),
+        Err(e) => {
+            eprintln!("[CACHE] Ignoring unreadable entry {}: {}", key, e);
+            Ok(None)
+        }
+    }
+}
+
+/// Store `value` under `key`; a `ttl_seconds` of None keeps it until it is invalidated
+pub fn store_cached<T: Serialize>(
+    conn: &Connection,
+    key: &str,
+    value: &T,
+    ttl_seconds: Option<i64>,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO computed_cache (key, value, computed_at, ttl_seconds) VALUES (?1, ?2, ?3, ?4)",
+        params![key, json, to_timestamp(now), ttl_seconds],
+    )?;
+    Ok(())
+}
+
+/// The cached value under `key`, or `compute` it and cache the result
+pub fn cached_or_compute<T, E, F>(conn: &Connection, key: &str, ttl_seconds: Option<i64>, compute: F) -> std::result::Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+    E: From<rusqlite::Error>,
+    F: FnOnce(&Connection) -> std::result::Result<T, E>,
+{
+    let now = Utc::now();
+    if let Some(value) = query_cached(conn, key, now)? {
+        return Ok(value);
+    }
+    let value = compute(conn)?;
+    store_cached(conn, key, &value, ttl_seconds, now)?;
+    Ok(value)
+}
+
+/// Delete the entries whose key starts with `prefix` (all of them for ""); returns how many
+pub fn invalidate_prefix(conn: &Connection, prefix: &str) -> Result<usize> {
+    // substr rather than LIKE: keys contain underscores, which LIKE treats as wildcards
+    conn.execute(
+        "DELETE FROM computed_cache WHERE substr(key, 1, length(?1)) = ?1",
+        params![prefix],
+    )
+}
+
+pub fn invalidate_cache(app: &AppHandle, prefix: &str) -> std::result::Result<usize, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let deleted = invalidate_prefix(&conn, prefix).map_err(|e| e.to_string())?;
+    println!("[CACHE] Invalidated {} entries under '{}'", deleted, prefix);
+    Ok(deleted)
+}
@@ -0,0 +1,247 @@
+// Opt-in snapshot of the workspace (application in front, monitor count, battery) taken when an
+// interval is answered, so weekly reviews can relate how work went to where it happened
+
+use crate::activity_capture::focused_window;
+use crate::db::{get_db_connection, timestamp_now};
+use crate::settings::get_context_snapshot_enabled;
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Applications listed in a weekly breakdown; the rest are left out
+pub const CONTEXT_TOP_APPS: usize = 5;
+
+/// Workspace at the moment an interval was answered. Each part is None when the platform
+/// couldn't tell.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ContextSnapshot {
+    pub active_app: Option<String>,
+    pub monitor_count: Option<u32>,
+    pub on_battery: Option<bool>,
+    pub captured_at: String,
+}
+
+/// Intervals answered in one context over a week, with their average energy rating
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContextGroup {
+    pub dimension: String,  // "power", "monitors" or "app"
+    pub value: String,      // e.g. "battery"/"plugged_in", "2", "Code"
+    pub intervals: i32,
+    pub average_energy: Option<f64>,  // None when none of them were rated
+}
+
+pub fn store_context_snapshot(conn: &Connection, interval_id: i64, snapshot: &ContextSnapshot) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO interval_context (interval_id, active_app, monitor_count, on_battery, captured_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![interval_id, snapshot.active_app, snapshot.monitor_count, snapshot.on_battery, snapshot.captured_at],
+    )?;
+    Ok(())
+}
+
+pub fn query_context_snapshot(conn: &Connection, interval_id: i64) -> Result<Option<ContextSnapshot>> {
+    conn.query_row(
+        "SELECT active_app, monitor_count, on_battery, captured_at FROM interval_context WHERE interval_id = ?1",
+        params![interval_id],
+        |row| {
+            Ok(ContextSnapshot {
+                active_app: row.get(0)?,
+                monitor_count: row.get(1)?,
+                on_battery: row.get(2)?,
+                captured_at: row.get(3)?,
+            })
+        },
+    )
+    .optional()
+}
+
+/// Intervals with a snapshot on workblocks dated in [start_date, end_date), grouped by power
+/// source, monitor count and (the CONTEXT_TOP_APPS most frequent) application
+pub fn query_context_breakdown(conn: &Connection, start_date: &str, end_date: &str) -> Result<Vec<ContextGroup>> {
+    let mut stmt = conn.prepare(
+        "SELECT c.active_app, c.monitor_count, c.on_battery, i.energy
+         FROM interval_context c
+         JOIN intervals i ON i.id = c.interval_id
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE w.date >= ?1 AND w.date < ?2",
+    )?;
+    let rows = stmt
+        .query_map(params![start_date, end_date], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<u32>>(1)?,
+                row.get::<_, Option<bool>>(2)?,
+                row.get::<_, Option<i32>>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    // (dimension, value) -> (intervals, energy ratings)
+    let mut groups: BTreeMap<(&str, String), (i32, Vec<i32>)> = BTreeMap::new();
+    for (app, monitors, on_battery, energy) in rows {
+        let power = on_battery.map(|b| if b { "battery" } else { "plugged_in" }.to_string());
+        for (dimension, value) in [("power", power), ("monitors", monitors.map(|m| m.to_string())), ("app", app)] {
+            if let Some(value) = value {
+                let group = groups.entry((dimension, value)).or_default();
+                group.0 += 1;
+                group.1.extend(energy);
+            }
+        }
+    }
+
+    let mut breakdown: Vec<ContextGroup> = groups
+        .into_iter()
+        .map(|((dimension, value), (intervals, energy))| ContextGroup {
+            dimension: dimension.to_string(),
+            value,
+            intervals,
+            average_energy: (!energy.is_empty()).then(|| energy.iter().sum::<i32>() as f64 / energy.len() as f64),
+        })
+        .collect();
+    // Most frequent first within each dimension, then keep only the top applications
+    breakdown.sort_by(|a, b| a.dimension.cmp(&b.dimension).then(b.intervals.cmp(&a.intervals)).then(a.value.cmp(&b.value)));
+    let mut apps = 0;
+    breakdown.retain(|g| {
+        if g.dimension != "app" {
+            return true;
+        }
+        apps += 1;
+        apps <= CONTEXT_TOP_APPS
+    });
+    Ok(breakdown)
+}
+
+/// Parse `pmset -g batt`: its first line names the power source, e.g. "Now drawing from 'Battery Power'"
+pub fn parse_pmset_batt(output: &str) -> Option<bool> {
+    let line = output.lines().next()?;
+    if line.contains("'Battery Power'") {
+        Some(true)
+    } else if line.contains("'AC Power'") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Whether a Linux machine runs on battery, from the supplies in /sys/class/power_supply.
+/// None without a battery (a desktop) or when nothing can be read.
+pub fn linux_on_battery(power_supply_dir: &Path) -> Option<bool> {
+    let mut has_battery = false;
+    let mut mains_online = None;
+    for entry in std::fs::read_dir(power_supply_dir).ok()?.filter_map(|e| e.ok()) {
+        let read = |file: &str| std::fs::read_to_string(entry.path().join(file)).ok().map(|s| s.trim().to_string());
+        match read("type").as_deref() {
+            Some("Battery") => has_battery = true,
+            Some("Mains") => {
+                let online = read("online").as_deref() == Some("1");
+                mains_online = Some(mains_online.unwrap_or(false) || online);
+            }
+            _ => {}
+        }
+    }
+    if !has_battery {
+        return None;
+    }
+    mains_online.map(|online| !online)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_on_battery() -> Option<bool> {
+    let output = std::process::Command::new("pmset").args(["-g", "batt"]).output().ok()?;
+    parse_pmset_batt(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_on_battery() -> Option<bool> {
+    #[repr(C)]
+    #[derive(Default)]
+    struct SystemPowerStatus {
+        ac_line_status: u8,
+        battery_flag: u8,
+        battery_life_percent: u8,
+        system_status_flag: u8,
+        battery_life_time: u32,
+        battery_full_life_time: u32,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+    }
+
+    const NO_SYSTEM_BATTERY: u8 = 128;
+    let mut status = SystemPowerStatus::default();
+    // SAFETY: `status` is a valid out pointer of the documented layout
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 || status.battery_flag & NO_SYSTEM_BATTERY != 0 {
+        return None;
+    }
+    match status.ac_line_status {
+        0 => Some(true),
+        1 => Some(false),
+        _ => None,  // 255 = unknown
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn platform_on_battery() -> Option<bool> {
+    linux_on_battery(Path::new("/sys/class/power_supply"))
+}
+
+/// Whether `app_name` is log15 itself (the prompt has focus while words are submitted)
+fn is_own_app(app: &AppHandle, app_name: &str) -> bool {
+    let exe = std::env::current_exe().ok();
+    let exe_name = exe.as_deref().and_then(|p| p.file_stem()).and_then(|s| s.to_str());
+    app_name.eq_ignore_ascii_case(&app.package_info().name) || exe_name.is_some_and(|n| app_name.eq_ignore_ascii_case(n))
+}
+
+/// The application with the most captured time in the interval, if activity capture recorded any
+fn query_main_interval_app(conn: &Connection, interval_id: i64) -> Result<Option<String>> {
+    conn.query_row(
+        "SELECT app_name FROM interval_app_usage WHERE interval_id = ?1
+         GROUP BY app_name ORDER BY SUM(seconds) DESC, app_name LIMIT 1",
+        params![interval_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Take a snapshot of the workspace for `interval_id` right now. When log15 itself is in front,
+/// the application comes from the interval's activity capture instead, if there is any.
+pub fn capture_context_snapshot(app: &AppHandle, interval_id: i64) -> ContextSnapshot {
+    let active_app = match focused_window() {
+        Some(window) if !is_own_app(app, &window.app_name) => Some(window.app_name),
+        _ => get_db_connection(app)
+            .and_then(|conn| Ok(query_main_interval_app(&conn, interval_id)?))
+            .ok()
+            .flatten(),
+    };
+    ContextSnapshot {
+        active_app,
+        monitor_count: app.available_monitors().ok().map(|m| m.len() as u32),
+        on_battery: platform_on_battery(),
+        captured_at: timestamp_now(),
+    }
+}
+
+/// Snapshot the workspace for an interval that was just answered, if the user opted in.
+/// Runs in the background so answering never waits on it; errors are only logged.
+pub fn record_context_snapshot(app: &AppHandle, interval_id: i64) {
+    if !get_context_snapshot_enabled(app) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let snapshot = capture_context_snapshot(&app, interval_id);
+        let result = get_db_connection(&app).and_then(|conn| Ok(store_context_snapshot(&conn, interval_id, &snapshot)?));
+        if let Err(e) = result {
+            eprintln!("[CONTEXT] Failed to store snapshot for interval {}: {}", interval_id, e);
+        }
+    });
+}
+
+pub fn get_context_snapshot(app: &AppHandle, interval_id: i64) -> Result<Option<ContextSnapshot>> {
+    let conn = get_db_connection(app)?;
+    query_context_snapshot(&conn, interval_id)
+}
@@ -0,0 +1,187 @@
+// Daily-note export for Obsidian and other Markdown vaults: a log15 section with the day's timeline
+// and activity totals, written into the note named after the date. The section sits between two
+// HTML comments, so exporting the same day again replaces it and leaves the rest of the note alone.
+
+use crate::db::{generate_daily_aggregate, parse_timestamp, DailyAggregate};
+use crate::format::Formatter;
+use crate::tray::format_tracked_minutes;
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+
+pub const SECTION_START: &str = "<!-- log15:start -->";
+pub const SECTION_END: &str = "<!-- log15:end -->";
+/// Obsidian's default daily note name
+pub const DEFAULT_NOTE_TEMPLATE: &str = "YYYY-MM-DD";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DailyNoteExport {
+    pub path: String,
+    pub replaced: bool,  // An earlier log15 section was replaced rather than appended
+    pub timeline_entries: usize,
+    pub total_minutes: i32,
+}
+
+/// Moment-style tokens of Obsidian's date format and their chrono equivalents, longest first
+const DATE_TOKENS: [(&str, &str); 8] = [
+    ("YYYY", "%Y"),
+    ("MMMM", "%B"),
+    ("dddd", "%A"),
+    ("MMM", "%b"),
+    ("ddd", "%a"),
+    ("YY", "%y"),
+    ("MM", "%m"),
+    ("DD", "%d"),
+];
+
+/// Note path for `date` from a template in Obsidian's date format, e.g. "Journal/YYYY/YYYY-MM-DD".
+/// Text in [brackets] is kept literally; ".md" is added unless the template ends with it.
+pub fn note_relative_path(template: &str, date: NaiveDate) -> Result<PathBuf, String> {
+    let template = match template.trim() {
+        "" => DEFAULT_NOTE_TEMPLATE,
+        template => template,
+    };
+    let mut pattern = String::new();
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if c == '[' {
+            let Some(end) = rest.find(']') else {
+                return Err(format!("Unclosed [ in note template '{}'", template));
+            };
+            pattern.push_str(&rest[1..end].replace('%', "%%"));
+            rest = &rest[end + 1..];
+        } else if let Some((token, spec)) = DATE_TOKENS.iter().find(|(token, _)| rest.starts_with(token)) {
+            pattern.push_str(spec);
+            rest = &rest[token.len()..];
+        } else {
+            pattern.push_str(if c == '%' { "%%" } else { &rest[..c.len_utf8()] });
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    let mut name = date.format(&pattern).to_string();
+    if !name.to_lowercase().ends_with(".md") {
+        name.push_str(".md");
+    }
+    let path = PathBuf::from(name);
+    // The note has to stay inside the vault
+    if !path.components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(format!("Note template '{}' must be a path inside the vault", template));
+    }
+    Ok(path)
+}
+
+/// Keep a table cell or list item on one line and out of the Markdown syntax
+fn inline(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").replace('|', "\\|")
+}
+
+/// The log15 section for a day, markers included
+pub fn render_daily_note_section(aggregate: &DailyAggregate, fmt: &Formatter) -> String {
+    let time = |timestamp: Option<&str>| {
+        timestamp
+            .and_then(parse_timestamp)
+            .map(|t| fmt.time(t.with_timezone(&Local).time()))
+            .unwrap_or_else(|| "…".to_string())
+    };
+    let workblocks = match aggregate.total_workblocks {
+        1 => "1 workblock".to_string(),
+        n => format!("{} workblocks", n),
+    };
+    let mut lines = vec![
+        SECTION_START.to_string(),
+        "## Log15".to_string(),
+        String::new(),
+        format!("**{}** tracked in {}", format_tracked_minutes(aggregate.total_minutes), workblocks),
+    ];
+
+    if !aggregate.timeline_data.is_empty() {
+        lines.extend([String::new(), "### Timeline".to_string(), String::new()]);
+        for entry in &aggregate.timeline_data {
+            let words = entry.words.as_deref().map(inline).filter(|w| !w.is_empty());
+            lines.push(format!(
+                "- {}–{} {}",
+                time(Some(&entry.start_time)),
+                time(entry.end_time.as_deref()),
+                words.as_deref().unwrap_or("(no entry)"),
+            ));
+        }
+    }
+
+    if !aggregate.activity_data.is_empty() {
+        lines.extend([
+            String::new(),
+            "### Activities".to_string(),
+            String::new(),
+            "| Activity | Time | Share |".to_string(),
+            "| --- | --- | --- |".to_string(),
+        ]);
+        for activity in &aggregate.activity_data {
+            lines.push(format!(
+                "| {} | {} | {}% |",
+                inline(&activity.words),
+                format_tracked_minutes(activity.total_minutes),
+                fmt.number(activity.percentage, 0),
+            ));
+        }
+    }
+    lines.push(SECTION_END.to_string());
+    lines.join("\n") + "\n"
+}
+
+/// `note` with its log15 section replaced by `section`, or with `section` appended if it has none.
+/// Returns the new note and whether a section was replaced.
+pub fn merge_daily_note(note: &str, section: &str) -> (String, bool) {
+    if let Some(start) = note.find(SECTION_START) {
+        if let Some(end) = note[start..].find(SECTION_END).map(|end| start + end + SECTION_END.len()) {
+            // The section already ends with a newline
+            let after = note[end..].strip_prefix('\n').unwrap_or(&note[end..]);
+            return (format!("{}{}{}", &note[..start], section, after), true);
+        }
+    }
+    let separator = match note {
+        "" => "",
+        note if note.ends_with("\n\n") => "",
+        note if note.ends_with('\n') => "\n",
+        _ => "\n\n",
+    };
+    (format!("{}{}{}", note, separator, section), false)
+}
+
+/// Write `section` into the note at `path`, creating the note (and its folders) if needed
+pub fn write_daily_note(path: &Path, section: &str) -> Result<bool, String> {
+    let note = match std::fs::read_to_string(path) {
+        Ok(note) => note,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let (merged, replaced) = merge_daily_note(&note, section);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(path, merged).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(replaced)
+}
+
+pub fn export_to_daily_note(
+    app: &AppHandle,
+    date: &str,
+    vault_path: &Path,
+    template: &str,
+) -> Result<DailyNoteExport, String> {
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("Invalid date '{}': {}", date, e))?;
+    if !vault_path.is_dir() {
+        return Err(format!("Vault folder {} does not exist", vault_path.display()));
+    }
+    let path = vault_path.join(note_relative_path(template, day)?);
+
+    let aggregate = generate_daily_aggregate(app, date).map_err(|e| e.to_string())?;
+    let section = render_daily_note_section(&aggregate, &Formatter::from_settings(app));
+    let replaced = write_daily_note(&path, &section)?;
+    Ok(DailyNoteExport {
+        path: path.to_string_lossy().into_owned(),
+        replaced,
+        timeline_entries: aggregate.timeline_data.len(),
+        total_minutes: aggregate.total_minutes,
+    })
+}
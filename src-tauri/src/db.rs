@@ -1,12 +1,14 @@
-use rusqlite::{Connection, Result, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use crate::error::{Log15Error, Result};
+use crate::events::{emit_event, AppEvent};
 
 /// Get the database path for the application
-fn get_db_path(app: &AppHandle) -> PathBuf {
+pub(crate) fn get_db_path(app: &AppHandle) -> PathBuf {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -20,7 +22,14 @@ fn get_db_path(app: &AppHandle) -> PathBuf {
 pub fn init_db(app: &AppHandle) -> Result<Connection> {
     let db_path = get_db_path(app);
     let conn = Connection::open(&db_path)?;
-    
+    crate::backup::backup_on_startup(app, &conn);
+    create_schema(&conn)?;
+    crate::settings::load_day_start_hour(&conn);
+    Ok(conn)
+}
+
+/// Create all tables and indexes (idempotent)
+pub fn create_schema(conn: &Connection) -> Result<()> {
     // Create workblocks table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS workblocks (
@@ -31,7 +40,7 @@ pub fn init_db(app: &AppHandle) -> Result<Connection> {
             duration_minutes INTEGER,
             status TEXT NOT NULL,
             is_archived BOOLEAN DEFAULT 0,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
         )",
         [],
     )?;
@@ -60,11 +69,219 @@ pub fn init_db(app: &AppHandle) -> Result<Connection> {
             total_workblocks INTEGER DEFAULT 0,
             total_minutes INTEGER DEFAULT 0,
             visualization_data TEXT,
-            archived_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            archived_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+        )",
+        [],
+    )?;
+    
+    // Create interval_attachments table (files copied into managed storage)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS interval_attachments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            interval_id INTEGER NOT NULL,
+            file_name TEXT NOT NULL,
+            stored_path TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL,
+            created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now')),
+            FOREIGN KEY (interval_id) REFERENCES intervals(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    
+    // Create interval_activities table (an interval split between several activities).
+    // PRAGMA foreign_keys is off, so the cascade doesn't run: deleting an interval deletes these rows too.
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS interval_activities (
+            interval_id INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            words TEXT NOT NULL,
+            percentage INTEGER NOT NULL,
+            PRIMARY KEY (interval_id, position),
+            FOREIGN KEY (interval_id) REFERENCES intervals(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    
+    // Create activity_aliases table (raw phrase -> canonical activity)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS activity_aliases (
+            alias TEXT PRIMARY KEY,
+            canonical TEXT NOT NULL,
+            created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+        )",
+        [],
+    )?;
+    
+    // Create pinned_activities table (favorites offered first in the prompt)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS pinned_activities (
+            activity TEXT PRIMARY KEY,
+            pinned_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+        )",
+        [],
+    )?;
+    
+    // Create activity_colors table (display color per canonical activity)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS activity_colors (
+            activity TEXT PRIMARY KEY,
+            color TEXT NOT NULL,
+            updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+        )",
+        [],
+    )?;
+    
+    // Create interval_app_usage table (opt-in: time each focused application was in front per interval)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS interval_app_usage (
+            interval_id INTEGER NOT NULL,
+            app_name TEXT NOT NULL,
+            window_title TEXT NOT NULL DEFAULT '',
+            seconds INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (interval_id, app_name, window_title),
+            FOREIGN KEY (interval_id) REFERENCES intervals(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    
+    // Create interval_context table (opt-in: workspace when the interval was answered)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS interval_context (
+            interval_id INTEGER PRIMARY KEY,
+            active_app TEXT,
+            monitor_count INTEGER,
+            on_battery BOOLEAN,
+            captured_at DATETIME NOT NULL,
+            FOREIGN KEY (interval_id) REFERENCES intervals(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    
+    // Create computer_activity table (opt-in: seconds active at the computer per day, for the time audit)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS computer_activity (
+            date TEXT PRIMARY KEY,
+            tracked_seconds INTEGER NOT NULL DEFAULT 0,
+            untracked_seconds INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    
+    // Create diagnostic_incidents table (problems the app detected and repaired on its own)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS diagnostic_incidents (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source TEXT NOT NULL,
+            detail TEXT NOT NULL,
+            occurred_at DATETIME NOT NULL
+        )",
+        [],
+    )?;
+    
+    // Create settings table (user preferences as key/value pairs)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+        )",
+        [],
+    )?;
+    
+    // Create settings_history table (every change to a setting, for explaining odd aggregates later)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS settings_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL,
+            old_value TEXT,
+            new_value TEXT NOT NULL,
+            changed_at DATETIME NOT NULL
+        )",
+        [],
+    )?;
+    
+    // Create category_rules table (auto-categorization of interval words)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS category_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            pattern TEXT NOT NULL,
+            match_type TEXT NOT NULL,
+            category TEXT NOT NULL,
+            priority INTEGER NOT NULL DEFAULT 0,
+            enabled BOOLEAN NOT NULL DEFAULT 1,
+            created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+        )",
+        [],
+    )?;
+    
+    // Create goals table (recurring daily targets, one per metric)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS goals (
+            metric TEXT PRIMARY KEY,
+            target INTEGER NOT NULL,
+            created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+        )",
+        [],
+    )?;
+    
+    // Create daily_rollovers table (one row per archived day; claiming a row guards the rollover)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS daily_rollovers (
+            date TEXT PRIMARY KEY,
+            claimed_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+        )",
+        [],
+    )?;
+    
+    // Create announced_milestones table (goal, budget and streak milestones already announced, see stats.rs)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS announced_milestones (
+            date TEXT NOT NULL,
+            key TEXT NOT NULL,
+            announced_at DATETIME NOT NULL,
+            PRIMARY KEY (date, key)
+        )",
+        [],
+    )?;
+    
+    // Create webhooks table (URLs notified of workblock and archive events, see webhooks.rs)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS webhooks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            url TEXT NOT NULL,
+            events TEXT NOT NULL,
+            secret TEXT NOT NULL,
+            enabled BOOLEAN NOT NULL DEFAULT 1,
+            created_at DATETIME NOT NULL,
+            last_status TEXT,
+            last_delivery_at DATETIME
+        )",
+        [],
+    )?;
+    
+    // Create deleted_rows table (tombstones for get_changes_since, see changes.rs)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS deleted_rows (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            table_name TEXT NOT NULL,
+            row_id INTEGER NOT NULL,
+            deleted_at DATETIME NOT NULL
         )",
         [],
     )?;
     
+    // Create computed_cache table (memoized derived data, see computed_cache.rs)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS computed_cache (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            computed_at DATETIME NOT NULL,
+            ttl_seconds INTEGER
+        )",
+        [],
+    )?;
+    crate::computed_cache::create_cache_triggers(conn)?;
+    
     // Create indexes for better query performance
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_workblocks_date ON workblocks(date)",
@@ -78,14 +295,158 @@ pub fn init_db(app: &AppHandle) -> Result<Connection> {
         "CREATE INDEX IF NOT EXISTS idx_intervals_workblock_id ON intervals(workblock_id)",
         [],
     )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_interval_attachments_interval_id ON interval_attachments(interval_id)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_settings_history_changed_at ON settings_history(changed_at)",
+        [],
+    )?;
+    // Secrets and window layouts written before they were kept out of the history
+    for key in crate::settings::SECRET_SETTING_KEYS.into_iter().chain(crate::settings::LAYOUT_SETTING_KEYS) {
+        conn.execute("DELETE FROM settings_history WHERE key = ?1", params![key])?;
+    }
     
-    Ok(conn)
+    // Columns added after the initial schema
+    add_column_if_missing(conn, "workblocks", "project", "TEXT")?;
+    add_column_if_missing(conn, "intervals", "project", "TEXT")?;  // NULL = inherit from workblock
+    add_column_if_missing(conn, "intervals", "category", "TEXT")?;  // Set by category rules
+    add_column_if_missing(conn, "intervals", "energy", "INTEGER")?;  // NULL = not rated
+    add_column_if_missing(conn, "intervals", "notes", "TEXT")?;  // Free-form detail beyond the short words
+    add_column_if_missing(conn, "intervals", "provenance", "TEXT NOT NULL DEFAULT 'live'")?;  // See IntervalProvenance
+    add_column_if_missing(conn, "intervals", "away_cause", "TEXT")?;  // See AwayCause; NULL for answered intervals
+    add_column_if_missing(conn, "workblocks", "updated_at", "DATETIME")?;  // Set by triggers, see changes.rs
+    add_column_if_missing(conn, "intervals", "updated_at", "DATETIME")?;
+    crate::changes::create_change_triggers(conn)?;
+    add_column_if_missing(conn, "workblocks", "cancel_reason", "TEXT")?;
+    add_column_if_missing(conn, "workblocks", "cancel_note", "TEXT")?;
+    add_column_if_missing(conn, "workblocks", "title", "TEXT")?;  // What the block is for, e.g. "Write Q3 report"
+    // duration_minutes becomes the actual length on cancel; this keeps the length the block was started with
+    add_column_if_missing(conn, "workblocks", "planned_duration_minutes", "INTEGER")?;
+    // Set on a workblock ended at the day start: its cut interval is the last one answered, not the scheduled last
+    add_column_if_missing(conn, "workblocks", "split_at_day_start", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "daily_archives", "goal_streak", "INTEGER DEFAULT 0")?;
+    add_column_if_missing(conn, "daily_archives", "goals_attained", "BOOLEAN")?;  // NULL = no goals were set
+    add_column_if_missing(conn, "daily_archives", "ai_summary", "TEXT")?;
+    
+    // Data migrations, tracked with SQLite's user_version
+    let version = query_schema_version(conn)?;
+    if version < 1 {
+        normalize_timestamps(conn)?;
+        conn.execute("PRAGMA user_version = 1", [])?;
+    }
+    
+    Ok(())
+}
+
+/// user_version once every data migration has run
+pub const SCHEMA_VERSION: i32 = 1;
+
+pub fn query_schema_version(conn: &Connection) -> Result<i32> {
+    Ok(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+}
+
+/// Timestamp columns rewritten by normalize_timestamps
+const TIMESTAMP_COLUMNS: &[(&str, &str)] = &[
+    ("workblocks", "start_time"),
+    ("workblocks", "end_time"),
+    ("workblocks", "created_at"),
+    ("intervals", "start_time"),
+    ("intervals", "end_time"),
+    ("intervals", "recorded_at"),
+    ("daily_archives", "archived_at"),
+    ("interval_attachments", "created_at"),
+    ("activity_aliases", "created_at"),
+    ("pinned_activities", "pinned_at"),
+    ("activity_colors", "updated_at"),
+    ("diagnostic_incidents", "occurred_at"),
+    ("settings", "updated_at"),
+    ("settings_history", "changed_at"),
+    ("category_rules", "created_at"),
+    ("goals", "created_at"),
+    ("daily_rollovers", "claimed_at"),
+];
+
+/// Rewrite stored timestamps as UTC RFC 3339. Older versions mixed local-offset RFC 3339 strings
+/// with SQLite's `datetime('now')` format; values that parse as neither are left alone.
+/// Returns how many values changed.
+pub fn normalize_timestamps(conn: &Connection) -> Result<usize> {
+    let mut changed = 0;
+    for (table, column) in TIMESTAMP_COLUMNS {
+        let values = conn
+            .prepare(&format!("SELECT rowid, {} FROM {} WHERE {} IS NOT NULL", column, table, column))?
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for (rowid, value) in values {
+            let Some(normalized) = parse_timestamp(&value).map(to_timestamp) else {
+                continue;
+            };
+            if normalized != value {
+                conn.execute(
+                    &format!("UPDATE {} SET {} = ?1 WHERE rowid = ?2", table, column),
+                    params![normalized, rowid],
+                )?;
+                changed += 1;
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Add a column to an existing table (no-op if it already exists)
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|name| name.ok())
+        .any(|name| name == column);
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Timestamps
+// ============================================================================
+
+/// Storage form of a timestamp: UTC RFC 3339 (e.g. 2024-05-01T07:00:00+00:00), so stored values
+/// sort and subtract correctly whatever the offset was when they were written. Convert to Local
+/// only for display or for picking a calendar day.
+pub fn to_timestamp<Tz: TimeZone>(time: DateTime<Tz>) -> String {
+    time.with_timezone(&Utc).to_rfc3339()
+}
+
+pub fn timestamp_now() -> String {
+    to_timestamp(Utc::now())
+}
+
+/// Parse a stored timestamp: RFC 3339 with any offset, or SQLite's `YYYY-MM-DD HH:MM:SS` (UTC)
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|t| t.and_utc())
+        })
 }
 
 /// Get a database connection
 pub fn get_db_connection(app: &AppHandle) -> Result<Connection> {
     let db_path = get_db_path(app);
-    Connection::open(&db_path)
+    Ok(Connection::open(&db_path)?)
+}
+
+/// Connection that SQLite itself refuses to write through (used for ad-hoc user queries)
+pub fn get_readonly_db_connection(app: &AppHandle) -> Result<Connection> {
+    let db_path = get_db_path(app);
+    Ok(Connection::open_with_flags(
+        &db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?)
 }
 
 // ============================================================================
@@ -102,6 +463,14 @@ pub struct Workblock {
     pub status: WorkblockStatus,
     pub is_archived: bool,
     pub created_at: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,  // Label inherited by intervals without their own project
+    #[serde(default)]
+    pub cancel_reason: Option<CancelReason>,  // Only set on cancelled workblocks
+    #[serde(default)]
+    pub cancel_note: Option<String>,          // Free-text detail for the cancellation
+    #[serde(default)]
+    pub title: Option<String>,                // Intention set when starting the block
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -130,6 +499,43 @@ impl WorkblockStatus {
     }
 }
 
+/// Why a workblock was cancelled (chosen by the user when cancelling)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelReason {
+    Interrupted,
+    Meeting,
+    Distracted,
+    Tired,
+    PlansChanged,
+    Other,
+}
+
+impl CancelReason {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CancelReason::Interrupted => "interrupted",
+            CancelReason::Meeting => "meeting",
+            CancelReason::Distracted => "distracted",
+            CancelReason::Tired => "tired",
+            CancelReason::PlansChanged => "plans_changed",
+            CancelReason::Other => "other",
+        }
+    }
+    
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "interrupted" => CancelReason::Interrupted,
+            "meeting" => CancelReason::Meeting,
+            "distracted" => CancelReason::Distracted,
+            "tired" => CancelReason::Tired,
+            "plans_changed" => CancelReason::PlansChanged,
+            _ => CancelReason::Other,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Interval {
     pub id: Option<i64>,
@@ -140,6 +546,70 @@ pub struct Interval {
     pub words: Option<String>,
     pub status: IntervalStatus,
     pub recorded_at: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,  // Per-interval override; None inherits the workblock's project
+    #[serde(default)]
+    pub category: Option<String>, // Assigned by the first matching category rule
+    #[serde(default)]
+    pub energy: Option<i32>,      // MIN_ENERGY (drained) to MAX_ENERGY (energized), if rated
+    #[serde(default)]
+    pub notes: Option<String>,    // Longer free-form notes; never used for activity grouping
+    #[serde(default)]
+    pub provenance: IntervalProvenance,
+    #[serde(default)]
+    pub away_cause: Option<AwayCause>,  // Why it was recorded as away; kept if the interval is relabelled later
+}
+
+/// Answers recorded more than this long after their interval ended count as after the fact
+pub const AFTER_THE_FACT_MINUTES: i64 = 60;
+
+/// How an interval's words got there, so after-the-fact data can be told apart from live answers
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum IntervalProvenance {
+    #[default]
+    Live,        // Answered at the prompt, or recorded by the timer as it happened
+    Backfilled,  // Filled in afterwards for an interval that was missed or lost to auto-away
+    Imported,    // Merged in from another database
+    Edited,      // Answered live, then changed long after the interval ended
+}
+
+impl IntervalProvenance {
+    pub fn as_str(&self) -> &str {
+        match self {
+            IntervalProvenance::Live => "live",
+            IntervalProvenance::Backfilled => "backfilled",
+            IntervalProvenance::Imported => "imported",
+            IntervalProvenance::Edited => "edited",
+        }
+    }
+    
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "backfilled" => IntervalProvenance::Backfilled,
+            "imported" => IntervalProvenance::Imported,
+            "edited" => IntervalProvenance::Edited,
+            _ => IntervalProvenance::Live,
+        }
+    }
+}
+
+/// Provenance of an interval whose words change from `previous` to `status` at `recorded_at`,
+/// or None to keep the current one. Only answers given long after the interval ended count.
+pub fn provenance_for_answer(
+    previous: &IntervalStatus,
+    status: &IntervalStatus,
+    ended_at: Option<DateTime<Utc>>,
+    recorded_at: DateTime<Utc>,
+) -> Option<IntervalProvenance> {
+    let late = ended_at.is_some_and(|end| (recorded_at - end).num_minutes() > AFTER_THE_FACT_MINUTES);
+    match (previous, status) {
+        _ if !late => None,
+        (_, IntervalStatus::Pending | IntervalStatus::AutoAway) => None,
+        (IntervalStatus::Recorded | IntervalStatus::QuickStatus, _) => Some(IntervalProvenance::Edited),
+        _ => Some(IntervalProvenance::Backfilled),
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -147,6 +617,7 @@ pub enum IntervalStatus {
     Pending,
     Recorded,
     AutoAway,
+    QuickStatus,  // Away for a named reason ("Lunch", "Errand"), kept apart from unexplained away time
 }
 
 impl IntervalStatus {
@@ -155,6 +626,7 @@ impl IntervalStatus {
             IntervalStatus::Pending => "pending",
             IntervalStatus::Recorded => "recorded",
             IntervalStatus::AutoAway => "auto_away",
+            IntervalStatus::QuickStatus => "quick_status",
         }
     }
     
@@ -163,11 +635,60 @@ impl IntervalStatus {
             "pending" => IntervalStatus::Pending,
             "recorded" => IntervalStatus::Recorded,
             "auto_away" => IntervalStatus::AutoAway,
+            "quick_status" => IntervalStatus::QuickStatus,
             _ => IntervalStatus::Pending,
         }
     }
 }
 
+/// Why an interval was recorded as away instead of being answered
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AwayCause {
+    PromptTimeout,  // The prompt went unanswered until the auto-away timeout
+    SystemIdle,     // No keyboard/mouse input past the idle threshold
+    ScreenLock,     // Idle with the screen locked
+    PromptsPaused,  // Do not disturb was on
+    SystemSleep,    // The interval ended while the machine was asleep
+    Cancelled,      // The interval ended while its workblock was cancelled, before it was resumed
+}
+
+impl AwayCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AwayCause::PromptTimeout => "prompt_timeout",
+            AwayCause::SystemIdle => "system_idle",
+            AwayCause::ScreenLock => "screen_lock",
+            AwayCause::PromptsPaused => "prompts_paused",
+            AwayCause::SystemSleep => "system_sleep",
+            AwayCause::Cancelled => "cancelled",
+        }
+    }
+    
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "prompt_timeout" => Some(AwayCause::PromptTimeout),
+            "system_idle" => Some(AwayCause::SystemIdle),
+            "screen_lock" => Some(AwayCause::ScreenLock),
+            "prompts_paused" => Some(AwayCause::PromptsPaused),
+            "system_sleep" => Some(AwayCause::SystemSleep),
+            "cancelled" => Some(AwayCause::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IntervalAttachment {
+    pub id: Option<i64>,
+    pub interval_id: i64,
+    pub file_name: String,    // Original file name as chosen by the user
+    pub stored_path: String,  // Absolute path inside the managed attachments directory
+    pub size_bytes: i64,
+    pub created_at: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DailyArchive {
     pub id: Option<i64>,
@@ -176,6 +697,20 @@ pub struct DailyArchive {
     pub total_minutes: i32,
     pub visualization_data: Option<String>,  // JSON string
     pub archived_at: Option<String>,
+    #[serde(default)]
+    pub goal_streak: i32,                 // Streak of days with every goal attained, ending on this day
+    #[serde(default)]
+    pub goals_attained: Option<bool>,     // None when no goals were set
+    #[serde(default)]
+    pub ai_summary: Option<String>,       // Written by generate_ai_summary, kept when the day is re-archived
+}
+
+/// Payload of the "archive-updated" event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveUpdated {
+    pub date: String,
+    pub total_workblocks: i32,
+    pub total_minutes: i32,
 }
 
 // ============================================================================
@@ -186,34 +721,73 @@ pub struct DailyArchive {
 pub fn create_workblock(app: &AppHandle, duration_minutes: i32) -> Result<Workblock> {
     let conn = get_db_connection(app)?;
     let now = Local::now();
-    let date = now.format("%Y-%m-%d").to_string();
-    let start_time = now.to_rfc3339();
+    let date = logical_date(now, crate::settings::day_start_hour()).format("%Y-%m-%d").to_string();
+    let start_time = to_timestamp(now);
     
+    // Written explicitly: databases created by older versions still default to SQLite's format
     conn.execute(
-        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
-         VALUES (?1, ?2, ?3, ?4, 0)",
+        "INSERT INTO workblocks (date, start_time, duration_minutes, planned_duration_minutes, status, is_archived, created_at)
+         VALUES (?1, ?2, ?3, ?3, ?4, 0, ?2)",
         params![date, start_time, duration_minutes, WorkblockStatus::Active.as_str()],
     )?;
     
     let id = conn.last_insert_rowid();
+    crate::cache::workblock_written(app, id);
     
     Ok(Workblock {
         id: Some(id),
         date,
-        start_time,
+        start_time: start_time.clone(),
         end_time: None,
         duration_minutes: Some(duration_minutes),
         status: WorkblockStatus::Active,
         is_archived: false,
-        created_at: Some(now.to_rfc3339()),
+        created_at: Some(start_time),
+        project: None,
+        cancel_reason: None,
+        cancel_note: None,
+        title: None,
     })
 }
 
+/// Longest title a workblock can have
+pub const MAX_WORKBLOCK_TITLE_LENGTH: usize = 100;
+
+/// The title as stored: trimmed, None when blank; too long a title is rejected
+pub fn normalize_workblock_title(title: Option<&str>) -> Result<Option<&str>> {
+    let title = title.map(str::trim).filter(|t| !t.is_empty());
+    if title.is_some_and(|t| t.chars().count() > MAX_WORKBLOCK_TITLE_LENGTH) {
+        return Err(Log15Error::InvalidInput(format!("Title must be at most {} characters", MAX_WORKBLOCK_TITLE_LENGTH)));
+    }
+    Ok(title)
+}
+
+/// Set or clear a workblock's title; blank titles are stored as NULL
+pub fn store_workblock_title(conn: &Connection, workblock_id: i64, title: Option<&str>) -> Result<()> {
+    let title = normalize_workblock_title(title)?;
+    let updated = conn.execute(
+        "UPDATE workblocks SET title = ?1 WHERE id = ?2",
+        params![title, workblock_id],
+    )?;
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows.into());
+    }
+    Ok(())
+}
+
+pub fn update_workblock_title(app: &AppHandle, workblock_id: i64, title: Option<&str>) -> Result<Workblock> {
+    let conn = get_db_connection(app)?;
+    store_workblock_title(&conn, workblock_id, title)?;
+    crate::cache::workblock_written(app, workblock_id);
+    query_workblock_by_id(&conn, workblock_id)
+}
+
 /// Get the active workblock (if any)
 pub fn get_active_workblock(app: &AppHandle) -> Result<Option<Workblock>> {
     let conn = get_db_connection(app)?;
     let mut stmt = conn.prepare(
-        "SELECT id, date, start_time, end_time, duration_minutes, status, is_archived, created_at
+        "SELECT id, date, start_time, end_time, duration_minutes, status, is_archived, created_at, project,
+                cancel_reason, cancel_note, title
          FROM workblocks
          WHERE status = 'active'
          ORDER BY start_time DESC
@@ -230,72 +804,209 @@ pub fn get_active_workblock(app: &AppHandle) -> Result<Option<Workblock>> {
             status: WorkblockStatus::from_str(&row.get::<_, String>(5)?),
             is_archived: row.get(6)?,
             created_at: row.get(7)?,
+            project: row.get(8)?,
+            cancel_reason: row.get::<_, Option<String>>(9)?.map(|r| CancelReason::from_str(&r)),
+            cancel_note: row.get(10)?,
+            title: row.get(11)?,
         })
     });
     
     match workblock_result {
         Ok(workblock) => Ok(Some(workblock)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e),
+        Err(e) => Err(e.into()),
     }
 }
 
 /// Complete a workblock
 pub fn complete_workblock(app: &AppHandle, workblock_id: i64) -> Result<Workblock> {
     let conn = get_db_connection(app)?;
-    let end_time = Local::now().to_rfc3339();
-    
-    // Calculate duration
-    let workblock = get_workblock_by_id(app, workblock_id)?;
-    let start_time = DateTime::parse_from_rfc3339(&workblock.start_time)
-        .map_err(|e| rusqlite::Error::InvalidColumnType(0, format!("Invalid start_time: {}", e), rusqlite::types::Type::Text))?;
-    let end_time_dt = DateTime::parse_from_rfc3339(&end_time)
-        .map_err(|e| rusqlite::Error::InvalidColumnType(0, format!("Invalid end_time: {}", e), rusqlite::types::Type::Text))?;
-    let duration = (end_time_dt - start_time).num_minutes() as i32;
+    let workblock = store_workblock_end(&conn, workblock_id, WorkblockStatus::Completed, Utc::now())?;
+    crate::cache::workblock_written(app, workblock_id);
+    Ok(workblock)
+}
+
+/// Mark a workblock as split at the day start, before it is completed there
+pub fn store_workblock_split(conn: &Connection, workblock_id: i64) -> Result<()> {
+    conn.execute("UPDATE workblocks SET split_at_day_start = 1 WHERE id = ?1", params![workblock_id])?;
+    Ok(())
+}
+
+pub fn mark_workblock_split(app: &AppHandle, workblock_id: i64) -> Result<()> {
+    let conn = get_db_connection(app)?;
+    store_workblock_split(&conn, workblock_id)?;
+    crate::cache::workblock_written(app, workblock_id);
+    Ok(())
+}
+
+/// Whether answering `interval` finishes its workblock, so the summary opens. The interval cut
+/// off by a split at the day start doesn't, even though the shortened workblock ends with it.
+pub fn query_is_last_interval(conn: &Connection, interval: &Interval) -> Result<bool> {
+    let (planned_minutes, split): (Option<i32>, bool) = conn.query_row(
+        "SELECT duration_minutes, split_at_day_start FROM workblocks WHERE id = ?1",
+        params![interval.workblock_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    if split {
+        return Ok(false);
+    }
+    let total_intervals = crate::interval_schedule::total_intervals(
+        planned_minutes.unwrap_or(60),
+        crate::interval_schedule::interval_seconds(),
+    );
+    Ok(crate::interval_schedule::is_last_interval(interval.interval_number, total_intervals))
+}
+
+/// Complete a workblock as of `end` (e.g. when it is split at the day start)
+pub fn complete_workblock_at(app: &AppHandle, workblock_id: i64, end: DateTime<Utc>) -> Result<Workblock> {
+    let conn = get_db_connection(app)?;
+    let workblock = store_workblock_end(&conn, workblock_id, WorkblockStatus::Completed, end)?;
+    crate::cache::workblock_written(app, workblock_id);
+    Ok(workblock)
+}
+
+/// Cancel a workblock
+pub fn cancel_workblock(app: &AppHandle, workblock_id: i64) -> Result<Workblock> {
+    let conn = get_db_connection(app)?;
+    let workblock = store_workblock_end(&conn, workblock_id, WorkblockStatus::Cancelled, Utc::now())?;
+    crate::cache::workblock_written(app, workblock_id);
+    Ok(workblock)
+}
+
+/// End a workblock at `end` with `status`; duration_minutes becomes the elapsed time.
+/// Both ends are instants, so the duration is right across DST changes and time zone moves.
+pub fn store_workblock_end(
+    conn: &Connection,
+    workblock_id: i64,
+    status: WorkblockStatus,
+    end: DateTime<Utc>,
+) -> Result<Workblock> {
+    let workblock = query_workblock_by_id(conn, workblock_id)?;
+    let start = parse_timestamp(&workblock.start_time).ok_or_else(|| {
+        rusqlite::Error::InvalidColumnType(0, format!("Invalid start_time: {}", workblock.start_time), rusqlite::types::Type::Text)
+    })?;
+    let duration = (end - start).num_minutes() as i32;
     
     conn.execute(
         "UPDATE workblocks 
-         SET end_time = ?1, duration_minutes = ?2, status = 'completed'
-         WHERE id = ?3",
-        params![end_time, duration, workblock_id],
+         SET end_time = ?1, duration_minutes = ?2, status = ?3
+         WHERE id = ?4",
+        params![to_timestamp(end), duration, status.as_str(), workblock_id],
     )?;
     
+    query_workblock_by_id(conn, workblock_id)
+}
+
+/// Change the planned length of an active workblock
+pub fn set_workblock_duration(app: &AppHandle, workblock_id: i64, duration_minutes: i32) -> Result<Workblock> {
+    if duration_minutes <= 0 {
+        return Err(Log15Error::InvalidInput("Workblock duration must be at least 1 minute".to_string()));
+    }
+    let conn = get_db_connection(app)?;
+    let updated = conn.execute(
+        "UPDATE workblocks SET duration_minutes = ?1, planned_duration_minutes = ?1
+         WHERE id = ?2 AND status = 'active'",
+        params![duration_minutes, workblock_id],
+    )?;
+    if updated == 0 {
+        return Err(Log15Error::InvalidInput(format!("Workblock {} is not active", workblock_id)));
+    }
+    crate::cache::workblock_written(app, workblock_id);
     get_workblock_by_id(app, workblock_id)
 }
 
-/// Cancel a workblock
-pub fn cancel_workblock(app: &AppHandle, workblock_id: i64) -> Result<Workblock> {
+/// Record why a workblock was cancelled; an empty note is stored as NULL
+pub fn set_cancel_reason(
+    app: &AppHandle,
+    workblock_id: i64,
+    reason: Option<CancelReason>,
+    note: Option<&str>,
+) -> Result<Workblock> {
     let conn = get_db_connection(app)?;
-    let end_time = Local::now().to_rfc3339();
+    store_cancel_reason(&conn, workblock_id, reason, note)?;
+    get_workblock_by_id(app, workblock_id)
+}
+
+pub fn store_cancel_reason(
+    conn: &Connection,
+    workblock_id: i64,
+    reason: Option<CancelReason>,
+    note: Option<&str>,
+) -> Result<()> {
+    let note = note.map(str::trim).filter(|n| !n.is_empty());
+    conn.execute(
+        "UPDATE workblocks SET cancel_reason = ?1, cancel_note = ?2 WHERE id = ?3",
+        params![reason.as_ref().map(|r| r.as_str()), note, workblock_id],
+    )?;
+    Ok(())
+}
+
+/// Reopen a workblock cancelled at most `window_minutes` before `now`, restoring its planned duration.
+/// The block keeps its original start time, so it must not already have run past its planned end.
+pub fn reopen_cancelled_workblock(
+    conn: &Connection,
+    workblock_id: i64,
+    window_minutes: i64,
+    now: DateTime<Local>,
+) -> Result<Workblock> {
+    let fail = Log15Error::InvalidInput;
+    let parse_time = |time: &str| {
+        DateTime::parse_from_rfc3339(time)
+            .map_err(|e| rusqlite::Error::InvalidColumnType(0, format!("Invalid time: {}", e), rusqlite::types::Type::Text))
+    };
     
-    // Calculate duration
-    let workblock = get_workblock_by_id(app, workblock_id)?;
-    let start_time = DateTime::parse_from_rfc3339(&workblock.start_time)
-        .map_err(|e| rusqlite::Error::InvalidColumnType(0, format!("Invalid start_time: {}", e), rusqlite::types::Type::Text))?;
-    let end_time_dt = DateTime::parse_from_rfc3339(&end_time)
-        .map_err(|e| rusqlite::Error::InvalidColumnType(0, format!("Invalid end_time: {}", e), rusqlite::types::Type::Text))?;
-    let duration = (end_time_dt - start_time).num_minutes() as i32;
+    let active: i64 = conn.query_row("SELECT COUNT(*) FROM workblocks WHERE status = 'active'", [], |row| row.get(0))?;
+    if active > 0 {
+        return Err(Log15Error::AlreadyRunning);
+    }
     
-    conn.execute(
-        "UPDATE workblocks 
-         SET end_time = ?1, duration_minutes = ?2, status = 'cancelled'
-         WHERE id = ?3",
-        params![end_time, duration, workblock_id],
+    let workblock = query_workblock_by_id(conn, workblock_id)?;
+    if workblock.status != WorkblockStatus::Cancelled {
+        return Err(fail(format!("Workblock {} is not cancelled", workblock_id)));
+    }
+    let cancelled_at = parse_time(workblock.end_time.as_deref().unwrap_or_default())?;
+    if now.signed_duration_since(cancelled_at) > chrono::Duration::minutes(window_minutes) {
+        return Err(fail(format!(
+            "Workblock {} was cancelled more than {} minutes ago",
+            workblock_id, window_minutes
+        )));
+    }
+    
+    let planned: Option<i32> = conn.query_row(
+        "SELECT planned_duration_minutes FROM workblocks WHERE id = ?1",
+        params![workblock_id],
+        |row| row.get(0),
     )?;
+    let planned = planned.ok_or_else(|| fail(format!("Workblock {} has no planned duration to resume", workblock_id)))?;
+    let planned_end = parse_time(&workblock.start_time)? + chrono::Duration::minutes(planned as i64);
+    if now >= planned_end {
+        return Err(fail(format!("Workblock {} would already have ended", workblock_id)));
+    }
     
-    get_workblock_by_id(app, workblock_id)
+    conn.execute(
+        "UPDATE workblocks
+         SET status = 'active', end_time = NULL, duration_minutes = ?1, cancel_reason = NULL, cancel_note = NULL
+         WHERE id = ?2",
+        params![planned, workblock_id],
+    )?;
+    query_workblock_by_id(conn, workblock_id)
 }
 
 /// Get workblock by ID
 pub fn get_workblock_by_id(app: &AppHandle, workblock_id: i64) -> Result<Workblock> {
     let conn = get_db_connection(app)?;
+    query_workblock_by_id(&conn, workblock_id)
+}
+
+pub fn query_workblock_by_id(conn: &Connection, workblock_id: i64) -> Result<Workblock> {
     let mut stmt = conn.prepare(
-        "SELECT id, date, start_time, end_time, duration_minutes, status, is_archived, created_at
+        "SELECT id, date, start_time, end_time, duration_minutes, status, is_archived, created_at, project,
+                cancel_reason, cancel_note, title
          FROM workblocks
          WHERE id = ?1"
     )?;
     
-    stmt.query_row(params![workblock_id], |row| {
+    Ok(stmt.query_row(params![workblock_id], |row| {
         Ok(Workblock {
             id: Some(row.get(0)?),
             date: row.get(1)?,
@@ -305,15 +1016,24 @@ pub fn get_workblock_by_id(app: &AppHandle, workblock_id: i64) -> Result<Workblo
             status: WorkblockStatus::from_str(&row.get::<_, String>(5)?),
             is_archived: row.get(6)?,
             created_at: row.get(7)?,
+            project: row.get(8)?,
+            cancel_reason: row.get::<_, Option<String>>(9)?.map(|r| CancelReason::from_str(&r)),
+            cancel_note: row.get(10)?,
+            title: row.get(11)?,
         })
-    })
+    })?)
 }
 
 /// Get all workblocks for a specific date
 pub fn get_workblocks_by_date(app: &AppHandle, date: &str) -> Result<Vec<Workblock>> {
     let conn = get_db_connection(app)?;
+    query_workblocks_by_date(&conn, date)
+}
+
+pub fn query_workblocks_by_date(conn: &Connection, date: &str) -> Result<Vec<Workblock>> {
     let mut stmt = conn.prepare(
-        "SELECT id, date, start_time, end_time, duration_minutes, status, is_archived, created_at
+        "SELECT id, date, start_time, end_time, duration_minutes, status, is_archived, created_at, project,
+                cancel_reason, cancel_note, title
          FROM workblocks
          WHERE date = ?1
          ORDER BY start_time ASC"
@@ -329,6 +1049,10 @@ pub fn get_workblocks_by_date(app: &AppHandle, date: &str) -> Result<Vec<Workblo
             status: WorkblockStatus::from_str(&row.get::<_, String>(5)?),
             is_archived: row.get(6)?,
             created_at: row.get(7)?,
+            project: row.get(8)?,
+            cancel_reason: row.get::<_, Option<String>>(9)?.map(|r| CancelReason::from_str(&r)),
+            cancel_note: row.get(10)?,
+            title: row.get(11)?,
         })
     })?;
     
@@ -346,7 +1070,7 @@ pub fn get_workblocks_by_date(app: &AppHandle, date: &str) -> Result<Vec<Workblo
 /// Add an interval to a workblock
 pub fn add_interval(app: &AppHandle, workblock_id: i64, interval_number: i32) -> Result<Interval> {
     let conn = get_db_connection(app)?;
-    let start_time = Local::now().to_rfc3339();
+    let start_time = timestamp_now();
     
     conn.execute(
         "INSERT INTO intervals (workblock_id, interval_number, start_time, status)
@@ -355,6 +1079,7 @@ pub fn add_interval(app: &AppHandle, workblock_id: i64, interval_number: i32) ->
     )?;
     
     let id = conn.last_insert_rowid();
+    crate::cache::interval_written(app, id);
     
     Ok(Interval {
         id: Some(id),
@@ -365,6 +1090,12 @@ pub fn add_interval(app: &AppHandle, workblock_id: i64, interval_number: i32) ->
         words: None,
         status: IntervalStatus::Pending,
         recorded_at: None,
+        project: None,
+        category: None,
+        energy: None,
+        notes: None,
+        provenance: IntervalProvenance::Live,
+        away_cause: None,
     })
 }
 
@@ -376,28 +1107,385 @@ pub fn update_interval_words(
     status: IntervalStatus,
 ) -> Result<Interval> {
     let conn = get_db_connection(app)?;
-    let recorded_at = Local::now().to_rfc3339();
-    
+    store_interval_words(&conn, interval_id, &words, status, Utc::now())?;
+    crate::cache::interval_written(app, interval_id);
+    get_interval_by_id(app, interval_id)
+}
+
+/// Record an interval's words at `recorded_at`; an interval without an end yet ends then
+pub fn store_interval_words(
+    conn: &Connection,
+    interval_id: i64,
+    words: &str,
+    status: IntervalStatus,
+    recorded_at: DateTime<Utc>,
+) -> Result<()> {
+    let (previous, end_time): (String, Option<String>) = conn.query_row(
+        "SELECT status, end_time FROM intervals WHERE id = ?1",
+        params![interval_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let ended_at = end_time.as_deref().and_then(parse_timestamp);
+    let provenance = provenance_for_answer(&IntervalStatus::from_str(&previous), &status, ended_at, recorded_at);
+    let recorded_at = to_timestamp(recorded_at);
     conn.execute(
         "UPDATE intervals 
-         SET words = ?1, status = ?2, recorded_at = ?3, end_time = ?3
-         WHERE id = ?4",
-        params![words, status.as_str(), recorded_at, interval_id],
+         SET words = ?1, status = ?2, recorded_at = ?3, end_time = COALESCE(end_time, ?3),
+             provenance = COALESCE(?4, provenance)
+         WHERE id = ?5",
+        params![words, status.as_str(), recorded_at, provenance.map(|p| p.as_str().to_string()), interval_id],
+    )?;
+    // New words replace any earlier split
+    conn.execute("DELETE FROM interval_activities WHERE interval_id = ?1", params![interval_id])?;
+    crate::rules::categorize_interval(conn, interval_id)?;
+    assign_new_activity_colors(conn, &[words])?;
+    Ok(())
+}
+
+/// One of several activities an interval was split between
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActivitySplit {
+    pub words: String,
+    pub percentage: i32,  // Share of the interval; an interval's splits add up to 100
+}
+
+/// Most activities one interval can be split between
+pub const MAX_ACTIVITY_SPLITS: usize = 4;
+
+/// Check a split answer: 2 to MAX_ACTIVITY_SPLITS activities with words, each 1-99%, adding up to 100%
+pub fn validate_activity_splits(splits: &[ActivitySplit]) -> Result<Vec<ActivitySplit>> {
+    if splits.len() < 2 || splits.len() > MAX_ACTIVITY_SPLITS {
+        return Err(Log15Error::InvalidInput(format!(
+            "An interval can be split between 2 and {} activities",
+            MAX_ACTIVITY_SPLITS
+        )));
+    }
+    let splits: Vec<ActivitySplit> = splits
+        .iter()
+        .map(|s| ActivitySplit { words: s.words.trim().to_string(), percentage: s.percentage })
+        .collect();
+    if splits.iter().any(|s| s.words.is_empty()) {
+        return Err(Log15Error::InvalidInput("Every activity in a split needs words".to_string()));
+    }
+    if splits.iter().any(|s| !(1..=99).contains(&s.percentage)) {
+        return Err(Log15Error::InvalidInput("Each activity must take between 1% and 99% of the interval".to_string()));
+    }
+    let total: i32 = splits.iter().map(|s| s.percentage).sum();
+    if total != 100 {
+        return Err(Log15Error::InvalidInput(format!("The split adds up to {}%, not 100%", total)));
+    }
+    Ok(splits)
+}
+
+/// The words stored on a split interval, e.g. "coding + meeting"
+pub fn split_words(splits: &[ActivitySplit]) -> String {
+    splits.iter().map(|s| s.words.as_str()).collect::<Vec<_>>().join(" + ")
+}
+
+/// Replace an interval's split (after its words were stored, which clears the old one)
+pub fn store_activity_splits(conn: &Connection, interval_id: i64, splits: &[ActivitySplit]) -> Result<()> {
+    conn.execute("DELETE FROM interval_activities WHERE interval_id = ?1", params![interval_id])?;
+    for (position, split) in splits.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO interval_activities (interval_id, position, words, percentage) VALUES (?1, ?2, ?3, ?4)",
+            params![interval_id, position as i64, split.words, split.percentage],
+        )?;
+    }
+    let split_words: Vec<&str> = splits.iter().map(|s| s.words.as_str()).collect();
+    assign_new_activity_colors(conn, &split_words)?;
+    Ok(())
+}
+
+pub fn set_activity_splits(app: &AppHandle, interval_id: i64, splits: &[ActivitySplit]) -> Result<()> {
+    let conn = get_db_connection(app)?;
+    store_activity_splits(&conn, interval_id, splits)?;
+    crate::cache::interval_written(app, interval_id);
+    Ok(())
+}
+
+/// Splits of a workblock's intervals, keyed by interval id (intervals with one activity are absent)
+pub fn query_activity_splits_by_workblock(conn: &Connection, workblock_id: i64) -> Result<HashMap<i64, Vec<ActivitySplit>>> {
+    let mut stmt = conn.prepare(
+        "SELECT a.interval_id, a.words, a.percentage
+         FROM interval_activities a
+         JOIN intervals i ON i.id = a.interval_id
+         WHERE i.workblock_id = ?1
+         ORDER BY a.interval_id, a.position",
     )?;
+    let rows = stmt.query_map(params![workblock_id], |row| {
+        Ok((row.get::<_, i64>(0)?, ActivitySplit { words: row.get(1)?, percentage: row.get(2)? }))
+    })?;
+    let mut splits: HashMap<i64, Vec<ActivitySplit>> = HashMap::new();
+    for row in rows {
+        let (interval_id, split) = row?;
+        splits.entry(interval_id).or_default().push(split);
+    }
+    Ok(splits)
+}
+
+pub fn get_activity_splits_by_workblock(app: &AppHandle, workblock_id: i64) -> Result<HashMap<i64, Vec<ActivitySplit>>> {
+    let conn = get_db_connection(app)?;
+    query_activity_splits_by_workblock(&conn, workblock_id)
+}
+
+/// What repeating the last interval copies onto the current one
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PreviousAnswer {
+    pub interval_id: i64,
+    pub words: String,
+    pub project: Option<String>,      // Per-interval project override, if it had one
+    pub splits: Vec<ActivitySplit>,   // Empty unless it was split between activities
+}
+
+/// The latest interval answered with words that started before `interval_id`, in any workblock
+pub fn query_previous_answer(conn: &Connection, interval_id: i64) -> Result<Option<PreviousAnswer>> {
+    let previous = conn.query_row(
+        "SELECT id, words, project FROM intervals
+         WHERE status = 'recorded' AND words IS NOT NULL AND id != ?1
+           AND start_time < (SELECT start_time FROM intervals WHERE id = ?1)
+         ORDER BY start_time DESC, id DESC
+         LIMIT 1",
+        params![interval_id],
+        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?)),
+    );
+    let (previous_id, words, project) = match previous {
+        Ok(previous) => previous,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
     
+    let mut stmt = conn.prepare("SELECT words, percentage FROM interval_activities WHERE interval_id = ?1 ORDER BY position")?;
+    let rows = stmt.query_map(params![previous_id], |row| Ok(ActivitySplit { words: row.get(0)?, percentage: row.get(1)? }))?;
+    let mut splits = Vec::new();
+    for split in rows {
+        splits.push(split?);
+    }
+    Ok(Some(PreviousAnswer { interval_id: previous_id, words, project, splits }))
+}
+
+/// Divide `minutes` between an interval's activities by their shares, so the parts add up to `minutes`
+/// (minutes lost to rounding go to the largest remainders). An interval without a split gives all of them to `words`.
+pub fn apportion_minutes(words: &str, splits: &[ActivitySplit], minutes: i32) -> Vec<(String, i32)> {
+    if splits.is_empty() {
+        return vec![(words.to_string(), minutes)];
+    }
+    let mut shares: Vec<(String, i32)> = splits
+        .iter()
+        .map(|s| (s.words.clone(), minutes * s.percentage / 100))
+        .collect();
+    let leftover = (minutes - shares.iter().map(|(_, m)| m).sum::<i32>()).max(0) as usize;
+    let mut by_remainder: Vec<usize> = (0..splits.len()).collect();
+    by_remainder.sort_by_key(|&i| std::cmp::Reverse(minutes * splits[i].percentage % 100));
+    for &i in by_remainder.iter().take(leftover) {
+        shares[i].1 += 1;
+    }
+    shares
+}
+
+/// An interval's minutes per canonical activity, skipping activities that normalize to nothing
+fn interval_activity_minutes(
+    interval: &Interval,
+    splits: &HashMap<i64, Vec<ActivitySplit>>,
+    minutes: i32,
+    aliases: &HashMap<String, String>,
+) -> Vec<(String, i32)> {
+    let Some(words) = &interval.words else { return Vec::new() };
+    let splits = interval.id.and_then(|id| splits.get(&id)).map(Vec::as_slice).unwrap_or_default();
+    apportion_minutes(words, splits, minutes)
+        .into_iter()
+        .map(|(words, minutes)| (normalize_activity(&words, aliases), minutes))
+        .filter(|(activity, _)| !activity.is_empty())
+        .collect()
+}
+
+/// Record an interval as away with the configured message (or another away text) and why
+pub fn record_away_interval(app: &AppHandle, interval_id: i64, message: String, cause: AwayCause) -> Result<Interval> {
+    let conn = get_db_connection(app)?;
+    store_interval_words(&conn, interval_id, &message, IntervalStatus::AutoAway, Utc::now())?;
+    store_away_cause(&conn, interval_id, cause)?;
+    crate::cache::interval_written(app, interval_id);
+    get_interval_by_id(app, interval_id)
+}
+
+pub fn store_away_cause(conn: &Connection, interval_id: i64, cause: AwayCause) -> Result<()> {
+    conn.execute(
+        "UPDATE intervals SET away_cause = ?1 WHERE id = ?2",
+        params![cause.as_str(), interval_id],
+    )?;
+    Ok(())
+}
+
+/// Longest notes an interval can hold
+pub const MAX_NOTES_LENGTH: usize = 5000;
+
+/// Set or clear an interval's notes; blank notes are stored as NULL
+pub fn store_interval_notes(conn: &Connection, interval_id: i64, notes: Option<&str>) -> Result<()> {
+    let notes = notes.map(str::trim).filter(|n| !n.is_empty());
+    if notes.is_some_and(|n| n.chars().count() > MAX_NOTES_LENGTH) {
+        return Err(Log15Error::InvalidInput(format!("Notes must be at most {} characters", MAX_NOTES_LENGTH)));
+    }
+    let updated = conn.execute(
+        "UPDATE intervals SET notes = ?1 WHERE id = ?2",
+        params![notes, interval_id],
+    )?;
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows.into());
+    }
+    Ok(())
+}
+
+pub fn update_interval_notes(app: &AppHandle, interval_id: i64, notes: Option<&str>) -> Result<Interval> {
+    let conn = get_db_connection(app)?;
+    store_interval_notes(&conn, interval_id, notes)?;
+    crate::cache::interval_written(app, interval_id);
     get_interval_by_id(app, interval_id)
 }
 
+/// An interval matching a search, with the day it belongs to
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IntervalSearchResult {
+    pub date: String,
+    pub interval: Interval,
+}
+
+/// Intervals whose words or notes contain `query` (case-insensitive), newest first
+pub fn query_search_intervals(conn: &Connection, query: &str, limit: i64) -> Result<Vec<IntervalSearchResult>> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pattern = format!(
+        "%{}%",
+        query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    );
+    let mut stmt = conn.prepare(
+        "SELECT w.date, i.id, i.workblock_id, i.interval_number, i.start_time, i.end_time, i.words, i.status,
+                i.recorded_at, i.project, i.category, i.energy, i.notes, i.provenance, i.away_cause
+         FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE i.words LIKE ?1 ESCAPE '\\' OR i.notes LIKE ?1 ESCAPE '\\'
+         ORDER BY i.start_time DESC
+         LIMIT ?2"
+    )?;
+    let rows = stmt.query_map(params![pattern, limit], |row| {
+        Ok(IntervalSearchResult {
+            date: row.get(0)?,
+            interval: Interval {
+                id: Some(row.get(1)?),
+                workblock_id: row.get(2)?,
+                interval_number: row.get(3)?,
+                start_time: row.get(4)?,
+                end_time: row.get(5)?,
+                words: row.get(6)?,
+                status: IntervalStatus::from_str(&row.get::<_, String>(7)?),
+                recorded_at: row.get(8)?,
+                project: row.get(9)?,
+                category: row.get(10)?,
+                energy: row.get(11)?,
+                notes: row.get(12)?,
+                provenance: IntervalProvenance::from_str(&row.get::<_, String>(13)?),
+                away_cause: row.get::<_, Option<String>>(14)?.and_then(|c| AwayCause::from_str(&c)),
+            },
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+pub fn search_intervals(app: &AppHandle, query: &str, limit: i64) -> Result<Vec<IntervalSearchResult>> {
+    let conn = get_db_connection(app)?;
+    query_search_intervals(&conn, query, limit)
+}
+
+/// Lowest and highest energy rating an interval can get
+pub const MIN_ENERGY: i32 = 1;
+pub const MAX_ENERGY: i32 = 5;
+
+/// Rate how drained (MIN_ENERGY) or energized (MAX_ENERGY) the user felt; None clears the rating
+pub fn store_interval_energy(conn: &Connection, interval_id: i64, energy: Option<i32>) -> Result<()> {
+    if let Some(energy) = energy.filter(|e| !(MIN_ENERGY..=MAX_ENERGY).contains(e)) {
+        return Err(Log15Error::InvalidInput(format!("Energy must be between {} and {}, got {}", MIN_ENERGY, MAX_ENERGY, energy)));
+    }
+    let updated = conn.execute(
+        "UPDATE intervals SET energy = ?1 WHERE id = ?2",
+        params![energy, interval_id],
+    )?;
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows.into());
+    }
+    Ok(())
+}
+
+pub fn set_interval_energy(app: &AppHandle, interval_id: i64, energy: Option<i32>) -> Result<()> {
+    let conn = get_db_connection(app)?;
+    store_interval_energy(&conn, interval_id, energy)?;
+    crate::cache::interval_written(app, interval_id);
+    Ok(())
+}
+
+/// Pin an interval's start and end to explicit times (e.g. wall-clock boundaries)
+pub fn set_interval_bounds(app: &AppHandle, interval_id: i64, start_time: &str, end_time: &str) -> Result<()> {
+    let conn = get_db_connection(app)?;
+    conn.execute(
+        "UPDATE intervals SET start_time = ?1, end_time = ?2 WHERE id = ?3",
+        params![start_time, end_time, interval_id],
+    )?;
+    crate::cache::interval_written(app, interval_id);
+    Ok(())
+}
+
+/// Set when an interval ended; recording words later keeps this end time
+pub fn set_interval_end_time(app: &AppHandle, interval_id: i64, end_time: &str) -> Result<()> {
+    let conn = get_db_connection(app)?;
+    conn.execute(
+        "UPDATE intervals SET end_time = ?1 WHERE id = ?2",
+        params![end_time, interval_id],
+    )?;
+    crate::cache::interval_written(app, interval_id);
+    Ok(())
+}
+
+/// Remove an interval that hasn't been answered yet, with its activity split; returns whether it
+/// was removed. Foreign keys aren't enforced, so the split rows are deleted here rather than cascaded.
+pub fn remove_pending_interval(conn: &Connection, interval_id: i64) -> Result<bool> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM interval_activities
+         WHERE interval_id = (SELECT id FROM intervals WHERE id = ?1 AND status = 'pending')",
+        params![interval_id],
+    )?;
+    let deleted = tx.execute(
+        "DELETE FROM intervals WHERE id = ?1 AND status = 'pending'",
+        params![interval_id],
+    )?;
+    tx.commit()?;
+    Ok(deleted > 0)
+}
+
+/// Remove an interval that hasn't been answered yet (e.g. one a split workblock started just
+/// after the day start). Answered intervals are left alone.
+pub fn delete_pending_interval(app: &AppHandle, interval_id: i64) -> Result<()> {
+    let conn = get_db_connection(app)?;
+    let interval = query_interval_by_id(&conn, interval_id)?;
+    if remove_pending_interval(&conn, interval_id)? {
+        crate::cache::workblock_written(app, interval.workblock_id);
+    }
+    Ok(())
+}
+
 /// Get interval by ID
 pub fn get_interval_by_id(app: &AppHandle, interval_id: i64) -> Result<Interval> {
     let conn = get_db_connection(app)?;
+    query_interval_by_id(&conn, interval_id)
+}
+
+pub fn query_interval_by_id(conn: &Connection, interval_id: i64) -> Result<Interval> {
     let mut stmt = conn.prepare(
-        "SELECT id, workblock_id, interval_number, start_time, end_time, words, status, recorded_at
+        "SELECT id, workblock_id, interval_number, start_time, end_time, words, status, recorded_at, project, category, energy, notes, provenance, away_cause
          FROM intervals
          WHERE id = ?1"
     )?;
     
-    stmt.query_row(params![interval_id], |row| {
+    Ok(stmt.query_row(params![interval_id], |row| {
         Ok(Interval {
             id: Some(row.get(0)?),
             workblock_id: row.get(1)?,
@@ -407,15 +1495,25 @@ pub fn get_interval_by_id(app: &AppHandle, interval_id: i64) -> Result<Interval>
             words: row.get(5)?,
             status: IntervalStatus::from_str(&row.get::<_, String>(6)?),
             recorded_at: row.get(7)?,
+            project: row.get(8)?,
+            category: row.get(9)?,
+            energy: row.get(10)?,
+            notes: row.get(11)?,
+            provenance: IntervalProvenance::from_str(&row.get::<_, String>(12)?),
+            away_cause: row.get::<_, Option<String>>(13)?.and_then(|c| AwayCause::from_str(&c)),
         })
-    })
+    })?)
 }
 
 /// Get all intervals for a workblock
 pub fn get_intervals_by_workblock(app: &AppHandle, workblock_id: i64) -> Result<Vec<Interval>> {
     let conn = get_db_connection(app)?;
+    query_intervals_by_workblock(&conn, workblock_id)
+}
+
+pub fn query_intervals_by_workblock(conn: &Connection, workblock_id: i64) -> Result<Vec<Interval>> {
     let mut stmt = conn.prepare(
-        "SELECT id, workblock_id, interval_number, start_time, end_time, words, status, recorded_at
+        "SELECT id, workblock_id, interval_number, start_time, end_time, words, status, recorded_at, project, category, energy, notes, provenance, away_cause
          FROM intervals
          WHERE workblock_id = ?1
          ORDER BY interval_number ASC"
@@ -431,6 +1529,12 @@ pub fn get_intervals_by_workblock(app: &AppHandle, workblock_id: i64) -> Result<
             words: row.get(5)?,
             status: IntervalStatus::from_str(&row.get::<_, String>(6)?),
             recorded_at: row.get(7)?,
+            project: row.get(8)?,
+            category: row.get(9)?,
+            energy: row.get(10)?,
+            notes: row.get(11)?,
+            provenance: IntervalProvenance::from_str(&row.get::<_, String>(12)?),
+            away_cause: row.get::<_, Option<String>>(13)?.and_then(|c| AwayCause::from_str(&c)),
         })
     })?;
     
@@ -445,7 +1549,7 @@ pub fn get_intervals_by_workblock(app: &AppHandle, workblock_id: i64) -> Result<
 pub fn get_current_interval(app: &AppHandle, workblock_id: i64) -> Result<Option<Interval>> {
     let conn = get_db_connection(app)?;
     let mut stmt = conn.prepare(
-        "SELECT id, workblock_id, interval_number, start_time, end_time, words, status, recorded_at
+        "SELECT id, workblock_id, interval_number, start_time, end_time, words, status, recorded_at, project, category, energy, notes, provenance, away_cause
          FROM intervals
          WHERE workblock_id = ?1 AND status = 'pending'
          ORDER BY interval_number DESC
@@ -462,58 +1566,389 @@ pub fn get_current_interval(app: &AppHandle, workblock_id: i64) -> Result<Option
             words: row.get(5)?,
             status: IntervalStatus::from_str(&row.get::<_, String>(6)?),
             recorded_at: row.get(7)?,
+            project: row.get(8)?,
+            category: row.get(9)?,
+            energy: row.get(10)?,
+            notes: row.get(11)?,
+            provenance: IntervalProvenance::from_str(&row.get::<_, String>(12)?),
+            away_cause: row.get::<_, Option<String>>(13)?.and_then(|c| AwayCause::from_str(&c)),
         })
     });
     
     match interval_result {
         Ok(interval) => Ok(Some(interval)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// ============================================================================
+// Reclassification (undo auto-away)
+// ============================================================================
+
+/// Offered by the next prompt when the previous interval was recorded as auto-away
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReclassifyOffer {
+    pub interval_id: i64,                 // The auto-away interval that can be rewritten
+    pub interval_number: i32,
+    pub suggested_words: Option<String>,  // Last words recorded before the away interval
+}
+
+/// Check whether the interval before `interval_id` was auto-away and can be reclassified
+pub fn get_reclassify_offer(app: &AppHandle, interval_id: i64) -> Result<Option<ReclassifyOffer>> {
+    let conn = get_db_connection(app)?;
+    query_reclassify_offer(&conn, interval_id)
+}
+
+pub fn query_reclassify_offer(conn: &Connection, interval_id: i64) -> Result<Option<ReclassifyOffer>> {
+    let (workblock_id, interval_number): (i64, i32) = conn.query_row(
+        "SELECT workblock_id, interval_number FROM intervals WHERE id = ?1",
+        params![interval_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    
+    let previous = conn.query_row(
+        "SELECT id, interval_number FROM intervals
+         WHERE workblock_id = ?1 AND interval_number = ?2 AND status = 'auto_away'",
+        params![workblock_id, interval_number - 1],
+        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i32>(1)?)),
+    );
+    let (previous_id, previous_number) = match previous {
+        Ok(previous) => previous,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    
+    let suggested_words = match conn.query_row(
+        "SELECT words FROM intervals
+         WHERE workblock_id = ?1 AND interval_number < ?2 AND status = 'recorded' AND words IS NOT NULL
+         ORDER BY interval_number DESC
+         LIMIT 1",
+        params![workblock_id, previous_number],
+        |row| row.get::<_, String>(0),
+    ) {
+        Ok(words) => Some(words),
+        Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => return Err(e.into()),
+    };
+    
+    Ok(Some(ReclassifyOffer {
+        interval_id: previous_id,
+        interval_number: previous_number,
+        suggested_words,
+    }))
+}
+
+/// Rewrite an auto-away interval's words and mark it Recorded (and backfilled).
+/// Timing columns are kept so timelines still reflect when the interval actually ran.
+pub fn reclassify_interval(app: &AppHandle, interval_id: i64, words: &str) -> Result<Interval> {
+    let conn = get_db_connection(app)?;
+    apply_reclassification(&conn, interval_id, words)?;
+    crate::cache::interval_written(app, interval_id);
+    get_interval_by_id(app, interval_id)
+}
+
+pub fn apply_reclassification(conn: &Connection, interval_id: i64, words: &str) -> Result<()> {
+    let words = words.trim();
+    if words.is_empty() {
+        return Err(Log15Error::InvalidInput("Words must not be empty".to_string()));
+    }
+    
+    let updated = conn.execute(
+        "UPDATE intervals SET words = ?1, status = ?2, provenance = ?3 WHERE id = ?4 AND status = ?5",
+        params![
+            words,
+            IntervalStatus::Recorded.as_str(),
+            IntervalProvenance::Backfilled.as_str(),
+            interval_id,
+            IntervalStatus::AutoAway.as_str()
+        ],
+    )?;
+    if updated == 0 {
+        return Err(Log15Error::InvalidInput(format!("Interval {} is not an auto-away interval", interval_id)));
+    }
+    crate::rules::categorize_interval(conn, interval_id)?;
+    Ok(())
+}
+
+/// Relabel an auto-away interval with a quick status once the user says where they were.
+/// Like reclassification, the interval keeps its timing.
+pub fn apply_quick_status(conn: &Connection, interval_id: i64, status: &str) -> Result<()> {
+    let status = status.trim();
+    if status.is_empty() {
+        return Err(Log15Error::InvalidInput("Quick status must not be empty".to_string()));
+    }
+    
+    let updated = conn.execute(
+        "UPDATE intervals SET words = ?1, status = ?2, provenance = ?3 WHERE id = ?4 AND status = ?5",
+        params![
+            status,
+            IntervalStatus::QuickStatus.as_str(),
+            IntervalProvenance::Backfilled.as_str(),
+            interval_id,
+            IntervalStatus::AutoAway.as_str()
+        ],
+    )?;
+    if updated == 0 {
+        return Err(Log15Error::InvalidInput(format!("Interval {} is not an auto-away interval", interval_id)));
+    }
+    crate::rules::categorize_interval(conn, interval_id)?;
+    Ok(())
+}
+
+// ============================================================================
+// Attachment Operations
+// ============================================================================
+
+/// Link a file (already copied into managed storage) to an interval
+pub fn add_interval_attachment(
+    app: &AppHandle,
+    interval_id: i64,
+    file_name: &str,
+    stored_path: &str,
+    size_bytes: i64,
+) -> Result<IntervalAttachment> {
+    let conn = get_db_connection(app)?;
+    let created_at = timestamp_now();
+    
+    conn.execute(
+        "INSERT INTO interval_attachments (interval_id, file_name, stored_path, size_bytes, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![interval_id, file_name, stored_path, size_bytes, created_at],
+    )?;
+    let id = conn.last_insert_rowid();
+    crate::cache::interval_written(app, interval_id);
+    
+    Ok(IntervalAttachment {
+        id: Some(id),
+        interval_id,
+        file_name: file_name.to_string(),
+        stored_path: stored_path.to_string(),
+        size_bytes,
+        created_at: Some(created_at),
+    })
+}
+
+/// Get all attachments for an interval
+pub fn get_attachments_by_interval(app: &AppHandle, interval_id: i64) -> Result<Vec<IntervalAttachment>> {
+    let conn = get_db_connection(app)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, interval_id, file_name, stored_path, size_bytes, created_at
+         FROM interval_attachments
+         WHERE interval_id = ?1
+         ORDER BY id ASC"
+    )?;
+    
+    let attachment_iter = stmt.query_map(params![interval_id], |row| {
+        Ok(IntervalAttachment {
+            id: Some(row.get(0)?),
+            interval_id: row.get(1)?,
+            file_name: row.get(2)?,
+            stored_path: row.get(3)?,
+            size_bytes: row.get(4)?,
+            created_at: row.get(5)?,
+        })
+    })?;
+    
+    let mut attachments = Vec::new();
+    for attachment in attachment_iter {
+        attachments.push(attachment?);
+    }
+    Ok(attachments)
+}
+
+/// Get attachments for every interval of a workblock, keyed by interval ID
+pub fn get_attachments_by_workblock(app: &AppHandle, workblock_id: i64) -> Result<HashMap<i64, Vec<IntervalAttachment>>> {
+    let conn = get_db_connection(app)?;
+    query_attachments_by_workblock(&conn, workblock_id)
+}
+
+pub fn query_attachments_by_workblock(conn: &Connection, workblock_id: i64) -> Result<HashMap<i64, Vec<IntervalAttachment>>> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.interval_id, a.file_name, a.stored_path, a.size_bytes, a.created_at
+         FROM interval_attachments a
+         JOIN intervals i ON i.id = a.interval_id
+         WHERE i.workblock_id = ?1
+         ORDER BY a.id ASC"
+    )?;
+    
+    let attachment_iter = stmt.query_map(params![workblock_id], |row| {
+        Ok(IntervalAttachment {
+            id: Some(row.get(0)?),
+            interval_id: row.get(1)?,
+            file_name: row.get(2)?,
+            stored_path: row.get(3)?,
+            size_bytes: row.get(4)?,
+            created_at: row.get(5)?,
+        })
+    })?;
+    
+    let mut attachments: HashMap<i64, Vec<IntervalAttachment>> = HashMap::new();
+    for attachment in attachment_iter {
+        let attachment = attachment?;
+        attachments.entry(attachment.interval_id).or_default().push(attachment);
     }
+    Ok(attachments)
 }
 
 // ============================================================================
 // Daily Operations
 // ============================================================================
 
-/// Get the date string for today
+/// Day that `at` belongs to when days begin at `day_start_hour` (0 = midnight):
+/// with a 4 AM start, 01:30 still counts toward the previous day
+pub fn logical_date(at: DateTime<Local>, day_start_hour: u32) -> chrono::NaiveDate {
+    (at - chrono::Duration::hours(day_start_hour as i64)).date_naive()
+}
+
+/// When `date` begins: its day start hour on the local clock. A day start skipped by a DST change
+/// begins an hour later instead.
+pub fn day_start_time(date: chrono::NaiveDate, day_start_hour: u32) -> DateTime<Local> {
+    let start = date.and_time(chrono::NaiveTime::from_hms_opt(day_start_hour, 0, 0).unwrap_or_default());
+    Local
+        .from_local_datetime(&start)
+        .earliest()
+        .or_else(|| Local.from_local_datetime(&(start + chrono::Duration::hours(1))).earliest())
+        .unwrap_or_else(|| Local.from_utc_datetime(&start))
+}
+
+/// Get the date string for today, honoring the configured day start hour
 pub fn get_today_date() -> String {
-    Local::now().format("%Y-%m-%d").to_string()
+    logical_date(crate::faults::now(), crate::settings::day_start_hour()).format("%Y-%m-%d").to_string()
 }
 
 /// Check if we need to reset for a new day and archive previous day
 pub fn check_and_reset_daily(app: &AppHandle) -> Result<Option<String>> {
-    let today = get_today_date();
+    let today = logical_date(crate::faults::now(), crate::settings::day_start_hour());
+    let yesterday = (today - chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+    let today = today.format("%Y-%m-%d").to_string();
+    let conn = get_db_connection(app)?;
+    let rolled_over = run_daily_rollover(&conn, &today, &yesterday, |date| archive_unless_quiet(app, &conn, date))?;
+    if rolled_over.is_some() {
+        crate::cache::clear();
+    }
+    Ok(rolled_over)
+}
+
+/// Whether the archive time has passed in the current day. Both times are measured from the day
+/// start hour, so with a 4 AM start an archive time of 01:00 falls late in the same day.
+pub fn archive_time_reached(now: DateTime<Local>, archive_time: chrono::NaiveTime, day_start_hour: u32) -> bool {
+    let day_start = chrono::NaiveTime::from_hms_opt(day_start_hour, 0, 0).unwrap_or_default();
+    let since_day_start = |time: chrono::NaiveTime| (time - day_start).num_minutes().rem_euclid(24 * 60);
+    since_day_start(now.time()) >= since_day_start(archive_time)
+}
+
+/// Archive today early at the configured archive time (e.g. 19:00), while the day boundary
+/// still decides which day work belongs to
+pub fn check_scheduled_archive(app: &AppHandle) -> Result<Option<String>> {
+    let Some(archive_time) = crate::settings::get_archive_time(app) else {
+        return Ok(None);
+    };
+    if !archive_time_reached(crate::faults::now(), archive_time, crate::settings::day_start_hour()) {
+        return Ok(None);
+    }
     let conn = get_db_connection(app)?;
+    run_scheduled_archive(&conn, &get_today_date(), |date| archive_unless_quiet(app, &conn, date))
+}
+
+/// Archive `today` (through `archive`) once nothing is active and some of its workblocks aren't
+/// archived yet. Unlike the rollover this claims nothing: work finished later in the evening
+/// re-archives the day the same way, and the rollover still covers whatever is left.
+pub fn run_scheduled_archive(
+    conn: &Connection,
+    today: &str,
+    mut archive: impl FnMut(&str) -> Result<bool>,
+) -> Result<Option<String>> {
+    let (active, unarchived): (i32, i32) = conn.query_row(
+        "SELECT COALESCE(SUM(status = 'active'), 0), COALESCE(SUM(is_archived = 0), 0)
+         FROM workblocks WHERE date = ?1",
+        params![today],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    if active > 0 || unarchived == 0 {
+        return Ok(None);
+    }
+    Ok(archive(today)?.then(|| today.to_string()))
+}
+
+/// Check the archive time once a minute, and right away when it or the day start hour changes
+pub fn spawn_archive_scheduler(app: &AppHandle) {
+    crate::settings::spawn_settings_listener("archive_scheduler", app, |app, change| async move {
+        let key = change.key.as_str();
+        if !key.is_empty() && key != crate::settings::ARCHIVE_TIME_KEY && key != crate::settings::DAY_START_HOUR_KEY {
+            return;
+        }
+        match check_scheduled_archive(&app) {
+            Ok(Some(date)) => println!("[DB] Archived {} at the new archive time", date),
+            Ok(None) => {}
+            Err(e) => eprintln!("[DB] Scheduled archive failed: {}", e),
+        }
+    });
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
+            match check_scheduled_archive(&app) {
+                Ok(Some(date)) => println!("[DB] Archived {} at the scheduled archive time", date),
+                Ok(None) => {}
+                Err(e) => eprintln!("[DB] Scheduled archive failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Roll over to `today`: archive (through `archive`) a previous day that still has an active
+/// workblock, or else yesterday's unarchived workblocks. A failed archive releases its claim so
+/// the next check retries it. Returns the archived date; quiet days (`archive` returned false) give None.
+pub fn run_daily_rollover(
+    conn: &Connection,
+    today: &str,
+    yesterday: &str,
+    mut archive: impl FnMut(&str) -> Result<bool>,
+) -> Result<Option<String>> {
+    let mut archive_claimed = |date: &str| -> Result<bool> {
+        let result = crate::faults::fail_point(crate::faults::FaultPoint::RolloverArchive)
+            .map_err(Log15Error::from)
+            .and_then(|_| archive(date));
+        if result.is_err() {
+            release_rollover(conn, date)?;
+        }
+        result
+    };
     
     // Check if there are any workblocks from previous days that are still active
+    // (a later date is possible right after the day start hour was moved later)
     let mut stmt = conn.prepare(
         "SELECT date FROM workblocks 
-         WHERE status = 'active' AND date != ?1
+         WHERE status = 'active' AND date < ?1
          LIMIT 1"
     )?;
     
     let previous_date_result = stmt.query_row(params![today], |row| {
-        Ok(row.get::<_, String>(0)?)
+        row.get::<_, String>(0)
     });
     
     if let Ok(previous_date) = previous_date_result {
-        // Archive the previous day
-        archive_daily_data(app, &previous_date)?;
+        // Another caller (startup, start_workblock, ...) may already have archived this day; only
+        // the claim holder archives it, but stale active workblocks are closed either way
+        let archived = if claim_rollover(conn, &previous_date)? {
+            archive_claimed(&previous_date)?
+        } else {
+            false
+        };
         
         // Mark any active workblocks from previous day as completed
         conn.execute(
             "UPDATE workblocks 
-             SET status = 'completed', end_time = datetime('now')
-             WHERE status = 'active' AND date != ?1",
-            params![today],
+             SET status = 'completed', end_time = ?2
+             WHERE status = 'active' AND date < ?1",
+            params![today, timestamp_now()],
         )?;
         
-        return Ok(Some(previous_date));
+        return Ok(archived.then_some(previous_date));
     }
     
     // Check if we need to archive yesterday (if there are completed workblocks from yesterday)
-    let yesterday = (Local::now() - chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
     let mut stmt = conn.prepare(
         "SELECT COUNT(*) FROM workblocks 
          WHERE date = ?1 AND is_archived = 0"
@@ -521,14 +1956,60 @@ pub fn check_and_reset_daily(app: &AppHandle) -> Result<Option<String>> {
     
     let count: i32 = stmt.query_row(params![yesterday], |row| row.get(0))?;
     
-    if count > 0 {
-        archive_daily_data(app, &yesterday)?;
-        return Ok(Some(yesterday));
+    if count > 0 && claim_rollover(conn, yesterday)? {
+        let archived = archive_claimed(yesterday)?;
+        return Ok(archived.then(|| yesterday.to_string()));
     }
     
     Ok(None)
 }
 
+/// Archive `date`, unless nothing was recorded that day (e.g. the app was left running untouched).
+/// Quiet days only get their workblocks marked archived: no empty archive and no archive-updated event.
+/// Returns whether an archive was written.
+pub(crate) fn archive_unless_quiet(app: &AppHandle, conn: &Connection, date: &str) -> Result<bool> {
+    if query_day_has_recorded_data(conn, date)? {
+        archive_daily_data(app, date)?;
+        return Ok(true);
+    }
+    conn.execute(
+        "UPDATE workblocks SET is_archived = 1 WHERE date = ?1",
+        params![date],
+    )?;
+    println!("[DB] Skipped archiving {}: nothing was recorded", date);
+    Ok(false)
+}
+
+/// Whether any interval of a workblock dated `date` has recorded words
+pub fn query_day_has_recorded_data(conn: &Connection, date: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT EXISTS(
+            SELECT 1 FROM intervals i
+            JOIN workblocks w ON w.id = i.workblock_id
+            WHERE w.date = ?1 AND TRIM(COALESCE(i.words, '')) != ''
+        )",
+        params![date],
+        |row| row.get(0),
+    )?)
+}
+
+/// Claim the rollover of `date`. Returns false if it was already claimed, so it runs exactly once
+/// even when several callers check for a day transition at the same time.
+pub fn claim_rollover(conn: &Connection, date: &str) -> Result<bool> {
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO daily_rollovers (date, claimed_at) VALUES (?1, ?2)",
+        params![date, timestamp_now()],
+    )?;
+    Ok(inserted == 1)
+}
+
+/// Give up a claim after a failed rollover so the next check retries it
+pub fn release_rollover(conn: &Connection, date: &str) -> Result<()> {
+    conn.execute("DELETE FROM daily_rollovers WHERE date = ?1", params![date])?;
+    Ok(())
+}
+
 /// Archive daily data and generate visualization JSON
 pub fn archive_daily_data(app: &AppHandle, date: &str) -> Result<DailyArchive> {
     let conn = get_db_connection(app)?;
@@ -537,10 +2018,7 @@ pub fn archive_daily_data(app: &AppHandle, date: &str) -> Result<DailyArchive> {
     let workblocks = get_workblocks_by_date(app, date)?;
     
     if workblocks.is_empty() {
-        return Err(rusqlite::Error::SqliteFailure(
-            rusqlite::ffi::Error::new(1),
-            Some("No workblocks found for date".to_string()),
-        ));
+        return Err(Log15Error::NotFound("No workblocks found for date".to_string()));
     }
     
     // Mark all workblocks as archived
@@ -561,22 +2039,45 @@ pub fn archive_daily_data(app: &AppHandle, date: &str) -> Result<DailyArchive> {
     let visualization_json = serde_json::to_string(&visualization_data)
         .map_err(|e| rusqlite::Error::InvalidColumnType(0, format!("JSON serialization error: {}", e), rusqlite::types::Type::Text))?;
     
-    // Insert or update daily archive
+    // Goal attainment as of the end of that day
+    let goal_status = crate::stats::query_goal_status(&conn, date, false)?;
+    let goals_attained = (!goal_status.goals.is_empty()).then_some(goal_status.all_attained);
+    
+    // Insert or update daily archive (an AI summary written earlier survives re-archiving)
+    let archived_at = timestamp_now();
+    let ai_summary: Option<String> = conn
+        .query_row("SELECT ai_summary FROM daily_archives WHERE date = ?1", params![date], |row| row.get(0))
+        .optional()?
+        .flatten();
     conn.execute(
-        "INSERT OR REPLACE INTO daily_archives (date, total_workblocks, total_minutes, visualization_data, archived_at)
-         VALUES (?1, ?2, ?3, ?4, datetime('now'))",
-        params![date, total_workblocks, total_minutes, visualization_json],
+        "INSERT OR REPLACE INTO daily_archives (date, total_workblocks, total_minutes, visualization_data, archived_at, goal_streak, goals_attained, ai_summary)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![date, total_workblocks, total_minutes, visualization_json, archived_at, goal_status.current_streak, goals_attained, ai_summary],
     )?;
     
     let id = conn.last_insert_rowid();
     
+    // Let any open history/summary view refresh instead of showing stale data
+    let archive_updated = ArchiveUpdated {
+        date: date.to_string(),
+        total_workblocks,
+        total_minutes,
+    };
+    if let Ok(data) = serde_json::to_value(&archive_updated) {
+        crate::webhooks::dispatch(app, crate::webhooks::WebhookEvent::DayArchived, data);
+    }
+    let _ = emit_event(app, AppEvent::ArchiveUpdated(archive_updated));
+    
     Ok(DailyArchive {
         id: Some(id),
         date: date.to_string(),
         total_workblocks,
         total_minutes,
         visualization_data: Some(visualization_json),
-        archived_at: Some(Local::now().to_rfc3339()),
+        archived_at: Some(archived_at),
+        goal_streak: goal_status.current_streak,
+        goals_attained,
+        ai_summary,
     })
 }
 
@@ -584,7 +2085,7 @@ pub fn archive_daily_data(app: &AppHandle, date: &str) -> Result<DailyArchive> {
 pub fn get_all_archived_dates(app: &AppHandle) -> Result<Vec<DailyArchive>> {
     let conn = get_db_connection(app)?;
     let mut stmt = conn.prepare(
-        "SELECT id, date, total_workblocks, total_minutes, visualization_data, archived_at 
+        "SELECT id, date, total_workblocks, total_minutes, visualization_data, archived_at, goal_streak, goals_attained, ai_summary
          FROM daily_archives 
          ORDER BY date DESC"
     )?;
@@ -597,6 +2098,9 @@ pub fn get_all_archived_dates(app: &AppHandle) -> Result<Vec<DailyArchive>> {
             total_minutes: row.get(3)?,
             visualization_data: row.get(4)?,
             archived_at: row.get(5)?,
+            goal_streak: row.get::<_, Option<i32>>(6)?.unwrap_or(0),
+            goals_attained: row.get(7)?,
+            ai_summary: row.get(8)?,
         })
     })?;
     
@@ -611,8 +2115,12 @@ pub fn get_all_archived_dates(app: &AppHandle) -> Result<Vec<DailyArchive>> {
 /// Get archived day data
 pub fn get_archived_day(app: &AppHandle, date: &str) -> Result<Option<DailyArchive>> {
     let conn = get_db_connection(app)?;
+    query_archived_day(&conn, date)
+}
+
+pub fn query_archived_day(conn: &Connection, date: &str) -> Result<Option<DailyArchive>> {
     let mut stmt = conn.prepare(
-        "SELECT id, date, total_workblocks, total_minutes, visualization_data, archived_at
+        "SELECT id, date, total_workblocks, total_minutes, visualization_data, archived_at, goal_streak, goals_attained, ai_summary
          FROM daily_archives
          WHERE date = ?1"
     )?;
@@ -625,21 +2133,107 @@ pub fn get_archived_day(app: &AppHandle, date: &str) -> Result<Option<DailyArchi
             total_minutes: row.get(3)?,
             visualization_data: row.get(4)?,
             archived_at: row.get(5)?,
+            goal_streak: row.get::<_, Option<i32>>(6)?.unwrap_or(0),
+            goals_attained: row.get(7)?,
+            ai_summary: row.get(8)?,
         })
     });
     
     match archive_result {
         Ok(archive) => Ok(Some(archive)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e),
+        Err(e) => Err(e.into()),
     }
 }
 
-// ============================================================================
-// Visualization Data Generation
-// ============================================================================
+/// Largest page get_archive_history returns
+pub const MAX_ARCHIVE_PAGE_SIZE: i64 = 366;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// An archived day without its visualization blob
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ArchiveSummary {
+    pub date: String,
+    pub total_workblocks: i32,
+    pub total_minutes: i32,
+    pub archived_at: Option<String>,
+    pub goal_streak: i32,
+    pub goals_attained: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ArchiveHistoryPage {
+    pub archives: Vec<ArchiveSummary>,  // Newest first
+    pub total_count: i64,               // Archived days overall, for the scroll extent
+    pub has_more: bool,
+}
+
+/// Page of archived days, newest first. `limit` is clamped to 1..=MAX_ARCHIVE_PAGE_SIZE.
+pub fn query_archive_history(conn: &Connection, offset: i64, limit: i64) -> Result<ArchiveHistoryPage> {
+    let offset = offset.max(0);
+    let limit = limit.clamp(1, MAX_ARCHIVE_PAGE_SIZE);
+    let total_count: i64 = conn.query_row("SELECT COUNT(*) FROM daily_archives", [], |row| row.get(0))?;
+    
+    let mut stmt = conn.prepare(
+        "SELECT date, total_workblocks, total_minutes, archived_at, goal_streak, goals_attained
+         FROM daily_archives
+         ORDER BY date DESC
+         LIMIT ?1 OFFSET ?2"
+    )?;
+    let archives = stmt
+        .query_map(params![limit, offset], |row| {
+            Ok(ArchiveSummary {
+                date: row.get(0)?,
+                total_workblocks: row.get(1)?,
+                total_minutes: row.get(2)?,
+                archived_at: row.get(3)?,
+                goal_streak: row.get::<_, Option<i32>>(4)?.unwrap_or(0),
+                goals_attained: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    
+    let has_more = offset + (archives.len() as i64) < total_count;
+    Ok(ArchiveHistoryPage { archives, total_count, has_more })
+}
+
+pub fn get_archive_history(app: &AppHandle, offset: i64, limit: i64) -> Result<ArchiveHistoryPage> {
+    let conn = get_db_connection(app)?;
+    query_archive_history(&conn, offset, limit)
+}
+
+/// Stored visualization of an archived day, parsed. Kept as a JSON value because archives
+/// written by older versions may not match the current DailyVisualizationData shape.
+pub fn query_archive_visualization(conn: &Connection, date: &str) -> Result<Option<serde_json::Value>> {
+    let stored: Option<Option<String>> = match conn.query_row(
+        "SELECT visualization_data FROM daily_archives WHERE date = ?1",
+        params![date],
+        |row| row.get(0),
+    ) {
+        Ok(data) => Some(data),
+        Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => return Err(e.into()),
+    };
+    
+    stored
+        .flatten()
+        .map(|json| {
+            serde_json::from_str(&json).map_err(|e| {
+                rusqlite::Error::InvalidColumnType(0, format!("JSON parse error: {}", e), rusqlite::types::Type::Text).into()
+            })
+        })
+        .transpose()
+}
+
+pub fn get_archive_visualization(app: &AppHandle, date: &str) -> Result<Option<serde_json::Value>> {
+    let conn = get_db_connection(app)?;
+    query_archive_visualization(&conn, date)
+}
+
+// ============================================================================
+// Visualization Data Generation
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TimelineData {
     pub interval_number: i32,
     pub start_time: String,
@@ -647,30 +2241,109 @@ pub struct TimelineData {
     pub words: Option<String>,
     pub duration_minutes: i32,
     pub workblock_status: Option<String>, // "active", "completed", or "cancelled"
+    #[serde(default)]
+    pub attachments: Vec<IntervalAttachment>,
+    #[serde(default)]
+    pub project: Option<String>, // Effective project (interval override or workblock project)
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub provenance: IntervalProvenance,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ActivityData {
     pub words: String,
     pub total_minutes: i32,
     pub percentage: f64,
+    #[serde(default)]
+    pub average_energy: Option<f64>, // Mean rating of the activity's rated intervals
+    #[serde(default)]
+    pub color: Option<String>,  // From activity_colors; None in archives made before colors were stored
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A rated interval, for plotting energy over the day
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EnergyPoint {
+    pub workblock_id: i64,
+    pub interval_number: i32,
+    pub start_time: String,
+    pub energy: i32,
+}
+
+/// Mean of energy ratings, None when nothing was rated
+pub fn average_energy(ratings: &[i32]) -> Option<f64> {
+    if ratings.is_empty() {
+        return None;
+    }
+    Some(ratings.iter().sum::<i32>() as f64 / ratings.len() as f64)
+}
+
+/// Rated intervals in chronological order
+fn energy_timeline(workblock_id: i64, intervals: &[Interval]) -> Vec<EnergyPoint> {
+    intervals
+        .iter()
+        .filter_map(|interval| {
+            Some(EnergyPoint {
+                workblock_id,
+                interval_number: interval.interval_number,
+                start_time: interval.start_time.clone(),
+                energy: interval.energy?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WordFrequency {
     pub word: String,
     pub count: i32,
 }
 
+/// Count the single words in `phrases` for a word cloud, most frequent first (ties alphabetically).
+/// Words are split at anything but letters, digits, apostrophes and inner hyphens, lowercased, and
+/// dropped when they are stop words, numbers or shorter than the minimum length.
+pub fn count_words<'a>(
+    phrases: impl IntoIterator<Item = &'a str>,
+    settings: &crate::settings::WordCloudSettings,
+) -> Vec<WordFrequency> {
+    let stop_words: HashSet<&str> = settings.stop_words.iter().map(String::as_str).collect();
+    let mut counts: HashMap<String, i32> = HashMap::new();
+    for phrase in phrases {
+        let lower = phrase.to_lowercase();
+        let words = lower
+            .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '-'))
+            .map(|word| word.trim_matches(|c| c == '\'' || c == '-'));
+        for word in words {
+            if word.chars().count() < settings.min_length
+                || stop_words.contains(word)
+                || word.chars().all(|c| c.is_numeric() || c == '-')
+            {
+                continue;
+            }
+            *counts.entry(word.to_string()).or_insert(0) += 1;
+        }
+    }
+    let mut frequencies: Vec<WordFrequency> = counts.into_iter().map(|(word, count)| WordFrequency { word, count }).collect();
+    frequencies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+    frequencies
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WorkblockVisualization {
     pub id: i64,
     pub timeline_data: Vec<TimelineData>,
     pub activity_data: Vec<ActivityData>,
-    pub word_frequency: Vec<WordFrequency>,
+    pub word_frequency: Vec<WordFrequency>, // Whole phrases (one per activity), counted per interval
+    #[serde(default)]
+    pub word_counts: Vec<WordFrequency>,    // Single words for a word cloud, without stop words
+    #[serde(default)]
+    pub apps_used: Vec<crate::activity_capture::AppUsage>, // Empty unless activity capture is enabled
+    #[serde(default)]
+    pub energy_timeline: Vec<EnergyPoint>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AggregateTimelineData {
     pub workblock_id: i64,
     pub interval_number: i32,
@@ -679,9 +2352,27 @@ pub struct AggregateTimelineData {
     pub words: Option<String>,
     pub duration_minutes: i32,
     pub workblock_status: Option<String>, // "active", "completed", or "cancelled"
+    #[serde(default)]
+    pub attachments: Vec<IntervalAttachment>,
+    #[serde(default)]
+    pub project: Option<String>, // Effective project (interval override or workblock project)
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub workblock_title: Option<String>,
+    #[serde(default)]
+    pub provenance: IntervalProvenance,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectData {
+    pub project: Option<String>, // None = intervals without any project
+    pub total_minutes: i32,
+    pub interval_count: i32,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WorkblockBoundary {
     pub id: i64,
     pub start_time: String,
@@ -689,7 +2380,7 @@ pub struct WorkblockBoundary {
     pub status: String, // "active", "completed", or "cancelled"
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DailyAggregate {
     pub total_workblocks: i32,
     pub total_minutes: i32,
@@ -697,6 +2388,88 @@ pub struct DailyAggregate {
     pub activity_data: Vec<ActivityData>,
     pub word_frequency: Vec<WordFrequency>,
     pub workblock_boundaries: Vec<WorkblockBoundary>,
+    #[serde(default)]
+    pub is_truncated: bool, // true when timeline_data was cut off by TimelineLimits::max_entries
+    #[serde(default)]
+    pub project_data: Vec<ProjectData>, // Time per interval-level project
+    #[serde(default)]
+    pub deep_work_sessions: Vec<DeepWorkSession>,
+    #[serde(default)]
+    pub over_daily_limit: bool, // Tracked time passed the wellbeing limit (only when the guard is on)
+    #[serde(default)]
+    pub energy_timeline: Vec<EnergyPoint>,
+    #[serde(default)]
+    pub after_the_fact_minutes: i32, // Timeline minutes that were backfilled, imported or edited
+    #[serde(default)]
+    pub away_causes: Vec<AwayCauseCount>, // Why intervals were recorded as away, most minutes first
+}
+
+/// Optional limits applied to timeline payloads so large days stay cheap to render
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimelineLimits {
+    pub min_segment_minutes: Option<i32>, // Merge segments shorter than this into the previous one
+    pub max_entries: Option<usize>,       // Cap on timeline entries returned
+}
+
+impl DailyAggregate {
+    /// Downsample and cap `timeline_data` in place
+    pub fn apply_limits(&mut self, limits: &TimelineLimits) {
+        let timeline = std::mem::take(&mut self.timeline_data);
+        let (timeline, is_truncated) = limit_timeline(timeline, limits);
+        self.timeline_data = timeline;
+        self.is_truncated = self.is_truncated || is_truncated;
+    }
+}
+
+/// Merge short segments and cap the number of entries.
+/// Expects `timeline` in chronological order; returns the limited timeline and whether it was truncated.
+pub fn limit_timeline(
+    timeline: Vec<AggregateTimelineData>,
+    limits: &TimelineLimits,
+) -> (Vec<AggregateTimelineData>, bool) {
+    let mut result: Vec<AggregateTimelineData> = Vec::with_capacity(timeline.len());
+
+    match limits.min_segment_minutes {
+        Some(min_minutes) if min_minutes > 0 => {
+            for entry in timeline {
+                let previous = result
+                    .last_mut()
+                    .filter(|prev| prev.workblock_id == entry.workblock_id);
+                match previous {
+                    // Short segments are folded into the preceding segment of the same workblock
+                    Some(prev) if entry.duration_minutes < min_minutes || prev.duration_minutes < min_minutes => {
+                        // The merged segment keeps the words of its longer part
+                        if entry.duration_minutes > prev.duration_minutes {
+                            prev.words = entry.words;
+                            prev.project = entry.project;
+                            prev.notes = entry.notes;
+                        }
+                        // Only a segment made entirely of live answers stays live
+                        if prev.provenance == IntervalProvenance::Live {
+                            prev.provenance = entry.provenance;
+                        }
+                        prev.duration_minutes += entry.duration_minutes;
+                        prev.end_time = entry.end_time;
+                        if entry.workblock_status.is_some() {
+                            prev.workblock_status = entry.workblock_status;
+                        }
+                    }
+                    _ => result.push(entry),
+                }
+            }
+        }
+        _ => result = timeline,
+    }
+
+    let mut is_truncated = false;
+    if let Some(max_entries) = limits.max_entries {
+        if result.len() > max_entries {
+            result.truncate(max_entries);
+            is_truncated = true;
+        }
+    }
+
+    (result, is_truncated)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -705,263 +2478,369 @@ pub struct DailyVisualizationData {
     pub daily_aggregate: DailyAggregate,
 }
 
-/// Generate visualization data for a single workblock
-pub fn generate_workblock_visualization(
-    app: &AppHandle,
-    workblock_id: i64,
-) -> Result<WorkblockVisualization> {
-    let workblock = get_workblock_by_id(app, workblock_id)?;
-    let mut intervals = get_intervals_by_workblock(app, workblock_id)?;
-    let is_cancelled = workblock.status == WorkblockStatus::Cancelled;
-    
-    // If cancelled, filter out intervals that start after cancellation time
-    // and identify the last interval to mark as cancelled
-    let cancellation_end_time = if is_cancelled {
-        workblock.end_time.as_ref().and_then(|et| {
-            DateTime::parse_from_rfc3339(et).ok()
-        })
-    } else {
-        None
-    };
-    
-    if let Some(cancel_time) = cancellation_end_time {
-        // Filter out intervals that start after cancellation
-        intervals.retain(|interval| {
-            if let Ok(start_time) = DateTime::parse_from_rfc3339(&interval.start_time) {
-                start_time <= cancel_time
-            } else {
-                true // Keep if we can't parse (shouldn't happen)
-            }
-        });
+/// A day's (or one workblock's) intervals with everything the visualizations derive from them,
+/// loaded once so the per-workblock and aggregate views don't query intervals again
+pub struct VisualizationSource {
+    pub workblocks: Vec<Workblock>,                          // In chronological order
+    intervals: HashMap<i64, Vec<Interval>>,                  // By workblock; cancelled tails already dropped
+    splits: HashMap<i64, Vec<ActivitySplit>>,                // By interval
+    attachments: HashMap<i64, Vec<IntervalAttachment>>,      // By interval
+    aliases: HashMap<String, String>,
+    word_cloud: crate::settings::WordCloudSettings,
+}
+
+impl VisualizationSource {
+    /// Load every interval of `date` with one query per table
+    pub fn load_day(conn: &Connection, date: &str) -> Result<Self> {
+        let workblocks = query_workblocks_by_date(conn, date)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT i.id, i.workblock_id, i.interval_number, i.start_time, i.end_time, i.words, i.status, i.recorded_at,
+                    i.project, i.category, i.energy, i.notes, i.provenance, i.away_cause
+             FROM intervals i
+             JOIN workblocks w ON w.id = i.workblock_id
+             WHERE w.date = ?1
+             ORDER BY i.workblock_id, i.interval_number ASC"
+        )?;
+        let interval_iter = stmt.query_map(params![date], |row| {
+            Ok(Interval {
+                id: Some(row.get(0)?),
+                workblock_id: row.get(1)?,
+                interval_number: row.get(2)?,
+                start_time: row.get(3)?,
+                end_time: row.get(4)?,
+                words: row.get(5)?,
+                status: IntervalStatus::from_str(&row.get::<_, String>(6)?),
+                recorded_at: row.get(7)?,
+                project: row.get(8)?,
+                category: row.get(9)?,
+                energy: row.get(10)?,
+                notes: row.get(11)?,
+                provenance: IntervalProvenance::from_str(&row.get::<_, String>(12)?),
+                away_cause: row.get::<_, Option<String>>(13)?.and_then(|c| AwayCause::from_str(&c)),
+            })
+        })?;
+        let mut intervals: HashMap<i64, Vec<Interval>> = HashMap::new();
+        for interval in interval_iter {
+            let interval = interval?;
+            intervals.entry(interval.workblock_id).or_default().push(interval);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT a.interval_id, a.words, a.percentage
+             FROM interval_activities a
+             JOIN intervals i ON i.id = a.interval_id
+             JOIN workblocks w ON w.id = i.workblock_id
+             WHERE w.date = ?1
+             ORDER BY a.interval_id, a.position",
+        )?;
+        let rows = stmt.query_map(params![date], |row| {
+            Ok((row.get::<_, i64>(0)?, ActivitySplit { words: row.get(1)?, percentage: row.get(2)? }))
+        })?;
+        let mut splits: HashMap<i64, Vec<ActivitySplit>> = HashMap::new();
+        for row in rows {
+            let (interval_id, split) = row?;
+            splits.entry(interval_id).or_default().push(split);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT a.id, a.interval_id, a.file_name, a.stored_path, a.size_bytes, a.created_at
+             FROM interval_attachments a
+             JOIN intervals i ON i.id = a.interval_id
+             JOIN workblocks w ON w.id = i.workblock_id
+             WHERE w.date = ?1
+             ORDER BY a.id ASC"
+        )?;
+        let attachment_iter = stmt.query_map(params![date], |row| {
+            Ok(IntervalAttachment {
+                id: Some(row.get(0)?),
+                interval_id: row.get(1)?,
+                file_name: row.get(2)?,
+                stored_path: row.get(3)?,
+                size_bytes: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?;
+        let mut attachments: HashMap<i64, Vec<IntervalAttachment>> = HashMap::new();
+        for attachment in attachment_iter {
+            let attachment = attachment?;
+            attachments.entry(attachment.interval_id).or_default().push(attachment);
+        }
+
+        Self::new(conn, workblocks, intervals, splits, attachments)
     }
-    
-    // Find the last interval number to mark as cancelled (only for cancelled workblocks)
-    let last_interval_number = if is_cancelled && !intervals.is_empty() {
-        intervals.iter().map(|i| i.interval_number).max()
-    } else {
-        None
-    };
-    
-    // Generate timeline data
-    let timeline_data: Vec<TimelineData> = intervals
-        .iter()
-        .map(|interval| {
-            let duration = if let Some(end_time) = &interval.end_time {
-                let start = DateTime::parse_from_rfc3339(&interval.start_time).unwrap();
-                let end = DateTime::parse_from_rfc3339(end_time).unwrap();
-                (end - start).num_minutes() as i32
-            } else {
-                15 // Default 15 minutes if not ended
-            };
-            
-            // Only mark as cancelled if this is the last interval and workblock is cancelled
-            let status = if is_cancelled && last_interval_number == Some(interval.interval_number) {
-                Some("cancelled".to_string())
-            } else {
-                None
+
+    /// Load a single workblock's intervals
+    pub fn load_workblock(conn: &Connection, workblock_id: i64) -> Result<Self> {
+        let workblock = query_workblock_by_id(conn, workblock_id)?;
+        let intervals = HashMap::from([(workblock_id, query_intervals_by_workblock(conn, workblock_id)?)]);
+        let splits = query_activity_splits_by_workblock(conn, workblock_id)?;
+        let attachments = query_attachments_by_workblock(conn, workblock_id)?;
+        Self::new(conn, vec![workblock], intervals, splits, attachments)
+    }
+
+    fn new(
+        conn: &Connection,
+        workblocks: Vec<Workblock>,
+        mut intervals: HashMap<i64, Vec<Interval>>,
+        splits: HashMap<i64, Vec<ActivitySplit>>,
+        attachments: HashMap<i64, Vec<IntervalAttachment>>,
+    ) -> Result<Self> {
+        // A cancelled workblock's intervals that start after the cancellation never happened
+        for workblock in &workblocks {
+            let cancel_time = match (&workblock.status, &workblock.end_time) {
+                (WorkblockStatus::Cancelled, Some(end_time)) => DateTime::parse_from_rfc3339(end_time).ok(),
+                _ => None,
             };
-            
-            TimelineData {
-                interval_number: interval.interval_number,
-                start_time: interval.start_time.clone(),
-                end_time: interval.end_time.clone(),
-                words: interval.words.clone(),
-                duration_minutes: duration,
-                workblock_status: status,
+            if let (Some(cancel_time), Some(intervals)) = (cancel_time, workblock.id.and_then(|id| intervals.get_mut(&id))) {
+                intervals.retain(|interval| {
+                    // Keep if we can't parse (shouldn't happen)
+                    DateTime::parse_from_rfc3339(&interval.start_time).map(|start| start <= cancel_time).unwrap_or(true)
+                });
             }
+        }
+        Ok(Self {
+            workblocks,
+            intervals,
+            splits,
+            attachments,
+            aliases: query_alias_map(conn)?,
+            word_cloud: crate::settings::query_word_cloud_settings(conn),
         })
-        .collect();
-    
-    // Generate activity data (group by words) - only from intervals that were actually used
-    let mut activity_map: HashMap<String, i32> = HashMap::new();
-    for interval in &intervals {
-        if let Some(words) = &interval.words {
-            let words_lower = words.to_lowercase().trim().to_string();
-            if !words_lower.is_empty() {
-                let duration = if let Some(end_time) = &interval.end_time {
-                    let start = DateTime::parse_from_rfc3339(&interval.start_time).unwrap_or_default();
-                    let end = DateTime::parse_from_rfc3339(end_time).unwrap_or_default();
-                    (end - start).num_minutes() as i32
-                } else {
-                    15 // Default 15 minutes if not ended
-                };
-                *activity_map.entry(words_lower).or_insert(0) += duration;
-            }
+    }
+
+    fn intervals_of(&self, workblock_id: i64) -> &[Interval] {
+        self.intervals.get(&workblock_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    fn attachments_of(&self, interval: &Interval) -> Vec<IntervalAttachment> {
+        interval.id.and_then(|id| self.attachments.get(&id)).cloned().unwrap_or_default()
+    }
+}
+
+/// Minutes an interval covers; 15 while it has not ended
+fn interval_duration_minutes(interval: &Interval) -> i32 {
+    match &interval.end_time {
+        Some(end_time) => {
+            let start = DateTime::parse_from_rfc3339(&interval.start_time).unwrap_or_default();
+            let end = DateTime::parse_from_rfc3339(end_time).unwrap_or_default();
+            (end - start).num_minutes() as i32
         }
+        None => 15, // Default 15 minutes if not ended
     }
-    
-    let total_minutes: i32 = activity_map.values().sum();
-    let activity_data: Vec<ActivityData> = activity_map
-        .into_iter()
-        .map(|(words, minutes)| {
-            let percentage = if total_minutes > 0 {
-                (minutes as f64 / total_minutes as f64) * 100.0
-            } else {
-                0.0
-            };
-            ActivityData {
-                words,
-                total_minutes: minutes,
-                percentage,
+}
+
+/// The interval a cancelled workblock stopped in, marked "cancelled" on the timeline
+fn cancelled_interval_number(workblock: &Workblock, intervals: &[Interval]) -> Option<i32> {
+    if workblock.status != WorkblockStatus::Cancelled {
+        return None;
+    }
+    intervals.iter().map(|i| i.interval_number).max()
+}
+
+fn timeline_status(cancelled_number: Option<i32>, interval: &Interval) -> Option<String> {
+    (cancelled_number == Some(interval.interval_number)).then(|| "cancelled".to_string())
+}
+
+/// Activity minutes, activity frequency (entire phrase as one activity, each activity of a split once)
+/// and energy ratings of `intervals` - only the duration actually used, shared out over a split
+#[derive(Default)]
+struct ActivityTotals {
+    minutes: HashMap<String, i32>,
+    frequency: HashMap<String, i32>,
+    energy: HashMap<String, Vec<i32>>,
+}
+
+impl ActivityTotals {
+    fn add(&mut self, interval: &Interval, source: &VisualizationSource) {
+        let duration = interval_duration_minutes(interval);
+        for (activity, minutes) in interval_activity_minutes(interval, &source.splits, duration, &source.aliases) {
+            if let Some(energy) = interval.energy {
+                self.energy.entry(activity.clone()).or_default().push(energy);
             }
+            *self.minutes.entry(activity.clone()).or_insert(0) += minutes;
+            *self.frequency.entry(activity).or_insert(0) += 1;
+        }
+    }
+
+    fn into_views(self, conn: &Connection) -> Result<(Vec<ActivityData>, Vec<WordFrequency>)> {
+        let total_minutes: i32 = self.minutes.values().sum();
+        let energy = self.energy;
+        let mut activity_data: Vec<ActivityData> = self
+            .minutes
+            .into_iter()
+            .map(|(words, minutes)| {
+                let percentage = if total_minutes > 0 {
+                    (minutes as f64 / total_minutes as f64) * 100.0
+                } else {
+                    0.0
+                };
+                let average_energy = energy.get(&words).and_then(|r| average_energy(r));
+                ActivityData {
+                    words,
+                    total_minutes: minutes,
+                    percentage,
+                    average_energy,
+                    color: None,
+                }
+            })
+            .collect();
+        fill_activity_colors(conn, &mut activity_data)?;
+        // A stable order, so rebuilding an unchanged day gives the same data (see cache.rs)
+        activity_data.sort_by(|a, b| b.total_minutes.cmp(&a.total_minutes).then_with(|| a.words.cmp(&b.words)));
+
+        let mut word_frequency: Vec<WordFrequency> = self
+            .frequency
+            .into_iter()
+            .map(|(word, count)| WordFrequency { word, count })
+            .collect();
+        word_frequency.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+        Ok((activity_data, word_frequency))
+    }
+}
+
+/// Generate visualization data for a single workblock
+pub fn generate_workblock_visualization(
+    app: &AppHandle,
+    workblock_id: i64,
+) -> Result<WorkblockVisualization> {
+    let conn = get_db_connection(app)?;
+    let source = VisualizationSource::load_workblock(&conn, workblock_id)?;
+    build_workblock_visualization(&conn, &source, &source.workblocks[0])
+}
+
+/// Build one workblock's view from already loaded intervals
+pub fn build_workblock_visualization(
+    conn: &Connection,
+    source: &VisualizationSource,
+    workblock: &Workblock,
+) -> Result<WorkblockVisualization> {
+    let workblock_id = workblock.id.unwrap_or_default();
+    let intervals = source.intervals_of(workblock_id);
+    let cancelled_number = cancelled_interval_number(workblock, intervals);
+
+    let timeline_data: Vec<TimelineData> = intervals
+        .iter()
+        .map(|interval| TimelineData {
+            interval_number: interval.interval_number,
+            start_time: interval.start_time.clone(),
+            end_time: interval.end_time.clone(),
+            words: interval.words.clone(),
+            duration_minutes: interval_duration_minutes(interval),
+            workblock_status: timeline_status(cancelled_number, interval),
+            attachments: source.attachments_of(interval),
+            project: effective_project(interval, workblock),
+            notes: interval.notes.clone(),
+            provenance: interval.provenance,
         })
         .collect();
-    
-    // Generate activity frequency (count entire phrase as one activity)
-    let mut word_freq_map: HashMap<String, i32> = HashMap::new();
-    for interval in &intervals {
-        if let Some(words) = &interval.words {
-            // Count entire phrase as one activity (not split by words)
-            let words_lower = words.to_lowercase().trim().to_string();
-            if !words_lower.is_empty() {
-                *word_freq_map.entry(words_lower).or_insert(0) += 1;
-            }
-        }
+
+    let mut totals = ActivityTotals::default();
+    for interval in intervals {
+        totals.add(interval, source);
     }
-    
-    let word_frequency: Vec<WordFrequency> = word_freq_map
-        .into_iter()
-        .map(|(word, count)| WordFrequency { word, count })
+    let (activity_data, word_frequency) = totals.into_views(conn)?;
+
+    // Single words, from the same canonical phrases
+    let phrases: Vec<String> = intervals
+        .iter()
+        .filter_map(|interval| interval.words.as_deref())
+        .map(|words| normalize_activity(words, &source.aliases))
         .collect();
-    
+    let word_counts = count_words(phrases.iter().map(String::as_str), &source.word_cloud);
+
+    let apps_used = crate::activity_capture::query_workblock_app_usage(conn, workblock_id)?;
+
     Ok(WorkblockVisualization {
         id: workblock_id,
         timeline_data,
         activity_data,
         word_frequency,
+        word_counts,
+        apps_used,
+        energy_timeline: energy_timeline(workblock_id, intervals),
     })
 }
 
 /// Generate daily aggregate visualization data
 pub fn generate_daily_aggregate(app: &AppHandle, date: &str) -> Result<DailyAggregate> {
-    let workblocks = get_workblocks_by_date(app, date)?;
-    
+    let conn = get_db_connection(app)?;
+    let source = VisualizationSource::load_day(&conn, date)?;
+    build_daily_aggregate(&conn, &source, date)
+}
+
+/// Build the whole day's view from already loaded intervals
+pub fn build_daily_aggregate(conn: &Connection, source: &VisualizationSource, date: &str) -> Result<DailyAggregate> {
+    let workblocks = &source.workblocks;
     let mut all_timeline_data: Vec<AggregateTimelineData> = Vec::new();
-    let mut activity_map: HashMap<String, i32> = HashMap::new();
-    let mut word_freq_map: HashMap<String, i32> = HashMap::new();
-    
-    for workblock in &workblocks {
-        let mut intervals = get_intervals_by_workblock(app, workblock.id.unwrap())?;
-        let is_cancelled = workblock.status == WorkblockStatus::Cancelled;
-        
-        // If cancelled, filter out intervals that start after cancellation time
-        let cancellation_end_time = if is_cancelled {
-            workblock.end_time.as_ref().and_then(|et| {
-                DateTime::parse_from_rfc3339(et).ok()
-            })
-        } else {
-            None
-        };
-        
-        if let Some(cancel_time) = cancellation_end_time {
-            // Filter out intervals that start after cancellation
-            intervals.retain(|interval| {
-                if let Ok(start_time) = DateTime::parse_from_rfc3339(&interval.start_time) {
-                    start_time <= cancel_time
-                } else {
-                    true // Keep if we can't parse (shouldn't happen)
-                }
-            });
-        }
-        
-        // Find the last interval number to mark as cancelled (only for cancelled workblocks)
-        let last_interval_number = if is_cancelled && !intervals.is_empty() {
-            intervals.iter().map(|i| i.interval_number).max()
-        } else {
-            None
-        };
-        
-        // Add to timeline
-        for interval in &intervals {
-            let duration = if let Some(end_time) = &interval.end_time {
-                let start = DateTime::parse_from_rfc3339(&interval.start_time).unwrap();
-                let end = DateTime::parse_from_rfc3339(end_time).unwrap();
-                (end - start).num_minutes() as i32
-            } else {
-                15
-            };
-            
-            // Only mark as cancelled if this is the last interval and workblock is cancelled
-            let status = if is_cancelled && last_interval_number == Some(interval.interval_number) {
-                Some("cancelled".to_string())
-            } else {
-                None
-            };
-            
+    let mut totals = ActivityTotals::default();
+    let mut all_energy_timeline: Vec<EnergyPoint> = Vec::new();
+    let mut deep_work_sessions: Vec<DeepWorkSession> = Vec::new();
+
+    for workblock in workblocks {
+        let workblock_id = workblock.id.unwrap_or_default();
+        let intervals = source.intervals_of(workblock_id);
+        let cancelled_number = cancelled_interval_number(workblock, intervals);
+        deep_work_sessions.extend(detect_deep_work(workblock_id, intervals, &source.aliases));
+        all_energy_timeline.extend(energy_timeline(workblock_id, intervals));
+
+        for interval in intervals {
             all_timeline_data.push(AggregateTimelineData {
-                workblock_id: workblock.id.unwrap(),
+                workblock_id,
                 interval_number: interval.interval_number,
                 start_time: interval.start_time.clone(),
                 end_time: interval.end_time.clone(),
                 words: interval.words.clone(),
-                duration_minutes: duration,
-                workblock_status: status,
+                duration_minutes: interval_duration_minutes(interval),
+                workblock_status: timeline_status(cancelled_number, interval),
+                attachments: source.attachments_of(interval),
+                project: effective_project(interval, workblock),
+                notes: interval.notes.clone(),
+                workblock_title: workblock.title.clone(),
+                provenance: interval.provenance,
             });
-            
-            // Add to activity map - only count duration that was actually used
-            if let Some(words) = &interval.words {
-                let words_lower = words.to_lowercase().trim().to_string();
-                if !words_lower.is_empty() {
-                    *activity_map.entry(words_lower).or_insert(0) += duration;
-                }
-            }
-            
-            // Add to activity frequency (count entire phrase as one activity)
-            if let Some(words) = &interval.words {
-                let words_lower = words.to_lowercase().trim().to_string();
-                if !words_lower.is_empty() {
-                    *word_freq_map.entry(words_lower).or_insert(0) += 1;
-                }
-            }
+            totals.add(interval, source);
         }
     }
-    
+
     // Sort timeline chronologically
     all_timeline_data.sort_by(|a, b| a.start_time.cmp(&b.start_time));
-    
-    // Calculate activity percentages
-    let total_minutes: i32 = activity_map.values().sum();
-    let activity_data: Vec<ActivityData> = activity_map
-        .into_iter()
-        .map(|(words, minutes)| {
-            let percentage = if total_minutes > 0 {
-                (minutes as f64 / total_minutes as f64) * 100.0
-            } else {
-                0.0
-            };
-            ActivityData {
-                words,
-                total_minutes: minutes,
-                percentage,
-            }
-        })
-        .collect();
-    
-    let word_frequency: Vec<WordFrequency> = word_freq_map
-        .into_iter()
-        .map(|(word, count)| WordFrequency { word, count })
-        .collect();
-    
+    all_energy_timeline.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+
+    let project_data = rollup_by_project(&all_timeline_data);
+    let after_the_fact_minutes = all_timeline_data
+        .iter()
+        .filter(|entry| entry.provenance != IntervalProvenance::Live)
+        .map(|entry| entry.duration_minutes)
+        .sum();
+
+    let (activity_data, word_frequency) = totals.into_views(conn)?;
+
+    let next_date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| (d + chrono::Duration::days(1)).format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let away_causes = query_away_causes(conn, date, &next_date)?;
+
     let total_workblocks = workblocks.len() as i32;
     let aggregate_total_minutes: i32 = workblocks
         .iter()
         .map(|wb| wb.duration_minutes.unwrap_or(0))
         .sum();
-    
+
     // Generate workblock boundaries (sorted by start_time to match chronological order)
     let mut workblock_boundaries: Vec<WorkblockBoundary> = workblocks
         .iter()
         .map(|wb| WorkblockBoundary {
-            id: wb.id.unwrap(),
+            id: wb.id.unwrap_or_default(),
             start_time: wb.start_time.clone(),
             end_time: wb.end_time.clone(),
             status: wb.status.as_str().to_string(),
         })
         .collect();
-    
+
     // Sort by start_time to ensure chronological order
     workblock_boundaries.sort_by(|a, b| a.start_time.cmp(&b.start_time));
-    
+
     Ok(DailyAggregate {
         total_workblocks,
         total_minutes: aggregate_total_minutes,
@@ -969,6 +2848,13 @@ pub fn generate_daily_aggregate(app: &AppHandle, date: &str) -> Result<DailyAggr
         activity_data,
         word_frequency,
         workblock_boundaries,
+        is_truncated: false,
+        project_data,
+        deep_work_sessions,
+        over_daily_limit: crate::settings::query_wellbeing_settings(conn).exceeded_by(aggregate_total_minutes),
+        energy_timeline: all_energy_timeline,
+        after_the_fact_minutes,
+        away_causes,
     })
 }
 
@@ -977,20 +2863,1242 @@ pub fn generate_daily_visualization_data(
     app: &AppHandle,
     date: &str,
 ) -> Result<DailyVisualizationData> {
-    let workblocks = get_workblocks_by_date(app, date)?;
+    let conn = get_db_connection(app)?;
+    query_daily_visualization_data(&conn, date)
+}
+
+/// Every view of a day, built from a single load of its intervals
+pub fn query_daily_visualization_data(conn: &Connection, date: &str) -> Result<DailyVisualizationData> {
+    let source = VisualizationSource::load_day(conn, date)?;
+    let workblocks = source
+        .workblocks
+        .iter()
+        .filter(|workblock| workblock.id.is_some())
+        .map(|workblock| build_workblock_visualization(conn, &source, workblock))
+        .collect::<Result<Vec<_>>>()?;
+    let daily_aggregate = build_daily_aggregate(conn, &source, date)?;
+
+    Ok(DailyVisualizationData {
+        workblocks,
+        daily_aggregate,
+    })
+}
+
+// ============================================================================
+// Monthly Summary
+// ============================================================================
+
+/// Number of activities returned in `MonthlySummary::top_activities`
+const MONTHLY_TOP_ACTIVITIES: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeatmapDay {
+    pub date: String,  // YYYY-MM-DD format
+    pub total_minutes: i32,
+    pub total_workblocks: i32,
+    #[serde(default)]
+    pub untracked: bool,  // Past day with nothing recorded (no workblocks, or only idle ones)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonthlySummary {
+    pub year: i32,
+    pub month: u32,
+    pub heatmap: Vec<HeatmapDay>,  // One entry per calendar day, including empty days
+    pub total_workblocks: i32,
+    pub total_minutes: i32,
+    pub top_activities: Vec<WordFrequency>,  // Most frequent activities, descending
+    pub average_workblock_minutes: f64,
+}
+
+/// Generate the monthly summary (calendar heatmap + totals) for a month
+pub fn generate_monthly_summary(app: &AppHandle, year: i32, month: u32) -> Result<MonthlySummary> {
+    let conn = get_db_connection(app)?;
+    let key = format!("{}{:04}-{:02}", crate::computed_cache::MONTHLY_SUMMARY_PREFIX, year, month);
+    crate::computed_cache::cached_or_compute(&conn, &key, Some(crate::computed_cache::DEFAULT_CACHE_TTL_SECONDS), |conn| {
+        query_monthly_summary(conn, year, month)
+    })
+}
+
+/// Build the monthly summary from an open connection
+pub fn query_monthly_summary(conn: &Connection, year: i32, month: u32) -> Result<MonthlySummary> {
+    let first_day = chrono::NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| {
+        Log15Error::InvalidInput(format!("Invalid month: {}-{}", year, month))
+    })?;
+    let first_of_next = first_day
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(first_day);
+    let start_date = first_day.format("%Y-%m-%d").to_string();
+    let end_date = first_of_next.format("%Y-%m-%d").to_string();
+
+    // Minutes and workblocks per day
+    let mut stmt = conn.prepare(
+        "SELECT date, COUNT(*), COALESCE(SUM(duration_minutes), 0)
+         FROM workblocks
+         WHERE date >= ?1 AND date < ?2
+         GROUP BY date"
+    )?;
+    let mut per_day: HashMap<String, (i32, i32)> = HashMap::new();
+    let rows = stmt.query_map(params![start_date, end_date], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)?, row.get::<_, i32>(2)?))
+    })?;
+    for row in rows {
+        let (date, workblocks, minutes) = row?;
+        per_day.insert(date, (workblocks, minutes));
+    }
+
+    // Days with at least one recorded interval
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT w.date
+         FROM workblocks w
+         JOIN intervals i ON i.workblock_id = w.id
+         WHERE w.date >= ?1 AND w.date < ?2 AND TRIM(COALESCE(i.words, '')) != ''"
+    )?;
+    let recorded_days = stmt
+        .query_map(params![start_date, end_date], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<HashSet<String>>>()?;
+    let today = get_today_date();
+
+    // Fill every day of the month so the heatmap has no gaps
+    let heatmap: Vec<HeatmapDay> = first_day
+        .iter_days()
+        .take_while(|day| *day < first_of_next)
+        .map(|day| {
+            let date = day.format("%Y-%m-%d").to_string();
+            let (total_workblocks, total_minutes) = per_day.get(&date).copied().unwrap_or((0, 0));
+            HeatmapDay {
+                untracked: date < today && !recorded_days.contains(&date),
+                date,
+                total_minutes,
+                total_workblocks,
+            }
+        })
+        .collect();
+
+    let total_workblocks: i32 = heatmap.iter().map(|d| d.total_workblocks).sum();
+    let total_minutes: i32 = heatmap.iter().map(|d| d.total_minutes).sum();
+    let average_workblock_minutes = if total_workblocks > 0 {
+        total_minutes as f64 / total_workblocks as f64
+    } else {
+        0.0
+    };
+
+    let top_activities = query_top_activities(conn, &start_date, &end_date, MONTHLY_TOP_ACTIVITIES)?;
+
+    Ok(MonthlySummary {
+        year,
+        month,
+        heatmap,
+        total_workblocks,
+        total_minutes,
+        top_activities,
+        average_workblock_minutes,
+    })
+}
+
+/// Most frequent activities for workblocks dated in [start_date, end_date), descending.
+/// Counts the entire phrase as one activity, same as daily aggregates.
+fn query_top_activities(conn: &Connection, start_date: &str, end_date: &str, limit: usize) -> Result<Vec<WordFrequency>> {
+    let aliases = query_alias_map(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT i.words
+         FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE w.date >= ?1 AND w.date < ?2 AND i.words IS NOT NULL"
+    )?;
+    let mut word_freq_map: HashMap<String, i32> = HashMap::new();
+    let rows = stmt.query_map(params![start_date, end_date], |row| row.get::<_, String>(0))?;
+    for words in rows {
+        let words_lower = normalize_activity(&words?, &aliases);
+        if !words_lower.is_empty() {
+            *word_freq_map.entry(words_lower).or_insert(0) += 1;
+        }
+    }
+
+    let mut top_activities: Vec<WordFrequency> = word_freq_map
+        .into_iter()
+        .map(|(word, count)| WordFrequency { word, count })
+        .collect();
+    top_activities.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+    top_activities.truncate(limit);
+    Ok(top_activities)
+}
+
+// ============================================================================
+// Deep Work
+// ============================================================================
+
+/// Minimum number of consecutive same-activity intervals that count as a deep work session
+pub const DEEP_WORK_MIN_INTERVALS: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeepWorkSession {
+    pub workblock_id: i64,
+    pub activity: String,  // Normalized activity shared by every interval in the session
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub interval_count: i32,
+    pub duration_minutes: i32,
+}
+
+/// Find deep work sessions in one workblock: runs of at least DEEP_WORK_MIN_INTERVALS
+/// consecutive recorded intervals with the same activity. Auto-away, unanswered or
+/// missing intervals break a run. Expects intervals ordered by interval_number.
+pub fn detect_deep_work(workblock_id: i64, intervals: &[Interval], aliases: &HashMap<String, String>) -> Vec<DeepWorkSession> {
+    let mut sessions = Vec::new();
+    let mut run: Vec<&Interval> = Vec::new();
+    let mut run_activity = String::new();
     
-    let mut workblock_visualizations = Vec::new();
-    for workblock in &workblocks {
-        if let Some(id) = workblock.id {
-            let viz = generate_workblock_visualization(app, id)?;
-            workblock_visualizations.push(viz);
+    let mut close_run = |run: &mut Vec<&Interval>, activity: &str| {
+        if run.len() >= DEEP_WORK_MIN_INTERVALS {
+            let duration_minutes = run
+                .iter()
+                .map(|interval| match &interval.end_time {
+                    Some(end_time) => {
+                        let start = DateTime::parse_from_rfc3339(&interval.start_time).unwrap_or_default();
+                        let end = DateTime::parse_from_rfc3339(end_time).unwrap_or_default();
+                        (end - start).num_minutes() as i32
+                    }
+                    None => 15,
+                })
+                .sum();
+            sessions.push(DeepWorkSession {
+                workblock_id,
+                activity: activity.to_string(),
+                start_time: run[0].start_time.clone(),
+                end_time: run[run.len() - 1].end_time.clone(),
+                interval_count: run.len() as i32,
+                duration_minutes,
+            });
+        }
+        run.clear();
+    };
+    
+    for interval in intervals {
+        let activity = match (&interval.status, &interval.words) {
+            (IntervalStatus::Recorded, Some(words)) => normalize_activity(words, aliases),
+            _ => String::new(),
+        };
+        if activity.is_empty() {
+            close_run(&mut run, &run_activity);
+            continue;
         }
+        
+        let continues_run = run
+            .last()
+            .map(|prev| prev.interval_number + 1 == interval.interval_number && activity == run_activity)
+            .unwrap_or(false);
+        if !continues_run {
+            close_run(&mut run, &run_activity);
+            run_activity = activity;
+        }
+        run.push(interval);
     }
+    close_run(&mut run, &run_activity);
     
-    let daily_aggregate = generate_daily_aggregate(app, date)?;
+    sessions
+}
+
+// ============================================================================
+// Workblock Summary
+// ============================================================================
+
+/// Number of activities listed in `WorkblockSummary::top_activities`
+const SUMMARY_TOP_ACTIVITIES: usize = 3;
+
+/// Everything the summary-ready window shows for one finished workblock
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkblockSummary {
+    pub workblock_id: i64,
+    pub date: String,  // YYYY-MM-DD format
+    pub status: String,
+    pub duration_minutes: i32,
+    pub intervals_recorded: i32,
+    pub intervals_away: i32,          // Auto-away, including paused prompts
+    pub intervals_quick_status: i32,  // Away for a named reason
+    pub top_activities: Vec<ActivityData>,
+    pub focus_score: i32,             // 0-100: share of recorded intervals that belong to a deep work session
+    pub note: Option<String>,         // Note left when the workblock was cancelled
+}
+
+pub fn get_workblock_summary(app: &AppHandle, workblock_id: i64) -> Result<WorkblockSummary> {
+    let conn = get_db_connection(app)?;
+    query_workblock_summary(&conn, workblock_id)
+}
+
+pub fn query_workblock_summary(conn: &Connection, workblock_id: i64) -> Result<WorkblockSummary> {
+    let workblock = query_workblock_by_id(conn, workblock_id)?;
+    let intervals = query_intervals_by_workblock(conn, workblock_id)?;
+    let aliases = query_alias_map(conn)?;
+    let splits = query_activity_splits_by_workblock(conn, workblock_id)?;
     
-    Ok(DailyVisualizationData {
-        workblocks: workblock_visualizations,
-        daily_aggregate,
+    let count = |status: IntervalStatus| intervals.iter().filter(|i| i.status == status).count() as i32;
+    let intervals_recorded = count(IntervalStatus::Recorded);
+    
+    let mut activity_map: HashMap<String, i32> = HashMap::new();
+    let mut energy_map: HashMap<String, Vec<i32>> = HashMap::new();
+    for interval in intervals.iter().filter(|i| i.status == IntervalStatus::Recorded) {
+        let duration = match &interval.end_time {
+            Some(end_time) => {
+                let start = DateTime::parse_from_rfc3339(&interval.start_time).unwrap_or_default();
+                let end = DateTime::parse_from_rfc3339(end_time).unwrap_or_default();
+                (end - start).num_minutes() as i32
+            }
+            None => 0,
+        };
+        for (activity, minutes) in interval_activity_minutes(interval, &splits, duration, &aliases) {
+            if let Some(energy) = interval.energy {
+                energy_map.entry(activity.clone()).or_default().push(energy);
+            }
+            *activity_map.entry(activity).or_insert(0) += minutes;
+        }
+    }
+    let activity_minutes: i32 = activity_map.values().sum();
+    let mut top_activities: Vec<ActivityData> = activity_map
+        .into_iter()
+        .map(|(words, minutes)| ActivityData {
+            average_energy: energy_map.get(&words).and_then(|r| average_energy(r)),
+            words,
+            total_minutes: minutes,
+            percentage: if activity_minutes > 0 {
+                minutes as f64 / activity_minutes as f64 * 100.0
+            } else {
+                0.0
+            },
+            color: None,
+        })
+        .collect();
+    top_activities.sort_by(|a, b| b.total_minutes.cmp(&a.total_minutes).then_with(|| a.words.cmp(&b.words)));
+    top_activities.truncate(SUMMARY_TOP_ACTIVITIES);
+    fill_activity_colors(conn, &mut top_activities)?;
+    
+    let deep_work_intervals: i32 = detect_deep_work(workblock_id, &intervals, &aliases)
+        .iter()
+        .map(|s| s.interval_count)
+        .sum();
+    let focus_score = if intervals_recorded > 0 {
+        (deep_work_intervals as f64 / intervals_recorded as f64 * 100.0).round() as i32
+    } else {
+        0
+    };
+    
+    Ok(WorkblockSummary {
+        workblock_id,
+        date: workblock.date,
+        status: workblock.status.as_str().to_string(),
+        duration_minutes: workblock.duration_minutes.unwrap_or(0),
+        intervals_recorded,
+        intervals_away: count(IntervalStatus::AutoAway),
+        intervals_quick_status: count(IntervalStatus::QuickStatus),
+        top_activities,
+        focus_score,
+        note: workblock.cancel_note,
+    })
+}
+
+// ============================================================================
+// Weekly Review
+// ============================================================================
+
+/// Number of activities returned in `WeeklyReview::top_activities`
+const WEEKLY_TOP_ACTIVITIES: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeeklyReviewDay {
+    pub date: String,  // YYYY-MM-DD format
+    pub total_minutes: i32,
+    pub total_workblocks: i32,
+    pub deep_work_sessions: i32,
+    pub deep_work_minutes: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelReasonCount {
+    pub reason: Option<CancelReason>,  // None = cancelled without giving a reason
+    pub count: i32,
+    pub notes: Vec<String>,            // Free-text notes given with this reason, most recent first
+}
+
+/// Away intervals with one cause, for seeing why time went untracked
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AwayCauseCount {
+    pub cause: Option<AwayCause>,  // None for intervals recorded before causes were kept
+    pub intervals: i32,
+    pub minutes: i32,
+}
+
+/// Intervals still recorded as away on workblocks dated in [start_date, end_date), by cause, most minutes first
+pub fn query_away_causes(conn: &Connection, start_date: &str, end_date: &str) -> Result<Vec<AwayCauseCount>> {
+    let mut stmt = conn.prepare(
+        "SELECT i.away_cause, COUNT(*),
+                COALESCE(SUM(CAST(ROUND((julianday(i.end_time) - julianday(i.start_time)) * 1440) AS INTEGER)), 0)
+         FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE i.status = ?1 AND w.date >= ?2 AND w.date < ?3
+         GROUP BY i.away_cause"
+    )?;
+    let mut causes = stmt
+        .query_map(params![IntervalStatus::AutoAway.as_str(), start_date, end_date], |row| {
+            Ok(AwayCauseCount {
+                cause: row.get::<_, Option<String>>(0)?.and_then(|c| AwayCause::from_str(&c)),
+                intervals: row.get(1)?,
+                minutes: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    // Unknown causes last among equals
+    causes.sort_by(|a, b| {
+        b.minutes.cmp(&a.minutes).then(b.intervals.cmp(&a.intervals)).then(a.cause.is_none().cmp(&b.cause.is_none()))
+    });
+    Ok(causes)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeeklyReview {
+    pub start_date: String,  // First day of the week (inclusive)
+    pub end_date: String,    // Last day of the week (inclusive)
+    pub days: Vec<WeeklyReviewDay>,  // Seven entries, including empty days
+    pub total_workblocks: i32,
+    pub total_minutes: i32,
+    pub deep_work_sessions: i32,
+    pub deep_work_minutes: i32,
+    pub top_activities: Vec<WordFrequency>,
+    pub cancelled_workblocks: i32,
+    pub cancellation_reasons: Vec<CancelReasonCount>,  // Most frequent first
+    pub burnout: crate::stats::BurnoutIndicator,
+    #[serde(default)]
+    pub context: Vec<crate::context_snapshot::ContextGroup>,  // Empty unless context snapshots are on
+    #[serde(default)]
+    pub away_causes: Vec<AwayCauseCount>,
+}
+
+/// Monday of the week containing `date`
+pub fn week_start_for(date: chrono::NaiveDate) -> chrono::NaiveDate {
+    use chrono::Datelike;
+    date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Generate the weekly review for the 7 days starting at `start_date` (YYYY-MM-DD)
+pub fn generate_weekly_review(app: &AppHandle, start_date: &str) -> Result<WeeklyReview> {
+    let conn = get_db_connection(app)?;
+    let key = format!("{}{}", crate::computed_cache::WEEKLY_REVIEW_PREFIX, start_date);
+    crate::computed_cache::cached_or_compute(&conn, &key, Some(crate::computed_cache::DEFAULT_CACHE_TTL_SECONDS), |conn| {
+        query_weekly_review(conn, start_date)
+    })
+}
+
+/// Build the weekly review from an open connection
+pub fn query_weekly_review(conn: &Connection, start_date: &str) -> Result<WeeklyReview> {
+    let first_day = chrono::NaiveDate::parse_from_str(start_date, "%Y-%m-%d").map_err(|e| {
+        Log15Error::InvalidInput(format!("Invalid date '{}': {}", start_date, e))
+    })?;
+    let after_last = first_day + chrono::Duration::days(7);
+    let start_date = first_day.format("%Y-%m-%d").to_string();
+    let end_exclusive = after_last.format("%Y-%m-%d").to_string();
+    
+    let mut days: Vec<WeeklyReviewDay> = first_day
+        .iter_days()
+        .take(7)
+        .map(|day| WeeklyReviewDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            total_minutes: 0,
+            total_workblocks: 0,
+            deep_work_sessions: 0,
+            deep_work_minutes: 0,
+        })
+        .collect();
+    
+    // Minutes and workblocks per day
+    let mut stmt = conn.prepare(
+        "SELECT date, COUNT(*), COALESCE(SUM(duration_minutes), 0)
+         FROM workblocks
+         WHERE date >= ?1 AND date < ?2
+         GROUP BY date"
+    )?;
+    let rows = stmt.query_map(params![start_date, end_exclusive], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)?, row.get::<_, i32>(2)?))
+    })?;
+    for row in rows {
+        let (date, workblocks, minutes) = row?;
+        if let Some(day) = days.iter_mut().find(|d| d.date == date) {
+            day.total_workblocks = workblocks;
+            day.total_minutes = minutes;
+        }
+    }
+    
+    // Deep work sessions per day, detected workblock by workblock
+    let aliases = query_alias_map(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT w.date, i.id, i.workblock_id, i.interval_number, i.start_time, i.end_time, i.words, i.status, i.recorded_at
+         FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE w.date >= ?1 AND w.date < ?2
+         ORDER BY i.workblock_id ASC, i.interval_number ASC"
+    )?;
+    let rows = stmt.query_map(params![start_date, end_exclusive], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            Interval {
+                id: Some(row.get(1)?),
+                workblock_id: row.get(2)?,
+                interval_number: row.get(3)?,
+                start_time: row.get(4)?,
+                end_time: row.get(5)?,
+                words: row.get(6)?,
+                status: IntervalStatus::from_str(&row.get::<_, String>(7)?),
+                recorded_at: row.get(8)?,
+                project: None,
+                category: None,
+                energy: None,
+                notes: None,
+                provenance: IntervalProvenance::Live,
+                away_cause: None,
+            },
+        ))
+    })?;
+    let mut by_workblock: Vec<(String, i64, Vec<Interval>)> = Vec::new();
+    for row in rows {
+        let (date, interval) = row?;
+        match by_workblock.last_mut() {
+            Some((_, workblock_id, intervals)) if *workblock_id == interval.workblock_id => intervals.push(interval),
+            _ => by_workblock.push((date, interval.workblock_id, vec![interval])),
+        }
+    }
+    for (date, workblock_id, intervals) in &by_workblock {
+        let sessions = detect_deep_work(*workblock_id, intervals, &aliases);
+        if let Some(day) = days.iter_mut().find(|d| &d.date == date) {
+            day.deep_work_sessions += sessions.len() as i32;
+            day.deep_work_minutes += sessions.iter().map(|s| s.duration_minutes).sum::<i32>();
+        }
+    }
+    
+    // Why workblocks were cancelled this week
+    let mut stmt = conn.prepare(
+        "SELECT cancel_reason, cancel_note
+         FROM workblocks
+         WHERE status = 'cancelled' AND date >= ?1 AND date < ?2
+         ORDER BY start_time DESC"
+    )?;
+    let rows = stmt.query_map(params![start_date, end_exclusive], |row| {
+        Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?))
+    })?;
+    let mut cancelled_workblocks = 0;
+    let mut cancellation_reasons: Vec<CancelReasonCount> = Vec::new();
+    for row in rows {
+        let (reason, note) = row?;
+        let reason = reason.map(|r| CancelReason::from_str(&r));
+        cancelled_workblocks += 1;
+        let entry = match cancellation_reasons.iter().position(|c| c.reason == reason) {
+            Some(index) => &mut cancellation_reasons[index],
+            None => {
+                cancellation_reasons.push(CancelReasonCount { reason, count: 0, notes: Vec::new() });
+                cancellation_reasons.last_mut().unwrap()
+            }
+        };
+        entry.count += 1;
+        entry.notes.extend(note);
+    }
+    cancellation_reasons.sort_by_key(|c| std::cmp::Reverse(c.count));
+    
+    Ok(WeeklyReview {
+        end_date: (after_last - chrono::Duration::days(1)).format("%Y-%m-%d").to_string(),
+        total_workblocks: days.iter().map(|d| d.total_workblocks).sum(),
+        total_minutes: days.iter().map(|d| d.total_minutes).sum(),
+        deep_work_sessions: days.iter().map(|d| d.deep_work_sessions).sum(),
+        deep_work_minutes: days.iter().map(|d| d.deep_work_minutes).sum(),
+        top_activities: query_top_activities(conn, &start_date, &end_exclusive, WEEKLY_TOP_ACTIVITIES)?,
+        cancelled_workblocks,
+        cancellation_reasons,
+        burnout: crate::stats::query_burnout_indicator(conn, first_day)?,
+        context: crate::context_snapshot::query_context_breakdown(conn, &start_date, &end_exclusive)?,
+        away_causes: query_away_causes(conn, &start_date, &end_exclusive)?,
+        start_date,
+        days,
+    })
+}
+
+// ============================================================================
+// Activity Suggestions
+// ============================================================================
+
+/// How many of the most recent recorded intervals feed the suggestion ranking
+const SUGGESTION_HISTORY_LIMIT: i64 = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActivitySuggestion {
+    pub words: String,      // Most recently used spelling of the phrase
+    pub count: i32,         // How often the (normalized) phrase was recorded
+    pub last_used: String,  // ISO 8601 format
+    pub score: f64,
+}
+
+/// Get autocomplete suggestions for the prompt window, ranked by recency and frequency
+pub fn suggest_activities(app: &AppHandle, prefix: &str, limit: usize) -> Result<Vec<ActivitySuggestion>> {
+    let conn = get_db_connection(app)?;
+    query_activity_suggestions(&conn, prefix, limit, Local::now())
+}
+
+/// Rank distinct recorded phrases matching `prefix` (case-insensitive).
+/// Score is the use count decayed by days since last use, so recent habits win over old ones.
+pub fn query_activity_suggestions(
+    conn: &Connection,
+    prefix: &str,
+    limit: usize,
+    now: DateTime<Local>,
+) -> Result<Vec<ActivitySuggestion>> {
+    let mut stmt = conn.prepare(
+        "SELECT words, COALESCE(recorded_at, start_time)
+         FROM intervals
+         WHERE status = 'recorded' AND words IS NOT NULL
+         ORDER BY COALESCE(recorded_at, start_time) DESC
+         LIMIT ?1"
+    )?;
+    let rows = stmt.query_map(params![SUGGESTION_HISTORY_LIMIT], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let prefix_lower = prefix.trim().to_lowercase();
+    // normalized phrase -> suggestion (rows arrive newest first)
+    let mut suggestions: HashMap<String, ActivitySuggestion> = HashMap::new();
+    for row in rows {
+        let (words, used_at) = row?;
+        let words = words.trim().to_string();
+        let normalized = words.to_lowercase();
+        if normalized.is_empty() || !normalized.starts_with(&prefix_lower) {
+            continue;
+        }
+        suggestions
+            .entry(normalized)
+            .and_modify(|s| s.count += 1)
+            .or_insert(ActivitySuggestion {
+                words,
+                count: 1,
+                last_used: used_at,
+                score: 0.0,
+            });
+    }
+
+    let mut ranked: Vec<ActivitySuggestion> = suggestions
+        .into_values()
+        .map(|mut s| {
+            let days_since = DateTime::parse_from_rfc3339(&s.last_used)
+                .map(|t| (now.fixed_offset() - t).num_minutes().max(0) as f64 / (24.0 * 60.0))
+                .unwrap_or(0.0);
+            s.score = s.count as f64 / (1.0 + days_since);
+            s
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.last_used.cmp(&a.last_used))
+    });
+    ranked.truncate(limit);
+
+    Ok(ranked)
+}
+
+/// The `limit` most recently recorded distinct phrases (case-insensitive), newest first
+pub fn query_recent_activities(conn: &Connection, limit: usize) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT words
+         FROM intervals
+         WHERE status = 'recorded' AND words IS NOT NULL
+         ORDER BY COALESCE(recorded_at, start_time) DESC
+         LIMIT ?1"
+    )?;
+    let rows = stmt.query_map(params![SUGGESTION_HISTORY_LIMIT], |row| row.get::<_, String>(0))?;
+
+    let mut seen = HashSet::new();
+    let mut recent = Vec::new();
+    for row in rows {
+        let words = row?.trim().to_string();
+        if words.is_empty() || !seen.insert(words.to_lowercase()) {
+            continue;
+        }
+        recent.push(words);
+        if recent.len() >= limit {
+            break;
+        }
+    }
+    Ok(recent)
+}
+
+// ============================================================================
+// Activity Aliases
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActivityAlias {
+    pub alias: String,      // Normalized raw phrase (lowercase, trimmed)
+    pub canonical: String,  // Normalized canonical activity
+    pub created_at: Option<String>,
+}
+
+/// Normalize a phrase for grouping: lowercase, trimmed, then mapped through the alias table
+pub fn normalize_activity(words: &str, aliases: &HashMap<String, String>) -> String {
+    let normalized = words.to_lowercase().trim().to_string();
+    match aliases.get(&normalized) {
+        Some(canonical) => canonical.clone(),
+        None => normalized,
+    }
+}
+
+/// Map a raw phrase to a canonical activity (replaces an existing mapping for the phrase)
+pub fn set_activity_alias(app: &AppHandle, alias: &str, canonical: &str) -> Result<ActivityAlias> {
+    let conn = get_db_connection(app)?;
+    let alias = alias.to_lowercase().trim().to_string();
+    let canonical = canonical.to_lowercase().trim().to_string();
+    
+    if alias.is_empty() || canonical.is_empty() {
+        return Err(Log15Error::InvalidInput("Alias and canonical activity must not be empty".to_string()));
+    }
+    
+    let created_at = timestamp_now();
+    conn.execute(
+        "INSERT OR REPLACE INTO activity_aliases (alias, canonical, created_at)
+         VALUES (?1, ?2, ?3)",
+        params![alias, canonical, created_at],
+    )?;
+    crate::cache::clear();
+    
+    Ok(ActivityAlias {
+        alias,
+        canonical,
+        created_at: Some(created_at),
     })
 }
+
+/// Remove the mapping for a raw phrase; returns whether one existed
+pub fn remove_activity_alias(app: &AppHandle, alias: &str) -> Result<bool> {
+    let conn = get_db_connection(app)?;
+    let removed = conn.execute(
+        "DELETE FROM activity_aliases WHERE alias = ?1",
+        params![alias.to_lowercase().trim()],
+    )?;
+    crate::cache::clear();
+    Ok(removed > 0)
+}
+
+/// Get all alias mappings
+pub fn get_activity_aliases(app: &AppHandle) -> Result<Vec<ActivityAlias>> {
+    let conn = get_db_connection(app)?;
+    let mut stmt = conn.prepare(
+        "SELECT alias, canonical, created_at
+         FROM activity_aliases
+         ORDER BY canonical ASC, alias ASC"
+    )?;
+    
+    let alias_iter = stmt.query_map([], |row| {
+        Ok(ActivityAlias {
+            alias: row.get(0)?,
+            canonical: row.get(1)?,
+            created_at: row.get(2)?,
+        })
+    })?;
+    
+    let mut aliases = Vec::new();
+    for alias in alias_iter {
+        aliases.push(alias?);
+    }
+    Ok(aliases)
+}
+
+/// Get the alias table as a lookup map (alias -> canonical)
+pub fn get_alias_map(app: &AppHandle) -> Result<HashMap<String, String>> {
+    let conn = get_db_connection(app)?;
+    query_alias_map(&conn)
+}
+
+/// Load the alias lookup map from an open connection
+pub fn query_alias_map(conn: &Connection) -> Result<HashMap<String, String>> {
+    let mut stmt = conn.prepare("SELECT alias, canonical FROM activity_aliases")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    
+    let mut aliases = HashMap::new();
+    for row in rows {
+        let (alias, canonical) = row?;
+        aliases.insert(alias, canonical);
+    }
+    Ok(aliases)
+}
+
+// ============================================================================
+// Activity Colors
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActivityColor {
+    pub activity: String,  // Normalized activity (lowercase, trimmed)
+    pub color: String,     // "#rrggbb"
+}
+
+/// Whether `color` is a "#rrggbb" hex color
+pub fn is_valid_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Set the display color of an activity (replaces an existing color)
+pub fn store_activity_color(conn: &Connection, activity: &str, color: &str) -> Result<ActivityColor> {
+    let activity = activity.to_lowercase().trim().to_string();
+    let color = color.trim().to_lowercase();
+    if activity.is_empty() || !is_valid_color(&color) {
+        return Err(Log15Error::InvalidInput(format!("Invalid activity color '{}' for '{}'", color, activity)));
+    }
+    conn.execute(
+        "INSERT OR REPLACE INTO activity_colors (activity, color, updated_at) VALUES (?1, ?2, ?3)",
+        params![activity, color, timestamp_now()],
+    )?;
+    Ok(ActivityColor { activity, color })
+}
+
+pub fn set_activity_color(app: &AppHandle, activity: &str, color: &str) -> Result<ActivityColor> {
+    let conn = get_db_connection(app)?;
+    let color = store_activity_color(&conn, activity, color)?;
+    crate::cache::clear();
+    Ok(color)
+}
+
+/// Remove the color of an activity; returns whether one existed
+pub fn remove_activity_color(app: &AppHandle, activity: &str) -> Result<bool> {
+    let conn = get_db_connection(app)?;
+    let removed = conn.execute(
+        "DELETE FROM activity_colors WHERE activity = ?1",
+        params![activity.to_lowercase().trim()],
+    )?;
+    crate::cache::clear();
+    Ok(removed > 0)
+}
+
+pub fn query_activity_colors(conn: &Connection) -> Result<Vec<ActivityColor>> {
+    let mut stmt = conn.prepare("SELECT activity, color FROM activity_colors ORDER BY activity ASC")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(ActivityColor {
+            activity: row.get(0)?,
+            color: row.get(1)?,
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+pub fn get_activity_colors(app: &AppHandle) -> Result<Vec<ActivityColor>> {
+    let conn = get_db_connection(app)?;
+    query_activity_colors(&conn)
+}
+
+/// Colors handed out to activities that don't have one yet (the charts' default palette)
+pub const ACTIVITY_PALETTE: [&str; 10] = [
+    "#4a90e2", "#4caf50", "#ff9800", "#e91e63", "#9c27b0", "#00bcd4", "#ffc107", "#795548", "#607d8b", "#f44336",
+];
+
+/// Palette color for a new activity: the first one no activity has yet, or once all are taken
+/// one picked from the activity's name, so the same activity always gets the same color
+pub fn palette_color(activity: &str, taken: &HashSet<String>) -> String {
+    if let Some(free) = ACTIVITY_PALETTE.iter().find(|c| !taken.contains(**c)) {
+        return free.to_string();
+    }
+    let hash = activity.bytes().fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    ACTIVITY_PALETTE[hash as usize % ACTIVITY_PALETTE.len()].to_string()
+}
+
+/// Colors of `activities`: the stored ones, and a palette color for each activity without one.
+/// Also returns the colors that were picked, in order.
+fn pick_activity_colors(conn: &Connection, activities: &[String]) -> Result<(HashMap<String, String>, Vec<(String, String)>)> {
+    let mut colors: HashMap<String, String> =
+        query_activity_colors(conn)?.into_iter().map(|c| (c.activity, c.color)).collect();
+    let mut taken: HashSet<String> = colors.values().cloned().collect();
+    let mut picked = Vec::new();
+    for activity in activities {
+        let activity = activity.to_lowercase().trim().to_string();
+        if activity.is_empty() || colors.contains_key(&activity) {
+            continue;
+        }
+        let color = palette_color(&activity, &taken);
+        taken.insert(color.clone());
+        colors.insert(activity.clone(), color.clone());
+        picked.push((activity, color));
+    }
+    Ok((colors, picked))
+}
+
+/// Colors of `activities`, storing a palette color for each one seen for the first time
+pub fn assign_activity_colors(conn: &Connection, activities: &[String]) -> Result<HashMap<String, String>> {
+    let (colors, picked) = pick_activity_colors(conn, activities)?;
+    for (activity, color) in picked {
+        conn.execute(
+            "INSERT OR IGNORE INTO activity_colors (activity, color, updated_at) VALUES (?1, ?2, ?3)",
+            params![activity, color, timestamp_now()],
+        )?;
+    }
+    Ok(colors)
+}
+
+/// Give the activities of a newly recorded answer their palette color, so it's stored when the
+/// activity first appears rather than when a summary is read
+fn assign_new_activity_colors(conn: &Connection, words: &[&str]) -> Result<()> {
+    let aliases = query_alias_map(conn)?;
+    let activities: Vec<String> = words.iter().map(|w| normalize_activity(w, &aliases)).collect();
+    assign_activity_colors(conn, &activities)?;
+    Ok(())
+}
+
+/// Set the color of each activity without writing anything: activities recorded before colors
+/// were assigned get a palette color for display (largest activities first)
+pub fn fill_activity_colors(conn: &Connection, activity_data: &mut [ActivityData]) -> Result<()> {
+    let mut by_size: Vec<&ActivityData> = activity_data.iter().collect();
+    by_size.sort_by(|a, b| b.total_minutes.cmp(&a.total_minutes).then_with(|| a.words.cmp(&b.words)));
+    let activities: Vec<String> = by_size.iter().map(|a| a.words.clone()).collect();
+    let (colors, _) = pick_activity_colors(conn, &activities)?;
+    for activity in activity_data.iter_mut() {
+        activity.color = colors.get(&activity.words).cloned();
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Pinned Activities
+// ============================================================================
+
+/// Pin an activity so the prompt always offers it
+pub fn pin_activity(app: &AppHandle, activity: &str) -> Result<Vec<String>> {
+    let conn = get_db_connection(app)?;
+    let activity = activity.trim();
+    if activity.is_empty() {
+        return Err(Log15Error::InvalidInput("Activity must not be empty".to_string()));
+    }
+    conn.execute(
+        "INSERT OR IGNORE INTO pinned_activities (activity, pinned_at) VALUES (?1, ?2)",
+        params![activity, timestamp_now()],
+    )?;
+    query_pinned_activities(&conn)
+}
+
+/// Unpin an activity (case-insensitive)
+pub fn unpin_activity(app: &AppHandle, activity: &str) -> Result<Vec<String>> {
+    let conn = get_db_connection(app)?;
+    conn.execute(
+        "DELETE FROM pinned_activities WHERE lower(activity) = lower(?1)",
+        params![activity.trim()],
+    )?;
+    query_pinned_activities(&conn)
+}
+
+/// Get pinned activities in the order they were pinned
+pub fn get_pinned_activities(app: &AppHandle) -> Result<Vec<String>> {
+    let conn = get_db_connection(app)?;
+    query_pinned_activities(&conn)
+}
+
+pub fn query_pinned_activities(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT activity FROM pinned_activities ORDER BY pinned_at ASC, activity ASC")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    
+    let mut activities = Vec::new();
+    for activity in rows {
+        activities.push(activity?);
+    }
+    Ok(activities)
+}
+
+// ============================================================================
+// Prompt Suggestions
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionSource {
+    Pinned,      // User-pinned favorite
+    Recent,      // Recently recorded interval words
+    Attachment,  // Name of a file attached earlier in the same workblock
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PromptSuggestion {
+    pub words: String,
+    pub sources: Vec<SuggestionSource>,
+    pub score: f64,
+}
+
+/// Pinned activities always rank above anything learned from history
+const PINNED_SUGGESTION_SCORE: f64 = 1000.0;
+/// Attachment names rank like a phrase used once today
+const ATTACHMENT_SUGGESTION_SCORE: f64 = 1.0;
+
+/// Build the ranked suggestion strip for the prompt window of an interval
+pub fn generate_prompt_suggestions(app: &AppHandle, interval_id: i64, limit: usize) -> Result<Vec<PromptSuggestion>> {
+    let interval = get_interval_by_id(app, interval_id)?;
+    let pinned = get_pinned_activities(app)?;
+    let recent = suggest_activities(app, "", limit.max(1) * 2)?;
+    
+    // File names (without extension) attached earlier in this workblock, e.g. "PROJ-123"
+    let mut attachment_names: Vec<String> = get_attachments_by_workblock(app, interval.workblock_id)?
+        .into_values()
+        .flatten()
+        .filter_map(|a| {
+            std::path::Path::new(&a.file_name)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.to_string())
+        })
+        .collect();
+    attachment_names.sort();
+    attachment_names.dedup();
+    
+    Ok(rank_prompt_suggestions(&pinned, &recent, &attachment_names, limit))
+}
+
+/// Merge suggestion sources into one ranked list, de-duplicated by normalized phrase
+pub fn rank_prompt_suggestions(
+    pinned: &[String],
+    recent: &[ActivitySuggestion],
+    attachment_names: &[String],
+    limit: usize,
+) -> Vec<PromptSuggestion> {
+    let candidates = pinned
+        .iter()
+        .enumerate()
+        // Keep pin order: earlier pins score slightly higher
+        .map(|(i, words)| (words.as_str(), SuggestionSource::Pinned, PINNED_SUGGESTION_SCORE - i as f64))
+        .chain(recent.iter().map(|s| (s.words.as_str(), SuggestionSource::Recent, s.score)))
+        .chain(attachment_names.iter().map(|w| (w.as_str(), SuggestionSource::Attachment, ATTACHMENT_SUGGESTION_SCORE)));
+    
+    let mut merged: Vec<PromptSuggestion> = Vec::new();
+    for (words, source, score) in candidates {
+        let words = words.trim();
+        if words.is_empty() {
+            continue;
+        }
+        let normalized = words.to_lowercase();
+        match merged.iter_mut().find(|s| s.words.to_lowercase() == normalized) {
+            Some(existing) => {
+                // A phrase coming from several sources ranks by its best score
+                existing.score = existing.score.max(score);
+                if !existing.sources.contains(&source) {
+                    existing.sources.push(source);
+                }
+            }
+            None => merged.push(PromptSuggestion {
+                words: words.to_string(),
+                sources: vec![source],
+                score,
+            }),
+        }
+    }
+    
+    merged.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    merged.truncate(limit);
+    merged
+}
+
+// ============================================================================
+// Prompt Quick Picks
+// ============================================================================
+
+/// How many of today's activities the prompt offers as quick picks
+pub const QUICK_PICK_TOP_ACTIVITIES: usize = 3;
+
+/// One-click answers for the prompt window
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PromptQuickPicks {
+    pub last_words: Option<String>,  // Words of the most recently recorded interval
+    pub top_today: Vec<String>,      // Today's most-logged activities (aliases applied), most intervals first
+    pub pinned: Vec<String>,         // In the order they were pinned
+}
+
+pub fn get_prompt_quick_picks(app: &AppHandle) -> Result<PromptQuickPicks> {
+    let conn = get_db_connection(app)?;
+    query_prompt_quick_picks(&conn, &get_today_date())
+}
+
+pub fn query_prompt_quick_picks(conn: &Connection, date: &str) -> Result<PromptQuickPicks> {
+    let last_words = query_recent_activities(conn, 1)?.into_iter().next();
+    
+    let aliases = query_alias_map(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT i.words
+         FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE w.date = ?1 AND i.status = 'recorded' AND i.words IS NOT NULL",
+    )?;
+    let rows = stmt.query_map(params![date], |row| row.get::<_, String>(0))?;
+    let mut counts: HashMap<String, i32> = HashMap::new();
+    for words in rows {
+        let activity = normalize_activity(&words?, &aliases);
+        if !activity.is_empty() {
+            *counts.entry(activity).or_insert(0) += 1;
+        }
+    }
+    let mut top_today: Vec<(String, i32)> = counts.into_iter().collect();
+    top_today.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_today.truncate(QUICK_PICK_TOP_ACTIVITIES);
+    
+    Ok(PromptQuickPicks {
+        last_words,
+        top_today: top_today.into_iter().map(|(activity, _)| activity).collect(),
+        pinned: query_pinned_activities(conn)?,
+    })
+}
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+/// Set a raw setting value, replacing any previous value; a changed value is announced to
+/// running subsystems so they apply it without a restart
+pub fn set_setting(app: &AppHandle, key: &str, value: &str) -> Result<()> {
+    let conn = get_db_connection(app)?;
+    let changed = query_setting(&conn, key)?.as_deref() != Some(value);
+    store_setting(&conn, key, value)?;
+    if changed {
+        crate::settings::notify_setting_changed(app, key, value);
+    }
+    Ok(())
+}
+
+pub fn query_setting(conn: &Connection, key: &str) -> Result<Option<String>> {
+    match conn.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        params![key],
+        |row| row.get::<_, String>(0),
+    ) {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store a setting; a changed value is also recorded in settings_history unless it is a secret
+/// or window layout
+pub fn store_setting(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    let old_value = query_setting(conn, key)?;
+    if old_value.as_deref() == Some(value) {
+        return Ok(());
+    }
+    let now = timestamp_now();
+    if crate::settings::is_secret_setting(key) || crate::settings::is_layout_setting(key) {
+        conn.execute(
+            "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![key, value, now],
+        )?;
+        return Ok(());
+    }
+    // The value and its history entry are written together or not at all
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![key, value, now],
+    )?;
+    crate::faults::fail_point(crate::faults::FaultPoint::SettingsHistory)?;
+    tx.execute(
+        "INSERT INTO settings_history (key, old_value, new_value, changed_at) VALUES (?1, ?2, ?3, ?4)",
+        params![key, old_value, value, now],
+    )?;
+    Ok(tx.commit()?)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SettingChange {
+    pub key: String,
+    pub old_value: Option<String>,  // None = the setting had no value before
+    pub new_value: String,
+    pub changed_at: String,
+}
+
+/// Recorded setting changes, newest first, optionally for one key and/or since a timestamp (RFC 3339)
+pub fn query_settings_history(conn: &Connection, key: Option<&str>, since: Option<&str>) -> Result<Vec<SettingChange>> {
+    let mut stmt = conn.prepare(
+        "SELECT key, old_value, new_value, changed_at
+         FROM settings_history
+         WHERE (?1 IS NULL OR key = ?1) AND (?2 IS NULL OR changed_at >= ?2)
+         ORDER BY changed_at DESC, id DESC"
+    )?;
+    let changes = stmt.query_map(params![key, since], |row| {
+        Ok(SettingChange {
+            key: row.get(0)?,
+            old_value: row.get(1)?,
+            new_value: row.get(2)?,
+            changed_at: row.get(3)?,
+        })
+    })?;
+    Ok(changes.collect::<rusqlite::Result<_>>()?)
+}
+
+pub fn get_settings_history(app: &AppHandle, key: Option<&str>, since: Option<&str>) -> Result<Vec<SettingChange>> {
+    let conn = get_db_connection(app)?;
+    query_settings_history(&conn, key, since)
+}
+
+/// Store a setting only if it has no value yet; returns whether it was stored
+pub fn store_setting_if_missing(conn: &Connection, key: &str, value: &str) -> Result<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)",
+        params![key, value, timestamp_now()],
+    )?;
+    Ok(inserted == 1)
+}
+
+// ============================================================================
+// Projects
+// ============================================================================
+
+/// Normalize a project label: trimmed, empty means "no project"
+fn clean_project(project: Option<&str>) -> Option<String> {
+    project.map(str::trim).filter(|p| !p.is_empty()).map(str::to_string)
+}
+
+/// The project an interval counts toward: its own override, else the workblock's project
+pub fn effective_project(interval: &Interval, workblock: &Workblock) -> Option<String> {
+    interval.project.clone().or_else(|| workblock.project.clone())
+}
+
+/// Set (or clear with None) the project label of a workblock
+pub fn set_workblock_project(app: &AppHandle, workblock_id: i64, project: Option<&str>) -> Result<Workblock> {
+    let conn = get_db_connection(app)?;
+    conn.execute(
+        "UPDATE workblocks SET project = ?1 WHERE id = ?2",
+        params![clean_project(project), workblock_id],
+    )?;
+    crate::cache::workblock_written(app, workblock_id);
+    get_workblock_by_id(app, workblock_id)
+}
+
+/// Override the project of a single interval; None makes it inherit from its workblock again
+pub fn set_interval_project(app: &AppHandle, interval_id: i64, project: Option<&str>) -> Result<Interval> {
+    let conn = get_db_connection(app)?;
+    conn.execute(
+        "UPDATE intervals SET project = ?1 WHERE id = ?2",
+        params![clean_project(project), interval_id],
+    )?;
+    crate::cache::interval_written(app, interval_id);
+    get_interval_by_id(app, interval_id)
+}
+
+/// Roll up recorded time by effective interval-level project, largest first
+pub fn rollup_by_project(timeline: &[AggregateTimelineData]) -> Vec<ProjectData> {
+    let mut project_map: HashMap<Option<String>, (i32, i32)> = HashMap::new();
+    for entry in timeline.iter().filter(|e| e.words.is_some()) {
+        let totals = project_map.entry(entry.project.clone()).or_insert((0, 0));
+        totals.0 += entry.duration_minutes;
+        totals.1 += 1;
+    }
+    
+    let total_minutes: i32 = project_map.values().map(|(minutes, _)| minutes).sum();
+    let mut project_data: Vec<ProjectData> = project_map
+        .into_iter()
+        .map(|(project, (minutes, count))| ProjectData {
+            project,
+            total_minutes: minutes,
+            interval_count: count,
+            percentage: if total_minutes > 0 {
+                (minutes as f64 / total_minutes as f64) * 100.0
+            } else {
+                0.0
+            },
+        })
+        .collect();
+    project_data.sort_by(|a, b| b.total_minutes.cmp(&a.total_minutes).then_with(|| a.project.cmp(&b.project)));
+    project_data
+}
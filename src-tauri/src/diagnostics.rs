@@ -0,0 +1,58 @@
+// Incidents the app detected and repaired on its own, kept for troubleshooting
+
+use crate::db::{get_db_connection, timestamp_now};
+use rusqlite::{params, Connection, Result};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Number of incidents kept; older ones are pruned on insert
+pub const MAX_INCIDENTS: i64 = 500;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Incident {
+    pub id: i64,
+    pub source: String,  // Subsystem that noticed the problem, e.g. "prompt_watchdog"
+    pub detail: String,
+    pub occurred_at: String,
+}
+
+pub fn store_incident(conn: &Connection, source: &str, detail: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO diagnostic_incidents (source, detail, occurred_at) VALUES (?1, ?2, ?3)",
+        params![source, detail, timestamp_now()],
+    )?;
+    conn.execute(
+        "DELETE FROM diagnostic_incidents WHERE id <= (SELECT MAX(id) FROM diagnostic_incidents) - ?1",
+        params![MAX_INCIDENTS],
+    )?;
+    Ok(())
+}
+
+/// Most recent incidents first
+pub fn query_incidents(conn: &Connection, limit: i64) -> Result<Vec<Incident>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, source, detail, occurred_at FROM diagnostic_incidents ORDER BY id DESC LIMIT ?1",
+    )?;
+    let rows = stmt.query_map(params![limit], |row| {
+        Ok(Incident {
+            id: row.get(0)?,
+            source: row.get(1)?,
+            detail: row.get(2)?,
+            occurred_at: row.get(3)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Record an incident; failures are only logged, diagnostics must never break the caller
+pub fn record_incident(app: &AppHandle, source: &str, detail: &str) {
+    eprintln!("[DIAGNOSTICS] {}: {}", source, detail);
+    if let Err(e) = get_db_connection(app).and_then(|conn| Ok(store_incident(&conn, source, detail)?)) {
+        eprintln!("[DIAGNOSTICS] Failed to record incident: {}", e);
+    }
+}
+
+pub fn get_incidents(app: &AppHandle, limit: i64) -> Result<Vec<Incident>> {
+    let conn = get_db_connection(app)?;
+    query_incidents(&conn, limit)
+}
@@ -0,0 +1,102 @@
+// Do-not-disturb: prompts are paused until a point in time, then resume on their own
+
+use crate::db::{get_db_connection, query_setting, set_setting, to_timestamp};
+use crate::events::{emit_event, AppEvent};
+use chrono::{DateTime, Local};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Setting holding the end of the current pause (RFC 3339); empty when prompts aren't paused
+pub const DND_UNTIL_KEY: &str = "dnd_until";
+
+/// Recorded for intervals that end while prompts are paused
+pub const DND_INTERVAL_MESSAGE: &str = "Prompts paused";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DndStatus {
+    pub active: bool,
+    pub until: Option<String>,
+    pub remaining_seconds: i64,
+}
+
+/// End of the pause in effect at `now`, if any (an expired pause counts as none)
+pub fn query_dnd_until(conn: &Connection, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let stored = query_setting(conn, DND_UNTIL_KEY).ok().flatten()?;
+    let until = DateTime::parse_from_rfc3339(&stored).ok()?.with_timezone(&Local);
+    (until > now).then_some(until)
+}
+
+pub fn dnd_status(until: Option<DateTime<Local>>, now: DateTime<Local>) -> DndStatus {
+    match until.filter(|u| *u > now) {
+        Some(until) => DndStatus {
+            active: true,
+            until: Some(until.to_rfc3339()),
+            remaining_seconds: (until - now).num_seconds(),
+        },
+        None => DndStatus {
+            active: false,
+            until: None,
+            remaining_seconds: 0,
+        },
+    }
+}
+
+pub fn get_dnd_status(app: &AppHandle) -> DndStatus {
+    let now = Local::now();
+    let until = get_db_connection(app).ok().and_then(|conn| query_dnd_until(&conn, now));
+    dnd_status(until, now)
+}
+
+/// Whether prompts are paused right now
+pub fn is_dnd_active(app: &AppHandle) -> bool {
+    get_dnd_status(app).active
+}
+
+/// Pause prompts for `minutes`, replacing any pause in effect, and clear it automatically when it runs out
+pub fn enable_dnd(app: &AppHandle, minutes: i32) -> Result<DndStatus, String> {
+    if minutes <= 0 {
+        return Err("Pause length must be at least 1 minute".to_string());
+    }
+    let now = Local::now();
+    let until = now + chrono::Duration::minutes(minutes as i64);
+    set_setting(app, DND_UNTIL_KEY, &to_timestamp(until)).map_err(|e| e.to_string())?;
+    println!("[DND] Prompts paused for {} minutes", minutes);
+    let status = dnd_status(Some(until), now);
+    let _ = emit_event(app, AppEvent::DndChanged(status.clone()));
+    schedule_clear(app, until);
+    Ok(status)
+}
+
+/// Clear the pause ending at `until` once it runs out, unless a newer pause (or an early resume) replaced it
+fn schedule_clear(app: &AppHandle, until: DateTime<Local>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let wait = (until - Local::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+        let stored = get_db_connection(&app)
+            .ok()
+            .and_then(|conn| query_setting(&conn, DND_UNTIL_KEY).ok().flatten());
+        if stored.as_deref() == Some(to_timestamp(until).as_str()) {
+            if let Err(e) = clear_dnd(&app) {
+                eprintln!("[DND] Failed to clear expired pause: {}", e);
+            }
+        }
+    });
+}
+
+/// Resume prompts now
+pub fn clear_dnd(app: &AppHandle) -> Result<DndStatus, String> {
+    set_setting(app, DND_UNTIL_KEY, "").map_err(|e| e.to_string())?;
+    println!("[DND] Prompts resumed");
+    let status = dnd_status(None, Local::now());
+    let _ = emit_event(app, AppEvent::DndChanged(status.clone()));
+    Ok(status)
+}
+
+/// Re-arm the automatic clear for a pause that was still running when the app quit
+pub fn restore_dnd(app: &AppHandle) {
+    if let Some(until) = get_db_connection(app).ok().and_then(|conn| query_dnd_until(&conn, Local::now())) {
+        schedule_clear(app, until);
+    }
+}
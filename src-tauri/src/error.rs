@@ -0,0 +1,97 @@
+// Errors returned by the database, timer and window layers and by commands. They reach the
+// frontend as { code, message }, so it can tell "no active workblock" from "database locked"
+// without matching on message text.
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Log15Error {
+    #[error("No active workblock")]
+    NoActiveWorkblock,
+    #[error("A workblock is already running")]
+    AlreadyRunning,
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    InvalidInput(String),
+    /// Another connection holds the write lock; retrying usually works
+    #[error("The database is busy: {0}")]
+    DatabaseLocked(rusqlite::Error),
+    #[error(transparent)]
+    Database(rusqlite::Error),
+    #[error("{0}")]
+    Window(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+pub type Result<T, E = Log15Error> = std::result::Result<T, E>;
+
+impl Log15Error {
+    /// Stable name for the frontend to branch on
+    pub fn code(&self) -> &'static str {
+        match self {
+            Log15Error::NoActiveWorkblock => "no_active_workblock",
+            Log15Error::AlreadyRunning => "already_running",
+            Log15Error::NotFound(_) => "not_found",
+            Log15Error::InvalidInput(_) => "invalid_input",
+            Log15Error::DatabaseLocked(_) => "database_locked",
+            Log15Error::Database(_) => "database",
+            Log15Error::Window(_) => "window",
+            Log15Error::Other(_) => "other",
+        }
+    }
+}
+
+impl From<rusqlite::Error> for Log15Error {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::SqliteFailure(ref failure, _)
+                if matches!(failure.code, rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked) =>
+            {
+                Log15Error::DatabaseLocked(e)
+            }
+            rusqlite::Error::QueryReturnedNoRows => Log15Error::NotFound(e.to_string()),
+            e => Log15Error::Database(e),
+        }
+    }
+}
+
+/// Errors of modules that still report plain messages
+impl From<String> for Log15Error {
+    fn from(message: String) -> Self {
+        Log15Error::Other(message)
+    }
+}
+
+impl From<crate::timer::DurationError> for Log15Error {
+    fn from(e: crate::timer::DurationError) -> Self {
+        Log15Error::InvalidInput(e.to_string())
+    }
+}
+
+impl From<Log15Error> for String {
+    fn from(e: Log15Error) -> Self {
+        e.to_string()
+    }
+}
+
+/// Lets functions that return rusqlite::Result keep using `?` on database helpers
+impl From<Log15Error> for rusqlite::Error {
+    fn from(e: Log15Error) -> Self {
+        match e {
+            Log15Error::DatabaseLocked(e) | Log15Error::Database(e) => e,
+            e => rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(1), Some(e.to_string())),
+        }
+    }
+}
+
+impl Serialize for Log15Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("Log15Error", 2)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
@@ -0,0 +1,361 @@
+// Catalog of every event the backend emits to the frontend. Each AppEvent variant carries its
+// payload type, so an event can't be sent with the wrong shape or a misspelled name; emit_event is
+// the one place events leave the backend. get_event_schema describes the catalog for the frontend,
+// whose EventPayloads type (src/types/workblock.ts) mirrors it.
+
+use crate::acked_events::emit_acked;
+use crate::cache::DailyAggregateDelta;
+use crate::db::{ArchiveUpdated, ReclassifyOffer, Workblock};
+use crate::dnd::DndStatus;
+use crate::settings::{Language, SettingChanged};
+use crate::startup::StartupReport;
+use crate::stats::{Milestone, WellbeingAlert};
+use crate::status_page::StatusPage;
+use crate::theme::ThemeInfo;
+use crate::timer::{BreakEnded, BreakInfo, DayRolledOver, IntervalEndingSoon, MissedIntervals, TimerTick};
+use crate::window_manager::{PromptQueue, SummaryWindowData};
+use crate::wipe::WipeSummary;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// Payload of "interval-complete": the prompt for this interval should be shown
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IntervalComplete {
+    pub workblock_id: i64,
+    pub interval_id: i64,
+    pub interval_number: i32,
+}
+
+/// An event with its payload
+#[derive(Debug)]
+pub enum AppEvent {
+    IntervalComplete(IntervalComplete),
+    IntervalEndingSoon(IntervalEndingSoon),
+    MissedIntervals(MissedIntervals),
+    WorkblockComplete(i64),  // Workblock id
+    WorkblockResumed(i64),   // Workblock id
+    WorkblockAdjusted(Workblock),
+    AutoAway(i64),           // Interval id recorded as away after the prompt timed out
+    IdleAutoAway(i64),       // Interval id recorded as away without prompting
+    PromptHide,
+    PromptIntervalId(i64),   // Interval the prompt window now asks about
+    PromptQueue(PromptQueue),
+    ReclassifyOffer(ReclassifyOffer),
+    SummaryData(SummaryWindowData),
+    TimerTick(TimerTick),
+    StatusPage(StatusPage),
+    BreakStarted(BreakInfo),
+    BreakEnded(BreakEnded),
+    BreakOverlayReset(u64),  // Break length in seconds
+    WellbeingLimitExceeded(WellbeingAlert),
+    Milestone(Milestone),
+    ArchiveUpdated(ArchiveUpdated),
+    DailyAggregateUpdated(DailyAggregateDelta),
+    DayRolledOver(DayRolledOver),
+    DndChanged(DndStatus),
+    SettingsChanged(SettingChanged),
+    LanguageChanged(Language),
+    ThemeChanged(ThemeInfo),
+    DataWiped(WipeSummary),
+    TrayStartWorkblock,
+    TrayViewLastWords,
+    Ready(StartupReport),
+}
+
+/// The events without their payloads
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    IntervalComplete,
+    IntervalEndingSoon,
+    MissedIntervals,
+    WorkblockComplete,
+    WorkblockResumed,
+    WorkblockAdjusted,
+    AutoAway,
+    IdleAutoAway,
+    PromptHide,
+    PromptIntervalId,
+    PromptQueue,
+    ReclassifyOffer,
+    SummaryData,
+    TimerTick,
+    StatusPage,
+    BreakStarted,
+    BreakEnded,
+    BreakOverlayReset,
+    WellbeingLimitExceeded,
+    Milestone,
+    ArchiveUpdated,
+    DailyAggregateUpdated,
+    DayRolledOver,
+    DndChanged,
+    SettingsChanged,
+    LanguageChanged,
+    ThemeChanged,
+    DataWiped,
+    TrayStartWorkblock,
+    TrayViewLastWords,
+    Ready,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 31] = [
+        EventKind::IntervalComplete,
+        EventKind::IntervalEndingSoon,
+        EventKind::MissedIntervals,
+        EventKind::WorkblockComplete,
+        EventKind::WorkblockResumed,
+        EventKind::WorkblockAdjusted,
+        EventKind::AutoAway,
+        EventKind::IdleAutoAway,
+        EventKind::PromptHide,
+        EventKind::PromptIntervalId,
+        EventKind::PromptQueue,
+        EventKind::ReclassifyOffer,
+        EventKind::SummaryData,
+        EventKind::TimerTick,
+        EventKind::StatusPage,
+        EventKind::BreakStarted,
+        EventKind::BreakEnded,
+        EventKind::BreakOverlayReset,
+        EventKind::WellbeingLimitExceeded,
+        EventKind::Milestone,
+        EventKind::ArchiveUpdated,
+        EventKind::DailyAggregateUpdated,
+        EventKind::DayRolledOver,
+        EventKind::DndChanged,
+        EventKind::SettingsChanged,
+        EventKind::LanguageChanged,
+        EventKind::ThemeChanged,
+        EventKind::DataWiped,
+        EventKind::TrayStartWorkblock,
+        EventKind::TrayViewLastWords,
+        EventKind::Ready,
+    ];
+
+    /// Name the frontend listens for
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::IntervalComplete => "interval-complete",
+            EventKind::IntervalEndingSoon => "interval-ending-soon",
+            EventKind::MissedIntervals => "missed-intervals",
+            EventKind::WorkblockComplete => "workblock-complete",
+            EventKind::WorkblockResumed => "workblock-resumed",
+            EventKind::WorkblockAdjusted => "workblock-adjusted",
+            EventKind::AutoAway => "auto-away",
+            EventKind::IdleAutoAway => "idle-auto-away",
+            EventKind::PromptHide => "prompt-hide",
+            EventKind::PromptIntervalId => "prompt-interval-id",
+            EventKind::PromptQueue => "prompt-queue",
+            EventKind::ReclassifyOffer => "reclassify-offer",
+            EventKind::SummaryData => "summary-data",
+            EventKind::TimerTick => "timer-tick",
+            EventKind::StatusPage => "status-page",
+            EventKind::BreakStarted => "break-started",
+            EventKind::BreakEnded => "break-ended",
+            EventKind::BreakOverlayReset => "break-overlay-reset",
+            EventKind::WellbeingLimitExceeded => "wellbeing-limit-exceeded",
+            EventKind::Milestone => "milestone",
+            EventKind::ArchiveUpdated => "archive-updated",
+            EventKind::DailyAggregateUpdated => "daily-aggregate-updated",
+            EventKind::DayRolledOver => "day-rolled-over",
+            EventKind::DndChanged => "dnd-changed",
+            EventKind::SettingsChanged => "settings-changed",
+            EventKind::LanguageChanged => "language-changed",
+            EventKind::ThemeChanged => "theme-changed",
+            EventKind::DataWiped => "data-wiped",
+            EventKind::TrayStartWorkblock => "tray-start-workblock",
+            EventKind::TrayViewLastWords => "tray-view-last-words",
+            EventKind::Ready => "ready",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        EventKind::ALL.into_iter().find(|kind| kind.name() == s)
+    }
+
+    /// Lifecycle-critical events arrive wrapped as AckedEvent and are replayed until acknowledged
+    pub fn acked(&self) -> bool {
+        matches!(self, EventKind::IntervalComplete | EventKind::MissedIntervals | EventKind::WorkblockComplete)
+    }
+
+    /// Payload type, named as in src/types/workblock.ts
+    pub fn payload(&self) -> &'static str {
+        match self {
+            EventKind::IntervalComplete => "IntervalComplete",
+            EventKind::IntervalEndingSoon => "IntervalEndingSoon",
+            EventKind::MissedIntervals => "MissedIntervals",
+            EventKind::WorkblockComplete
+            | EventKind::WorkblockResumed
+            | EventKind::AutoAway
+            | EventKind::IdleAutoAway
+            | EventKind::PromptIntervalId
+            | EventKind::BreakOverlayReset => "number",
+            EventKind::WorkblockAdjusted => "Workblock",
+            EventKind::PromptQueue => "PromptQueue",
+            EventKind::PromptHide | EventKind::TrayStartWorkblock | EventKind::TrayViewLastWords => "null",
+            EventKind::ReclassifyOffer => "ReclassifyOffer",
+            EventKind::SummaryData => "SummaryWindowData",
+            EventKind::TimerTick => "TimerTick",
+            EventKind::StatusPage => "StatusPage",
+            EventKind::BreakStarted => "BreakInfo",
+            EventKind::BreakEnded => "BreakEnded",
+            EventKind::WellbeingLimitExceeded => "WellbeingAlert",
+            EventKind::Milestone => "Milestone",
+            EventKind::ArchiveUpdated => "ArchiveUpdated",
+            EventKind::DailyAggregateUpdated => "DailyAggregateDelta",
+            EventKind::DayRolledOver => "DayRolledOver",
+            EventKind::DndChanged => "DndStatus",
+            EventKind::SettingsChanged => "SettingChanged",
+            EventKind::LanguageChanged => "Language",
+            EventKind::ThemeChanged => "ThemeInfo",
+            EventKind::DataWiped => "WipeSummary",
+            EventKind::Ready => "StartupReport",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            EventKind::IntervalComplete => "An interval ended; show its prompt",
+            EventKind::IntervalEndingSoon => "The current interval ends soon; wrap up before its prompt",
+            EventKind::MissedIntervals => "Intervals passed while the system slept and were recorded as away",
+            EventKind::WorkblockComplete => "A workblock finished",
+            EventKind::WorkblockResumed => "A workblock interrupted by a quit or crash was resumed",
+            EventKind::WorkblockAdjusted => "The running workblock was made longer or shorter",
+            EventKind::AutoAway => "A prompt went unanswered and its interval was recorded as away",
+            EventKind::IdleAutoAway => "An interval was recorded as away without prompting because the system was idle",
+            EventKind::PromptHide => "The prompt window should close",
+            EventKind::PromptIntervalId => "The prompt window now asks about this interval",
+            EventKind::PromptQueue => "Prompts waiting behind the one on screen changed",
+            EventKind::ReclassifyOffer => "The previous interval was auto-away and may be corrected",
+            EventKind::SummaryData => "Data for the summary window",
+            EventKind::TimerTick => "Countdown of the running workblock, every second while subscribed",
+            EventKind::StatusPage => "The read-only status changed",
+            EventKind::BreakStarted => "A Pomodoro break started",
+            EventKind::BreakEnded => "A Pomodoro break ended or was skipped",
+            EventKind::BreakOverlayReset => "The break overlay was reused for a new break",
+            EventKind::WellbeingLimitExceeded => "Tracked time passed the daily wellbeing limit",
+            EventKind::Milestone => "A goal, budget or streak milestone was reached",
+            EventKind::ArchiveUpdated => "A day was archived or re-archived",
+            EventKind::DailyAggregateUpdated => "A cached day's aggregate changed; apply the delta instead of refetching",
+            EventKind::DayRolledOver => "A new day began while the app was running; a workblock running into it was split",
+            EventKind::DndChanged => "Prompts were paused or resumed",
+            EventKind::SettingsChanged => "A setting changed (an empty key means all of them)",
+            EventKind::LanguageChanged => "The interface language changed",
+            EventKind::ThemeChanged => "The effective light or dark theme changed",
+            EventKind::DataWiped => "All data was wiped",
+            EventKind::TrayStartWorkblock => "The tray asked the main window to start a workblock",
+            EventKind::TrayViewLastWords => "The tray asked the main window to show the last entries",
+            EventKind::Ready => "Startup finished, with the time each step took",
+        }
+    }
+}
+
+impl AppEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            AppEvent::IntervalComplete(_) => EventKind::IntervalComplete,
+            AppEvent::IntervalEndingSoon(_) => EventKind::IntervalEndingSoon,
+            AppEvent::MissedIntervals(_) => EventKind::MissedIntervals,
+            AppEvent::WorkblockComplete(_) => EventKind::WorkblockComplete,
+            AppEvent::WorkblockResumed(_) => EventKind::WorkblockResumed,
+            AppEvent::WorkblockAdjusted(_) => EventKind::WorkblockAdjusted,
+            AppEvent::AutoAway(_) => EventKind::AutoAway,
+            AppEvent::IdleAutoAway(_) => EventKind::IdleAutoAway,
+            AppEvent::PromptHide => EventKind::PromptHide,
+            AppEvent::PromptIntervalId(_) => EventKind::PromptIntervalId,
+            AppEvent::PromptQueue(_) => EventKind::PromptQueue,
+            AppEvent::ReclassifyOffer(_) => EventKind::ReclassifyOffer,
+            AppEvent::SummaryData(_) => EventKind::SummaryData,
+            AppEvent::TimerTick(_) => EventKind::TimerTick,
+            AppEvent::StatusPage(_) => EventKind::StatusPage,
+            AppEvent::BreakStarted(_) => EventKind::BreakStarted,
+            AppEvent::BreakEnded(_) => EventKind::BreakEnded,
+            AppEvent::BreakOverlayReset(_) => EventKind::BreakOverlayReset,
+            AppEvent::WellbeingLimitExceeded(_) => EventKind::WellbeingLimitExceeded,
+            AppEvent::Milestone(_) => EventKind::Milestone,
+            AppEvent::ArchiveUpdated(_) => EventKind::ArchiveUpdated,
+            AppEvent::DailyAggregateUpdated(_) => EventKind::DailyAggregateUpdated,
+            AppEvent::DayRolledOver(_) => EventKind::DayRolledOver,
+            AppEvent::DndChanged(_) => EventKind::DndChanged,
+            AppEvent::SettingsChanged(_) => EventKind::SettingsChanged,
+            AppEvent::LanguageChanged(_) => EventKind::LanguageChanged,
+            AppEvent::ThemeChanged(_) => EventKind::ThemeChanged,
+            AppEvent::DataWiped(_) => EventKind::DataWiped,
+            AppEvent::TrayStartWorkblock => EventKind::TrayStartWorkblock,
+            AppEvent::TrayViewLastWords => EventKind::TrayViewLastWords,
+            AppEvent::Ready(_) => EventKind::Ready,
+        }
+    }
+
+    /// The payload as sent (events without one send null)
+    pub fn payload(&self) -> serde_json::Result<serde_json::Value> {
+        match self {
+            AppEvent::IntervalComplete(p) => serde_json::to_value(p),
+            AppEvent::IntervalEndingSoon(p) => serde_json::to_value(p),
+            AppEvent::MissedIntervals(p) => serde_json::to_value(p),
+            AppEvent::WorkblockComplete(id)
+            | AppEvent::WorkblockResumed(id)
+            | AppEvent::AutoAway(id)
+            | AppEvent::IdleAutoAway(id)
+            | AppEvent::PromptIntervalId(id) => serde_json::to_value(id),
+            AppEvent::WorkblockAdjusted(p) => serde_json::to_value(p),
+            AppEvent::PromptQueue(p) => serde_json::to_value(p),
+            AppEvent::PromptHide | AppEvent::TrayStartWorkblock | AppEvent::TrayViewLastWords => Ok(serde_json::Value::Null),
+            AppEvent::ReclassifyOffer(p) => serde_json::to_value(p),
+            AppEvent::SummaryData(p) => serde_json::to_value(p),
+            AppEvent::TimerTick(p) => serde_json::to_value(p),
+            AppEvent::StatusPage(p) => serde_json::to_value(p),
+            AppEvent::BreakStarted(p) => serde_json::to_value(p),
+            AppEvent::BreakEnded(p) => serde_json::to_value(p),
+            AppEvent::BreakOverlayReset(seconds) => serde_json::to_value(seconds),
+            AppEvent::WellbeingLimitExceeded(p) => serde_json::to_value(p),
+            AppEvent::Milestone(p) => serde_json::to_value(p),
+            AppEvent::ArchiveUpdated(p) => serde_json::to_value(p),
+            AppEvent::DailyAggregateUpdated(p) => serde_json::to_value(p),
+            AppEvent::DayRolledOver(p) => serde_json::to_value(p),
+            AppEvent::DndChanged(p) => serde_json::to_value(p),
+            AppEvent::SettingsChanged(p) => serde_json::to_value(p),
+            AppEvent::LanguageChanged(p) => serde_json::to_value(p),
+            AppEvent::ThemeChanged(p) => serde_json::to_value(p),
+            AppEvent::DataWiped(p) => serde_json::to_value(p),
+            AppEvent::Ready(p) => serde_json::to_value(p),
+        }
+    }
+}
+
+/// Send `event` to every window; lifecycle-critical events go through acknowledged delivery
+pub fn emit_event(app: &AppHandle, event: AppEvent) -> Result<(), String> {
+    let kind = event.kind();
+    let payload = event
+        .payload()
+        .map_err(|e| format!("Failed to serialize {} payload: {}", kind.name(), e))?;
+    if kind.acked() {
+        emit_acked(app, kind.name(), payload);
+        Ok(())
+    } else {
+        app.emit(kind.name(), payload).map_err(|e| format!("Failed to emit {}: {}", kind.name(), e))
+    }
+}
+
+/// One entry of get_event_schema
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EventSchema {
+    pub name: String,
+    pub payload: String,  // Type name in src/types/workblock.ts, or number/null
+    pub acked: bool,      // Payload arrives wrapped as { seq, payload } and must be acknowledged
+    pub description: String,
+}
+
+pub fn event_schema() -> Vec<EventSchema> {
+    EventKind::ALL
+        .iter()
+        .map(|kind| EventSchema {
+            name: kind.name().to_string(),
+            payload: kind.payload().to_string(),
+            acked: kind.acked(),
+            description: kind.description().to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,141 @@
+// Read-only SQL queries over the database for ad-hoc analysis without exporting
+
+use crate::db::get_readonly_db_connection;
+use rusqlite::types::ValueRef;
+use rusqlite::{Batch, Connection, ErrorCode};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::AppHandle;
+
+pub const DEFAULT_MAX_ROWS: usize = 1000;
+pub const MAX_ROWS_LIMIT: usize = 10_000;
+pub const QUERY_TIMEOUT_SECONDS: u64 = 5;
+
+/// Statements a query may start with; anything else is rejected before it reaches SQLite
+const ALLOWED_KEYWORDS: [&str; 2] = ["select", "with"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryLimits {
+    pub max_rows: usize,
+    pub timeout: Duration,
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            max_rows: DEFAULT_MAX_ROWS,
+            timeout: Duration::from_secs(QUERY_TIMEOUT_SECONDS),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,  // One value per column, in column order
+    pub truncated: bool,                     // More rows matched than max_rows
+}
+
+/// First keyword of `sql`, skipping whitespace and comments
+fn leading_keyword(sql: &str) -> String {
+    let mut rest = sql.trim_start();
+    loop {
+        if let Some(comment) = rest.strip_prefix("--") {
+            rest = comment.split_once('\n').map_or("", |(_, after)| after).trim_start();
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, after)| after).trim_start();
+        } else {
+            break;
+        }
+    }
+    rest.chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn to_json(value: ValueRef<'_>) -> serde_json::Value {
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => f.into(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+        ValueRef::Blob(b) => format!("<blob {} bytes>", b.len()).into(),
+    }
+}
+
+/// Run a single SELECT statement, returning at most `limits.max_rows` rows.
+/// The statement is interrupted once it runs longer than `limits.timeout`.
+pub fn execute_readonly_query(conn: &Connection, sql: &str, limits: &QueryLimits) -> Result<QueryResult, String> {
+    let sql = sql.trim().trim_end_matches(';');
+    let keyword = leading_keyword(sql);
+    if !ALLOWED_KEYWORDS.contains(&keyword.as_str()) {
+        return Err("Only SELECT queries are allowed".to_string());
+    }
+    
+    // Exactly one statement, and readonly() catches writes hidden inside a WITH
+    let mut batch = Batch::new(conn, sql);
+    let mut stmt = batch
+        .next()
+        .map_err(|e| format!("Invalid query: {}", e))?
+        .ok_or_else(|| "Query is empty".to_string())?;
+    if !matches!(batch.next(), Ok(None)) {
+        return Err("Only a single statement can be run".to_string());
+    }
+    if !stmt.readonly() {
+        return Err("Only read-only queries are allowed".to_string());
+    }
+    let columns: Vec<String> = stmt.column_names().into_iter().map(str::to_string).collect();
+    
+    // Watchdog: interrupt the query if it is still running when the timeout expires
+    let interrupt = conn.get_interrupt_handle();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+    let timeout = limits.timeout;
+    let watchdog = std::thread::spawn(move || {
+        if done_rx.recv_timeout(timeout) == Err(mpsc::RecvTimeoutError::Timeout) {
+            interrupt.interrupt();
+        }
+    });
+    
+    let mut result = QueryResult {
+        columns,
+        rows: Vec::new(),
+        truncated: false,
+    };
+    let outcome = (|| -> rusqlite::Result<()> {
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            if result.rows.len() == limits.max_rows {
+                result.truncated = true;
+                break;
+            }
+            let values = (0..result.columns.len())
+                .map(|i| row.get_ref(i).map(to_json))
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            result.rows.push(values);
+        }
+        Ok(())
+    })();
+    drop(done_tx);
+    let _ = watchdog.join();
+    
+    match outcome {
+        Ok(()) => Ok(result),
+        Err(rusqlite::Error::SqliteFailure(e, _)) if e.code == ErrorCode::OperationInterrupted => Err(format!(
+            "Query took longer than {} seconds and was stopped",
+            limits.timeout.as_secs()
+        )),
+        Err(e) => Err(format!("Query failed: {}", e)),
+    }
+}
+
+/// Run a user query against a read-only connection to the app database
+pub fn run_readonly_query(app: &AppHandle, sql: &str, max_rows: Option<usize>) -> Result<QueryResult, String> {
+    let limits = QueryLimits {
+        max_rows: max_rows.unwrap_or(DEFAULT_MAX_ROWS).clamp(1, MAX_ROWS_LIMIT),
+        ..QueryLimits::default()
+    };
+    let conn = get_readonly_db_connection(app).map_err(|e| e.to_string())?;
+    execute_readonly_query(&conn, sql, &limits)
+}
@@ -0,0 +1,112 @@
+// Fault injection for exercising recovery paths in integration tests.
+// Only active with the `fault-injection` feature; otherwise every hook is a no-op.
+
+use chrono::{DateTime, Local};
+
+/// Places in the app where a fault can be injected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaultPoint {
+    RolloverArchive,   // Archiving the previous day during the daily rollover
+    SettingsHistory,   // Recording a setting change after the new value was written
+    IntervalLoop,      // Interval loop waking up at a boundary
+}
+
+/// What happens when a fault point is hit
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fault {
+    DbError,  // The operation fails with a database error
+    Abort,    // The running task stops as if it had been aborted
+}
+
+#[cfg(feature = "fault-injection")]
+mod registry {
+    use super::{Fault, FaultPoint};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    pub struct Registry {
+        pub faults: HashMap<FaultPoint, (Fault, u32)>,  // Fault and remaining hits
+        pub clock_offset: chrono::Duration,
+    }
+
+    pub static REGISTRY: Mutex<Option<Registry>> = Mutex::new(None);
+
+    pub fn with<T>(f: impl FnOnce(&mut Registry) -> T) -> T {
+        let mut guard = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        let registry = guard.get_or_insert_with(|| Registry {
+            faults: HashMap::new(),
+            clock_offset: chrono::Duration::zero(),
+        });
+        f(registry)
+    }
+}
+
+/// Make the next `hits` passes through `point` fail with `fault`; 0 hits removes the fault
+#[cfg(feature = "fault-injection")]
+pub fn inject(point: FaultPoint, fault: Fault, hits: u32) {
+    registry::with(|r| {
+        if hits == 0 {
+            r.faults.remove(&point);
+        } else {
+            r.faults.insert(point, (fault, hits));
+        }
+    });
+}
+
+/// Move the app's clock by `offset` (cumulative), e.g. to simulate a system sleep over midnight
+#[cfg(feature = "fault-injection")]
+pub fn jump_clock(offset: chrono::Duration) {
+    registry::with(|r| r.clock_offset += offset);
+}
+
+/// Remove all injected faults and restore the real clock
+#[cfg(feature = "fault-injection")]
+pub fn reset() {
+    registry::with(|r| {
+        r.faults.clear();
+        r.clock_offset = chrono::Duration::zero();
+    });
+}
+
+/// Consume one hit of the fault injected at `point`, if any
+#[cfg(feature = "fault-injection")]
+fn take(point: FaultPoint) -> Option<Fault> {
+    registry::with(|r| {
+        let (fault, hits) = r.faults.get_mut(&point)?;
+        let fault = fault.clone();
+        *hits = hits.saturating_sub(1);
+        if *hits == 0 {
+            r.faults.remove(&point);
+        }
+        Some(fault)
+    })
+}
+
+#[cfg(not(feature = "fault-injection"))]
+fn take(_point: FaultPoint) -> Option<Fault> {
+    None
+}
+
+/// Fail with a database error if one was injected at `point`
+pub fn fail_point(point: FaultPoint) -> rusqlite::Result<()> {
+    match take(point) {
+        Some(Fault::DbError) => Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(1),
+            Some(format!("Injected fault at {:?}", point)),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Whether the task passing through `point` should stop as if aborted
+pub fn should_abort(point: FaultPoint) -> bool {
+    matches!(take(point), Some(Fault::Abort))
+}
+
+/// Current time, shifted by any injected clock jump
+pub fn now() -> DateTime<Local> {
+    #[cfg(feature = "fault-injection")]
+    return Local::now() + registry::with(|r| r.clock_offset);
+    #[cfg(not(feature = "fault-injection"))]
+    Local::now()
+}
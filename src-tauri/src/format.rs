@@ -0,0 +1,82 @@
+// Locale-aware formatting of numbers, dates and times for exports
+
+use crate::settings::{get_locale_settings, DateOrder, DecimalSeparator, LocaleSettings, TimeFormat};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, Timelike};
+use tauri::AppHandle;
+
+/// Formats values for exported files according to the user's locale settings
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Formatter {
+    locale: LocaleSettings,
+}
+
+impl Formatter {
+    pub fn new(locale: LocaleSettings) -> Self {
+        Self { locale }
+    }
+
+    /// Formatter for the locale currently configured in settings
+    pub fn from_settings(app: &AppHandle) -> Self {
+        Self::new(get_locale_settings(app))
+    }
+
+    pub fn locale(&self) -> LocaleSettings {
+        self.locale
+    }
+
+    /// A number with a fixed number of decimals, e.g. "1.50" or "1,50"
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value);
+        match self.locale.decimal_separator {
+            DecimalSeparator::Dot => formatted,
+            DecimalSeparator::Comma => formatted.replace('.', ","),
+        }
+    }
+
+    /// Minutes as decimal hours with two decimals, e.g. 90 -> "1.50"
+    pub fn hours(&self, minutes: i32) -> String {
+        self.number(minutes as f64 / 60.0, 2)
+    }
+
+    pub fn date(&self, date: NaiveDate) -> String {
+        let pattern = match self.locale.date_order {
+            DateOrder::Ymd => "%Y-%m-%d",
+            DateOrder::Dmy => "%d.%m.%Y",
+            DateOrder::Mdy => "%m/%d/%Y",
+        };
+        date.format(pattern).to_string()
+    }
+
+    /// A stored YYYY-MM-DD date; anything else is passed through unchanged
+    pub fn date_str(&self, date: &str) -> String {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").map_or_else(|_| date.to_string(), |d| self.date(d))
+    }
+
+    pub fn time(&self, time: NaiveTime) -> String {
+        match self.locale.time_format {
+            TimeFormat::H24 => time.format("%H:%M").to_string(),
+            TimeFormat::H12 => {
+                let (pm, hour) = time.hour12();
+                format!("{}:{:02} {}", hour, time.minute(), if pm { "PM" } else { "AM" })
+            }
+        }
+    }
+
+    pub fn datetime(&self, datetime: DateTime<Local>) -> String {
+        format!("{} {}", self.date(datetime.date_naive()), self.time(datetime.time()))
+    }
+
+    /// A stored RFC 3339 timestamp in local time; anything else is passed through unchanged
+    pub fn datetime_str(&self, timestamp: &str) -> String {
+        DateTime::parse_from_rfc3339(timestamp)
+            .map_or_else(|_| timestamp.to_string(), |t| self.datetime(t.with_timezone(&Local)))
+    }
+
+    /// CSV field delimiter; locales with a decimal comma use semicolons so numbers stay in one field
+    pub fn csv_delimiter(&self) -> char {
+        match self.locale.decimal_separator {
+            DecimalSeparator::Dot => ',',
+            DecimalSeparator::Comma => ';',
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// Global hotkeys: open the prompt early, repeat the last interval and start/stop a workblock from anywhere
+
+use crate::db::get_active_workblock;
+use crate::settings::{get_hotkey_settings, set_hotkey_settings, HotkeySettings};
+use crate::timer::TimerManager;
+use crate::window_manager::WindowManager;
+use std::sync::Arc;
+use tauri::{async_runtime, AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Copy)]
+enum HotkeyAction {
+    OpenPrompt,
+    RepeatLast,
+    ToggleWorkblock,
+}
+
+/// Parse a hotkey string like "CommandOrControl+Shift+L"; empty means disabled
+fn parse_hotkey(hotkey: &str) -> Result<Option<Shortcut>, String> {
+    let hotkey = hotkey.trim();
+    if hotkey.is_empty() {
+        return Ok(None);
+    }
+    hotkey
+        .parse::<Shortcut>()
+        .map(Some)
+        .map_err(|e| format!("Invalid hotkey '{}': {}", hotkey, e))
+}
+
+/// (Re-)register all hotkeys from settings, replacing any previously registered ones
+pub fn register_hotkeys(app: &AppHandle) -> Result<(), String> {
+    let hotkeys = get_hotkey_settings(app);
+    let global_shortcut = app.global_shortcut();
+    global_shortcut
+        .unregister_all()
+        .map_err(|e| format!("Failed to unregister hotkeys: {}", e))?;
+
+    for (hotkey, action) in [
+        (&hotkeys.open_prompt, HotkeyAction::OpenPrompt),
+        (&hotkeys.repeat_last, HotkeyAction::RepeatLast),
+        (&hotkeys.toggle_workblock, HotkeyAction::ToggleWorkblock),
+    ] {
+        if let Some(shortcut) = parse_hotkey(hotkey)? {
+            global_shortcut
+                .on_shortcut(shortcut, move |app, _shortcut, event| {
+                    if event.state == ShortcutState::Pressed {
+                        let app = app.clone();
+                        async_runtime::spawn(async move {
+                            if let Err(e) = handle_hotkey(&app, action).await {
+                                eprintln!("[HOTKEYS] {:?} failed: {}", action, e);
+                            }
+                        });
+                    }
+                })
+                .map_err(|e| format!("Failed to register hotkey '{}': {}", hotkey, e))?;
+            println!("[HOTKEYS] Registered {} for {:?}", hotkey, action);
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate, store and re-register hotkeys
+pub fn update_hotkeys(app: &AppHandle, hotkeys: &HotkeySettings) -> Result<HotkeySettings, String> {
+    parse_hotkey(&hotkeys.open_prompt)?;
+    parse_hotkey(&hotkeys.toggle_workblock)?;
+    parse_hotkey(&hotkeys.repeat_last)?;
+    set_hotkey_settings(app, hotkeys)?;
+    register_hotkeys(app)?;
+    Ok(get_hotkey_settings(app))
+}
+
+async fn handle_hotkey(app: &AppHandle, action: HotkeyAction) -> Result<(), String> {
+    match action {
+        HotkeyAction::OpenPrompt => {
+            let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+            let interval_id = timer_manager.lock().await.get_state().await.current_interval_id;
+            let interval_id = interval_id.ok_or_else(|| "No interval is running".to_string())?;
+            // The interval is still running, so no auto-away timer: the regular prompt arms it at interval end
+            let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+            let window_mgr = window_manager.lock().await;
+            // A prompt still waiting on an earlier interval is answered first
+            if !window_mgr.is_prompt_visible().await {
+                window_mgr.show_prompt_window(interval_id).await?;
+            }
+            // Asked for explicitly, so focus it even when prompts are passive
+            Ok(window_mgr.focus_prompt_window().await?)
+        }
+        HotkeyAction::RepeatLast => crate::repeat_last_from_outside(app.clone()).await,
+        HotkeyAction::ToggleWorkblock => {
+            let active = get_active_workblock(app).map_err(|e| e.to_string())?;
+            match active.and_then(|wb| wb.id) {
+                Some(workblock_id) => {
+                    crate::cancel_workblock_cmd(app.clone(), workblock_id, None, None).await?;
+                    Ok(())
+                }
+                None => {
+                    let minutes = get_hotkey_settings(app).workblock_minutes;
+                    crate::start_workblock(app.clone(), minutes, None).await?;
+                    Ok(())
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// Translations of strings the backend shows itself (the tray menu); the frontend has its own
+
+use crate::settings::Language;
+
+/// Backend strings that are shown to the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    StartWorkblock,
+    CustomDuration,
+    LogCurrentInterval,
+    RepeatLastInterval,
+    NoRecentActivities,
+    PausePromptsFor,    // {} is the pause length
+    ResumePromptsLeft,  // {} is the time left
+    ViewSummary,
+    ViewLastWords,
+    ShowWindow,
+    HideWindow,
+    Quit,
+    Minutes,
+    StatusAvailable,
+    StatusFocusUntil,   // {} is the end time
+    StatusBreakUntil,   // {} is the end time
+    StatusPausedUntil,  // {} is the end time
+}
+
+/// `text` in `language`
+pub fn tr(language: Language, text: Text) -> &'static str {
+    match language {
+        Language::En => match text {
+            Text::StartWorkblock => "Start Workblock",
+            Text::CustomDuration => "Custom...",
+            Text::LogCurrentInterval => "Log Current Interval",
+            Text::RepeatLastInterval => "Same as Last Interval",
+            Text::NoRecentActivities => "No recent activities",
+            Text::PausePromptsFor => "Pause Prompts for {}",
+            Text::ResumePromptsLeft => "Resume Prompts ({} left)",
+            Text::ViewSummary => "View Summary",
+            Text::ViewLastWords => "View Last Words",
+            Text::ShowWindow => "Show Window",
+            Text::HideWindow => "Hide Window",
+            Text::Quit => "Quit",
+            Text::Minutes => "minutes",
+            Text::StatusAvailable => "Available",
+            Text::StatusFocusUntil => "In a focus block until {}",
+            Text::StatusBreakUntil => "On a break until {}",
+            Text::StatusPausedUntil => "Not to be disturbed until {}",
+        },
+        Language::De => match text {
+            Text::StartWorkblock => "Arbeitsblock starten",
+            Text::CustomDuration => "Benutzerdefiniert...",
+            Text::LogCurrentInterval => "Aktuelles Intervall erfassen",
+            Text::RepeatLastInterval => "Wie das letzte Intervall",
+            Text::NoRecentActivities => "Keine letzten Aktivitäten",
+            Text::PausePromptsFor => "Abfragen für {} pausieren",
+            Text::ResumePromptsLeft => "Abfragen fortsetzen (noch {})",
+            Text::ViewSummary => "Zusammenfassung anzeigen",
+            Text::ViewLastWords => "Letzte Einträge anzeigen",
+            Text::ShowWindow => "Fenster anzeigen",
+            Text::HideWindow => "Fenster ausblenden",
+            Text::Quit => "Beenden",
+            Text::Minutes => "Minuten",
+            Text::StatusAvailable => "Verfügbar",
+            Text::StatusFocusUntil => "In einem Fokusblock bis {}",
+            Text::StatusBreakUntil => "In der Pause bis {}",
+            Text::StatusPausedUntil => "Bitte nicht stören bis {}",
+        },
+        Language::Fr => match text {
+            Text::StartWorkblock => "Démarrer un bloc de travail",
+            Text::CustomDuration => "Personnalisé...",
+            Text::LogCurrentInterval => "Noter l'intervalle en cours",
+            Text::RepeatLastInterval => "Comme l'intervalle précédent",
+            Text::NoRecentActivities => "Aucune activité récente",
+            Text::PausePromptsFor => "Suspendre les invites pendant {}",
+            Text::ResumePromptsLeft => "Reprendre les invites (encore {})",
+            Text::ViewSummary => "Voir le résumé",
+            Text::ViewLastWords => "Voir les derniers mots",
+            Text::ShowWindow => "Afficher la fenêtre",
+            Text::HideWindow => "Masquer la fenêtre",
+            Text::Quit => "Quitter",
+            Text::Minutes => "minutes",
+            Text::StatusAvailable => "Disponible",
+            Text::StatusFocusUntil => "En session de concentration jusqu'à {}",
+            Text::StatusBreakUntil => "En pause jusqu'à {}",
+            Text::StatusPausedUntil => "Ne pas déranger jusqu'à {}",
+        },
+        Language::Es => match text {
+            Text::StartWorkblock => "Iniciar bloque de trabajo",
+            Text::CustomDuration => "Personalizado...",
+            Text::LogCurrentInterval => "Registrar intervalo actual",
+            Text::RepeatLastInterval => "Igual que el intervalo anterior",
+            Text::NoRecentActivities => "Sin actividades recientes",
+            Text::PausePromptsFor => "Pausar avisos durante {}",
+            Text::ResumePromptsLeft => "Reanudar avisos (quedan {})",
+            Text::ViewSummary => "Ver resumen",
+            Text::ViewLastWords => "Ver últimas palabras",
+            Text::ShowWindow => "Mostrar ventana",
+            Text::HideWindow => "Ocultar ventana",
+            Text::Quit => "Salir",
+            Text::Minutes => "minutos",
+            Text::StatusAvailable => "Disponible",
+            Text::StatusFocusUntil => "En un bloque de concentración hasta las {}",
+            Text::StatusBreakUntil => "En descanso hasta las {}",
+            Text::StatusPausedUntil => "No molestar hasta las {}",
+        },
+    }
+}
+
+/// `text` in `language` with its placeholder filled in
+pub fn tr_with(language: Language, text: Text, arg: &str) -> String {
+    tr(language, text).replacen("{}", arg, 1)
+}
+
+/// A workblock length for menus, e.g. "45 minutes", "2 h" or "1 h 30 min"
+pub fn duration_label(language: Language, duration_minutes: i32) -> String {
+    match (duration_minutes / 60, duration_minutes % 60) {
+        (0, minutes) => format!("{} {}", minutes, tr(language, Text::Minutes)),
+        (hours, 0) => format!("{} h", hours),
+        (hours, minutes) => format!("{} h {} min", hours, minutes),
+    }
+}
@@ -0,0 +1,131 @@
+// System idle detection: time since the last keyboard/mouse input
+
+use crate::settings::get_idle_threshold_seconds;
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Whether the user has been idle for at least the configured threshold.
+/// Always false when idle detection is disabled or unsupported on this platform.
+pub fn is_user_idle(app: &AppHandle) -> bool {
+    let threshold = get_idle_threshold_seconds(app);
+    if threshold == 0 {
+        return false;
+    }
+    system_idle_time()
+        .map(|idle| idle >= Duration::from_secs(threshold))
+        .unwrap_or(false)
+}
+
+/// Time since the last user input, if the platform exposes it
+pub fn system_idle_time() -> Option<Duration> {
+    platform_idle_time()
+}
+
+#[cfg(target_os = "macos")]
+fn platform_idle_time() -> Option<Duration> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-c", "IOHIDSystem", "-d", "4"])
+        .output()
+        .ok()?;
+    parse_ioreg_idle_time(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_idle_time() -> Option<Duration> {
+    #[repr(C)]
+    struct LastInputInfo {
+        cb_size: u32,
+        dw_time: u32,
+    }
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetLastInputInfo(plii: *mut LastInputInfo) -> i32;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetTickCount() -> u32;
+    }
+
+    let mut info = LastInputInfo {
+        cb_size: std::mem::size_of::<LastInputInfo>() as u32,
+        dw_time: 0,
+    };
+    // SAFETY: `info` is a properly sized, initialized LASTINPUTINFO
+    if unsafe { GetLastInputInfo(&mut info) } == 0 {
+        return None;
+    }
+    // SAFETY: GetTickCount has no preconditions
+    let now = unsafe { GetTickCount() };
+    Some(Duration::from_millis(now.wrapping_sub(info.dw_time) as u64))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn platform_idle_time() -> Option<Duration> {
+    // X11 only, and only if xprintidle is installed
+    let output = std::process::Command::new("xprintidle").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_xprintidle(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether the screen is locked; false when the platform can't tell
+pub fn is_screen_locked() -> bool {
+    platform_screen_locked().unwrap_or(false)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_screen_locked() -> Option<bool> {
+    let output = std::process::Command::new("ioreg").args(["-n", "Root", "-d", "1"]).output().ok()?;
+    Some(parse_ioreg_screen_locked(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_screen_locked() -> Option<bool> {
+    // Windows only reports locking through session notifications to a window
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn platform_screen_locked() -> Option<bool> {
+    // systemd-logind sessions only
+    let session = std::env::var("XDG_SESSION_ID").unwrap_or_else(|_| "auto".to_string());
+    let output = std::process::Command::new("loginctl")
+        .args(["show-session", &session, "-p", "LockedHint"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_locked_hint(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether `ioreg -n Root -d 1` output shows the console session's screen as locked
+pub fn parse_ioreg_screen_locked(output: &str) -> bool {
+    output.contains("\"CGSSessionScreenIsLocked\"=Yes")
+}
+
+/// Parse `loginctl show-session <id> -p LockedHint` output ("LockedHint=yes")
+pub fn parse_locked_hint(output: &str) -> Option<bool> {
+    match output.trim().strip_prefix("LockedHint=")? {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse `"HIDIdleTime" = <nanoseconds>` from `ioreg -c IOHIDSystem` output
+pub fn parse_ioreg_idle_time(output: &str) -> Option<Duration> {
+    output
+        .lines()
+        .find_map(|line| line.split("\"HIDIdleTime\" = ").nth(1))
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_nanos)
+}
+
+/// Parse xprintidle output (milliseconds)
+pub fn parse_xprintidle(output: &str) -> Option<Duration> {
+    output.trim().parse::<u64>().ok().map(Duration::from_millis)
+}
@@ -0,0 +1,228 @@
+// Import another log15 database (a backup or a copy from another machine), either replacing the
+// current data or merging its workblocks into it.
+
+use crate::backup::{create_backup_in, get_backup_dir, restore_backup_into, BackupInfo, BackupReason};
+use crate::db::{create_schema, get_db_path, get_today_date, IntervalProvenance};
+use crate::error::Log15Error;
+use chrono::Utc;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+use tauri::AppHandle;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    Replace,  // The imported database replaces the current one
+    Merge,    // Workblocks not present yet are added to the current database
+}
+
+impl ImportMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ImportMode::Replace => "replace",
+            ImportMode::Merge => "merge",
+        }
+    }
+    
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "replace" => Some(ImportMode::Replace),
+            "merge" => Some(ImportMode::Merge),
+            _ => None,
+        }
+    }
+}
+
+/// What a merge added to the target database
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct MergeResult {
+    pub workblocks_imported: usize,
+    pub intervals_imported: usize,
+    pub duplicates_skipped: usize,  // Same (date, start_time) as an existing workblock
+    pub active_skipped: usize,      // Still running in the source; importing would leave two active
+    pub dates: Vec<String>,         // Dates that received workblocks, ascending
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ImportSummary {
+    pub mode: ImportMode,
+    pub merge: Option<MergeResult>,   // None for replace
+    pub rebuilt_archives: Vec<String>,
+    pub backup: BackupInfo,           // The database as it was before the import
+}
+
+/// Copy the database at `path` into memory and bring it up to the current schema, so databases
+/// from older versions merge with the same columns and UTC timestamps as the current one
+pub fn stage_database(path: &Path) -> Result<Connection, String> {
+    let mut staged = Connection::open_in_memory().map_err(|e| e.to_string())?;
+    restore_backup_into(&mut staged, path)?;
+    create_schema(&staged).map_err(|e| format!("Failed to upgrade imported database: {}", e))?;
+    Ok(staged)
+}
+
+/// Columns of `table` in `conn`, without those in `skip`
+fn table_columns(conn: &Connection, table: &str, skip: &[&str]) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(columns.into_iter().filter(|c| !skip.contains(&c.as_str())).collect())
+}
+
+/// Rows of `table` matching `filter` (one `?1` parameter), as the values of `columns` after the id
+fn select_rows(
+    conn: &Connection,
+    table: &str,
+    id_column: &str,
+    columns: &[String],
+    filter: &str,
+    param: i64,
+) -> rusqlite::Result<Vec<(i64, Vec<Value>)>> {
+    let sql = format!("SELECT {}, {} FROM {} WHERE {} ORDER BY {}", id_column, columns.join(", "), table, filter, id_column);
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params![param], |row| {
+        let values = (1..=columns.len())
+            .map(|i| row.get::<_, Value>(i))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok((row.get(0)?, values))
+    })?;
+    rows.collect()
+}
+
+fn insert_row(conn: &Connection, table: &str, columns: &[String], values: Vec<Value>) -> rusqlite::Result<i64> {
+    let placeholders = (1..=columns.len()).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ");
+    conn.execute(
+        &format!("INSERT INTO {} ({}) VALUES ({})", table, columns.join(", "), placeholders),
+        params_from_iter(values),
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Add the workblocks of `source` that `target` doesn't have yet (matched by date and start
+/// time), with their intervals, activity splits, app usage and context snapshots under new ids. Imported
+/// workblocks are left unarchived so their days can be archived again, and their intervals are
+/// marked imported. Attachments aren't copied: their files live in the other machine's app data.
+/// Runs in one transaction.
+pub fn merge_database(target: &Connection, source: &Connection) -> crate::error::Result<MergeResult> {
+    let tx = target.unchecked_transaction()?;
+    let mut result = MergeResult::default();
+    let mut dates = BTreeSet::new();
+    
+    // Columns both sides have (the source was staged to the current schema)
+    let workblock_columns = table_columns(&tx, "workblocks", &["id"])?;
+    let interval_columns = table_columns(&tx, "intervals", &["id", "workblock_id"])?;
+    // Per-interval tables, copied along with their interval
+    let interval_tables = ["interval_app_usage", "interval_context", "interval_activities"]
+        .into_iter()
+        .map(|table| {
+            let columns = table_columns(&tx, table, &["interval_id"])?;
+            let mut insert_columns = vec!["interval_id".to_string()];
+            insert_columns.extend(columns.iter().cloned());
+            Ok((table, columns, insert_columns))
+        })
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let date_index = workblock_columns.iter().position(|c| c == "date");
+    let start_index = workblock_columns.iter().position(|c| c == "start_time");
+    let status_index = workblock_columns.iter().position(|c| c == "status");
+    let archived_index = workblock_columns.iter().position(|c| c == "is_archived");
+    let provenance_index = interval_columns.iter().position(|c| c == "provenance");
+    let (Some(date_index), Some(start_index), Some(status_index)) = (date_index, start_index, status_index) else {
+        return Err(Log15Error::InvalidInput("Unexpected workblocks table".to_string()));
+    };
+    
+    let mut workblock_ids = Vec::new();  // (source id, target id)
+    for (source_id, mut values) in select_rows(source, "workblocks", "id", &workblock_columns, "id > ?1", 0)? {
+        if values[status_index] == Value::Text("active".to_string()) {
+            result.active_skipped += 1;
+            continue;
+        }
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM workblocks WHERE date = ?1 AND start_time = ?2)",
+            params![&values[date_index], &values[start_index]],
+            |row| row.get(0),
+        )?;
+        if exists {
+            result.duplicates_skipped += 1;
+            continue;
+        }
+        if let Value::Text(date) = &values[date_index] {
+            dates.insert(date.clone());
+        }
+        if let Some(i) = archived_index {
+            values[i] = Value::Integer(0);
+        }
+        workblock_ids.push((source_id, insert_row(&tx, "workblocks", &workblock_columns, values)?));
+        result.workblocks_imported += 1;
+    }
+    
+    let mut interval_insert_columns = vec!["workblock_id".to_string()];
+    interval_insert_columns.extend(interval_columns.iter().cloned());
+    for (source_workblock, target_workblock) in workblock_ids {
+        for (source_interval, mut values) in select_rows(source, "intervals", "id", &interval_columns, "workblock_id = ?1", source_workblock)? {
+            if let Some(i) = provenance_index {
+                values[i] = Value::Text(IntervalProvenance::Imported.as_str().to_string());
+            }
+            let mut row = vec![Value::Integer(target_workblock)];
+            row.extend(values);
+            let target_interval = insert_row(&tx, "intervals", &interval_insert_columns, row)?;
+            result.intervals_imported += 1;
+    
+            for (table, columns, insert_columns) in &interval_tables {
+                for (_, values) in select_rows(source, table, "interval_id", columns, "interval_id = ?1", source_interval)? {
+                    let mut row = vec![Value::Integer(target_interval)];
+                    row.extend(values);
+                    insert_row(&tx, table, insert_columns, row)?;
+                }
+            }
+        }
+    }
+    
+    tx.commit()?;
+    result.dates = dates.into_iter().collect();
+    Ok(result)
+}
+
+/// Import the log15 database at `path`. The current database is backed up first; a merge then
+/// re-archives the past days it added workblocks to.
+pub fn import_database(app: &AppHandle, path: &Path, mode: ImportMode) -> Result<ImportSummary, String> {
+    let staged = stage_database(path)?;
+    let mut conn = Connection::open(get_db_path(app)).map_err(|e| e.to_string())?;
+    let backup = create_backup_in(&conn, &get_backup_dir(app), BackupReason::PreImport, Utc::now())?;
+    
+    match mode {
+        ImportMode::Replace => {
+            restore_backup_into(&mut conn, path)?;
+            create_schema(&conn).map_err(|e| e.to_string())?;
+            crate::settings::load_day_start_hour(&conn);
+            crate::cache::clear();
+            println!("[IMPORT] Replaced the database with {}", path.display());
+            Ok(ImportSummary { mode, merge: None, rebuilt_archives: Vec::new(), backup })
+        }
+        ImportMode::Merge => {
+            let merge = merge_database(&conn, &staged).map_err(|e| format!("Merge failed: {}", e))?;
+            drop(conn);
+            crate::cache::clear();
+    
+            // Today is archived by the rollover or archive time as usual
+            let today = get_today_date();
+            let mut rebuilt_archives = Vec::new();
+            for date in merge.dates.iter().filter(|date| date.as_str() < today.as_str()) {
+                let conn = crate::db::get_db_connection(app).map_err(|e| e.to_string())?;
+                if crate::db::archive_unless_quiet(app, &conn, date).map_err(|e| e.to_string())? {
+                    rebuilt_archives.push(date.clone());
+                }
+            }
+            println!(
+                "[IMPORT] Merged {} workblocks from {} ({} duplicates skipped)",
+                merge.workblocks_imported,
+                path.display(),
+                merge.duplicates_skipped
+            );
+            Ok(ImportSummary { mode, merge: Some(merge), rebuilt_archives, backup })
+        }
+    }
+}
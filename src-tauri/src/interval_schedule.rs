@@ -0,0 +1,74 @@
+// Interval math for a workblock: how many intervals it has, where they start and end, and how much
+// time is left. The timer, interval answers, auto-away and the status views all go through here, so
+// they agree on when a workblock ends. Every function takes the interval length explicitly.
+
+use crate::timing::timing;
+use chrono::{DateTime, Local, Timelike};
+
+/// Configured length of one interval, from the timing profile
+pub fn interval_seconds() -> i64 {
+    timing().interval_seconds
+}
+
+/// Number of intervals in a workblock of `duration_minutes`; a partial last interval counts as a whole one
+pub fn total_intervals(duration_minutes: i32, interval_seconds: i64) -> i32 {
+    let interval_seconds = interval_seconds.max(1);
+    ((duration_minutes.max(0) as i64 * 60 + interval_seconds - 1) / interval_seconds) as i32
+}
+
+/// Length in whole minutes (rounded up) of `total_intervals` intervals
+pub fn planned_minutes(total_intervals: i32, interval_seconds: i64) -> i32 {
+    ((total_intervals.max(0) as i64 * interval_seconds.max(1) + 59) / 60) as i32
+}
+
+/// Whether interval `interval_number` is the final one of a workblock with `total_intervals`
+pub fn is_last_interval(interval_number: i32, total_intervals: i32) -> bool {
+    interval_number >= total_intervals
+}
+
+/// Number of whole intervals that have ended between `schedule_start` and `now`
+pub fn intervals_elapsed(schedule_start: DateTime<Local>, now: DateTime<Local>, interval_seconds: i64) -> i32 {
+    ((now - schedule_start).num_seconds().max(0) / interval_seconds.max(1)) as i32
+}
+
+/// The interval boundary at or before `time`, counted from midnight
+pub fn align_to_boundary(time: DateTime<Local>, interval_seconds: i64) -> DateTime<Local> {
+    let into_interval = time.num_seconds_from_midnight() as i64 % interval_seconds.max(1);
+    time.with_nanosecond(0).unwrap_or(time) - chrono::Duration::seconds(into_interval)
+}
+
+/// Wall-clock time at which interval `interval_number` ends (interval 1 starts at `schedule_start`)
+pub fn interval_boundary(schedule_start: DateTime<Local>, interval_number: i32, interval_seconds: i64) -> DateTime<Local> {
+    schedule_start + chrono::Duration::seconds(interval_seconds * interval_number as i64)
+}
+
+/// When the current interval (started at `interval_start`) ends
+pub fn interval_end(interval_start: DateTime<Local>, interval_seconds: i64) -> DateTime<Local> {
+    interval_boundary(interval_start, 1, interval_seconds)
+}
+
+/// When the workblock ends, given the start of its current interval
+pub fn workblock_end(
+    interval_start: DateTime<Local>,
+    current_interval_number: i32,
+    total_intervals: i32,
+    interval_seconds: i64,
+) -> DateTime<Local> {
+    let intervals_left = (total_intervals - current_interval_number).max(0) + 1;
+    interval_boundary(interval_start, intervals_left, interval_seconds)
+}
+
+/// When to warn that the interval ending at `boundary` is about to end, or None if the warning is off
+/// (`seconds_before` of 0) or wouldn't come after the interval started
+pub fn warning_time(boundary: DateTime<Local>, seconds_before: u64, interval_seconds: i64) -> Option<DateTime<Local>> {
+    let seconds_before = seconds_before as i64;
+    if seconds_before == 0 || seconds_before >= interval_seconds {
+        return None;
+    }
+    Some(boundary - chrono::Duration::seconds(seconds_before))
+}
+
+/// Seconds from `now` until `end`, never negative
+pub fn remaining_seconds(end: DateTime<Local>, now: DateTime<Local>) -> i64 {
+    (end - now).num_seconds().max(0)
+}
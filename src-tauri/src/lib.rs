@@ -1,25 +1,81 @@
+pub mod acked_events;
+pub mod activity_capture;
+pub mod ai;
+pub mod api;
+pub mod attachments;
+pub mod backup;
+pub mod cache;
+pub mod changes;
+pub mod compliance_export;
+pub mod computed_cache;
+pub mod context_snapshot;
+pub mod daily_note;
 pub mod db;
+pub mod diagnostics;
+pub mod dnd;
+pub mod error;
+pub mod events;
+pub mod explorer;
+pub mod faults;
+pub mod format;
+pub mod hotkeys;
+pub mod i18n;
+pub mod idle;
+pub mod import;
+pub mod interval_schedule;
+pub mod onboarding;
+#[cfg(feature = "parquet-export")]
+pub mod parquet_export;
+pub mod reports;
+#[cfg(feature = "rest-api")]
+pub mod rest_api;
+pub mod rules;
+pub mod settings;
+pub mod share_card;
+pub mod speech;
+pub mod startup;
+pub mod stats;
+pub mod status_file;
+pub mod status_page;
+pub mod taxonomy;
+pub mod theme;
+pub mod time_audit;
 pub mod timer;
+pub mod timer_selftest;
+pub mod timing;
 pub mod tray;
+pub mod watchdog;
+pub mod webhooks;
 pub mod window_manager;
+pub mod window_state;
+pub mod wipe;
 
 pub use tray::TrayManager;
 
 use db::{
-    init_db, create_workblock, get_active_workblock, cancel_workblock, get_workblock_by_id,
+    init_db, create_workblock, get_active_workblock, get_workblock_by_id, set_cancel_reason,
     get_workblocks_by_date,
     add_interval, update_interval_words, get_intervals_by_workblock, get_current_interval,
-    check_and_reset_daily, get_archived_day, get_all_archived_dates, get_today_date,
-    generate_workblock_visualization, generate_daily_aggregate, generate_daily_visualization_data,
+    check_and_reset_daily, archive_daily_data, get_archived_day, get_all_archived_dates, get_today_date,
+    generate_workblock_visualization, generate_daily_visualization_data,
+    generate_monthly_summary, generate_weekly_review, week_start_for, suggest_activities, get_attachments_by_interval,
+    set_activity_alias, remove_activity_alias, get_activity_aliases,
+    get_reclassify_offer, reclassify_interval, set_workblock_project, set_interval_project,
+    pin_activity, unpin_activity, get_pinned_activities, generate_prompt_suggestions,
 };
+use error::Log15Error;
+use events::{emit_event, AppEvent};
 use timer::TimerManager;
-use window_manager::WindowManager;
+use window_manager::{PromptQueue, WindowManager};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tauri::{Manager, Emitter, async_runtime};
+use tauri::{Manager, async_runtime};
 
 // Re-export types for frontend
-pub use db::{Workblock, Interval, DailyArchive, WorkblockStatus, IntervalStatus};
+pub use db::{
+    Workblock, Interval, DailyArchive, WorkblockStatus, CancelReason, IntervalStatus, IntervalAttachment, ActivityAlias,
+    WorkblockVisualization, DailyAggregate, DailyVisualizationData, MonthlySummary, WeeklyReview, TimelineLimits,
+};
 
 // ============================================================================
 // Tauri Commands
@@ -30,9 +86,15 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// Versioned command names and deprecations, used by the frontend's API facade
+#[tauri::command]
+fn get_api_manifest() -> api::ApiManifest {
+    api::api_manifest()
+}
+
 #[tauri::command]
-fn init_database(app: tauri::AppHandle) -> Result<(), String> {
-    init_db(&app).map_err(|e| e.to_string())?;
+fn init_database(app: tauri::AppHandle) -> Result<(), Log15Error> {
+    init_db(&app)?;
     Ok(())
 }
 
@@ -41,18 +103,26 @@ fn init_database(app: tauri::AppHandle) -> Result<(), String> {
 async fn start_workblock(
     app: tauri::AppHandle,
     duration_minutes: i32,
-) -> Result<Workblock, String> {
+    title: Option<String>,
+) -> Result<Workblock, Log15Error> {
+    let duration_minutes = timer::validate_duration_minutes(duration_minutes)?;
+    // Reject a bad title before a workblock exists that the timer never picks up
+    db::normalize_workblock_title(title.as_deref())?;
+    
     // Check and reset daily if needed
-    check_and_reset_daily(&app).map_err(|e| e.to_string())?;
+    check_and_reset_daily(&app)?;
     
     // Check if there's already an active workblock
-    if let Ok(Some(active)) = get_active_workblock(&app) {
-        return Err(format!("Workblock {} is already active", active.id.unwrap()));
+    if let Ok(Some(_)) = get_active_workblock(&app) {
+        return Err(Log15Error::AlreadyRunning);
     }
     
     // Create workblock
-    let workblock = create_workblock(&app, duration_minutes).map_err(|e| e.to_string())?;
+    let mut workblock = create_workblock(&app, duration_minutes)?;
     let workblock_id = workblock.id.unwrap();
+    if title.is_some() {
+        workblock = db::update_workblock_title(&app, workblock_id, title.as_deref())?;
+    }
     
     // Get timer manager from app state
     let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
@@ -64,24 +134,34 @@ async fn start_workblock(
     Ok(workblock)
 }
 
+/// Label a workblock with what it is for, e.g. "Write Q3 report"; None or blank clears it
+#[tauri::command]
+fn update_workblock_title(app: tauri::AppHandle, workblock_id: i64, title: Option<String>) -> Result<Workblock, Log15Error> {
+    db::update_workblock_title(&app, workblock_id, title.as_deref())
+}
+
 #[tauri::command]
-async fn cancel_workblock_cmd(app: tauri::AppHandle, workblock_id: i64) -> Result<Workblock, String> {
+async fn cancel_workblock_cmd(
+    app: tauri::AppHandle,
+    workblock_id: i64,
+    reason: Option<CancelReason>,
+    note: Option<String>,
+) -> Result<Workblock, Log15Error> {
     // Verify workblock exists and is active
-    let workblock = get_active_workblock(&app)
-        .map_err(|e| format!("Failed to get active workblock: {}", e))?
-        .ok_or_else(|| "No active workblock found".to_string())?;
+    let workblock = get_active_workblock(&app)?.ok_or(Log15Error::NoActiveWorkblock)?;
     
     if workblock.id != Some(workblock_id) {
-        return Err(format!("Workblock ID mismatch: expected {}, got {:?}", workblock_id, workblock.id));
+        return Err(Log15Error::InvalidInput(format!("Workblock ID mismatch: expected {}, got {:?}", workblock_id, workblock.id)));
     }
     
     // Get the current interval before cancelling (to remember which interval was active)
     // This is optional - if there's no current interval, that's fine
     let _current_interval = get_current_interval(&app, workblock_id).ok().flatten();
     
-    // Hide prompt window if it's open
+    // Hide prompt window if it's open, along with the prompts queued behind it
     let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
     let window_mgr = window_manager.lock().await;
+    window_mgr.clear_prompt_queue().await;
     window_mgr.hide_prompt_window().await.ok();
     drop(window_mgr);
     
@@ -96,33 +176,187 @@ async fn cancel_workblock_cmd(app: tauri::AppHandle, workblock_id: i64) -> Resul
     })?;
     drop(timer);
     
-    // Get the cancelled workblock
-    let cancelled = get_workblock_by_id(&app, workblock_id)
+    // Record why it was cancelled and return the cancelled workblock
+    let cancelled = set_cancel_reason(&app, workblock_id, reason, note.as_deref())
         .map_err(|e| format!("Failed to get cancelled workblock: {}", e))?;
     
     Ok(cancelled)
 }
 
+/// Reopen a recently cancelled workblock and continue its timer at the current interval offset
+#[tauri::command]
+async fn resume_cancelled_workblock(app: tauri::AppHandle, workblock_id: i64) -> Result<Workblock, Log15Error> {
+    let window_minutes = settings::get_resume_window_minutes(&app);
+    let conn = db::get_db_connection(&app)?;
+    let workblock = db::reopen_cancelled_workblock(&conn, workblock_id, window_minutes, chrono::Local::now())?;
+    drop(conn);
+    cache::workblock_written(&app, workblock_id);
+    
+    let started_at = chrono::DateTime::parse_from_rfc3339(&workblock.start_time)
+        .map_err(|e| format!("Invalid start_time: {}", e))?
+        .with_timezone(&chrono::Local);
+    let duration_minutes = workblock.duration_minutes.unwrap_or(60);
+    
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer = timer_manager.lock().await;
+    timer.resume_workblock(workblock_id, started_at, duration_minutes).await?;
+    drop(timer);
+    
+    let _ = emit_event(&app, AppEvent::WorkblockResumed(workblock_id));
+    Ok(workblock)
+}
+
+/// Extend or shorten the running workblock without restarting it
+#[tauri::command]
+async fn adjust_workblock_duration(
+    app: tauri::AppHandle,
+    workblock_id: i64,
+    new_duration_minutes: i32,
+) -> Result<Workblock, Log15Error> {
+    let new_duration_minutes = timer::validate_duration_minutes(new_duration_minutes)?;
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer = timer_manager.lock().await;
+    timer.adjust_workblock_duration(workblock_id, new_duration_minutes).await?;
+    drop(timer);
+    
+    let workblock = get_workblock_by_id(&app, workblock_id)?;
+    let _ = emit_event(&app, AppEvent::WorkblockAdjusted(workblock.clone()));
+    Ok(workblock)
+}
+
+/// Preview when the prompts of a workblock would appear if it were started now.
+/// `interval_minutes` overrides the interval length; by default the timer's own length is used.
+#[tauri::command]
+fn preview_workblock_plan(
+    app: tauri::AppHandle,
+    duration_minutes: i32,
+    interval_minutes: Option<i32>,
+) -> Result<timer::WorkblockPlan, Log15Error> {
+    let duration_minutes = timer::validate_duration_minutes(duration_minutes)?;
+    let interval_seconds = match interval_minutes {
+        Some(minutes) if minutes <= 0 => {
+            return Err(Log15Error::InvalidInput("Intervals must be at least 1 minute".to_string()))
+        }
+        Some(minutes) if minutes > duration_minutes => {
+            return Err(Log15Error::InvalidInput("Intervals cannot be longer than the workblock".to_string()))
+        }
+        Some(minutes) => minutes as i64 * 60,
+        None => interval_schedule::interval_seconds(),
+    };
+    let align_to_clock = settings::get_align_intervals_to_clock(&app);
+    Ok(timer::plan_workblock(chrono::Local::now(), duration_minutes, interval_seconds, align_to_clock))
+}
+
+#[tauri::command]
+fn set_goal(app: tauri::AppHandle, metric: stats::GoalMetric, target: i32) -> Result<stats::Goal, Log15Error> {
+    stats::set_goal(&app, metric, target).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn remove_goal(app: tauri::AppHandle, metric: stats::GoalMetric) -> Result<bool, Log15Error> {
+    stats::remove_goal(&app, metric).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_goals(app: tauri::AppHandle) -> Result<Vec<stats::Goal>, Log15Error> {
+    stats::get_goals(&app).map_err(Log15Error::from)
+}
+
+/// Today's goal progress and current streak, for live display
+#[tauri::command]
+fn get_goal_progress(app: tauri::AppHandle) -> Result<stats::DailyGoalStatus, Log15Error> {
+    stats::get_goal_progress(&app).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_onboarding_state(app: tauri::AppHandle) -> Result<onboarding::OnboardingState, Log15Error> {
+    onboarding::get_onboarding_state(&app).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn complete_onboarding_step(
+    app: tauri::AppHandle,
+    step: onboarding::OnboardingStep,
+) -> Result<onboarding::OnboardingState, Log15Error> {
+    onboarding::complete_onboarding_step(&app, step).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_duration_presets(app: tauri::AppHandle) -> Vec<i32> {
+    settings::get_duration_presets(&app)
+}
+
+#[tauri::command]
+fn set_duration_presets(app: tauri::AppHandle, presets: Vec<i32>) -> Result<Vec<i32>, Log15Error> {
+    settings::set_duration_presets(&app, &presets).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_context_snapshot_enabled(app: tauri::AppHandle) -> bool {
+    settings::get_context_snapshot_enabled(&app)
+}
+
+#[tauri::command]
+fn set_context_snapshot_enabled(app: tauri::AppHandle, enabled: bool) -> Result<bool, Log15Error> {
+    settings::set_context_snapshot_enabled(&app, enabled).map_err(Log15Error::from)
+}
+
+/// Workspace captured when the interval was answered, if snapshots were on
+#[tauri::command]
+fn get_interval_context(app: tauri::AppHandle, interval_id: i64) -> Result<Option<context_snapshot::ContextSnapshot>, Log15Error> {
+    context_snapshot::get_context_snapshot(&app, interval_id).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_align_intervals(app: tauri::AppHandle) -> bool {
+    settings::get_align_intervals_to_clock(&app)
+}
+
+#[tauri::command]
+fn set_align_intervals(app: tauri::AppHandle, enabled: bool) -> Result<bool, Log15Error> {
+    settings::set_align_intervals_to_clock(&app, enabled).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_resume_window(app: tauri::AppHandle) -> i64 {
+    settings::get_resume_window_minutes(&app)
+}
+
+#[tauri::command]
+fn set_resume_window(app: tauri::AppHandle, window_minutes: i64) -> Result<i64, Log15Error> {
+    settings::set_resume_window_minutes(&app, window_minutes).map_err(Log15Error::from)
+}
+
 #[tauri::command]
-fn get_active_workblock_cmd(app: tauri::AppHandle) -> Result<Option<Workblock>, String> {
-    get_active_workblock(&app).map_err(|e| e.to_string())
+fn get_active_workblock_cmd(app: tauri::AppHandle) -> Result<Option<Workblock>, Log15Error> {
+    get_active_workblock(&app)
 }
 
 #[tauri::command]
-fn get_workblocks_by_date_cmd(app: tauri::AppHandle, date: String) -> Result<Vec<Workblock>, String> {
-    get_workblocks_by_date(&app, &date).map_err(|e| e.to_string())
+fn get_workblocks_by_date_cmd(app: tauri::AppHandle, date: String) -> Result<Vec<Workblock>, Log15Error> {
+    get_workblocks_by_date(&app, &date)
 }
 
 #[tauri::command]
-fn get_today_workblocks(app: tauri::AppHandle) -> Result<Vec<Workblock>, String> {
+fn get_today_workblocks(app: tauri::AppHandle) -> Result<Vec<Workblock>, Log15Error> {
     let today = get_today_date();
-    get_workblocks_by_date(&app, &today).map_err(|e| e.to_string())
+    get_workblocks_by_date(&app, &today)
+}
+
+#[tauri::command]
+fn set_workblock_project_cmd(app: tauri::AppHandle, workblock_id: i64, project: Option<String>) -> Result<Workblock, Log15Error> {
+    set_workblock_project(&app, workblock_id, project.as_deref())
 }
 
 // Interval commands
 #[tauri::command]
-fn create_interval(app: tauri::AppHandle, workblock_id: i64, interval_number: i32) -> Result<Interval, String> {
-    add_interval(&app, workblock_id, interval_number).map_err(|e| e.to_string())
+fn create_interval(app: tauri::AppHandle, workblock_id: i64, interval_number: i32) -> Result<Interval, Log15Error> {
+    add_interval(&app, workblock_id, interval_number)
+}
+
+#[tauri::command]
+fn set_interval_project_cmd(app: tauri::AppHandle, interval_id: i64, project: Option<String>) -> Result<Interval, Log15Error> {
+    set_interval_project(&app, interval_id, project.as_deref())
 }
 
 #[tauri::command]
@@ -130,7 +364,114 @@ async fn submit_interval_words(
     app: tauri::AppHandle,
     interval_id: i64,
     words: String,
-) -> Result<serde_json::Value, String> {
+    energy: Option<i32>,
+    activities: Option<Vec<db::ActivitySplit>>,
+) -> Result<serde_json::Value, Log15Error> {
+    // An interval split between several activities is recorded under their combined words
+    let splits = match activities {
+        Some(activities) => db::validate_activity_splits(&activities)?,
+        None => Vec::new(),
+    };
+    let words = if splits.is_empty() { words } else { db::split_words(&splits) };
+    // Rate first so an out-of-range energy leaves the interval unanswered
+    if energy.is_some() {
+        db::set_interval_energy(&app, interval_id, energy)?;
+    }
+    answer_interval(app, interval_id, words, IntervalStatus::Recorded, &splits).await
+}
+
+/// Answer an interval with the previous interval's words, project and activity split in one call
+#[tauri::command]
+async fn repeat_last_interval(app: tauri::AppHandle, interval_id: i64) -> Result<serde_json::Value, Log15Error> {
+    let conn = db::get_db_connection(&app)?;
+    let previous = db::query_previous_answer(&conn, interval_id)?
+        .ok_or_else(|| Log15Error::NotFound("There is no earlier interval to repeat".to_string()))?;
+    drop(conn);
+    println!("[REPEAT] Interval {} repeats interval {}: '{}'", interval_id, previous.interval_id, previous.words);
+    if previous.project.is_some() {
+        db::set_interval_project(&app, interval_id, previous.project.as_deref())?;
+    }
+    answer_interval(app, interval_id, previous.words, IntervalStatus::Recorded, &previous.splits).await
+}
+
+/// Repeat the last interval for the prompt that is open (tray and hotkey) and close the prompt
+pub(crate) async fn repeat_last_from_outside(app: tauri::AppHandle) -> Result<(), String> {
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let interval_id = window_manager
+        .lock()
+        .await
+        .get_current_interval_id()
+        .await
+        .ok_or_else(|| "No prompt is waiting for an answer".to_string())?;
+    
+    repeat_last_interval(app.clone(), interval_id).await?;
+    hide_prompt_window_cmd(app).await?;
+    Ok(())
+}
+
+/// Answer an interval by voice: transcribe the recording (a 16 kHz 16-bit WAV) and record the text.
+/// The recording is the raw request body and the interval id goes in the Interval-Id header.
+/// The result also carries the transcript, so the prompt can show what was heard.
+#[tauri::command]
+async fn transcribe_and_submit(
+    app: tauri::AppHandle,
+    request: tauri::ipc::Request<'_>,
+) -> Result<serde_json::Value, Log15Error> {
+    let tauri::ipc::InvokeBody::Raw(audio_bytes) = request.body() else {
+        return Err(Log15Error::InvalidInput("Expected the recording as a raw body".to_string()));
+    };
+    let interval_id = request
+        .headers()
+        .get("Interval-Id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<i64>().ok())
+        .ok_or_else(|| Log15Error::InvalidInput("Missing or invalid Interval-Id header".to_string()))?;
+    let words = speech::transcribe(&app, audio_bytes.clone()).await.map_err(Log15Error::Other)?;
+    println!("[VOICE] Interval {} answered by voice: '{}'", interval_id, words);
+    let mut result = submit_interval_words(app, interval_id, words.clone(), None, None).await?;
+    result["transcript"] = serde_json::Value::String(words);
+    Ok(result)
+}
+
+#[tauri::command]
+fn get_transcription_settings(app: tauri::AppHandle) -> settings::TranscriptionSettings {
+    settings::get_transcription_settings(&app)
+}
+
+#[tauri::command]
+fn set_transcription_settings(
+    app: tauri::AppHandle,
+    transcription: settings::TranscriptionSettings,
+) -> Result<settings::TranscriptionSettings, Log15Error> {
+    settings::set_transcription_settings(&app, &transcription).map_err(Log15Error::from)
+}
+
+/// Change (or clear) the energy rating of an already answered interval
+#[tauri::command]
+fn set_interval_energy(app: tauri::AppHandle, interval_id: i64, energy: Option<i32>) -> Result<(), Log15Error> {
+    db::set_interval_energy(&app, interval_id, energy)
+}
+
+/// Set or clear the longer notes kept alongside an interval's words
+#[tauri::command]
+fn update_interval_notes(app: tauri::AppHandle, interval_id: i64, notes: Option<String>) -> Result<Interval, Log15Error> {
+    db::update_interval_notes(&app, interval_id, notes.as_deref())
+}
+
+/// Intervals whose words or notes contain `query`, newest first
+#[tauri::command]
+fn search_intervals(app: tauri::AppHandle, query: String, limit: Option<i64>) -> Result<Vec<db::IntervalSearchResult>, Log15Error> {
+    db::search_intervals(&app, &query, limit.unwrap_or(100))
+}
+
+/// Record the user's answer for a pending interval and move on (summary after the last one)
+async fn answer_interval(
+    app: tauri::AppHandle,
+    interval_id: i64,
+    words: String,
+    status: IntervalStatus,
+    splits: &[db::ActivitySplit],
+) -> Result<serde_json::Value, Log15Error> {
     // Cancel auto-away timer since user submitted words
     let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
     let timer = timer_manager.lock().await;
@@ -138,26 +479,24 @@ async fn submit_interval_words(
     drop(timer);
     
     // Update interval with words
-    let interval = update_interval_words(&app, interval_id, words, IntervalStatus::Recorded)
-        .map_err(|e| e.to_string())?;
+    let interval = update_interval_words(&app, interval_id, words, status)?;
+    if !splits.is_empty() {
+        db::set_activity_splits(&app, interval_id, splits)?;
+    }
+    TrayManager::refresh_menu(&app);
+    stats::check_goal_milestones(&app);
+    context_snapshot::record_context_snapshot(&app, interval_id);
     
     // Check if this is the last interval
     let workblock_id = interval.workblock_id;
-    let workblock = get_workblock_by_id(&app, workblock_id)
-        .map_err(|e| e.to_string())?;
-    
-    // TESTING: Calculate based on 10-second intervals (normally 15-minute intervals)
-    // For testing: 1 interval per 10 seconds, so duration_minutes * 6 intervals per minute
-    let total_intervals = workblock.duration_minutes.unwrap_or(60) * 6; // TESTING: Changed from / 15
-    // If this interval's number equals total_intervals, it's the last one
-    let is_last_interval = interval.interval_number >= total_intervals;
+    let is_last_interval = db::query_is_last_interval(&db::get_db_connection(&app)?, &interval)?;
     
     let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
     let window_mgr = window_manager.lock().await;
     
     if is_last_interval {
-        // Show summary ready view instead of hiding
-        window_mgr.show_summary_ready().await.map_err(|e| e.to_string())?;
+        // Open the day's summary in its own window; the prompt closes itself after its checkmark
+        window_mgr.show_summary_window(Some(workblock_id)).await?;
         
         // Update tray state to SummaryReady
         let tray_manager = app.state::<Arc<Mutex<TrayManager>>>();
@@ -180,6 +519,9 @@ async fn submit_interval_words(
         // #endregion
         // Don't hide window here - let frontend handle closing after checkmark animation completes
         // Frontend will call hide_prompt_window_cmd after the 2-second checkmark display
+        
+        let tray_manager = app.state::<Arc<Mutex<TrayManager>>>();
+        tray_manager.lock().await.refresh_today_totals();
     }
     drop(window_mgr);
     
@@ -190,182 +532,1157 @@ async fn submit_interval_words(
 }
 
 // Window management commands
+/// Show the prompt for an interval, or queue it behind the prompt still on screen.
+/// Returns whether it was shown; a queued prompt arms its auto-away once it comes up.
 #[tauri::command]
 async fn show_prompt_window_cmd(
     app: tauri::AppHandle,
     interval_id: i64,
-) -> Result<(), String> {
+) -> Result<bool, Log15Error> {
     println!("[WINDOW] show_prompt_window_cmd called with interval_id={}", interval_id);
     let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
     let window_mgr = window_manager.lock().await;
     
     // Show the prompt window
-    match window_mgr.show_prompt_window(interval_id).await {
-        Ok(_) => {
-            println!("[WINDOW] Successfully showed prompt window");
+    let shown = match window_mgr.show_or_queue_prompt(interval_id).await {
+        Ok(shown) => {
+            println!("[WINDOW] Successfully {} prompt window", if shown { "showed" } else { "queued" });
+            shown
         }
         Err(e) => {
             eprintln!("[WINDOW] Failed to show prompt window: {}", e);
             return Err(e);
         }
-    }
+    };
+    drop(window_mgr);
     
     // Start auto-away timer
+    if shown {
+        let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+        let timer = timer_manager.lock().await;
+        timer.start_auto_away_timer(interval_id).await?;
+    }
+    
+    Ok(shown)
+}
+
+/// Once the prompt is gone, show the oldest queued prompt and arm its auto-away
+pub(crate) async fn show_next_queued_prompt(app: &tauri::AppHandle) {
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let window_mgr = window_manager.lock().await;
+    let Some(interval_id) = window_mgr.next_queued_prompt().await else {
+        return;
+    };
+    println!("[WINDOW] Showing queued prompt for interval {}", interval_id);
+    if let Err(e) = window_mgr.show_or_queue_prompt(interval_id).await {
+        eprintln!("[WINDOW] Failed to show queued prompt for interval {}: {}", interval_id, e);
+        return;
+    }
+    drop(window_mgr);
+    
     let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
     let timer = timer_manager.lock().await;
-    timer.start_auto_away_timer(interval_id).await?;
-    
-    Ok(())
+    let _ = timer.start_auto_away_timer(interval_id).await;
 }
 
+/// The prompt on screen and the prompts queued behind it, each with its interval's time range
 #[tauri::command]
-async fn hide_prompt_window_cmd(app: tauri::AppHandle) -> Result<(), String> {
+async fn get_prompt_queue(app: tauri::AppHandle) -> PromptQueue {
     let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
     let window_mgr = window_manager.lock().await;
+    window_mgr.get_prompt_queue().await
+}
+
+/// Hide the prompt and bring it back after the snooze (defaults to the configured duration)
+#[tauri::command]
+async fn snooze_prompt(
+    app: tauri::AppHandle,
+    interval_id: i64,
+    duration_seconds: Option<u64>,
+) -> Result<(), Log15Error> {
+    let snooze_seconds = duration_seconds
+        .filter(|s| *s > 0)
+        .unwrap_or_else(|| settings::get_snooze_seconds(&app));
     
-    // Check if summary is showing - if so, update tray to Idle
-    let was_summary = window_mgr.is_summary_ready().await;
+    // Reschedule first so auto-away can't fire while the window is closing
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer = timer_manager.lock().await;
+    timer.snooze_prompt(interval_id, snooze_seconds).await?;
+    drop(timer);
     
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let window_mgr = window_manager.lock().await;
     window_mgr.hide_prompt_window().await?;
     
-    // If summary was showing, update tray to Idle
-    if was_summary {
-        let tray_manager = app.state::<Arc<Mutex<TrayManager>>>();
-        let mut tray = tray_manager.lock().await;
-        tray.update_icon_state(crate::tray::TrayIconState::Idle).await;
+    Ok(())
+}
+
+/// Hide the prompt; the next queued prompt, if any, takes its place
+#[tauri::command]
+async fn hide_prompt_window_cmd(app: tauri::AppHandle) -> Result<(), Log15Error> {
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let window_mgr = window_manager.lock().await;
+    window_mgr.hide_prompt_window().await?;
+    drop(window_mgr);
+    
+    show_next_queued_prompt(&app).await;
+    Ok(())
+}
+
+#[tauri::command]
+async fn focus_prompt_window(app: tauri::AppHandle) -> Result<(), Log15Error> {
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let window_mgr = window_manager.lock().await;
+    window_mgr.focus_prompt_window().await
+}
+
+#[tauri::command]
+fn get_passive_prompt(app: tauri::AppHandle) -> bool {
+    settings::get_passive_prompt(&app)
+}
+
+#[tauri::command]
+fn set_passive_prompt(app: tauri::AppHandle, enabled: bool) -> Result<bool, Log15Error> {
+    settings::set_passive_prompt(&app, enabled).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_last_viewed_date(app: tauri::AppHandle) -> Option<String> {
+    window_state::get_last_viewed_date(&app)
+}
+
+#[tauri::command]
+fn set_last_viewed_date(app: tauri::AppHandle, date: Option<String>) -> Result<(), Log15Error> {
+    window_state::set_last_viewed_date(&app, date.as_deref()).map_err(Log15Error::from)
+}
+
+/// Open today's summary in the summary window
+#[tauri::command]
+async fn show_summary_window(app: tauri::AppHandle, workblock_id: Option<i64>) -> Result<(), Log15Error> {
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let window_mgr = window_manager.lock().await;
+    window_mgr.show_summary_window(workblock_id).await
+}
+
+/// Record `words` for the interval whose prompt is open, picked from the tray's quick-log submenu
+async fn quick_log_from_tray(app: tauri::AppHandle, words: String) -> Result<(), String> {
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let interval_id = window_manager
+        .lock()
+        .await
+        .get_current_interval_id()
+        .await
+        .ok_or_else(|| "No prompt is waiting for an answer".to_string())?;
+    
+    log_interval_from_outside(app, interval_id, words, None).await?;
+    Ok(())
+}
+
+/// Answer an interval from outside its prompt (tray, local API) and close the prompt
+pub(crate) async fn log_interval_from_outside(
+    app: tauri::AppHandle,
+    interval_id: i64,
+    words: String,
+    energy: Option<i32>,
+) -> Result<serde_json::Value, String> {
+    let result = submit_interval_words(app.clone(), interval_id, words, energy, None).await?;
+    // The prompt window only closes itself after an answer typed there, so close it here, even
+    // after the last interval, and let queued prompts through
+    hide_prompt_window_cmd(app).await?;
+    Ok(result)
+}
+
+/// Answer an interval with one of the configured quick statuses. A pending interval is answered
+/// like a prompt; an interval already lost to auto-away is relabelled in place.
+#[tauri::command]
+async fn submit_quick_status(
+    app: tauri::AppHandle,
+    interval_id: i64,
+    status: String,
+) -> Result<serde_json::Value, Log15Error> {
+    let status = settings::find_quick_status(&settings::get_quick_statuses(&app), &status)
+        .ok_or_else(|| Log15Error::InvalidInput(format!("'{}' is not a quick status", status.trim())))?;
+    let interval = db::get_interval_by_id(&app, interval_id)?;
+    if interval.status != IntervalStatus::AutoAway {
+        return answer_interval(app, interval_id, status, IntervalStatus::QuickStatus, &[]).await;
     }
     
+    let conn = db::get_db_connection(&app)?;
+    db::apply_quick_status(&conn, interval_id, &status)?;
+    cache::interval_written(&app, interval_id);
+    let interval = db::get_interval_by_id(&app, interval_id)?;
+    Ok(serde_json::json!({
+        "interval": interval,
+        "is_last_interval": false
+    }))
+}
+
+/// Answer the interval the prompt is waiting on with a quick status picked in the tray
+async fn quick_status_from_tray(app: tauri::AppHandle, status: String) -> Result<(), String> {
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let interval_id = window_manager
+        .lock()
+        .await
+        .get_current_interval_id()
+        .await
+        .ok_or_else(|| "No prompt is waiting for an answer".to_string())?;
+    
+    submit_quick_status(app.clone(), interval_id, status).await?;
+    hide_prompt_window_cmd(app).await?;
     Ok(())
 }
 
 #[tauri::command]
-fn auto_away_interval(app: tauri::AppHandle, interval_id: i64) -> Result<Interval, String> {
-    update_interval_words(&app, interval_id, "Away from workspace".to_string(), IntervalStatus::AutoAway)
-        .map_err(|e| e.to_string())
+fn get_quick_statuses(app: tauri::AppHandle) -> Vec<String> {
+    settings::get_quick_statuses(&app)
 }
 
 #[tauri::command]
-fn get_intervals_by_workblock_cmd(app: tauri::AppHandle, workblock_id: i64) -> Result<Vec<Interval>, String> {
-    get_intervals_by_workblock(&app, workblock_id).map_err(|e| e.to_string())
+fn set_quick_statuses(app: tauri::AppHandle, statuses: Vec<String>) -> Result<Vec<String>, Log15Error> {
+    settings::set_quick_statuses(&app, &statuses).map_err(Log15Error::from)
 }
 
 #[tauri::command]
-async fn get_current_interval_cmd(
+fn get_word_cloud_settings(app: tauri::AppHandle) -> settings::WordCloudSettings {
+    settings::get_word_cloud_settings(&app)
+}
+
+/// Stop words and minimum length for the single-word counts of workblock visualizations
+#[tauri::command]
+fn set_word_cloud_settings(
     app: tauri::AppHandle,
-    workblock_id: i64,
-) -> Result<Option<Interval>, String> {
-    get_current_interval(&app, workblock_id).map_err(|e| e.to_string())
+    word_cloud: settings::WordCloudSettings,
+) -> Result<settings::WordCloudSettings, Log15Error> {
+    settings::set_word_cloud_settings(&app, &word_cloud).map_err(Log15Error::from)
 }
 
 #[tauri::command]
-async fn get_timer_state(app: tauri::AppHandle) -> Result<timer::TimerState, String> {
-    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
-    let timer = timer_manager.lock().await;
-    Ok(timer.get_state().await)
+fn auto_away_interval(app: tauri::AppHandle, interval_id: i64) -> Result<Interval, Log15Error> {
+    let message = settings::get_auto_away_settings(&app).message;
+    db::record_away_interval(&app, interval_id, message, db::AwayCause::PromptTimeout)
 }
 
+/// Compact summary of one workblock for the summary-ready window
 #[tauri::command]
-async fn get_interval_time_remaining(app: tauri::AppHandle) -> Result<Option<i64>, String> {
-    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
-    let timer: tokio::sync::MutexGuard<'_, TimerManager> = timer_manager.lock().await;
-    Ok(timer.get_interval_time_remaining().await)
+fn get_workblock_summary(app: tauri::AppHandle, workblock_id: i64) -> Result<db::WorkblockSummary, Log15Error> {
+    db::get_workblock_summary(&app, workblock_id)
 }
 
-// Daily commands
 #[tauri::command]
-fn check_and_reset_daily_cmd(app: tauri::AppHandle) -> Result<Option<String>, String> {
-    check_and_reset_daily(&app).map_err(|e| e.to_string())
+fn get_reclassify_offer_cmd(app: tauri::AppHandle, interval_id: i64) -> Result<Option<db::ReclassifyOffer>, Log15Error> {
+    get_reclassify_offer(&app, interval_id)
 }
 
 #[tauri::command]
-fn get_today_date_cmd() -> String {
-    get_today_date()
+fn reclassify_interval_cmd(app: tauri::AppHandle, interval_id: i64, words: String) -> Result<Interval, Log15Error> {
+    reclassify_interval(&app, interval_id, &words)
 }
 
+// Category rule commands
 #[tauri::command]
-fn get_archived_day_cmd(app: tauri::AppHandle, date: String) -> Result<Option<DailyArchive>, String> {
-    get_archived_day(&app, &date).map_err(|e| e.to_string())
+fn add_category_rule(
+    app: tauri::AppHandle,
+    pattern: String,
+    match_type: rules::RuleMatchType,
+    category: String,
+    priority: Option<i32>,
+) -> Result<rules::CategoryRule, Log15Error> {
+    rules::add_rule(&app, &pattern, match_type, &category, priority.unwrap_or(0)).map_err(Log15Error::from)
 }
 
 #[tauri::command]
-fn get_all_archived_dates_cmd(app: tauri::AppHandle) -> Result<Vec<DailyArchive>, String> {
-    get_all_archived_dates(&app).map_err(|e| e.to_string())
+fn remove_category_rule(app: tauri::AppHandle, rule_id: i64) -> Result<bool, Log15Error> {
+    rules::remove_rule(&app, rule_id).map_err(Log15Error::from)
 }
 
-// Visualization commands
 #[tauri::command]
-fn get_workblock_visualization(app: tauri::AppHandle, workblock_id: i64) -> Result<String, String> {
-    let viz = generate_workblock_visualization(&app, workblock_id)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_string(&viz).map_err(|e| e.to_string())
+fn get_category_rules(app: tauri::AppHandle) -> Result<Vec<rules::CategoryRule>, Log15Error> {
+    rules::get_rules(&app).map_err(Log15Error::from)
 }
 
+/// Show which intervals dated start_date..=end_date a rule would match, without saving it
 #[tauri::command]
-fn get_daily_aggregate_cmd(app: tauri::AppHandle, date: String) -> Result<String, String> {
-    let aggregate = generate_daily_aggregate(&app, &date)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_string(&aggregate).map_err(|e| e.to_string())
+fn preview_rule(
+    app: tauri::AppHandle,
+    pattern: String,
+    match_type: rules::RuleMatchType,
+    category: String,
+    start_date: String,
+    end_date: String,
+) -> Result<rules::RulePreview, Log15Error> {
+    rules::preview_rule(&app, &pattern, match_type, &category, &start_date, &end_date).map_err(Log15Error::from)
 }
 
+/// Re-categorize intervals of workblocks dated start_date..=end_date; returns how many changed
 #[tauri::command]
-fn get_daily_visualization_data_cmd(app: tauri::AppHandle, date: String) -> Result<String, String> {
-    let data = generate_daily_visualization_data(&app, &date)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_string(&data).map_err(|e| e.to_string())
+fn apply_rules(app: tauri::AppHandle, start_date: String, end_date: String) -> Result<usize, Log15Error> {
+    rules::apply_rules(&app, &start_date, &end_date).map_err(Log15Error::from)
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .setup(|app| {
-            // Initialize database on app startup
-            if let Err(e) = init_db(&app.handle()) {
-                eprintln!("Failed to initialize database: {}", e);
-            }
-            
-            // Check and reset daily on startup
-            if let Err(e) = check_and_reset_daily(&app.handle()) {
-                eprintln!("Failed to check daily reset: {}", e);
-            }
-            
-            // Initialize timer manager
-            let timer_manager = Arc::new(Mutex::new(TimerManager::new(app.handle().clone())));
-            app.manage(timer_manager.clone());
-            
-            // Initialize tray manager
-            let tray_manager = Arc::new(Mutex::new(TrayManager::new(app.handle().clone())));
-            app.manage(tray_manager.clone());
-            
-            // Initialize window manager
-            let window_manager = Arc::new(Mutex::new(WindowManager::new(app.handle().clone())));
-            app.manage(window_manager);
-            
-            // Setup system tray
-            if let Err(e) = TrayManager::setup_tray(&app.handle()) {
-                eprintln!("Failed to setup system tray: {}", e);
-            }
-            
-            // Restore active workblock if one exists (for app restart scenarios)
-            // Use Tauri's async runtime instead of tokio::spawn
-            let timer_clone = timer_manager.clone();
-            let tray_clone = tray_manager.clone();
-            async_runtime::spawn(async move {
-                let timer = timer_clone.lock().await;
-                if let Err(e) = timer.restore_active_workblock().await {
-                    eprintln!("Failed to restore active workblock: {}", e);
-                }
-                drop(timer);
-                
-                // Refresh tray state after restoring workblock
-                let mut tray = tray_clone.lock().await;
-                tray.refresh_state().await;
-            });
-            
-            Ok(())
-        })
-        .on_tray_icon_event(|app, event| {
-            TrayManager::handle_tray_event(app, event);
+#[tauri::command]
+fn get_auto_away_settings(app: tauri::AppHandle) -> settings::AutoAwaySettings {
+    settings::get_auto_away_settings(&app)
+}
+
+#[tauri::command]
+fn set_auto_away_timeout(app: tauri::AppHandle, timeout_seconds: u64) -> Result<settings::AutoAwaySettings, Log15Error> {
+    settings::set_auto_away_timeout(&app, timeout_seconds).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_hotkeys(app: tauri::AppHandle) -> settings::HotkeySettings {
+    settings::get_hotkey_settings(&app)
+}
+
+#[tauri::command]
+fn set_hotkeys(app: tauri::AppHandle, hotkeys: settings::HotkeySettings) -> Result<settings::HotkeySettings, Log15Error> {
+    hotkeys::update_hotkeys(&app, &hotkeys).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_idle_threshold(app: tauri::AppHandle) -> u64 {
+    settings::get_idle_threshold_seconds(&app)
+}
+
+/// 0 turns idle detection off
+#[tauri::command]
+fn set_idle_threshold(app: tauri::AppHandle, threshold_seconds: u64) -> Result<u64, Log15Error> {
+    settings::set_idle_threshold_seconds(&app, threshold_seconds).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_snooze_seconds(app: tauri::AppHandle) -> u64 {
+    settings::get_snooze_seconds(&app)
+}
+
+#[tauri::command]
+fn set_snooze_seconds(app: tauri::AppHandle, snooze_seconds: u64) -> Result<u64, Log15Error> {
+    settings::set_snooze_seconds(&app, snooze_seconds).map_err(Log15Error::from)
+}
+
+/// Run a short synthetic workblock on a fake clock to check the timers on this machine
+#[tauri::command]
+async fn run_timer_selftest(app: tauri::AppHandle) -> timer_selftest::SelftestReport {
+    timer_selftest::run_timer_selftest(&app).await
+}
+
+/// Problems the app detected and repaired on its own, most recent first
+#[tauri::command]
+fn get_diagnostics(app: tauri::AppHandle, limit: Option<i64>) -> Result<Vec<diagnostics::Incident>, Log15Error> {
+    diagnostics::get_incidents(&app, limit.unwrap_or(100)).map_err(Log15Error::from)
+}
+
+/// Timings of this launch (the "ready" payload), or null while startup is still running
+#[tauri::command]
+fn get_startup_report() -> Option<startup::StartupReport> {
+    startup::get_startup_report()
+}
+
+/// Every event the backend emits, with its payload type
+#[tauri::command]
+fn get_event_schema() -> Vec<events::EventSchema> {
+    events::event_schema()
+}
+
+/// When settings were changed, to explain odd aggregates (e.g. intervals changed mid-week)
+#[tauri::command]
+fn get_settings_history(
+    app: tauri::AppHandle,
+    key: Option<String>,
+    since: Option<String>,
+) -> Result<Vec<db::SettingChange>, Log15Error> {
+    db::get_settings_history(&app, key.as_deref(), since.as_deref())
+}
+
+#[tauri::command]
+fn get_wellbeing_settings(app: tauri::AppHandle) -> settings::WellbeingSettings {
+    settings::get_wellbeing_settings(&app)
+}
+
+#[tauri::command]
+fn set_wellbeing_settings(
+    app: tauri::AppHandle,
+    wellbeing: settings::WellbeingSettings,
+) -> Result<settings::WellbeingSettings, Log15Error> {
+    settings::set_wellbeing_settings(&app, &wellbeing).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_interval_warning_settings(app: tauri::AppHandle) -> settings::IntervalWarningSettings {
+    settings::get_interval_warning_settings(&app)
+}
+
+#[tauri::command]
+fn set_interval_warning_settings(
+    app: tauri::AppHandle,
+    warning: settings::IntervalWarningSettings,
+) -> Result<settings::IntervalWarningSettings, Log15Error> {
+    settings::set_interval_warning_settings(&app, &warning).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_locale_settings(app: tauri::AppHandle) -> settings::LocaleSettings {
+    settings::get_locale_settings(&app)
+}
+
+#[tauri::command]
+fn set_locale_settings(app: tauri::AppHandle, locale: settings::LocaleSettings) -> Result<settings::LocaleSettings, Log15Error> {
+    settings::set_locale_settings(&app, &locale).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_activity_capture_settings(app: tauri::AppHandle) -> settings::ActivityCaptureSettings {
+    settings::get_activity_capture_settings(&app)
+}
+
+#[tauri::command]
+fn set_activity_capture_settings(
+    app: tauri::AppHandle,
+    settings: settings::ActivityCaptureSettings,
+) -> Result<settings::ActivityCaptureSettings, Log15Error> {
+    settings::set_activity_capture_settings(&app, &settings).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_day_start_hour() -> u32 {
+    settings::day_start_hour()
+}
+
+/// Hour (0-23) at which a new day begins, e.g. 4 so work until 4 AM counts toward the previous day
+#[tauri::command]
+fn set_day_start_hour(app: tauri::AppHandle, hour: u32) -> Result<u32, Log15Error> {
+    settings::set_day_start_hour(&app, hour).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_archive_time(app: tauri::AppHandle) -> Option<String> {
+    settings::get_archive_time(&app).map(|t| t.format("%H:%M").to_string())
+}
+
+/// Archive the day early at "HH:MM" (e.g. "19:00"); None archives only at the day boundary
+#[tauri::command]
+fn set_archive_time(app: tauri::AppHandle, archive_time: Option<String>) -> Result<Option<String>, Log15Error> {
+    settings::set_archive_time(&app, archive_time.as_deref()).map_err(Log15Error::from)
+}
+
+/// Tracked vs. meeting vs. active-computer minutes for a day
+#[tauri::command]
+fn get_time_audit(app: tauri::AppHandle, date: String) -> Result<time_audit::TimeAudit, Log15Error> {
+    time_audit::get_time_audit(&app, &date).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_interval_app_usage(app: tauri::AppHandle, interval_id: i64) -> Result<Vec<activity_capture::IntervalAppUsage>, Log15Error> {
+    activity_capture::get_interval_app_usage(&app, interval_id).map_err(Log15Error::from)
+}
+
+/// Delete all captured application data
+#[tauri::command]
+fn clear_activity_capture(app: tauri::AppHandle) -> Result<usize, Log15Error> {
+    activity_capture::clear_activity_capture(&app).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_language(app: tauri::AppHandle) -> settings::Language {
+    settings::get_language(&app)
+}
+
+#[tauri::command]
+fn get_theme(app: tauri::AppHandle) -> theme::ThemeInfo {
+    theme::current_theme(&app)
+}
+
+/// Store the theme preference; the window manager applies it to every window ("theme-changed" follows)
+#[tauri::command]
+fn set_theme_preference(app: tauri::AppHandle, preference: settings::ThemePreference) -> Result<theme::ThemeInfo, Log15Error> {
+    settings::set_theme_preference(&app, preference)?;
+    Ok(theme::current_theme(&app))
+}
+
+/// Switch the backend's language; the tray menu is rebuilt through the settings broadcast
+#[tauri::command]
+fn set_language(app: tauri::AppHandle, language: settings::Language) -> Result<settings::Language, Log15Error> {
+    let language = settings::set_language(&app, language)?;
+    let _ = emit_event(&app, AppEvent::LanguageChanged(language));
+    Ok(language)
+}
+
+#[tauri::command]
+fn get_dnd_status(app: tauri::AppHandle) -> dnd::DndStatus {
+    dnd::get_dnd_status(&app)
+}
+
+/// Pause prompts for `minutes` (defaults to the configured preset)
+#[tauri::command]
+fn enable_dnd(app: tauri::AppHandle, minutes: Option<i32>) -> Result<dnd::DndStatus, Log15Error> {
+    dnd::enable_dnd(&app, minutes.unwrap_or_else(|| settings::get_dnd_preset_minutes(&app))).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn clear_dnd(app: tauri::AppHandle) -> Result<dnd::DndStatus, Log15Error> {
+    dnd::clear_dnd(&app).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_dnd_preset(app: tauri::AppHandle) -> i32 {
+    settings::get_dnd_preset_minutes(&app)
+}
+
+#[tauri::command]
+fn set_dnd_preset(app: tauri::AppHandle, minutes: i32) -> Result<i32, Log15Error> {
+    settings::set_dnd_preset_minutes(&app, minutes).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_tray_countdown(app: tauri::AppHandle) -> settings::TrayCountdownMode {
+    settings::get_tray_countdown_mode(&app)
+}
+
+/// Takes effect immediately; the title is then refreshed every 30 seconds
+#[tauri::command]
+async fn set_tray_countdown(
+    app: tauri::AppHandle,
+    mode: settings::TrayCountdownMode,
+) -> Result<settings::TrayCountdownMode, Log15Error> {
+    let mode = settings::set_tray_countdown_mode(&app, mode)?;
+    TrayManager::refresh_countdown(&app).await;
+    Ok(mode)
+}
+
+#[tauri::command]
+fn get_pomodoro_settings(app: tauri::AppHandle) -> settings::PomodoroSettings {
+    settings::get_pomodoro_settings(&app)
+}
+
+#[tauri::command]
+fn set_pomodoro_settings(
+    app: tauri::AppHandle,
+    pomodoro: settings::PomodoroSettings,
+) -> Result<settings::PomodoroSettings, Log15Error> {
+    settings::set_pomodoro_settings(&app, &pomodoro).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn set_auto_away_message(app: tauri::AppHandle, message: String) -> Result<settings::AutoAwaySettings, Log15Error> {
+    settings::set_auto_away_message(&app, &message).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_activity_suggestions(
+    app: tauri::AppHandle,
+    prefix: String,
+    limit: Option<usize>,
+) -> Result<Vec<db::ActivitySuggestion>, Log15Error> {
+    suggest_activities(&app, &prefix, limit.unwrap_or(8))
+}
+
+#[tauri::command]
+fn get_prompt_suggestions(
+    app: tauri::AppHandle,
+    interval_id: i64,
+    limit: Option<usize>,
+) -> Result<Vec<db::PromptSuggestion>, Log15Error> {
+    generate_prompt_suggestions(&app, interval_id, limit.unwrap_or(8))
+}
+
+/// Last interval's words, today's top activities and pinned favorites for one-click logging
+#[tauri::command]
+fn get_prompt_quick_picks(app: tauri::AppHandle) -> Result<db::PromptQuickPicks, Log15Error> {
+    db::get_prompt_quick_picks(&app)
+}
+
+#[tauri::command]
+fn pin_activity_cmd(app: tauri::AppHandle, activity: String) -> Result<Vec<String>, Log15Error> {
+    pin_activity(&app, &activity)
+}
+
+#[tauri::command]
+fn unpin_activity_cmd(app: tauri::AppHandle, activity: String) -> Result<Vec<String>, Log15Error> {
+    unpin_activity(&app, &activity)
+}
+
+#[tauri::command]
+fn get_pinned_activities_cmd(app: tauri::AppHandle) -> Result<Vec<String>, Log15Error> {
+    get_pinned_activities(&app)
+}
+
+#[tauri::command]
+fn attach_file_to_interval(app: tauri::AppHandle, interval_id: i64, path: String) -> Result<IntervalAttachment, Log15Error> {
+    attachments::attach_file(&app, interval_id, std::path::Path::new(&path)).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_interval_attachments(app: tauri::AppHandle, interval_id: i64) -> Result<Vec<IntervalAttachment>, Log15Error> {
+    get_attachments_by_interval(&app, interval_id)
+}
+
+// Activity alias commands
+#[tauri::command]
+fn set_activity_alias_cmd(app: tauri::AppHandle, alias: String, canonical: String) -> Result<ActivityAlias, Log15Error> {
+    set_activity_alias(&app, &alias, &canonical)
+}
+
+#[tauri::command]
+fn remove_activity_alias_cmd(app: tauri::AppHandle, alias: String) -> Result<bool, Log15Error> {
+    remove_activity_alias(&app, &alias)
+}
+
+#[tauri::command]
+fn get_activity_aliases_cmd(app: tauri::AppHandle) -> Result<Vec<ActivityAlias>, Log15Error> {
+    get_activity_aliases(&app)
+}
+
+#[tauri::command]
+fn set_activity_color(app: tauri::AppHandle, activity: String, color: String) -> Result<db::ActivityColor, Log15Error> {
+    db::set_activity_color(&app, &activity, &color)
+}
+
+#[tauri::command]
+fn remove_activity_color(app: tauri::AppHandle, activity: String) -> Result<bool, Log15Error> {
+    db::remove_activity_color(&app, &activity)
+}
+
+#[tauri::command]
+fn get_activity_colors(app: tauri::AppHandle) -> Result<Vec<db::ActivityColor>, Log15Error> {
+    db::get_activity_colors(&app)
+}
+
+/// Write intervals.parquet and workblocks.parquet into `dir` (needs the `parquet-export` feature)
+#[cfg(feature = "parquet-export")]
+#[tauri::command]
+fn export_parquet(app: tauri::AppHandle, dir: String) -> Result<parquet_export::ParquetExportSummary, Log15Error> {
+    parquet_export::export_parquet(&app, std::path::Path::new(&dir)).map_err(Log15Error::from)
+}
+
+#[cfg(not(feature = "parquet-export"))]
+#[tauri::command]
+fn export_parquet(_app: tauri::AppHandle, _dir: String) -> Result<(), Log15Error> {
+    Err(Log15Error::Other("Parquet export is not available in this build (enable the `parquet-export` feature)".to_string()))
+}
+
+/// Write a PNG with the day's headline stats (no recorded words) for sharing
+#[tauri::command]
+fn generate_share_card(app: tauri::AppHandle, date: String, path: String) -> Result<share_card::ShareCardStats, Log15Error> {
+    share_card::generate_share_card(&app, &date, std::path::Path::new(&path)).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn export_taxonomy_pack(app: tauri::AppHandle, path: String) -> Result<taxonomy::TaxonomyPack, Log15Error> {
+    taxonomy::export_taxonomy_pack(&app, std::path::Path::new(&path)).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn import_taxonomy_pack(app: tauri::AppHandle, path: String, mode: taxonomy::ImportMode) -> Result<taxonomy::ImportSummary, Log15Error> {
+    taxonomy::import_taxonomy_pack(&app, std::path::Path::new(&path), mode).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_intervals_by_workblock_cmd(app: tauri::AppHandle, workblock_id: i64) -> Result<Vec<Interval>, Log15Error> {
+    get_intervals_by_workblock(&app, workblock_id)
+}
+
+#[tauri::command]
+async fn get_current_interval_cmd(
+    app: tauri::AppHandle,
+    workblock_id: i64,
+) -> Result<Option<Interval>, Log15Error> {
+    get_current_interval(&app, workblock_id)
+}
+
+#[tauri::command]
+async fn get_timer_state(app: tauri::AppHandle) -> Result<timer::TimerState, Log15Error> {
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer = timer_manager.lock().await;
+    Ok(timer.get_state().await)
+}
+
+#[tauri::command]
+async fn get_interval_time_remaining(app: tauri::AppHandle) -> Result<Option<i64>, Log15Error> {
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer: tokio::sync::MutexGuard<'_, TimerManager> = timer_manager.lock().await;
+    Ok(timer.get_interval_time_remaining().await)
+}
+
+/// Start receiving "timer-tick" events every second (pair with stop_timer_ticker)
+#[tauri::command]
+async fn start_timer_ticker(app: tauri::AppHandle) -> Result<usize, Log15Error> {
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer = timer_manager.lock().await;
+    Ok(timer.start_ticker().await)
+}
+
+#[tauri::command]
+async fn stop_timer_ticker(app: tauri::AppHandle) -> Result<usize, Log15Error> {
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer = timer_manager.lock().await;
+    Ok(timer.stop_ticker().await)
+}
+
+/// Read-only status ("In a focus block until 15:30") without any entry text; kept current by "status-page" events
+#[tauri::command]
+async fn get_status_page(app: tauri::AppHandle) -> status_page::StatusPage {
+    status_page::get_status_page(&app).await
+}
+
+#[tauri::command]
+async fn show_status_window(app: tauri::AppHandle) -> Result<(), Log15Error> {
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let window_mgr = window_manager.lock().await;
+    window_mgr.show_status_window().await
+}
+
+#[tauri::command]
+async fn get_break_state(app: tauri::AppHandle) -> Result<Option<timer::BreakInfo>, Log15Error> {
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer = timer_manager.lock().await;
+    Ok(timer.get_break().await)
+}
+
+/// End the current break early without starting the next workblock
+#[tauri::command]
+async fn skip_break(app: tauri::AppHandle) -> Result<bool, Log15Error> {
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let timer = timer_manager.lock().await;
+    Ok(timer.end_break().await)
+}
+
+/// Ad-hoc SELECT over the database; rows are capped and slow queries are stopped
+#[tauri::command]
+fn run_readonly_query(app: tauri::AppHandle, sql: String, max_rows: Option<usize>) -> Result<explorer::QueryResult, Log15Error> {
+    explorer::run_readonly_query(&app, &sql, max_rows).map_err(Log15Error::from)
+}
+
+// Daily commands
+#[tauri::command]
+fn check_and_reset_daily_cmd(app: tauri::AppHandle) -> Result<Option<String>, Log15Error> {
+    check_and_reset_daily(&app)
+}
+
+#[tauri::command]
+fn get_today_date_cmd() -> String {
+    get_today_date()
+}
+
+#[tauri::command]
+fn get_archived_day_cmd(app: tauri::AppHandle, date: String) -> Result<Option<DailyArchive>, Log15Error> {
+    get_archived_day(&app, &date)
+}
+
+/// Rebuild the archive for a date (e.g. after reclassifying or editing intervals)
+#[tauri::command]
+fn regenerate_archive_cmd(app: tauri::AppHandle, date: String) -> Result<DailyArchive, Log15Error> {
+    archive_daily_data(&app, &date)
+}
+
+#[tauri::command]
+fn get_all_archived_dates_cmd(app: tauri::AppHandle) -> Result<Vec<DailyArchive>, Log15Error> {
+    get_all_archived_dates(&app)
+}
+
+/// Archived days newest first, without visualization data (load that per day with get_archive_visualization)
+#[tauri::command]
+fn get_archive_history(app: tauri::AppHandle, offset: i64, limit: i64) -> Result<db::ArchiveHistoryPage, Log15Error> {
+    db::get_archive_history(&app, offset, limit)
+}
+
+#[tauri::command]
+fn get_archive_visualization(app: tauri::AppHandle, date: String) -> Result<Option<serde_json::Value>, Log15Error> {
+    db::get_archive_visualization(&app, &date)
+}
+
+// Visualization commands
+#[tauri::command]
+fn get_workblock_visualization(app: tauri::AppHandle, workblock_id: i64) -> Result<WorkblockVisualization, Log15Error> {
+    generate_workblock_visualization(&app, workblock_id)
+}
+
+#[tauri::command]
+fn get_daily_aggregate_cmd(
+    app: tauri::AppHandle,
+    date: String,
+    limits: Option<TimelineLimits>,
+) -> Result<DailyAggregate, Log15Error> {
+    let mut aggregate = cache::daily_aggregate(&app, &date)?;
+    if let Some(limits) = limits {
+        aggregate.apply_limits(&limits);
+    }
+    Ok(aggregate)
+}
+
+#[tauri::command]
+fn get_daily_visualization_data_cmd(
+    app: tauri::AppHandle,
+    date: String,
+    limits: Option<TimelineLimits>,
+) -> Result<DailyVisualizationData, Log15Error> {
+    let mut data = generate_daily_visualization_data(&app, &date)?;
+    cache::remember(&date, &data.daily_aggregate);
+    if let Some(limits) = limits {
+        data.daily_aggregate.apply_limits(&limits);
+    }
+    Ok(data)
+}
+
+#[tauri::command]
+fn get_monthly_summary_cmd(app: tauri::AppHandle, year: i32, month: u32) -> Result<MonthlySummary, Log15Error> {
+    generate_monthly_summary(&app, year, month)
+}
+
+/// Weekly review for the 7 days starting at week_start (defaults to this week's Monday)
+#[tauri::command]
+fn get_weekly_review(app: tauri::AppHandle, week_start: Option<String>) -> Result<WeeklyReview, Log15Error> {
+    let week_start = week_start.unwrap_or_else(|| {
+        week_start_for(db::logical_date(chrono::Local::now(), settings::day_start_hour())).format("%Y-%m-%d").to_string()
+    });
+    generate_weekly_review(&app, &week_start)
+}
+
+/// Workblocks, intervals and archives created, modified or deleted since `since` (RFC 3339)
+#[tauri::command]
+fn get_changes_since(app: tauri::AppHandle, since: String) -> Result<changes::ChangeSet, Log15Error> {
+    changes::get_changes_since(&app, &since)
+}
+
+/// Drop cached monthly summaries, weekly reviews, ... whose key starts with `prefix` (all if omitted)
+#[tauri::command]
+fn invalidate_cache(app: tauri::AppHandle, prefix: Option<String>) -> Result<usize, Log15Error> {
+    computed_cache::invalidate_cache(&app, prefix.as_deref().unwrap_or("")).map_err(Log15Error::from)
+}
+
+/// Acknowledge a lifecycle event (interval-complete, workblock-complete, missed-intervals) by sequence number
+#[tauri::command]
+fn ack_event(seq: u64) -> bool {
+    acked_events::ack_event(seq)
+}
+
+#[tauri::command]
+fn create_backup_now(app: tauri::AppHandle) -> Result<backup::BackupInfo, Log15Error> {
+    backup::create_backup_now(&app).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn list_backups(app: tauri::AppHandle) -> Result<Vec<backup::BackupInfo>, Log15Error> {
+    backup::list_backups(&app).map_err(Log15Error::from)
+}
+
+/// Replace the database with a backup; returns the backup taken of the database it replaced
+#[tauri::command]
+async fn restore_backup(app: tauri::AppHandle, path: String) -> Result<backup::BackupInfo, Log15Error> {
+    backup::restore_backup(&app, std::path::Path::new(&path)).await.map_err(Log15Error::from)
+}
+
+/// Import a log15 database from a backup or another machine; `mode` is "replace" or "merge"
+#[tauri::command]
+fn import_database(app: tauri::AppHandle, path: String, mode: String) -> Result<import::ImportSummary, Log15Error> {
+    let mode = import::ImportMode::from_str(&mode).ok_or_else(|| format!("Unknown import mode: {}", mode))?;
+    import::import_database(&app, std::path::Path::new(&path), mode).map_err(Log15Error::from)
+}
+
+/// Delete all data, and the backups too if `delete_backups`; `confirm_token` must be wipe::WIPE_CONFIRM_TOKEN
+#[tauri::command]
+async fn wipe_all_data(
+    app: tauri::AppHandle,
+    confirm_token: String,
+    delete_backups: Option<bool>,
+) -> Result<wipe::WipeSummary, Log15Error> {
+    wipe::wipe_all_data(&app, &confirm_token, delete_backups.unwrap_or(false)).await.map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_rest_api_settings(app: tauri::AppHandle) -> settings::RestApiSettings {
+    settings::get_rest_api_settings(&app)
+}
+
+/// Turn the local REST API on or off and choose its port (needs the `rest-api` feature to enable)
+#[tauri::command]
+fn set_rest_api_settings(app: tauri::AppHandle, enabled: bool, port: u16) -> Result<settings::RestApiSettings, Log15Error> {
+    settings::set_rest_api_settings(&app, enabled, port).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn regenerate_rest_api_token(app: tauri::AppHandle) -> Result<String, Log15Error> {
+    settings::regenerate_rest_api_token(&app).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_ai_settings(app: tauri::AppHandle) -> settings::AiSettings {
+    settings::get_ai_settings(&app)
+}
+
+#[tauri::command]
+fn set_ai_settings(app: tauri::AppHandle, ai: settings::AiSettings) -> Result<settings::AiSettings, Log15Error> {
+    settings::set_ai_settings(&app, &ai).map_err(Log15Error::from)
+}
+
+/// Summarize a day's accomplishments with the configured model (sends only the interval words)
+#[tauri::command]
+async fn generate_ai_summary(app: tauri::AppHandle, date: String) -> Result<ai::AiSummary, Log15Error> {
+    ai::generate_ai_summary(&app, &date).await.map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_status_file_settings(app: tauri::AppHandle) -> settings::StatusFileSettings {
+    settings::get_status_file_settings(&app)
+}
+
+#[tauri::command]
+fn set_status_file_settings(
+    app: tauri::AppHandle,
+    status_file: settings::StatusFileSettings,
+) -> Result<settings::StatusFileSettings, Log15Error> {
+    settings::set_status_file_settings(&app, &status_file).map_err(Log15Error::from)
+}
+
+/// What the status file holds right now, for previewing it in settings
+#[tauri::command]
+async fn get_status_file_preview(app: tauri::AppHandle) -> status_file::StatusFile {
+    status_file::current_status_file(&app).await
+}
+
+#[tauri::command]
+fn get_webhooks(app: tauri::AppHandle) -> Result<Vec<webhooks::Webhook>, Log15Error> {
+    webhooks::get_webhooks(&app).map_err(Log15Error::from)
+}
+
+/// Notify `url` of `events` with signed POSTs; a secret is generated when none is given
+#[tauri::command]
+fn add_webhook(
+    app: tauri::AppHandle,
+    url: String,
+    events: Vec<webhooks::WebhookEvent>,
+    secret: Option<String>,
+) -> Result<webhooks::Webhook, Log15Error> {
+    webhooks::add_webhook(&app, &url, &events, secret.as_deref())
+}
+
+#[tauri::command]
+fn update_webhook(
+    app: tauri::AppHandle,
+    id: i64,
+    url: String,
+    events: Vec<webhooks::WebhookEvent>,
+    enabled: bool,
+) -> Result<webhooks::Webhook, Log15Error> {
+    webhooks::edit_webhook(&app, id, &url, &events, enabled)
+}
+
+#[tauri::command]
+fn remove_webhook(app: tauri::AppHandle, id: i64) -> Result<bool, Log15Error> {
+    webhooks::remove_webhook(&app, id).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn get_milestone_templates(app: tauri::AppHandle) -> settings::MilestoneTemplates {
+    settings::get_milestone_templates(&app)
+}
+
+/// Messages sent with goal, budget and streak webhooks, e.g. "{streak}-day goal streak on {date}"
+#[tauri::command]
+fn set_milestone_templates(
+    app: tauri::AppHandle,
+    templates: settings::MilestoneTemplates,
+) -> Result<settings::MilestoneTemplates, Log15Error> {
+    settings::set_milestone_templates(&app, &templates).map_err(Log15Error::from)
+}
+
+/// Hashed CSV of workblock times and daily totals, without any entry text
+#[tauri::command]
+fn export_compliance_csv(
+    app: tauri::AppHandle,
+    start_date: String,
+    end_date: String,
+    path: String,
+) -> Result<compliance_export::ComplianceExportSummary, Log15Error> {
+    compliance_export::export_compliance_csv(&app, &start_date, &end_date, std::path::Path::new(&path)).map_err(Log15Error::from)
+}
+
+/// Write the day's timeline and activity totals into its Obsidian daily note, replacing an earlier export.
+/// `template` names the note in Obsidian's date format (default "YYYY-MM-DD").
+#[tauri::command]
+fn export_to_daily_note(
+    app: tauri::AppHandle,
+    date: String,
+    vault_path: String,
+    template: Option<String>,
+) -> Result<daily_note::DailyNoteExport, Log15Error> {
+    daily_note::export_to_daily_note(&app, &date, std::path::Path::new(&vault_path), template.as_deref().unwrap_or_default()).map_err(Log15Error::from)
+}
+
+/// Any report in the versioned JSON envelope, for scripts and automation
+#[tauri::command]
+fn get_report(app: tauri::AppHandle, request: reports::ReportRequest) -> Result<reports::ReportEnvelope, Log15Error> {
+    reports::generate_report(&app, &request).map_err(Log15Error::from)
+}
+
+#[tauri::command]
+fn export_report_json(
+    app: tauri::AppHandle,
+    request: reports::ReportRequest,
+    path: String,
+) -> Result<reports::ReportEnvelope, Log15Error> {
+    reports::export_report_json(&app, &request, std::path::Path::new(&path)).map_err(Log15Error::from)
+}
+
+// Deprecated: string-encoded variants kept temporarily for frontends that still JSON.parse the result.
+// Remove once nothing calls the *_json commands.
+#[tauri::command]
+fn get_workblock_visualization_json(app: tauri::AppHandle, workblock_id: i64) -> Result<String, Log15Error> {
+    let viz = get_workblock_visualization(app, workblock_id)?;
+    serde_json::to_string(&viz).map_err(|e| Log15Error::Other(e.to_string()))
+}
+
+#[tauri::command]
+fn get_daily_aggregate_json(app: tauri::AppHandle, date: String) -> Result<String, Log15Error> {
+    let aggregate = get_daily_aggregate_cmd(app, date, None)?;
+    serde_json::to_string(&aggregate).map_err(|e| Log15Error::Other(e.to_string()))
+}
+
+#[tauri::command]
+fn get_daily_visualization_data_json(app: tauri::AppHandle, date: String) -> Result<String, Log15Error> {
+    let data = get_daily_visualization_data_cmd(app, date, None)?;
+    serde_json::to_string(&data).map_err(|e| Log15Error::Other(e.to_string()))
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .setup(|app| {
+            let mut startup = startup::StartupTimer::new();
+            
+            // Initialize database on app startup (seeds default settings on the first launch).
+            // Everything else reads it, so the migration runs before anything else starts.
+            startup.time("database", || {
+                match init_db(app.handle()).and_then(|conn| Ok(onboarding::initialize_first_run(&conn)?)) {
+                    Ok(true) => println!("First launch detected; onboarding pending"),
+                    Ok(false) => {}
+                    Err(e) => eprintln!("Failed to initialize database: {}", e),
+                }
+            });
+            
+            // Register the managers; commands look them up, so they exist before the first one runs
+            let (timer_manager, tray_manager, window_manager) = startup.time("managers", || {
+                let timer_manager = Arc::new(Mutex::new(TimerManager::new(app.handle().clone())));
+                app.manage(timer_manager.clone());
+                let tray_manager = Arc::new(Mutex::new(TrayManager::new(app.handle().clone())));
+                app.manage(tray_manager.clone());
+                let window_manager = Arc::new(Mutex::new(WindowManager::new(app.handle().clone())));
+                app.manage(window_manager.clone());
+                (timer_manager, tray_manager, window_manager)
+            });
+            
+            // The main window starts hidden and is shown with the size and position it had last time
+            startup.time("main_window", || {
+                window_state::restore_main_window(app.handle());
+                theme::apply_theme(app.handle());
+            });
+            startup.setup_done();
+            
+            // The rest starts in the background and ends with the "ready" event
+            let app = app.handle().clone();
+            async_runtime::spawn(async move {
+                // Check and reset daily on startup (archives days missed while the app was closed)
+                startup.time("daily_reset", || {
+                    if let Err(e) = check_and_reset_daily(&app) {
+                        eprintln!("Failed to check daily reset: {}", e);
+                    }
+                });
+                
+                // Setup system tray
+                startup.time("tray", || {
+                    if let Err(e) = TrayManager::setup_tray(&app) {
+                        eprintln!("Failed to setup system tray: {}", e);
+                    }
+                });
+                
+                // Create the prompt window up front (hidden) so intervals don't wait for a new webview
+                let started = std::time::Instant::now();
+                if let Err(e) = window_manager.lock().await.prepare_prompt_window().await {
+                    eprintln!("Failed to prepare prompt window: {}", e);
+                }
+                startup.record("prompt_window", started);
+                
+                startup.time("background_tasks", || {
+                    // Resume prompts on time if a pause was still running when the app quit
+                    dnd::restore_dnd(&app);
+                    
+                    // Sample the focused application during workblocks (only while the user has opted in)
+                    activity_capture::spawn_activity_capture(&app);
+                    
+                    // Close orphaned prompts and re-show lost ones
+                    watchdog::spawn_prompt_watchdog(&app);
+                    
+                    // Archive early at the configured archive time, if one is set
+                    db::spawn_archive_scheduler(&app);
+                    
+                    // Split the running workblock and archive yesterday when the day changes
+                    timer::spawn_day_rollover_watcher(&app);
+                    
+                    // Keep the optional countdown in the tray title up to date
+                    TrayManager::spawn_countdown_updates(&app);
+                    
+                    // Apply changed settings to the running timer, windows and tray without a restart
+                    timer::spawn_settings_listener(&app);
+                    WindowManager::spawn_settings_listener(&app);
+                    TrayManager::spawn_settings_listener(&app);
+                    cache::spawn_settings_listener(&app);
+                });
+                
+                startup.time("integrations", || {
+                    // Serve the local REST API while it is enabled
+                    #[cfg(feature = "rest-api")]
+                    rest_api::spawn_settings_listener(&app);
+                    
+                    // Keep the status file for status bars current while it is enabled
+                    status_file::spawn_settings_listener(&app);
+                    
+                    // Register global hotkeys from settings
+                    if let Err(e) = hotkeys::register_hotkeys(&app) {
+                        eprintln!("Failed to register hotkeys: {}", e);
+                    }
+                });
+                
+                // Restore active workblock if one exists (for app restart scenarios)
+                let started = std::time::Instant::now();
+                let timer = timer_manager.lock().await;
+                if let Err(e) = timer.restore_active_workblock().await {
+                    eprintln!("Failed to restore active workblock: {}", e);
+                }
+                drop(timer);
+                
+                // Refresh tray state after restoring workblock
+                tray_manager.lock().await.refresh_state().await;
+                startup.record("restore_workblock", started);
+                
+                startup::finish_startup(&app, startup);
+            });
+            
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            // A reloaded webview may have missed lifecycle events; replay whatever wasn't acknowledged
+            if let tauri::WindowEvent::Focused(true) = event {
+                if window.label() == "main" {
+                    acked_events::replay_unacked(window.app_handle());
+                }
+            }
+            // Keep the tray glyph readable when the OS switches between light and dark
+            if let tauri::WindowEvent::ThemeChanged(_) = event {
+                if window.label() == "main" {
+                    if settings::get_theme_preference(window.app_handle()) == settings::ThemePreference::System {
+                        theme::emit_theme_changed(window.app_handle());
+                    }
+                    let app = window.app_handle().clone();
+                    async_runtime::spawn(async move {
+                        let tray_manager = app.state::<Arc<Mutex<TrayManager>>>();
+                        tray_manager.lock().await.apply_icon();
+                    });
+                }
+            }
+        })
+        .on_tray_icon_event(|app, event| {
+            TrayManager::handle_tray_event(app, event);
         })
         .on_menu_event(|app, event| {
             // Handle menu item clicks
@@ -375,21 +1692,22 @@ pub fn run() {
                     if let Some(window) = app.get_webview_window("main") {
                         let _ = window.show();
                         let _ = window.set_focus();
-                        let _ = window.emit("tray-start-workblock", ());
+                        let _ = emit_event(app, AppEvent::TrayStartWorkblock);
                     }
                 }
                 "view_summary" => {
-                    if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                        let _ = window.emit("tray-view-summary", ());
-                    }
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = show_summary_window(app, None).await {
+                            eprintln!("[TRAY] Failed to show summary window: {}", e);
+                        }
+                    });
                 }
                 "view_last_words" => {
                     if let Some(window) = app.get_webview_window("main") {
                         let _ = window.show();
                         let _ = window.set_focus();
-                        let _ = window.emit("tray-view-last-words", ());
+                        let _ = emit_event(app, AppEvent::TrayViewLastWords);
                     }
                 }
                 "show_window" => {
@@ -403,37 +1721,242 @@ pub fn run() {
                         let _ = window.hide();
                     }
                 }
+                "repeat_last_interval" => {
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = repeat_last_from_outside(app).await {
+                            eprintln!("[TRAY] Repeating the last interval failed: {}", e);
+                        }
+                    });
+                }
+                "pause_prompts" => {
+                    if let Err(e) = dnd::enable_dnd(app, settings::get_dnd_preset_minutes(app)) {
+                        eprintln!("[TRAY] Failed to pause prompts: {}", e);
+                    }
+                }
+                "resume_prompts" => {
+                    if let Err(e) = dnd::clear_dnd(app) {
+                        eprintln!("[TRAY] Failed to resume prompts: {}", e);
+                    }
+                }
                 "quit" => {
                     app.exit(0);
                 }
-                _ => {}
+                id => {
+                    if let Some(duration_minutes) = tray::parse_start_menu_id(id) {
+                        // Start straight from the tray; the main window stays as it is
+                        let app = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = start_workblock(app.clone(), duration_minutes, None).await {
+                                eprintln!("[TRAY] Failed to start workblock: {}", e);
+                                return;
+                            }
+                            let tray_manager = app.state::<Arc<Mutex<TrayManager>>>();
+                            tray_manager.lock().await.refresh_state().await;
+                        });
+                    } else if let Some(words) = tray::parse_quick_log_menu_id(id) {
+                        let app = app.clone();
+                        let words = words.to_string();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = quick_log_from_tray(app, words).await {
+                                eprintln!("[TRAY] Quick log failed: {}", e);
+                            }
+                        });
+                    } else if let Some(status) = tray::parse_quick_status_menu_id(id) {
+                        let app = app.clone();
+                        let status = status.to_string();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = quick_status_from_tray(app, status).await {
+                                eprintln!("[TRAY] Quick status failed: {}", e);
+                            }
+                        });
+                    }
+                }
+            }
+        })
+        .invoke_handler({
+            let handler: fn(tauri::ipc::Invoke) -> bool = tauri::generate_handler![
+                greet,
+                get_api_manifest,
+                init_database,
+                start_workblock,
+                cancel_workblock_cmd,
+                update_workblock_title,
+                resume_cancelled_workblock,
+                adjust_workblock_duration,
+                preview_workblock_plan,
+                get_duration_presets,
+                set_goal,
+                remove_goal,
+                get_goals,
+                get_goal_progress,
+                get_onboarding_state,
+                complete_onboarding_step,
+                set_duration_presets,
+                get_resume_window,
+                get_context_snapshot_enabled,
+                set_context_snapshot_enabled,
+                get_interval_context,
+                get_align_intervals,
+                set_align_intervals,
+                set_resume_window,
+                get_active_workblock_cmd,
+                get_workblocks_by_date_cmd,
+                get_today_workblocks,
+                set_workblock_project_cmd,
+                create_interval,
+                submit_interval_words,
+                repeat_last_interval,
+                transcribe_and_submit,
+                get_transcription_settings,
+                set_transcription_settings,
+                set_interval_energy,
+                update_interval_notes,
+                search_intervals,
+                set_interval_project_cmd,
+                auto_away_interval,
+                submit_quick_status,
+                get_workblock_summary,
+                get_quick_statuses,
+                set_quick_statuses,
+                get_word_cloud_settings,
+                set_word_cloud_settings,
+                get_reclassify_offer_cmd,
+                reclassify_interval_cmd,
+                add_category_rule,
+                remove_category_rule,
+                get_category_rules,
+                apply_rules,
+                preview_rule,
+                get_auto_away_settings,
+                set_auto_away_timeout,
+                set_auto_away_message,
+                get_pomodoro_settings,
+                get_locale_settings,
+                set_locale_settings,
+                get_activity_capture_settings,
+                set_activity_capture_settings,
+                get_interval_app_usage,
+                clear_activity_capture,
+                get_theme,
+                set_theme_preference,
+                get_day_start_hour,
+                get_archive_time,
+                set_archive_time,
+                set_day_start_hour,
+                get_time_audit,
+                create_backup_now,
+                list_backups,
+                restore_backup,
+                import_database,
+                wipe_all_data,
+                get_rest_api_settings,
+                set_rest_api_settings,
+                regenerate_rest_api_token,
+                get_ai_settings,
+                set_ai_settings,
+                generate_ai_summary,
+                get_status_file_settings,
+                set_status_file_settings,
+                get_status_file_preview,
+                get_webhooks,
+                add_webhook,
+                update_webhook,
+                remove_webhook,
+                get_milestone_templates,
+                set_milestone_templates,
+                export_compliance_csv,
+                export_to_daily_note,
+                get_report,
+                ack_event,
+                export_report_json,
+                get_language,
+                set_language,
+                get_dnd_status,
+                enable_dnd,
+                clear_dnd,
+                get_dnd_preset,
+                set_dnd_preset,
+                get_tray_countdown,
+                set_tray_countdown,
+                get_wellbeing_settings,
+                set_wellbeing_settings,
+                get_interval_warning_settings,
+                set_interval_warning_settings,
+                get_settings_history,
+                get_diagnostics,
+                get_startup_report,
+                get_event_schema,
+                run_timer_selftest,
+                set_pomodoro_settings,
+                get_idle_threshold,
+                set_idle_threshold,
+                get_snooze_seconds,
+                get_hotkeys,
+                set_hotkeys,
+                set_snooze_seconds,
+                get_activity_suggestions,
+                get_prompt_suggestions,
+                get_prompt_quick_picks,
+                pin_activity_cmd,
+                unpin_activity_cmd,
+                get_pinned_activities_cmd,
+                attach_file_to_interval,
+                get_interval_attachments,
+                set_activity_alias_cmd,
+                remove_activity_alias_cmd,
+                get_activity_aliases_cmd,
+                set_activity_color,
+                remove_activity_color,
+                get_activity_colors,
+                export_taxonomy_pack,
+                export_parquet,
+                generate_share_card,
+                import_taxonomy_pack,
+                get_intervals_by_workblock_cmd,
+                get_current_interval_cmd,
+                check_and_reset_daily_cmd,
+                regenerate_archive_cmd,
+                get_today_date_cmd,
+                get_archived_day_cmd,
+                get_all_archived_dates_cmd,
+                get_archive_history,
+                get_archive_visualization,
+                get_workblock_visualization,
+                get_daily_aggregate_cmd,
+                get_daily_visualization_data_cmd,
+                get_monthly_summary_cmd,
+                get_weekly_review,
+                invalidate_cache,
+                get_changes_since,
+                get_workblock_visualization_json,
+                get_daily_aggregate_json,
+                get_daily_visualization_data_json,
+                get_timer_state,
+                get_interval_time_remaining,
+                start_timer_ticker,
+                stop_timer_ticker,
+                get_break_state,
+                get_status_page,
+                show_status_window,
+                run_readonly_query,
+                skip_break,
+                show_prompt_window_cmd,
+                hide_prompt_window_cmd,
+                get_prompt_queue,
+                focus_prompt_window,
+                get_passive_prompt,
+                set_passive_prompt,
+                show_summary_window,
+                get_last_viewed_date,
+                set_last_viewed_date,
+                snooze_prompt,
+            ];
+            move |invoke| {
+                api::log_deprecated_invoke(&invoke.message);
+                handler(invoke)
             }
         })
-        .invoke_handler(tauri::generate_handler![
-            greet,
-            init_database,
-            start_workblock,
-            cancel_workblock_cmd,
-            get_active_workblock_cmd,
-            get_workblocks_by_date_cmd,
-            get_today_workblocks,
-            create_interval,
-            submit_interval_words,
-            auto_away_interval,
-            get_intervals_by_workblock_cmd,
-            get_current_interval_cmd,
-            check_and_reset_daily_cmd,
-            get_today_date_cmd,
-            get_archived_day_cmd,
-            get_all_archived_dates_cmd,
-            get_workblock_visualization,
-            get_daily_aggregate_cmd,
-            get_daily_visualization_data_cmd,
-            get_timer_state,
-            get_interval_time_remaining,
-            show_prompt_window_cmd,
-            hide_prompt_window_cmd,
-        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
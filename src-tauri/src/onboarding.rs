@@ -0,0 +1,108 @@
+// First-run detection and progress through the guided setup flow
+
+use crate::db::{create_schema, get_db_connection, query_setting, store_setting, store_setting_if_missing, timestamp_now};
+use crate::settings::seed_default_settings;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+pub const FIRST_LAUNCH_KEY: &str = "onboarding_first_launch_at";
+pub const COMPLETED_STEPS_KEY: &str = "onboarding_completed_steps";
+pub const COMPLETED_AT_KEY: &str = "onboarding_completed_at";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    Welcome,
+    Durations,       // Pick workblock duration presets
+    AutoAway,        // Auto-away timeout and message
+    Hotkeys,
+    FirstWorkblock,
+}
+
+impl OnboardingStep {
+    /// All steps in the order the setup flow presents them
+    pub const ALL: [OnboardingStep; 5] = [
+        OnboardingStep::Welcome,
+        OnboardingStep::Durations,
+        OnboardingStep::AutoAway,
+        OnboardingStep::Hotkeys,
+        OnboardingStep::FirstWorkblock,
+    ];
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OnboardingState {
+    pub first_launch_at: Option<String>,
+    pub completed_steps: Vec<OnboardingStep>,
+    pub next_step: Option<OnboardingStep>,  // None once every step is done
+    pub completed_at: Option<String>,
+}
+
+/// Prepare the database on launch. On the very first launch this seeds default settings
+/// and records the launch time; returns whether this was the first launch.
+pub fn initialize_first_run(conn: &Connection) -> rusqlite::Result<bool> {
+    create_schema(conn)?;
+    // Claiming the first-launch key decides first-run detection, even if two launches race
+    if !store_setting_if_missing(conn, FIRST_LAUNCH_KEY, &timestamp_now())? {
+        return Ok(false);
+    }
+    let seeded = seed_default_settings(conn)?;
+    println!("[ONBOARDING] First launch: seeded {} default settings", seeded);
+    
+    // Installs from before onboarding existed already have history; don't walk them through setup
+    let has_history: bool = conn.query_row("SELECT EXISTS(SELECT 1 FROM workblocks)", [], |row| row.get(0))?;
+    if has_history {
+        for step in OnboardingStep::ALL {
+            record_onboarding_step(conn, step)?;
+        }
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+pub fn query_onboarding_state(conn: &Connection) -> rusqlite::Result<OnboardingState> {
+    let completed: Vec<OnboardingStep> = query_setting(conn, COMPLETED_STEPS_KEY)?
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default();
+    // Keep flow order regardless of the order steps were completed in
+    let completed_steps: Vec<OnboardingStep> = OnboardingStep::ALL
+        .into_iter()
+        .filter(|step| completed.contains(step))
+        .collect();
+    let next_step = OnboardingStep::ALL
+        .into_iter()
+        .find(|step| !completed_steps.contains(step));
+    Ok(OnboardingState {
+        first_launch_at: query_setting(conn, FIRST_LAUNCH_KEY)?,
+        completed_steps,
+        next_step,
+        completed_at: query_setting(conn, COMPLETED_AT_KEY)?,
+    })
+}
+
+/// Mark a step done (idempotent); completing the last remaining step records onboarding completion
+pub fn record_onboarding_step(conn: &Connection, step: OnboardingStep) -> rusqlite::Result<OnboardingState> {
+    let mut state = query_onboarding_state(conn)?;
+    if !state.completed_steps.contains(&step) {
+        state.completed_steps.push(step);
+        let value = serde_json::to_string(&state.completed_steps).unwrap_or_default();
+        store_setting(conn, COMPLETED_STEPS_KEY, &value)?;
+    }
+    let state = query_onboarding_state(conn)?;
+    if state.next_step.is_none() {
+        store_setting_if_missing(conn, COMPLETED_AT_KEY, &timestamp_now())?;
+        return query_onboarding_state(conn);
+    }
+    Ok(state)
+}
+
+pub fn get_onboarding_state(app: &AppHandle) -> Result<OnboardingState, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    query_onboarding_state(&conn).map_err(|e| e.to_string())
+}
+
+pub fn complete_onboarding_step(app: &AppHandle, step: OnboardingStep) -> Result<OnboardingState, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    record_onboarding_step(&conn, step).map_err(|e| e.to_string())
+}
@@ -0,0 +1,175 @@
+// Columnar export of intervals and workblocks for analysis in pandas/DuckDB.
+// Only built with the `parquet-export` feature.
+
+use crate::db::get_db_connection;
+use arrow_array::{ArrayRef, Int32Array, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+pub const INTERVALS_FILE: &str = "intervals.parquet";
+pub const WORKBLOCKS_FILE: &str = "workblocks.parquet";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ParquetExportSummary {
+    pub intervals_path: String,
+    pub workblocks_path: String,
+    pub intervals: usize,
+    pub workblocks: usize,
+}
+
+/// One row per interval; `project` is the effective project (interval override, else the workblock's)
+pub fn query_intervals_batch(conn: &Connection) -> Result<RecordBatch, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT i.id, i.workblock_id, w.date, i.interval_number, i.start_time, i.end_time,
+                    i.words, i.status, i.recorded_at, COALESCE(i.project, w.project), i.category, i.energy, i.notes
+             FROM intervals i
+             JOIN workblocks w ON w.id = i.workblock_id
+             ORDER BY i.workblock_id, i.interval_number",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i32>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, String>(7)?,
+                row.get::<_, Option<String>>(8)?,
+                row.get::<_, Option<String>>(9)?,
+                row.get::<_, Option<String>>(10)?,
+                row.get::<_, Option<i32>>(11)?,
+                row.get::<_, Option<String>>(12)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("workblock_id", DataType::Int64, false),
+        Field::new("date", DataType::Utf8, false),
+        Field::new("interval_number", DataType::Int32, false),
+        Field::new("start_time", DataType::Utf8, false),
+        Field::new("end_time", DataType::Utf8, true),
+        Field::new("words", DataType::Utf8, true),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("recorded_at", DataType::Utf8, true),
+        Field::new("project", DataType::Utf8, true),
+        Field::new("category", DataType::Utf8, true),
+        Field::new("energy", DataType::Int32, true),
+        Field::new("notes", DataType::Utf8, true),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.0))),
+        Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.1))),
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|r| &r.2))),
+        Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.3))),
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|r| &r.4))),
+        Arc::new(rows.iter().map(|r| r.5.as_deref()).collect::<StringArray>()),
+        Arc::new(rows.iter().map(|r| r.6.as_deref()).collect::<StringArray>()),
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|r| &r.7))),
+        Arc::new(rows.iter().map(|r| r.8.as_deref()).collect::<StringArray>()),
+        Arc::new(rows.iter().map(|r| r.9.as_deref()).collect::<StringArray>()),
+        Arc::new(rows.iter().map(|r| r.10.as_deref()).collect::<StringArray>()),
+        Arc::new(rows.iter().map(|r| r.11).collect::<Int32Array>()),
+        Arc::new(rows.iter().map(|r| r.12.as_deref()).collect::<StringArray>()),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).map_err(|e| e.to_string())
+}
+
+/// One row per workblock, archived days included
+pub fn query_workblocks_batch(conn: &Connection) -> Result<RecordBatch, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, start_time, end_time, duration_minutes, status, project, cancel_reason, title
+             FROM workblocks
+             ORDER BY date, start_time",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<i32>>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, Option<String>>(7)?,
+                row.get::<_, Option<String>>(8)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("date", DataType::Utf8, false),
+        Field::new("start_time", DataType::Utf8, false),
+        Field::new("end_time", DataType::Utf8, true),
+        Field::new("duration_minutes", DataType::Int32, true),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("project", DataType::Utf8, true),
+        Field::new("cancel_reason", DataType::Utf8, true),
+        Field::new("title", DataType::Utf8, true),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.0))),
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|r| &r.1))),
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|r| &r.2))),
+        Arc::new(rows.iter().map(|r| r.3.as_deref()).collect::<StringArray>()),
+        Arc::new(rows.iter().map(|r| r.4).collect::<Int32Array>()),
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|r| &r.5))),
+        Arc::new(rows.iter().map(|r| r.6.as_deref()).collect::<StringArray>()),
+        Arc::new(rows.iter().map(|r| r.7.as_deref()).collect::<StringArray>()),
+        Arc::new(rows.iter().map(|r| r.8.as_deref()).collect::<StringArray>()),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).map_err(|e| e.to_string())
+}
+
+/// Write a batch to a Snappy-compressed Parquet file
+pub fn write_parquet(batch: &RecordBatch, path: &Path) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let props = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props)).map_err(|e| e.to_string())?;
+    writer.write(batch).map_err(|e| e.to_string())?;
+    writer.close().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Write intervals.parquet and workblocks.parquet into `dir`
+pub fn export_to_dir(conn: &Connection, dir: &Path) -> Result<ParquetExportSummary, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let intervals = query_intervals_batch(conn)?;
+    let workblocks = query_workblocks_batch(conn)?;
+    let intervals_path = dir.join(INTERVALS_FILE);
+    let workblocks_path = dir.join(WORKBLOCKS_FILE);
+    write_parquet(&intervals, &intervals_path)?;
+    write_parquet(&workblocks, &workblocks_path)?;
+    Ok(ParquetExportSummary {
+        intervals_path: intervals_path.display().to_string(),
+        workblocks_path: workblocks_path.display().to_string(),
+        intervals: intervals.num_rows(),
+        workblocks: workblocks.num_rows(),
+    })
+}
+
+pub fn export_parquet(app: &AppHandle, dir: &Path) -> Result<ParquetExportSummary, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    export_to_dir(&conn, dir)
+}
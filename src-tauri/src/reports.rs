@@ -0,0 +1,120 @@
+// Machine-readable report output: every report/summary wrapped in one versioned JSON envelope,
+// so scripts can consume reports without scraping the formatted views.
+//
+// Field names come from the report structs' serde derives and are part of the contract:
+// renaming or removing one is a breaking change and needs a REPORT_SCHEMA_VERSION bump.
+
+use crate::db::{self, get_db_connection};
+use crate::{share_card, stats, time_audit};
+use chrono::{Local, NaiveDate, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+/// Version of the report envelope and the report payloads it carries
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Which report to generate, with its parameters.
+/// Serialized as e.g. `{"report": "daily_aggregate", "date": "2024-05-01"}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "report", rename_all = "snake_case")]
+pub enum ReportRequest {
+    DailyAggregate { date: String },
+    WorkblockSummary { workblock_id: i64 },
+    WeeklyReview { week_start: String },
+    MonthlySummary { year: i32, month: u32 },
+    GoalStatus { date: String },
+    BurnoutIndicator { week_start: String },
+    TimeAudit { date: String },
+    ShareCardStats { date: String },
+}
+
+impl ReportRequest {
+    /// Stable report name, matching the `report` tag
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportRequest::DailyAggregate { .. } => "daily_aggregate",
+            ReportRequest::WorkblockSummary { .. } => "workblock_summary",
+            ReportRequest::WeeklyReview { .. } => "weekly_review",
+            ReportRequest::MonthlySummary { .. } => "monthly_summary",
+            ReportRequest::GoalStatus { .. } => "goal_status",
+            ReportRequest::BurnoutIndicator { .. } => "burnout_indicator",
+            ReportRequest::TimeAudit { .. } => "time_audit",
+            ReportRequest::ShareCardStats { .. } => "share_card_stats",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReportEnvelope {
+    pub schema_version: u32,
+    pub report: String,
+    pub generated_at: String,  // RFC 3339, local time
+    pub request: ReportRequest,
+    pub data: Value,           // The report struct, serialized with its serde field names
+}
+
+/// Wrap an already generated report in the envelope
+pub fn to_envelope<T: Serialize>(request: &ReportRequest, data: &T, generated_at: &str) -> Result<ReportEnvelope, String> {
+    Ok(ReportEnvelope {
+        schema_version: REPORT_SCHEMA_VERSION,
+        report: request.name().to_string(),
+        generated_at: generated_at.to_string(),
+        request: request.clone(),
+        data: serde_json::to_value(data).map_err(|e| e.to_string())?,
+    })
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("Invalid date '{}': {}", date, e))
+}
+
+pub fn generate_report(app: &AppHandle, request: &ReportRequest) -> Result<ReportEnvelope, String> {
+    let now = Local::now();
+    let generated_at = now.to_rfc3339_opts(SecondsFormat::Secs, false);
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+
+    match request {
+        ReportRequest::DailyAggregate { date } => {
+            let aggregate = db::generate_daily_aggregate(app, date).map_err(|e| e.to_string())?;
+            to_envelope(request, &aggregate, &generated_at)
+        }
+        ReportRequest::WorkblockSummary { workblock_id } => {
+            let summary = db::query_workblock_summary(&conn, *workblock_id).map_err(|e| e.to_string())?;
+            to_envelope(request, &summary, &generated_at)
+        }
+        ReportRequest::WeeklyReview { week_start } => {
+            let review = db::query_weekly_review(&conn, week_start).map_err(|e| e.to_string())?;
+            to_envelope(request, &review, &generated_at)
+        }
+        ReportRequest::MonthlySummary { year, month } => {
+            let summary = db::query_monthly_summary(&conn, *year, *month).map_err(|e| e.to_string())?;
+            to_envelope(request, &summary, &generated_at)
+        }
+        ReportRequest::GoalStatus { date } => {
+            let in_progress = *date == db::get_today_date();
+            let status = stats::query_goal_status(&conn, date, in_progress).map_err(|e| e.to_string())?;
+            to_envelope(request, &status, &generated_at)
+        }
+        ReportRequest::BurnoutIndicator { week_start } => {
+            let indicator = stats::query_burnout_indicator(&conn, parse_date(week_start)?).map_err(|e| e.to_string())?;
+            to_envelope(request, &indicator, &generated_at)
+        }
+        ReportRequest::TimeAudit { date } => {
+            let audit = time_audit::query_time_audit(&conn, date, now).map_err(|e| e.to_string())?;
+            to_envelope(request, &audit, &generated_at)
+        }
+        ReportRequest::ShareCardStats { date } => {
+            let card = share_card::query_share_card_stats(&conn, date).map_err(|e| e.to_string())?;
+            to_envelope(request, &card, &generated_at)
+        }
+    }
+}
+
+/// Generate a report and write it as pretty-printed JSON to `path`
+pub fn export_report_json(app: &AppHandle, request: &ReportRequest, path: &std::path::Path) -> Result<ReportEnvelope, String> {
+    let envelope = generate_report(app, request)?;
+    let json = serde_json::to_string_pretty(&envelope).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())?;
+    Ok(envelope)
+}
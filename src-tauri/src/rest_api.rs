@@ -0,0 +1,164 @@
+// Local HTTP API for scripts and external tools: today's workblocks and aggregate, the timer state,
+// and logging the interval whose prompt is open. Only built with the `rest-api` feature.
+// It listens on 127.0.0.1 only, and every request needs "Authorization: Bearer <token>".
+
+use crate::db::{get_today_date, get_workblocks_by_date, DailyAggregate, Workblock};
+use crate::settings::{get_rest_api_settings, RestApiSettings};
+use crate::timer::{TimerManager, TimerState};
+use crate::window_manager::WindowManager;
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+/// Settings keys that move, restart or stop the server
+const REST_API_KEY_PREFIX: &str = "rest_api_";
+
+#[derive(Clone)]
+struct ApiState {
+    app: AppHandle,
+    token: Arc<str>,
+}
+
+/// Body of GET /v1/timer
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TimerStatus {
+    pub timer: TimerState,
+    pub interval_seconds_remaining: Option<i64>,
+}
+
+/// Body of POST /v1/interval
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LogIntervalRequest {
+    pub words: String,
+    pub energy: Option<i32>,
+}
+
+/// An error answered as {"error": "..."}
+pub struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+fn internal(e: impl ToString) -> ApiError {
+    ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
+
+/// Whether an Authorization header value carries `token`. An empty token authorizes nothing.
+pub fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(given) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    let (given, token) = (given.trim().as_bytes(), token.as_bytes());
+    // Compare every byte, so the time taken doesn't tell how much of a guess was right
+    !token.is_empty() && given.len() == token.len() && given.iter().zip(token).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+async fn require_token(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let authorization = request.headers().get(header::AUTHORIZATION).and_then(|value| value.to_str().ok());
+    if !is_authorized(authorization, &state.token) {
+        return ApiError(StatusCode::UNAUTHORIZED, "Missing or invalid token".to_string()).into_response();
+    }
+    next.run(request).await
+}
+
+async fn today_workblocks(State(state): State<ApiState>) -> Result<Json<Vec<Workblock>>, ApiError> {
+    get_workblocks_by_date(&state.app, &get_today_date()).map(Json).map_err(internal)
+}
+
+async fn today_aggregate(State(state): State<ApiState>) -> Result<Json<DailyAggregate>, ApiError> {
+    crate::cache::daily_aggregate(&state.app, &get_today_date()).map(Json).map_err(internal)
+}
+
+async fn timer_status(State(state): State<ApiState>) -> Json<TimerStatus> {
+    let Some(timer_manager) = state.app.try_state::<Arc<Mutex<TimerManager>>>() else {
+        return Json(TimerStatus { timer: TimerState::default(), interval_seconds_remaining: None });
+    };
+    let timer = timer_manager.lock().await;
+    Json(TimerStatus {
+        timer: timer.get_state().await,
+        interval_seconds_remaining: timer.get_interval_time_remaining().await,
+    })
+}
+
+/// Answer the interval whose prompt is open, as if `words` were typed into it
+async fn log_interval(
+    State(state): State<ApiState>,
+    Json(request): Json<LogIntervalRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let window_manager = state.app.state::<Arc<Mutex<WindowManager>>>();
+    let interval_id = window_manager.lock().await.get_current_interval_id().await.ok_or_else(|| {
+        ApiError(StatusCode::CONFLICT, "No prompt is waiting for an answer".to_string())
+    })?;
+    crate::log_interval_from_outside(state.app.clone(), interval_id, request.words, request.energy)
+        .await
+        .map(Json)
+        .map_err(|e| ApiError(StatusCode::BAD_REQUEST, e))
+}
+
+pub fn router(app: AppHandle, token: &str) -> Router {
+    let state = ApiState { app, token: Arc::from(token) };
+    Router::new()
+        .route("/v1/workblocks/today", get(today_workblocks))
+        .route("/v1/aggregate/today", get(today_aggregate))
+        .route("/v1/timer", get(timer_status))
+        .route("/v1/interval", post(log_interval))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+async fn serve(app: AppHandle, settings: RestApiSettings) {
+    let listener = match tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, settings.port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[REST API] Failed to listen on port {}: {}", settings.port, e);
+            return;
+        }
+    };
+    println!("[REST API] Listening on http://127.0.0.1:{}", settings.port);
+    if let Err(e) = axum::serve(listener, router(app, &settings.token)).await {
+        eprintln!("[REST API] Server stopped: {}", e);
+    }
+}
+
+/// The running server and the settings it was started with
+static SERVER: std::sync::Mutex<Option<(RestApiSettings, tauri::async_runtime::JoinHandle<()>)>> =
+    std::sync::Mutex::new(None);
+
+/// Start, stop or restart the server so it matches the current settings
+pub fn apply_rest_api_settings(app: &AppHandle) {
+    let settings = get_rest_api_settings(app);
+    let wanted = (settings.enabled && !settings.token.is_empty()).then_some(settings);
+    let mut server = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    if server.as_ref().map(|(running, _)| running) == wanted.as_ref() {
+        return;
+    }
+    if let Some((_, handle)) = server.take() {
+        handle.abort();
+        println!("[REST API] Stopped");
+    }
+    if let Some(settings) = wanted {
+        let handle = tauri::async_runtime::spawn(serve(app.clone(), settings.clone()));
+        *server = Some((settings, handle));
+    }
+}
+
+/// Serve according to the settings now, and follow them as they change
+pub fn spawn_settings_listener(app: &AppHandle) {
+    apply_rest_api_settings(app);
+    crate::settings::spawn_settings_listener("rest_api", app, |app, change| async move {
+        if change.key.is_empty() || change.key.starts_with(REST_API_KEY_PREFIX) {
+            apply_rest_api_settings(&app);
+        }
+    });
+}
@@ -0,0 +1,330 @@
+// User-defined rules that categorize intervals from their recorded words
+
+use crate::db::{get_db_connection, timestamp_now};
+use regex::{Regex, RegexBuilder};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMatchType {
+    Contains,  // Case-insensitive substring match
+    Regex,     // Case-insensitive regular expression
+}
+
+impl RuleMatchType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RuleMatchType::Contains => "contains",
+            RuleMatchType::Regex => "regex",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "regex" => RuleMatchType::Regex,
+            _ => RuleMatchType::Contains,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryRule {
+    pub id: Option<i64>,
+    pub pattern: String,
+    pub match_type: RuleMatchType,
+    pub category: String,
+    pub priority: i32,  // Lower runs first; the first matching rule wins
+    pub enabled: bool,
+    pub created_at: Option<String>,
+}
+
+enum Matcher {
+    Contains(String),  // Lowercased pattern
+    Regex(Regex),
+}
+
+/// A rule ready for matching
+pub struct CompiledRule {
+    pub rule: CategoryRule,
+    matcher: Matcher,
+}
+
+impl CompiledRule {
+    pub fn compile(rule: CategoryRule) -> Result<Self, String> {
+        let matcher = match rule.match_type {
+            RuleMatchType::Contains => Matcher::Contains(rule.pattern.trim().to_lowercase()),
+            RuleMatchType::Regex => Matcher::Regex(
+                RegexBuilder::new(&rule.pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| format!("Invalid regex '{}': {}", rule.pattern, e))?,
+            ),
+        };
+        Ok(Self { rule, matcher })
+    }
+
+    pub fn matches(&self, words: &str) -> bool {
+        match &self.matcher {
+            Matcher::Contains(pattern) => !pattern.is_empty() && words.to_lowercase().contains(pattern.as_str()),
+            Matcher::Regex(regex) => regex.is_match(words),
+        }
+    }
+}
+
+/// Category of the first rule matching `words` (rules must already be in priority order)
+pub fn categorize<'a>(words: &str, rules: &'a [CompiledRule]) -> Option<&'a str> {
+    rules
+        .iter()
+        .find(|r| r.matches(words))
+        .map(|r| r.rule.category.as_str())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RuleMatch {
+    pub interval_id: i64,
+    pub workblock_id: i64,
+    pub date: String,
+    pub words: String,
+    pub current_category: Option<String>,
+}
+
+/// What a proposed rule would match, shown before it is saved
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RulePreview {
+    pub total_matches: usize,
+    pub changed_count: usize,       // Matches whose category would actually change
+    pub matches: Vec<RuleMatch>,    // Most recent first, capped at MAX_PREVIEW_MATCHES
+}
+
+const MAX_PREVIEW_MATCHES: usize = 200;
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+/// All rules in the order they are evaluated
+pub fn query_rules(conn: &Connection) -> rusqlite::Result<Vec<CategoryRule>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, pattern, match_type, category, priority, enabled, created_at
+         FROM category_rules
+         ORDER BY priority ASC, id ASC"
+    )?;
+    let rule_iter = stmt.query_map([], |row| {
+        Ok(CategoryRule {
+            id: Some(row.get(0)?),
+            pattern: row.get(1)?,
+            match_type: RuleMatchType::from_str(&row.get::<_, String>(2)?),
+            category: row.get(3)?,
+            priority: row.get(4)?,
+            enabled: row.get(5)?,
+            created_at: row.get(6)?,
+        })
+    })?;
+
+    let mut rules = Vec::new();
+    for rule in rule_iter {
+        rules.push(rule?);
+    }
+    Ok(rules)
+}
+
+/// Enabled rules compiled for matching; rules that no longer compile are skipped
+pub fn load_compiled_rules(conn: &Connection) -> rusqlite::Result<Vec<CompiledRule>> {
+    Ok(query_rules(conn)?
+        .into_iter()
+        .filter(|r| r.enabled)
+        .filter_map(|r| match CompiledRule::compile(r) {
+            Ok(compiled) => Some(compiled),
+            Err(e) => {
+                eprintln!("[RULES] Skipping rule: {}", e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Recompute the category of a single interval from its words (called when words are recorded)
+pub fn categorize_interval(conn: &Connection, interval_id: i64) -> rusqlite::Result<Option<String>> {
+    let rules = load_compiled_rules(conn)?;
+    let words: Option<String> = conn.query_row(
+        "SELECT words FROM intervals WHERE id = ?1",
+        params![interval_id],
+        |row| row.get(0),
+    )?;
+    let category = words.as_deref().and_then(|w| categorize(w, &rules)).map(str::to_string);
+    conn.execute(
+        "UPDATE intervals SET category = ?1 WHERE id = ?2",
+        params![category, interval_id],
+    )?;
+    Ok(category)
+}
+
+/// Re-run all rules over intervals of workblocks dated within [start_date, end_date] (YYYY-MM-DD).
+/// Returns the number of intervals whose category changed.
+pub fn apply_rules_in_range(conn: &Connection, start_date: &str, end_date: &str) -> rusqlite::Result<usize> {
+    let rules = load_compiled_rules(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT i.id, i.words, i.category
+         FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE w.date >= ?1 AND w.date <= ?2"
+    )?;
+    let rows = stmt
+        .query_map(params![start_date, end_date], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, Option<String>>(2)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut changed = 0;
+    for (interval_id, words, current) in rows {
+        let category = words.as_deref().and_then(|w| categorize(w, &rules));
+        if category != current.as_deref() {
+            conn.execute(
+                "UPDATE intervals SET category = ?1 WHERE id = ?2",
+                params![category, interval_id],
+            )?;
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Run a proposed (unsaved) rule over historical intervals in [start_date, end_date]
+pub fn preview_rule_in_range(
+    conn: &Connection,
+    rule: CategoryRule,
+    start_date: &str,
+    end_date: &str,
+) -> Result<RulePreview, String> {
+    let compiled = CompiledRule::compile(rule)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT i.id, i.workblock_id, w.date, i.words, i.category
+             FROM intervals i
+             JOIN workblocks w ON w.id = i.workblock_id
+             WHERE w.date >= ?1 AND w.date <= ?2 AND i.words IS NOT NULL
+             ORDER BY i.start_time DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![start_date, end_date], |row| {
+            Ok(RuleMatch {
+                interval_id: row.get(0)?,
+                workblock_id: row.get(1)?,
+                date: row.get(2)?,
+                words: row.get(3)?,
+                current_category: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut preview = RulePreview {
+        total_matches: 0,
+        changed_count: 0,
+        matches: Vec::new(),
+    };
+    for row in rows {
+        let candidate = row.map_err(|e| e.to_string())?;
+        if !compiled.matches(&candidate.words) {
+            continue;
+        }
+        preview.total_matches += 1;
+        if candidate.current_category.as_deref() != Some(compiled.rule.category.as_str()) {
+            preview.changed_count += 1;
+        }
+        if preview.matches.len() < MAX_PREVIEW_MATCHES {
+            preview.matches.push(candidate);
+        }
+    }
+    Ok(preview)
+}
+
+pub fn insert_rule(
+    conn: &Connection,
+    pattern: &str,
+    match_type: RuleMatchType,
+    category: &str,
+    priority: i32,
+) -> Result<CategoryRule, String> {
+    let category = category.trim();
+    if pattern.trim().is_empty() || category.is_empty() {
+        return Err("Rule pattern and category must not be empty".to_string());
+    }
+    let rule = CategoryRule {
+        id: None,
+        pattern: pattern.to_string(),
+        match_type,
+        category: category.to_string(),
+        priority,
+        enabled: true,
+        created_at: Some(timestamp_now()),
+    };
+    // Reject rules that would never compile
+    let rule = CompiledRule::compile(rule)?.rule;
+
+    conn.execute(
+        "INSERT INTO category_rules (pattern, match_type, category, priority, enabled, created_at)
+         VALUES (?1, ?2, ?3, ?4, 1, ?5)",
+        params![rule.pattern, rule.match_type.as_str(), rule.category, rule.priority, rule.created_at],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(CategoryRule {
+        id: Some(conn.last_insert_rowid()),
+        ..rule
+    })
+}
+
+pub fn add_rule(
+    app: &AppHandle,
+    pattern: &str,
+    match_type: RuleMatchType,
+    category: &str,
+    priority: i32,
+) -> Result<CategoryRule, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    insert_rule(&conn, pattern, match_type, category, priority)
+}
+
+/// Delete a rule; already-categorized intervals keep their category until rules are re-applied
+pub fn remove_rule(app: &AppHandle, rule_id: i64) -> Result<bool, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let removed = conn
+        .execute("DELETE FROM category_rules WHERE id = ?1", params![rule_id])
+        .map_err(|e| e.to_string())?;
+    Ok(removed > 0)
+}
+
+pub fn get_rules(app: &AppHandle) -> Result<Vec<CategoryRule>, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    query_rules(&conn).map_err(|e| e.to_string())
+}
+
+pub fn apply_rules(app: &AppHandle, start_date: &str, end_date: &str) -> Result<usize, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    apply_rules_in_range(&conn, start_date, end_date).map_err(|e| e.to_string())
+}
+
+pub fn preview_rule(
+    app: &AppHandle,
+    pattern: &str,
+    match_type: RuleMatchType,
+    category: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Result<RulePreview, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let rule = CategoryRule {
+        id: None,
+        pattern: pattern.to_string(),
+        match_type,
+        category: category.trim().to_string(),
+        priority: 0,
+        enabled: true,
+        created_at: None,
+    };
+    preview_rule_in_range(&conn, rule, start_date, end_date)
+}
@@ -0,0 +1,1461 @@
+// Typed access to user settings stored in the settings table
+
+use crate::db::{get_db_connection, query_setting, set_setting, store_setting_if_missing};
+use crate::stats::Milestone;
+use crate::timer::validate_duration_minutes;
+use crate::timing::{timing, Timing, TimingProfile};
+use chrono::NaiveTime;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+use tokio::sync::broadcast;
+
+pub const AUTO_AWAY_TIMEOUT_KEY: &str = "auto_away_timeout_seconds";
+pub const AUTO_AWAY_MESSAGE_KEY: &str = "auto_away_message";
+pub const SNOOZE_DURATION_KEY: &str = "snooze_seconds";
+pub const OPEN_PROMPT_HOTKEY_KEY: &str = "hotkey_open_prompt";
+pub const TOGGLE_WORKBLOCK_HOTKEY_KEY: &str = "hotkey_toggle_workblock";
+pub const REPEAT_LAST_HOTKEY_KEY: &str = "hotkey_repeat_last";
+pub const HOTKEY_WORKBLOCK_MINUTES_KEY: &str = "hotkey_workblock_minutes";
+pub const IDLE_THRESHOLD_KEY: &str = "idle_threshold_seconds";
+pub const RESUME_WINDOW_KEY: &str = "resume_window_minutes";
+pub const ALIGN_INTERVALS_KEY: &str = "align_intervals_to_clock";
+pub const DURATION_PRESETS_KEY: &str = "duration_presets";
+pub const POMODORO_ENABLED_KEY: &str = "pomodoro_enabled";
+pub const BREAK_DURATION_KEY: &str = "break_seconds";
+pub const BREAK_AUTO_START_KEY: &str = "break_auto_start_next";
+pub const INTERVAL_WARNING_SECONDS_KEY: &str = "interval_warning_seconds";
+pub const INTERVAL_WARNING_NOTIFICATION_KEY: &str = "interval_warning_notification";
+pub const PASSIVE_PROMPT_KEY: &str = "passive_prompt";
+pub const WELLBEING_ENABLED_KEY: &str = "wellbeing_guard_enabled";
+pub const WELLBEING_LIMIT_KEY: &str = "wellbeing_daily_limit_minutes";
+pub const TRAY_COUNTDOWN_KEY: &str = "tray_countdown";
+pub const DND_PRESET_MINUTES_KEY: &str = "dnd_preset_minutes";
+pub const QUICK_STATUSES_KEY: &str = "quick_statuses";
+pub const LOCALE_DECIMAL_SEPARATOR_KEY: &str = "locale_decimal_separator";
+pub const LOCALE_TIME_FORMAT_KEY: &str = "locale_time_format";
+pub const LOCALE_DATE_ORDER_KEY: &str = "locale_date_order";
+pub const LANGUAGE_KEY: &str = "language";
+pub const ACTIVITY_CAPTURE_ENABLED_KEY: &str = "activity_capture_enabled";
+pub const ACTIVITY_CAPTURE_TITLES_KEY: &str = "activity_capture_window_titles";
+pub const ACTIVITY_CAPTURE_SAMPLE_KEY: &str = "activity_capture_sample_seconds";
+pub const DAY_START_HOUR_KEY: &str = "day_start_hour";
+pub const THEME_KEY: &str = "theme";
+pub const ARCHIVE_TIME_KEY: &str = "archive_time";
+pub const CONTEXT_SNAPSHOT_KEY: &str = "context_snapshot_enabled";
+pub const REST_API_ENABLED_KEY: &str = "rest_api_enabled";
+pub const REST_API_PORT_KEY: &str = "rest_api_port";
+pub const REST_API_TOKEN_KEY: &str = "rest_api_token";
+pub const STATUS_FILE_ENABLED_KEY: &str = "status_file_enabled";
+pub const STATUS_FILE_PATH_KEY: &str = "status_file_path";
+pub const STATUS_FILE_FORMAT_KEY: &str = "status_file_format";
+pub const AI_SUMMARY_ENABLED_KEY: &str = "ai_summary_enabled";
+pub const AI_ENDPOINT_KEY: &str = "ai_endpoint";
+pub const AI_MODEL_KEY: &str = "ai_model";
+pub const AI_API_KEY_KEY: &str = "ai_api_key";
+pub const VOICE_INPUT_ENABLED_KEY: &str = "voice_input_enabled";
+pub const TRANSCRIPTION_BACKEND_KEY: &str = "transcription_backend";
+pub const TRANSCRIPTION_ENDPOINT_KEY: &str = "transcription_endpoint";
+pub const TRANSCRIPTION_MODEL_KEY: &str = "transcription_model";
+pub const TRANSCRIPTION_API_KEY_KEY: &str = "transcription_api_key";
+pub const WHISPER_MODEL_PATH_KEY: &str = "whisper_model_path";
+pub const WORD_CLOUD_STOP_WORDS_KEY: &str = "word_cloud_stop_words";
+pub const WORD_CLOUD_MIN_LENGTH_KEY: &str = "word_cloud_min_length";
+pub const GOAL_REACHED_TEMPLATE_KEY: &str = "milestone_template_goal_reached";
+pub const BUDGET_EXCEEDED_TEMPLATE_KEY: &str = "milestone_template_budget_exceeded";
+pub const STREAK_MILESTONE_TEMPLATE_KEY: &str = "milestone_template_streak_milestone";
+
+/// Settings holding credentials: their values stay out of settings_history and settings-changed payloads
+pub const SECRET_SETTING_KEYS: [&str; 3] = [REST_API_TOKEN_KEY, AI_API_KEY_KEY, TRANSCRIPTION_API_KEY_KEY];
+/// What a settings-changed payload carries instead of a secret value
+pub const REDACTED_SETTING_VALUE: &str = "[redacted]";
+
+pub fn is_secret_setting(key: &str) -> bool {
+    SECRET_SETTING_KEYS.contains(&key)
+}
+
+/// Settings that only remember the UI layout and are rewritten as the window moves: they stay out
+/// of settings_history and don't invalidate the computed cache
+pub const LAYOUT_SETTING_KEYS: [&str; 1] = [crate::window_state::MAIN_WINDOW_STATE_KEY];
+
+pub fn is_layout_setting(key: &str) -> bool {
+    LAYOUT_SETTING_KEYS.contains(&key)
+}
+
+// Default auto-away timeout, snooze, idle threshold and break length come from the timing profile
+pub const DEFAULT_AUTO_AWAY_MESSAGE: &str = "Away from workspace";
+pub const DEFAULT_OPEN_PROMPT_HOTKEY: &str = "CommandOrControl+Shift+L";
+pub const DEFAULT_TOGGLE_WORKBLOCK_HOTKEY: &str = "CommandOrControl+Shift+B";
+pub const DEFAULT_REPEAT_LAST_HOTKEY: &str = ""; // Opt-in: common shortcuts already mean something elsewhere
+pub const DEFAULT_HOTKEY_WORKBLOCK_MINUTES: i32 = 60;
+pub const DEFAULT_RESUME_WINDOW_MINUTES: i64 = 10;
+pub const DEFAULT_DURATION_PRESETS: [i32; 5] = [15, 30, 60, 90, 120];
+pub const DEFAULT_INTERVAL_WARNING_SECONDS: u64 = 60;
+pub const MAX_INTERVAL_WARNING_SECONDS: u64 = 10 * 60;
+pub const DEFAULT_WELLBEING_LIMIT_MINUTES: i32 = 9 * 60;
+pub const DEFAULT_DND_PRESET_MINUTES: i32 = 60;
+pub const DEFAULT_ACTIVITY_CAPTURE_SAMPLE_SECONDS: u64 = 10;
+pub const DEFAULT_DAY_START_HOUR: u32 = 0;
+pub const DEFAULT_QUICK_STATUSES: [&str; 3] = ["Lunch", "Meeting ran over", "Errand"];
+pub const DEFAULT_REST_API_PORT: u16 = 15015;
+pub const DEFAULT_AI_ENDPOINT: &str = "https://api.openai.com/v1";
+pub const DEFAULT_AI_MODEL: &str = "gpt-4o-mini";
+pub const DEFAULT_TRANSCRIPTION_MODEL: &str = "whisper-1";
+pub const DEFAULT_WORD_CLOUD_MIN_LENGTH: usize = 3;
+pub const DEFAULT_GOAL_REACHED_TEMPLATE: &str = "Goal reached on {date}: {actual} of {target} {metric}";
+pub const DEFAULT_BUDGET_EXCEEDED_TEMPLATE: &str =
+    "Daily limit exceeded on {date}: {tracked_minutes} of {daily_limit_minutes} minutes tracked";
+pub const DEFAULT_STREAK_MILESTONE_TEMPLATE: &str = "{streak}-day goal streak on {date}";
+/// Common English words that say nothing about the activity
+pub const DEFAULT_STOP_WORDS: [&str; 40] = [
+    "a", "about", "after", "an", "and", "are", "as", "at", "be", "but", "by", "did", "for", "from", "had", "has", "have",
+    "in", "into", "is", "it", "its", "more", "of", "on", "or", "out", "some", "still", "that", "the", "then",
+    "this", "to", "up", "was", "were", "while", "will", "with",
+];
+/// Longest quick status name, matching the prompt's input limit
+pub const MAX_QUICK_STATUS_LENGTH: usize = 50;
+
+/// Settings whose defaults come from the timing profile
+const PROFILE_TIMEOUT_KEYS: [&str; 4] = [AUTO_AWAY_TIMEOUT_KEY, SNOOZE_DURATION_KEY, IDLE_THRESHOLD_KEY, BREAK_DURATION_KEY];
+
+/// Write the default value of every setting that has none yet (existing choices are kept).
+/// Returns how many settings were seeded. Under a non-production timing profile the timeouts it
+/// shortens are left unset, so a test run doesn't leave them behind in a real database.
+pub fn seed_default_settings(conn: &Connection) -> rusqlite::Result<usize> {
+    let presets = serde_json::to_string(&DEFAULT_DURATION_PRESETS).unwrap_or_default();
+    let quick_statuses = serde_json::to_string(&DEFAULT_QUICK_STATUSES).unwrap_or_default();
+    let stop_words = serde_json::to_string(&DEFAULT_STOP_WORDS[..]).unwrap_or_default();
+    let defaults = [
+        (AUTO_AWAY_TIMEOUT_KEY, Timing::PRODUCTION.auto_away_timeout_seconds.to_string()),
+        (AUTO_AWAY_MESSAGE_KEY, DEFAULT_AUTO_AWAY_MESSAGE.to_string()),
+        (SNOOZE_DURATION_KEY, Timing::PRODUCTION.snooze_seconds.to_string()),
+        (OPEN_PROMPT_HOTKEY_KEY, DEFAULT_OPEN_PROMPT_HOTKEY.to_string()),
+        (TOGGLE_WORKBLOCK_HOTKEY_KEY, DEFAULT_TOGGLE_WORKBLOCK_HOTKEY.to_string()),
+        (REPEAT_LAST_HOTKEY_KEY, DEFAULT_REPEAT_LAST_HOTKEY.to_string()),
+        (HOTKEY_WORKBLOCK_MINUTES_KEY, DEFAULT_HOTKEY_WORKBLOCK_MINUTES.to_string()),
+        (IDLE_THRESHOLD_KEY, Timing::PRODUCTION.idle_threshold_seconds.to_string()),
+        (RESUME_WINDOW_KEY, DEFAULT_RESUME_WINDOW_MINUTES.to_string()),
+        (ALIGN_INTERVALS_KEY, false.to_string()),
+        (DURATION_PRESETS_KEY, presets),
+        (POMODORO_ENABLED_KEY, false.to_string()),
+        (BREAK_DURATION_KEY, Timing::PRODUCTION.break_seconds.to_string()),
+        (BREAK_AUTO_START_KEY, false.to_string()),
+        (INTERVAL_WARNING_SECONDS_KEY, DEFAULT_INTERVAL_WARNING_SECONDS.to_string()),
+        (INTERVAL_WARNING_NOTIFICATION_KEY, false.to_string()),
+        (PASSIVE_PROMPT_KEY, false.to_string()),
+        (WELLBEING_ENABLED_KEY, false.to_string()),
+        (WELLBEING_LIMIT_KEY, DEFAULT_WELLBEING_LIMIT_MINUTES.to_string()),
+        (TRAY_COUNTDOWN_KEY, TrayCountdownMode::Off.as_str().to_string()),
+        (DND_PRESET_MINUTES_KEY, DEFAULT_DND_PRESET_MINUTES.to_string()),
+        (QUICK_STATUSES_KEY, quick_statuses),
+        (LOCALE_DECIMAL_SEPARATOR_KEY, DecimalSeparator::Dot.as_str().to_string()),
+        (LOCALE_TIME_FORMAT_KEY, TimeFormat::H24.as_str().to_string()),
+        (LOCALE_DATE_ORDER_KEY, DateOrder::Ymd.as_str().to_string()),
+        (LANGUAGE_KEY, Language::En.as_str().to_string()),
+        (ACTIVITY_CAPTURE_ENABLED_KEY, false.to_string()),
+        (ACTIVITY_CAPTURE_TITLES_KEY, false.to_string()),
+        (ACTIVITY_CAPTURE_SAMPLE_KEY, DEFAULT_ACTIVITY_CAPTURE_SAMPLE_SECONDS.to_string()),
+        (DAY_START_HOUR_KEY, DEFAULT_DAY_START_HOUR.to_string()),
+        (THEME_KEY, ThemePreference::System.as_str().to_string()),
+        (ARCHIVE_TIME_KEY, String::new()),
+        (CONTEXT_SNAPSHOT_KEY, false.to_string()),
+        (REST_API_ENABLED_KEY, false.to_string()),
+        (REST_API_PORT_KEY, DEFAULT_REST_API_PORT.to_string()),
+        (REST_API_TOKEN_KEY, String::new()),
+        (STATUS_FILE_ENABLED_KEY, false.to_string()),
+        (STATUS_FILE_PATH_KEY, String::new()),
+        (STATUS_FILE_FORMAT_KEY, StatusFileFormat::Json.as_str().to_string()),
+        (AI_SUMMARY_ENABLED_KEY, false.to_string()),
+        (AI_ENDPOINT_KEY, DEFAULT_AI_ENDPOINT.to_string()),
+        (AI_MODEL_KEY, DEFAULT_AI_MODEL.to_string()),
+        (AI_API_KEY_KEY, String::new()),
+        (VOICE_INPUT_ENABLED_KEY, false.to_string()),
+        (TRANSCRIPTION_BACKEND_KEY, TranscriptionBackend::Api.as_str().to_string()),
+        (TRANSCRIPTION_ENDPOINT_KEY, DEFAULT_AI_ENDPOINT.to_string()),
+        (TRANSCRIPTION_MODEL_KEY, DEFAULT_TRANSCRIPTION_MODEL.to_string()),
+        (TRANSCRIPTION_API_KEY_KEY, String::new()),
+        (WHISPER_MODEL_PATH_KEY, String::new()),
+        (WORD_CLOUD_STOP_WORDS_KEY, stop_words),
+        (WORD_CLOUD_MIN_LENGTH_KEY, DEFAULT_WORD_CLOUD_MIN_LENGTH.to_string()),
+        (GOAL_REACHED_TEMPLATE_KEY, DEFAULT_GOAL_REACHED_TEMPLATE.to_string()),
+        (BUDGET_EXCEEDED_TEMPLATE_KEY, DEFAULT_BUDGET_EXCEEDED_TEMPLATE.to_string()),
+        (STREAK_MILESTONE_TEMPLATE_KEY, DEFAULT_STREAK_MILESTONE_TEMPLATE.to_string()),
+    ];
+    let production = timing().profile == TimingProfile::Production;
+    let mut seeded = 0;
+    for (key, value) in defaults {
+        if !production && PROFILE_TIMEOUT_KEYS.contains(&key) {
+            continue;
+        }
+        if store_setting_if_missing(conn, key, &value)? {
+            seeded += 1;
+        }
+    }
+    Ok(seeded)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AutoAwaySettings {
+    pub timeout_seconds: u64,
+    pub message: String,
+}
+
+impl Default for AutoAwaySettings {
+    fn default() -> Self {
+        Self {
+            timeout_seconds: timing().auto_away_timeout_seconds,
+            message: DEFAULT_AUTO_AWAY_MESSAGE.to_string(),
+        }
+    }
+}
+
+/// Read auto-away settings, falling back to defaults if the database is unavailable
+pub fn get_auto_away_settings(app: &AppHandle) -> AutoAwaySettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_auto_away_settings(&conn),
+        Err(_) => AutoAwaySettings::default(),
+    }
+}
+
+/// Read auto-away settings, falling back to defaults for missing or malformed values
+pub fn query_auto_away_settings(conn: &Connection) -> AutoAwaySettings {
+    let defaults = AutoAwaySettings::default();
+    let timeout_seconds = query_setting(conn, AUTO_AWAY_TIMEOUT_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|s| *s > 0)
+        .unwrap_or(defaults.timeout_seconds);
+    let message = query_setting(conn, AUTO_AWAY_MESSAGE_KEY)
+        .ok()
+        .flatten()
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or(defaults.message);
+    AutoAwaySettings { timeout_seconds, message }
+}
+
+pub fn set_auto_away_timeout(app: &AppHandle, timeout_seconds: u64) -> Result<AutoAwaySettings, String> {
+    if timeout_seconds == 0 {
+        return Err("Auto-away timeout must be at least 1 second".to_string());
+    }
+    set_setting(app, AUTO_AWAY_TIMEOUT_KEY, &timeout_seconds.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_auto_away_settings(app))
+}
+
+pub fn set_auto_away_message(app: &AppHandle, message: &str) -> Result<AutoAwaySettings, String> {
+    let message = message.trim();
+    if message.is_empty() {
+        return Err("Auto-away message must not be empty".to_string());
+    }
+    set_setting(app, AUTO_AWAY_MESSAGE_KEY, message).map_err(|e| e.to_string())?;
+    Ok(get_auto_away_settings(app))
+}
+
+/// Named away reasons ("Lunch", "Errand", ...) offered when the user returns
+pub fn get_quick_statuses(app: &AppHandle) -> Vec<String> {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_quick_statuses(&conn))
+        .unwrap_or_else(|| DEFAULT_QUICK_STATUSES.iter().map(|s| s.to_string()).collect())
+}
+
+/// Stored quick statuses, or None if unset or no longer valid
+pub fn query_quick_statuses(conn: &Connection) -> Option<Vec<String>> {
+    let stored = query_setting(conn, QUICK_STATUSES_KEY).ok().flatten()?;
+    let statuses: Vec<String> = serde_json::from_str(&stored).ok()?;
+    normalize_quick_statuses(&statuses).ok()
+}
+
+/// Trim names and drop blanks and case-insensitive duplicates, keeping the user's order.
+/// An empty list is allowed and hides the quick statuses.
+pub fn normalize_quick_statuses(statuses: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for status in statuses.iter().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        if status.chars().count() > MAX_QUICK_STATUS_LENGTH {
+            return Err(format!("Quick status '{}' is longer than {} characters", status, MAX_QUICK_STATUS_LENGTH));
+        }
+        if !normalized.iter().any(|s| s.eq_ignore_ascii_case(status)) {
+            normalized.push(status.to_string());
+        }
+    }
+    Ok(normalized)
+}
+
+pub fn set_quick_statuses(app: &AppHandle, statuses: &[String]) -> Result<Vec<String>, String> {
+    let statuses = normalize_quick_statuses(statuses)?;
+    let value = serde_json::to_string(&statuses).map_err(|e| e.to_string())?;
+    set_setting(app, QUICK_STATUSES_KEY, &value).map_err(|e| e.to_string())?;
+    Ok(statuses)
+}
+
+/// The configured quick status matching `name` (ignoring case and surrounding whitespace)
+pub fn find_quick_status(statuses: &[String], name: &str) -> Option<String> {
+    statuses.iter().find(|s| s.eq_ignore_ascii_case(name.trim())).cloned()
+}
+
+/// How long a snoozed prompt stays hidden
+pub fn get_snooze_seconds(app: &AppHandle) -> u64 {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_setting(&conn, SNOOZE_DURATION_KEY).ok().flatten())
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|s| *s > 0)
+        .unwrap_or(timing().snooze_seconds)
+}
+
+pub fn set_snooze_seconds(app: &AppHandle, snooze_seconds: u64) -> Result<u64, String> {
+    if snooze_seconds == 0 {
+        return Err("Snooze duration must be at least 1 second".to_string());
+    }
+    set_setting(app, SNOOZE_DURATION_KEY, &snooze_seconds.to_string()).map_err(|e| e.to_string())?;
+    Ok(snooze_seconds)
+}
+
+/// No input for this long marks intervals as auto-away (0 = idle detection off)
+pub fn get_idle_threshold_seconds(app: &AppHandle) -> u64 {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_setting(&conn, IDLE_THRESHOLD_KEY).ok().flatten())
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(timing().idle_threshold_seconds)
+}
+
+pub fn set_idle_threshold_seconds(app: &AppHandle, threshold_seconds: u64) -> Result<u64, String> {
+    set_setting(app, IDLE_THRESHOLD_KEY, &threshold_seconds.to_string()).map_err(|e| e.to_string())?;
+    Ok(threshold_seconds)
+}
+
+/// Workblock lengths (minutes) offered as one-click choices in the UI
+pub fn get_duration_presets(app: &AppHandle) -> Vec<i32> {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_duration_presets(&conn))
+        .unwrap_or_else(|| DEFAULT_DURATION_PRESETS.to_vec())
+}
+
+/// Stored presets, or None if unset or no longer valid
+pub fn query_duration_presets(conn: &Connection) -> Option<Vec<i32>> {
+    let stored = query_setting(conn, DURATION_PRESETS_KEY).ok().flatten()?;
+    let presets: Vec<i32> = serde_json::from_str(&stored).ok()?;
+    normalize_duration_presets(&presets).ok()
+}
+
+/// Validate presets and return them sorted and deduplicated
+pub fn normalize_duration_presets(presets: &[i32]) -> Result<Vec<i32>, String> {
+    if presets.is_empty() {
+        return Err("At least one duration preset is required".to_string());
+    }
+    let mut normalized = presets
+        .iter()
+        .map(|&minutes| validate_duration_minutes(minutes))
+        .collect::<Result<Vec<i32>, _>>()
+        .map_err(|e| e.to_string())?;
+    normalized.sort_unstable();
+    normalized.dedup();
+    Ok(normalized)
+}
+
+pub fn set_duration_presets(app: &AppHandle, presets: &[i32]) -> Result<Vec<i32>, String> {
+    let presets = normalize_duration_presets(presets)?;
+    let value = serde_json::to_string(&presets).map_err(|e| e.to_string())?;
+    set_setting(app, DURATION_PRESETS_KEY, &value).map_err(|e| e.to_string())?;
+    Ok(presets)
+}
+
+/// How entries are split into words for the word cloud (WorkblockVisualization::word_counts)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WordCloudSettings {
+    pub stop_words: Vec<String>,  // Lowercase words never counted
+    pub min_length: usize,        // Shorter words aren't counted either
+}
+
+impl Default for WordCloudSettings {
+    fn default() -> Self {
+        Self {
+            stop_words: DEFAULT_STOP_WORDS.iter().map(|w| w.to_string()).collect(),
+            min_length: DEFAULT_WORD_CLOUD_MIN_LENGTH,
+        }
+    }
+}
+
+pub fn get_word_cloud_settings(app: &AppHandle) -> WordCloudSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_word_cloud_settings(&conn),
+        Err(_) => WordCloudSettings::default(),
+    }
+}
+
+pub fn query_word_cloud_settings(conn: &Connection) -> WordCloudSettings {
+    let defaults = WordCloudSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    WordCloudSettings {
+        stop_words: get(WORD_CLOUD_STOP_WORDS_KEY)
+            .and_then(|v| serde_json::from_str::<Vec<String>>(&v).ok())
+            .map(|words| normalize_stop_words(&words))
+            .unwrap_or(defaults.stop_words),
+        min_length: get(WORD_CLOUD_MIN_LENGTH_KEY)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.min_length),
+    }
+}
+
+/// Lowercase and trim stop words, dropping blanks and duplicates
+pub fn normalize_stop_words(words: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = words.iter().map(|w| w.trim().to_lowercase()).filter(|w| !w.is_empty()).collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+pub fn set_word_cloud_settings(app: &AppHandle, word_cloud: &WordCloudSettings) -> Result<WordCloudSettings, String> {
+    if word_cloud.min_length == 0 {
+        return Err("Minimum word length must be at least 1".to_string());
+    }
+    let stop_words = serde_json::to_string(&normalize_stop_words(&word_cloud.stop_words)).map_err(|e| e.to_string())?;
+    set_setting(app, WORD_CLOUD_STOP_WORDS_KEY, &stop_words).map_err(|e| e.to_string())?;
+    set_setting(app, WORD_CLOUD_MIN_LENGTH_KEY, &word_cloud.min_length.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_word_cloud_settings(app))
+}
+
+/// Whether intervals snap to clock boundaries instead of starting when the workblock starts.
+/// The first interval is shortened and the interval count is unchanged, so an aligned block ends on a boundary.
+pub fn get_align_intervals_to_clock(app: &AppHandle) -> bool {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_setting(&conn, ALIGN_INTERVALS_KEY).ok().flatten())
+        .map(|v| v == "true")
+        .unwrap_or(false)
+}
+
+/// Takes effect from the next workblock
+pub fn set_align_intervals_to_clock(app: &AppHandle, enabled: bool) -> Result<bool, String> {
+    set_setting(app, ALIGN_INTERVALS_KEY, &enabled.to_string()).map_err(|e| e.to_string())?;
+    Ok(enabled)
+}
+
+/// Whether the prompt appears without taking keyboard focus; the hotkey or a click on it focuses the input
+pub fn get_passive_prompt(app: &AppHandle) -> bool {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_setting(&conn, PASSIVE_PROMPT_KEY).ok().flatten())
+        .map(|v| v == "true")
+        .unwrap_or(false)
+}
+
+/// Takes effect the next time the prompt is shown
+pub fn set_passive_prompt(app: &AppHandle, enabled: bool) -> Result<bool, String> {
+    set_setting(app, PASSIVE_PROMPT_KEY, &enabled.to_string()).map_err(|e| e.to_string())?;
+    Ok(enabled)
+}
+
+/// Whether a context snapshot (application, monitors, battery) is stored with each answer
+pub fn get_context_snapshot_enabled(app: &AppHandle) -> bool {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_setting(&conn, CONTEXT_SNAPSHOT_KEY).ok().flatten())
+        .map(|v| v == "true")
+        .unwrap_or(false)
+}
+
+pub fn set_context_snapshot_enabled(app: &AppHandle, enabled: bool) -> Result<bool, String> {
+    set_setting(app, CONTEXT_SNAPSHOT_KEY, &enabled.to_string()).map_err(|e| e.to_string())?;
+    Ok(enabled)
+}
+
+/// Local HTTP API (rest_api.rs, needs the `rest-api` feature). It only listens on 127.0.0.1, and
+/// every request must carry the token as "Authorization: Bearer <token>".
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RestApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,  // Empty until the API is first enabled
+}
+
+impl Default for RestApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_REST_API_PORT,
+            token: String::new(),
+        }
+    }
+}
+
+pub fn get_rest_api_settings(app: &AppHandle) -> RestApiSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_rest_api_settings(&conn),
+        Err(_) => RestApiSettings::default(),
+    }
+}
+
+/// Read REST API settings, falling back to defaults for missing or malformed values
+pub fn query_rest_api_settings(conn: &Connection) -> RestApiSettings {
+    let defaults = RestApiSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    RestApiSettings {
+        enabled: get(REST_API_ENABLED_KEY).map(|v| v == "true").unwrap_or(defaults.enabled),
+        port: get(REST_API_PORT_KEY)
+            .and_then(|v| v.parse::<u16>().ok())
+            .filter(|p| *p > 0)
+            .unwrap_or(defaults.port),
+        token: get(REST_API_TOKEN_KEY).unwrap_or(defaults.token),
+    }
+}
+
+/// Enable or disable the API and choose its port; a token is generated the first time it is enabled.
+/// The server is started, stopped or moved by its settings listener.
+pub fn set_rest_api_settings(app: &AppHandle, enabled: bool, port: u16) -> Result<RestApiSettings, String> {
+    if enabled && !cfg!(feature = "rest-api") {
+        return Err("The REST API is not available in this build (enable the `rest-api` feature)".to_string());
+    }
+    if port == 0 {
+        return Err("Port must be between 1 and 65535".to_string());
+    }
+    if enabled && get_rest_api_settings(app).token.is_empty() {
+        regenerate_rest_api_token(app)?;
+    }
+    set_setting(app, REST_API_PORT_KEY, &port.to_string()).map_err(|e| e.to_string())?;
+    set_setting(app, REST_API_ENABLED_KEY, &enabled.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_rest_api_settings(app))
+}
+
+/// Replace the API token, locking out every client that uses the old one
+pub fn regenerate_rest_api_token(app: &AppHandle) -> Result<String, String> {
+    let token = crate::webhooks::generate_secret();
+    set_setting(app, REST_API_TOKEN_KEY, &token).map_err(|e| e.to_string())?;
+    Ok(token)
+}
+
+/// Contents of the status file
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusFileFormat {
+    Json,
+    Text,  // A single line, e.g. "writing docs · 7:32 · 2h 15m today"
+}
+
+impl StatusFileFormat {
+    pub fn as_str(&self) -> &str {
+        match self {
+            StatusFileFormat::Json => "json",
+            StatusFileFormat::Text => "text",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "text" => StatusFileFormat::Text,
+            _ => StatusFileFormat::Json,
+        }
+    }
+}
+
+/// A file kept up to date with the current status for status bars (status_file.rs)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StatusFileSettings {
+    pub enabled: bool,
+    pub path: String,  // Absolute; empty writes status.json or status.txt in the app data directory
+    pub format: StatusFileFormat,
+}
+
+impl Default for StatusFileSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            format: StatusFileFormat::Json,
+        }
+    }
+}
+
+pub fn get_status_file_settings(app: &AppHandle) -> StatusFileSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_status_file_settings(&conn),
+        Err(_) => StatusFileSettings::default(),
+    }
+}
+
+pub fn query_status_file_settings(conn: &Connection) -> StatusFileSettings {
+    let defaults = StatusFileSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    StatusFileSettings {
+        enabled: get(STATUS_FILE_ENABLED_KEY).map(|v| v == "true").unwrap_or(defaults.enabled),
+        path: get(STATUS_FILE_PATH_KEY).unwrap_or(defaults.path),
+        format: get(STATUS_FILE_FORMAT_KEY).map(|v| StatusFileFormat::from_str(&v)).unwrap_or(defaults.format),
+    }
+}
+
+/// Save status file settings; the writer picks them up through its settings listener
+pub fn set_status_file_settings(app: &AppHandle, status_file: &StatusFileSettings) -> Result<StatusFileSettings, String> {
+    let path = status_file.path.trim();
+    if !path.is_empty() && !std::path::Path::new(path).is_absolute() {
+        return Err(format!("Status file path must be absolute: {}", path));
+    }
+    set_setting(app, STATUS_FILE_PATH_KEY, path).map_err(|e| e.to_string())?;
+    set_setting(app, STATUS_FILE_FORMAT_KEY, status_file.format.as_str()).map_err(|e| e.to_string())?;
+    set_setting(app, STATUS_FILE_ENABLED_KEY, &status_file.enabled.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_status_file_settings(app))
+}
+
+/// End-of-day summaries written by a language model (ai.rs). Only the day's interval words are
+/// sent, to an OpenAI-compatible endpoint of the user's choosing (a local server works too).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AiSettings {
+    pub enabled: bool,
+    pub endpoint: String,  // Base URL; /chat/completions is appended
+    pub model: String,
+    pub api_key: String,   // Sent as a bearer token; empty sends none
+}
+
+impl Default for AiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: DEFAULT_AI_ENDPOINT.to_string(),
+            model: DEFAULT_AI_MODEL.to_string(),
+            api_key: String::new(),
+        }
+    }
+}
+
+pub fn get_ai_settings(app: &AppHandle) -> AiSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_ai_settings(&conn),
+        Err(_) => AiSettings::default(),
+    }
+}
+
+pub fn query_ai_settings(conn: &Connection) -> AiSettings {
+    let defaults = AiSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    AiSettings {
+        enabled: get(AI_SUMMARY_ENABLED_KEY).map(|v| v == "true").unwrap_or(defaults.enabled),
+        endpoint: get(AI_ENDPOINT_KEY).filter(|v| !v.is_empty()).unwrap_or(defaults.endpoint),
+        model: get(AI_MODEL_KEY).filter(|v| !v.is_empty()).unwrap_or(defaults.model),
+        api_key: get(AI_API_KEY_KEY).unwrap_or(defaults.api_key),
+    }
+}
+
+pub fn set_ai_settings(app: &AppHandle, ai: &AiSettings) -> Result<AiSettings, String> {
+    let endpoint = ai.endpoint.trim();
+    if !(endpoint.starts_with("https://") || endpoint.starts_with("http://")) {
+        return Err(format!("Endpoint must be an http(s) URL: {}", endpoint));
+    }
+    if ai.model.trim().is_empty() {
+        return Err("Model must not be empty".to_string());
+    }
+    set_setting(app, AI_ENDPOINT_KEY, endpoint).map_err(|e| e.to_string())?;
+    set_setting(app, AI_MODEL_KEY, ai.model.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, AI_API_KEY_KEY, ai.api_key.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, AI_SUMMARY_ENABLED_KEY, &ai.enabled.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_ai_settings(app))
+}
+
+/// Where spoken answers are turned into text (speech.rs)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionBackend {
+    Local,  // whisper on this machine; needs the `local-whisper` feature and a model file
+    Api,    // An OpenAI-compatible /audio/transcriptions endpoint
+}
+
+impl TranscriptionBackend {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TranscriptionBackend::Local => "local",
+            TranscriptionBackend::Api => "api",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "local" => TranscriptionBackend::Local,
+            _ => TranscriptionBackend::Api,
+        }
+    }
+}
+
+/// Voice answers to the prompt. With the API backend the recording is uploaded to the endpoint;
+/// with the local backend it never leaves the machine.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TranscriptionSettings {
+    pub enabled: bool,
+    pub backend: TranscriptionBackend,
+    pub endpoint: String,          // Base URL; /audio/transcriptions is appended
+    pub model: String,
+    pub api_key: String,           // Sent as a bearer token; empty sends none
+    pub whisper_model_path: String, // ggml model file for the local backend
+}
+
+impl Default for TranscriptionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: TranscriptionBackend::Api,
+            endpoint: DEFAULT_AI_ENDPOINT.to_string(),
+            model: DEFAULT_TRANSCRIPTION_MODEL.to_string(),
+            api_key: String::new(),
+            whisper_model_path: String::new(),
+        }
+    }
+}
+
+pub fn get_transcription_settings(app: &AppHandle) -> TranscriptionSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_transcription_settings(&conn),
+        Err(_) => TranscriptionSettings::default(),
+    }
+}
+
+pub fn query_transcription_settings(conn: &Connection) -> TranscriptionSettings {
+    let defaults = TranscriptionSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    TranscriptionSettings {
+        enabled: get(VOICE_INPUT_ENABLED_KEY).map(|v| v == "true").unwrap_or(defaults.enabled),
+        backend: get(TRANSCRIPTION_BACKEND_KEY).map(|v| TranscriptionBackend::from_str(&v)).unwrap_or(defaults.backend),
+        endpoint: get(TRANSCRIPTION_ENDPOINT_KEY).filter(|v| !v.is_empty()).unwrap_or(defaults.endpoint),
+        model: get(TRANSCRIPTION_MODEL_KEY).filter(|v| !v.is_empty()).unwrap_or(defaults.model),
+        api_key: get(TRANSCRIPTION_API_KEY_KEY).unwrap_or(defaults.api_key),
+        whisper_model_path: get(WHISPER_MODEL_PATH_KEY).unwrap_or(defaults.whisper_model_path),
+    }
+}
+
+pub fn set_transcription_settings(app: &AppHandle, transcription: &TranscriptionSettings) -> Result<TranscriptionSettings, String> {
+    let endpoint = transcription.endpoint.trim();
+    if !(endpoint.starts_with("https://") || endpoint.starts_with("http://")) {
+        return Err(format!("Endpoint must be an http(s) URL: {}", endpoint));
+    }
+    if transcription.model.trim().is_empty() {
+        return Err("Model must not be empty".to_string());
+    }
+    let model_path = transcription.whisper_model_path.trim();
+    if !model_path.is_empty() && !std::path::Path::new(model_path).is_absolute() {
+        return Err(format!("Whisper model path must be absolute: {}", model_path));
+    }
+    set_setting(app, TRANSCRIPTION_BACKEND_KEY, transcription.backend.as_str()).map_err(|e| e.to_string())?;
+    set_setting(app, TRANSCRIPTION_ENDPOINT_KEY, endpoint).map_err(|e| e.to_string())?;
+    set_setting(app, TRANSCRIPTION_MODEL_KEY, transcription.model.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, TRANSCRIPTION_API_KEY_KEY, transcription.api_key.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, WHISPER_MODEL_PATH_KEY, model_path).map_err(|e| e.to_string())?;
+    set_setting(app, VOICE_INPUT_ENABLED_KEY, &transcription.enabled.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_transcription_settings(app))
+}
+
+/// Pomodoro mode: a break timer runs after each completed workblock
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PomodoroSettings {
+    pub enabled: bool,
+    pub break_seconds: u64,
+    pub auto_start_next: bool,  // Start another workblock of the same length when the break ends
+}
+
+impl Default for PomodoroSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            break_seconds: timing().break_seconds,
+            auto_start_next: false,
+        }
+    }
+}
+
+/// Read Pomodoro settings, falling back to defaults if the database is unavailable
+pub fn get_pomodoro_settings(app: &AppHandle) -> PomodoroSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_pomodoro_settings(&conn),
+        Err(_) => PomodoroSettings::default(),
+    }
+}
+
+/// Read Pomodoro settings, falling back to defaults for missing or malformed values
+pub fn query_pomodoro_settings(conn: &Connection) -> PomodoroSettings {
+    let defaults = PomodoroSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    PomodoroSettings {
+        enabled: get(POMODORO_ENABLED_KEY).map_or(defaults.enabled, |v| v == "true"),
+        break_seconds: get(BREAK_DURATION_KEY)
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|s| *s > 0)
+            .unwrap_or(defaults.break_seconds),
+        auto_start_next: get(BREAK_AUTO_START_KEY).map_or(defaults.auto_start_next, |v| v == "true"),
+    }
+}
+
+/// Takes effect from the next completed workblock
+pub fn set_pomodoro_settings(app: &AppHandle, pomodoro: &PomodoroSettings) -> Result<PomodoroSettings, String> {
+    if pomodoro.break_seconds == 0 {
+        return Err("Break length must be at least 1 second".to_string());
+    }
+    set_setting(app, POMODORO_ENABLED_KEY, &pomodoro.enabled.to_string()).map_err(|e| e.to_string())?;
+    set_setting(app, BREAK_DURATION_KEY, &pomodoro.break_seconds.to_string()).map_err(|e| e.to_string())?;
+    set_setting(app, BREAK_AUTO_START_KEY, &pomodoro.auto_start_next.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_pomodoro_settings(app))
+}
+
+/// Heads-up shortly before each interval ends, so a thought can be wrapped up before the prompt
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IntervalWarningSettings {
+    pub seconds_before: u64,  // 0 = no warning
+    pub notification: bool,   // Also show a system notification, not just the event
+}
+
+impl Default for IntervalWarningSettings {
+    fn default() -> Self {
+        Self {
+            seconds_before: DEFAULT_INTERVAL_WARNING_SECONDS,
+            notification: false,
+        }
+    }
+}
+
+pub fn get_interval_warning_settings(app: &AppHandle) -> IntervalWarningSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_interval_warning_settings(&conn),
+        Err(_) => IntervalWarningSettings::default(),
+    }
+}
+
+/// Read the interval warning settings, falling back to defaults for missing or malformed values
+pub fn query_interval_warning_settings(conn: &Connection) -> IntervalWarningSettings {
+    let defaults = IntervalWarningSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    IntervalWarningSettings {
+        seconds_before: get(INTERVAL_WARNING_SECONDS_KEY)
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|s| *s <= MAX_INTERVAL_WARNING_SECONDS)
+            .unwrap_or(defaults.seconds_before),
+        notification: get(INTERVAL_WARNING_NOTIFICATION_KEY).map_or(defaults.notification, |v| v == "true"),
+    }
+}
+
+/// Takes effect from the next interval
+pub fn set_interval_warning_settings(app: &AppHandle, warning: &IntervalWarningSettings) -> Result<IntervalWarningSettings, String> {
+    if warning.seconds_before > MAX_INTERVAL_WARNING_SECONDS {
+        return Err(format!("The warning can come at most {} seconds before the prompt", MAX_INTERVAL_WARNING_SECONDS));
+    }
+    set_setting(app, INTERVAL_WARNING_SECONDS_KEY, &warning.seconds_before.to_string()).map_err(|e| e.to_string())?;
+    set_setting(app, INTERVAL_WARNING_NOTIFICATION_KEY, &warning.notification.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_interval_warning_settings(app))
+}
+
+/// Opt-in guard that suggests stopping once a day's tracked time passes a limit
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WellbeingSettings {
+    pub enabled: bool,
+    pub daily_limit_minutes: i32,
+}
+
+impl Default for WellbeingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            daily_limit_minutes: DEFAULT_WELLBEING_LIMIT_MINUTES,
+        }
+    }
+}
+
+impl WellbeingSettings {
+    /// Whether a day with `tracked_minutes` is over the limit (never, while the guard is off)
+    pub fn exceeded_by(&self, tracked_minutes: i32) -> bool {
+        self.enabled && tracked_minutes > self.daily_limit_minutes
+    }
+}
+
+pub fn get_wellbeing_settings(app: &AppHandle) -> WellbeingSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_wellbeing_settings(&conn),
+        Err(_) => WellbeingSettings::default(),
+    }
+}
+
+pub fn query_wellbeing_settings(conn: &Connection) -> WellbeingSettings {
+    let defaults = WellbeingSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    WellbeingSettings {
+        enabled: get(WELLBEING_ENABLED_KEY).map_or(defaults.enabled, |v| v == "true"),
+        daily_limit_minutes: get(WELLBEING_LIMIT_KEY)
+            .and_then(|v| v.parse::<i32>().ok())
+            .filter(|m| *m > 0)
+            .unwrap_or(defaults.daily_limit_minutes),
+    }
+}
+
+pub fn set_wellbeing_settings(app: &AppHandle, wellbeing: &WellbeingSettings) -> Result<WellbeingSettings, String> {
+    if !(1..=24 * 60).contains(&wellbeing.daily_limit_minutes) {
+        return Err("Daily limit must be between 1 minute and 24 hours".to_string());
+    }
+    set_setting(app, WELLBEING_ENABLED_KEY, &wellbeing.enabled.to_string()).map_err(|e| e.to_string())?;
+    set_setting(app, WELLBEING_LIMIT_KEY, &wellbeing.daily_limit_minutes.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_wellbeing_settings(app))
+}
+
+/// What the tray title (text next to the icon, macOS) counts down
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayCountdownMode {
+    Off,
+    Interval,   // Time left in the current interval
+    Workblock,  // Time left in the whole workblock
+}
+
+impl TrayCountdownMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TrayCountdownMode::Off => "off",
+            TrayCountdownMode::Interval => "interval",
+            TrayCountdownMode::Workblock => "workblock",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "interval" => TrayCountdownMode::Interval,
+            "workblock" => TrayCountdownMode::Workblock,
+            _ => TrayCountdownMode::Off,
+        }
+    }
+}
+
+pub fn get_tray_countdown_mode(app: &AppHandle) -> TrayCountdownMode {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_setting(&conn, TRAY_COUNTDOWN_KEY).ok().flatten())
+        .map(|v| TrayCountdownMode::from_str(&v))
+        .unwrap_or(TrayCountdownMode::Off)
+}
+
+pub fn set_tray_countdown_mode(app: &AppHandle, mode: TrayCountdownMode) -> Result<TrayCountdownMode, String> {
+    set_setting(app, TRAY_COUNTDOWN_KEY, mode.as_str()).map_err(|e| e.to_string())?;
+    Ok(mode)
+}
+
+/// How long the tray's one-click pause silences prompts
+pub fn get_dnd_preset_minutes(app: &AppHandle) -> i32 {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_setting(&conn, DND_PRESET_MINUTES_KEY).ok().flatten())
+        .and_then(|v| v.parse::<i32>().ok())
+        .filter(|m| *m > 0)
+        .unwrap_or(DEFAULT_DND_PRESET_MINUTES)
+}
+
+pub fn set_dnd_preset_minutes(app: &AppHandle, minutes: i32) -> Result<i32, String> {
+    if minutes <= 0 {
+        return Err("Pause length must be at least 1 minute".to_string());
+    }
+    set_setting(app, DND_PRESET_MINUTES_KEY, &minutes.to_string()).map_err(|e| e.to_string())?;
+    Ok(minutes)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DecimalSeparator {
+    #[default]
+    Dot,    // 1.5
+    Comma,  // 1,5
+}
+
+impl DecimalSeparator {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DecimalSeparator::Dot => "dot",
+            DecimalSeparator::Comma => "comma",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "comma" => DecimalSeparator::Comma,
+            _ => DecimalSeparator::Dot,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeFormat {
+    #[default]
+    H24,  // 14:05
+    H12,  // 2:05 PM
+}
+
+impl TimeFormat {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TimeFormat::H24 => "h24",
+            TimeFormat::H12 => "h12",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "h12" => TimeFormat::H12,
+            _ => TimeFormat::H24,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DateOrder {
+    #[default]
+    Ymd,  // 2024-06-03
+    Dmy,  // 03.06.2024
+    Mdy,  // 06/03/2024
+}
+
+impl DateOrder {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DateOrder::Ymd => "ymd",
+            DateOrder::Dmy => "dmy",
+            DateOrder::Mdy => "mdy",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "dmy" => DateOrder::Dmy,
+            "mdy" => DateOrder::Mdy,
+            _ => DateOrder::Ymd,
+        }
+    }
+}
+
+/// How numbers, dates and times are written in exports
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct LocaleSettings {
+    pub decimal_separator: DecimalSeparator,
+    pub time_format: TimeFormat,
+    pub date_order: DateOrder,
+}
+
+/// Read locale settings, falling back to defaults if the database is unavailable
+pub fn get_locale_settings(app: &AppHandle) -> LocaleSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_locale_settings(&conn),
+        Err(_) => LocaleSettings::default(),
+    }
+}
+
+/// Read locale settings; missing values fall back to defaults
+pub fn query_locale_settings(conn: &Connection) -> LocaleSettings {
+    let get = |key: &str| query_setting(conn, key).ok().flatten().unwrap_or_default();
+    LocaleSettings {
+        decimal_separator: DecimalSeparator::from_str(&get(LOCALE_DECIMAL_SEPARATOR_KEY)),
+        time_format: TimeFormat::from_str(&get(LOCALE_TIME_FORMAT_KEY)),
+        date_order: DateOrder::from_str(&get(LOCALE_DATE_ORDER_KEY)),
+    }
+}
+
+pub fn set_locale_settings(app: &AppHandle, locale: &LocaleSettings) -> Result<LocaleSettings, String> {
+    set_setting(app, LOCALE_DECIMAL_SEPARATOR_KEY, locale.decimal_separator.as_str()).map_err(|e| e.to_string())?;
+    set_setting(app, LOCALE_TIME_FORMAT_KEY, locale.time_format.as_str()).map_err(|e| e.to_string())?;
+    set_setting(app, LOCALE_DATE_ORDER_KEY, locale.date_order.as_str()).map_err(|e| e.to_string())?;
+    Ok(get_locale_settings(app))
+}
+
+/// How long after cancelling a workblock it can still be resumed
+pub fn get_resume_window_minutes(app: &AppHandle) -> i64 {
+    get_db_connection(app)
+        .ok()
+        .and_then(|conn| query_setting(&conn, RESUME_WINDOW_KEY).ok().flatten())
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|m| *m >= 0)
+        .unwrap_or(DEFAULT_RESUME_WINDOW_MINUTES)
+}
+
+pub fn set_resume_window_minutes(app: &AppHandle, window_minutes: i64) -> Result<i64, String> {
+    if window_minutes < 0 {
+        return Err("Resume window must not be negative".to_string());
+    }
+    set_setting(app, RESUME_WINDOW_KEY, &window_minutes.to_string()).map_err(|e| e.to_string())?;
+    Ok(window_minutes)
+}
+
+/// Global hotkeys; an empty string disables that hotkey
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HotkeySettings {
+    pub open_prompt: String,
+    pub toggle_workblock: String,
+    pub workblock_minutes: i32,  // Length of workblocks started from the hotkey
+    #[serde(default = "default_repeat_last_hotkey")]
+    pub repeat_last: String,     // Answers the open prompt with the previous interval's words
+}
+
+fn default_repeat_last_hotkey() -> String {
+    DEFAULT_REPEAT_LAST_HOTKEY.to_string()
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            open_prompt: DEFAULT_OPEN_PROMPT_HOTKEY.to_string(),
+            toggle_workblock: DEFAULT_TOGGLE_WORKBLOCK_HOTKEY.to_string(),
+            workblock_minutes: DEFAULT_HOTKEY_WORKBLOCK_MINUTES,
+            repeat_last: default_repeat_last_hotkey(),
+        }
+    }
+}
+
+pub fn get_hotkey_settings(app: &AppHandle) -> HotkeySettings {
+    let defaults = HotkeySettings::default();
+    let conn = match get_db_connection(app) {
+        Ok(conn) => conn,
+        Err(_) => return defaults,
+    };
+    let get = |key: &str| query_setting(&conn, key).ok().flatten();
+    HotkeySettings {
+        open_prompt: get(OPEN_PROMPT_HOTKEY_KEY).unwrap_or(defaults.open_prompt),
+        toggle_workblock: get(TOGGLE_WORKBLOCK_HOTKEY_KEY).unwrap_or(defaults.toggle_workblock),
+        workblock_minutes: get(HOTKEY_WORKBLOCK_MINUTES_KEY)
+            .and_then(|v| v.parse::<i32>().ok())
+            .filter(|m| *m > 0)
+            .unwrap_or(defaults.workblock_minutes),
+        repeat_last: get(REPEAT_LAST_HOTKEY_KEY).unwrap_or(defaults.repeat_last),
+    }
+}
+
+pub fn set_hotkey_settings(app: &AppHandle, hotkeys: &HotkeySettings) -> Result<(), String> {
+    validate_duration_minutes(hotkeys.workblock_minutes).map_err(|e| e.to_string())?;
+    set_setting(app, OPEN_PROMPT_HOTKEY_KEY, hotkeys.open_prompt.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, TOGGLE_WORKBLOCK_HOTKEY_KEY, hotkeys.toggle_workblock.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, REPEAT_LAST_HOTKEY_KEY, hotkeys.repeat_last.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, HOTKEY_WORKBLOCK_MINUTES_KEY, &hotkeys.workblock_minutes.to_string()).map_err(|e| e.to_string())
+}
+
+/// Language of the strings the backend shows (tray menu)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+}
+
+impl Language {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Language::En => "en",
+            Language::De => "de",
+            Language::Fr => "fr",
+            Language::Es => "es",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "de" => Language::De,
+            "fr" => Language::Fr,
+            "es" => Language::Es,
+            _ => Language::En,
+        }
+    }
+}
+
+pub fn get_language(app: &AppHandle) -> Language {
+    get_db_connection(app)
+        .ok()
+        .map(|conn| query_language(&conn))
+        .unwrap_or_default()
+}
+
+pub fn query_language(conn: &Connection) -> Language {
+    query_setting(conn, LANGUAGE_KEY)
+        .ok()
+        .flatten()
+        .map(|v| Language::from_str(&v))
+        .unwrap_or_default()
+}
+
+pub fn set_language(app: &AppHandle, language: Language) -> Result<Language, String> {
+    set_setting(app, LANGUAGE_KEY, language.as_str()).map_err(|e| e.to_string())?;
+    Ok(language)
+}
+
+/// Theme for all windows; System follows the OS
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemePreference {
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl ThemePreference {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ThemePreference::System => "system",
+            ThemePreference::Dark => "dark",
+            ThemePreference::Light => "light",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "dark" => ThemePreference::Dark,
+            "light" => ThemePreference::Light,
+            _ => ThemePreference::System,
+        }
+    }
+}
+
+pub fn get_theme_preference(app: &AppHandle) -> ThemePreference {
+    get_db_connection(app)
+        .ok()
+        .map(|conn| query_theme_preference(&conn))
+        .unwrap_or_default()
+}
+
+pub fn query_theme_preference(conn: &Connection) -> ThemePreference {
+    query_setting(conn, THEME_KEY)
+        .ok()
+        .flatten()
+        .map(|v| ThemePreference::from_str(&v))
+        .unwrap_or_default()
+}
+
+pub fn set_theme_preference(app: &AppHandle, preference: ThemePreference) -> Result<ThemePreference, String> {
+    set_setting(app, THEME_KEY, preference.as_str()).map_err(|e| e.to_string())?;
+    Ok(preference)
+}
+
+/// Opt-in capture of the focused application during workblocks
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct ActivityCaptureSettings {
+    pub enabled: bool,
+    pub window_titles: bool,  // Also keep window titles, not just application names
+    pub sample_seconds: u64,
+}
+
+impl Default for ActivityCaptureSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_titles: false,
+            sample_seconds: DEFAULT_ACTIVITY_CAPTURE_SAMPLE_SECONDS,
+        }
+    }
+}
+
+pub fn get_activity_capture_settings(app: &AppHandle) -> ActivityCaptureSettings {
+    match get_db_connection(app) {
+        Ok(conn) => query_activity_capture_settings(&conn),
+        Err(_) => ActivityCaptureSettings::default(),
+    }
+}
+
+/// Read activity capture settings, falling back to defaults for missing or malformed values
+pub fn query_activity_capture_settings(conn: &Connection) -> ActivityCaptureSettings {
+    let defaults = ActivityCaptureSettings::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten();
+    ActivityCaptureSettings {
+        enabled: get(ACTIVITY_CAPTURE_ENABLED_KEY).map(|v| v == "true").unwrap_or(defaults.enabled),
+        window_titles: get(ACTIVITY_CAPTURE_TITLES_KEY).map(|v| v == "true").unwrap_or(defaults.window_titles),
+        sample_seconds: get(ACTIVITY_CAPTURE_SAMPLE_KEY)
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|s| *s > 0)
+            .unwrap_or(defaults.sample_seconds),
+    }
+}
+
+pub fn set_activity_capture_settings(
+    app: &AppHandle,
+    settings: &ActivityCaptureSettings,
+) -> Result<ActivityCaptureSettings, String> {
+    if settings.sample_seconds == 0 {
+        return Err("Sample interval must be at least 1 second".to_string());
+    }
+    set_setting(app, ACTIVITY_CAPTURE_ENABLED_KEY, &settings.enabled.to_string()).map_err(|e| e.to_string())?;
+    set_setting(app, ACTIVITY_CAPTURE_TITLES_KEY, &settings.window_titles.to_string()).map_err(|e| e.to_string())?;
+    set_setting(app, ACTIVITY_CAPTURE_SAMPLE_KEY, &settings.sample_seconds.to_string()).map_err(|e| e.to_string())?;
+    Ok(get_activity_capture_settings(app))
+}
+
+/// Hour (0-23) at which a new day begins; work before it counts toward the previous day.
+/// Cached because every date computation needs it, including ones without a connection at hand.
+static DAY_START_HOUR: AtomicU32 = AtomicU32::new(DEFAULT_DAY_START_HOUR);
+
+pub fn day_start_hour() -> u32 {
+    DAY_START_HOUR.load(Ordering::Relaxed)
+}
+
+pub fn query_day_start_hour(conn: &Connection) -> u32 {
+    query_setting(conn, DAY_START_HOUR_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|h| *h < 24)
+        .unwrap_or(DEFAULT_DAY_START_HOUR)
+}
+
+/// Refresh the cached day start hour from the database (on startup)
+pub fn load_day_start_hour(conn: &Connection) -> u32 {
+    let hour = query_day_start_hour(conn);
+    DAY_START_HOUR.store(hour, Ordering::Relaxed);
+    hour
+}
+
+pub fn set_day_start_hour(app: &AppHandle, hour: u32) -> Result<u32, String> {
+    if hour >= 24 {
+        return Err("Day start hour must be between 0 and 23".to_string());
+    }
+    set_setting(app, DAY_START_HOUR_KEY, &hour.to_string()).map_err(|e| e.to_string())?;
+    DAY_START_HOUR.store(hour, Ordering::Relaxed);
+    Ok(hour)
+}
+
+/// Time of day ("HH:MM") to archive the day early, e.g. at the end of the workday.
+/// None (stored as an empty string) archives only at the day boundary.
+pub fn get_archive_time(app: &AppHandle) -> Option<NaiveTime> {
+    get_db_connection(app).ok().and_then(|conn| query_archive_time(&conn))
+}
+
+pub fn query_archive_time(conn: &Connection) -> Option<NaiveTime> {
+    query_setting(conn, ARCHIVE_TIME_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| NaiveTime::parse_from_str(v.trim(), "%H:%M").ok())
+}
+
+pub fn set_archive_time(app: &AppHandle, archive_time: Option<&str>) -> Result<Option<String>, String> {
+    let archive_time = match archive_time.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(time) => Some(
+            NaiveTime::parse_from_str(time, "%H:%M")
+                .map_err(|_| format!("Invalid archive time '{}', expected HH:MM", time))?
+                .format("%H:%M")
+                .to_string(),
+        ),
+        None => None,
+    };
+    set_setting(app, ARCHIVE_TIME_KEY, archive_time.as_deref().unwrap_or_default()).map_err(|e| e.to_string())?;
+    Ok(archive_time)
+}
+
+/// Messages sent with milestone webhooks. `{field}` is replaced with that field of the milestone:
+/// {date}, {metric}, {target}, {actual}, {tracked_minutes}, {daily_limit_minutes} or {streak}.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MilestoneTemplates {
+    pub goal_reached: String,
+    pub budget_exceeded: String,
+    pub streak_milestone: String,
+}
+
+impl Default for MilestoneTemplates {
+    fn default() -> Self {
+        Self {
+            goal_reached: DEFAULT_GOAL_REACHED_TEMPLATE.to_string(),
+            budget_exceeded: DEFAULT_BUDGET_EXCEEDED_TEMPLATE.to_string(),
+            streak_milestone: DEFAULT_STREAK_MILESTONE_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl MilestoneTemplates {
+    /// The template for the kind of `milestone`
+    pub fn for_milestone(&self, milestone: &Milestone) -> &str {
+        match milestone {
+            Milestone::GoalReached { .. } => &self.goal_reached,
+            Milestone::BudgetExceeded { .. } => &self.budget_exceeded,
+            Milestone::StreakMilestone { .. } => &self.streak_milestone,
+        }
+    }
+}
+
+pub fn get_milestone_templates(app: &AppHandle) -> MilestoneTemplates {
+    match get_db_connection(app) {
+        Ok(conn) => query_milestone_templates(&conn),
+        Err(_) => MilestoneTemplates::default(),
+    }
+}
+
+/// Stored templates; a blank one falls back to its default
+pub fn query_milestone_templates(conn: &Connection) -> MilestoneTemplates {
+    let defaults = MilestoneTemplates::default();
+    let get = |key: &str| query_setting(conn, key).ok().flatten().filter(|v| !v.trim().is_empty());
+    MilestoneTemplates {
+        goal_reached: get(GOAL_REACHED_TEMPLATE_KEY).unwrap_or(defaults.goal_reached),
+        budget_exceeded: get(BUDGET_EXCEEDED_TEMPLATE_KEY).unwrap_or(defaults.budget_exceeded),
+        streak_milestone: get(STREAK_MILESTONE_TEMPLATE_KEY).unwrap_or(defaults.streak_milestone),
+    }
+}
+
+/// Save the templates; a blank one goes back to its default
+pub fn set_milestone_templates(app: &AppHandle, templates: &MilestoneTemplates) -> Result<MilestoneTemplates, String> {
+    set_setting(app, GOAL_REACHED_TEMPLATE_KEY, templates.goal_reached.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, BUDGET_EXCEEDED_TEMPLATE_KEY, templates.budget_exceeded.trim()).map_err(|e| e.to_string())?;
+    set_setting(app, STREAK_MILESTONE_TEMPLATE_KEY, templates.streak_milestone.trim()).map_err(|e| e.to_string())?;
+    Ok(get_milestone_templates(app))
+}
+
+/// Payload of the "settings-changed" event, also broadcast to running subsystems
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SettingChanged {
+    pub key: String,
+    pub value: String,
+}
+
+/// Changes a slow subscriber may fall behind by before it misses some
+const SETTINGS_CHANNEL_CAPACITY: usize = 64;
+
+static SETTINGS_CHANNEL: OnceLock<broadcast::Sender<SettingChanged>> = OnceLock::new();
+
+fn settings_channel() -> &'static broadcast::Sender<SettingChanged> {
+    SETTINGS_CHANNEL.get_or_init(|| broadcast::channel(SETTINGS_CHANNEL_CAPACITY).0)
+}
+
+/// Receive every setting change from now on
+pub fn subscribe_settings() -> broadcast::Receiver<SettingChanged> {
+    settings_channel().subscribe()
+}
+
+/// Send a change to the subscribers; returns how many there were
+pub fn publish_setting_change(change: SettingChanged) -> usize {
+    settings_channel().send(change).unwrap_or(0)
+}
+
+/// Announce a changed setting to the subsystems and the frontend (called by set_setting).
+/// Secret values are redacted; subscribers read the setting when they need it.
+pub fn notify_setting_changed(app: &AppHandle, key: &str, value: &str) {
+    let value = if is_secret_setting(key) { REDACTED_SETTING_VALUE } else { value };
+    let change = SettingChanged { key: key.to_string(), value: value.to_string() };
+    let _ = crate::events::emit_event(app, crate::events::AppEvent::SettingsChanged(change.clone()));
+    publish_setting_change(change);
+}
+
+/// Run `apply` for each setting change in a background task. A subscriber that fell behind
+/// gets the keys it missed as one change with an empty key, meaning "re-read everything".
+pub fn spawn_settings_listener<F, Fut>(name: &'static str, app: &AppHandle, apply: F)
+where
+    F: Fn(AppHandle, SettingChanged) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = ()> + Send,
+{
+    let app = app.clone();
+    let mut changes = subscribe_settings();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let change = match changes.recv().await {
+                Ok(change) => change,
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    eprintln!("[SETTINGS] {} missed {} setting changes", name, missed);
+                    SettingChanged { key: String::new(), value: String::new() }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            apply(app.clone(), change).await;
+        }
+    });
+}
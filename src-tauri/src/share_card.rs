@@ -0,0 +1,210 @@
+// Shareable PNG card with a day's headline stats, without any of the recorded words
+
+use crate::db::get_db_connection;
+use crate::stats::query_tracked_minutes;
+use chrono::{DateTime, Local, Timelike};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+
+pub const CARD_WIDTH: u32 = 600;
+pub const CARD_HEIGHT: u32 = 315;
+
+const BACKGROUND: [u8; 4] = [0x1e, 0x1e, 0x2e, 0xff];
+const FOREGROUND: [u8; 4] = [0xf5, 0xf5, 0xf5, 0xff];
+const MUTED: [u8; 4] = [0x8a, 0x8a, 0x9e, 0xff];
+const ACCENT: [u8; 4] = [0x4a, 0x90, 0xd9, 0xff];
+
+/// What the card shows: totals and minutes per hour of the day
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ShareCardStats {
+    pub date: String,
+    pub tracked_minutes: i32,
+    pub workblocks: i32,
+    pub intervals_recorded: i32,
+    pub hourly_minutes: Vec<i32>,  // 24 entries, by local start hour of recorded intervals
+}
+
+pub fn query_share_card_stats(conn: &Connection, date: &str) -> rusqlite::Result<ShareCardStats> {
+    let workblocks: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM workblocks WHERE date = ?1",
+        params![date],
+        |row| row.get(0),
+    )?;
+    let mut stmt = conn.prepare(
+        "SELECT i.start_time, i.end_time
+         FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE w.date = ?1 AND i.status = 'recorded'",
+    )?;
+    let intervals = stmt
+        .query_map(params![date], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut hourly_minutes = vec![0; 24];
+    for (start, end) in &intervals {
+        let Ok(start) = DateTime::parse_from_rfc3339(start) else { continue };
+        let minutes = end
+            .as_deref()
+            .and_then(|end| DateTime::parse_from_rfc3339(end).ok())
+            .map_or(0, |end| (end - start).num_minutes().max(0) as i32);
+        hourly_minutes[start.with_timezone(&Local).hour() as usize] += minutes;
+    }
+
+    Ok(ShareCardStats {
+        date: date.to_string(),
+        tracked_minutes: query_tracked_minutes(conn, date, Local::now())?,
+        workblocks,
+        intervals_recorded: intervals.len() as i32,
+        hourly_minutes,
+    })
+}
+
+/// An RGBA image being drawn
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, color: [u8; 4]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: color.repeat((width * height) as usize),
+        }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 4]) {
+        for py in y..(y + height).min(self.height) {
+            for px in x..(x + width).min(self.width) {
+                let i = ((py * self.width + px) * 4) as usize;
+                self.pixels[i..i + 4].copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Draw `text` in the built-in 5x7 font, each font pixel `scale` pixels wide
+    fn text(&mut self, x: u32, y: u32, text: &str, scale: u32, color: [u8; 4]) {
+        for (n, c) in text.chars().enumerate() {
+            let left = x + n as u32 * 6 * scale;
+            for (row, bits) in glyph(c.to_ascii_uppercase()).iter().enumerate() {
+                for col in 0..5 {
+                    if bits & (0b10000 >> col) != 0 {
+                        self.fill_rect(left + col * scale, y + row as u32 * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Rows of a 5x7 glyph, most significant of the low five bits leftmost; unknown characters are blank
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        ':' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        '/' => [0b00001, 0b00010, 0b00010, 0b00100, 0b01000, 0b01000, 0b10000],
+        '%' => [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011],
+        _ => [0; 7],
+    }
+}
+
+/// "4H 30M", "45M"
+fn format_minutes(minutes: i32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}M", m),
+        (h, 0) => format!("{}H", h),
+        (h, m) => format!("{}H {}M", h, m),
+    }
+}
+
+/// Draw the card as RGBA pixels (CARD_WIDTH x CARD_HEIGHT)
+pub fn render_share_card(stats: &ShareCardStats) -> Vec<u8> {
+    let mut canvas = Canvas::new(CARD_WIDTH, CARD_HEIGHT, BACKGROUND);
+
+    canvas.text(32, 28, "LOG15", 3, ACCENT);
+    canvas.text(CARD_WIDTH - 32 - stats.date.len() as u32 * 12, 32, &stats.date, 2, MUTED);
+    canvas.text(32, 80, &format!("{} TRACKED", format_minutes(stats.tracked_minutes)), 4, FOREGROUND);
+    canvas.text(
+        32,
+        128,
+        &format!("{} WORKBLOCKS  {} INTERVALS", stats.workblocks, stats.intervals_recorded),
+        2,
+        MUTED,
+    );
+
+    // Minutes per hour of the day, scaled to the busiest hour
+    let (chart_left, chart_bottom, chart_height, bar_width) = (32u32, 272u32, 100u32, 22u32);
+    let busiest = stats.hourly_minutes.iter().copied().max().unwrap_or(0).max(1);
+    for (hour, minutes) in stats.hourly_minutes.iter().enumerate().take(24) {
+        let x = chart_left + hour as u32 * bar_width;
+        let height = (*minutes).max(0) as u32 * chart_height / busiest as u32;
+        canvas.fill_rect(x, chart_bottom - height, bar_width - 4, height, ACCENT);
+        canvas.fill_rect(x, chart_bottom, bar_width - 4, 2, MUTED);
+        if hour % 6 == 0 {
+            canvas.text(x, chart_bottom + 10, &hour.to_string(), 2, MUTED);
+        }
+    }
+
+    canvas.pixels
+}
+
+pub fn encode_png(pixels: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    writer.write_image_data(pixels).map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    writer.finish().map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(bytes)
+}
+
+/// Render the share card for `date` and write it to `path` as PNG
+pub fn generate_share_card(app: &AppHandle, date: &str, path: &Path) -> Result<ShareCardStats, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let stats = query_share_card_stats(&conn, date).map_err(|e| e.to_string())?;
+    let png = encode_png(&render_share_card(&stats), CARD_WIDTH, CARD_HEIGHT)?;
+    std::fs::write(path, png).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(stats)
+}
@@ -0,0 +1,209 @@
+// Voice answers for the prompt. The prompt window records a 16-bit PCM WAV; it is transcribed
+// either on this machine by whisper (the `local-whisper` feature plus a ggml model file) or by
+// the OpenAI-compatible transcription endpoint in the settings, and the text is then recorded
+// like typed words.
+
+use crate::settings::{get_transcription_settings, TranscriptionBackend, TranscriptionSettings};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Largest recording accepted (the limit of most hosted transcription APIs)
+pub const MAX_AUDIO_BYTES: usize = 25 * 1024 * 1024;
+
+/// Sample rate whisper expects; the prompt window records at this rate
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Uploads of a long recording can take a while
+const REQUEST_TIMEOUT_SECONDS: u64 = 60;
+
+/// Decoded 16-bit PCM WAV audio
+#[derive(Debug, Clone, PartialEq)]
+pub struct WavAudio {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub samples: Vec<i16>,  // Interleaved when there are several channels
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Parse a RIFF/WAVE file with 16-bit PCM samples
+pub fn parse_wav(bytes: &[u8]) -> Result<WavAudio, String> {
+    if bytes.get(0..4) != Some(&b"RIFF"[..]) || bytes.get(8..12) != Some(&b"WAVE"[..]) {
+        return Err("The recording is not a WAV file".to_string());
+    }
+    let mut format = None;
+    let mut at = 12;
+    while let (Some(id), Some(size)) = (bytes.get(at..at + 4), read_u32(bytes, at + 4)) {
+        let body = at + 8;
+        let end = body.saturating_add(size as usize).min(bytes.len());
+        match id {
+            b"fmt " => {
+                let audio_format = read_u16(bytes, body).ok_or("Truncated WAV format chunk")?;
+                let channels = read_u16(bytes, body + 2).ok_or("Truncated WAV format chunk")?;
+                let sample_rate = read_u32(bytes, body + 4).ok_or("Truncated WAV format chunk")?;
+                let bits_per_sample = read_u16(bytes, body + 14).ok_or("Truncated WAV format chunk")?;
+                if audio_format != 1 || bits_per_sample != 16 {
+                    return Err("The recording must be 16-bit PCM".to_string());
+                }
+                if channels == 0 {
+                    return Err("The recording has no channels".to_string());
+                }
+                format = Some((channels, sample_rate));
+            }
+            b"data" => {
+                let (channels, sample_rate) = format.ok_or("WAV data comes before its format")?;
+                let samples = bytes[body..end]
+                    .chunks_exact(2)
+                    .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                return Ok(WavAudio { sample_rate, channels, samples });
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even length
+        at = end + (size as usize & 1);
+    }
+    Err("The WAV file has no audio data".to_string())
+}
+
+/// Mono samples in -1.0..1.0 at whisper's sample rate
+pub fn to_whisper_samples(audio: &WavAudio) -> Result<Vec<f32>, String> {
+    if audio.sample_rate != WHISPER_SAMPLE_RATE {
+        return Err(format!(
+            "The recording must be sampled at {} Hz, not {} Hz",
+            WHISPER_SAMPLE_RATE, audio.sample_rate
+        ));
+    }
+    Ok(audio
+        .samples
+        .chunks(audio.channels as usize)
+        .map(|frame| frame.iter().map(|s| *s as f32 / 32768.0).sum::<f32>() / frame.len() as f32)
+        .collect())
+}
+
+/// Tidy a transcript into interval words: one line, no sentence punctuation at the end.
+/// None if nothing was said.
+pub fn clean_transcript(text: &str) -> Option<String> {
+    let words = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let words = words.trim_end_matches(['.', '!', '?', ',']).trim();
+    (!words.is_empty()).then(|| words.to_string())
+}
+
+/// The audio transcriptions URL under an endpoint's base URL
+pub fn transcriptions_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    if endpoint.ends_with("/audio/transcriptions") {
+        endpoint.to_string()
+    } else {
+        format!("{}/audio/transcriptions", endpoint)
+    }
+}
+
+/// multipart/form-data body uploading `audio` as answer.wav for `model`
+pub fn build_transcription_body(boundary: &str, model: &str, audio: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(audio.len() + 512);
+    body.extend_from_slice(
+        format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"model\"\r\n\r\n{model}\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"response_format\"\r\n\r\njson\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"answer.wav\"\r\n\
+             Content-Type: audio/wav\r\n\r\n",
+            b = boundary,
+            model = model,
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(audio);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    body
+}
+
+/// The text of a transcription response
+pub fn parse_transcription_response(response: &Value) -> Result<String, String> {
+    if let Some(message) = response["error"]["message"].as_str() {
+        return Err(format!("Transcription failed: {}", message));
+    }
+    response["text"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "The transcription service returned no text".to_string())
+}
+
+fn request_transcription(settings: &TranscriptionSettings, audio: &[u8]) -> Result<String, String> {
+    let boundary = format!("log15-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    let body = build_transcription_body(&boundary, &settings.model, audio);
+    let mut request = ureq::post(&transcriptions_url(&settings.endpoint))
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
+        .set("Content-Type", &format!("multipart/form-data; boundary={}", boundary));
+    if !settings.api_key.is_empty() {
+        request = request.set("Authorization", &format!("Bearer {}", settings.api_key));
+    }
+    let response: Value = match request.send_bytes(&body) {
+        Ok(response) => response.into_json().map_err(|e| format!("Unreadable response: {}", e))?,
+        // Error bodies usually say what went wrong (bad key, unsupported audio)
+        Err(ureq::Error::Status(status, response)) => response
+            .into_json()
+            .unwrap_or_else(|_| json!({ "error": { "message": format!("HTTP {}", status) } })),
+        Err(ureq::Error::Transport(e)) => return Err(format!("Failed to reach {}: {}", settings.endpoint, e)),
+    };
+    parse_transcription_response(&response)
+}
+
+#[cfg(feature = "local-whisper")]
+fn transcribe_locally(model_path: &str, audio: &[u8]) -> Result<String, String> {
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    if model_path.is_empty() {
+        return Err("Choose a whisper model file in the voice input settings".to_string());
+    }
+    let samples = to_whisper_samples(&parse_wav(audio)?)?;
+    let context = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+        .map_err(|e| format!("Failed to load whisper model {}: {}", model_path, e))?;
+    let mut state = context.create_state().map_err(|e| format!("Failed to start whisper: {}", e))?;
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+    state.full(params, &samples).map_err(|e| format!("Transcription failed: {}", e))?;
+
+    let segments = state.full_n_segments().map_err(|e| format!("Transcription failed: {}", e))?;
+    let mut text = String::new();
+    for segment in 0..segments {
+        text.push_str(&state.full_get_segment_text(segment).map_err(|e| format!("Transcription failed: {}", e))?);
+    }
+    Ok(text)
+}
+
+#[cfg(not(feature = "local-whisper"))]
+fn transcribe_locally(_model_path: &str, _audio: &[u8]) -> Result<String, String> {
+    Err("Local transcription is not available in this build (enable the `local-whisper` feature)".to_string())
+}
+
+/// Turn a recorded answer into interval words with the configured backend
+pub async fn transcribe(app: &AppHandle, audio: Vec<u8>) -> Result<String, String> {
+    let settings = get_transcription_settings(app);
+    if !settings.enabled {
+        return Err("Voice input is turned off in settings".to_string());
+    }
+    if audio.is_empty() {
+        return Err("The recording is empty".to_string());
+    }
+    if audio.len() > MAX_AUDIO_BYTES {
+        return Err(format!("The recording is larger than {} MB", MAX_AUDIO_BYTES / (1024 * 1024)));
+    }
+
+    let text = tauri::async_runtime::spawn_blocking(move || match settings.backend {
+        TranscriptionBackend::Local => transcribe_locally(settings.whisper_model_path.trim(), &audio),
+        TranscriptionBackend::Api => request_transcription(&settings, &audio),
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    clean_transcript(&text).ok_or_else(|| "No words were recognized in the recording".to_string())
+}
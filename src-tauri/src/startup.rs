@@ -0,0 +1,98 @@
+// Startup in two stages. `setup` only migrates the database, registers the managers and restores
+// the main window, so the window appears without waiting on anything else. The tray, the prompt
+// window, the daily archive check, the schedulers and the integrations start in a background task,
+// which emits "ready" with the time each step took.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Instant;
+use crate::events::{emit_event, AppEvent};
+use tauri::AppHandle;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StartupPhase {
+    pub name: String,
+    pub millis: u64,
+    pub background: bool,  // Ran after `setup` returned, while the window was already usable
+}
+
+/// Payload of the "ready" event
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StartupReport {
+    pub phases: Vec<StartupPhase>,
+    pub setup_millis: u64,  // Until `setup` returned
+    pub total_millis: u64,  // Until every background step finished
+}
+
+/// Times startup steps as they run
+#[derive(Debug)]
+pub struct StartupTimer {
+    started: Instant,
+    setup_millis: Option<u64>,
+    phases: Vec<StartupPhase>,
+}
+
+impl StartupTimer {
+    pub fn new() -> Self {
+        StartupTimer { started: Instant::now(), setup_millis: None, phases: Vec::new() }
+    }
+
+    /// Run `step` and record how long it took under `name`
+    pub fn time<T>(&mut self, name: &str, step: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = step();
+        self.record(name, started);
+        result
+    }
+
+    /// Record a step that started at `started` and just finished (for async steps)
+    pub fn record(&mut self, name: &str, started: Instant) {
+        self.phases.push(StartupPhase {
+            name: name.to_string(),
+            millis: started.elapsed().as_millis() as u64,
+            background: self.setup_millis.is_some(),
+        });
+    }
+
+    /// Mark the end of `setup`; later steps count as background
+    pub fn setup_done(&mut self) {
+        self.setup_millis = Some(self.started.elapsed().as_millis() as u64);
+    }
+
+    pub fn finish(self) -> StartupReport {
+        let total_millis = self.started.elapsed().as_millis() as u64;
+        StartupReport {
+            phases: self.phases,
+            setup_millis: self.setup_millis.unwrap_or(total_millis),
+            total_millis,
+        }
+    }
+}
+
+impl Default for StartupTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The report of this launch, once startup has finished
+static REPORT: Mutex<Option<StartupReport>> = Mutex::new(None);
+
+/// Startup timings, or None while background steps are still running
+pub fn get_startup_report() -> Option<StartupReport> {
+    REPORT.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Publish the finished startup as the "ready" event (and for get_startup_report, for a window that loads later)
+pub fn finish_startup(app: &AppHandle, timer: StartupTimer) {
+    let report = timer.finish();
+    let phases: Vec<String> = report.phases.iter().map(|p| format!("{} {}ms", p.name, p.millis)).collect();
+    println!(
+        "[STARTUP] Ready after {}ms (window after {}ms): {}",
+        report.total_millis,
+        report.setup_millis,
+        phases.join(", ")
+    );
+    *REPORT.lock().unwrap_or_else(|e| e.into_inner()) = Some(report.clone());
+    let _ = emit_event(app, AppEvent::Ready(report));
+}
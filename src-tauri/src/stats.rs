@@ -0,0 +1,525 @@
+// Recurring daily goals, goal progress and streaks
+
+use crate::db::{get_db_connection, timestamp_now};
+use crate::error::Log15Error;
+use crate::settings::{query_wellbeing_settings, WellbeingSettings};
+use chrono::{DateTime, Local, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// Streaks are not followed back further than this
+const MAX_STREAK_DAYS: i64 = 3650;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalMetric {
+    TrackedMinutes,     // Minutes of workblocks per day
+    RecordedIntervals,  // Intervals with words recorded by the user per day
+}
+
+impl GoalMetric {
+    pub fn as_str(&self) -> &str {
+        match self {
+            GoalMetric::TrackedMinutes => "tracked_minutes",
+            GoalMetric::RecordedIntervals => "recorded_intervals",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "recorded_intervals" => GoalMetric::RecordedIntervals,
+            _ => GoalMetric::TrackedMinutes,
+        }
+    }
+}
+
+/// A daily target for one metric (at most one goal per metric)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Goal {
+    pub metric: GoalMetric,
+    pub target: i32,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GoalProgress {
+    pub metric: GoalMetric,
+    pub target: i32,
+    pub actual: i32,
+    pub attained: bool,
+    pub percentage: f64,  // Capped at 100
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyGoalStatus {
+    pub date: String,  // YYYY-MM-DD format
+    pub goals: Vec<GoalProgress>,
+    pub all_attained: bool,     // False when no goals are set
+    pub current_streak: i32,    // Consecutive days with every goal attained, ending at `date`
+}
+
+// ============================================================================
+// Goals
+// ============================================================================
+
+pub fn query_goals(conn: &Connection) -> rusqlite::Result<Vec<Goal>> {
+    let mut stmt = conn.prepare("SELECT metric, target, created_at FROM goals ORDER BY metric ASC")?;
+    let goals = stmt
+        .query_map([], |row| {
+            Ok(Goal {
+                metric: GoalMetric::from_str(&row.get::<_, String>(0)?),
+                target: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(goals)
+}
+
+/// Create or replace the goal for a metric
+pub fn store_goal(conn: &Connection, metric: GoalMetric, target: i32) -> Result<Goal, String> {
+    if target <= 0 {
+        return Err("Goal target must be positive".to_string());
+    }
+    let created_at = timestamp_now();
+    conn.execute(
+        "INSERT INTO goals (metric, target, created_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(metric) DO UPDATE SET target = excluded.target",
+        params![metric.as_str(), target, created_at],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(Goal { metric, target, created_at: Some(created_at) })
+}
+
+pub fn set_goal(app: &AppHandle, metric: GoalMetric, target: i32) -> Result<Goal, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    store_goal(&conn, metric, target)
+}
+
+pub fn remove_goal(app: &AppHandle, metric: GoalMetric) -> Result<bool, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let removed = conn
+        .execute("DELETE FROM goals WHERE metric = ?1", params![metric.as_str()])
+        .map_err(|e| e.to_string())?;
+    Ok(removed > 0)
+}
+
+pub fn get_goals(app: &AppHandle) -> Result<Vec<Goal>, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    query_goals(&conn).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Progress and Streaks
+// ============================================================================
+
+/// Daily value of a metric for every day on or before `date` that has any activity. Tracked minutes
+/// count what was actually worked: an active workblock adds the time elapsed so far, not its planned length.
+fn query_daily_totals(conn: &Connection, metric: GoalMetric, date: &str) -> rusqlite::Result<HashMap<String, i32>> {
+    match metric {
+        GoalMetric::TrackedMinutes => {
+            let now = Local::now();
+            let mut stmt = conn.prepare("SELECT date, status, start_time, duration_minutes FROM workblocks WHERE date <= ?1")?;
+            let rows = stmt
+                .query_map(params![date], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, Option<i32>>(3)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let mut totals = HashMap::new();
+            for (day, status, start_time, duration) in rows {
+                *totals.entry(day).or_insert(0) += worked_minutes(&status, &start_time, duration, now);
+            }
+            Ok(totals)
+        }
+        GoalMetric::RecordedIntervals => {
+            let mut stmt = conn.prepare(
+                "SELECT w.date, COUNT(*)
+                 FROM intervals i
+                 JOIN workblocks w ON w.id = i.workblock_id
+                 WHERE w.date <= ?1 AND i.status = 'recorded'
+                 GROUP BY w.date",
+            )?;
+            let totals = stmt
+                .query_map(params![date], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)?)))?
+                .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+            Ok(totals)
+        }
+    }
+}
+
+fn progress_for(goal: &Goal, actual: i32) -> GoalProgress {
+    GoalProgress {
+        metric: goal.metric,
+        target: goal.target,
+        actual,
+        attained: actual >= goal.target,
+        percentage: (actual as f64 / goal.target as f64 * 100.0).min(100.0),
+    }
+}
+
+/// Goal progress for `date` and the streak ending there.
+/// With `in_progress` (live display of today) an unmet goal doesn't break the streak yet:
+/// the streak then counts the days before `date`.
+pub fn query_goal_status(conn: &Connection, date: &str, in_progress: bool) -> crate::error::Result<DailyGoalStatus> {
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| Log15Error::InvalidInput(format!("Invalid date '{}': {}", date, e)))?;
+    let goals = query_goals(conn)?;
+    let totals = goals
+        .iter()
+        .map(|goal| Ok((goal.metric, query_daily_totals(conn, goal.metric, date)?)))
+        .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+    let attained_on = |day: NaiveDate| {
+        let key = day.format("%Y-%m-%d").to_string();
+        !goals.is_empty()
+            && goals
+                .iter()
+                .all(|goal| totals[&goal.metric].get(&key).copied().unwrap_or(0) >= goal.target)
+    };
+    
+    let progress: Vec<GoalProgress> = goals
+        .iter()
+        .map(|goal| progress_for(goal, totals[&goal.metric].get(date).copied().unwrap_or(0)))
+        .collect();
+    let all_attained = attained_on(day);
+    
+    let first_streak_day = if all_attained || !in_progress { day } else { day - chrono::Duration::days(1) };
+    let current_streak = (0..MAX_STREAK_DAYS)
+        .map(|offset| first_streak_day - chrono::Duration::days(offset))
+        .take_while(|d| attained_on(*d))
+        .count() as i32;
+    
+    Ok(DailyGoalStatus {
+        date: date.to_string(),
+        goals: progress,
+        all_attained,
+        current_streak,
+    })
+}
+
+// ============================================================================
+// Wellbeing
+// ============================================================================
+
+/// Payload of the "wellbeing-limit-exceeded" event
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WellbeingAlert {
+    pub date: String,
+    pub tracked_minutes: i32,
+    pub daily_limit_minutes: i32,
+}
+
+/// Minutes tracked on `date`; finished workblocks count their recorded length, an active one the time elapsed by `now`
+pub fn query_tracked_minutes(conn: &Connection, date: &str, now: DateTime<Local>) -> rusqlite::Result<i32> {
+    let mut stmt = conn.prepare("SELECT status, start_time, duration_minutes FROM workblocks WHERE date = ?1")?;
+    let rows = stmt
+        .query_map(params![date], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<i32>>(2)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows
+        .into_iter()
+        .map(|(status, start_time, duration)| worked_minutes(&status, &start_time, duration, now))
+        .sum())
+}
+
+/// Minutes a workblock has been worked by `now`: its recorded length once finished, the elapsed time
+/// (at most the planned length) while active
+fn worked_minutes(status: &str, start_time: &str, duration: Option<i32>, now: DateTime<Local>) -> i32 {
+    match status {
+        "active" => DateTime::parse_from_rfc3339(start_time)
+            .map(|start| {
+                let elapsed = (now.signed_duration_since(start).num_minutes() as i32).max(0);
+                duration.map_or(elapsed, |planned| elapsed.min(planned))
+            })
+            .unwrap_or(0),
+        _ => duration.unwrap_or(0),
+    }
+}
+
+/// An alert if tracking on `date` has passed the wellbeing limit
+pub fn query_wellbeing_alert(
+    conn: &Connection,
+    date: &str,
+    now: DateTime<Local>,
+    wellbeing: &WellbeingSettings,
+) -> rusqlite::Result<Option<WellbeingAlert>> {
+    if !wellbeing.enabled {
+        return Ok(None);
+    }
+    let tracked_minutes = query_tracked_minutes(conn, date, now)?;
+    Ok(wellbeing.exceeded_by(tracked_minutes).then(|| WellbeingAlert {
+        date: date.to_string(),
+        tracked_minutes,
+        daily_limit_minutes: wellbeing.daily_limit_minutes,
+    }))
+}
+
+/// Intervals starting in [LATE_EVENING_HOUR, 24) or [0, EARLY_MORNING_HOUR) count as late
+pub const LATE_EVENING_HOUR: u32 = 21;
+pub const EARLY_MORNING_HOUR: u32 = 5;
+/// Weeks averaged into the rolling burnout score
+pub const BURNOUT_ROLLING_WEEKS: i64 = 4;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnoutLevel {
+    Low,
+    Moderate,
+    High,
+}
+
+impl BurnoutLevel {
+    fn for_score(score: i32) -> Self {
+        match score {
+            s if s < 34 => BurnoutLevel::Low,
+            s if s < 67 => BurnoutLevel::Moderate,
+            _ => BurnoutLevel::High,
+        }
+    }
+}
+
+/// Signs of unsustainable habits in one week (Monday to Sunday)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BurnoutSignals {
+    pub week_start: String,
+    pub late_intervals: i32,
+    pub total_intervals: i32,
+    pub weekend_minutes: i32,
+    pub total_minutes: i32,
+    pub over_limit_days: i32,  // Days tracked past the wellbeing daily limit
+    pub score: i32,            // 0 (sustainable) to 100
+}
+
+impl BurnoutSignals {
+    /// Each signal saturates (a quarter of intervals late, a fifth of time on weekends, three long days)
+    /// and the score is their mean
+    fn compute_score(&mut self) {
+        let ratio = |part: i32, whole: i32| if whole > 0 { part as f64 / whole as f64 } else { 0.0 };
+        let late = (ratio(self.late_intervals, self.total_intervals) / 0.25).min(1.0);
+        let weekend = (ratio(self.weekend_minutes, self.total_minutes) / 0.2).min(1.0);
+        let long_days = (self.over_limit_days as f64 / 3.0).min(1.0);
+        self.score = ((late + weekend + long_days) / 3.0 * 100.0).round() as i32;
+    }
+}
+
+/// Rolling burnout indicator, included in the weekly review
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BurnoutIndicator {
+    pub week: BurnoutSignals,         // The reviewed week
+    pub rolling_score: i32,           // Mean score of the reviewed week and the weeks before it
+    pub level: BurnoutLevel,          // From rolling_score
+    pub weekly_scores: Vec<i32>,      // Oldest first, ending with the reviewed week
+}
+
+fn query_burnout_signals(conn: &Connection, week_start: NaiveDate, daily_limit_minutes: i32) -> rusqlite::Result<BurnoutSignals> {
+    use chrono::{Datelike, Timelike, Weekday};
+    let start = week_start.format("%Y-%m-%d").to_string();
+    let end = (week_start + chrono::Duration::days(7)).format("%Y-%m-%d").to_string();
+    let mut signals = BurnoutSignals {
+        week_start: start.clone(),
+        late_intervals: 0,
+        total_intervals: 0,
+        weekend_minutes: 0,
+        total_minutes: 0,
+        over_limit_days: 0,
+        score: 0,
+    };
+    
+    // Interval start times keep the offset they were recorded in, so the hour is local wall-clock time
+    let mut stmt = conn.prepare(
+        "SELECT i.start_time
+         FROM intervals i
+         JOIN workblocks w ON w.id = i.workblock_id
+         WHERE w.date >= ?1 AND w.date < ?2 AND i.words IS NOT NULL"
+    )?;
+    let starts = stmt
+        .query_map(params![start, end], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for start_time in starts {
+        signals.total_intervals += 1;
+        if let Ok(time) = DateTime::parse_from_rfc3339(&start_time).map(|t| t.with_timezone(&Local)) {
+            if time.hour() >= LATE_EVENING_HOUR || time.hour() < EARLY_MORNING_HOUR {
+                signals.late_intervals += 1;
+            }
+        }
+    }
+    
+    let mut stmt = conn.prepare(
+        "SELECT date, COALESCE(SUM(duration_minutes), 0)
+         FROM workblocks
+         WHERE date >= ?1 AND date < ?2
+         GROUP BY date"
+    )?;
+    let days = stmt
+        .query_map(params![start, end], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (date, minutes) in days {
+        signals.total_minutes += minutes;
+        if minutes > daily_limit_minutes {
+            signals.over_limit_days += 1;
+        }
+        let weekday = NaiveDate::parse_from_str(&date, "%Y-%m-%d").map(|d| d.weekday());
+        if matches!(weekday, Ok(Weekday::Sat | Weekday::Sun)) {
+            signals.weekend_minutes += minutes;
+        }
+    }
+    
+    signals.compute_score();
+    Ok(signals)
+}
+
+/// Burnout indicator for the week starting `week_start`, averaged over BURNOUT_ROLLING_WEEKS weeks.
+/// Long days are measured against the wellbeing daily limit, whether or not the guard is on.
+pub fn query_burnout_indicator(conn: &Connection, week_start: NaiveDate) -> rusqlite::Result<BurnoutIndicator> {
+    let daily_limit_minutes = query_wellbeing_settings(conn).daily_limit_minutes;
+    let mut weeks = (0..BURNOUT_ROLLING_WEEKS)
+        .rev()
+        .map(|ago| query_burnout_signals(conn, week_start - chrono::Duration::weeks(ago), daily_limit_minutes))
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let weekly_scores: Vec<i32> = weeks.iter().map(|w| w.score).collect();
+    let rolling_score = (weekly_scores.iter().sum::<i32>() as f64 / weekly_scores.len() as f64).round() as i32;
+    Ok(BurnoutIndicator {
+        week: weeks.pop().expect("at least one week"),
+        rolling_score,
+        level: BurnoutLevel::for_score(rolling_score),
+        weekly_scores,
+    })
+}
+
+/// Today's wellbeing alert, if the guard is on and the limit has been passed
+pub fn check_wellbeing(app: &AppHandle) -> Result<Option<WellbeingAlert>, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let wellbeing = query_wellbeing_settings(&conn);
+    let now = Local::now();
+    let today = crate::db::get_today_date();
+    query_wellbeing_alert(&conn, &today, now, &wellbeing).map_err(|e| e.to_string())
+}
+
+/// Live progress toward today's goals
+pub fn get_goal_progress(app: &AppHandle) -> Result<DailyGoalStatus, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let today = crate::db::get_today_date();
+    query_goal_status(&conn, &today, true).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Milestones
+// ============================================================================
+
+/// Streak lengths (in days) worth announcing
+pub const STREAK_MILESTONES: [i32; 7] = [3, 7, 14, 30, 60, 100, 365];
+
+/// Payload of the "milestone" event, for automations reacting to progress rather than raw intervals
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Milestone {
+    GoalReached { date: String, metric: GoalMetric, target: i32, actual: i32 },
+    BudgetExceeded { date: String, tracked_minutes: i32, daily_limit_minutes: i32 },
+    StreakMilestone { date: String, streak: i32 },
+}
+
+impl Milestone {
+    /// Day the milestone was reached
+    pub fn date(&self) -> &str {
+        match self {
+            Milestone::GoalReached { date, .. }
+            | Milestone::BudgetExceeded { date, .. }
+            | Milestone::StreakMilestone { date, .. } => date,
+        }
+    }
+    
+    /// Identifies a milestone so it is announced once per day
+    pub fn key(&self) -> String {
+        match self {
+            Milestone::GoalReached { date, metric, .. } => format!("goal_reached:{}:{}", date, metric.as_str()),
+            Milestone::BudgetExceeded { date, .. } => format!("budget_exceeded:{}", date),
+            Milestone::StreakMilestone { date, streak } => format!("streak_milestone:{}:{}", date, streak),
+        }
+    }
+}
+
+impl From<WellbeingAlert> for Milestone {
+    fn from(alert: WellbeingAlert) -> Self {
+        Milestone::BudgetExceeded {
+            date: alert.date,
+            tracked_minutes: alert.tracked_minutes,
+            daily_limit_minutes: alert.daily_limit_minutes,
+        }
+    }
+}
+
+/// Goals attained and streak milestones reached according to `status`
+pub fn goal_milestones(status: &DailyGoalStatus) -> Vec<Milestone> {
+    let mut milestones: Vec<Milestone> = status
+        .goals
+        .iter()
+        .filter(|goal| goal.attained)
+        .map(|goal| Milestone::GoalReached {
+            date: status.date.clone(),
+            metric: goal.metric,
+            target: goal.target,
+            actual: goal.actual,
+        })
+        .collect();
+    if status.all_attained && STREAK_MILESTONES.contains(&status.current_streak) {
+        milestones.push(Milestone::StreakMilestone {
+            date: status.date.clone(),
+            streak: status.current_streak,
+        });
+    }
+    milestones
+}
+
+/// Remember that `milestone` was announced; false if it already was (also before a restart)
+pub fn record_milestone(conn: &Connection, milestone: &Milestone) -> rusqlite::Result<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO announced_milestones (date, key, announced_at) VALUES (?1, ?2, ?3)",
+        params![milestone.date(), milestone.key(), timestamp_now()],
+    )?;
+    Ok(inserted == 1)
+}
+
+/// Emit "milestone" and notify webhooks for each milestone not announced yet; returns those announced
+pub fn announce_milestones(app: &AppHandle, milestones: Vec<Milestone>) -> Vec<Milestone> {
+    let conn = match get_db_connection(app) {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("[STATS] Can't tell which milestones are new: {}", e);
+            return Vec::new();
+        }
+    };
+    let fresh: Vec<Milestone> = milestones
+        .into_iter()
+        .filter(|m| {
+            record_milestone(&conn, m).unwrap_or_else(|e| {
+                eprintln!("[STATS] Failed to record milestone {}: {}", m.key(), e);
+                false
+            })
+        })
+        .collect();
+    drop(conn);
+    for milestone in &fresh {
+        println!("[STATS] Milestone: {}", milestone.key());
+        let _ = crate::events::emit_event(app, crate::events::AppEvent::Milestone(milestone.clone()));
+        crate::webhooks::dispatch_milestone(app, milestone);
+    }
+    fresh
+}
+
+/// Announce goals and streaks newly reached today
+pub fn check_goal_milestones(app: &AppHandle) {
+    match get_goal_progress(app) {
+        Ok(status) => {
+            announce_milestones(app, goal_milestones(&status));
+        }
+        Err(e) => eprintln!("[STATS] Milestone check failed: {}", e),
+    }
+}
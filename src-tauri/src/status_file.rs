@@ -0,0 +1,192 @@
+// Status file for Polybar, xbar or Stream Deck scripts: the current activity, the time left in the
+// interval and today's total, rewritten every second while the setting is on.
+// Scripts only read a file, so they work whether or not the app window is open.
+
+use crate::db::{get_db_connection, get_today_date};
+use crate::settings::{get_status_file_settings, StatusFileFormat, StatusFileSettings};
+use crate::stats::query_tracked_minutes;
+use crate::status_page::{status_page_for, StatusState};
+use crate::timer::{compute_tick, TimerManager, TimerState};
+use crate::tray::{format_countdown, format_tracked_minutes};
+use chrono::Local;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+/// Settings keys that start, move or stop the writer
+const STATUS_FILE_KEY_PREFIX: &str = "status_file_";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StatusFile {
+    pub state: StatusState,
+    pub activity: Option<String>,                 // Latest answer in the running workblock, else its title
+    pub interval_seconds_remaining: Option<i64>,  // None outside a workblock
+    pub today_minutes: i32,
+    pub text: String,                             // The line written in the text format
+}
+
+/// What the user is doing: the latest answer in the active workblock, or its title before the first answer
+pub fn query_current_activity(conn: &Connection) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT COALESCE(
+             (SELECT words FROM intervals
+              WHERE workblock_id = w.id AND status = 'recorded' AND TRIM(COALESCE(words, '')) != ''
+              ORDER BY interval_number DESC LIMIT 1),
+             NULLIF(TRIM(w.title), ''))
+         FROM workblocks w WHERE w.status = 'active' ORDER BY w.start_time DESC LIMIT 1",
+        [],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .optional()
+    .map(Option::flatten)
+}
+
+/// One line for a status bar, e.g. "writing docs · 7:32 · 2h 15m today" or "Break · 2h 15m today"
+pub fn status_line(
+    state: StatusState,
+    activity: Option<&str>,
+    interval_seconds_remaining: Option<i64>,
+    today_minutes: i32,
+) -> String {
+    let label = match state {
+        StatusState::Focus => activity.unwrap_or("Focus").to_string(),
+        StatusState::Break => "Break".to_string(),
+        StatusState::Paused => "Paused".to_string(),
+        StatusState::Available => "Idle".to_string(),
+    };
+    let mut parts = vec![label];
+    parts.extend(interval_seconds_remaining.map(format_countdown));
+    parts.push(format!("{} today", format_tracked_minutes(today_minutes)));
+    parts.join(" · ")
+}
+
+pub fn build_status_file(
+    state: StatusState,
+    timer: &TimerState,
+    activity: Option<String>,
+    today_minutes: i32,
+    now: chrono::DateTime<Local>,
+) -> StatusFile {
+    let interval_seconds_remaining = compute_tick(timer, now).map(|tick| tick.interval_remaining_seconds);
+    // The activity only means something while a workblock runs
+    let activity = activity.filter(|_| interval_seconds_remaining.is_some());
+    StatusFile {
+        state,
+        text: status_line(state, activity.as_deref(), interval_seconds_remaining, today_minutes),
+        activity,
+        interval_seconds_remaining,
+        today_minutes,
+    }
+}
+
+/// File contents in `format`
+pub fn render_status_file(status: &StatusFile, format: StatusFileFormat) -> String {
+    match format {
+        StatusFileFormat::Json => serde_json::to_string(status).unwrap_or_default() + "\n",
+        StatusFileFormat::Text => format!("{}\n", status.text),
+    }
+}
+
+/// Replace the file at `path` with `contents`, so a reader never sees it half written
+pub fn write_status_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    std::fs::write(&temp, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    std::fs::rename(&temp, path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Where the file goes: the configured path, or status.json/status.txt in the app data directory
+pub fn status_file_path(app: &AppHandle, settings: &StatusFileSettings) -> Option<PathBuf> {
+    if !settings.path.is_empty() {
+        return Some(PathBuf::from(&settings.path));
+    }
+    let name = match settings.format {
+        StatusFileFormat::Json => "status.json",
+        StatusFileFormat::Text => "status.txt",
+    };
+    app.path().app_data_dir().ok().map(|dir| dir.join(name))
+}
+
+/// The status right now
+pub async fn current_status_file(app: &AppHandle) -> StatusFile {
+    let (timer, break_info) = match app.try_state::<Arc<Mutex<TimerManager>>>() {
+        Some(timer_manager) => {
+            let timer = timer_manager.lock().await;
+            (timer.get_state().await, timer.get_break().await)
+        }
+        None => (TimerState::default(), None),
+    };
+    let state = status_page_for(app, &timer, break_info.as_ref()).state;
+    let now = Local::now();
+    let (activity, today_minutes) = match get_db_connection(app) {
+        Ok(conn) => (
+            query_current_activity(&conn).ok().flatten(),
+            query_tracked_minutes(&conn, &get_today_date(), now).unwrap_or(0),
+        ),
+        Err(_) => (None, 0),
+    };
+    build_status_file(state, &timer, activity, today_minutes, now)
+}
+
+async fn write_every_second(app: AppHandle, path: PathBuf, format: StatusFileFormat) {
+    println!("[STATUS_FILE] Writing {}", path.display());
+    let mut ticker = tokio::time::interval(Duration::from_secs(1));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut last_written = None;
+    loop {
+        ticker.tick().await;
+        let contents = render_status_file(&current_status_file(&app).await, format);
+        if last_written.as_ref() == Some(&contents) {
+            continue;
+        }
+        match write_status_file(&path, &contents) {
+            Ok(()) => last_written = Some(contents),
+            Err(e) => eprintln!("[STATUS_FILE] {}", e),
+        }
+    }
+}
+
+/// The running writer and the file it writes
+static WRITER: std::sync::Mutex<Option<(PathBuf, StatusFileFormat, tauri::async_runtime::JoinHandle<()>)>> =
+    std::sync::Mutex::new(None);
+
+/// Start, stop or redirect the writer so it matches the current settings
+pub fn apply_status_file_settings(app: &AppHandle) {
+    let settings = get_status_file_settings(app);
+    let wanted = settings
+        .enabled
+        .then(|| status_file_path(app, &settings))
+        .flatten()
+        .map(|path| (path, settings.format));
+    let mut writer = WRITER.lock().unwrap_or_else(|e| e.into_inner());
+    if writer.as_ref().map(|(path, format, _)| (path, format)) == wanted.as_ref().map(|(path, format)| (path, format)) {
+        return;
+    }
+    if let Some((path, _, handle)) = writer.take() {
+        handle.abort();
+        // A file left behind would show a stale status forever
+        let _ = std::fs::remove_file(&path);
+        println!("[STATUS_FILE] Stopped writing {}", path.display());
+    }
+    if let Some((path, format)) = wanted {
+        let handle = tauri::async_runtime::spawn(write_every_second(app.clone(), path.clone(), format));
+        *writer = Some((path, format, handle));
+    }
+}
+
+/// Write the file according to the settings now, and follow them as they change
+pub fn spawn_settings_listener(app: &AppHandle) {
+    apply_status_file_settings(app);
+    crate::settings::spawn_settings_listener("status_file", app, |app, change| async move {
+        if change.key.is_empty() || change.key.starts_with(STATUS_FILE_KEY_PREFIX) {
+            apply_status_file_settings(&app);
+        }
+    });
+}
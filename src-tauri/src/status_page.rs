@@ -0,0 +1,92 @@
+// Read-only status for a second monitor or an office door display ("In a focus block until 15:30").
+// Built from the timer state only: no entry text, titles or projects ever appear in it.
+
+use crate::dnd::{get_dnd_status, DndStatus};
+use crate::format::Formatter;
+use crate::i18n::{tr, tr_with, Text};
+use crate::settings::{get_language, Language};
+use crate::interval_schedule::{interval_seconds, workblock_end};
+use crate::timer::{compute_tick, BreakInfo, TimerManager, TimerState};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusState {
+    Available,
+    Focus,   // A workblock is running
+    Break,   // Pomodoro break between workblocks
+    Paused,  // Prompts paused (do not disturb) outside a workblock
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StatusPage {
+    pub state: StatusState,
+    pub headline: String,       // e.g. "In a focus block until 15:30", in the configured language
+    pub until: Option<String>,  // When the state ends (RFC 3339); None while available
+}
+
+/// The status at `now`. A running workblock wins over a break, which wins over a pause.
+pub fn build_status_page(
+    timer: &TimerState,
+    break_info: Option<&BreakInfo>,
+    dnd: &DndStatus,
+    now: DateTime<Local>,
+    language: Language,
+    formatter: &Formatter,
+) -> StatusPage {
+    // The scheduled end of the workblock, which stays put from tick to tick
+    let focus_until = compute_tick(timer, now).and(timer.interval_start_time).map(|interval_start| {
+        workblock_end(interval_start, timer.current_interval_number, timer.total_intervals, interval_seconds())
+    });
+    let break_until = break_info
+        .and_then(|b| DateTime::parse_from_rfc3339(&b.ends_at).ok())
+        .map(|until| until.with_timezone(&Local))
+        .filter(|until| *until > now);
+    let dnd_until = dnd.until.as_deref()
+        .and_then(|until| DateTime::parse_from_rfc3339(until).ok())
+        .map(|until| until.with_timezone(&Local))
+        .filter(|_| dnd.active);
+
+    let (state, text, until) = match (focus_until, break_until, dnd_until) {
+        (Some(until), _, _) => (StatusState::Focus, Text::StatusFocusUntil, Some(until)),
+        (None, Some(until), _) => (StatusState::Break, Text::StatusBreakUntil, Some(until)),
+        (None, None, Some(until)) => (StatusState::Paused, Text::StatusPausedUntil, Some(until)),
+        (None, None, None) => (StatusState::Available, Text::StatusAvailable, None),
+    };
+    let headline = match until {
+        Some(until) => tr_with(language, text, &formatter.time(until.time())),
+        None => tr(language, text).to_string(),
+    };
+    StatusPage {
+        state,
+        headline,
+        until: until.map(|until| until.to_rfc3339()),
+    }
+}
+
+/// The status for `timer` and `break_info` right now, with the user's pause, language and time format
+pub fn status_page_for(app: &AppHandle, timer: &TimerState, break_info: Option<&BreakInfo>) -> StatusPage {
+    build_status_page(
+        timer,
+        break_info,
+        &get_dnd_status(app),
+        Local::now(),
+        get_language(app),
+        &Formatter::from_settings(app),
+    )
+}
+
+pub async fn get_status_page(app: &AppHandle) -> StatusPage {
+    let Some(timer_manager) = app.try_state::<Arc<Mutex<TimerManager>>>() else {
+        return status_page_for(app, &TimerState::default(), None);
+    };
+    let timer = timer_manager.lock().await;
+    let state = timer.get_state().await;
+    let break_info = timer.get_break().await;
+    drop(timer);
+    status_page_for(app, &state, break_info.as_ref())
+}
@@ -0,0 +1,194 @@
+// Taxonomy packs: the labeling scheme (rules, aliases, colors, pinned activities) as shareable JSON
+
+use crate::db::{
+    get_db_connection, query_activity_colors, query_alias_map, query_pinned_activities, store_activity_color, timestamp_now,
+    ActivityColor,
+};
+use crate::rules::{query_rules, CategoryRule, CompiledRule, RuleMatchType};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Format version written into exported packs; newer packs are rejected
+pub const TAXONOMY_PACK_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PackRule {
+    pub pattern: String,
+    pub match_type: RuleMatchType,
+    pub category: String,
+    pub priority: i32,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PackAlias {
+    pub alias: String,
+    pub canonical: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaxonomyPack {
+    pub version: u32,
+    pub exported_at: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<PackRule>,
+    #[serde(default)]
+    pub aliases: Vec<PackAlias>,
+    #[serde(default)]
+    pub colors: Vec<ActivityColor>,
+    #[serde(default)]
+    pub pinned: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    Merge,    // Add to the current scheme; imported aliases and colors win on conflict
+    Replace,  // Clear rules, aliases, colors and pins first
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ImportSummary {
+    pub rules_added: usize,
+    pub aliases_set: usize,
+    pub colors_set: usize,
+    pub pinned_added: usize,
+}
+
+/// The current labeling scheme as a pack
+pub fn query_taxonomy_pack(conn: &Connection) -> rusqlite::Result<TaxonomyPack> {
+    let rules = query_rules(conn)?
+        .into_iter()
+        .map(|r| PackRule {
+            pattern: r.pattern,
+            match_type: r.match_type,
+            category: r.category,
+            priority: r.priority,
+            enabled: r.enabled,
+        })
+        .collect();
+    let mut aliases: Vec<PackAlias> = query_alias_map(conn)?
+        .into_iter()
+        .map(|(alias, canonical)| PackAlias { alias, canonical })
+        .collect();
+    aliases.sort_by(|a, b| a.canonical.cmp(&b.canonical).then_with(|| a.alias.cmp(&b.alias)));
+
+    Ok(TaxonomyPack {
+        version: TAXONOMY_PACK_VERSION,
+        exported_at: Some(timestamp_now()),
+        rules,
+        aliases,
+        colors: query_activity_colors(conn)?,
+        pinned: query_pinned_activities(conn)?,
+    })
+}
+
+/// Apply a pack in one transaction; nothing changes if any entry is invalid.
+/// Rules identical to an existing one (pattern, match type and category) are not duplicated.
+pub fn apply_taxonomy_pack(conn: &Connection, pack: &TaxonomyPack, mode: ImportMode) -> Result<ImportSummary, String> {
+    if pack.version > TAXONOMY_PACK_VERSION {
+        return Err(format!(
+            "Taxonomy pack version {} is newer than supported version {}",
+            pack.version, TAXONOMY_PACK_VERSION
+        ));
+    }
+    // Validate everything up front so a bad entry can't leave a half-imported scheme
+    for rule in &pack.rules {
+        if rule.pattern.trim().is_empty() || rule.category.trim().is_empty() {
+            return Err("Rule pattern and category must not be empty".to_string());
+        }
+        CompiledRule::compile(CategoryRule {
+            id: None,
+            pattern: rule.pattern.clone(),
+            match_type: rule.match_type.clone(),
+            category: rule.category.clone(),
+            priority: rule.priority,
+            enabled: rule.enabled,
+            created_at: None,
+        })?;
+    }
+    if pack.aliases.iter().any(|a| a.alias.trim().is_empty() || a.canonical.trim().is_empty()) {
+        return Err("Alias and canonical activity must not be empty".to_string());
+    }
+
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let mut summary = ImportSummary::default();
+    let now = timestamp_now();
+
+    if mode == ImportMode::Replace {
+        tx.execute_batch(
+            "DELETE FROM category_rules;
+             DELETE FROM activity_aliases;
+             DELETE FROM activity_colors;
+             DELETE FROM pinned_activities;",
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    for rule in &pack.rules {
+        let exists: bool = tx
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM category_rules WHERE pattern = ?1 AND match_type = ?2 AND category = ?3)",
+                params![rule.pattern, rule.match_type.as_str(), rule.category.trim()],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if exists {
+            continue;
+        }
+        tx.execute(
+            "INSERT INTO category_rules (pattern, match_type, category, priority, enabled, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![rule.pattern, rule.match_type.as_str(), rule.category.trim(), rule.priority, rule.enabled, now],
+        )
+        .map_err(|e| e.to_string())?;
+        summary.rules_added += 1;
+    }
+
+    for alias in &pack.aliases {
+        tx.execute(
+            "INSERT OR REPLACE INTO activity_aliases (alias, canonical, created_at) VALUES (?1, ?2, ?3)",
+            params![alias.alias.to_lowercase().trim(), alias.canonical.to_lowercase().trim(), now],
+        )
+        .map_err(|e| e.to_string())?;
+        summary.aliases_set += 1;
+    }
+
+    for color in &pack.colors {
+        store_activity_color(&tx, &color.activity, &color.color).map_err(|e| e.to_string())?;
+        summary.colors_set += 1;
+    }
+
+    for activity in pack.pinned.iter().map(|a| a.trim()).filter(|a| !a.is_empty()) {
+        let added = tx
+            .execute(
+                "INSERT INTO pinned_activities (activity, pinned_at)
+                 SELECT ?1, ?2 WHERE NOT EXISTS (SELECT 1 FROM pinned_activities WHERE lower(activity) = lower(?1))",
+                params![activity, now],
+            )
+            .map_err(|e| e.to_string())?;
+        summary.pinned_added += added;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(summary)
+}
+
+/// Write the current labeling scheme to `path` as a JSON pack
+pub fn export_taxonomy_pack(app: &AppHandle, path: &Path) -> Result<TaxonomyPack, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let pack = query_taxonomy_pack(&conn).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&pack).map_err(|e| format!("Failed to serialize taxonomy pack: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(pack)
+}
+
+/// Read a JSON pack from `path` and apply it
+pub fn import_taxonomy_pack(app: &AppHandle, path: &Path, mode: ImportMode) -> Result<ImportSummary, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let pack: TaxonomyPack = serde_json::from_str(&json).map_err(|e| format!("Invalid taxonomy pack: {}", e))?;
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    apply_taxonomy_pack(&conn, &pack, mode)
+}
@@ -0,0 +1,153 @@
+// Theme preference resolved against the OS theme and applied to every window.
+// Windows created later (prompt, summary, break) start with the same theme through window_theme().
+
+use crate::settings::{get_theme_preference, ThemePreference};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Theme};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolvedTheme {
+    Light,
+    Dark,
+}
+
+impl From<ResolvedTheme> for Theme {
+    fn from(theme: ResolvedTheme) -> Self {
+        match theme {
+            ResolvedTheme::Light => Theme::Light,
+            ResolvedTheme::Dark => Theme::Dark,
+        }
+    }
+}
+
+/// Payload of the "theme-changed" event and result of get_theme
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct ThemeInfo {
+    pub preference: ThemePreference,
+    pub theme: ResolvedTheme,  // What the windows actually show
+}
+
+pub fn resolve_theme(preference: ThemePreference, os_theme: ResolvedTheme) -> ResolvedTheme {
+    match preference {
+        ThemePreference::System => os_theme,
+        ThemePreference::Dark => ResolvedTheme::Dark,
+        ThemePreference::Light => ResolvedTheme::Light,
+    }
+}
+
+/// Output of `defaults read -g AppleInterfaceStyle` (the key only exists in dark mode)
+pub fn parse_apple_interface_style(output: &str) -> ResolvedTheme {
+    if output.trim().eq_ignore_ascii_case("dark") {
+        ResolvedTheme::Dark
+    } else {
+        ResolvedTheme::Light
+    }
+}
+
+/// Output of `reg query ...\Themes\Personalize /v AppsUseLightTheme`
+pub fn parse_apps_use_light_theme(output: &str) -> Option<ResolvedTheme> {
+    let line = output.lines().find(|line| line.contains("AppsUseLightTheme"))?;
+    match line.split_whitespace().last()? {
+        "0x0" => Some(ResolvedTheme::Dark),
+        "0x1" => Some(ResolvedTheme::Light),
+        _ => None,
+    }
+}
+
+/// Output of `gsettings get org.gnome.desktop.interface color-scheme`; None for 'default',
+/// which leaves it to the GTK theme name
+pub fn parse_gnome_color_scheme(output: &str) -> Option<ResolvedTheme> {
+    match output.trim().trim_matches('\'') {
+        "prefer-dark" => Some(ResolvedTheme::Dark),
+        "prefer-light" => Some(ResolvedTheme::Light),
+        _ => None,
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<(bool, String)> {
+    let mut command = std::process::Command::new(program);
+    command.args(args);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command.output().ok()?;
+    Some((output.status.success(), String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+/// The OS theme, independent of any theme forced on our windows
+#[cfg(target_os = "macos")]
+pub fn detect_os_theme() -> Option<ResolvedTheme> {
+    // Fails (key missing) in light mode
+    let (_, output) = command_output("defaults", &["read", "-g", "AppleInterfaceStyle"])?;
+    Some(parse_apple_interface_style(&output))
+}
+
+#[cfg(target_os = "windows")]
+pub fn detect_os_theme() -> Option<ResolvedTheme> {
+    let (_, output) = command_output(
+        "reg",
+        &["query", r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize", "/v", "AppsUseLightTheme"],
+    )?;
+    parse_apps_use_light_theme(&output)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn detect_os_theme() -> Option<ResolvedTheme> {
+    let (ok, output) = command_output("gsettings", &["get", "org.gnome.desktop.interface", "color-scheme"])?;
+    if let Some(theme) = ok.then(|| parse_gnome_color_scheme(&output)).flatten() {
+        return Some(theme);
+    }
+    let (ok, gtk_theme) = command_output("gsettings", &["get", "org.gnome.desktop.interface", "gtk-theme"])?;
+    ok.then(|| {
+        if gtk_theme.to_lowercase().contains("dark") {
+            ResolvedTheme::Dark
+        } else {
+            ResolvedTheme::Light
+        }
+    })
+}
+
+/// OS theme, falling back to what the main window reports (light if unknown)
+pub fn os_theme(app: &AppHandle) -> ResolvedTheme {
+    detect_os_theme().unwrap_or_else(|| {
+        match app.get_webview_window("main").and_then(|window| window.theme().ok()) {
+            Some(Theme::Dark) => ResolvedTheme::Dark,
+            _ => ResolvedTheme::Light,
+        }
+    })
+}
+
+pub fn current_theme(app: &AppHandle) -> ThemeInfo {
+    let preference = get_theme_preference(app);
+    ThemeInfo { preference, theme: resolve_theme(preference, os_theme(app)) }
+}
+
+/// Theme to create a new window with (None follows the OS)
+pub fn window_theme(app: &AppHandle) -> Option<Theme> {
+    match get_theme_preference(app) {
+        ThemePreference::System => None,
+        ThemePreference::Dark => Some(Theme::Dark),
+        ThemePreference::Light => Some(Theme::Light),
+    }
+}
+
+/// Apply the stored preference to every open window and tell the frontends
+pub fn apply_theme(app: &AppHandle) -> ThemeInfo {
+    let theme = window_theme(app);
+    for window in app.webview_windows().values() {
+        if let Err(e) = window.set_theme(theme) {
+            eprintln!("[THEME] Failed to set theme on {}: {}", window.label(), e);
+        }
+    }
+    emit_theme_changed(app)
+}
+
+pub fn emit_theme_changed(app: &AppHandle) -> ThemeInfo {
+    let info = current_theme(app);
+    let _ = crate::events::emit_event(app, crate::events::AppEvent::ThemeChanged(info));
+    info
+}
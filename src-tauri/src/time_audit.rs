@@ -0,0 +1,44 @@
+// Daily time audit: tracked time against what else is known about the day
+
+use crate::activity_capture::query_computer_activity;
+use crate::db::get_db_connection;
+use crate::stats::query_tracked_minutes;
+use chrono::{DateTime, Local};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Tracked minutes next to meeting and active-computer minutes for one day.
+/// Sources that weren't enabled (or had no data that day) are None rather than 0.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TimeAudit {
+    pub date: String,
+    pub tracked_minutes: i32,
+    pub meeting_minutes: Option<i32>,            // There is no calendar integration yet, so always None
+    pub active_computer_minutes: Option<i32>,    // Needs activity capture
+    pub untracked_computer_minutes: Option<i32>, // Active at the computer outside any workblock
+    pub untracked_percentage: Option<f64>,       // Share of active computer time that wasn't tracked
+}
+
+pub fn query_time_audit(conn: &Connection, date: &str, now: DateTime<Local>) -> rusqlite::Result<TimeAudit> {
+    let activity = query_computer_activity(conn, date)?;
+    let active_computer_minutes = activity.map(|(tracked, untracked)| ((tracked + untracked) / 60) as i32);
+    let untracked_computer_minutes = activity.map(|(_, untracked)| (untracked / 60) as i32);
+    let untracked_percentage = activity.and_then(|(tracked, untracked)| {
+        (tracked + untracked > 0).then(|| untracked as f64 / (tracked + untracked) as f64 * 100.0)
+    });
+
+    Ok(TimeAudit {
+        date: date.to_string(),
+        tracked_minutes: query_tracked_minutes(conn, date, now)?,
+        meeting_minutes: None,
+        active_computer_minutes,
+        untracked_computer_minutes,
+        untracked_percentage,
+    })
+}
+
+pub fn get_time_audit(app: &AppHandle, date: &str) -> Result<TimeAudit, String> {
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    query_time_audit(&conn, date, Local::now()).map_err(|e| e.to_string())
+}
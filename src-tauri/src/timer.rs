@@ -1,26 +1,265 @@
 // Timer system for managing workblocks and 15-minute intervals
 
 use crate::db::{
-    add_interval, get_active_workblock, get_current_interval, get_interval_by_id,
-    get_workblock_by_id, update_interval_words, complete_workblock, IntervalStatus,
+    add_interval, check_and_reset_daily, day_start_time, get_active_workblock, get_current_interval,
+    get_interval_by_id, get_workblock_by_id, logical_date, query_interval_by_id, query_workblock_by_id,
+    set_interval_bounds, set_interval_end_time, record_away_interval, store_away_cause, store_interval_words,
+    to_timestamp, AwayCause, Interval, IntervalStatus, WorkblockStatus,
 };
+use crate::error::Log15Error;
+use crate::events::{emit_event, AppEvent, IntervalComplete};
+use crate::interval_schedule::{self, align_to_boundary, interval_seconds, remaining_seconds};
+use crate::settings::{
+    get_align_intervals_to_clock, get_auto_away_settings, get_interval_warning_settings, get_pomodoro_settings,
+    AUTO_AWAY_TIMEOUT_KEY,
+};
+use crate::timing::timing;
 use crate::tray::{TrayIconState, TrayManager};
+use crate::webhooks::{self, WebhookEvent};
 use crate::window_manager::WindowManager;
 use chrono::{DateTime, Local, Utc};
+use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager};
 use tokio::sync::Mutex;
-use tokio::time::{interval, Duration};
+use tokio::time::Duration;
+
+
+/// Longest workblock that can be started (the shortest comes from the timing profile)
+pub const MAX_WORKBLOCK_MINUTES: i32 = 8 * 60;
+
+/// Why a workblock duration was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum DurationError {
+    TooShort { minimum: i32 },
+    TooLong { maximum: i32 },
+}
+
+impl std::fmt::Display for DurationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DurationError::TooShort { minimum } => write!(f, "Workblocks must be at least {} minutes", minimum),
+            DurationError::TooLong { maximum } => write!(f, "Workblocks can be at most {} minutes", maximum),
+        }
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+/// Validate a workblock duration, rounding it up to a whole number of intervals
+pub fn validate_duration_minutes(duration_minutes: i32) -> Result<i32, DurationError> {
+    let min_workblock_minutes = timing().min_workblock_minutes;
+    if duration_minutes < min_workblock_minutes {
+        return Err(DurationError::TooShort { minimum: min_workblock_minutes });
+    }
+    if duration_minutes > MAX_WORKBLOCK_MINUTES {
+        return Err(DurationError::TooLong { maximum: MAX_WORKBLOCK_MINUTES });
+    }
+    let total_intervals = interval_schedule::total_intervals(duration_minutes, interval_seconds());
+    Ok(interval_schedule::planned_minutes(total_intervals, interval_seconds()))
+}
+
+/// How often the interval loop re-checks the wall clock while waiting for a boundary.
+/// Tokio timers are monotonic and pause while the system sleeps, so one long sleep would fire late after a wake.
+const WALL_CLOCK_CHECK_SECONDS: i64 = 5;
+
+/// Words recorded for intervals that ended while the system was asleep
+pub const MISSED_INTERVAL_MESSAGE: &str = "Missed (system asleep)";
+
+/// Payload of the "missed-intervals" event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissedIntervals {
+    pub workblock_id: i64,
+    pub interval_ids: Vec<i64>,
+    pub first_interval_number: i32,
+    pub last_interval_number: i32,
+}
+
+/// Payload of the "day-rolled-over" event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayRolledOver {
+    pub previous_date: String,
+    pub date: String,
+    pub archived_date: Option<String>,          // None when the previous day was quiet or already archived
+    pub ended_workblock_id: Option<i64>,        // Workblock that ran into the new day and ended at its start
+    pub continued_workblock_id: Option<i64>,    // Workblock carrying on with the rest of its planned time
+}
+
+/// Length of the workblock that carries on after a split at the day start, for a schedule that was
+/// planned to end at `planned_end`: the whole intervals still left. None when that is less than the
+/// shortest workblock.
+pub fn continuation_minutes(planned_end: DateTime<Local>, now: DateTime<Local>) -> Option<i32> {
+    let intervals_left = ((planned_end - now).num_seconds().max(0) / interval_seconds().max(1)) as i32;
+    validate_duration_minutes(interval_schedule::planned_minutes(intervals_left, interval_seconds())).ok()
+}
+
+/// Number of whole intervals that have ended between `schedule_start` and `now`
+pub fn intervals_elapsed(schedule_start: DateTime<Local>, now: DateTime<Local>) -> i32 {
+    interval_schedule::intervals_elapsed(schedule_start, now, interval_seconds())
+}
+
+/// The clock boundary (e.g. :00, :15, :30, :45) at or before `time`
+pub fn clock_aligned_start(time: DateTime<Local>) -> DateTime<Local> {
+    align_to_boundary(time, interval_seconds())
+}
+
+/// Anchor of the interval schedule for a workblock started at `started_at`.
+/// When aligned to the clock the anchor is the previous clock boundary, so the first interval is shortened.
+pub fn schedule_anchor(started_at: DateTime<Local>, align_to_clock: bool) -> DateTime<Local> {
+    if align_to_clock {
+        clock_aligned_start(started_at)
+    } else {
+        started_at
+    }
+}
+
+/// Wall-clock time at which interval `interval_number` ends (interval 1 starts at `schedule_start`)
+pub fn interval_boundary(schedule_start: DateTime<Local>, interval_number: i32) -> DateTime<Local> {
+    interval_schedule::interval_boundary(schedule_start, interval_number, interval_seconds())
+}
+
+/// One interval of a planned workblock
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlannedInterval {
+    pub interval_number: i32,
+    pub start_time: String,
+    pub end_time: String,  // When the prompt for this interval appears
+}
+
+/// Interval boundaries of a workblock that has not been started yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkblockPlan {
+    pub duration_minutes: i32,
+    pub interval_seconds: i64,
+    pub aligned_to_clock: bool,
+    pub intervals: Vec<PlannedInterval>,
+}
+
+/// Plan a workblock of `duration_minutes` started at `started_at` with intervals of `interval_seconds`.
+/// Mirrors the timer: when aligned the first interval is shortened to end on a clock boundary.
+pub fn plan_workblock(
+    started_at: DateTime<Local>,
+    duration_minutes: i32,
+    interval_seconds: i64,
+    align_to_clock: bool,
+) -> WorkblockPlan {
+    let interval_seconds = interval_seconds.max(1);
+    let total_intervals = interval_schedule::total_intervals(duration_minutes, interval_seconds);
+    let anchor = if align_to_clock {
+        align_to_boundary(started_at, interval_seconds)
+    } else {
+        started_at
+    };
+    let boundary = |n: i32| interval_schedule::interval_boundary(anchor, n, interval_seconds);
+    
+    let intervals = (1..=total_intervals)
+        .map(|n| PlannedInterval {
+            interval_number: n,
+            start_time: if n == 1 { started_at } else { boundary(n - 1) }.to_rfc3339(),
+            end_time: boundary(n).to_rfc3339(),
+        })
+        .collect();
+    WorkblockPlan {
+        duration_minutes,
+        interval_seconds,
+        aligned_to_clock: align_to_clock,
+        intervals,
+    }
+}
+
+/// A break running after a completed workblock (Pomodoro mode); payload of "break-started"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakInfo {
+    pub workblock_id: i64,  // The workblock the break follows
+    pub break_seconds: u64,
+    pub started_at: String,
+    pub ends_at: String,
+    pub next_duration_minutes: i32,  // Length of the workblock auto-started when the break ends
+}
+
+/// Payload of the "break-ended" event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakEnded {
+    pub workblock_id: i64,
+    pub skipped: bool,  // Ended early (skipped, or a workblock was started during the break)
+}
+
+/// Payload of the "interval-ending-soon" event, emitted shortly before each interval boundary
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IntervalEndingSoon {
+    pub workblock_id: i64,
+    pub interval_id: Option<i64>,
+    pub interval_number: i32,
+    pub ends_at: String,
+    pub seconds_left: i64,
+    pub notification: bool,  // The user also wants a system notification
+}
+
+/// Payload of the "timer-tick" event, emitted every second while the ticker is on and a workblock runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimerTick {
+    pub workblock_id: i64,
+    pub interval_number: i32,
+    pub total_intervals: i32,
+    pub interval_remaining_seconds: i64,
+    pub workblock_remaining_seconds: i64,
+}
+
+/// Countdown for the running workblock at `now`, or None if no workblock is running
+pub fn compute_tick(state: &TimerState, now: DateTime<Local>) -> Option<TimerTick> {
+    let workblock_id = state.workblock_id.filter(|_| state.is_running)?;
+    let interval_start = state.interval_start_time?;
+    let interval_end = interval_schedule::interval_end(interval_start, interval_seconds());
+    let workblock_end = interval_schedule::workblock_end(
+        interval_start,
+        state.current_interval_number,
+        state.total_intervals,
+        interval_seconds(),
+    );
+    Some(TimerTick {
+        workblock_id,
+        interval_number: state.current_interval_number,
+        total_intervals: state.total_intervals,
+        interval_remaining_seconds: remaining_seconds(interval_end, now),
+        workblock_remaining_seconds: remaining_seconds(workblock_end, now),
+    })
+}
+
+/// Timer state once the interval loop died: nothing counts down any more, while the workblock
+/// stays active in the database for restore_active_workblock to resume, as after a crash
+pub fn stop_after_loop_abort(state: &mut TimerState) {
+    state.is_running = false;
+    state.interval_start_time = None;
+}
+
+/// The per-second ticker, shared by everything that subscribed to it
+struct Ticker {
+    handle: tokio::task::JoinHandle<()>,
+    subscribers: usize,
+}
+
+struct ActiveBreak {
+    info: BreakInfo,
+    handle: tokio::task::JoinHandle<()>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimerState {
     pub workblock_id: Option<i64>,
     pub current_interval_id: Option<i64>,
     pub current_interval_number: i32,
-    pub interval_start_time: Option<DateTime<Local>>,
+    pub interval_start_time: Option<DateTime<Local>>, // Start of the current interval's slot on the schedule
     pub prompt_shown_time: Option<DateTime<Local>>, // When prompt window was shown
     pub is_running: bool,
+    #[serde(default)]
+    pub total_intervals: i32, // Read by the interval loop every tick, so the workblock can be resized while running
+    #[serde(default)]
+    pub prompted_interval_id: Option<i64>, // Interval whose prompt went out most recently
+    #[serde(default)]
+    pub snoozed_until: Option<DateTime<Local>>, // Prompt hidden on purpose until then
+    #[serde(default)]
+    pub auto_away_pending: Option<AutoAwayCountdown>, // Running auto-away countdown, if any
 }
 
 impl Default for TimerState {
@@ -32,15 +271,29 @@ impl Default for TimerState {
             interval_start_time: None,
             prompt_shown_time: None,
             is_running: false,
+            total_intervals: 0,
+            prompted_interval_id: None,
+            snoozed_until: None,
+            auto_away_pending: None,
         }
     }
 }
 
+/// An auto-away countdown for an unanswered prompt
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AutoAwayCountdown {
+    pub interval_id: i64,
+    pub since: DateTime<Local>,
+}
+
 pub struct TimerManager {
     state: Arc<Mutex<TimerState>>,
     app: AppHandle,
     interval_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
     auto_away_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    active_break: Arc<Mutex<Option<ActiveBreak>>>,
+    ticker: Arc<Mutex<Option<Ticker>>>,
+    wellbeing_alerted_on: Arc<Mutex<Option<String>>>, // Date of the last wellbeing alert (one per day)
 }
 
 impl TimerManager {
@@ -50,25 +303,33 @@ impl TimerManager {
             app,
             interval_handle: Arc::new(Mutex::new(None)),
             auto_away_handle: Arc::new(Mutex::new(None)),
+            active_break: Arc::new(Mutex::new(None)),
+            ticker: Arc::new(Mutex::new(None)),
+            wellbeing_alerted_on: Arc::new(Mutex::new(None)),
         }
     }
 
     /// Start a workblock timer
-    pub async fn start_workblock(&self, workblock_id: i64, duration_minutes: i32) -> Result<(), String> {
+    pub async fn start_workblock(&self, workblock_id: i64, duration_minutes: i32) -> Result<(), Log15Error> {
+        // Starting work cuts any running break short
+        self.end_break().await;
+        
         let mut state = self.state.lock().await;
         
         if state.is_running {
-            return Err("A workblock is already running".to_string());
+            return Err(Log15Error::AlreadyRunning);
         }
 
-        // Calculate number of intervals
-        // TESTING: Calculate intervals based on 10-second intervals instead of 15-minute
-        // For testing: 1 interval per 10 seconds, so duration_minutes * 6 intervals per minute
-        let total_intervals = duration_minutes * 6; // TESTING: Changed from duration_minutes / 15
+        let total_intervals = interval_schedule::total_intervals(duration_minutes, interval_seconds());
+        
+        // Interval boundaries are fixed multiples of the interval length after the schedule anchor
+        let align_to_clock = get_align_intervals_to_clock(&self.app);
+        let schedule_start = schedule_anchor(Local::now(), align_to_clock);
         
         // Initialize state
         state.workblock_id = Some(workblock_id);
         state.current_interval_number = 0;
+        state.total_intervals = total_intervals;
         state.is_running = true;
         
         // Create first interval and set its start time
@@ -76,34 +337,133 @@ impl TimerManager {
             Ok(interval) => {
                 state.current_interval_id = interval.id;
                 state.current_interval_number = 1;
-                state.interval_start_time = Some(Local::now()); // Set start time when interval is created
+                state.interval_start_time = Some(schedule_start); // Nominal slot start (the actual start is later when aligned)
             }
             Err(e) => {
                 state.is_running = false;
-                return Err(format!("Failed to create interval: {}", e));
+                return Err(e);
             }
         }
 
+        drop(state);
+
         // Start the interval timer
+        self.spawn_interval_loop(schedule_start, 1, align_to_clock).await;
+        webhooks::dispatch_workblock_event(&self.app, WebhookEvent::WorkblockStarted, workblock_id);
+        
+        Ok(())
+    }
+
+    /// Resume a reopened workblock on its original wall-clock schedule.
+    /// Intervals that ended while it was cancelled are recorded with the away message.
+    pub async fn resume_workblock(
+        &self,
+        workblock_id: i64,
+        started_at: DateTime<Local>,
+        duration_minutes: i32,
+    ) -> Result<(), Log15Error> {
+        let mut state = self.state.lock().await;
+        
+        if state.is_running {
+            return Err(Log15Error::AlreadyRunning);
+        }
+        
+        let align_to_clock = get_align_intervals_to_clock(&self.app);
+        let schedule_start = schedule_anchor(started_at, align_to_clock);
+        
+        let total_intervals = interval_schedule::total_intervals(duration_minutes, interval_seconds());
+        let ended = intervals_elapsed(schedule_start, Local::now());
+        if ended >= total_intervals {
+            return Err(Log15Error::InvalidInput("Workblock would already have ended".to_string()));
+        }
+        let current_interval_num = ended + 1;
+        
+        // The interval that was in progress when the block was cancelled
+        let pending = get_current_interval(&self.app, workblock_id)?;
+        let first_unrecorded = pending.as_ref().map_or(current_interval_num, |i| i.interval_number);
+        if first_unrecorded < current_interval_num {
+            let interval_ids = record_missed_intervals(
+                &self.app,
+                workblock_id,
+                pending.as_ref().and_then(|i| i.id),
+                schedule_start,
+                first_unrecorded,
+                ended,
+                AwayCause::Cancelled,
+            );
+            println!("[TIMER] Resumed workblock {}: recorded {} interval(s) as away", workblock_id, interval_ids.len());
+        }
+        
+        let current_interval_id = match pending {
+            Some(interval) if interval.interval_number == current_interval_num => interval.id,
+            _ => add_interval(&self.app, workblock_id, current_interval_num)?.id,
+        };
+        state.workblock_id = Some(workblock_id);
+        state.current_interval_id = current_interval_id;
+        state.current_interval_number = current_interval_num;
+        state.interval_start_time = Some(interval_boundary(schedule_start, ended));
+        state.prompt_shown_time = None;
+        state.total_intervals = total_intervals;
+        state.is_running = true;
+        drop(state);
+        
+        self.spawn_interval_loop(schedule_start, current_interval_num, align_to_clock).await;
+        
+        Ok(())
+    }
+
+    /// Change the length of the running workblock; the interval loop picks up the new total on its next tick.
+    /// Returns the new total number of intervals.
+    pub async fn adjust_workblock_duration(&self, workblock_id: i64, duration_minutes: i32) -> Result<i32, Log15Error> {
+        let mut state = self.state.lock().await;
+        
+        if !state.is_running || state.workblock_id != Some(workblock_id) {
+            return Err(Log15Error::NoActiveWorkblock);
+        }
+        // Once the final interval has ticked the loop is gone and only the final prompt remains
+        if self.interval_handle.lock().await.as_ref().is_none_or(|h| h.is_finished()) {
+            return Err(Log15Error::InvalidInput("Workblock is already finishing".to_string()));
+        }
+        
+        let total_intervals = interval_schedule::total_intervals(duration_minutes, interval_seconds());
+        if total_intervals < state.current_interval_number {
+            return Err(Log15Error::InvalidInput(format!(
+                "Workblock is already in interval {}; it cannot end before that",
+                state.current_interval_number
+            )));
+        }
+        
+        crate::db::set_workblock_duration(&self.app, workblock_id, duration_minutes)?;
+        state.total_intervals = total_intervals;
+        println!("[TIMER] Workblock {} now has {} intervals", workblock_id, total_intervals);
+        
+        Ok(total_intervals)
+    }
+
+    /// Run the interval loop from `first_interval_num` (already created and in state) until the final interval
+    async fn spawn_interval_loop(
+        &self,
+        schedule_start: DateTime<Local>,
+        first_interval_num: i32,
+        align_to_clock: bool,
+    ) {
         let state_clone = Arc::clone(&self.state);
         let app_clone = self.app.clone();
+        let wellbeing_alerted_on = Arc::clone(&self.wellbeing_alerted_on);
         
         let handle = tokio::spawn(async move {
-            // TESTING: 10 seconds instead of 15 minutes
-            let mut interval_timer = interval(Duration::from_secs(10)); // TESTING: Changed from 15 * 60
-            interval_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-
-            // Consume the immediate first tick to establish the baseline "now"
-            // After this, each tick represents a full interval duration passing
-            interval_timer.tick().await;
-
-            // Start with interval 1 (the first interval that was already created)
-            let mut current_interval_num = 1;
-            let total_intervals = total_intervals;
+            let mut current_interval_num = first_interval_num;
             
             loop {
-                // Wait for the current interval to complete (full duration)
-                interval_timer.tick().await;
+                // Wait for the current interval to complete on the wall clock, warning shortly before it does
+                let boundary = interval_boundary(schedule_start, current_interval_num);
+                warn_before_boundary(&app_clone, &state_clone, current_interval_num, boundary).await;
+                sleep_until_wall_clock(boundary).await;
+                if crate::faults::should_abort(crate::faults::FaultPoint::IntervalLoop) {
+                    eprintln!("[TIMER] Interval loop aborted by injected fault");
+                    stop_after_loop_abort(&mut *state_clone.lock().await);
+                    break;
+                }
                 
                 // Check if timer should still be running
                 let state = state_clone.lock().await;
@@ -111,8 +471,55 @@ impl TimerManager {
                     break;
                 }
                 let workblock_id = state.workblock_id.unwrap();
+                let first_missed_id = state.current_interval_id;
+                let total_intervals = state.total_intervals;
                 drop(state);
                 
+                // Reconcile with the wall clock: after a system sleep several intervals may have ended at once
+                let ended = intervals_elapsed(schedule_start, crate::faults::now()).min(total_intervals);
+                if ended > current_interval_num {
+                    // Everything that ended while asleep is marked missed, except the final interval,
+                    // which still prompts so the workblock completes through the normal flow
+                    let last_missed = if ended >= total_intervals { total_intervals - 1 } else { ended };
+                    println!(
+                        "[TIMER] Wall clock jumped ahead (system sleep?): intervals {}..={} missed",
+                        current_interval_num, last_missed
+                    );
+                    let interval_ids = record_missed_intervals(
+                        &app_clone,
+                        workblock_id,
+                        first_missed_id,
+                        schedule_start,
+                        current_interval_num,
+                        last_missed,
+                        AwayCause::SystemSleep,
+                    );
+                    let _ = emit_event(&app_clone, AppEvent::MissedIntervals(MissedIntervals {
+                        workblock_id,
+                        interval_ids,
+                        first_interval_number: current_interval_num,
+                        last_interval_number: last_missed,
+                    }));
+                    
+                    // Realign to the interval in progress on the wall clock
+                    current_interval_num = last_missed + 1;
+                    let mut state = state_clone.lock().await;
+                    if let Ok(new_interval) = add_interval(&app_clone, workblock_id, current_interval_num) {
+                        state.current_interval_id = new_interval.id;
+                        state.current_interval_number = current_interval_num;
+                        state.interval_start_time = Some(interval_boundary(schedule_start, last_missed));
+                    }
+                    drop(state);
+                    
+                    // Only the final interval has already ended; otherwise wait for the next boundary
+                    if current_interval_num > ended {
+                        continue;
+                    }
+                }
+                
+                alert_if_over_daily_limit(&app_clone, &wellbeing_alerted_on).await;
+                crate::stats::check_goal_milestones(&app_clone);
+                
                 // Emit interval-complete event with interval info
                 // Use the current interval number BEFORE incrementing
                 let state = state_clone.lock().await;
@@ -121,17 +528,52 @@ impl TimerManager {
                 let prompt_time = Local::now();
                 drop(state);
                 
-                if let Some(interval_id) = interval_id {
+                // Clock-aligned intervals end exactly on their boundary, whenever the prompt gets answered
+                if let (Some(interval_id), true) = (interval_id, align_to_clock) {
+                    let _ = set_interval_end_time(
+                        &app_clone,
+                        interval_id,
+                        &to_timestamp(interval_boundary(schedule_start, interval_number)),
+                    );
+                }
+                
+                // While prompts are paused or the system is idle, record the interval as away instead of prompting.
+                // The final interval always prompts so the workblock completes through the normal flow.
+                let is_last_interval = interval_schedule::is_last_interval(current_interval_num, total_intervals);
+                let recorded_as_idle = match interval_id {
+                    Some(interval_id) if !is_last_interval && crate::dnd::is_dnd_active(&app_clone) => {
+                        println!("[TIMER] Prompts paused: recording interval {} without prompting", interval_id);
+                        let _ = record_away_interval(
+                            &app_clone,
+                            interval_id,
+                            crate::dnd::DND_INTERVAL_MESSAGE.to_string(),
+                            AwayCause::PromptsPaused,
+                        );
+                        true
+                    }
+                    Some(interval_id) if !is_last_interval && crate::idle::is_user_idle(&app_clone) => {
+                        let message = get_auto_away_settings(&app_clone).message;
+                        println!("[TIMER] System idle: recording '{}' for interval {} without prompting", message, interval_id);
+                        let cause = if crate::idle::is_screen_locked() { AwayCause::ScreenLock } else { AwayCause::SystemIdle };
+                        let _ = record_away_interval(&app_clone, interval_id, message, cause);
+                        let _ = emit_event(&app_clone, AppEvent::IdleAutoAway(interval_id));
+                        true
+                    }
+                    _ => false,
+                };
+                
+                if let (Some(interval_id), false) = (interval_id, recorded_as_idle) {
                     println!("[TIMER] Emitting interval-complete: interval_id={}, interval_number={}", interval_id, interval_number);
-                    let _ = app_clone.emit("interval-complete", serde_json::json!({
-                        "workblock_id": workblock_id,
-                        "interval_id": interval_id,
-                        "interval_number": interval_number
+                    let _ = emit_event(&app_clone, AppEvent::IntervalComplete(IntervalComplete {
+                        workblock_id,
+                        interval_id,
+                        interval_number,
                     }));
                     
                     // Update prompt shown time
                     let mut state = state_clone.lock().await;
                     state.prompt_shown_time = Some(prompt_time);
+                    state.prompted_interval_id = Some(interval_id);
                     drop(state);
                     
                     // Emit event to show prompt window (frontend will handle it)
@@ -158,7 +600,7 @@ impl TimerManager {
                 if let Ok(new_interval) = add_interval(&app_clone, workblock_id, current_interval_num) {
                     state.current_interval_id = new_interval.id;
                     state.current_interval_number = current_interval_num; // Update state with new interval number
-                    state.interval_start_time = Some(Local::now());
+                    state.interval_start_time = Some(interval_boundary(schedule_start, current_interval_num - 1));
                     // Don't set prompt_shown_time here - it will be set when the prompt actually appears
                     println!("[TIMER] Created next interval: interval_number={}", current_interval_num);
                 }
@@ -167,19 +609,18 @@ impl TimerManager {
         });
         
         *self.interval_handle.lock().await = Some(handle);
-        
-        Ok(())
     }
 
     /// Complete the current workblock (when it naturally finishes)
-    pub async fn complete_workblock(&self, workblock_id: i64) -> Result<(), String> {
+    pub async fn complete_workblock(&self, workblock_id: i64) -> Result<(), Log15Error> {
         let mut state = self.state.lock().await;
         
         if state.workblock_id != Some(workblock_id) {
-            return Err("Workblock ID mismatch".to_string());
+            return Err(Log15Error::InvalidInput(format!("Workblock {} is not the running workblock", workblock_id)));
         }
         
         state.is_running = false;
+        let planned_minutes = interval_schedule::planned_minutes(state.total_intervals, interval_seconds());
         drop(state);
         
         // Cancel interval timer
@@ -193,21 +634,20 @@ impl TimerManager {
         }
         
         // Complete the workblock
-        complete_workblock(&self.app, workblock_id)
-            .map_err(|e| format!("Failed to complete workblock: {}", e))?;
-        
-        // Emit workblock-complete event
-        let _ = self.app.emit("workblock-complete", workblock_id);
+        record_workblock_completed(&self.app, workblock_id, Utc::now()).await?;
         
         // Reset state
         let mut state = self.state.lock().await;
         *state = TimerState::default();
+        drop(state);
+        
+        start_break(&self.app, &self.active_break, workblock_id, planned_minutes).await;
         
         Ok(())
     }
 
     /// Cancel the current workblock (when user clicks cancel)
-    pub async fn cancel_workblock(&self, workblock_id: i64) -> Result<(), String> {
+    pub async fn cancel_workblock(&self, workblock_id: i64) -> Result<(), Log15Error> {
         let mut state = self.state.lock().await;
         
         // Check if workblock ID matches, but don't fail if it doesn't - just log it
@@ -235,13 +675,10 @@ impl TimerManager {
         
         // Cancel the workblock (sets status to cancelled)
         crate::db::cancel_workblock(&self.app, workblock_id)
-            .map_err(|e| {
-                eprintln!("[TIMER] Error cancelling workblock in database: {}", e);
-                format!("Failed to cancel workblock: {}", e)
-            })?;
+            .inspect_err(|e| eprintln!("[TIMER] Error cancelling workblock in database: {}", e))?;
         
         // Emit workblock-complete event (frontend can check status to see if cancelled)
-        let _ = self.app.emit("workblock-complete", workblock_id);
+        let _ = emit_event(&self.app, AppEvent::WorkblockComplete(workblock_id));
         
         // Reset state
         let mut state = self.state.lock().await;
@@ -250,83 +687,186 @@ impl TimerManager {
         Ok(())
     }
 
-    /// Start the auto-away timer (10 minutes after prompt is shown)
-    pub async fn start_auto_away_timer(&self, interval_id: i64) -> Result<(), String> {
+    /// Split the running workblock at `day_start` when it belongs to an earlier day than `today`.
+    /// It ends there, the interval in progress is cut short and prompted for, and when enough of its
+    /// planned time is left a workblock with the same title and project carries on in the new day.
+    /// Returns the ended workblock and its continuation, if any.
+    pub async fn split_at_day_start(
+        &self,
+        today: &str,
+        day_start: DateTime<Local>,
+    ) -> Result<Option<(i64, Option<i64>)>, Log15Error> {
+        let state = self.state.lock().await;
+        let Some(workblock_id) = state.workblock_id.filter(|_| state.is_running) else {
+            return Ok(None);
+        };
+        drop(state);
+        let workblock = get_workblock_by_id(&self.app, workblock_id)?;
+        if workblock.date.as_str() >= today {
+            return Ok(None);
+        }
+
+        let started_at = DateTime::parse_from_rfc3339(&workblock.start_time)
+            .map_err(|e| Log15Error::InvalidInput(format!("Invalid start_time: {}", e)))?
+            .with_timezone(&Local);
+        let schedule_start = schedule_anchor(started_at, get_align_intervals_to_clock(&self.app));
+        let planned_end = interval_boundary(schedule_start, self.state.lock().await.total_intervals);
+
+        // Once the final interval has ticked only its prompt is left: the workblock ends with that
+        // interval, and the prompt (with its auto-away countdown) stays for the user to answer
+        let Some(handle) = self.interval_handle.lock().await.take().filter(|h| !h.is_finished()) else {
+            record_workblock_completed(&self.app, workblock_id, planned_end.min(day_start).with_timezone(&Utc)).await?;
+            let mut state = self.state.lock().await;
+            *state = TimerState {
+                prompt_shown_time: state.prompt_shown_time,
+                prompted_interval_id: state.prompted_interval_id,
+                auto_away_pending: state.auto_away_pending.take(),
+                ..TimerState::default()
+            };
+            println!("[TIMER] Workblock {} ended before {}; its final prompt is still open", workblock_id, today);
+            return Ok(Some((workblock_id, None)));
+        };
+        handle.abort();
+
+        let state = self.state.lock().await.clone();
+
+        // The interval in progress ends with the old day; one the loop already began in the new day goes
+        let mut cut_interval = None;
+        if let Some(interval_id) = state.current_interval_id {
+            if state.interval_start_time.is_some_and(|start| start < day_start) {
+                set_interval_end_time(&self.app, interval_id, &to_timestamp(day_start))?;
+                cut_interval = Some((interval_id, state.current_interval_number));
+            } else {
+                crate::db::delete_pending_interval(&self.app, interval_id)?;
+            }
+        }
+
+        crate::db::mark_workblock_split(&self.app, workblock_id)?;
+        record_workblock_completed(&self.app, workblock_id, day_start.with_timezone(&Utc)).await?;
+        *self.state.lock().await = TimerState::default();
+        println!("[TIMER] Workblock {} ended at the start of {}", workblock_id, today);
+
+        let continued_id = match continuation_minutes(planned_end, crate::faults::now()) {
+            Some(duration_minutes) => {
+                let continued = crate::db::create_workblock(&self.app, duration_minutes)?;
+                let continued_id = continued.id.unwrap();
+                if workblock.title.is_some() {
+                    crate::db::update_workblock_title(&self.app, continued_id, workblock.title.as_deref())?;
+                }
+                if workblock.project.is_some() {
+                    crate::db::set_workblock_project(&self.app, continued_id, workblock.project.as_deref())?;
+                }
+                self.start_workblock(continued_id, duration_minutes).await?;
+                println!("[TIMER] Workblock {} continues as {} for {} minutes", workblock_id, continued_id, duration_minutes);
+                Some(continued_id)
+            }
+            None => None,
+        };
+
+        if let Some((interval_id, interval_number)) = cut_interval {
+            let _ = emit_event(&self.app, AppEvent::IntervalComplete(IntervalComplete {
+                workblock_id,
+                interval_id,
+                interval_number,
+            }));
+            let mut state = self.state.lock().await;
+            state.prompt_shown_time = Some(Local::now());
+            state.prompted_interval_id = Some(interval_id);
+        }
+
+        Ok(Some((workblock_id, continued_id)))
+    }
+
+    /// Start the auto-away timer (configured timeout after prompt is shown, 10 minutes by default)
+    pub async fn start_auto_away_timer(&self, interval_id: i64) -> Result<(), Log15Error> {
         // Cancel any existing auto-away timer
         if let Some(handle) = self.auto_away_handle.lock().await.take() {
             handle.abort();
         }
         
+        self.state.lock().await.auto_away_pending = Some(AutoAwayCountdown { interval_id, since: Local::now() });
+        let timeout = Duration::from_secs(get_auto_away_settings(&self.app).timeout_seconds);
+        let handle = spawn_auto_away(
+            self.app.clone(),
+            Arc::clone(&self.state),
+            Arc::clone(&self.interval_handle),
+            Arc::clone(&self.active_break),
+            interval_id,
+            timeout,
+        );
+        *self.auto_away_handle.lock().await = Some(handle);
+        
+        Ok(())
+    }
+
+    /// Restart a pending auto-away countdown with the configured timeout, keeping the time
+    /// already waited (after the timeout setting changed). A snoozed prompt picks it up when it returns.
+    pub async fn rearm_auto_away(&self) {
+        let state = self.state.lock().await;
+        let (Some(AutoAwayCountdown { interval_id, since }), None) = (state.auto_away_pending.clone(), state.snoozed_until) else {
+            return;
+        };
+        drop(state);
+        
+        let mut auto_away_handle = self.auto_away_handle.lock().await;
+        match auto_away_handle.take() {
+            Some(handle) if !handle.is_finished() => handle.abort(),
+            _ => return, // Nothing pending: the words were recorded or auto-away already fired
+        }
+        let timeout_seconds = get_auto_away_settings(&self.app).timeout_seconds;
+        let wait = auto_away_wait(timeout_seconds, since, Local::now());
+        println!("[TIMER] Auto-away timeout is now {}s: interval {} goes away in {}s", timeout_seconds, interval_id, wait.as_secs());
+        *auto_away_handle = Some(spawn_auto_away(
+            self.app.clone(),
+            Arc::clone(&self.state),
+            Arc::clone(&self.interval_handle),
+            Arc::clone(&self.active_break),
+            interval_id,
+            wait,
+        ));
+    }
+
+    /// Snooze the prompt: hide it now, re-show it after the snooze and only then restart auto-away
+    pub async fn snooze_prompt(&self, interval_id: i64, snooze_seconds: u64) -> Result<(), Log15Error> {
+        // The pending auto-away is replaced by the snooze
+        if let Some(handle) = self.auto_away_handle.lock().await.take() {
+            handle.abort();
+        }
+        
+        self.state.lock().await.snoozed_until = Some(Local::now() + chrono::Duration::seconds(snooze_seconds as i64));
+        
         let app_clone = self.app.clone();
         let state_clone = Arc::clone(&self.state);
         let interval_handle_clone = Arc::clone(&self.interval_handle);
+        let auto_away_handle_clone = Arc::clone(&self.auto_away_handle);
+        let active_break_clone = Arc::clone(&self.active_break);
         
         let handle = tokio::spawn(async move {
-            // TESTING: 5 seconds instead of 10 minutes
-            tokio::time::sleep(Duration::from_secs(5)).await; // TESTING: Changed from 10 * 60
+            tokio::time::sleep(Duration::from_secs(snooze_seconds)).await;
+            state_clone.lock().await.snoozed_until = None;
             
-            // Check if the specific interval still has no recorded words
-            if let Ok(interval) = get_interval_by_id(&app_clone, interval_id) {
-                if interval.words.is_none() {
-                    // Auto-away: record "Away from workspace"
-                    let _ = update_interval_words(
-                        &app_clone,
-                        interval_id,
-                        "Away from workspace".to_string(),
-                        IntervalStatus::AutoAway,
-                    );
-                    
-                    // Hide prompt window - emit events that frontend will handle
-                    println!("[TIMER] Auto-away: Recording 'Away from workspace' for interval {}", interval_id);
-                    
-                    // Emit auto-away event (PromptWindow listens for this)
-                    let _ = app_clone.emit("auto-away", interval_id);
-                    
-                    // Also emit prompt-hide to ensure window closes
-                    let _ = app_clone.emit("prompt-hide", ());
-                    
-                    // Call hide command directly to ensure window closes
-                    // Note: We use try_state which returns Option, and Tauri uses async_runtime::Mutex
-                    if let Some(window_mgr_state) = app_clone.try_state::<Arc<tauri::async_runtime::Mutex<WindowManager>>>() {
-                        let window_mgr = window_mgr_state.lock().await;
-                        let _ = window_mgr.hide_prompt_window().await;
-                        println!("[TIMER] Auto-away: Called hide_prompt_window");
-                    }
-
-                    // If this was the last interval, finalize the workblock now.
-                    // (Timer loop intentionally does not complete the workblock on the last tick.)
-                    if let Ok(workblock) = get_workblock_by_id(&app_clone, interval.workblock_id) {
-                        let total_intervals = workblock.duration_minutes.unwrap_or(60) * 6; // TESTING
-                        let is_last_interval = interval.interval_number >= total_intervals;
-
-                        if is_last_interval {
-                            println!(
-                                "[TIMER] Auto-away on final interval; completing workblock_id={}",
-                                interval.workblock_id
-                            );
-
-                            let _ = complete_workblock(&app_clone, interval.workblock_id);
-                            let _ = app_clone.emit("workblock-complete", interval.workblock_id);
-
-                            // Update tray state to SummaryReady
-                            if let Some(tray_mgr_state) = app_clone.try_state::<Arc<Mutex<TrayManager>>>() {
-                                let mut tray = tray_mgr_state.lock().await;
-                                tray.update_icon_state(TrayIconState::SummaryReady).await;
-                            }
-
-                            // Reset timer state
-                            let mut state = state_clone.lock().await;
-                            *state = TimerState::default();
-                            drop(state);
-
-                            // Stop interval ticking task if it still exists
-                            if let Some(h) = interval_handle_clone.lock().await.take() {
-                                h.abort();
-                            }
-                        }
-                    }
+            // Words may have been submitted from elsewhere (e.g. the main window) in the meantime
+            match get_interval_by_id(&app_clone, interval_id) {
+                Ok(interval) if interval.words.is_none() => {}
+                _ => return,
+            }
+            
+            println!("[TIMER] Snooze over, re-showing prompt for interval {}", interval_id);
+            if let Some(window_mgr_state) = app_clone.try_state::<Arc<tauri::async_runtime::Mutex<WindowManager>>>() {
+                let window_mgr = window_mgr_state.lock().await;
+                match window_mgr.show_or_queue_prompt(interval_id).await {
+                    // Another prompt came up meanwhile; this one arms auto-away when its turn comes
+                    Ok(false) => return,
+                    Ok(true) => {}
+                    Err(e) => eprintln!("[TIMER] Failed to re-show prompt after snooze: {}", e),
                 }
             }
+            
+            // Replace this task's handle with the new auto-away task (dropping a JoinHandle does not abort it)
+            state_clone.lock().await.auto_away_pending = Some(AutoAwayCountdown { interval_id, since: Local::now() });
+            let timeout = Duration::from_secs(get_auto_away_settings(&app_clone).timeout_seconds);
+            let auto_away = spawn_auto_away(app_clone, state_clone, interval_handle_clone, active_break_clone, interval_id, timeout);
+            *auto_away_handle_clone.lock().await = Some(auto_away);
         });
         
         *self.auto_away_handle.lock().await = Some(handle);
@@ -339,6 +879,97 @@ impl TimerManager {
         if let Some(handle) = self.auto_away_handle.lock().await.take() {
             handle.abort();
         }
+        self.state.lock().await.auto_away_pending = None;
+    }
+
+    /// End the running break early; returns false if there was none
+    pub async fn end_break(&self) -> bool {
+        let Some(active) = self.active_break.lock().await.take() else {
+            return false;
+        };
+        active.handle.abort();
+        println!("[TIMER] Break after workblock {} ended early", active.info.workblock_id);
+        finish_break(&self.app, active.info.workblock_id, true).await;
+        true
+    }
+
+    /// The break currently running, if any
+    pub async fn get_break(&self) -> Option<BreakInfo> {
+        self.active_break.lock().await.as_ref().map(|b| b.info.clone())
+    }
+
+    /// Subscribe to "timer-tick" events (and "status-page" whenever the status changes); the ticker
+    /// runs while at least one subscriber remains.
+    /// Returns the number of subscribers.
+    pub async fn start_ticker(&self) -> usize {
+        let mut ticker = self.ticker.lock().await;
+        if let Some(ticker) = ticker.as_mut() {
+            ticker.subscribers += 1;
+            return ticker.subscribers;
+        }
+        
+        let state_clone = Arc::clone(&self.state);
+        let active_break = Arc::clone(&self.active_break);
+        let app_clone = self.app.clone();
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut last_status = None;
+            loop {
+                interval.tick().await;
+                let state = state_clone.lock().await.clone();
+                if let Some(tick) = compute_tick(&state, Local::now()) {
+                    let _ = emit_event(&app_clone, AppEvent::TimerTick(tick));
+                }
+                
+                // The status page only changes when a block, break or pause starts or ends
+                let break_info = active_break.lock().await.as_ref().map(|b| b.info.clone());
+                let status = crate::status_page::status_page_for(&app_clone, &state, break_info.as_ref());
+                if last_status.as_ref() != Some(&status) {
+                    let _ = emit_event(&app_clone, AppEvent::StatusPage(status.clone()));
+                    last_status = Some(status);
+                }
+            }
+        });
+        *ticker = Some(Ticker { handle, subscribers: 1 });
+        println!("[TIMER] Ticker started");
+        1
+    }
+
+    /// Drop one ticker subscription; the ticker stops when none are left. Returns the remaining subscribers.
+    pub async fn stop_ticker(&self) -> usize {
+        let mut ticker = self.ticker.lock().await;
+        let remaining = match ticker.as_mut() {
+            Some(t) => {
+                t.subscribers = t.subscribers.saturating_sub(1);
+                t.subscribers
+            }
+            None => return 0,
+        };
+        if remaining == 0 {
+            if let Some(t) = ticker.take() {
+                t.handle.abort();
+                println!("[TIMER] Ticker stopped");
+            }
+        }
+        remaining
+    }
+
+    /// Stop the interval loop, auto-away countdown and break, and forget the workblock without
+    /// touching the database (used when all data is wiped). Ticker subscriptions are kept.
+    pub async fn reset(&self) {
+        if let Some(handle) = self.interval_handle.lock().await.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.auto_away_handle.lock().await.take() {
+            handle.abort();
+        }
+        if let Some(active) = self.active_break.lock().await.take() {
+            active.handle.abort();
+        }
+        *self.wellbeing_alerted_on.lock().await = None;
+        *self.state.lock().await = TimerState::default();
+        println!("[TIMER] Timer reset");
     }
 
     /// Get current timer state
@@ -350,17 +981,13 @@ impl TimerManager {
     pub async fn get_interval_time_remaining(&self) -> Option<i64> {
         let state = self.state.lock().await;
         
-        if let Some(start_time) = state.interval_start_time {
-            let elapsed = (Local::now() - start_time).num_seconds();
-            let remaining = 10 - elapsed; // TESTING: 10 seconds (normally 15 * 60 = 900)
-            Some(remaining.max(0))
-        } else {
-            None
-        }
+        state.interval_start_time.map(|start_time| {
+            remaining_seconds(interval_schedule::interval_end(start_time, interval_seconds()), Local::now())
+        })
     }
 
     /// Check if there's an active workblock and restore timer if needed
-    pub async fn restore_active_workblock(&self) -> Result<(), String> {
+    pub async fn restore_active_workblock(&self) -> Result<(), Log15Error> {
         // Check database for active workblock
         match get_active_workblock(&self.app) {
             Ok(Some(workblock)) => {
@@ -383,8 +1010,7 @@ impl TimerManager {
                     
                     // Calculate remaining intervals
                     let elapsed_intervals = current_interval.interval_number;
-                    // TESTING: 10-second intervals (duration_minutes * 6 per minute)
-                    let total_intervals = duration * 6; // TESTING: Changed from duration / 15
+                    let total_intervals = interval_schedule::total_intervals(duration, interval_seconds());
                     let remaining_intervals = total_intervals - elapsed_intervals;
                     
                     if remaining_intervals > 0 {
@@ -404,10 +1030,384 @@ impl TimerManager {
                 *state = TimerState::default();
             }
             Err(e) => {
-                return Err(format!("Failed to get active workblock: {}", e));
+                return Err(e);
             }
         }
         
         Ok(())
     }
 }
+
+/// Sleep until the wall clock reaches `target`, waking periodically to notice a system sleep
+pub(crate) async fn sleep_until_wall_clock(target: DateTime<Local>) {
+    loop {
+        let remaining_ms = (target - crate::faults::now()).num_milliseconds();
+        if remaining_ms <= 0 {
+            return;
+        }
+        let chunk_ms = remaining_ms.min(WALL_CLOCK_CHECK_SECONDS * 1000) as u64;
+        tokio::time::sleep(Duration::from_millis(chunk_ms)).await;
+    }
+}
+
+/// Record a workblock as completed at `end` and tell the frontend, webhooks and tray. Every way a
+/// workblock completes goes through here.
+async fn record_workblock_completed(app: &AppHandle, workblock_id: i64, end: DateTime<Utc>) -> Result<(), Log15Error> {
+    crate::db::complete_workblock_at(app, workblock_id, end)?;
+    let _ = emit_event(app, AppEvent::WorkblockComplete(workblock_id));
+    webhooks::dispatch_workblock_event(app, WebhookEvent::WorkblockCompleted, workblock_id);
+    if let Some(tray_mgr_state) = app.try_state::<Arc<Mutex<TrayManager>>>() {
+        tray_mgr_state.lock().await.refresh_today_totals();
+    }
+    Ok(())
+}
+
+/// Roll over to the new day while the app is running, instead of waiting for the next start or
+/// workblock. The wait for the next day start is recalculated at least once a minute, so a system
+/// sleep or a changed day start hour is noticed.
+pub fn spawn_day_rollover_watcher(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut date = logical_date(crate::faults::now(), crate::settings::day_start_hour());
+        loop {
+            let now = crate::faults::now();
+            let day_start_hour = crate::settings::day_start_hour();
+            let next_day_start = day_start_time(logical_date(now, day_start_hour) + chrono::Duration::days(1), day_start_hour);
+            sleep_until_wall_clock(next_day_start.min(now + chrono::Duration::minutes(1))).await;
+
+            let today = logical_date(crate::faults::now(), crate::settings::day_start_hour());
+            if today > date {
+                roll_over_day(&app, date, today).await;
+            }
+            date = today;
+        }
+    });
+}
+
+/// Split the running workblock at the start of `today`, archive the previous day and emit "day-rolled-over"
+async fn roll_over_day(app: &AppHandle, previous_date: chrono::NaiveDate, today: chrono::NaiveDate) {
+    let date = today.format("%Y-%m-%d").to_string();
+    let day_start = day_start_time(today, crate::settings::day_start_hour());
+    println!("[TIMER] Day rolled over from {} to {}", previous_date, date);
+
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let split = timer_manager.lock().await.split_at_day_start(&date, day_start).await;
+    let (ended_workblock_id, continued_workblock_id) = match split {
+        Ok(Some((ended, continued))) => (Some(ended), continued),
+        Ok(None) => (None, None),
+        Err(e) => {
+            eprintln!("[TIMER] Failed to split the workblock at the day start: {}", e);
+            (None, None)
+        }
+    };
+
+    let archived_date = check_and_reset_daily(app).unwrap_or_else(|e| {
+        eprintln!("[TIMER] Failed to archive the previous day: {}", e);
+        None
+    });
+    if let Some(tray_mgr_state) = app.try_state::<Arc<Mutex<TrayManager>>>() {
+        tray_mgr_state.lock().await.refresh_today_totals();
+    }
+
+    let _ = emit_event(app, AppEvent::DayRolledOver(DayRolledOver {
+        previous_date: previous_date.format("%Y-%m-%d").to_string(),
+        date,
+        archived_date,
+        ended_workblock_id,
+        continued_workblock_id,
+    }));
+}
+
+/// Sleep until the interval warning is due and emit "interval-ending-soon", unless the warning is off,
+/// already past, or the timer moved on in the meantime
+async fn warn_before_boundary(
+    app: &AppHandle,
+    state: &Arc<Mutex<TimerState>>,
+    interval_number: i32,
+    boundary: DateTime<Local>,
+) {
+    let warning = get_interval_warning_settings(app);
+    let Some(warn_at) = interval_schedule::warning_time(boundary, warning.seconds_before, interval_seconds()) else {
+        return;
+    };
+    if warn_at <= crate::faults::now() {
+        return;
+    }
+    sleep_until_wall_clock(warn_at).await;
+    
+    let state = state.lock().await;
+    let Some(workblock_id) = state.workblock_id.filter(|_| state.is_running) else {
+        return;
+    };
+    if state.current_interval_number != interval_number {
+        return;
+    }
+    let interval_id = state.current_interval_id;
+    drop(state);
+    
+    let _ = emit_event(app, AppEvent::IntervalEndingSoon(IntervalEndingSoon {
+        workblock_id,
+        interval_id,
+        interval_number,
+        ends_at: boundary.to_rfc3339(),
+        seconds_left: remaining_seconds(boundary, crate::faults::now()),
+        notification: warning.notification,
+    }));
+}
+
+/// Record intervals `first..=last` as away for `cause`, pinned to their wall-clock bounds.
+/// `first_interval_id` is the already-created interval `first`; the rest are created here.
+fn record_missed_intervals(
+    app: &AppHandle,
+    workblock_id: i64,
+    first_interval_id: Option<i64>,
+    schedule_start: DateTime<Local>,
+    first: i32,
+    last: i32,
+    cause: AwayCause,
+) -> Vec<i64> {
+    let message = match cause {
+        AwayCause::SystemSleep => MISSED_INTERVAL_MESSAGE.to_string(),
+        _ => get_auto_away_settings(app).message,
+    };
+    let mut interval_ids = Vec::new();
+    for interval_number in first..=last {
+        let interval_id = match (interval_number == first, first_interval_id) {
+            // Keep words recorded early (e.g. via the open-prompt hotkey) before the sleep
+            (true, Some(id)) if get_interval_by_id(app, id).is_ok_and(|i| i.words.is_some()) => continue,
+            (true, Some(id)) => id,
+            _ => match add_interval(app, workblock_id, interval_number) {
+                Ok(interval) => interval.id.unwrap(),
+                Err(e) => {
+                    eprintln!("[TIMER] Failed to create missed interval {}: {}", interval_number, e);
+                    continue;
+                }
+            },
+        };
+        let _ = record_away_interval(app, interval_id, message.clone(), cause);
+        let _ = set_interval_bounds(
+            app,
+            interval_id,
+            &to_timestamp(interval_boundary(schedule_start, interval_number - 1)),
+            &to_timestamp(interval_boundary(schedule_start, interval_number)),
+        );
+        interval_ids.push(interval_id);
+    }
+    interval_ids
+}
+
+/// Emit "wellbeing-limit-exceeded" the first time today's tracked time passes the limit
+async fn alert_if_over_daily_limit(app: &AppHandle, alerted_on: &Arc<Mutex<Option<String>>>) {
+    let alert = match crate::stats::check_wellbeing(app) {
+        Ok(Some(alert)) => alert,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("[TIMER] Wellbeing check failed: {}", e);
+            return;
+        }
+    };
+    let mut alerted_on = alerted_on.lock().await;
+    if alerted_on.as_deref() == Some(alert.date.as_str()) {
+        return;
+    }
+    println!(
+        "[TIMER] Tracked {} minutes today, past the {} minute limit",
+        alert.tracked_minutes, alert.daily_limit_minutes
+    );
+    *alerted_on = Some(alert.date.clone());
+    let _ = emit_event(app, AppEvent::WellbeingLimitExceeded(alert.clone()));
+    crate::stats::announce_milestones(app, vec![alert.into()]);
+}
+
+/// Start a break after `workblock_id` completed, if Pomodoro mode is on.
+/// When the break runs out the overlay closes and, if configured, the next workblock starts.
+async fn start_break(
+    app: &AppHandle,
+    active_break: &Arc<Mutex<Option<ActiveBreak>>>,
+    workblock_id: i64,
+    next_duration_minutes: i32,
+) {
+    let pomodoro = get_pomodoro_settings(app);
+    if !pomodoro.enabled {
+        return;
+    }
+    
+    let started_at = Local::now();
+    let ends_at = started_at + chrono::Duration::seconds(pomodoro.break_seconds as i64);
+    let info = BreakInfo {
+        workblock_id,
+        break_seconds: pomodoro.break_seconds,
+        started_at: started_at.to_rfc3339(),
+        ends_at: ends_at.to_rfc3339(),
+        next_duration_minutes,
+    };
+    println!("[TIMER] Starting {}s break after workblock {}", pomodoro.break_seconds, workblock_id);
+    let _ = emit_event(app, AppEvent::BreakStarted(info.clone()));
+    if let Some(window_mgr_state) = app.try_state::<Arc<tauri::async_runtime::Mutex<WindowManager>>>() {
+        let window_mgr = window_mgr_state.lock().await;
+        if let Err(e) = window_mgr.show_break_window(pomodoro.break_seconds).await {
+            eprintln!("[TIMER] Failed to show break window: {}", e);
+        }
+    }
+    
+    let app_clone = app.clone();
+    let active_break_clone = Arc::clone(active_break);
+    let handle = tokio::spawn(async move {
+        sleep_until_wall_clock(ends_at).await;
+        
+        // Taking the break out of the slot means a concurrent end_break can no longer abort this task
+        if active_break_clone.lock().await.take().is_none() {
+            return;
+        }
+        finish_break(&app_clone, workblock_id, false).await;
+        
+        if pomodoro.auto_start_next {
+            println!("[TIMER] Break over, auto-starting a {} minute workblock", next_duration_minutes);
+            if let Err(e) = crate::start_workblock(app_clone.clone(), next_duration_minutes, None).await {
+                eprintln!("[TIMER] Failed to auto-start next workblock: {}", e);
+            }
+        }
+    });
+    
+    // Replace (and stop) a break that was somehow still running
+    if let Some(previous) = active_break.lock().await.replace(ActiveBreak { info, handle }) {
+        previous.handle.abort();
+    }
+}
+
+/// Announce the end of a break and close its overlay
+async fn finish_break(app: &AppHandle, workblock_id: i64, skipped: bool) {
+    let _ = emit_event(app, AppEvent::BreakEnded(BreakEnded { workblock_id, skipped }));
+    if let Some(window_mgr_state) = app.try_state::<Arc<tauri::async_runtime::Mutex<WindowManager>>>() {
+        let window_mgr = window_mgr_state.lock().await;
+        let _ = window_mgr.hide_break_window().await;
+    }
+}
+
+/// Time left of an auto-away countdown of `timeout_seconds` that started at `since`
+pub fn auto_away_wait(timeout_seconds: u64, since: DateTime<Local>, now: DateTime<Local>) -> Duration {
+    let waited = (now - since).num_seconds().max(0) as u64;
+    Duration::from_secs(timeout_seconds.saturating_sub(waited))
+}
+
+/// What the auto-away timeout recorded for an interval that was still unanswered
+#[derive(Debug, Clone)]
+pub struct AutoAwayRecorded {
+    pub interval: Interval,
+    pub planned_minutes: i32,
+    pub completes_workblock: bool, // It was the active workblock's last interval, so the workblock ends now
+}
+
+/// The database side of the auto-away timeout: record `message` on the interval unless it was
+/// answered meanwhile (then None). The self-test runs this same step against its own database.
+pub fn store_auto_away(
+    conn: &Connection,
+    interval_id: i64,
+    message: &str,
+    at: DateTime<Utc>,
+) -> Result<Option<AutoAwayRecorded>, Log15Error> {
+    if query_interval_by_id(conn, interval_id)?.words.is_some() {
+        return Ok(None);
+    }
+    store_interval_words(conn, interval_id, message, IntervalStatus::AutoAway, at)?;
+    store_away_cause(conn, interval_id, AwayCause::PromptTimeout)?;
+    let interval = query_interval_by_id(conn, interval_id)?;
+    
+    let workblock = query_workblock_by_id(conn, interval.workblock_id)?;
+    let planned_minutes = workblock.duration_minutes.unwrap_or(60);
+    let total_intervals = interval_schedule::total_intervals(planned_minutes, interval_seconds());
+    // A workblock split at the day start was already completed there
+    let completes_workblock = workblock.status == WorkblockStatus::Active
+        && interval_schedule::is_last_interval(interval.interval_number, total_intervals);
+    Ok(Some(AutoAwayRecorded { interval, planned_minutes, completes_workblock }))
+}
+
+/// Apply setting changes to the running timer: a pending auto-away countdown takes a new timeout.
+/// Interval alignment and Pomodoro settings are read when a workblock or break starts.
+pub fn spawn_settings_listener(app: &AppHandle) {
+    crate::settings::spawn_settings_listener("timer", app, |app, change| async move {
+        if !change.key.is_empty() && change.key != AUTO_AWAY_TIMEOUT_KEY {
+            return;
+        }
+        if let Some(timer_manager) = app.try_state::<Arc<Mutex<TimerManager>>>() {
+            timer_manager.lock().await.rearm_auto_away().await;
+        }
+    });
+}
+
+/// Spawn the task that records the configured away message if the interval is still unanswered after `wait`
+fn spawn_auto_away(
+    app: AppHandle,
+    timer_state: Arc<Mutex<TimerState>>,
+    interval_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    active_break: Arc<Mutex<Option<ActiveBreak>>>,
+    interval_id: i64,
+    wait: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        tokio::time::sleep(wait).await;
+        // Read now, so a message changed during the countdown is used
+        let auto_away = get_auto_away_settings(&app);
+        timer_state.lock().await.auto_away_pending = None;
+        
+        // Record the away message if the interval still has no recorded words
+        let recorded = crate::db::get_db_connection(&app)
+            .and_then(|conn| store_auto_away(&conn, interval_id, &auto_away.message, Utc::now()));
+        match recorded {
+            Ok(None) => {}
+            Err(e) => eprintln!("[TIMER] Auto-away failed for interval {}: {}", interval_id, e),
+            Ok(Some(recorded)) => {
+                crate::cache::interval_written(&app, interval_id);
+                let interval = recorded.interval;
+                
+                // Hide prompt window - emit events that frontend will handle
+                println!("[TIMER] Auto-away: Recording '{}' for interval {}", auto_away.message, interval_id);
+                
+                // Emit auto-away event (PromptWindow listens for this)
+                let _ = emit_event(&app, AppEvent::AutoAway(interval_id));
+                
+                // Also emit prompt-hide to ensure window closes
+                let _ = emit_event(&app, AppEvent::PromptHide);
+                
+                // Call hide command directly to ensure window closes
+                // Note: We use try_state which returns Option, and Tauri uses async_runtime::Mutex
+                if let Some(window_mgr_state) = app.try_state::<Arc<tauri::async_runtime::Mutex<WindowManager>>>() {
+                    let window_mgr = window_mgr_state.lock().await;
+                    let _ = window_mgr.hide_prompt_window().await;
+                    println!("[TIMER] Auto-away: Called hide_prompt_window");
+                }
+
+                // If this was the last interval, finalize the workblock now.
+                // (Timer loop intentionally does not complete the workblock on the last tick.)
+                if recorded.completes_workblock {
+                    println!(
+                        "[TIMER] Auto-away on final interval; completing workblock_id={}",
+                        interval.workblock_id
+                    );
+
+                    let _ = record_workblock_completed(&app, interval.workblock_id, Utc::now()).await;
+
+                    // Update tray state to SummaryReady
+                    if let Some(tray_mgr_state) = app.try_state::<Arc<Mutex<TrayManager>>>() {
+                        tray_mgr_state.lock().await.update_icon_state(TrayIconState::SummaryReady).await;
+                    }
+
+                    // Reset timer state
+                    let mut state = timer_state.lock().await;
+                    *state = TimerState::default();
+                    drop(state);
+
+                    // Stop interval ticking task if it still exists
+                    if let Some(h) = interval_handle.lock().await.take() {
+                        h.abort();
+                    }
+                    
+                    start_break(&app, &active_break, interval.workblock_id, recorded.planned_minutes).await;
+                }
+                
+                // Move on to a prompt queued behind this one; its auto-away replaces this task's handle
+                tauri::async_runtime::spawn(async move { crate::show_next_queued_prompt(&app).await });
+            }
+        }
+    })
+}
@@ -0,0 +1,254 @@
+// Timer self-test: runs a short synthetic workblock on a fake clock against an in-memory
+// database, so scheduling, auto-away and completion can be checked on a user's machine
+// without touching their data or waiting for real intervals.
+
+use crate::db::{
+    create_schema, query_intervals_by_workblock, query_workblock_by_id, store_interval_words,
+    store_workblock_end, to_timestamp, Interval, IntervalStatus, WorkblockStatus,
+};
+use crate::interval_schedule::{interval_seconds, planned_minutes, total_intervals};
+use crate::settings::AutoAwaySettings;
+use crate::timer::{compute_tick, interval_boundary, intervals_elapsed, plan_workblock, store_auto_away, TimerState};
+use chrono::{DateTime, Local, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Intervals in the synthetic workblock (a few more if they don't add up to whole minutes)
+pub const SELFTEST_INTERVALS: i32 = 6;
+
+/// How late a real wall-clock wait may wake before the check fails
+pub const MAX_WAKE_DELAY_MS: i64 = 1000;
+
+const WALL_CLOCK_WAIT_MS: i64 = 300;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SelftestCheck {
+    pub name: String,  // "tick_scheduling", "missed_intervals", "auto_away", "completion" or "wall_clock_wait"
+    pub passed: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SelftestReport {
+    pub passed: bool,
+    pub checks: Vec<SelftestCheck>,
+    pub ran_at: String,
+    pub elapsed_ms: i64,
+}
+
+/// A clock that only moves when told to
+pub struct FakeClock {
+    now: DateTime<Local>,
+}
+
+impl FakeClock {
+    pub fn new(now: DateTime<Local>) -> Self {
+        Self { now }
+    }
+    
+    pub fn now(&self) -> DateTime<Local> {
+        self.now
+    }
+    
+    pub fn advance(&mut self, seconds: i64) {
+        self.now += chrono::Duration::seconds(seconds);
+    }
+}
+
+/// Length of the synthetic workblock under the configured interval length
+fn selftest_workblock_minutes() -> i32 {
+    planned_minutes(SELFTEST_INTERVALS, interval_seconds())
+}
+
+fn check(name: &str, result: Result<String, String>) -> SelftestCheck {
+    let (passed, detail) = match result {
+        Ok(detail) => (true, detail),
+        Err(detail) => (false, detail),
+    };
+    SelftestCheck { name: name.to_string(), passed, detail }
+}
+
+/// Step the fake clock second by second through a workblock started at `start`: every interval
+/// must end exactly on its boundary, and the countdown must reach zero when the last one ends
+fn check_tick_scheduling(start: DateTime<Local>) -> Result<String, String> {
+    let total = total_intervals(selftest_workblock_minutes(), interval_seconds());
+    let plan = plan_workblock(start, selftest_workblock_minutes(), interval_seconds(), false);
+    if plan.intervals.len() as i32 != total {
+        return Err(format!("Planned {} intervals, expected {}", plan.intervals.len(), total));
+    }
+    
+    let mut clock = FakeClock::new(start);
+    let mut state = TimerState {
+        workblock_id: Some(1),
+        current_interval_number: 1,
+        interval_start_time: Some(start),
+        is_running: true,
+        total_intervals: total,
+        ..Default::default()
+    };
+    let mut fired = 0;
+    while fired < total {
+        clock.advance(1);
+        let tick = compute_tick(&state, clock.now()).ok_or("No countdown while running")?;
+        let boundary = interval_boundary(start, state.current_interval_number);
+        if tick.interval_remaining_seconds != (boundary - clock.now()).num_seconds().max(0) {
+            return Err(format!("Countdown off at interval {}", state.current_interval_number));
+        }
+    
+        let ended = intervals_elapsed(start, clock.now());
+        if ended > fired {
+            fired += 1;
+            if ended != fired || clock.now() != boundary {
+                return Err(format!("Interval {} ended at the wrong time", fired));
+            }
+            if plan.intervals[fired as usize - 1].end_time != boundary.to_rfc3339() {
+                return Err(format!("Plan and timer disagree on the end of interval {}", fired));
+            }
+            if fired < total {
+                state.current_interval_number = fired + 1;
+                state.interval_start_time = Some(boundary);
+            } else if tick.workblock_remaining_seconds != 0 {
+                return Err("Workblock countdown didn't reach zero at the last boundary".to_string());
+            }
+        }
+    }
+    Ok(format!("{} intervals of {}s fired on their boundaries", total, interval_seconds()))
+}
+
+/// Jump the fake clock as a system sleep would: the intervals that ended meanwhile are all noticed
+fn check_missed_intervals(start: DateTime<Local>) -> Result<String, String> {
+    let mut clock = FakeClock::new(start);
+    clock.advance(interval_seconds() / 2);
+    clock.advance(interval_seconds() * 3);
+    match intervals_elapsed(start, clock.now()) {
+        3 => Ok("A jump over 3 boundaries was reconciled".to_string()),
+        ended => Err(format!("A jump over 3 boundaries reported {} ended intervals", ended)),
+    }
+}
+
+/// Run the synthetic workblock in `conn`: every interval is answered except the last, which goes
+/// unanswered past the auto-away timeout; the timer's own timeout step records the away message and
+/// decides that the workblock completes.
+/// Returns the auto-away and completion checks.
+pub fn simulate_workblock(
+    conn: &Connection,
+    start: DateTime<Local>,
+    auto_away: &AutoAwaySettings,
+) -> rusqlite::Result<(Result<String, String>, Result<String, String>)> {
+    let total = total_intervals(selftest_workblock_minutes(), interval_seconds());
+    let mut clock = FakeClock::new(start);
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, planned_duration_minutes, status, is_archived)
+         VALUES (?1, ?2, ?3, ?3, ?4, 0)",
+        params![
+            start.format("%Y-%m-%d").to_string(),
+            to_timestamp(start),
+            selftest_workblock_minutes(),
+            WorkblockStatus::Active.as_str()
+        ],
+    )?;
+    let workblock_id = conn.last_insert_rowid();
+    
+    for interval_number in 1..=total {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, status) VALUES (?1, ?2, ?3, 'pending')",
+            params![workblock_id, interval_number, to_timestamp(clock.now())],
+        )?;
+        let interval_id = conn.last_insert_rowid();
+        clock.advance(interval_seconds());
+        if interval_number < total {
+            store_interval_words(conn, interval_id, "Self-test", IntervalStatus::Recorded, clock.now().with_timezone(&Utc))?;
+        }
+    }
+    
+    // The final prompt goes unanswered until the auto-away timeout runs out, which runs the real timeout step
+    clock.advance(auto_away.timeout_seconds as i64);
+    let last = query_intervals_by_workblock(conn, workblock_id)?
+        .into_iter()
+        .find(|i| i.interval_number == total);
+    let recorded = match last {
+        Some(Interval { id: Some(id), words: None, .. }) => {
+            store_auto_away(conn, id, &auto_away.message, clock.now().with_timezone(&Utc))?
+        }
+        _ => None,
+    };
+    let auto_away_check = match &recorded {
+        Some(r) if r.interval.status == IntervalStatus::AutoAway && r.interval.words.as_deref() == Some(auto_away.message.as_str()) => {
+            Ok(format!("Unanswered final prompt recorded as away after {}s", auto_away.timeout_seconds))
+        }
+        Some(_) => Err("The away message wasn't recorded".to_string()),
+        None => Err("The final interval wasn't left unanswered".to_string()),
+    };
+    if !recorded.is_some_and(|r| r.completes_workblock) {
+        return Ok((auto_away_check, Err("Auto-away on the final interval didn't end the workblock".to_string())));
+    }
+    
+    // What the timeout task does next, minus the events
+    let workblock = store_workblock_end(conn, workblock_id, WorkblockStatus::Completed, clock.now().with_timezone(&Utc))?;
+    let expected_minutes = ((total as i64 * interval_seconds() + auto_away.timeout_seconds as i64) / 60) as i32;
+    let stored = query_workblock_by_id(conn, workblock_id)?;
+    let completion_check = if stored.status != WorkblockStatus::Completed {
+        Err(format!("Workblock ended as {}", stored.status.as_str()))
+    } else if workblock.duration_minutes != Some(expected_minutes) {
+        Err(format!("Workblock lasted {:?} minutes, expected {}", workblock.duration_minutes, expected_minutes))
+    } else {
+        Ok(format!("Workblock completed after {} minutes", expected_minutes))
+    };
+    Ok((auto_away_check, completion_check))
+}
+
+/// Checks that only need the fake clock and an in-memory database
+pub fn run_simulated_checks(start: DateTime<Local>, auto_away: &AutoAwaySettings) -> Vec<SelftestCheck> {
+    let mut checks = vec![
+        check("tick_scheduling", check_tick_scheduling(start)),
+        check("missed_intervals", check_missed_intervals(start)),
+    ];
+    let simulated = Connection::open_in_memory()
+        .and_then(|conn| Ok(create_schema(&conn).map(|_| conn)?))
+        .and_then(|conn| simulate_workblock(&conn, start, auto_away));
+    match simulated {
+        Ok((auto_away_check, completion_check)) => {
+            checks.push(check("auto_away", auto_away_check));
+            checks.push(check("completion", completion_check));
+        }
+        Err(e) => checks.push(check("auto_away", Err(format!("Simulation failed: {}", e)))),
+    }
+    checks
+}
+
+/// Wait briefly on the real wall clock, the way the interval loop does, and measure how late it wakes
+async fn check_wall_clock_wait() -> Result<String, String> {
+    let target = crate::faults::now() + chrono::Duration::milliseconds(WALL_CLOCK_WAIT_MS);
+    crate::timer::sleep_until_wall_clock(target).await;
+    let late_ms = (crate::faults::now() - target).num_milliseconds();
+    if late_ms > MAX_WAKE_DELAY_MS {
+        Err(format!("Woke {}ms late", late_ms))
+    } else {
+        Ok(format!("Woke {}ms after the target", late_ms))
+    }
+}
+
+/// Run every check; failures are also recorded as a diagnostics incident
+pub async fn run_timer_selftest(app: &AppHandle) -> SelftestReport {
+    let started = Local::now();
+    let auto_away = crate::settings::get_auto_away_settings(app);
+    let mut checks = run_simulated_checks(started, &auto_away);
+    checks.push(check("wall_clock_wait", check_wall_clock_wait().await));
+    
+    let passed = checks.iter().all(|c| c.passed);
+    if !passed {
+        let failures: Vec<String> = checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| format!("{}: {}", c.name, c.detail))
+            .collect();
+        crate::diagnostics::record_incident(app, "timer_selftest", &failures.join("; "));
+    }
+    SelftestReport {
+        passed,
+        checks,
+        ran_at: to_timestamp(started),
+        elapsed_ms: (Local::now() - started).num_milliseconds(),
+    }
+}
@@ -0,0 +1,123 @@
+// Timing profiles: the interval length, shortest workblock and default timeouts the app runs with.
+// Production uses real 15-minute intervals; fast-test runs everything at a 10-second cadence for
+// development and tests. The profile is picked once per process: LOG15_TIMING_PROFILE
+// ("production", "fast-test" or "custom") wins, otherwise the `fast-timing` feature selects
+// fast-test, otherwise production. A custom profile starts from production and takes any of
+// LOG15_INTERVAL_SECONDS, LOG15_MIN_WORKBLOCK_MINUTES, LOG15_AUTO_AWAY_SECONDS,
+// LOG15_SNOOZE_SECONDS, LOG15_IDLE_THRESHOLD_SECONDS and LOG15_BREAK_SECONDS that are set.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+pub const TIMING_PROFILE_ENV: &str = "LOG15_TIMING_PROFILE";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimingProfile {
+    Production,
+    FastTest,
+    Custom,
+}
+
+impl TimingProfile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimingProfile::Production => "production",
+            TimingProfile::FastTest => "fast-test",
+            TimingProfile::Custom => "custom",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim() {
+            "production" => Some(TimingProfile::Production),
+            "fast-test" => Some(TimingProfile::FastTest),
+            "custom" => Some(TimingProfile::Custom),
+            _ => None,
+        }
+    }
+
+    /// The profile a build uses when LOG15_TIMING_PROFILE isn't set
+    pub fn compiled_default() -> Self {
+        if cfg!(feature = "fast-timing") {
+            TimingProfile::FastTest
+        } else {
+            TimingProfile::Production
+        }
+    }
+}
+
+/// Durations that depend on the timing profile
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Timing {
+    pub profile: TimingProfile,
+    pub interval_seconds: i64,
+    pub min_workblock_minutes: i32,
+    pub auto_away_timeout_seconds: u64,  // Defaults for new installs; the settings hold the values in use
+    pub snooze_seconds: u64,
+    pub idle_threshold_seconds: u64,
+    pub break_seconds: u64,
+}
+
+impl Timing {
+    pub const PRODUCTION: Timing = Timing {
+        profile: TimingProfile::Production,
+        interval_seconds: 15 * 60,
+        min_workblock_minutes: 15,
+        auto_away_timeout_seconds: 10 * 60,
+        snooze_seconds: 2 * 60,
+        idle_threshold_seconds: 10 * 60,
+        break_seconds: 5 * 60,
+    };
+
+    pub const FAST_TEST: Timing = Timing {
+        profile: TimingProfile::FastTest,
+        interval_seconds: 10,
+        min_workblock_minutes: 1,
+        auto_away_timeout_seconds: 5,
+        snooze_seconds: 10,
+        idle_threshold_seconds: 30,
+        break_seconds: 20,
+    };
+
+    /// Production with the values `var` returns for the LOG15_* overrides
+    pub fn custom(var: impl Fn(&str) -> Option<String>) -> Timing {
+        fn parse<T: std::str::FromStr + PartialOrd + Default>(value: Option<String>, fallback: T) -> T {
+            value.and_then(|v| v.trim().parse().ok()).filter(|v| *v > T::default()).unwrap_or(fallback)
+        }
+        let base = Timing::PRODUCTION;
+        Timing {
+            profile: TimingProfile::Custom,
+            interval_seconds: parse(var("LOG15_INTERVAL_SECONDS"), base.interval_seconds),
+            min_workblock_minutes: parse(var("LOG15_MIN_WORKBLOCK_MINUTES"), base.min_workblock_minutes),
+            auto_away_timeout_seconds: parse(var("LOG15_AUTO_AWAY_SECONDS"), base.auto_away_timeout_seconds),
+            snooze_seconds: parse(var("LOG15_SNOOZE_SECONDS"), base.snooze_seconds),
+            idle_threshold_seconds: parse(var("LOG15_IDLE_THRESHOLD_SECONDS"), base.idle_threshold_seconds),
+            break_seconds: parse(var("LOG15_BREAK_SECONDS"), base.break_seconds),
+        }
+    }
+
+    /// The timing selected by the environment `var` reads from, falling back to the compiled default
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Timing {
+        let profile = var(TIMING_PROFILE_ENV).and_then(|p| {
+            let profile = TimingProfile::from_str(&p);
+            if profile.is_none() {
+                eprintln!("[TIMING] Unknown {} '{}', using the default profile", TIMING_PROFILE_ENV, p);
+            }
+            profile
+        });
+        match profile.unwrap_or_else(TimingProfile::compiled_default) {
+            TimingProfile::Production => Timing::PRODUCTION,
+            TimingProfile::FastTest => Timing::FAST_TEST,
+            TimingProfile::Custom => Timing::custom(var),
+        }
+    }
+}
+
+static TIMING: OnceLock<Timing> = OnceLock::new();
+
+/// The timing this process runs with
+pub fn timing() -> Timing {
+    *TIMING.get_or_init(|| Timing::from_env(|name| std::env::var(name).ok()))
+}
@@ -1,11 +1,157 @@
 // System tray integration for Log15
 
-use crate::db::{get_active_workblock, get_today_date, get_workblocks_by_date};
+use crate::db::{get_active_workblock, get_db_connection, get_today_date, get_workblocks_by_date, query_recent_activities};
+use crate::stats::query_tracked_minutes;
+use rusqlite::{params, Connection};
+use crate::dnd::{get_dnd_status, DndStatus};
+use crate::i18n::{duration_label, tr, tr_with, Text};
+use crate::settings::{
+    get_dnd_preset_minutes, get_duration_presets, get_language, get_quick_statuses, get_tray_countdown_mode, Language,
+    TrayCountdownMode, DND_PRESET_MINUTES_KEY, DURATION_PRESETS_KEY, LANGUAGE_KEY, QUICK_STATUSES_KEY, TRAY_COUNTDOWN_KEY,
+};
+use crate::timer::{compute_tick, TimerManager};
+use std::sync::Arc;
+use tokio::sync::Mutex;
 use tauri::{
-    AppHandle, Manager, tray::{TrayIconBuilder, TrayIconEvent},
-    menu::{Menu, MenuItem},
+    AppHandle, Manager, Theme, Wry, tray::{TrayIconBuilder, TrayIconEvent},
+    image::Image,
+    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
 };
 
+/// ID of the single tray icon, used to look it up when its image changes
+pub const TRAY_ID: &str = "log15-tray";
+
+/// How often the countdown in the tray title is refreshed
+const TRAY_COUNTDOWN_REFRESH_SECONDS: u64 = 30;
+
+/// Settings the tray menu shows; it is rebuilt when one of them changes
+pub const MENU_SETTING_KEYS: [&str; 5] = [
+    DURATION_PRESETS_KEY,
+    QUICK_STATUSES_KEY,
+    LANGUAGE_KEY,
+    DND_PRESET_MINUTES_KEY,
+    crate::dnd::DND_UNTIL_KEY,
+];
+
+/// How many recent activities the quick-log submenu offers
+const QUICK_LOG_ACTIVITIES: usize = 5;
+
+/// Menu IDs of quick-log items are this prefix followed by the phrase to record
+const QUICK_LOG_MENU_PREFIX: &str = "quick_log:";
+
+pub fn quick_log_menu_id(words: &str) -> String {
+    format!("{}{}", QUICK_LOG_MENU_PREFIX, words)
+}
+
+/// The phrase behind a quick-log menu ID, or None for any other menu item
+pub fn parse_quick_log_menu_id(id: &str) -> Option<&str> {
+    id.strip_prefix(QUICK_LOG_MENU_PREFIX)
+}
+
+/// Menu IDs of quick status items are this prefix followed by the status name
+const QUICK_STATUS_MENU_PREFIX: &str = "quick_status:";
+
+pub fn quick_status_menu_id(status: &str) -> String {
+    format!("{}{}", QUICK_STATUS_MENU_PREFIX, status)
+}
+
+/// The status behind a quick status menu ID, or None for any other menu item
+pub fn parse_quick_status_menu_id(id: &str) -> Option<&str> {
+    id.strip_prefix(QUICK_STATUS_MENU_PREFIX)
+}
+
+/// Durations (minutes) always offered by the tray's start submenu, next to the user's presets
+const TRAY_START_DURATIONS: [i32; 4] = [30, 60, 90, 120];
+
+/// Menu IDs of start items are this prefix followed by the duration in minutes
+const START_MENU_PREFIX: &str = "start_workblock:";
+
+/// Durations for the start submenu: the fixed choices plus the user's presets, sorted and deduplicated
+pub fn tray_start_durations(presets: &[i32]) -> Vec<i32> {
+    let mut durations: Vec<i32> = TRAY_START_DURATIONS.iter().chain(presets).copied().collect();
+    durations.sort_unstable();
+    durations.dedup();
+    durations
+}
+
+pub fn start_menu_id(duration_minutes: i32) -> String {
+    format!("{}{}", START_MENU_PREFIX, duration_minutes)
+}
+
+/// The duration behind a start menu ID, or None for any other menu item
+pub fn parse_start_menu_id(id: &str) -> Option<i32> {
+    id.strip_prefix(START_MENU_PREFIX)?.parse().ok()
+}
+
+/// Menu label for a duration in English, e.g. "45 minutes" or "1 h 30 min"
+pub fn format_duration_label(duration_minutes: i32) -> String {
+    duration_label(Language::En, duration_minutes)
+}
+
+/// Countdown text for the tray title, e.g. "12:30" or "1:05:00"
+pub fn format_countdown(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (hours, minutes, secs) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{}:{:02}", minutes, secs)
+    }
+}
+
+/// Label of the pause/resume item: the preset length while prompts run, the time left while paused
+pub fn dnd_menu_label(language: Language, status: &DndStatus, preset_minutes: i32) -> String {
+    if status.active {
+        let minutes_left = ((status.remaining_seconds + 59) / 60) as i32;
+        tr_with(language, Text::ResumePromptsLeft, &format_tracked_minutes(minutes_left))
+    } else {
+        tr_with(language, Text::PausePromptsFor, &duration_label(language, preset_minutes))
+    }
+}
+
+/// Today's tracking so far, shown in the tray tooltip
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TodayTotals {
+    pub tracked_minutes: i32,
+    pub completed_workblocks: i32,
+}
+
+pub fn query_today_totals(conn: &Connection, date: &str, now: chrono::DateTime<chrono::Local>) -> rusqlite::Result<TodayTotals> {
+    Ok(TodayTotals {
+        tracked_minutes: query_tracked_minutes(conn, date, now)?,
+        completed_workblocks: conn.query_row(
+            "SELECT COUNT(*) FROM workblocks WHERE date = ?1 AND status = 'completed'",
+            params![date],
+            |row| row.get(0),
+        )?,
+    })
+}
+
+/// Tracked time for the tooltip, e.g. "3h 45m" or "20m"
+pub fn format_tracked_minutes(minutes: i32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// Tooltip for a state, followed by today's totals once anything was tracked
+pub fn tooltip_text(state: TrayIconState, totals: TodayTotals) -> String {
+    if totals.tracked_minutes <= 0 && totals.completed_workblocks == 0 {
+        return state.tooltip().to_string();
+    }
+    let workblocks = match totals.completed_workblocks {
+        1 => "1 workblock".to_string(),
+        n => format!("{} workblocks", n),
+    };
+    format!(
+        "{}\n{} tracked today, {} completed",
+        state.tooltip(),
+        format_tracked_minutes(totals.tracked_minutes),
+        workblocks
+    )
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TrayIconState {
     Idle,          // No active workblock
@@ -13,9 +159,38 @@ pub enum TrayIconState {
     SummaryReady,  // Workblock completed, summary available
 }
 
+impl TrayIconState {
+    pub fn tooltip(&self) -> &'static str {
+        match self {
+            TrayIconState::Idle => "Log15 - No active workblock",
+            TrayIconState::Active => "Log15 - Workblock in progress",
+            TrayIconState::SummaryReady => "Log15 - Summary ready",
+        }
+    }
+
+    /// PNG for this state; dark themes get a light glyph and vice versa
+    pub fn icon_png(&self, theme: Theme) -> &'static [u8] {
+        let dark = theme == Theme::Dark;
+        match (self, dark) {
+            (TrayIconState::Idle, false) => include_bytes!("../icons/tray/idle-light.png"),
+            (TrayIconState::Idle, true) => include_bytes!("../icons/tray/idle-dark.png"),
+            (TrayIconState::Active, false) => include_bytes!("../icons/tray/active-light.png"),
+            (TrayIconState::Active, true) => include_bytes!("../icons/tray/active-dark.png"),
+            (TrayIconState::SummaryReady, false) => include_bytes!("../icons/tray/summary-light.png"),
+            (TrayIconState::SummaryReady, true) => include_bytes!("../icons/tray/summary-dark.png"),
+        }
+    }
+}
+
+/// OS theme; the menu bar follows it even when the app's windows are forced dark or light
+fn current_theme(app: &AppHandle) -> Theme {
+    crate::theme::os_theme(app).into()
+}
+
 pub struct TrayManager {
     app: AppHandle,
     current_state: TrayIconState,
+    today: TodayTotals,
 }
 
 impl TrayManager {
@@ -23,38 +198,117 @@ impl TrayManager {
         Self {
             app,
             current_state: TrayIconState::Idle,
+            today: TodayTotals::default(),
         }
     }
 
     /// Create and setup the system tray
     pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-        // Create menu items
-        let start_workblock = MenuItem::with_id(app, "start_workblock", "Start Workblock", true, None::<&str>)?;
-        let view_summary = MenuItem::with_id(app, "view_summary", "View Summary", false, None::<&str>)?;
-        let view_last_words = MenuItem::with_id(app, "view_last_words", "View Last Words", false, None::<&str>)?;
-        let show_window = MenuItem::with_id(app, "show_window", "Show Window", true, None::<&str>)?;
-        let hide_window = MenuItem::with_id(app, "hide_window", "Hide Window", false, None::<&str>)?;
-        let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+        let menu = Self::build_menu(app)?;
+
+        // Build tray icon (starts idle; swapped by update_icon_state)
+        let initial = TrayIconState::Idle;
+        let _tray_icon = TrayIconBuilder::with_id(TRAY_ID)
+            .icon(Image::from_bytes(initial.icon_png(current_theme(app)))?)
+            .menu(&menu)
+            .tooltip(initial.tooltip())
+            .build(app)?;
+
+        Ok(())
+    }
+
+    /// Tray menu, with submenus to start a workblock of a preset length and to record one of the
+    /// recent activities (or repeat the last interval) for the open prompt
+    fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+        // Create menu items, labelled in the configured language
+        let language = get_language(app);
+        let start_workblock = Submenu::with_id(app, "start_workblock_menu", tr(language, Text::StartWorkblock), true)?;
+        for minutes in tray_start_durations(&get_duration_presets(app)) {
+            start_workblock.append(&MenuItem::with_id(
+                app,
+                start_menu_id(minutes),
+                duration_label(language, minutes),
+                true,
+                None::<&str>,
+            )?)?;
+        }
+        // Any other length is picked in the main window
+        start_workblock.append(&MenuItem::with_id(app, "start_workblock", tr(language, Text::CustomDuration), true, None::<&str>)?)?;
+        let view_summary = MenuItem::with_id(app, "view_summary", tr(language, Text::ViewSummary), false, None::<&str>)?;
+        let view_last_words = MenuItem::with_id(app, "view_last_words", tr(language, Text::ViewLastWords), false, None::<&str>)?;
+        let show_window = MenuItem::with_id(app, "show_window", tr(language, Text::ShowWindow), true, None::<&str>)?;
+        let hide_window = MenuItem::with_id(app, "hide_window", tr(language, Text::HideWindow), false, None::<&str>)?;
+        let quit = MenuItem::with_id(app, "quit", tr(language, Text::Quit), true, None::<&str>)?;
+
+        let dnd = get_dnd_status(app);
+        let pause_prompts = MenuItem::with_id(
+            app,
+            if dnd.active { "resume_prompts" } else { "pause_prompts" },
+            dnd_menu_label(language, &dnd, get_dnd_preset_minutes(app)),
+            true,
+            None::<&str>,
+        )?;
+
+        let recent = get_db_connection(app)
+            .and_then(|conn| query_recent_activities(&conn, QUICK_LOG_ACTIVITIES))
+            .unwrap_or_else(|e| {
+                eprintln!("[TRAY] Failed to load recent activities: {}", e);
+                Vec::new()
+            });
+        let quick_log = Submenu::with_id(app, "quick_log", tr(language, Text::LogCurrentInterval), true)?;
+        quick_log.append(&MenuItem::with_id(
+            app,
+            "repeat_last_interval",
+            tr(language, Text::RepeatLastInterval),
+            !recent.is_empty(),
+            None::<&str>,
+        )?)?;
+        quick_log.append(&PredefinedMenuItem::separator(app)?)?;
+        if recent.is_empty() {
+            quick_log.append(&MenuItem::with_id(
+                app,
+                "quick_log_empty",
+                tr(language, Text::NoRecentActivities),
+                false,
+                None::<&str>,
+            )?)?;
+        }
+        for words in &recent {
+            quick_log.append(&MenuItem::with_id(app, quick_log_menu_id(words), words, true, None::<&str>)?)?;
+        }
+        // Named away reasons go in the same submenu, after the activities
+        let quick_statuses = get_quick_statuses(app);
+        if !quick_statuses.is_empty() {
+            quick_log.append(&PredefinedMenuItem::separator(app)?)?;
+        }
+        for status in &quick_statuses {
+            quick_log.append(&MenuItem::with_id(app, quick_status_menu_id(status), status, true, None::<&str>)?)?;
+        }
 
         // Create menu
-        let menu = Menu::with_items(app, &[
+        Menu::with_items(app, &[
             &start_workblock,
+            &quick_log,
+            &pause_prompts,
             &view_summary,
             &view_last_words,
             &show_window,
             &hide_window,
             &quit,
-        ])?;
-
-        // Build tray icon
-        // Note: Icon loading from file requires image decoding
-        // For MVP, we'll use default icon (can be enhanced later with custom icons for different states)
-        let _tray_icon = TrayIconBuilder::new()
-            .menu(&menu)
-            .tooltip("Log15 - Workblock Tracker")
-            .build(app)?;
+        ])
+    }
 
-        Ok(())
+    /// Rebuild the tray menu so its submenus list the latest presets and activities
+    pub fn refresh_menu(app: &AppHandle) {
+        let Some(tray) = app.tray_by_id(TRAY_ID) else {
+            return;
+        };
+        match Self::build_menu(app) {
+            Ok(menu) => {
+                let _ = tray.set_menu(Some(menu));
+            }
+            Err(e) => eprintln!("[TRAY] Failed to rebuild menu: {}", e),
+        }
     }
 
     /// Update tray icon state
@@ -64,17 +318,38 @@ impl TrayManager {
         }
 
         self.current_state = state;
+        self.apply_icon();
+        self.update_menu().await;
+    }
 
-        // Update tooltip based on state
-        let _tooltip = match state {
-            TrayIconState::Idle => "Log15 - No active workblock",
-            TrayIconState::Active => "Log15 - Workblock in progress",
-            TrayIconState::SummaryReady => "Log15 - Summary ready",
+    /// Swap the tray image and tooltip to match the current state and OS theme
+    pub fn apply_icon(&self) {
+        let Some(tray) = self.app.tray_by_id(TRAY_ID) else {
+            return;
         };
+        let state = self.current_state;
+        match Image::from_bytes(state.icon_png(current_theme(&self.app))) {
+            Ok(icon) => {
+                if let Err(e) = tray.set_icon(Some(icon)) {
+                    eprintln!("[TRAY] Failed to set icon: {}", e);
+                }
+            }
+            Err(e) => eprintln!("[TRAY] Failed to load icon for {:?}: {}", state, e),
+        }
+        let _ = tray.set_tooltip(Some(tooltip_text(state, self.today)));
+    }
 
-        // Update tooltip (icon state changes would require different icon files)
-        // For MVP, we'll update tooltip and menu visibility
-        self.update_menu().await;
+    /// Recompute today's totals and show them in the tooltip
+    /// (called when an interval is recorded or a workblock completes)
+    pub fn refresh_today_totals(&mut self) {
+        let today = get_today_date();
+        match get_db_connection(&self.app).and_then(|conn| Ok(query_today_totals(&conn, &today, chrono::Local::now())?)) {
+            Ok(totals) => self.today = totals,
+            Err(e) => eprintln!("[TRAY] Failed to compute today's totals: {}", e),
+        }
+        if let Some(tray) = self.app.tray_by_id(TRAY_ID) {
+            let _ = tray.set_tooltip(Some(tooltip_text(self.current_state, self.today)));
+        }
     }
 
     /// Update tray menu based on current state
@@ -117,17 +392,88 @@ impl TrayManager {
         }
     }
 
+    /// Refresh the countdown in the tray title every TRAY_COUNTDOWN_REFRESH_SECONDS
+    pub fn spawn_countdown_updates(app: &AppHandle) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut dnd_label = None;
+            loop {
+                Self::refresh_countdown(&app).await;
+                // Keep the pause time left in the menu current
+                let label = dnd_menu_label(get_language(&app), &get_dnd_status(&app), get_dnd_preset_minutes(&app));
+                if dnd_label.as_ref().is_some_and(|l| *l != label) {
+                    Self::refresh_menu(&app);
+                }
+                dnd_label = Some(label);
+                tokio::time::sleep(tokio::time::Duration::from_secs(TRAY_COUNTDOWN_REFRESH_SECONDS)).await;
+            }
+        });
+    }
+
+    /// Rebuild the menu or countdown when a setting they show changes
+    pub fn spawn_settings_listener(app: &AppHandle) {
+        crate::settings::spawn_settings_listener("tray", app, |app, change| async move {
+            let key = change.key.as_str();
+            if key.is_empty() || MENU_SETTING_KEYS.contains(&key) {
+                TrayManager::refresh_menu(&app);
+            }
+            if key.is_empty() || key == TRAY_COUNTDOWN_KEY {
+                TrayManager::refresh_countdown(&app).await;
+            }
+        });
+    }
+
+    /// Show the remaining time in the tray title, or clear it when the mode is off or nothing runs
+    pub async fn refresh_countdown(app: &AppHandle) {
+        let Some(tray) = app.tray_by_id(TRAY_ID) else {
+            return;
+        };
+        let mode = get_tray_countdown_mode(app);
+        let tick = match (mode, app.try_state::<Arc<Mutex<TimerManager>>>()) {
+            (TrayCountdownMode::Off, _) | (_, None) => None,
+            (_, Some(timer_manager)) => {
+                let state = timer_manager.lock().await.get_state().await;
+                compute_tick(&state, chrono::Local::now())
+            }
+        };
+        let title = tick.map(|tick| match mode {
+            TrayCountdownMode::Workblock => format_countdown(tick.workblock_remaining_seconds),
+            _ => format_countdown(tick.interval_remaining_seconds),
+        });
+        let _ = tray.set_title(title);
+    }
+
+    /// Back to idle with no time tracked today (after all data was wiped)
+    pub async fn reset(&mut self) {
+        self.today = TodayTotals::default();
+        self.current_state = TrayIconState::Idle;
+        self.apply_icon();
+        Self::refresh_menu(&self.app);
+        Self::refresh_countdown(&self.app).await;
+    }
+
     /// Get current state
     pub fn get_state(&self) -> TrayIconState {
         self.current_state
     }
 
+    /// Leave SummaryReady once the summary window was closed
+    pub async fn summary_viewed(&mut self) {
+        if self.current_state != TrayIconState::SummaryReady {
+            return;
+        }
+        let has_active = get_active_workblock(&self.app).is_ok_and(|opt| opt.is_some());
+        self.update_icon_state(if has_active { TrayIconState::Active } else { TrayIconState::Idle }).await;
+        self.refresh_today_totals();
+    }
+
     /// Update tray state based on workblock status
     pub async fn refresh_state(&mut self) {
         // Check if summary window is open first (highest priority)
         // We'll check this via a command instead of direct state access
         // The window manager will update tray state when summary opens/closes
         
+        self.refresh_today_totals();
         let has_active = get_active_workblock(&self.app).is_ok_and(|opt| opt.is_some());
 
         let new_state = if has_active {
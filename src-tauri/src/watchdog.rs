@@ -0,0 +1,138 @@
+// Watchdog that reconciles the prompt window with the interval waiting for an answer
+
+use crate::db::{get_interval_by_id, IntervalStatus};
+use crate::diagnostics::record_incident;
+use crate::timer::TimerManager;
+use crate::window_manager::WindowManager;
+use chrono::{DateTime, Duration, Local};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+/// How long a mismatch must persist before the watchdog steps in
+pub const WATCHDOG_GRACE_SECONDS: i64 = 60;
+const WATCHDOG_CHECK_SECONDS: u64 = 15;
+const INCIDENT_SOURCE: &str = "prompt_watchdog";
+
+/// Ways the prompt window can disagree with the interval awaiting an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptMismatch {
+    OrphanWindow { shown_interval: Option<i64> },      // Visible, but nothing is waiting for an answer
+    MissingWindow { interval_id: i64 },                 // An interval is waiting, but no prompt is visible
+    WrongInterval { shown_interval: Option<i64>, interval_id: i64 }, // Visible for another interval
+}
+
+impl PromptMismatch {
+    pub fn describe(&self) -> String {
+        match self {
+            PromptMismatch::OrphanWindow { shown_interval } => {
+                format!("Prompt window visible (interval {:?}) with no interval awaiting an answer; closed it", shown_interval)
+            }
+            PromptMismatch::MissingWindow { interval_id } => {
+                format!("Interval {} awaiting an answer without a visible prompt; re-showed it", interval_id)
+            }
+            PromptMismatch::WrongInterval { shown_interval, interval_id } => {
+                format!("Prompt window showed interval {:?} while interval {} awaits an answer; re-sent the interval", shown_interval, interval_id)
+            }
+        }
+    }
+}
+
+/// Compare what the prompt window shows with the interval awaiting an answer
+pub fn detect_mismatch(
+    window_visible: bool,
+    shown_interval: Option<i64>,
+    awaiting_interval: Option<i64>,
+) -> Option<PromptMismatch> {
+    match (window_visible, awaiting_interval) {
+        (true, None) => Some(PromptMismatch::OrphanWindow { shown_interval }),
+        (false, Some(interval_id)) => Some(PromptMismatch::MissingWindow { interval_id }),
+        (true, Some(interval_id)) if shown_interval != Some(interval_id) => {
+            Some(PromptMismatch::WrongInterval { shown_interval, interval_id })
+        }
+        _ => None,
+    }
+}
+
+/// Tracks how long the current mismatch has lasted, so brief transitions are left alone
+#[derive(Debug, Default)]
+pub struct PromptWatchdog {
+    since: Option<(PromptMismatch, DateTime<Local>)>,
+}
+
+impl PromptWatchdog {
+    /// Feed one observation; returns the mismatch to repair once it has lasted past the grace period
+    pub fn observe(&mut self, mismatch: Option<PromptMismatch>, now: DateTime<Local>) -> Option<PromptMismatch> {
+        let Some(mismatch) = mismatch else {
+            self.since = None;
+            return None;
+        };
+        match self.since {
+            Some((seen, since)) if seen == mismatch => {
+                if now - since >= Duration::seconds(WATCHDOG_GRACE_SECONDS) {
+                    self.since = None;
+                    Some(mismatch)
+                } else {
+                    None
+                }
+            }
+            _ => {
+                self.since = Some((mismatch, now));
+                None
+            }
+        }
+    }
+}
+
+/// Interval whose prompt went out and still has no answer, unless it is snoozed
+async fn awaiting_interval(app: &AppHandle) -> Option<i64> {
+    let timer_manager = app.state::<Arc<Mutex<TimerManager>>>();
+    let state = timer_manager.lock().await.get_state().await;
+    if state.snoozed_until.is_some_and(|until| until > Local::now()) {
+        return None;
+    }
+    let interval_id = state.prompted_interval_id?;
+    match get_interval_by_id(app, interval_id) {
+        Ok(interval) if interval.status == IntervalStatus::Pending => Some(interval_id),
+        _ => None,
+    }
+}
+
+async fn check(app: &AppHandle, watchdog: &mut PromptWatchdog) {
+    let awaiting = awaiting_interval(app).await;
+    let window_manager = app.state::<Arc<Mutex<WindowManager>>>();
+    let window_mgr = window_manager.lock().await;
+    let visible = window_mgr.is_prompt_visible().await;
+    let shown = window_mgr.get_current_interval_id().await;
+    // The latest prompt may be queued behind an earlier one, which is what the window should show
+    let awaiting = match awaiting {
+        Some(interval_id) if visible && window_mgr.is_prompt_queued(interval_id).await => shown,
+        awaiting => awaiting,
+    };
+
+    let Some(mismatch) = watchdog.observe(detect_mismatch(visible, shown, awaiting), Local::now()) else {
+        return;
+    };
+    let result = match mismatch {
+        PromptMismatch::OrphanWindow { .. } => window_mgr.hide_prompt_window().await,
+        PromptMismatch::MissingWindow { interval_id } | PromptMismatch::WrongInterval { interval_id, .. } => {
+            window_mgr.show_prompt_window(interval_id).await
+        }
+    };
+    match result {
+        Ok(()) => record_incident(app, INCIDENT_SOURCE, &mismatch.describe()),
+        Err(e) => record_incident(app, INCIDENT_SOURCE, &format!("{} failed: {}", mismatch.describe(), e)),
+    }
+}
+
+/// Check the prompt window periodically and repair mismatches that outlast the grace period
+pub fn spawn_prompt_watchdog(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut watchdog = PromptWatchdog::default();
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(WATCHDOG_CHECK_SECONDS)).await;
+            check(&app, &mut watchdog).await;
+        }
+    });
+}
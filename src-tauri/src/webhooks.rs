@@ -0,0 +1,397 @@
+// Webhooks: user-configured URLs that receive a signed JSON POST when a workblock starts or
+// completes, when a day is archived and on milestones (for n8n, Zapier, ...). Deliveries run in the background
+// and are retried with exponential backoff; the outcome of the last one is kept on the webhook.
+
+use crate::db::{get_db_connection, query_intervals_by_workblock, query_workblock_by_id, timestamp_now};
+use crate::error::Log15Error;
+use crate::settings::{get_milestone_templates, MilestoneTemplates};
+use crate::stats::Milestone;
+use hmac::{Hmac, Mac};
+use rusqlite::{params, Connection, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Header carrying "sha256=<hex HMAC-SHA256 of the body keyed with the webhook's secret>"
+pub const SIGNATURE_HEADER: &str = "X-Log15-Signature";
+/// Header carrying the event name, e.g. "workblock_completed"
+pub const EVENT_HEADER: &str = "X-Log15-Event";
+
+/// Attempts per delivery, the first one included
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 5;
+/// Wait before the first retry; it doubles with each further one
+pub const RETRY_BASE_DELAY_SECONDS: u64 = 5;
+const REQUEST_TIMEOUT_SECONDS: u64 = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    WorkblockStarted,
+    WorkblockCompleted,
+    DayArchived,
+    GoalReached,
+    BudgetExceeded,
+    StreakMilestone,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 6] = [
+        WebhookEvent::WorkblockStarted,
+        WebhookEvent::WorkblockCompleted,
+        WebhookEvent::DayArchived,
+        WebhookEvent::GoalReached,
+        WebhookEvent::BudgetExceeded,
+        WebhookEvent::StreakMilestone,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::WorkblockStarted => "workblock_started",
+            WebhookEvent::WorkblockCompleted => "workblock_completed",
+            WebhookEvent::DayArchived => "day_archived",
+            WebhookEvent::GoalReached => "goal_reached",
+            WebhookEvent::BudgetExceeded => "budget_exceeded",
+            WebhookEvent::StreakMilestone => "streak_milestone",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event| event.as_str() == s)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub secret: String,                    // Key of the signature header
+    pub enabled: bool,
+    pub created_at: String,
+    pub last_status: Option<String>,       // e.g. "200", "503" or the transport error; None before the first delivery
+    pub last_delivery_at: Option<String>,
+}
+
+/// Body of every delivery
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub occurred_at: String,
+    pub data: serde_json::Value,
+}
+
+/// Only http(s) URLs with a host can be delivered to
+pub fn validate_webhook_url(url: &str) -> crate::error::Result<String> {
+    let url = url.trim();
+    let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"));
+    match rest {
+        Some(rest) if !rest.is_empty() && !rest.starts_with('/') => Ok(url.to_string()),
+        _ => Err(Log15Error::InvalidInput(format!("Not an http(s) URL: {}", url))),
+    }
+}
+
+/// Events stored as a comma-separated list; at least one is required
+fn events_to_column(events: &[WebhookEvent]) -> crate::error::Result<String> {
+    if events.is_empty() {
+        return Err(Log15Error::InvalidInput("Select at least one event".to_string()));
+    }
+    let names: Vec<&str> = WebhookEvent::ALL.iter().filter(|e| events.contains(e)).map(|e| e.as_str()).collect();
+    Ok(names.join(","))
+}
+
+fn events_from_column(column: &str) -> Vec<WebhookEvent> {
+    column.split(',').filter_map(WebhookEvent::from_str).collect()
+}
+
+/// A random 64-character hex secret
+pub fn generate_secret() -> String {
+    let mut hasher = Sha256::new();
+    for _ in 0..4 {
+        // RandomState keys are seeded from the OS for every instance
+        let mut random = std::collections::hash_map::RandomState::new().build_hasher();
+        random.write_u128(chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u128);
+        hasher.update(random.finish().to_le_bytes());
+    }
+    hasher.update(std::process::id().to_le_bytes());
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Value of the signature header for `body`
+pub fn sign_payload(secret: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    let digest: String = mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", digest)
+}
+
+/// Wait before retry `attempt` (1 = the first retry)
+pub fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs(RETRY_BASE_DELAY_SECONDS << attempt.saturating_sub(1).min(10))
+}
+
+/// Whether a delivery answered with `status` is worth trying again: server errors and rate limits are,
+/// other client errors (a wrong URL, a rejected signature) won't go away by themselves
+pub fn should_retry(status: u16) -> bool {
+    status == 408 || status == 429 || status >= 500
+}
+
+const WEBHOOK_COLUMNS: &str = "id, url, events, secret, enabled, created_at, last_status, last_delivery_at";
+
+fn row_to_webhook(row: &rusqlite::Row) -> Result<Webhook> {
+    Ok(Webhook {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        events: events_from_column(&row.get::<_, String>(2)?),
+        secret: row.get(3)?,
+        enabled: row.get(4)?,
+        created_at: row.get(5)?,
+        last_status: row.get(6)?,
+        last_delivery_at: row.get(7)?,
+    })
+}
+
+pub fn query_webhook(conn: &Connection, id: i64) -> Result<Webhook> {
+    conn.query_row(
+        &format!("SELECT {} FROM webhooks WHERE id = ?1", WEBHOOK_COLUMNS),
+        params![id],
+        row_to_webhook,
+    )
+}
+
+pub fn query_webhooks(conn: &Connection) -> Result<Vec<Webhook>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM webhooks ORDER BY id", WEBHOOK_COLUMNS))?;
+    let webhooks = stmt.query_map([], row_to_webhook)?.collect();
+    webhooks
+}
+
+/// Enabled webhooks subscribed to `event`
+pub fn query_webhooks_for_event(conn: &Connection, event: WebhookEvent) -> Result<Vec<Webhook>> {
+    Ok(query_webhooks(conn)?
+        .into_iter()
+        .filter(|webhook| webhook.enabled && webhook.events.contains(&event))
+        .collect())
+}
+
+/// Add a webhook; a secret is generated unless one is given
+pub fn insert_webhook(
+    conn: &Connection,
+    url: &str,
+    events: &[WebhookEvent],
+    secret: Option<&str>,
+) -> crate::error::Result<Webhook> {
+    let url = validate_webhook_url(url)?;
+    let events = events_to_column(events)?;
+    let secret = secret.map(str::trim).filter(|s| !s.is_empty()).map(String::from).unwrap_or_else(generate_secret);
+    conn.execute(
+        "INSERT INTO webhooks (url, events, secret, enabled, created_at) VALUES (?1, ?2, ?3, 1, ?4)",
+        params![url, events, secret, timestamp_now()],
+    )?;
+    Ok(query_webhook(conn, conn.last_insert_rowid())?)
+}
+
+pub fn update_webhook(
+    conn: &Connection,
+    id: i64,
+    url: &str,
+    events: &[WebhookEvent],
+    enabled: bool,
+) -> crate::error::Result<Webhook> {
+    let url = validate_webhook_url(url)?;
+    let events = events_to_column(events)?;
+    let updated = conn.execute(
+        "UPDATE webhooks SET url = ?1, events = ?2, enabled = ?3 WHERE id = ?4",
+        params![url, events, enabled, id],
+    )?;
+    if updated == 0 {
+        return Err(Log15Error::NotFound(format!("Webhook {} not found", id)));
+    }
+    Ok(query_webhook(conn, id)?)
+}
+
+pub fn delete_webhook(conn: &Connection, id: i64) -> Result<bool> {
+    Ok(conn.execute("DELETE FROM webhooks WHERE id = ?1", params![id])? > 0)
+}
+
+pub fn store_delivery_result(conn: &Connection, id: i64, status: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE webhooks SET last_status = ?1, last_delivery_at = ?2 WHERE id = ?3",
+        params![status, timestamp_now(), id],
+    )?;
+    Ok(())
+}
+
+/// Result of one POST: the HTTP status, or why no response came back
+fn post_once(url: &str, event: WebhookEvent, signature: &str, body: &str) -> std::result::Result<u16, String> {
+    let response = ureq::post(url)
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
+        .set("Content-Type", "application/json")
+        .set(EVENT_HEADER, event.as_str())
+        .set(SIGNATURE_HEADER, signature)
+        .send_string(body);
+    match response {
+        Ok(response) => Ok(response.status()),
+        Err(ureq::Error::Status(status, _)) => Ok(status),
+        Err(ureq::Error::Transport(e)) => Err(e.to_string()),
+    }
+}
+
+/// POST `body` to `webhook` until it is accepted, fails for good or runs out of attempts
+async fn deliver(app: AppHandle, webhook: Webhook, event: WebhookEvent, body: String) {
+    let signature = sign_payload(&webhook.secret, &body);
+    let mut outcome = String::new();
+    for attempt in 0..MAX_DELIVERY_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(retry_delay(attempt)).await;
+        }
+        let (url, signature, body) = (webhook.url.clone(), signature.clone(), body.clone());
+        let result = tauri::async_runtime::spawn_blocking(move || post_once(&url, event, &signature, &body))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()));
+        let retry = match &result {
+            Ok(status) => {
+                outcome = status.to_string();
+                should_retry(*status)
+            }
+            Err(e) => {
+                outcome = e.clone();
+                true
+            }
+        };
+        if !retry {
+            break;
+        }
+        eprintln!(
+            "[WEBHOOK] {} to {} failed ({}), attempt {}/{}",
+            event.as_str(), webhook.url, outcome, attempt + 1, MAX_DELIVERY_ATTEMPTS
+        );
+    }
+    println!("[WEBHOOK] {} to {}: {}", event.as_str(), webhook.url, outcome);
+    let result = get_db_connection(&app).and_then(|conn| Ok(store_delivery_result(&conn, webhook.id, &outcome)?));
+    if let Err(e) = result {
+        eprintln!("[WEBHOOK] Failed to store delivery result: {}", e);
+    }
+}
+
+/// Send `data` for `event` to every webhook subscribed to it, in the background.
+/// Errors are only logged: a webhook never gets in the way of the timer or the archive.
+pub fn dispatch(app: &AppHandle, event: WebhookEvent, data: serde_json::Value) {
+    let webhooks = match get_db_connection(app).and_then(|conn| Ok(query_webhooks_for_event(&conn, event)?)) {
+        Ok(webhooks) => webhooks,
+        Err(e) => {
+            eprintln!("[WEBHOOK] Failed to load webhooks: {}", e);
+            return;
+        }
+    };
+    if webhooks.is_empty() {
+        return;
+    }
+    let payload = WebhookPayload { event, occurred_at: timestamp_now(), data };
+    let body = match serde_json::to_string(&payload) {
+        Ok(body) => body,
+        Err(e) => {
+            eprintln!("[WEBHOOK] Failed to serialize {}: {}", event.as_str(), e);
+            return;
+        }
+    };
+    for webhook in webhooks {
+        tauri::async_runtime::spawn(deliver(app.clone(), webhook, event, body.clone()));
+    }
+}
+
+/// Data sent for a workblock event: the workblock, and its intervals once it is completed
+pub fn workblock_event_data(conn: &Connection, event: WebhookEvent, workblock_id: i64) -> Result<serde_json::Value> {
+    let workblock = query_workblock_by_id(conn, workblock_id)?;
+    let data = if event == WebhookEvent::WorkblockCompleted {
+        let intervals = query_intervals_by_workblock(conn, workblock_id)?;
+        serde_json::json!({ "workblock": workblock, "intervals": intervals })
+    } else {
+        serde_json::json!({ "workblock": workblock })
+    };
+    Ok(data)
+}
+
+/// Dispatch a workblock event, unless no webhook listens for it
+pub fn dispatch_workblock_event(app: &AppHandle, event: WebhookEvent, workblock_id: i64) {
+    let data = get_db_connection(app).and_then(|conn| {
+        if query_webhooks_for_event(&conn, event)?.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(workblock_event_data(&conn, event, workblock_id)?))
+    });
+    match data {
+        Ok(Some(data)) => dispatch(app, event, data),
+        Ok(None) => {}
+        Err(e) => eprintln!("[WEBHOOK] Failed to load workblock {}: {}", workblock_id, e),
+    }
+}
+
+/// The webhook event a milestone is delivered as
+pub fn milestone_event(milestone: &Milestone) -> WebhookEvent {
+    match milestone {
+        Milestone::GoalReached { .. } => WebhookEvent::GoalReached,
+        Milestone::BudgetExceeded { .. } => WebhookEvent::BudgetExceeded,
+        Milestone::StreakMilestone { .. } => WebhookEvent::StreakMilestone,
+    }
+}
+
+/// Fill in a milestone message template: `{field}` becomes that field of the milestone, with
+/// underscores in text shown as spaces ("tracked_minutes" reads "tracked minutes")
+pub fn render_milestone_message(template: &str, milestone: &Milestone) -> String {
+    let fields = serde_json::to_value(milestone).unwrap_or_default();
+    let mut message = template.to_string();
+    for (name, value) in fields.as_object().into_iter().flatten() {
+        let value = match value {
+            serde_json::Value::String(text) => text.replace('_', " "),
+            other => other.to_string(),
+        };
+        message = message.replace(&format!("{{{}}}", name), &value);
+    }
+    message
+}
+
+/// Data sent for a milestone: its fields, plus a message rendered from the user's template for
+/// its event that an automation can forward as is (to chat, email, ...)
+pub fn milestone_event_data(milestone: &Milestone, templates: &MilestoneTemplates) -> serde_json::Value {
+    let message = render_milestone_message(templates.for_milestone(milestone), milestone);
+    serde_json::json!({ "message": message, "milestone": milestone })
+}
+
+/// Dispatch a milestone to the webhooks subscribed to its event
+pub fn dispatch_milestone(app: &AppHandle, milestone: &Milestone) {
+    let templates = get_milestone_templates(app);
+    dispatch(app, milestone_event(milestone), milestone_event_data(milestone, &templates));
+}
+
+pub fn get_webhooks(app: &AppHandle) -> Result<Vec<Webhook>> {
+    let conn = get_db_connection(app)?;
+    query_webhooks(&conn)
+}
+
+pub fn add_webhook(
+    app: &AppHandle,
+    url: &str,
+    events: &[WebhookEvent],
+    secret: Option<&str>,
+) -> crate::error::Result<Webhook> {
+    let conn = get_db_connection(app)?;
+    insert_webhook(&conn, url, events, secret)
+}
+
+pub fn edit_webhook(
+    app: &AppHandle,
+    id: i64,
+    url: &str,
+    events: &[WebhookEvent],
+    enabled: bool,
+) -> crate::error::Result<Webhook> {
+    let conn = get_db_connection(app)?;
+    update_webhook(&conn, id, url, events, enabled)
+}
+
+pub fn remove_webhook(app: &AppHandle, id: i64) -> Result<bool> {
+    let conn = get_db_connection(app)?;
+    delete_webhook(&conn, id)
+}
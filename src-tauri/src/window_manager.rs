@@ -1,14 +1,127 @@
-// Window manager for overlay prompt windows
+// Window manager for overlay prompt windows and the summary window
 
-use tauri::{AppHandle, Manager, Emitter, WebviewUrl, WebviewWindowBuilder};
+use crate::db::{
+    generate_daily_visualization_data, get_archived_day, get_interval_by_id, get_reclassify_offer, get_today_date,
+    set_interval_end_time, to_timestamp, DailyVisualizationData,
+};
+use crate::tray::TrayManager;
+use serde::{Deserialize, Serialize};
+use crate::error::Log15Error;
+use crate::events::{emit_event, AppEvent};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+const PROMPT_WINDOW_LABEL: &str = "prompt";
+const PROMPT_WINDOW_WIDTH: f64 = 300.0;
+const SUMMARY_WINDOW_LABEL: &str = "summary";
+const STATUS_WINDOW_LABEL: &str = "status";
+
+/// Sent to the summary window as "summary-data" whenever it is shown
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryWindowData {
+    pub date: String,
+    pub workblock_id: Option<i64>,  // Workblock that just finished, if the window opened for one
+    pub data: DailyVisualizationData,
+    pub ai_summary: Option<String>,  // Stored with the day's archive, if one was generated
+    pub ai_enabled: bool,            // Whether the window may offer to generate one
+}
+
+/// A prompt labelled with the time range its interval covered
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PromptLabel {
+    pub interval_id: i64,
+    pub interval_number: i32,
+    pub start_time: String,
+    pub end_time: Option<String>,
+}
+
+/// Payload of "prompt-queue": the prompt on screen and the ones waiting behind it, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PromptQueue {
+    pub current: Option<PromptLabel>,
+    pub pending: Vec<PromptLabel>,
+}
+
+/// Intervals whose prompt is waiting for the one on screen to be answered, oldest first
+#[derive(Debug, Default)]
+pub struct PendingPrompts {
+    intervals: VecDeque<i64>,
+}
+
+impl PendingPrompts {
+    /// Queue an interval behind the prompt showing `shown`; returns false if it is already there
+    pub fn enqueue(&mut self, interval_id: i64, shown: Option<i64>) -> bool {
+        if shown == Some(interval_id) || self.intervals.contains(&interval_id) {
+            return false;
+        }
+        self.intervals.push_back(interval_id);
+        true
+    }
+
+    /// Take the oldest queued interval for which `is_unanswered` holds, dropping answered ones on the way
+    pub fn next(&mut self, mut is_unanswered: impl FnMut(i64) -> bool) -> Option<i64> {
+        while let Some(interval_id) = self.intervals.pop_front() {
+            if is_unanswered(interval_id) {
+                return Some(interval_id);
+            }
+        }
+        None
+    }
+
+    pub fn contains(&self, interval_id: i64) -> bool {
+        self.intervals.contains(&interval_id)
+    }
+
+    pub fn clear(&mut self) {
+        self.intervals.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        self.intervals.iter().copied()
+    }
+}
+
+/// Whether an interval still has nothing recorded
+fn is_unanswered(app: &AppHandle, interval_id: i64) -> bool {
+    get_interval_by_id(app, interval_id).is_ok_and(|interval| interval.words.is_none())
+}
+
+fn prompt_label(app: &AppHandle, interval_id: i64) -> Option<PromptLabel> {
+    let interval = get_interval_by_id(app, interval_id).ok()?;
+    Some(PromptLabel {
+        interval_id,
+        interval_number: interval.interval_number,
+        start_time: interval.start_time,
+        end_time: interval.end_time,
+    })
+}
+
+/// Place the prompt window at the top-right of its monitor
+fn position_prompt_window(window: &WebviewWindow) {
+    match window.current_monitor() {
+        Ok(Some(monitor)) => {
+            // Convert physical size to logical size (accounting for DPI scaling)
+            let scale_factor = monitor.scale_factor();
+            let logical_width = monitor.size().width as f64 / scale_factor;
+            
+            let x = logical_width - PROMPT_WINDOW_WIDTH - 20.0; // 20px margin from right
+            let y = 20.0; // 20px margin from top
+            if let Err(e) = window.set_position(tauri::LogicalPosition::new(x, y)) {
+                eprintln!("[WINDOW_MGR] Failed to position window: {}", e);
+            }
+        }
+        Ok(None) => eprintln!("[WINDOW_MGR] No monitor found"),
+        Err(e) => eprintln!("[WINDOW_MGR] Failed to get current monitor: {}", e),
+    }
+}
+
 pub struct WindowManager {
     app: AppHandle,
     prompt_window: Arc<Mutex<Option<tauri::WebviewWindow>>>,
     current_interval_id: Arc<Mutex<Option<i64>>>,
-    is_summary_ready: Arc<Mutex<bool>>,
+    pending_prompts: Arc<Mutex<PendingPrompts>>,
 }
 
 impl WindowManager {
@@ -17,13 +130,25 @@ impl WindowManager {
             app,
             prompt_window: Arc::new(Mutex::new(None)),
             current_interval_id: Arc::new(Mutex::new(None)),
-            is_summary_ready: Arc::new(Mutex::new(false)),
+            pending_prompts: Arc::new(Mutex::new(PendingPrompts::default())),
+        }
+    }
+
+    /// Create the prompt window hidden at startup, so each interval only has to show it
+    pub async fn prepare_prompt_window(&self) -> Result<(), Log15Error> {
+        let mut prompt = self.prompt_window.lock().await;
+        if prompt.is_some() || self.app.get_webview_window(PROMPT_WINDOW_LABEL).is_some() {
+            return Ok(());
         }
+        let window = self.build_prompt_window("index.html#/prompt".to_string(), false)?;
+        *prompt = Some(window);
+        println!("[WINDOW_MGR] Prompt window prepared (hidden)");
+        Ok(())
     }
 
-    /// Show the prompt window for an interval
-    /// Always creates a fresh window - closes any existing window first
-    pub async fn show_prompt_window(&self, interval_id: i64) -> Result<(), String> {
+    /// Show the prompt window for an interval.
+    /// Reuses the prepared window and sends it the interval; creates one only if none exists.
+    pub async fn show_prompt_window(&self, interval_id: i64) -> Result<(), Log15Error> {
         // #region agent log
         use std::fs::OpenOptions;
         use std::io::Write;
@@ -33,159 +158,238 @@ impl WindowManager {
         // #endregion
         println!("[WINDOW_MGR] show_prompt_window called with interval_id={}", interval_id);
         
-        // First, close any existing window directly without emitting prompt-hide event
-        // (to avoid interfering with frontend's own fade-out sequence)
-        // #region agent log
-        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open("/Users/ronaldlin/log15/.cursor/debug.log") {
-            let _ = writeln!(file, r#"{{"location":"window_manager.rs:36","message":"Closing existing window directly (not using hide_prompt_window)","data":{{"timestamp":{}}},"timestamp":{},"sessionId":"debug-session","runId":"run3","hypothesisId":"G"}}"#, chrono::Utc::now().timestamp_millis(), chrono::Utc::now().timestamp_millis());
-        }
-        // #endregion
+        // Waits for a hide in progress (which holds the lock through its fade-out)
         let mut prompt = self.prompt_window.lock().await;
-        if let Some(window) = prompt.take() {
-            println!("[WINDOW_MGR] Closing existing window directly");
-            let _ = window.close();
-        } else if let Some(window) = self.app.get_webview_window("prompt") {
-            println!("[WINDOW_MGR] Window exists in Tauri but not in state, closing it directly");
-            let _ = window.close();
+        if prompt.is_none() {
+            // Window exists in Tauri but not in our state - adopt it
+            *prompt = self.app.get_webview_window(PROMPT_WINDOW_LABEL);
         }
-        drop(prompt);
         
-        // Clear interval ID state
-        *self.current_interval_id.lock().await = None;
-        *self.is_summary_ready.lock().await = false;
+        // Store the new interval ID
+        *self.current_interval_id.lock().await = Some(interval_id);
         
-        // Wait for fade-out animation to complete (300ms) + buffer before creating a new window
-        // This ensures the old window is fully closed before the new one appears
-        tokio::time::sleep(tokio::time::Duration::from_millis(350)).await; // 300ms animation + 50ms buffer
+        // A passive prompt can't take focus until the hotkey or a click asks for it
+        let passive = crate::settings::get_passive_prompt(&self.app);
+        let reused = match prompt.as_ref() {
+            Some(window) => {
+                // The screen layout may have changed since the window was created
+                position_prompt_window(window);
+                window.set_focusable(!passive).ok();
+                emit_event(&self.app, AppEvent::PromptIntervalId(interval_id)).is_ok() && window.show().is_ok()
+            }
+            None => false,
+        };
         
-        // Double-check: if window still exists in Tauri, try to close it again
-        if let Some(existing_window) = self.app.get_webview_window("prompt") {
-            println!("[WINDOW_MGR] Window still exists after close, force closing");
-            let _ = existing_window.close();
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        if !reused {
+            // Fallback: create the window on demand with the interval in its URL
+            if let Some(stale) = prompt.take() {
+                println!("[WINDOW_MGR] Prepared prompt window unusable, recreating it");
+                let _ = stale.destroy();
+            }
+            println!("[WINDOW_MGR] Creating new prompt window");
+            let window = self.build_prompt_window(format!("index.html#/prompt?intervalId={}", interval_id), true)?;
+            window.show().map_err(|e| {
+                eprintln!("[WINDOW_MGR] Failed to show window: {}", e);
+                Log15Error::Window(format!("Failed to show window: {}", e))
+            })?;
+            *prompt = Some(window);
         }
         
-        // Store the new interval ID
-        *self.current_interval_id.lock().await = Some(interval_id);
+        let Some(window) = prompt.as_ref() else {
+            return Ok(());
+        };
+        if !passive {
+            window.set_focus().ok();
+        }
+        println!(
+            "[WINDOW_MGR] Prompt shown for interval {} ({}{}). Is visible: {}",
+            interval_id,
+            if reused { "reused" } else { "created" },
+            if passive { ", passive" } else { "" },
+            window.is_visible().unwrap_or(false)
+        );
+
+        // Offer to undo an auto-away on the previous interval (also fetched by the frontend on load)
+        if let Ok(Some(offer)) = get_reclassify_offer(&self.app, interval_id) {
+            println!("[WINDOW_MGR] Previous interval {} was auto-away, emitting reclassify-offer", offer.interval_id);
+            let _ = emit_event(&self.app, AppEvent::ReclassifyOffer(offer));
+        }
+
+        Ok(())
+    }
+
+    /// Show the prompt for an interval that just ended, or queue it while the prompt is still
+    /// waiting on an earlier interval. Returns whether it was shown.
+    pub async fn show_or_queue_prompt(&self, interval_id: i64) -> Result<bool, Log15Error> {
+        let shown = *self.current_interval_id.lock().await;
+        let busy = shown.is_some_and(|shown| shown != interval_id && is_unanswered(&self.app, shown));
+        if busy && self.is_prompt_visible().await {
+            if self.pending_prompts.lock().await.enqueue(interval_id, shown) {
+                // It ended now, even if it only gets answered once the prompts before it are
+                if let Ok(interval) = get_interval_by_id(&self.app, interval_id) {
+                    if interval.end_time.is_none() {
+                        let _ = set_interval_end_time(&self.app, interval_id, &to_timestamp(chrono::Local::now()));
+                    }
+                }
+                println!("[WINDOW_MGR] Prompt busy with interval {:?}, queued interval {}", shown, interval_id);
+            }
+            self.emit_prompt_queue().await;
+            return Ok(false);
+        }
+        self.show_prompt_window(interval_id).await?;
+        self.emit_prompt_queue().await;
+        Ok(true)
+    }
+
+    /// Take the next queued interval that still needs an answer, unless a prompt is on screen
+    pub async fn next_queued_prompt(&self) -> Option<i64> {
+        if self.is_prompt_visible().await {
+            return None;
+        }
+        self.pending_prompts.lock().await.next(|interval_id| is_unanswered(&self.app, interval_id))
+    }
+
+    /// Whether an interval is waiting behind the prompt on screen
+    pub async fn is_prompt_queued(&self, interval_id: i64) -> bool {
+        self.pending_prompts.lock().await.contains(interval_id)
+    }
+
+    /// Forget the queued prompts (the workblock they belong to is gone)
+    pub async fn clear_prompt_queue(&self) {
+        self.pending_prompts.lock().await.clear();
+        self.emit_prompt_queue().await;
+    }
+
+    /// The prompt on screen and the prompts queued behind it
+    pub async fn get_prompt_queue(&self) -> PromptQueue {
+        let current = self.current_interval_id.lock().await.and_then(|id| prompt_label(&self.app, id));
+        let pending = self
+            .pending_prompts
+            .lock()
+            .await
+            .iter()
+            .filter_map(|id| prompt_label(&self.app, id))
+            .collect();
+        PromptQueue { current, pending }
+    }
+
+    async fn emit_prompt_queue(&self) {
+        let _ = emit_event(&self.app, AppEvent::PromptQueue(self.get_prompt_queue().await));
+    }
+
+    /// Give the prompt keyboard focus, also when it was shown passively
+    pub async fn focus_prompt_window(&self) -> Result<(), Log15Error> {
+        let prompt = self.prompt_window.lock().await;
+        let window = prompt
+            .clone()
+            .or_else(|| self.app.get_webview_window(PROMPT_WINDOW_LABEL))
+            .filter(|window| window.is_visible().unwrap_or(false))
+            .ok_or_else(|| Log15Error::Window("The prompt window isn't shown".to_string()))?;
+        window.set_focusable(true).map_err(|e| Log15Error::Window(format!("Failed to focus prompt window: {}", e)))?;
+        window.set_focus().map_err(|e| Log15Error::Window(format!("Failed to focus prompt window: {}", e)))
+    }
 
-        println!("[WINDOW_MGR] Creating new prompt window");
-        // Create the prompt window with intervalId in URL query parameter
-        // For now, we'll use a URL that points to a route in the main app
-        // In production, you might want a separate HTML file
-        let url_with_interval = format!("index.html#/prompt?intervalId={}", interval_id);
-        println!("[WINDOW_MGR] Creating window with URL: {}", url_with_interval);
+    /// Build the prompt overlay at the top-right of the screen
+    fn build_prompt_window(&self, url: String, visible: bool) -> Result<WebviewWindow, Log15Error> {
+        let passive = crate::settings::get_passive_prompt(&self.app);
+        println!("[WINDOW_MGR] Creating window with URL: {}", url);
         let window = WebviewWindowBuilder::new(
             &self.app,
-            "prompt",
-            WebviewUrl::App(url_with_interval.into()),
+            PROMPT_WINDOW_LABEL,
+            WebviewUrl::App(url.into()),
         )
         .title("Log15 - What did you do?")
-        .inner_size(300.0, 180.0) // Increased height for summary view
+        .inner_size(PROMPT_WINDOW_WIDTH, 180.0)
         .decorations(false)
         .always_on_top(true)
         .skip_taskbar(true)
-        .visible(true) // Start visible - we'll position it immediately
+        .focused(!passive)
+        .focusable(!passive)
+        .visible(visible)
+        .theme(crate::theme::window_theme(&self.app))
         .build()
         .map_err(|e| {
             eprintln!("[WINDOW_MGR] Failed to create window: {}", e);
-            format!("Failed to create prompt window: {}", e)
+            Log15Error::Window(format!("Failed to create prompt window: {}", e))
         })?;
-        
-        println!("[WINDOW_MGR] Window created successfully");
+        position_prompt_window(&window);
+        Ok(window)
+    }
 
-        // Position window at top-right of screen
-        // Wait a moment for window to be ready before positioning
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    /// Show today's summary in its own resizable window, creating it if needed.
+    /// The day's data is sent as "summary-data"; closing the window takes the tray out of SummaryReady.
+    pub async fn show_summary_window(&self, workblock_id: Option<i64>) -> Result<(), Log15Error> {
+        let date = get_today_date();
+        let data = generate_daily_visualization_data(&self.app, &date)?;
+        let ai_summary = get_archived_day(&self.app, &date).ok().flatten().and_then(|archive| archive.ai_summary);
+        let ai_enabled = crate::settings::get_ai_settings(&self.app).enabled;
+        let payload = SummaryWindowData { date: date.clone(), workblock_id, data, ai_summary, ai_enabled };
         
-        if let Ok(monitor) = window.current_monitor() {
-            if let Some(monitor) = monitor {
-                let screen_size = monitor.size();
-                // Convert physical size to logical size (accounting for DPI scaling)
-                let scale_factor = monitor.scale_factor();
-                let logical_width = screen_size.width as f64 / scale_factor;
-                let logical_height = screen_size.height as f64 / scale_factor;
-                
-                // Use default size for positioning
-                let window_width = 300.0;
-                let window_height = 180.0;
-                
-                let x = logical_width - window_width - 20.0; // 20px margin from right
-                let y = 20.0; // 20px margin from top
-                
-                println!("[WINDOW_MGR] Positioning window at logical ({}, {}) on screen logical size ({}, {}), scale_factor: {}", 
-                    x, y, logical_width, logical_height, scale_factor);
-                
-                let pos_result = window.set_position(tauri::LogicalPosition::new(x, y));
-                match pos_result {
-                    Ok(_) => println!("[WINDOW_MGR] Window positioned successfully"),
-                    Err(e) => eprintln!("[WINDOW_MGR] Failed to position window: {}", e),
-                }
-            } else {
-                eprintln!("[WINDOW_MGR] No monitor found");
-            }
-        } else {
-            eprintln!("[WINDOW_MGR] Failed to get current monitor");
+        if let Some(window) = self.app.get_webview_window(SUMMARY_WINDOW_LABEL) {
+            emit_event(&self.app, AppEvent::SummaryData(payload)).map_err(Log15Error::Window)?;
+            window.show().map_err(|e| Log15Error::Window(format!("Failed to show summary window: {}", e)))?;
+            window.set_focus().ok();
+            return Ok(());
         }
-
-        // Show window with fade-in (handled by frontend CSS)
-        println!("[WINDOW_MGR] Showing window");
-        
-        window.show().map_err(|e| {
-            eprintln!("[WINDOW_MGR] Failed to show window: {}", e);
-            format!("Failed to show window: {}", e)
-        })?;
-        
-        window.set_focus().ok();
-        
-        // Verify window is actually visible
-        let is_visible = window.is_visible().unwrap_or(false);
-        println!("[WINDOW_MGR] Window shown and focused. Is visible: {}", is_visible);
         
-        // Get window position for debugging
-        if let Ok(pos) = window.outer_position() {
-            println!("[WINDOW_MGR] Window position: {:?}", pos);
+        println!("[WINDOW_MGR] Creating summary window for {}", date);
+        let mut url = format!("index.html#/summary?date={}", date);
+        if let Some(workblock_id) = workblock_id {
+            url.push_str(&format!("&workblockId={}", workblock_id));
         }
+        let window = WebviewWindowBuilder::new(&self.app, SUMMARY_WINDOW_LABEL, WebviewUrl::App(url.into()))
+            .title("Log15 - Summary")
+            .inner_size(900.0, 680.0)
+            .min_inner_size(600.0, 420.0)
+            .resizable(true)
+            .center()
+            .visible(true)
+            .theme(crate::theme::window_theme(&self.app))
+            .build()
+            .map_err(|e| Log15Error::Window(format!("Failed to create summary window: {}", e)))?;
         
-        if let Ok(size) = window.outer_size() {
-            println!("[WINDOW_MGR] Window size: {:?}", size);
-        }
-
-        // Note: intervalId is now passed in URL, so we don't need to emit the event
-        // Keeping event emission as fallback for now, but URL should be primary method
-        println!("[WINDOW_MGR] Window created with intervalId={} in URL, emitting event as fallback", interval_id);
-        let emit_result = window.emit("prompt-interval-id", interval_id);
-        match emit_result {
-            Ok(_) => println!("[WINDOW_MGR] Event emitted successfully (fallback)"),
-            Err(e) => eprintln!("[WINDOW_MGR] Failed to emit interval ID (fallback): {}", e),
-        }
-
-        // Store window in state AFTER everything is set up
-        let mut prompt = self.prompt_window.lock().await;
-        *prompt = Some(window);
-
+        let app = self.app.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::Destroyed = event {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let tray_manager = app.state::<Arc<Mutex<TrayManager>>>();
+                    tray_manager.lock().await.summary_viewed().await;
+                });
+            }
+        });
+        
+        // A freshly created page may not be listening yet; it also loads the data itself on mount
+        let _ = emit_event(&self.app, AppEvent::SummaryData(payload));
+        window.set_focus().ok();
         Ok(())
     }
 
-    /// Show summary ready view (transitions from prompt to summary)
-    pub async fn show_summary_ready(&self) -> Result<(), String> {
-        let prompt = self.prompt_window.lock().await;
-        
-        if let Some(window) = prompt.as_ref() {
-            // Set summary ready state
-            *self.is_summary_ready.lock().await = true;
-            
-            // Emit event to show summary view
-            window
-                .emit("show-summary-ready", ())
-                .map_err(|e| format!("Failed to emit show-summary event: {}", e))?;
+    /// Show the read-only status page in its own window (for a second monitor or a door display)
+    pub async fn show_status_window(&self) -> Result<(), Log15Error> {
+        if let Some(window) = self.app.get_webview_window(STATUS_WINDOW_LABEL) {
+            window.show().map_err(|e| Log15Error::Window(format!("Failed to show status window: {}", e)))?;
+            window.set_focus().ok();
+            return Ok(());
         }
-
+        
+        println!("[WINDOW_MGR] Creating status window");
+        WebviewWindowBuilder::new(&self.app, STATUS_WINDOW_LABEL, WebviewUrl::App("index.html#/status".into()))
+            .title("Log15 - Status")
+            .inner_size(480.0, 240.0)
+            .min_inner_size(240.0, 120.0)
+            .resizable(true)
+            .center()
+            .visible(true)
+            .theme(crate::theme::window_theme(&self.app))
+            .build()
+            .map_err(|e| Log15Error::Window(format!("Failed to create status window: {}", e)))?;
         Ok(())
     }
 
     /// Hide the prompt window
-    /// Closes the window and clears all state
-    pub async fn hide_prompt_window(&self) -> Result<(), String> {
+    /// Keeps the window around (hidden) for the next interval and clears the interval state
+    pub async fn hide_prompt_window(&self) -> Result<(), Log15Error> {
         println!("[WINDOW_MGR] hide_prompt_window called");
         let mut prompt = self.prompt_window.lock().await;
         
@@ -193,10 +397,9 @@ impl WindowManager {
         let window_to_close = if let Some(window) = prompt.as_ref() {
             // Window exists in our state
             Some(window)
-        } else if let Some(window) = self.app.get_webview_window("prompt") {
-            // Window exists in Tauri but not in our state - restore it temporarily
-            // This can happen if state was cleared but window wasn't closed
-            println!("[WINDOW_MGR] Window exists in Tauri but not in state, closing it");
+        } else if let Some(window) = self.app.get_webview_window(PROMPT_WINDOW_LABEL) {
+            // Window exists in Tauri but not in our state - adopt it
+            println!("[WINDOW_MGR] Window exists in Tauri but not in state, hiding it");
             *prompt = Some(window);
             prompt.as_ref()
         } else {
@@ -205,52 +408,87 @@ impl WindowManager {
         };
         
         if let Some(window) = window_to_close {
-            // Check if summary is showing
-            let is_summary = *self.is_summary_ready.lock().await;
+            if !window.is_visible().unwrap_or(true) {
+                println!("[WINDOW_MGR] Prompt window already hidden");
+                *self.current_interval_id.lock().await = None;
+                return Ok(());
+            }
             
-            if is_summary {
-                // Emit close event for summary
-                window
-                    .emit("close-summary", ())
-                    .map_err(|e| format!("Failed to emit close-summary event: {}", e))?;
-                
-                // Wait for fade-out animation
-                tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
-                
-                // Reset summary state
-                *self.is_summary_ready.lock().await = false;
-            } else {
-                // #region agent log
-                use std::fs::OpenOptions;
-                use std::io::Write;
-                if let Ok(mut file) = OpenOptions::new().create(true).append(true).open("/Users/ronaldlin/log15/.cursor/debug.log") {
-                    let _ = writeln!(file, r#"{{"location":"window_manager.rs:198","message":"Emitting prompt-hide event","data":{{"timestamp":{}}},"timestamp":{},"sessionId":"debug-session","runId":"run1","hypothesisId":"A"}}"#, chrono::Utc::now().timestamp_millis(), chrono::Utc::now().timestamp_millis());
-                }
-                // #endregion
-                // Trigger fade-out animation (handled by frontend)
-                window
-                    .emit("prompt-hide", ())
-                    .map_err(|e| format!("Failed to emit hide event: {}", e))?;
-                
-                // Wait a bit for animation, then actually hide
-                tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+            // #region agent log
+            use std::fs::OpenOptions;
+            use std::io::Write;
+            if let Ok(mut file) = OpenOptions::new().create(true).append(true).open("/Users/ronaldlin/log15/.cursor/debug.log") {
+                let _ = writeln!(file, r#"{{"location":"window_manager.rs:198","message":"Emitting prompt-hide event","data":{{"timestamp":{}}},"timestamp":{},"sessionId":"debug-session","runId":"run1","hypothesisId":"A"}}"#, chrono::Utc::now().timestamp_millis(), chrono::Utc::now().timestamp_millis());
             }
+            // #endregion
+            // Trigger fade-out animation (handled by frontend)
+            emit_event(&self.app, AppEvent::PromptHide).map_err(Log15Error::Window)?;
             
-            // Close the window
-            window.close().map_err(|e| format!("Failed to close window: {}", e))?;
+            // Wait a bit for animation, then actually hide
+            tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+            
+            // Hide (not close) so the next interval can reuse the window
+            window.hide().map_err(|e| Log15Error::Window(format!("Failed to hide window: {}", e)))?;
             
-            // Clear all state
             *self.current_interval_id.lock().await = None;
-            *prompt = None;
-            println!("[WINDOW_MGR] Window closed successfully");
+            println!("[WINDOW_MGR] Window hidden successfully");
         }
 
         Ok(())
     }
     
-    /// Check if summary window is currently showing
-    pub async fn is_summary_ready(&self) -> bool {
-        *self.is_summary_ready.lock().await
+    /// Show the break overlay between workblocks (Pomodoro mode)
+    pub async fn show_break_window(&self, break_seconds: u64) -> Result<(), Log15Error> {
+        // Reuse an overlay left over from a previous break
+        if let Some(window) = self.app.get_webview_window("break") {
+            let _ = emit_event(&self.app, AppEvent::BreakOverlayReset(break_seconds));
+            window.show().map_err(|e| Log15Error::Window(format!("Failed to show break window: {}", e)))?;
+            return Ok(());
+        }
+
+        println!("[WINDOW_MGR] Creating break window for {}s", break_seconds);
+        let url = format!("index.html#/break?seconds={}", break_seconds);
+        let window = WebviewWindowBuilder::new(&self.app, "break", WebviewUrl::App(url.into()))
+            .title("Log15 - Break")
+            .inner_size(360.0, 200.0)
+            .decorations(false)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .center()
+            .visible(true)
+            .theme(crate::theme::window_theme(&self.app))
+            .build()
+            .map_err(|e| Log15Error::Window(format!("Failed to create break window: {}", e)))?;
+        window.set_focus().ok();
+
+        Ok(())
+    }
+
+    /// Close the break overlay if it is open
+    pub async fn hide_break_window(&self) -> Result<(), Log15Error> {
+        if let Some(window) = self.app.get_webview_window("break") {
+            window.close().map_err(|e| Log15Error::Window(format!("Failed to close break window: {}", e)))?;
+            println!("[WINDOW_MGR] Break window closed");
+        }
+        Ok(())
+    }
+
+    /// Restyle open windows when the theme preference changes
+    pub fn spawn_settings_listener(app: &AppHandle) {
+        crate::settings::spawn_settings_listener("window_manager", app, |app, change| async move {
+            if change.key.is_empty() || change.key == crate::settings::THEME_KEY {
+                crate::theme::apply_theme(&app);
+            }
+        });
+    }
+
+    /// Whether the prompt window exists and is on screen
+    pub async fn is_prompt_visible(&self) -> bool {
+        let prompt = self.prompt_window.lock().await;
+        prompt
+            .clone()
+            .or_else(|| self.app.get_webview_window(PROMPT_WINDOW_LABEL))
+            .is_some_and(|window| window.is_visible().unwrap_or(false))
     }
 
     /// Get current interval ID
@@ -0,0 +1,162 @@
+// Main window layout (size, position, last viewed date) saved in settings and restored on launch
+
+use crate::db::{get_db_connection, query_setting, set_setting, store_setting};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, LogicalPosition, LogicalSize, Manager, WebviewWindow, WindowEvent};
+
+pub const MAIN_WINDOW_LABEL: &str = "main";
+pub const MAIN_WINDOW_STATE_KEY: &str = "main_window_state";
+pub const LAST_VIEWED_DATE_KEY: &str = "last_viewed_date";
+
+/// Saved sizes below this are ignored (e.g. a window that was collapsed by the OS)
+pub const MIN_WINDOW_WIDTH: f64 = 400.0;
+pub const MIN_WINDOW_HEIGHT: f64 = 300.0;
+
+/// How much of the window's title bar must be on a monitor for the saved position to be used
+const VISIBLE_MARGIN: f64 = 50.0;
+
+/// Moves and resizes arrive in bursts; the layout is saved once they've been quiet this long
+const SAVE_DELAY_MS: u64 = 1000;
+
+/// Main window bounds in logical pixels
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct WindowState {
+    pub width: f64,
+    pub height: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A monitor's area in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Saved layout, or None if unset, malformed or implausibly small
+pub fn query_window_state(conn: &Connection) -> Option<WindowState> {
+    let stored = query_setting(conn, MAIN_WINDOW_STATE_KEY).ok().flatten()?;
+    let state: WindowState = serde_json::from_str(&stored).ok()?;
+    (state.width >= MIN_WINDOW_WIDTH && state.height >= MIN_WINDOW_HEIGHT).then_some(state)
+}
+
+pub fn store_window_state(conn: &Connection, state: &WindowState) -> rusqlite::Result<()> {
+    let value = serde_json::to_string(state).unwrap_or_default();
+    Ok(store_setting(conn, MAIN_WINDOW_STATE_KEY, &value)?)
+}
+
+/// Whether a window at `state` can be grabbed on one of `monitors`, so a layout saved on a
+/// monitor that is now disconnected doesn't open off-screen
+pub fn is_on_screen(state: &WindowState, monitors: &[MonitorRect]) -> bool {
+    monitors.iter().any(|m| {
+        state.x + state.width - VISIBLE_MARGIN > m.x
+            && state.x < m.x + m.width - VISIBLE_MARGIN
+            && state.y >= m.y
+            && state.y < m.y + m.height - VISIBLE_MARGIN
+    })
+}
+
+pub fn query_last_viewed_date(conn: &Connection) -> Option<String> {
+    let date = query_setting(conn, LAST_VIEWED_DATE_KEY).ok().flatten()?;
+    NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_ok().then_some(date)
+}
+
+pub fn get_last_viewed_date(app: &AppHandle) -> Option<String> {
+    get_db_connection(app).ok().and_then(|conn| query_last_viewed_date(&conn))
+}
+
+/// Remember the day the user last looked at; None forgets it
+pub fn set_last_viewed_date(app: &AppHandle, date: Option<&str>) -> Result<(), String> {
+    let date = date.map(str::trim).filter(|d| !d.is_empty());
+    if let Some(date) = date {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("Invalid date '{}'", date))?;
+    }
+    set_setting(app, LAST_VIEWED_DATE_KEY, date.unwrap_or("")).map_err(|e| e.to_string())
+}
+
+fn current_state(window: &WebviewWindow) -> Option<WindowState> {
+    let scale = window.scale_factor().ok()?;
+    let size = window.inner_size().ok()?.to_logical::<f64>(scale);
+    let position = window.outer_position().ok()?.to_logical::<f64>(scale);
+    Some(WindowState {
+        width: size.width,
+        height: size.height,
+        x: position.x,
+        y: position.y,
+    })
+}
+
+fn monitor_rects(window: &WebviewWindow) -> Vec<MonitorRect> {
+    window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|m| {
+            let scale = m.scale_factor();
+            MonitorRect {
+                x: m.position().x as f64 / scale,
+                y: m.position().y as f64 / scale,
+                width: m.size().width as f64 / scale,
+                height: m.size().height as f64 / scale,
+            }
+        })
+        .collect()
+}
+
+/// Apply the saved layout to the main window (which starts hidden) and show it
+pub fn restore_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return;
+    };
+    if let Some(state) = get_db_connection(app).ok().and_then(|conn| query_window_state(&conn)) {
+        let _ = window.set_size(LogicalSize::new(state.width, state.height));
+        if is_on_screen(&state, &monitor_rects(&window)) {
+            let _ = window.set_position(LogicalPosition::new(state.x, state.y));
+        } else {
+            let _ = window.center();
+        }
+        println!("[WINDOW_STATE] Restored main window to {}x{}", state.width, state.height);
+    }
+    let _ = window.show();
+    track_main_window(app, &window);
+}
+
+/// Incremented on every move/resize; a pending save only runs if no newer one was scheduled
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Save the main window's layout whenever it is moved or resized
+fn track_main_window(app: &AppHandle, window: &WebviewWindow) {
+    let app = app.clone();
+    window.on_window_event(move |event| {
+        if !matches!(event, WindowEvent::Moved(_) | WindowEvent::Resized(_)) {
+            return;
+        }
+        let generation = SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(SAVE_DELAY_MS)).await;
+            if SAVE_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+                return;
+            };
+            // Minimized or maximized bounds aren't a layout worth restoring
+            if window.is_minimized().unwrap_or(false) || window.is_maximized().unwrap_or(false) {
+                return;
+            }
+            let Some(state) = current_state(&window) else {
+                return;
+            };
+            if let Err(e) = get_db_connection(&app).and_then(|conn| Ok(store_window_state(&conn, &state)?)) {
+                eprintln!("[WINDOW_STATE] Failed to save main window layout: {}", e);
+            }
+        });
+    });
+}
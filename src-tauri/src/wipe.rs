@@ -0,0 +1,115 @@
+// Full data wipe: deletes every workblock, interval, archive and setting (and optionally the
+// backups), then leaves the app as it is on a fresh install.
+
+use crate::backup::{get_backup_dir, prune_backups};
+use crate::db::get_db_connection;
+use crate::events::{emit_event, AppEvent};
+use crate::timer::TimerManager;
+use crate::tray::TrayManager;
+use crate::window_manager::WindowManager;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+/// What the user has to type to confirm a wipe
+pub const WIPE_CONFIRM_TOKEN: &str = "DELETE ALL DATA";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct WipeSummary {
+    pub workblocks_deleted: usize,
+    pub intervals_deleted: usize,
+    pub archives_deleted: usize,
+    pub settings_deleted: usize,
+    pub attachments_deleted: usize,  // Files removed from managed storage
+    pub backups_deleted: usize,      // 0 unless backups were included
+}
+
+fn count_rows(conn: &Connection, table: &str) -> rusqlite::Result<usize> {
+    conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+}
+
+/// Empty every table in one transaction, and restart the ids. The schema itself is kept.
+pub fn wipe_database(conn: &Connection) -> rusqlite::Result<WipeSummary> {
+    let tx = conn.unchecked_transaction()?;
+    let summary = WipeSummary {
+        workblocks_deleted: count_rows(&tx, "workblocks")?,
+        intervals_deleted: count_rows(&tx, "intervals")?,
+        archives_deleted: count_rows(&tx, "daily_archives")?,
+        settings_deleted: count_rows(&tx, "settings")?,
+        ..Default::default()
+    };
+
+    // Every table, so ones added later are wiped too; sqlite_sequence holds the AUTOINCREMENT counters
+    let tables: Vec<String> = tx
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'")?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    for table in &tables {
+        tx.execute(&format!("DELETE FROM {}", table), [])?;
+    }
+    tx.execute("DELETE FROM sqlite_sequence", [])?;
+
+    tx.commit()?;
+    Ok(summary)
+}
+
+/// Delete every file in `dir`; returns how many were deleted
+fn delete_files_in(dir: &std::path::Path) -> Result<usize, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+    };
+    let mut deleted = 0;
+    for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|p| p.is_file()) {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+/// Wipe all data once `confirm_token` matches WIPE_CONFIRM_TOKEN. The running workblock is
+/// stopped first; afterwards default settings are seeded as on a first launch and "data-wiped"
+/// is emitted with the summary so the frontend can start over.
+pub async fn wipe_all_data(app: &AppHandle, confirm_token: &str, delete_backups: bool) -> Result<WipeSummary, String> {
+    if confirm_token != WIPE_CONFIRM_TOKEN {
+        return Err(format!("Type \"{}\" to confirm", WIPE_CONFIRM_TOKEN));
+    }
+
+    if let Some(timer_manager) = app.try_state::<Arc<Mutex<TimerManager>>>() {
+        timer_manager.lock().await.reset().await;
+    }
+    if let Some(window_manager) = app.try_state::<Arc<Mutex<WindowManager>>>() {
+        let window_mgr = window_manager.lock().await;
+        window_mgr.clear_prompt_queue().await;
+        let _ = window_mgr.hide_prompt_window().await;
+    }
+
+    let conn = get_db_connection(app).map_err(|e| e.to_string())?;
+    let mut summary = wipe_database(&conn).map_err(|e| format!("Wipe failed: {}", e))?;
+    crate::onboarding::initialize_first_run(&conn).map_err(|e| e.to_string())?;
+    crate::settings::load_day_start_hour(&conn);
+    drop(conn);
+
+    summary.attachments_deleted = delete_files_in(&crate::attachments::get_attachments_dir(app)?)?;
+    if delete_backups {
+        summary.backups_deleted = prune_backups(&get_backup_dir(app), 0)?;
+    }
+    crate::acked_events::clear_pending();
+    crate::cache::clear();
+
+    // Every setting is back to its default
+    crate::settings::notify_setting_changed(app, "", "");
+    if let Some(tray_manager) = app.try_state::<Arc<Mutex<TrayManager>>>() {
+        tray_manager.lock().await.reset().await;
+    }
+
+    println!(
+        "[WIPE] Deleted {} workblocks, {} intervals and {} archives",
+        summary.workblocks_deleted, summary.intervals_deleted, summary.archives_deleted
+    );
+    let _ = emit_event(app, AppEvent::DataWiped(summary.clone()));
+    Ok(summary)
+}
@@ -0,0 +1,59 @@
+// Test acknowledged event bookkeeping
+// Run with: cargo test --test acked_events_test
+
+use chrono::{Duration, Local};
+use log15_lib::acked_events::{PendingEvents, MAX_PENDING_EVENTS, PENDING_EVENT_TTL_MINUTES};
+use serde_json::json;
+
+#[test]
+fn test_ack_and_replay() {
+    let now = Local::now();
+    let mut events = PendingEvents::new();
+    
+    let first = events.push("interval-complete", json!({"interval_id": 7}), now);
+    let second = events.push("workblock-complete", json!(3), now);
+    assert_eq!((first.seq, second.seq), (1, 2));
+    assert_eq!(first.payload["interval_id"], 7);
+    
+    // Unacknowledged events are replayed in order
+    let replay = events.replayable(now);
+    assert_eq!(replay.len(), 2);
+    assert_eq!(replay[0].0, "interval-complete");
+    assert_eq!(replay[1].1, second);
+    
+    assert!(events.ack(first.seq));
+    assert!(!events.ack(first.seq));
+    let replay = events.replayable(now);
+    assert_eq!(replay.len(), 1);
+    assert_eq!(replay[0].1.seq, second.seq);
+    
+    // Clearing drops what's pending; numbering carries on
+    assert_eq!(events.clear(), 1);
+    assert!(events.replayable(now).is_empty());
+    assert_eq!(events.push("workblock-complete", json!(4), now).seq, 3);
+    
+    println!("✓ Test: Ack and replay passed");
+}
+
+#[test]
+fn test_pending_events_are_bounded() {
+    let now = Local::now();
+    let mut events = PendingEvents::new();
+    
+    // Expired events are dropped rather than replayed
+    events.push("workblock-complete", json!(1), now - Duration::minutes(PENDING_EVENT_TTL_MINUTES + 1));
+    events.push("workblock-complete", json!(2), now);
+    let replay = events.replayable(now);
+    assert_eq!(replay.len(), 1);
+    assert_eq!(replay[0].1.payload, json!(2));
+    
+    // Only the newest MAX_PENDING_EVENTS are kept
+    for i in 0..MAX_PENDING_EVENTS + 10 {
+        events.push("interval-complete", json!(i), now);
+    }
+    let replay = events.replayable(now);
+    assert_eq!(replay.len(), MAX_PENDING_EVENTS);
+    assert_eq!(replay[0].1.payload, json!(10));
+    
+    println!("✓ Test: Pending events bounded passed");
+}
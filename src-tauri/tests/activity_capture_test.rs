@@ -0,0 +1,119 @@
+// Test opt-in capture of focused applications per interval
+// Run with: cargo test --test activity_capture_test
+
+use log15_lib::activity_capture::*;
+use log15_lib::db::create_schema;
+use log15_lib::settings::{query_activity_capture_settings, ActivityCaptureSettings};
+use rusqlite::{params, Connection};
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn
+}
+
+fn insert_interval(conn: &Connection, workblock_id: i64, number: i32) -> i64 {
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, status)
+         VALUES (?1, ?2, '2024-05-01T09:00:00+00:00', 'pending')",
+        params![workblock_id, number],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+#[test]
+fn test_app_usage_per_interval_and_workblock() {
+    let conn = setup();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 30, 'active', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    let first = insert_interval(&conn, workblock_id, 1);
+    let second = insert_interval(&conn, workblock_id, 2);
+
+    // Repeated samples of the same app and title add up
+    record_app_sample(&conn, first, "Code", Some("main.rs"), 10).unwrap();
+    record_app_sample(&conn, first, "Code", Some("main.rs"), 10).unwrap();
+    record_app_sample(&conn, first, "Firefox", None, 10).unwrap();
+    record_app_sample(&conn, second, "Code", None, 40).unwrap();
+
+    let usage = query_interval_app_usage(&conn, first).unwrap();
+    assert_eq!(usage.len(), 2);
+    assert_eq!(usage[0].app_name, "Code");
+    assert_eq!(usage[0].window_title.as_deref(), Some("main.rs"));
+    assert_eq!(usage[0].seconds, 20);
+    assert_eq!(usage[1].window_title, None);
+
+    // The workblock view merges titles per app
+    let apps = query_workblock_app_usage(&conn, workblock_id).unwrap();
+    assert_eq!(apps.len(), 2);
+    assert_eq!((apps[0].app_name.as_str(), apps[0].seconds), ("Code", 60));
+    assert!((apps[0].percentage - 85.714).abs() < 0.01);
+    assert_eq!((apps[1].app_name.as_str(), apps[1].seconds), ("Firefox", 10));
+
+    // Clearing removes everything
+    assert_eq!(clear_app_usage(&conn).unwrap(), 3);
+    assert!(query_workblock_app_usage(&conn, workblock_id).unwrap().is_empty());
+
+    println!("✓ Test: App usage per interval and workblock passed");
+}
+
+#[test]
+fn test_activity_capture_is_opt_in() {
+    let conn = setup();
+    assert_eq!(query_activity_capture_settings(&conn), ActivityCaptureSettings::default());
+    assert!(!ActivityCaptureSettings::default().enabled);
+    assert!(!ActivityCaptureSettings::default().window_titles);
+
+    println!("✓ Test: Activity capture is opt-in passed");
+}
+
+#[test]
+fn test_parse_focused_window_sources() {
+    let window = parse_osascript_output("Safari\nlog15 - GitHub\n").unwrap();
+    assert_eq!(window.app_name, "Safari");
+    assert_eq!(window.title.as_deref(), Some("log15 - GitHub"));
+    assert_eq!(parse_osascript_output("Finder\n\n").unwrap().title, None);
+    assert_eq!(parse_osascript_output(""), None);
+
+    assert_eq!(parse_xdotool_output("4242\nTerminal\n"), Some((4242, Some("Terminal".to_string()))));
+    assert_eq!(parse_xdotool_output("XGetWindowProperty failed"), None);
+
+    assert_eq!(app_name_from_path(r"C:\Program Files\Microsoft VS Code\Code.exe"), "Code");
+    assert_eq!(app_name_from_path("/usr/bin/firefox"), "firefox");
+
+    println!("✓ Test: Focused window source parsing passed");
+}
+
+#[test]
+fn test_time_audit() {
+    use log15_lib::time_audit::query_time_audit;
+    
+    let conn = setup();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 60, 'completed', 0)",
+        [],
+    ).unwrap();
+    
+    // Without activity capture only tracked time is known
+    let audit = query_time_audit(&conn, "2024-05-01", chrono::Local::now()).unwrap();
+    assert_eq!(audit.tracked_minutes, 60);
+    assert_eq!((audit.active_computer_minutes, audit.untracked_computer_minutes, audit.meeting_minutes), (None, None, None));
+    
+    record_computer_activity(&conn, "2024-05-01", 3600, true).unwrap();
+    record_computer_activity(&conn, "2024-05-01", 1200, false).unwrap();
+    record_computer_activity(&conn, "2024-05-01", 600, false).unwrap();
+    let audit = query_time_audit(&conn, "2024-05-01", chrono::Local::now()).unwrap();
+    assert_eq!(audit.active_computer_minutes, Some(90));
+    assert_eq!(audit.untracked_computer_minutes, Some(30));
+    assert!((audit.untracked_percentage.unwrap() - 33.333).abs() < 0.01);
+    
+    // Clearing captured data removes the daily totals too
+    clear_app_usage(&conn).unwrap();
+    assert_eq!(query_computer_activity(&conn, "2024-05-01").unwrap(), None);
+    
+    println!("✓ Test: Time audit passed");
+}
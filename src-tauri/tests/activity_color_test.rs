@@ -0,0 +1,101 @@
+// Test the activity color registry: palette assignment for new activities and colors in ActivityData
+// Run with: cargo test --test activity_color_test
+
+use chrono::{Duration, Local, TimeZone, Utc};
+use log15_lib::db::*;
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+
+#[test]
+fn test_palette_color() {
+    let mut taken = HashSet::new();
+    assert_eq!(palette_color("coding", &taken), ACTIVITY_PALETTE[0]);
+    taken.insert(ACTIVITY_PALETTE[0].to_string());
+    assert_eq!(palette_color("coding", &taken), ACTIVITY_PALETTE[1]);
+    
+    // Once the palette is used up the activity's name picks the color, the same every time
+    let taken: HashSet<String> = ACTIVITY_PALETTE.iter().map(|c| c.to_string()).collect();
+    let color = palette_color("email", &taken);
+    assert!(ACTIVITY_PALETTE.contains(&color.as_str()));
+    assert_eq!(palette_color("email", &taken), color);
+    
+    println!("✓ Test: Palette color passed");
+}
+
+#[test]
+fn test_assign_activity_colors() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    store_activity_color(&conn, "lunch", ACTIVITY_PALETTE[0]).unwrap();
+    
+    let colors = assign_activity_colors(&conn, &["Coding".to_string(), "lunch".to_string()]).unwrap();
+    assert_eq!(colors["lunch"], ACTIVITY_PALETTE[0]);  // Chosen colors are kept
+    assert_eq!(colors["coding"], ACTIVITY_PALETTE[1]);
+    
+    // Assigned colors are stored, so the activity keeps its color on other days
+    assert_eq!(query_activity_colors(&conn).unwrap().len(), 2);
+    assert_eq!(assign_activity_colors(&conn, &["coding".to_string()]).unwrap()["coding"], ACTIVITY_PALETTE[1]);
+    
+    println!("✓ Test: Assign activity colors passed");
+}
+
+#[test]
+fn test_summary_activity_colors() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    store_activity_color(&conn, "email", "#123456").unwrap();
+    let start = Local.with_ymd_and_hms(2024, 5, 6, 9, 0, 0).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-06', ?1, 30, 'completed', 0)",
+        params![start.to_rfc3339()],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    for (i, words) in ["coding", "coding", "email"].iter().enumerate() {
+        let interval_start = start + Duration::minutes(i as i64 * 10);
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, 'recorded')",
+            params![workblock_id, i as i32 + 1, interval_start.to_rfc3339(), (interval_start + Duration::minutes(10)).to_rfc3339(), words],
+        ).unwrap();
+    }
+    
+    let summary = query_workblock_summary(&conn, workblock_id).unwrap();
+    let colors: Vec<(&str, Option<&str>)> = summary.top_activities.iter().map(|a| (a.words.as_str(), a.color.as_deref())).collect();
+    assert_eq!(colors, vec![("coding", Some(ACTIVITY_PALETTE[0])), ("email", Some("#123456"))]);
+    // Reading the summary doesn't store colors
+    assert_eq!(query_activity_colors(&conn).unwrap().len(), 1);
+    
+    println!("✓ Test: Summary activity colors passed");
+}
+
+#[test]
+fn test_colors_assigned_when_recorded() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute("INSERT INTO activity_aliases (alias, canonical) VALUES ('emails', 'email')", []).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-06', '2024-05-06T09:00:00+00:00', 30, 'active', 0)",
+        [],
+    ).unwrap();
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, status) VALUES (?1, 1, '2024-05-06T09:00:00+00:00', 'pending')",
+        params![conn.last_insert_rowid()],
+    ).unwrap();
+    let interval_id = conn.last_insert_rowid();
+    
+    // Answering stores a color for the canonical activity, and each activity of a split
+    store_interval_words(&conn, interval_id, "Emails", IntervalStatus::Recorded, Utc::now()).unwrap();
+    store_activity_splits(&conn, interval_id, &[
+        ActivitySplit { words: "email".to_string(), percentage: 50 },
+        ActivitySplit { words: "Coding".to_string(), percentage: 50 },
+    ]).unwrap();
+    let stored: Vec<(String, String)> = query_activity_colors(&conn).unwrap().into_iter().map(|c| (c.activity, c.color)).collect();
+    assert_eq!(stored, vec![
+        ("coding".to_string(), ACTIVITY_PALETTE[1].to_string()),
+        ("email".to_string(), ACTIVITY_PALETTE[0].to_string()),
+    ]);
+    
+    println!("✓ Test: Colors assigned when recorded passed");
+}
@@ -0,0 +1,121 @@
+// Test intervals split between several activities: validation, stored splits and apportioned minutes
+// Run with: cargo test --test activity_split_test
+
+use chrono::{Duration, Local, TimeZone, Utc};
+use log15_lib::db::*;
+use rusqlite::{params, Connection};
+
+fn split(words: &str, percentage: i32) -> ActivitySplit {
+    ActivitySplit { words: words.to_string(), percentage }
+}
+
+#[test]
+fn test_validate_activity_splits() {
+    let splits = validate_activity_splits(&[split(" coding ", 60), split("meeting", 40)]).unwrap();
+    assert_eq!(splits, vec![split("coding", 60), split("meeting", 40)]);
+    assert_eq!(split_words(&splits), "coding + meeting");
+    
+    assert!(validate_activity_splits(&[split("coding", 100)]).is_err());
+    assert!(validate_activity_splits(&[split("coding", 60), split("meeting", 30)]).is_err());
+    assert!(validate_activity_splits(&[split("coding", 100), split("meeting", 0)]).is_err());
+    assert!(validate_activity_splits(&[split("coding", 50), split("  ", 50)]).is_err());
+    let too_many: Vec<ActivitySplit> = (0..=MAX_ACTIVITY_SPLITS).map(|n| split(&format!("task {}", n), 20)).collect();
+    assert!(validate_activity_splits(&too_many).is_err());
+    
+    println!("✓ Test: Validate activity splits passed");
+}
+
+#[test]
+fn test_apportion_minutes() {
+    assert_eq!(apportion_minutes("coding", &[], 15), vec![("coding".to_string(), 15)]);
+    assert_eq!(
+        apportion_minutes("coding + meeting", &[split("coding", 50), split("meeting", 50)], 15),
+        vec![("coding".to_string(), 8), ("meeting".to_string(), 7)]
+    );
+    assert_eq!(
+        apportion_minutes("a + b + c", &[split("a", 34), split("b", 33), split("c", 33)], 30),
+        vec![("a".to_string(), 10), ("b".to_string(), 10), ("c".to_string(), 10)]
+    );
+    
+    println!("✓ Test: Apportion minutes passed");
+}
+
+#[test]
+fn test_split_interval_in_summary() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let start = Local.with_ymd_and_hms(2024, 5, 6, 9, 0, 0).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-06', ?1, 40, 'completed', 0)",
+        params![start.to_rfc3339()],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    
+    let mut interval_ids = Vec::new();
+    for (i, words) in ["coding", "coding + meeting"].iter().enumerate() {
+        let interval_start = start + Duration::minutes(i as i64 * 20);
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, 'recorded')",
+            params![workblock_id, i as i32 + 1, interval_start.to_rfc3339(), (interval_start + Duration::minutes(20)).to_rfc3339(), words],
+        ).unwrap();
+        interval_ids.push(conn.last_insert_rowid());
+    }
+    store_activity_splits(&conn, interval_ids[1], &[split("coding", 25), split("Meeting", 75)]).unwrap();
+    assert_eq!(query_activity_splits_by_workblock(&conn, workblock_id).unwrap().len(), 1);
+    
+    // The split interval's 20 minutes go 5 to coding and 15 to the meeting
+    let top = |conn: &Connection| -> Vec<(String, i32)> {
+        query_workblock_summary(conn, workblock_id).unwrap()
+            .top_activities
+            .into_iter()
+            .map(|a| (a.words, a.total_minutes))
+            .collect()
+    };
+    assert_eq!(top(&conn), vec![("coding".to_string(), 25), ("meeting".to_string(), 15)]);
+    
+    // Answering the interval again replaces the split
+    store_interval_words(&conn, interval_ids[1], "meeting", IntervalStatus::Recorded, Utc::now()).unwrap();
+    assert!(query_activity_splits_by_workblock(&conn, workblock_id).unwrap().is_empty());
+    assert_eq!(top(&conn), vec![("coding".to_string(), 20), ("meeting".to_string(), 20)]);
+    
+    println!("✓ Test: Split interval in summary passed");
+}
+
+#[test]
+fn test_removing_pending_interval_removes_its_split() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-06', '2024-05-06T09:00:00+00:00', 30, 'active', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    let mut interval_ids = Vec::new();
+    for (number, status) in [(1, "recorded"), (2, "pending")] {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+             VALUES (?1, ?2, '2024-05-06T09:00:00+00:00', 'coding + meeting', ?3)",
+            params![workblock_id, number, status],
+        ).unwrap();
+        let interval_id = conn.last_insert_rowid();
+        store_activity_splits(&conn, interval_id, &[split("coding", 50), split("meeting", 50)]).unwrap();
+        interval_ids.push(interval_id);
+    }
+    let split_rows = |conn: &Connection, interval_id: i64| -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM interval_activities WHERE interval_id = ?1", params![interval_id], |row| row.get(0)).unwrap()
+    };
+    
+    // An answered interval keeps its split
+    assert!(!remove_pending_interval(&conn, interval_ids[0]).unwrap());
+    assert_eq!(split_rows(&conn, interval_ids[0]), 2);
+    
+    // A pending one goes with its split, so a reused id doesn't inherit it
+    assert!(remove_pending_interval(&conn, interval_ids[1]).unwrap());
+    assert_eq!(split_rows(&conn, interval_ids[1]), 0);
+    assert_eq!(query_intervals_by_workblock(&conn, workblock_id).unwrap().len(), 1);
+    
+    println!("✓ Test: Removing pending interval removes its split passed");
+}
@@ -0,0 +1,87 @@
+// Test AI summaries: what is sent, how responses are read and where summaries are stored
+// Run with: cargo test --test ai_test
+
+use log15_lib::ai::*;
+use log15_lib::db::{create_schema, query_archived_day};
+use log15_lib::settings::{query_ai_settings, AiSettings};
+use rusqlite::{params, Connection};
+use serde_json::json;
+
+fn setup_day(conn: &Connection) {
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, title, project) VALUES ('2024-05-06', '2024-05-06T09:00:00+00:00', 60, 'completed', 'Secret title', 'Client X')",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    let intervals = [
+        (2, "09:15", Some("reviewed PRs"), "recorded"),
+        (1, "09:00", Some("wrote release notes"), "recorded"),
+        (3, "09:30", Some("Away from workspace"), "away"),
+        (4, "09:45", Some("  "), "recorded"),
+    ];
+    for (number, time, words, status) in intervals {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status, notes) VALUES (?1, ?2, ?3, ?4, ?5, 'private note')",
+            params![workblock_id, number, format!("2024-05-06T{}:00+00:00", time), words, status],
+        ).unwrap();
+    }
+}
+
+#[test]
+fn test_only_interval_words_are_sent() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    setup_day(&conn);
+    
+    let words = query_day_words(&conn, "2024-05-06").unwrap();
+    assert_eq!(words, vec!["wrote release notes", "reviewed PRs"]);
+    assert!(query_day_words(&conn, "2024-05-07").unwrap().is_empty());
+    
+    let request = build_summary_request("gpt-4o-mini", &words);
+    assert_eq!(request["model"], "gpt-4o-mini");
+    assert_eq!(request["messages"][1]["content"], "- wrote release notes\n- reviewed PRs");
+    let sent = request.to_string();
+    for private in ["Secret title", "Client X", "private note", "2024-05-06"] {
+        assert!(!sent.contains(private), "{} was sent", private);
+    }
+    
+    println!("✓ Test: Only interval words are sent passed");
+}
+
+#[test]
+fn test_chat_completions_url_and_response() {
+    assert_eq!(chat_completions_url("https://api.openai.com/v1"), "https://api.openai.com/v1/chat/completions");
+    assert_eq!(chat_completions_url("http://localhost:11434/v1/ "), "http://localhost:11434/v1/chat/completions");
+    assert_eq!(chat_completions_url("http://host/v1/chat/completions"), "http://host/v1/chat/completions");
+    
+    let response = json!({ "choices": [{ "message": { "role": "assistant", "content": "  You shipped the release.\n" } }] });
+    assert_eq!(parse_summary_response(&response).unwrap(), "You shipped the release.");
+    let error = json!({ "error": { "message": "Invalid API key" } });
+    assert!(parse_summary_response(&error).unwrap_err().contains("Invalid API key"));
+    assert!(parse_summary_response(&json!({ "choices": [] })).is_err());
+    
+    println!("✓ Test: Chat completions URL and response passed");
+}
+
+#[test]
+fn test_store_ai_summary() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    
+    // No archive to keep it with yet
+    assert!(!store_ai_summary(&conn, "2024-05-06", "You shipped the release.").unwrap());
+    
+    conn.execute(
+        "INSERT INTO daily_archives (date, total_workblocks, total_minutes, archived_at) VALUES ('2024-05-06', 1, 60, '2024-05-07T00:00:00+00:00')",
+        [],
+    ).unwrap();
+    assert!(store_ai_summary(&conn, "2024-05-06", "You shipped the release.").unwrap());
+    let archive = query_archived_day(&conn, "2024-05-06").unwrap().unwrap();
+    assert_eq!(archive.ai_summary.as_deref(), Some("You shipped the release."));
+    
+    // Off until the user opts in
+    assert_eq!(query_ai_settings(&conn), AiSettings::default());
+    assert!(!AiSettings::default().enabled);
+    
+    println!("✓ Test: Store AI summary passed");
+}
@@ -366,3 +366,37 @@ fn test_actual_archiving_function() {
     // Cleanup
     std::fs::remove_file(get_test_db_path()).ok();
 }
+
+#[test]
+fn test_archive_history() {
+    use log15_lib::db::{create_schema, query_archive_history, query_archive_visualization};
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    for day in 1..=5 {
+        conn.execute(
+            "INSERT INTO daily_archives (date, total_workblocks, total_minutes, visualization_data, archived_at)
+             VALUES (?1, ?2, ?3, ?4, ?1)",
+            rusqlite::params![format!("2024-05-0{}", day), day, day * 60, json!({"day": day}).to_string()],
+        ).unwrap();
+    }
+    
+    // Newest first, paginated without the visualization blob
+    let page = query_archive_history(&conn, 0, 2).unwrap();
+    assert_eq!(page.total_count, 5);
+    assert!(page.has_more);
+    let dates: Vec<&str> = page.archives.iter().map(|a| a.date.as_str()).collect();
+    assert_eq!(dates, vec!["2024-05-05", "2024-05-04"]);
+    assert_eq!(page.archives[0].total_minutes, 300);
+    
+    let last = query_archive_history(&conn, 4, 2).unwrap();
+    assert_eq!(last.archives.len(), 1);
+    assert!(!last.has_more);
+    
+    // Visualization loads per day
+    let viz: Value = query_archive_visualization(&conn, "2024-05-03").unwrap().unwrap();
+    assert_eq!(viz["day"], 3);
+    assert_eq!(query_archive_visualization(&conn, "2024-06-01").unwrap(), None);
+    
+    println!("✓ Test: Archive history passed");
+}
@@ -0,0 +1,92 @@
+// Test database backups: snapshot, rotation and restore
+// Run with: cargo test --test backup_test
+
+use chrono::{Duration, TimeZone, Utc};
+use log15_lib::backup::*;
+use log15_lib::db::create_schema;
+use rusqlite::Connection;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn test_dir() -> PathBuf {
+    let counter = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let dir = std::env::temp_dir().join(format!("log15_backup_test_{}_{}", std::process::id(), counter));
+    std::fs::remove_dir_all(&dir).ok();
+    dir
+}
+
+fn workblock_count(conn: &Connection) -> i32 {
+    conn.query_row("SELECT COUNT(*) FROM workblocks", [], |row| row.get(0)).unwrap()
+}
+
+fn insert_workblock(conn: &Connection) {
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 60, 'completed', 0)",
+        [],
+    ).unwrap();
+}
+
+#[test]
+fn test_backup_file_names() {
+    let created_at = Utc.with_ymd_and_hms(2024, 5, 1, 7, 0, 0).unwrap() + Duration::milliseconds(123);
+    let name = backup_file_name(created_at, BackupReason::PreRestore);
+    assert_eq!(name, "log15-20240501-070000-123-pre_restore.db");
+    assert_eq!(parse_backup_file_name(&name), Some((created_at, BackupReason::PreRestore)));
+    
+    assert_eq!(parse_backup_file_name("log15.db"), None);
+    assert_eq!(parse_backup_file_name("log15-20240501-070000-123-unknown.db"), None);
+    assert_eq!(parse_backup_file_name("notes.txt"), None);
+    
+    println!("✓ Test: Backup file names passed");
+}
+
+#[test]
+fn test_backup_rotation_and_restore() {
+    let dir = test_dir();
+    let mut conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    insert_workblock(&conn);
+    
+    let start = Utc.with_ymd_and_hms(2024, 5, 1, 7, 0, 0).unwrap();
+    let first = create_backup_in(&conn, &dir, BackupReason::Manual, start).unwrap();
+    assert_eq!(first.reason, BackupReason::Manual);
+    assert!(first.size_bytes > 0);
+    
+    // Rotation keeps the newest MAX_BACKUPS, newest first
+    for i in 1..=MAX_BACKUPS as i64 {
+        create_backup_in(&conn, &dir, BackupReason::Startup, start + Duration::minutes(i)).unwrap();
+    }
+    let backups = list_backups_in(&dir).unwrap();
+    assert_eq!(backups.len(), MAX_BACKUPS);
+    assert!(backups.iter().all(|b| b.file_name != first.file_name));
+    assert!(backups[0].created_at > backups[1].created_at);
+    
+    // Restoring brings back the snapshot's data
+    let snapshot = PathBuf::from(&backups[0].path);
+    insert_workblock(&conn);
+    assert_eq!(workblock_count(&conn), 2);
+    restore_backup_into(&mut conn, &snapshot).unwrap();
+    assert_eq!(workblock_count(&conn), 1);
+    
+    // Anything that isn't a log15 database is refused
+    let other = dir.join("other.db");
+    Connection::open(&other).unwrap().execute("CREATE TABLE t (x INTEGER)", []).unwrap();
+    assert!(restore_backup_into(&mut conn, &other).is_err());
+    assert_eq!(workblock_count(&conn), 1);
+    
+    // Restoring the oldest of MAX_BACKUPS works: the pre-restore backup only prunes afterwards
+    let oldest = list_backups_in(&dir).unwrap().pop().unwrap();
+    insert_workblock(&conn);
+    let safety = restore_backup_in(&mut conn, &dir, &PathBuf::from(&oldest.path), start + Duration::hours(1)).unwrap();
+    assert_eq!(safety.reason, BackupReason::PreRestore);
+    assert_eq!(workblock_count(&conn), 1);
+    let backups = list_backups_in(&dir).unwrap();
+    assert_eq!(backups.len(), MAX_BACKUPS);
+    assert_eq!(backups[0].file_name, safety.file_name);
+    
+    std::fs::remove_dir_all(&dir).ok();
+    println!("✓ Test: Backup rotation and restore passed");
+}
@@ -0,0 +1,80 @@
+// Test the daily aggregate cache: what a delta carries after interval and workblock writes
+// Run with: cargo test --test cache_test
+
+use log15_lib::cache::*;
+use log15_lib::db::{build_daily_aggregate, create_schema, store_interval_words, DailyAggregate, IntervalStatus, VisualizationSource};
+use rusqlite::{params, Connection};
+
+const DATE: &str = "2024-05-06";
+
+fn aggregate(conn: &Connection) -> DailyAggregate {
+    let source = VisualizationSource::load_day(conn, DATE).unwrap();
+    build_daily_aggregate(conn, &source, DATE).unwrap()
+}
+
+fn add_interval(conn: &Connection, workblock_id: i64, number: i32, words: Option<&str>) -> i64 {
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            workblock_id,
+            number,
+            format!("{}T09:{:02}:00+00:00", DATE, (number - 1) * 15),
+            format!("{}T09:{:02}:00+00:00", DATE, number * 15),
+            words,
+            if words.is_some() { "recorded" } else { "pending" },
+        ],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+#[test]
+fn test_aggregate_delta() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES (?1, ?2, 60, 'active')",
+        params![DATE, format!("{}T09:00:00+00:00", DATE)],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    add_interval(&conn, workblock_id, 1, Some("coding"));
+    let pending = add_interval(&conn, workblock_id, 2, None);
+    let before = aggregate(&conn);
+
+    // Nothing written, nothing sent
+    assert!(aggregate_delta(DATE, &before, &aggregate(&conn)).is_none());
+
+    // Answering an interval changes only its timeline entry; the new activity shifts both percentages
+    store_interval_words(&conn, pending, "meeting", IntervalStatus::Recorded, chrono::Utc::now()).unwrap();
+    let answered = aggregate(&conn);
+    let delta = aggregate_delta(DATE, &before, &answered).unwrap();
+    assert_eq!(delta.date, DATE);
+    assert_eq!(delta.timeline_upserted.len(), 1);
+    assert_eq!(delta.timeline_upserted[0].words.as_deref(), Some("meeting"));
+    assert!(delta.timeline_removed.is_empty());
+    let mut activities: Vec<&str> = delta.activities_upserted.iter().map(|a| a.words.as_str()).collect();
+    activities.sort();
+    assert_eq!(activities, vec!["coding", "meeting"]);
+    assert!(delta.activities_removed.is_empty());
+    assert!(delta.workblock_boundaries.is_none());
+    assert!(delta.word_frequency.is_some());
+
+    // Deleting an interval removes its entry and, with it, its activity
+    conn.execute("DELETE FROM intervals WHERE id = ?1", params![pending]).unwrap();
+    let delta = aggregate_delta(DATE, &answered, &aggregate(&conn)).unwrap();
+    assert!(delta.timeline_upserted.is_empty());
+    assert_eq!(delta.timeline_removed, vec![TimelineEntryKey { workblock_id, interval_number: 2 }]);
+    assert_eq!(delta.activities_removed, vec!["meeting".to_string()]);
+
+    // Ending the workblock changes its boundary and the totals
+    conn.execute(
+        "UPDATE workblocks SET status = 'completed', end_time = ?1, duration_minutes = 15 WHERE id = ?2",
+        params![format!("{}T09:15:00+00:00", DATE), workblock_id],
+    ).unwrap();
+    let ended = aggregate(&conn);
+    let delta = aggregate_delta(DATE, &before, &ended).unwrap();
+    assert_eq!(delta.total_minutes, 15);
+    assert_eq!(delta.workblock_boundaries.unwrap()[0].status, "completed");
+
+    println!("✓ Test: Aggregate delta passed");
+}
@@ -0,0 +1,67 @@
+// Test incremental change sets: stamping on insert and update, tombstones and cursors
+// Run with: cargo test --test changes_test
+
+use log15_lib::changes::*;
+use log15_lib::db::{create_schema, store_interval_notes};
+use log15_lib::error::Log15Error;
+use rusqlite::{params, Connection};
+
+fn wait_a_moment() {
+    // Stamps have millisecond precision
+    std::thread::sleep(std::time::Duration::from_millis(5));
+}
+
+#[test]
+fn test_changes_since() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 60, 'completed')",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+         VALUES (?1, 1, '2024-05-01T09:00:00+00:00', 'wrote tests', 'recorded')",
+        params![workblock_id],
+    ).unwrap();
+    let interval_id = conn.last_insert_rowid();
+    conn.execute(
+        "INSERT INTO daily_archives (date, total_workblocks, total_minutes, archived_at) VALUES ('2024-05-01', 1, 60, '2024-05-02T00:00:00+00:00')",
+        [],
+    ).unwrap();
+    
+    // Everything is new to a first sync
+    wait_a_moment();
+    let first = query_changes_since(&conn, "2000-01-01T00:00:00Z").unwrap();
+    assert_eq!(first.since, "2000-01-01T00:00:00+00:00");
+    assert_eq!(first.workblocks.len(), 1);
+    assert_eq!(first.intervals.len(), 1);
+    assert_eq!(first.archives.len(), 1);
+    assert!(first.deleted.is_empty());
+    
+    // Nothing changed since the cursor
+    wait_a_moment();
+    let quiet = query_changes_since(&conn, &first.until).unwrap();
+    assert!(quiet.workblocks.is_empty() && quiet.intervals.is_empty() && quiet.archives.is_empty());
+    
+    // Editing an interval sends only that interval
+    wait_a_moment();
+    store_interval_notes(&conn, interval_id, Some("with a fake clock")).unwrap();
+    let edited = query_changes_since(&conn, &quiet.until).unwrap();
+    assert!(edited.workblocks.is_empty());
+    assert_eq!(edited.intervals.len(), 1);
+    assert_eq!(edited.intervals[0].notes.as_deref(), Some("with a fake clock"));
+    
+    // Deletions leave tombstones
+    wait_a_moment();
+    conn.execute("DELETE FROM intervals WHERE id = ?1", params![interval_id]).unwrap();
+    conn.execute("DELETE FROM workblocks WHERE id = ?1", params![workblock_id]).unwrap();
+    let deleted = query_changes_since(&conn, &edited.until).unwrap();
+    let tombstones: Vec<(&str, i64)> = deleted.deleted.iter().map(|d| (d.table_name.as_str(), d.row_id)).collect();
+    assert_eq!(tombstones, vec![("intervals", interval_id), ("workblocks", workblock_id)]);
+    
+    assert!(matches!(query_changes_since(&conn, "yesterday"), Err(Log15Error::InvalidInput(_))));
+    
+    println!("✓ Test: Changes since passed");
+}
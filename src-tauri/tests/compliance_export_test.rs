@@ -0,0 +1,81 @@
+// Test the compliance CSV: no entry text, daily totals, locale formatting and the hash line
+// Run with: cargo test --test compliance_export_test
+
+use log15_lib::compliance_export::*;
+use log15_lib::db::create_schema;
+use log15_lib::format::Formatter;
+use log15_lib::settings::{DateOrder, DecimalSeparator, LocaleSettings, TimeFormat};
+use rusqlite::{params, Connection};
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let workblocks = [
+        ("2024-05-01", "2024-05-01T07:00:00+00:00", 60, "completed"),
+        ("2024-05-01", "2024-05-01T09:00:00+00:00", 30, "cancelled"),
+        ("2024-05-02", "2024-05-02T07:00:00+00:00", 90, "completed"),
+        ("2024-05-02", "2024-05-02T12:00:00+00:00", 60, "active"),
+        ("2024-05-03", "2024-05-03T07:00:00+00:00", 45, "completed"),
+    ];
+    for (date, start, minutes, status) in workblocks {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, end_time, duration_minutes, status, is_archived, title)
+             VALUES (?1, ?2, ?2, ?3, ?4, 0, 'Secret project')",
+            params![date, start, minutes, status],
+        ).unwrap();
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+             VALUES (?1, 1, ?2, 'private words', 'recorded')",
+            params![conn.last_insert_rowid(), start],
+        ).unwrap();
+    }
+    conn
+}
+
+#[test]
+fn test_compliance_csv_content() {
+    let conn = setup();
+    let workblocks = query_compliance_workblocks(&conn, "2024-05-01", "2024-05-02").unwrap();
+    assert_eq!(workblocks.len(), 3);  // Active workblocks and other days are left out
+    
+    let csv = render_compliance_csv(&workblocks, &Formatter::default());
+    assert!(!csv.contains("private words"));
+    assert!(!csv.contains("Secret project"));
+    
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "Date,Start,End,Hours,Status");
+    assert!(lines[1].starts_with("2024-05-01,") && lines[1].ends_with(",1.00,completed"));
+    assert!(lines[2].ends_with(",0.50,cancelled"));
+    assert_eq!(lines[3], "2024-05-01,,,1.50,day total");
+    assert_eq!(lines[5], "2024-05-02,,,1.50,day total");
+    assert_eq!(lines[6], "Total,,,3.00,");
+    
+    // Locale decides delimiter, decimal separator and date order
+    let european = Formatter::new(LocaleSettings {
+        decimal_separator: DecimalSeparator::Comma,
+        time_format: TimeFormat::H24,
+        date_order: DateOrder::Dmy,
+    });
+    let csv = render_compliance_csv(&workblocks, &european);
+    assert!(csv.contains("01.05.2024;;;1,50;day total"));
+    
+    println!("✓ Test: Compliance CSV content passed");
+}
+
+#[test]
+fn test_compliance_csv_signature() {
+    let conn = setup();
+    let workblocks = query_compliance_workblocks(&conn, "2024-05-01", "2024-05-03").unwrap();
+    let signed = sign_csv(&render_compliance_csv(&workblocks, &Formatter::default()));
+    
+    let last = signed.lines().last().unwrap();
+    assert!(last.starts_with(SIGNATURE_PREFIX));
+    assert_eq!(last.len(), SIGNATURE_PREFIX.len() + 64);
+    assert!(verify_compliance_csv(&signed));
+    
+    // Any edit breaks the signature
+    assert!(!verify_compliance_csv(&signed.replace("1.00", "2.00")));
+    assert!(!verify_compliance_csv("Date,Start,End,Hours,Status\n"));
+    
+    println!("✓ Test: Compliance CSV signature passed");
+}
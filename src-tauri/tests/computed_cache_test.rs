@@ -0,0 +1,82 @@
+// Test the computed_cache table: expiry, prefix invalidation and invalidation on writes
+// Run with: cargo test --test computed_cache_test
+
+use chrono::{Duration, Utc};
+use log15_lib::computed_cache::*;
+use log15_lib::db::{create_schema, query_monthly_summary, store_setting, MonthlySummary};
+use log15_lib::window_state::MAIN_WINDOW_STATE_KEY;
+use rusqlite::{params, Connection};
+use std::cell::Cell;
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn
+}
+
+#[test]
+fn test_cache_expiry_and_prefixes() {
+    let conn = setup();
+    let now = Utc::now();
+    
+    store_cached(&conn, "weekly_review:2024-05-06", &vec![1, 2, 3], Some(60), now).unwrap();
+    store_cached(&conn, "monthly_summary:2024-05", &"May".to_string(), None, now).unwrap();
+    assert_eq!(query_cached::<Vec<i32>>(&conn, "weekly_review:2024-05-06", now).unwrap(), Some(vec![1, 2, 3]));
+    assert_eq!(query_cached::<Vec<i32>>(&conn, "weekly_review:2024-05-13", now).unwrap(), None);
+    
+    // Expired entries and entries of another shape are misses
+    assert_eq!(query_cached::<Vec<i32>>(&conn, "weekly_review:2024-05-06", now + Duration::seconds(60)).unwrap(), None);
+    assert_eq!(query_cached::<Vec<i32>>(&conn, "monthly_summary:2024-05", now).unwrap(), None);
+    assert_eq!(
+        query_cached::<String>(&conn, "monthly_summary:2024-05", now + Duration::days(365)).unwrap().as_deref(),
+        Some("May")
+    );
+    
+    // Underscores in the prefix are matched literally
+    assert_eq!(invalidate_prefix(&conn, "monthly%").unwrap(), 0);
+    assert_eq!(invalidate_prefix(&conn, "monthly_summary:").unwrap(), 1);
+    assert_eq!(invalidate_prefix(&conn, "").unwrap(), 1);
+    
+    println!("✓ Test: Cache expiry and prefixes passed");
+}
+
+#[test]
+fn test_cache_invalidated_by_writes() {
+    let conn = setup();
+    let computed = Cell::new(0);
+    let summary = |conn: &Connection| -> MonthlySummary {
+        cached_or_compute(conn, "monthly_summary:2024-05", Some(DEFAULT_CACHE_TTL_SECONDS), |conn| {
+            computed.set(computed.get() + 1);
+            query_monthly_summary(conn, 2024, 5)
+        }).unwrap()
+    };
+    
+    assert_eq!(summary(&conn).total_workblocks, 0);
+    assert_eq!(summary(&conn).total_workblocks, 0);
+    assert_eq!(computed.get(), 1);
+    
+    // A new workblock makes the cached summary stale
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES ('2024-05-02', '2024-05-02T09:00:00+00:00', 60, 'completed')",
+        [],
+    ).unwrap();
+    assert_eq!(summary(&conn).total_workblocks, 1);
+    assert_eq!(computed.get(), 2);
+    
+    // So do interval and settings writes
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status) VALUES (?1, 1, '2024-05-02T09:00:00+00:00', 'Writing', 'recorded')",
+        params![1],
+    ).unwrap();
+    assert_eq!(summary(&conn).top_activities.len(), 1);
+    store_setting(&conn, "language", "de").unwrap();
+    summary(&conn);
+    assert_eq!(computed.get(), 4);
+    
+    // Saving the window layout doesn't
+    store_setting(&conn, MAIN_WINDOW_STATE_KEY, r#"{"width":1024,"height":768,"x":0,"y":0}"#).unwrap();
+    summary(&conn);
+    assert_eq!(computed.get(), 4);
+    
+    println!("✓ Test: Cache invalidated by writes passed");
+}
@@ -0,0 +1,92 @@
+// Test context snapshots: storage, the weekly breakdown and power source detection
+// Run with: cargo test --test context_snapshot_test
+
+use log15_lib::context_snapshot::*;
+use log15_lib::db::create_schema;
+use rusqlite::{params, Connection};
+
+fn insert_interval(conn: &Connection, workblock_id: i64, number: i32, energy: Option<i32>) -> i64 {
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status, energy)
+         VALUES (?1, ?2, '2024-05-06T09:00:00+00:00', 'Writing', 'recorded', ?3)",
+        params![workblock_id, number, energy],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+fn snapshot(app: Option<&str>, monitors: u32, on_battery: Option<bool>) -> ContextSnapshot {
+    ContextSnapshot {
+        active_app: app.map(str::to_string),
+        monitor_count: Some(monitors),
+        on_battery,
+        captured_at: "2024-05-06T09:15:00+00:00".to_string(),
+    }
+}
+
+#[test]
+fn test_context_breakdown() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES ('2024-05-06', '2024-05-06T09:00:00+00:00', 60, 'completed')",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    
+    let first = insert_interval(&conn, workblock_id, 1, Some(4));
+    let second = insert_interval(&conn, workblock_id, 2, Some(2));
+    let third = insert_interval(&conn, workblock_id, 3, None);
+    insert_interval(&conn, workblock_id, 4, Some(5));  // Answered before snapshots were turned on
+    store_context_snapshot(&conn, first, &snapshot(Some("Code"), 2, Some(false))).unwrap();
+    store_context_snapshot(&conn, second, &snapshot(Some("Code"), 1, Some(true))).unwrap();
+    store_context_snapshot(&conn, third, &snapshot(None, 1, None)).unwrap();
+    assert_eq!(query_context_snapshot(&conn, first).unwrap(), Some(snapshot(Some("Code"), 2, Some(false))));
+    
+    let breakdown = query_context_breakdown(&conn, "2024-05-06", "2024-05-13").unwrap();
+    let group = |dimension: &str, value: &str| {
+        breakdown.iter().find(|g| g.dimension == dimension && g.value == value).cloned()
+    };
+    let code = group("app", "Code").unwrap();
+    assert_eq!((code.intervals, code.average_energy), (2, Some(3.0)));
+    let single_monitor = group("monitors", "1").unwrap();
+    assert_eq!((single_monitor.intervals, single_monitor.average_energy), (2, Some(2.0)));
+    assert_eq!(group("power", "battery").unwrap().intervals, 1);
+    assert_eq!(group("power", "plugged_in").unwrap().average_energy, Some(4.0));
+    assert_eq!(breakdown.len(), 5);
+    
+    // Snapshots go with their interval
+    conn.execute("DELETE FROM intervals WHERE id = ?1", params![first]).unwrap();
+    assert_eq!(query_context_snapshot(&conn, first).unwrap(), None);
+    assert!(query_context_breakdown(&conn, "2024-05-13", "2024-05-20").unwrap().is_empty());
+    
+    println!("✓ Test: Context breakdown passed");
+}
+
+#[test]
+fn test_power_source_detection() {
+    assert_eq!(parse_pmset_batt("Now drawing from 'Battery Power'\n -InternalBattery-0 85%"), Some(true));
+    assert_eq!(parse_pmset_batt("Now drawing from 'AC Power'\n"), Some(false));
+    assert_eq!(parse_pmset_batt(""), None);
+    
+    let dir = std::env::temp_dir().join(format!("log15_power_supply_{}", std::process::id()));
+    let supply = |name: &str, kind: &str, online: Option<&str>| {
+        let path = dir.join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("type"), format!("{}\n", kind)).unwrap();
+        if let Some(online) = online {
+            std::fs::write(path.join("online"), format!("{}\n", online)).unwrap();
+        }
+    };
+    
+    // A desktop has no battery
+    supply("AC", "Mains", Some("1"));
+    assert_eq!(linux_on_battery(&dir), None);
+    supply("BAT0", "Battery", None);
+    assert_eq!(linux_on_battery(&dir), Some(false));
+    supply("AC", "Mains", Some("0"));
+    assert_eq!(linux_on_battery(&dir), Some(true));
+    assert_eq!(linux_on_battery(&dir.join("missing")), None);
+    
+    std::fs::remove_dir_all(&dir).ok();
+    println!("✓ Test: Power source detection passed");
+}
@@ -0,0 +1,117 @@
+// Test the daily-note export: note names, the rendered section and idempotent re-runs
+// Run with: cargo test --test daily_note_test
+
+use chrono::{Local, NaiveDate, TimeZone};
+use log15_lib::daily_note::*;
+use log15_lib::db::DailyAggregate;
+use log15_lib::format::Formatter;
+use log15_lib::settings::LocaleSettings;
+use std::path::PathBuf;
+
+fn at(hour: u32, minute: u32) -> String {
+    Local.with_ymd_and_hms(2024, 5, 6, hour, minute, 0).unwrap().to_rfc3339()
+}
+
+fn aggregate() -> DailyAggregate {
+    serde_json::from_value(serde_json::json!({
+        "total_workblocks": 1,
+        "total_minutes": 30,
+        "timeline_data": [
+            { "workblock_id": 1, "interval_number": 1, "start_time": at(9, 0), "end_time": at(9, 15),
+              "words": "writing  docs", "duration_minutes": 15, "workblock_status": "completed" },
+            { "workblock_id": 1, "interval_number": 2, "start_time": at(9, 15), "end_time": null,
+              "words": null, "duration_minutes": 15, "workblock_status": "completed" },
+        ],
+        "activity_data": [
+            { "words": "writing docs", "total_minutes": 15, "percentage": 50.0 },
+            { "words": "a|b", "total_minutes": 15, "percentage": 50.0 },
+        ],
+        "word_frequency": [],
+        "workblock_boundaries": [],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_note_relative_path() {
+    let date = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+    assert_eq!(note_relative_path("", date).unwrap(), PathBuf::from("2024-05-06.md"));
+    assert_eq!(note_relative_path("Journal/YYYY/YYYY-MM-DD", date).unwrap(), PathBuf::from("Journal/2024/2024-05-06.md"));
+    assert_eq!(note_relative_path("dddd, MMMM DD", date).unwrap(), PathBuf::from("Monday, May 06.md"));
+    assert_eq!(note_relative_path("[Day] DD [100%].md", date).unwrap(), PathBuf::from("Day 06 100%.md"));
+    
+    // Notes stay inside the vault
+    assert!(note_relative_path("../YYYY-MM-DD", date).is_err());
+    assert!(note_relative_path("/tmp/YYYY-MM-DD", date).is_err());
+    assert!(note_relative_path("[unclosed", date).is_err());
+    
+    println!("✓ Test: Note relative path passed");
+}
+
+#[test]
+fn test_render_daily_note_section() {
+    let section = render_daily_note_section(&aggregate(), &Formatter::new(LocaleSettings::default()));
+    let expected = [
+        SECTION_START,
+        "## Log15",
+        "",
+        "**30m** tracked in 1 workblock",
+        "",
+        "### Timeline",
+        "",
+        "- 09:00–09:15 writing docs",
+        "- 09:15–… (no entry)",
+        "",
+        "### Activities",
+        "",
+        "| Activity | Time | Share |",
+        "| --- | --- | --- |",
+        "| writing docs | 15m | 50% |",
+        "| a\\|b | 15m | 50% |",
+        SECTION_END,
+    ]
+    .join("\n")
+        + "\n";
+    assert_eq!(section, expected);
+    
+    println!("✓ Test: Render daily note section passed");
+}
+
+#[test]
+fn test_merge_daily_note() {
+    let section = format!("{}\nfirst\n{}\n", SECTION_START, SECTION_END);
+    let (created, replaced) = merge_daily_note("", &section);
+    assert_eq!(created, section);
+    assert!(!replaced);
+    
+    // Appended after the user's own text
+    let (appended, replaced) = merge_daily_note("# Monday\nnotes", &section);
+    assert_eq!(appended, format!("# Monday\nnotes\n\n{}", section));
+    assert!(!replaced);
+    
+    // Re-running replaces only the section
+    let note = format!("{}\n## Later\nmore\n", appended);
+    let updated = format!("{}\nsecond\n{}\n", SECTION_START, SECTION_END);
+    let (merged, replaced) = merge_daily_note(&note, &updated);
+    assert!(replaced);
+    assert_eq!(merged, format!("# Monday\nnotes\n\n{}\n## Later\nmore\n", updated));
+    assert_eq!(merge_daily_note(&merged, &updated).0, merged);
+    
+    println!("✓ Test: Merge daily note passed");
+}
+
+#[test]
+fn test_write_daily_note() {
+    let vault = std::env::temp_dir().join(format!("log15_daily_note_{}", std::process::id()));
+    let path = vault.join("Journal").join("2024-05-06.md");
+    let section = render_daily_note_section(&aggregate(), &Formatter::new(LocaleSettings::default()));
+    
+    assert!(!write_daily_note(&path, &section).unwrap());
+    std::fs::write(&path, format!("# Monday\n\n{}", std::fs::read_to_string(&path).unwrap())).unwrap();
+    assert!(write_daily_note(&path, &section).unwrap());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), format!("# Monday\n\n{}", section));
+    
+    std::fs::remove_dir_all(&vault).unwrap();
+    
+    println!("✓ Test: Write daily note passed");
+}
@@ -598,3 +598,123 @@ fn test_multiple_workblocks_archiving() {
     
     mock_app.cleanup();
 }
+
+#[test]
+fn test_custom_day_start_hour() {
+    use chrono::{NaiveDate, TimeZone};
+    use log15_lib::db::{create_schema, logical_date, run_daily_rollover};
+    use log15_lib::settings::{query_day_start_hour, DAY_START_HOUR_KEY};
+    
+    let late_night = Local.with_ymd_and_hms(2024, 5, 2, 1, 30, 0).unwrap();
+    let morning = Local.with_ymd_and_hms(2024, 5, 2, 9, 0, 0).unwrap();
+    let may_1 = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let may_2 = NaiveDate::from_ymd_opt(2024, 5, 2).unwrap();
+    
+    // Midnight boundary by default; with a 4 AM start, 01:30 still belongs to the previous day
+    assert_eq!(logical_date(late_night, 0), may_2);
+    assert_eq!(logical_date(late_night, 4), may_1);
+    assert_eq!(logical_date(morning, 4), may_2);
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    assert_eq!(query_day_start_hour(&conn), 0);
+    for (value, expected) in [("4", 4), ("24", 0), ("late", 0)] {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params![DAY_START_HOUR_KEY, value],
+        ).unwrap();
+        assert_eq!(query_day_start_hour(&conn), expected, "day_start_hour = {}", value);
+    }
+    
+    // Moving the boundary later can make an active workblock look like tomorrow's; it isn't rolled over
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-02', '2024-05-02T00:30:00+00:00', 60, 'active', 0)",
+        [],
+    ).unwrap();
+    let archived = run_daily_rollover(&conn, "2024-05-01", "2024-04-30", |_| Ok(true)).unwrap();
+    assert_eq!(archived, None);
+    let status: String = conn.query_row("SELECT status FROM workblocks", [], |row| row.get(0)).unwrap();
+    assert_eq!(status, "active");
+    
+    // Once its day is over it is closed, even when that day's rollover was already claimed
+    assert!(log15_lib::db::claim_rollover(&conn, "2024-05-02").unwrap());
+    let mut archived_dates = Vec::new();
+    let archived = run_daily_rollover(&conn, "2024-05-03", "2024-05-02", |date| {
+        archived_dates.push(date.to_string());
+        Ok(true)
+    }).unwrap();
+    assert_eq!(archived, None);
+    assert!(archived_dates.is_empty());
+    let status: String = conn.query_row("SELECT status FROM workblocks", [], |row| row.get(0)).unwrap();
+    assert_eq!(status, "completed");
+    
+    println!("✓ Test: Custom day start hour passed");
+}
+
+#[test]
+fn test_scheduled_archive_time() {
+    use chrono::{NaiveTime, TimeZone};
+    use log15_lib::db::{archive_time_reached, create_schema, run_scheduled_archive};
+    
+    let at = |h, m| Local.with_ymd_and_hms(2024, 5, 1, h, m, 0).unwrap();
+    let seven_pm = NaiveTime::from_hms_opt(19, 0, 0).unwrap();
+    let one_am = NaiveTime::from_hms_opt(1, 0, 0).unwrap();
+    assert!(!archive_time_reached(at(18, 59), seven_pm, 0));
+    assert!(archive_time_reached(at(19, 0), seven_pm, 0));
+    assert!(archive_time_reached(at(23, 30), seven_pm, 0));
+    // With a 4 AM day start, 01:00 is late in the day, not early
+    assert!(!archive_time_reached(at(23, 30), one_am, 4));
+    assert!(archive_time_reached(at(2, 0), one_am, 4));
+    assert!(!archive_time_reached(at(5, 0), one_am, 4));
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let insert = |status: &str| {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+             VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 60, ?1, 0)",
+            [status],
+        ).unwrap();
+        conn.last_insert_rowid()
+    };
+    let mark_archived = |date: &str| {
+        conn.execute("UPDATE workblocks SET is_archived = 1 WHERE date = ?1", [date]).unwrap();
+        Ok(true)
+    };
+    
+    // Nothing to archive yet, then waits for the active workblock to finish
+    assert_eq!(run_scheduled_archive(&conn, "2024-05-01", mark_archived).unwrap(), None);
+    let active = insert("active");
+    assert_eq!(run_scheduled_archive(&conn, "2024-05-01", mark_archived).unwrap(), None);
+    conn.execute("UPDATE workblocks SET status = 'completed' WHERE id = ?1", [active]).unwrap();
+    assert_eq!(run_scheduled_archive(&conn, "2024-05-01", mark_archived).unwrap(), Some("2024-05-01".to_string()));
+    assert_eq!(run_scheduled_archive(&conn, "2024-05-01", mark_archived).unwrap(), None);
+    
+    // Work finished after the archive time archives the day again, and the rollover isn't claimed
+    insert("completed");
+    assert_eq!(run_scheduled_archive(&conn, "2024-05-01", mark_archived).unwrap(), Some("2024-05-01".to_string()));
+    let claims: i32 = conn.query_row("SELECT COUNT(*) FROM daily_rollovers", [], |row| row.get(0)).unwrap();
+    assert_eq!(claims, 0);
+    
+    println!("✓ Test: Scheduled archive time passed");
+}
+
+#[test]
+fn test_day_start_time() {
+    use chrono::{NaiveDate, TimeZone};
+    use log15_lib::db::{day_start_time, logical_date};
+    
+    let may_2 = NaiveDate::from_ymd_opt(2024, 5, 2).unwrap();
+    assert_eq!(day_start_time(may_2, 0), Local.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap());
+    assert_eq!(day_start_time(may_2, 4), Local.with_ymd_and_hms(2024, 5, 2, 4, 0, 0).unwrap());
+    
+    // The day starts exactly at its start time, and the moment before still belongs to the previous day
+    for hour in [0, 4] {
+        let start = day_start_time(may_2, hour);
+        assert_eq!(logical_date(start, hour), may_2);
+        assert_eq!(logical_date(start - Duration::seconds(1), hour), may_2.pred_opt().unwrap());
+    }
+    
+    println!("✓ Test: Day start time passed");
+}
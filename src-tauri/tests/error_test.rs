@@ -0,0 +1,61 @@
+// Test the structured error type: codes, classification of SQLite errors and the serialized shape
+// Run with: cargo test --test error_test
+
+use chrono::Local;
+use log15_lib::db::{create_schema, query_workblock_by_id, reopen_cancelled_workblock, store_interval_energy};
+use log15_lib::error::Log15Error;
+use rusqlite::Connection;
+
+fn sqlite_failure(code: i32) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(code), None)
+}
+
+#[test]
+fn test_sqlite_errors_are_classified() {
+    let busy: Log15Error = sqlite_failure(rusqlite::ffi::SQLITE_BUSY).into();
+    assert_eq!(busy.code(), "database_locked");
+    let locked: Log15Error = sqlite_failure(rusqlite::ffi::SQLITE_LOCKED).into();
+    assert_eq!(locked.code(), "database_locked");
+    
+    let missing: Log15Error = rusqlite::Error::QueryReturnedNoRows.into();
+    assert_eq!(missing.code(), "not_found");
+    let other: Log15Error = sqlite_failure(rusqlite::ffi::SQLITE_CORRUPT).into();
+    assert_eq!(other.code(), "database");
+    
+    println!("✓ Test: SQLite errors are classified passed");
+}
+
+#[test]
+fn test_error_serialization() {
+    let error = serde_json::to_value(Log15Error::NoActiveWorkblock).unwrap();
+    assert_eq!(error, serde_json::json!({ "code": "no_active_workblock", "message": "No active workblock" }));
+    
+    let error = serde_json::to_value(Log15Error::InvalidInput("Words must not be empty".to_string())).unwrap();
+    assert_eq!(error["code"], "invalid_input");
+    assert_eq!(error["message"], "Words must not be empty");
+    
+    // Modules that still report plain messages keep them
+    let message: String = Log15Error::AlreadyRunning.into();
+    assert_eq!(message, "A workblock is already running");
+    assert_eq!(Log15Error::from("Disk full".to_string()).code(), "other");
+    
+    println!("✓ Test: Error serialization passed");
+}
+
+#[test]
+fn test_database_functions_return_codes() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    
+    assert_eq!(query_workblock_by_id(&conn, 42).unwrap_err().code(), "not_found");
+    assert_eq!(store_interval_energy(&conn, 1, Some(99)).unwrap_err().code(), "invalid_input");
+    
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 60, 'active')",
+        [],
+    ).unwrap();
+    let error = reopen_cancelled_workblock(&conn, 1, 10, Local::now()).unwrap_err();
+    assert!(matches!(error, Log15Error::AlreadyRunning));
+    
+    println!("✓ Test: Database functions return codes passed");
+}
@@ -0,0 +1,66 @@
+// Test the event catalog: names, acknowledged delivery, payload shapes and the schema
+// Run with: cargo test --test events_test
+
+use log15_lib::events::*;
+use log15_lib::timer::BreakEnded;
+use serde_json::json;
+use std::collections::HashSet;
+
+#[test]
+fn test_event_names() {
+    let names: HashSet<&str> = EventKind::ALL.iter().map(|kind| kind.name()).collect();
+    assert_eq!(names.len(), EventKind::ALL.len());
+    
+    for kind in EventKind::ALL {
+        assert_eq!(EventKind::from_str(kind.name()), Some(kind));
+        assert!(!kind.description().is_empty());
+    }
+    assert_eq!(EventKind::from_str("interval-complete"), Some(EventKind::IntervalComplete));
+    assert_eq!(EventKind::from_str("interval_complete"), None);
+    
+    println!("✓ Test: Event names passed");
+}
+
+#[test]
+fn test_acked_events() {
+    let acked: Vec<&str> = EventKind::ALL.iter().filter(|kind| kind.acked()).map(|kind| kind.name()).collect();
+    assert_eq!(acked, vec!["interval-complete", "missed-intervals", "workblock-complete"]);
+    
+    println!("✓ Test: Acked events passed");
+}
+
+#[test]
+fn test_event_payloads() {
+    let event = AppEvent::IntervalComplete(IntervalComplete { workblock_id: 3, interval_id: 12, interval_number: 2 });
+    assert_eq!(event.kind(), EventKind::IntervalComplete);
+    assert_eq!(event.payload().unwrap(), json!({ "workblock_id": 3, "interval_id": 12, "interval_number": 2 }));
+    
+    assert_eq!(AppEvent::WorkblockComplete(7).payload().unwrap(), json!(7));
+    assert_eq!(AppEvent::BreakOverlayReset(300).payload().unwrap(), json!(300));
+    assert_eq!(
+        AppEvent::BreakEnded(BreakEnded { workblock_id: 7, skipped: true }).payload().unwrap(),
+        json!({ "workblock_id": 7, "skipped": true })
+    );
+    
+    // Events without a payload send null
+    assert_eq!(AppEvent::PromptHide.payload().unwrap(), serde_json::Value::Null);
+    assert_eq!(AppEvent::TrayStartWorkblock.kind().payload(), "null");
+    
+    println!("✓ Test: Event payloads passed");
+}
+
+#[test]
+fn test_event_schema() {
+    let schema = event_schema();
+    assert_eq!(schema.len(), 31);
+    
+    let ready = schema.iter().find(|entry| entry.name == "ready").unwrap();
+    assert_eq!(ready.payload, "StartupReport");
+    assert!(!ready.acked);
+    
+    let complete = schema.iter().find(|entry| entry.name == "workblock-complete").unwrap();
+    assert_eq!(complete.payload, "number");
+    assert!(complete.acked);
+    
+    println!("✓ Test: Event schema passed");
+}
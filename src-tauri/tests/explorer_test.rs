@@ -0,0 +1,91 @@
+// Test the read-only query command used by the data explorer
+// Run with: cargo test --test explorer_test
+
+use log15_lib::db::create_schema;
+use log15_lib::explorer::*;
+use rusqlite::Connection;
+use std::time::Duration;
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    for (date, minutes) in [("2024-03-01", 60), ("2024-03-01", 30), ("2024-03-02", 90)] {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+             VALUES (?1, ?2, ?3, 'completed', 0)",
+            rusqlite::params![date, format!("{}T09:00:00+00:00", date), minutes],
+        ).unwrap();
+    }
+    conn
+}
+
+#[test]
+fn test_readonly_query_returns_rows() {
+    let conn = setup();
+    
+    let result = execute_readonly_query(
+        &conn,
+        "SELECT date, SUM(duration_minutes) AS minutes, NULL AS note FROM workblocks GROUP BY date ORDER BY date;",
+        &QueryLimits::default(),
+    ).unwrap();
+    assert_eq!(result.columns, vec!["date", "minutes", "note"]);
+    assert_eq!(result.rows.len(), 2);
+    assert_eq!(result.rows[0], vec![serde_json::json!("2024-03-01"), serde_json::json!(90), serde_json::Value::Null]);
+    assert!(!result.truncated);
+    
+    // Leading comments and CTEs are fine
+    let result = execute_readonly_query(
+        &conn,
+        "-- longest block\nWITH b AS (SELECT MAX(duration_minutes) AS m FROM workblocks) SELECT m FROM b",
+        &QueryLimits::default(),
+    ).unwrap();
+    assert_eq!(result.rows, vec![vec![serde_json::json!(90)]]);
+    
+    // Row limit
+    let limits = QueryLimits { max_rows: 2, ..QueryLimits::default() };
+    let result = execute_readonly_query(&conn, "SELECT id FROM workblocks", &limits).unwrap();
+    assert_eq!(result.rows.len(), 2);
+    assert!(result.truncated);
+    
+    println!("✓ Test: Read-only query returns rows passed");
+}
+
+#[test]
+fn test_readonly_query_rejects_writes() {
+    let conn = setup();
+    let limits = QueryLimits::default();
+    
+    for sql in [
+        "DELETE FROM workblocks",
+        "/* sneaky */ UPDATE workblocks SET duration_minutes = 0",
+        "PRAGMA journal_mode = DELETE",
+        "ATTACH DATABASE ':memory:' AS other",
+        "SELECT 1; DELETE FROM workblocks",
+        "WITH x AS (SELECT 1) DELETE FROM workblocks",
+    ] {
+        assert!(execute_readonly_query(&conn, sql, &limits).is_err(), "accepted: {}", sql);
+    }
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM workblocks", [], |row| row.get(0)).unwrap();
+    assert_eq!(count, 3);
+    
+    println!("✓ Test: Read-only query rejects writes passed");
+}
+
+#[test]
+fn test_readonly_query_time_limit() {
+    let conn = setup();
+    let limits = QueryLimits { max_rows: 10, timeout: Duration::from_millis(100) };
+    
+    // Never finishes on its own
+    let err = execute_readonly_query(
+        &conn,
+        "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n) SELECT COUNT(*) FROM n",
+        &limits,
+    ).unwrap_err();
+    assert!(err.contains("stopped"), "{}", err);
+    
+    // The connection is still usable afterwards
+    assert!(execute_readonly_query(&conn, "SELECT 1", &limits).is_ok());
+    
+    println!("✓ Test: Read-only query time limit passed");
+}
@@ -0,0 +1,159 @@
+// Test recovery paths under injected faults (DB errors, clock jumps, task aborts)
+// Run with: cargo test --features fault-injection --test fault_injection_test
+
+#![cfg(feature = "fault-injection")]
+
+use chrono::{Duration, Local};
+use log15_lib::db::*;
+use log15_lib::faults::{self, Fault, FaultPoint};
+use log15_lib::timer::{compute_tick, stop_after_loop_abort, TimerState};
+use rusqlite::Connection;
+use std::sync::Mutex;
+
+// Faults are process-wide, so tests injecting them must not overlap
+static FAULT_LOCK: Mutex<()> = Mutex::new(());
+
+fn setup() -> (Connection, std::sync::MutexGuard<'static, ()>) {
+    let guard = FAULT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    faults::reset();
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    (conn, guard)
+}
+
+fn insert_workblock(conn: &Connection, date: &str, status: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 30, ?3, 0)",
+        rusqlite::params![date, format!("{}T09:00:00+00:00", date), status],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+fn rollover_claims(conn: &Connection) -> i32 {
+    conn.query_row("SELECT COUNT(*) FROM daily_rollovers", [], |row| row.get(0)).unwrap()
+}
+
+#[test]
+fn test_rollover_retries_after_archive_failure() {
+    let (conn, _guard) = setup();
+    insert_workblock(&conn, "2024-05-01", "completed");
+
+    // The first attempt fails while archiving and must give its claim back
+    faults::inject(FaultPoint::RolloverArchive, Fault::DbError, 1);
+    let mut archived = Vec::new();
+    let result = run_daily_rollover(&conn, "2024-05-02", "2024-05-01", |date| {
+        archived.push(date.to_string());
+        Ok(true)
+    });
+    assert!(result.is_err());
+    assert!(archived.is_empty());
+    assert_eq!(rollover_claims(&conn), 0);
+
+    // The next check retries and succeeds
+    let result = run_daily_rollover(&conn, "2024-05-02", "2024-05-01", |date| {
+        archived.push(date.to_string());
+        Ok(true)
+    });
+    assert_eq!(result.unwrap().as_deref(), Some("2024-05-01"));
+    assert_eq!(archived, vec!["2024-05-01"]);
+    assert_eq!(rollover_claims(&conn), 1);
+
+    println!("✓ Test: Rollover retries after archive failure passed");
+}
+
+#[test]
+fn test_setting_change_rolled_back_on_fault() {
+    let (conn, _guard) = setup();
+    store_setting(&conn, "theme", "light").unwrap();
+
+    // Failing between the value and its history entry leaves neither behind
+    faults::inject(FaultPoint::SettingsHistory, Fault::DbError, 1);
+    assert!(store_setting(&conn, "theme", "dark").is_err());
+    assert_eq!(query_setting(&conn, "theme").unwrap().as_deref(), Some("light"));
+    assert_eq!(query_settings_history(&conn, Some("theme"), None).unwrap().len(), 1);
+
+    store_setting(&conn, "theme", "dark").unwrap();
+    assert_eq!(query_setting(&conn, "theme").unwrap().as_deref(), Some("dark"));
+    assert_eq!(query_settings_history(&conn, Some("theme"), None).unwrap().len(), 2);
+
+    println!("✓ Test: Setting change rolled back on fault passed");
+}
+
+#[test]
+fn test_clock_jump_over_midnight_rolls_over() {
+    let (conn, _guard) = setup();
+    let started_on = get_today_date();
+    let workblock_id = insert_workblock(&conn, &started_on, "active");
+
+    // The machine sleeps through midnight with a workblock still running
+    faults::jump_clock(Duration::days(1));
+    let today = get_today_date();
+    assert_ne!(today, started_on);
+
+    let yesterday = (faults::now() - Duration::days(1)).format("%Y-%m-%d").to_string();
+    let result = run_daily_rollover(&conn, &today, &yesterday, |_| Ok(true)).unwrap();
+    assert_eq!(result.as_deref(), Some(started_on.as_str()));
+    assert_eq!(query_workblock_by_id(&conn, workblock_id).unwrap().status.as_str(), "completed");
+
+    // A second check after the rollover is a no-op
+    assert_eq!(run_daily_rollover(&conn, &today, &yesterday, |_| Ok(true)).unwrap(), None);
+
+    faults::reset();
+    println!("✓ Test: Clock jump over midnight rolls over passed");
+}
+
+#[test]
+fn test_abort_fault_fires_for_configured_hits() {
+    let (_conn, _guard) = setup();
+
+    faults::inject(FaultPoint::IntervalLoop, Fault::Abort, 2);
+    // An abort is not a DB error and doesn't touch other points
+    assert!(faults::fail_point(FaultPoint::RolloverArchive).is_ok());
+    assert!(faults::should_abort(FaultPoint::IntervalLoop));
+    assert!(faults::should_abort(FaultPoint::IntervalLoop));
+    assert!(!faults::should_abort(FaultPoint::IntervalLoop));
+
+    // Zero hits injects nothing
+    faults::inject(FaultPoint::IntervalLoop, Fault::Abort, 0);
+    assert!(!faults::should_abort(FaultPoint::IntervalLoop));
+
+    println!("✓ Test: Abort fault fires for configured hits passed");
+}
+
+#[test]
+fn test_interval_loop_abort_leaves_workblock_resumable() {
+    let (conn, _guard) = setup();
+    let today = get_today_date();
+    let workblock_id = insert_workblock(&conn, &today, "active");
+    let now = Local::now();
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, status) VALUES (?1, 1, ?2, 'pending')",
+        rusqlite::params![workblock_id, now.to_rfc3339()],
+    ).unwrap();
+    let mut state = TimerState {
+        workblock_id: Some(workblock_id),
+        current_interval_id: Some(conn.last_insert_rowid()),
+        current_interval_number: 1,
+        interval_start_time: Some(now),
+        is_running: true,
+        total_intervals: 2,
+        ..Default::default()
+    };
+    assert!(compute_tick(&state, now).is_some());
+
+    // The loop dies at its next boundary: the countdown stops...
+    faults::inject(FaultPoint::IntervalLoop, Fault::Abort, 1);
+    assert!(faults::should_abort(FaultPoint::IntervalLoop));
+    stop_after_loop_abort(&mut state);
+    assert!(!state.is_running);
+    assert_eq!(compute_tick(&state, now), None);
+
+    // ...while the workblock and its pending interval are still there to resume on the next launch
+    assert_eq!(query_workblock_by_id(&conn, workblock_id).unwrap().status.as_str(), "active");
+    let intervals = query_intervals_by_workblock(&conn, workblock_id).unwrap();
+    assert_eq!(intervals.len(), 1);
+    assert_eq!(intervals[0].status.as_str(), "pending");
+
+    println!("✓ Test: Interval loop abort leaves workblock resumable passed");
+}
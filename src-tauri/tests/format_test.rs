@@ -0,0 +1,61 @@
+// Test locale-aware formatting used by exports
+// Run with: cargo test --test format_test
+
+use chrono::{NaiveDate, NaiveTime};
+use log15_lib::db::create_schema;
+use log15_lib::format::Formatter;
+use log15_lib::settings::*;
+use rusqlite::Connection;
+
+#[test]
+fn test_default_locale_formatting() {
+    let fmt = Formatter::default();
+    
+    assert_eq!(fmt.number(1.5, 2), "1.50");
+    assert_eq!(fmt.hours(90), "1.50");
+    assert_eq!(fmt.date(NaiveDate::from_ymd_opt(2024, 6, 3).unwrap()), "2024-06-03");
+    assert_eq!(fmt.time(NaiveTime::from_hms_opt(14, 5, 0).unwrap()), "14:05");
+    assert_eq!(fmt.csv_delimiter(), ',');
+    assert_eq!(fmt.date_str("not a date"), "not a date");
+    
+    println!("✓ Test: Default locale formatting passed");
+}
+
+#[test]
+fn test_custom_locale_formatting() {
+    let european = Formatter::new(LocaleSettings {
+        decimal_separator: DecimalSeparator::Comma,
+        time_format: TimeFormat::H24,
+        date_order: DateOrder::Dmy,
+    });
+    assert_eq!(european.hours(45), "0,75");
+    assert_eq!(european.date_str("2024-06-03"), "03.06.2024");
+    assert_eq!(european.csv_delimiter(), ';');
+    
+    let us = Formatter::new(LocaleSettings {
+        decimal_separator: DecimalSeparator::Dot,
+        time_format: TimeFormat::H12,
+        date_order: DateOrder::Mdy,
+    });
+    assert_eq!(us.date_str("2024-06-03"), "06/03/2024");
+    assert_eq!(us.time(NaiveTime::from_hms_opt(14, 5, 0).unwrap()), "2:05 PM");
+    assert_eq!(us.time(NaiveTime::from_hms_opt(0, 30, 0).unwrap()), "12:30 AM");
+    
+    println!("✓ Test: Custom locale formatting passed");
+}
+
+#[test]
+fn test_locale_settings_storage() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    assert_eq!(query_locale_settings(&conn), LocaleSettings::default());
+    
+    seed_default_settings(&conn).unwrap();
+    log15_lib::db::store_setting(&conn, LOCALE_DATE_ORDER_KEY, "dmy").unwrap();
+    log15_lib::db::store_setting(&conn, LOCALE_TIME_FORMAT_KEY, "bogus").unwrap();
+    let locale = query_locale_settings(&conn);
+    assert_eq!(locale.date_order, DateOrder::Dmy);
+    assert_eq!(locale.time_format, TimeFormat::H24);
+    
+    println!("✓ Test: Locale settings storage passed");
+}
@@ -0,0 +1,40 @@
+// Test parsing of platform idle-time sources
+// Run with: cargo test --test idle_test
+
+use log15_lib::idle::{parse_ioreg_idle_time, parse_ioreg_screen_locked, parse_locked_hint, parse_xprintidle};
+use std::time::Duration;
+
+#[test]
+fn test_parse_ioreg_idle_time() {
+    let output = r#"
+    | |   "HIDParameters" = {"HIDClickTime"=500000000}
+    | |   "HIDIdleTime" = 12500000000
+    | |   "HIDDefaultParameters" = Yes
+    "#;
+    assert_eq!(parse_ioreg_idle_time(output), Some(Duration::from_millis(12_500)));
+    assert_eq!(parse_ioreg_idle_time("no idle time here"), None);
+    
+    println!("✓ Test: ioreg idle time parsing passed");
+}
+
+#[test]
+fn test_parse_xprintidle() {
+    assert_eq!(parse_xprintidle("4321\n"), Some(Duration::from_millis(4_321)));
+    assert_eq!(parse_xprintidle("couldn't open display"), None);
+    
+    println!("✓ Test: xprintidle parsing passed");
+}
+
+#[test]
+fn test_parse_screen_lock() {
+    let locked = r#"  | "IOConsoleUsers" = ({"kCGSSessionOnConsoleKey"=Yes,"CGSSessionScreenIsLocked"=Yes,"kCGSSessionUserNameKey"="ada"})"#;
+    let unlocked = r#"  | "IOConsoleUsers" = ({"kCGSSessionOnConsoleKey"=Yes,"kCGSSessionUserNameKey"="ada"})"#;
+    assert!(parse_ioreg_screen_locked(locked));
+    assert!(!parse_ioreg_screen_locked(unlocked));
+    
+    assert_eq!(parse_locked_hint("LockedHint=yes\n"), Some(true));
+    assert_eq!(parse_locked_hint("LockedHint=no\n"), Some(false));
+    assert_eq!(parse_locked_hint("Failed to get session: No session"), None);
+    
+    println!("✓ Test: Screen lock parsing passed");
+}
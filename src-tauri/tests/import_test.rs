@@ -0,0 +1,118 @@
+// Test importing another log15 database: staging and merging with de-duplication
+// Run with: cargo test --test import_test
+
+use log15_lib::db::{create_schema, query_intervals_by_workblock, IntervalProvenance};
+use log15_lib::import::*;
+use rusqlite::{params, Connection};
+
+fn insert_workblock(conn: &Connection, date: &str, start_time: &str, status: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 60, ?3, 1)",
+        params![date, start_time, status],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+fn insert_interval(conn: &Connection, workblock_id: i64, number: i32, words: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+         VALUES (?1, ?2, '2024-05-01T09:00:00+00:00', ?3, 'recorded')",
+        params![workblock_id, number, words],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+#[test]
+fn test_import_modes() {
+    assert_eq!(ImportMode::from_str("replace"), Some(ImportMode::Replace));
+    assert_eq!(ImportMode::from_str("merge"), Some(ImportMode::Merge));
+    assert_eq!(ImportMode::from_str("append"), None);
+    assert_eq!(ImportMode::Merge.as_str(), "merge");
+    
+    println!("✓ Test: Import modes passed");
+}
+
+#[test]
+fn test_merge_database() {
+    let path = std::env::temp_dir().join(format!("log15_import_test_{}.db", std::process::id()));
+    std::fs::remove_file(&path).ok();
+    
+    // The other machine's database, written with SQLite-style timestamps by an older version
+    let other = Connection::open(&path).unwrap();
+    create_schema(&other).unwrap();
+    other.execute("PRAGMA user_version = 0", []).unwrap();
+    let shared = insert_workblock(&other, "2024-05-01", "2024-05-01 09:00:00", "completed");
+    insert_interval(&other, shared, 1, "shared work");
+    let padding = insert_workblock(&other, "2024-04-01", "2024-04-01 09:00:00", "completed");
+    other.execute("DELETE FROM workblocks WHERE id = ?1", params![padding]).unwrap();
+    let new = insert_workblock(&other, "2024-05-02", "2024-05-02 10:00:00", "completed");
+    let first = insert_interval(&other, new, 1, "imported one");
+    let split = insert_interval(&other, new, 2, "imported two");
+    other.execute(
+        "INSERT INTO interval_activities (interval_id, position, words, percentage)
+         VALUES (?1, 0, 'coding', 70), (?1, 1, 'meeting', 30)",
+        params![split],
+    ).unwrap();
+    other.execute(
+        "INSERT INTO interval_app_usage (interval_id, app_name, seconds) VALUES (?1, 'Editor', 600)",
+        params![first],
+    ).unwrap();
+    insert_workblock(&other, "2024-05-03", "2024-05-03 08:00:00", "active");
+    drop(other);
+    
+    // This machine already has the shared workblock, stored as UTC RFC 3339
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let existing = insert_workblock(&conn, "2024-05-01", "2024-05-01T09:00:00+00:00", "completed");
+    insert_interval(&conn, existing, 1, "shared work");
+    
+    let staged = stage_database(&path).unwrap();
+    let result = merge_database(&conn, &staged).unwrap();
+    assert_eq!(result.workblocks_imported, 1);
+    assert_eq!(result.intervals_imported, 2);
+    assert_eq!(result.duplicates_skipped, 1);
+    assert_eq!(result.active_skipped, 1);
+    assert_eq!(result.dates, vec!["2024-05-02".to_string()]);
+    
+    // Intervals and app usage follow the workblock to its new id; it waits to be archived again
+    let (imported, is_archived): (i64, bool) = conn.query_row(
+        "SELECT id, is_archived FROM workblocks WHERE date = '2024-05-02'",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).unwrap();
+    assert!(!is_archived);
+    let words: Vec<String> = conn
+        .prepare("SELECT words FROM intervals WHERE workblock_id = ?1 ORDER BY interval_number").unwrap()
+        .query_map(params![imported], |row| row.get(0)).unwrap()
+        .collect::<Result<_, _>>().unwrap();
+    assert_eq!(words, vec!["imported one", "imported two"]);
+    let provenance: Vec<IntervalProvenance> = query_intervals_by_workblock(&conn, imported).unwrap()
+        .iter().map(|i| i.provenance).collect();
+    assert_eq!(provenance, vec![IntervalProvenance::Imported; 2]);
+    let usage: i64 = conn.query_row(
+        "SELECT u.seconds FROM interval_app_usage u JOIN intervals i ON i.id = u.interval_id
+         WHERE i.workblock_id = ?1 AND i.interval_number = 1",
+        params![imported],
+        |row| row.get(0),
+    ).unwrap();
+    assert_eq!(usage, 600);
+    
+    // The activity split of a combined answer comes along too
+    let activities: Vec<(String, i32)> = conn
+        .prepare(
+            "SELECT a.words, a.percentage FROM interval_activities a JOIN intervals i ON i.id = a.interval_id
+             WHERE i.workblock_id = ?1 AND i.interval_number = 2 ORDER BY a.position",
+        ).unwrap()
+        .query_map(params![imported], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+        .collect::<Result<_, _>>().unwrap();
+    assert_eq!(activities, vec![("coding".to_string(), 70), ("meeting".to_string(), 30)]);
+    
+    // Merging the same database again adds nothing
+    let again = merge_database(&conn, &staged).unwrap();
+    assert_eq!(again.workblocks_imported, 0);
+    assert_eq!(again.duplicates_skipped, 2);
+    
+    std::fs::remove_file(&path).ok();
+    println!("✓ Test: Merge database passed");
+}
@@ -73,6 +73,9 @@ fn init_test_db() -> Connection {
         [],
     ).unwrap();
     
+    // Remaining tables come from the real schema
+    create_schema(&conn).unwrap();
+    
     conn
 }
 
@@ -341,3 +344,1246 @@ fn test_day_transition() {
     // Cleanup
     std::fs::remove_file(get_test_db_path()).ok();
 }
+
+#[test]
+fn test_monthly_summary() {
+    let conn = init_test_db();
+    
+    // Two workblocks on Feb 3rd, one on Feb 20th, one in March (excluded)
+    let workblocks = [
+        ("2024-02-03", "2024-02-03T09:00:00+00:00", 60),
+        ("2024-02-03", "2024-02-03T14:00:00+00:00", 30),
+        ("2024-02-20", "2024-02-20T10:00:00+00:00", 90),
+        ("2024-03-01", "2024-03-01T10:00:00+00:00", 45),
+    ];
+    for (date, start_time, duration) in workblocks {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+             VALUES (?1, ?2, ?3, 'completed', 0)",
+            rusqlite::params![date, start_time, duration],
+        ).unwrap();
+        let workblock_id = conn.last_insert_rowid();
+        
+        let words_list = ["Coding ", "coding", "meeting"];
+        for (i, words) in words_list.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+                 VALUES (?1, ?2, ?3, ?4, 'recorded')",
+                rusqlite::params![workblock_id, i as i32 + 1, start_time, words],
+            ).unwrap();
+        }
+    }
+    
+    let summary = query_monthly_summary(&conn, 2024, 2).unwrap();
+    
+    // Leap-year February: every day present, including untracked ones
+    assert_eq!(summary.heatmap.len(), 29);
+    assert_eq!(summary.heatmap[0].date, "2024-02-01");
+    assert_eq!(summary.heatmap[0].total_minutes, 0);
+    assert_eq!(summary.heatmap[2].total_minutes, 90);
+    assert_eq!(summary.heatmap[2].total_workblocks, 2);
+    assert_eq!(summary.heatmap[19].total_minutes, 90);
+    
+    assert_eq!(summary.total_workblocks, 3);
+    assert_eq!(summary.total_minutes, 180);
+    assert!((summary.average_workblock_minutes - 60.0).abs() < f64::EPSILON);
+    
+    // Phrases are normalized before counting; March data is excluded
+    assert_eq!(summary.top_activities[0].word, "coding");
+    assert_eq!(summary.top_activities[0].count, 6);
+    assert_eq!(summary.top_activities[1].word, "meeting");
+    assert_eq!(summary.top_activities[1].count, 3);
+    
+    // Invalid month is rejected
+    assert!(query_monthly_summary(&conn, 2024, 13).is_err());
+    
+    println!("✓ Test: Monthly summary passed");
+}
+
+#[test]
+fn test_quiet_days_untracked() {
+    let conn = init_test_db();
+    
+    // April 2nd: app left running, every interval auto-away. April 3rd: real work.
+    let days = [("2024-04-02", None, "auto_away"), ("2024-04-03", Some("coding"), "recorded")];
+    for (date, words, status) in days {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+             VALUES (?1, ?2, 15, 'completed', 0)",
+            rusqlite::params![date, format!("{}T09:00:00+00:00", date)],
+        ).unwrap();
+        let workblock_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+             VALUES (?1, 1, ?2, ?3, ?4)",
+            rusqlite::params![workblock_id, format!("{}T09:00:00+00:00", date), words, status],
+        ).unwrap();
+    }
+    
+    assert!(!query_day_has_recorded_data(&conn, "2024-04-02").unwrap());
+    assert!(query_day_has_recorded_data(&conn, "2024-04-03").unwrap());
+    assert!(!query_day_has_recorded_data(&conn, "2024-04-04").unwrap());
+    
+    let summary = query_monthly_summary(&conn, 2024, 4).unwrap();
+    assert!(summary.heatmap[0].untracked);
+    assert!(summary.heatmap[1].untracked);
+    assert_eq!(summary.heatmap[1].total_workblocks, 1);
+    assert!(!summary.heatmap[2].untracked);
+    assert!(summary.heatmap[3].untracked);
+    
+    println!("✓ Test: Quiet days untracked passed");
+}
+
+fn timeline_entry(workblock_id: i64, interval_number: i32, words: &str, duration_minutes: i32) -> AggregateTimelineData {
+    AggregateTimelineData {
+        workblock_id,
+        interval_number,
+        start_time: format!("2024-02-03T09:{:02}:00+00:00", interval_number),
+        end_time: Some(format!("2024-02-03T09:{:02}:59+00:00", interval_number)),
+        words: Some(words.to_string()),
+        duration_minutes,
+        workblock_status: None,
+        attachments: Vec::new(),
+        project: None,
+        notes: None,
+        workblock_title: None,
+        provenance: IntervalProvenance::Live,
+    }
+}
+
+#[test]
+fn test_timeline_limits() {
+    let timeline = vec![
+        timeline_entry(1, 1, "coding", 15),
+        timeline_entry(1, 2, "coding", 1),
+        timeline_entry(1, 3, "email", 2),
+        timeline_entry(1, 4, "meeting", 15),
+        timeline_entry(2, 1, "review", 1),
+    ];
+    
+    // Short segments fold into the previous segment of the same workblock only
+    let limits = TimelineLimits { min_segment_minutes: Some(5), max_entries: None };
+    let (merged, truncated) = limit_timeline(timeline, &limits);
+    assert!(!truncated);
+    assert_eq!(merged.len(), 3);
+    assert_eq!(merged[0].duration_minutes, 18);
+    assert_eq!(merged[0].words.as_deref(), Some("coding"));
+    assert_eq!(merged[0].end_time.as_deref(), Some("2024-02-03T09:03:59+00:00"));
+    assert_eq!(merged[1].words.as_deref(), Some("meeting"));
+    assert_eq!(merged[2].workblock_id, 2);
+    
+    // Cap entries and flag truncation
+    let limits = TimelineLimits { min_segment_minutes: None, max_entries: Some(2) };
+    let (capped, truncated) = limit_timeline(merged, &limits);
+    assert!(truncated);
+    assert_eq!(capped.len(), 2);
+    
+    println!("✓ Test: Timeline limits passed");
+}
+
+#[test]
+fn test_activity_suggestions() {
+    let conn = init_test_db();
+    let now = Local::now();
+    
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 60, 'completed', 0)",
+        rusqlite::params![now.format("%Y-%m-%d").to_string(), now.to_rfc3339()],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    
+    // (words, status, days ago)
+    let entries = [
+        ("code review", "recorded", 30),
+        ("code review", "recorded", 30),
+        ("code review", "recorded", 30),
+        ("Coding", "recorded", 0),
+        ("coding ", "recorded", 1),
+        ("Away from workspace", "auto_away", 0),
+        ("meeting", "recorded", 0),
+    ];
+    for (i, (words, status, days_ago)) in entries.iter().enumerate() {
+        let recorded_at = (now - Duration::days(*days_ago)).to_rfc3339();
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?3)",
+            rusqlite::params![workblock_id, i as i32 + 1, recorded_at, words, status],
+        ).unwrap();
+    }
+    
+    let suggestions = query_activity_suggestions(&conn, "CO", 10, now).unwrap();
+    assert_eq!(suggestions.len(), 2);
+    // Recent "coding" outranks the older but more frequent "code review"
+    assert_eq!(suggestions[0].words, "Coding");
+    assert_eq!(suggestions[0].count, 2);
+    assert_eq!(suggestions[1].words, "code review");
+    assert_eq!(suggestions[1].count, 3);
+    
+    // Auto-away entries are never suggested
+    let all = query_activity_suggestions(&conn, "", 10, now).unwrap();
+    assert!(all.iter().all(|s| s.words != "Away from workspace"));
+    assert_eq!(query_activity_suggestions(&conn, "", 1, now).unwrap().len(), 1);
+    
+    println!("✓ Test: Activity suggestions passed");
+}
+
+#[test]
+fn test_activity_aliases() {
+    let conn = init_test_db();
+    for alias in ["reviewing prs", "pr review"] {
+        conn.execute(
+            "INSERT INTO activity_aliases (alias, canonical) VALUES (?1, 'code review')",
+            rusqlite::params![alias],
+        ).unwrap();
+    }
+    
+    let aliases = query_alias_map(&conn).unwrap();
+    assert_eq!(aliases.len(), 2);
+    assert_eq!(normalize_activity("  PR Review ", &aliases), "code review");
+    assert_eq!(normalize_activity("Reviewing PRs", &aliases), "code review");
+    assert_eq!(normalize_activity("Code Review", &aliases), "code review");
+    assert_eq!(normalize_activity("Coding", &aliases), "coding");
+    
+    println!("✓ Test: Activity aliases passed");
+}
+
+#[test]
+fn test_prompt_suggestion_ranking() {
+    let pinned = vec!["Deep work".to_string(), "email".to_string()];
+    let recent = vec![
+        ActivitySuggestion { words: "coding".to_string(), count: 4, last_used: Local::now().to_rfc3339(), score: 4.0 },
+        ActivitySuggestion { words: "Email".to_string(), count: 1, last_used: Local::now().to_rfc3339(), score: 1.0 },
+        ActivitySuggestion { words: "meeting".to_string(), count: 1, last_used: Local::now().to_rfc3339(), score: 0.5 },
+    ];
+    let attachments = vec!["PROJ-123".to_string()];
+    
+    let ranked = rank_prompt_suggestions(&pinned, &recent, &attachments, 10);
+    let words: Vec<&str> = ranked.iter().map(|s| s.words.as_str()).collect();
+    assert_eq!(words, vec!["Deep work", "email", "coding", "PROJ-123", "meeting"]);
+    
+    // "email" came from both the pins and history
+    assert_eq!(ranked[1].sources, vec![SuggestionSource::Pinned, SuggestionSource::Recent]);
+    assert_eq!(rank_prompt_suggestions(&pinned, &recent, &attachments, 2).len(), 2);
+    
+    println!("✓ Test: Prompt suggestion ranking passed");
+}
+
+#[test]
+fn test_prompt_quick_picks() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let now = Local::now();
+    let today = now.format("%Y-%m-%d").to_string();
+    
+    // Nothing recorded or pinned yet
+    let empty = query_prompt_quick_picks(&conn, &today).unwrap();
+    assert_eq!(empty, PromptQuickPicks { last_words: None, top_today: vec![], pinned: vec![] });
+    
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 120, 'active', 0)",
+        rusqlite::params![today, now.to_rfc3339()],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    conn.execute(
+        "INSERT INTO activity_aliases (alias, canonical) VALUES ('pr review', 'code review')",
+        [],
+    ).unwrap();
+    let answers = [
+        ("Coding", "recorded"),
+        ("code review", "recorded"),
+        ("PR review", "recorded"),
+        ("meeting", "recorded"),
+        ("coding", "recorded"),
+        ("email", "recorded"),
+        ("Away from workspace", "auto_away"),
+        ("Email", "recorded"),
+    ];
+    for (i, (words, status)) in answers.iter().enumerate() {
+        let recorded_at = (now + Duration::minutes(i as i64)).to_rfc3339();
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status, recorded_at)
+             VALUES (?1, ?2, ?3, ?3, ?4, ?5, ?3)",
+            rusqlite::params![workblock_id, i as i32 + 1, recorded_at, words, status],
+        ).unwrap();
+    }
+    conn.execute("INSERT INTO pinned_activities (activity, pinned_at) VALUES ('Deep work', ?1)", [now.to_rfc3339()]).unwrap();
+    
+    let picks = query_prompt_quick_picks(&conn, &today).unwrap();
+    // The away interval doesn't count as the last answer
+    assert_eq!(picks.last_words.as_deref(), Some("Email"));
+    // Aliases and case are merged; ties go alphabetically; "meeting" misses the cut
+    assert_eq!(picks.top_today, vec!["code review", "coding", "email"]);
+    assert_eq!(picks.top_today.len(), QUICK_PICK_TOP_ACTIVITIES);
+    assert_eq!(picks.pinned, vec!["Deep work"]);
+    
+    // Another day's intervals are not today's top activities
+    let other_day = query_prompt_quick_picks(&conn, "2000-01-01").unwrap();
+    assert!(other_day.top_today.is_empty());
+    assert_eq!(other_day.last_words.as_deref(), Some("Email"));
+    
+    println!("✓ Test: Prompt quick picks passed");
+}
+
+#[test]
+fn test_previous_answer() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let now = Local::now();
+    
+    let insert_workblock = || {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+             VALUES (?1, ?2, 60, 'active', 0)",
+            rusqlite::params![now.format("%Y-%m-%d").to_string(), now.to_rfc3339()],
+        ).unwrap();
+        conn.last_insert_rowid()
+    };
+    let first_workblock = insert_workblock();
+    let second_workblock = insert_workblock();
+    let insert_interval = |workblock_id: i64, number: i32, minutes: i64, words: Option<&str>, status: &str, project: Option<&str>| {
+        let start = to_timestamp(now + Duration::minutes(minutes));
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status, project)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![workblock_id, number, start, words, status, project],
+        ).unwrap();
+        conn.last_insert_rowid()
+    };
+    let first = insert_interval(first_workblock, 1, 0, None, "pending", None);
+    let split = insert_interval(first_workblock, 2, 15, Some("coding + meeting"), "recorded", Some("Apollo"));
+    insert_interval(first_workblock, 3, 30, Some("Away from workspace"), "auto_away", None);
+    let current = insert_interval(second_workblock, 1, 45, None, "pending", None);
+    store_activity_splits(&conn, split, &[
+        ActivitySplit { words: "coding".to_string(), percentage: 60 },
+        ActivitySplit { words: "meeting".to_string(), percentage: 40 },
+    ]).unwrap();
+    
+    // Nothing was answered before the first interval
+    assert_eq!(query_previous_answer(&conn, first).unwrap(), None);
+    
+    // The away interval is skipped; the previous workblock's answer counts
+    let previous = query_previous_answer(&conn, current).unwrap().unwrap();
+    assert_eq!(previous.interval_id, split);
+    assert_eq!(previous.words, "coding + meeting");
+    assert_eq!(previous.project.as_deref(), Some("Apollo"));
+    assert_eq!(previous.splits.len(), 2);
+    assert_eq!(previous.splits[0], ActivitySplit { words: "coding".to_string(), percentage: 60 });
+    
+    println!("✓ Test: Previous answer passed");
+}
+
+#[test]
+fn test_auto_away_settings() {
+    use log15_lib::settings::*;
+    
+    let conn = init_test_db();
+    
+    // Defaults when nothing is stored
+    assert_eq!(query_auto_away_settings(&conn), AutoAwaySettings::default());
+    
+    store_setting(&conn, AUTO_AWAY_TIMEOUT_KEY, "900").unwrap();
+    store_setting(&conn, AUTO_AWAY_MESSAGE_KEY, "Stepped out").unwrap();
+    let settings = query_auto_away_settings(&conn);
+    assert_eq!(settings.timeout_seconds, 900);
+    assert_eq!(settings.message, "Stepped out");
+    
+    // Overwrites replace; malformed values fall back to defaults
+    store_setting(&conn, AUTO_AWAY_TIMEOUT_KEY, "soon").unwrap();
+    store_setting(&conn, AUTO_AWAY_MESSAGE_KEY, "   ").unwrap();
+    assert_eq!(query_auto_away_settings(&conn), AutoAwaySettings::default());
+    
+    // Duration presets are sorted and deduplicated; invalid stored presets are ignored
+    assert_eq!(query_duration_presets(&conn), None);
+    assert_eq!(normalize_duration_presets(&[60, 30, 60]).unwrap(), vec![30, 60]);
+    assert!(normalize_duration_presets(&[]).is_err());
+    assert!(normalize_duration_presets(&[30, 0]).is_err());
+    store_setting(&conn, DURATION_PRESETS_KEY, "[90, 45]").unwrap();
+    assert_eq!(query_duration_presets(&conn), Some(vec![45, 90]));
+    store_setting(&conn, DURATION_PRESETS_KEY, "[-5]").unwrap();
+    assert_eq!(query_duration_presets(&conn), None);
+    
+    println!("✓ Test: Auto-away settings passed");
+}
+
+#[test]
+fn test_settings_history() {
+    let conn = init_test_db();
+    
+    store_setting(&conn, "snooze_seconds", "60").unwrap();
+    store_setting(&conn, "snooze_seconds", "60").unwrap(); // Unchanged: not recorded
+    store_setting(&conn, "snooze_seconds", "120").unwrap();
+    store_setting(&conn, "auto_away_message", "Out").unwrap();
+    // Seeding defaults is not a change
+    assert!(store_setting_if_missing(&conn, "idle_threshold_seconds", "30").unwrap());
+    
+    let history = query_settings_history(&conn, None, None).unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].key, "auto_away_message");
+    assert_eq!(history[1].old_value.as_deref(), Some("60"));
+    assert_eq!(history[1].new_value, "120");
+    assert_eq!(history[2].old_value, None);
+    
+    // Filter by key and by time
+    let snooze = query_settings_history(&conn, Some("snooze_seconds"), None).unwrap();
+    assert_eq!(snooze.len(), 2);
+    let future = (Local::now() + Duration::hours(1)).to_rfc3339();
+    assert!(query_settings_history(&conn, None, Some(&future)).unwrap().is_empty());
+    
+    // Secrets are stored but never recorded
+    for key in log15_lib::settings::SECRET_SETTING_KEYS {
+        store_setting(&conn, key, "s3cret").unwrap();
+        assert_eq!(query_setting(&conn, key).unwrap().as_deref(), Some("s3cret"));
+        assert!(query_settings_history(&conn, Some(key), None).unwrap().is_empty());
+    }
+    
+    println!("✓ Test: Settings history passed");
+}
+
+#[test]
+fn test_settings_broadcast() {
+    use log15_lib::settings::*;
+    
+    // Every subscriber sees each change, in order
+    let mut timer = subscribe_settings();
+    let mut tray = subscribe_settings();
+    let change = |key: &str, value: &str| SettingChanged { key: key.to_string(), value: value.to_string() };
+    assert!(publish_setting_change(change(AUTO_AWAY_TIMEOUT_KEY, "30")) >= 2);
+    publish_setting_change(change(TRAY_COUNTDOWN_KEY, "interval"));
+    
+    // Other tests may publish concurrently, so look for this test's changes among them
+    let received = |rx: &mut tokio::sync::broadcast::Receiver<SettingChanged>| {
+        let mut keys = Vec::new();
+        while let Ok(c) = rx.try_recv() {
+            if c == change(AUTO_AWAY_TIMEOUT_KEY, "30") || c == change(TRAY_COUNTDOWN_KEY, "interval") {
+                keys.push(c.key);
+            }
+        }
+        keys
+    };
+    assert_eq!(received(&mut timer), vec![AUTO_AWAY_TIMEOUT_KEY, TRAY_COUNTDOWN_KEY]);
+    assert_eq!(received(&mut tray), vec![AUTO_AWAY_TIMEOUT_KEY, TRAY_COUNTDOWN_KEY]);
+    
+    println!("✓ Test: Settings broadcast passed");
+}
+
+#[test]
+fn test_pomodoro_settings() {
+    use log15_lib::settings::*;
+    use log15_lib::timing::timing;
+    
+    let conn = init_test_db();
+    
+    // Pomodoro mode is off until enabled
+    let defaults = query_pomodoro_settings(&conn);
+    assert_eq!(defaults, PomodoroSettings::default());
+    assert!(!defaults.enabled);
+    assert!(!defaults.auto_start_next);
+    
+    store_setting(&conn, POMODORO_ENABLED_KEY, "true").unwrap();
+    store_setting(&conn, BREAK_DURATION_KEY, "300").unwrap();
+    store_setting(&conn, BREAK_AUTO_START_KEY, "true").unwrap();
+    assert_eq!(query_pomodoro_settings(&conn), PomodoroSettings {
+        enabled: true,
+        break_seconds: 300,
+        auto_start_next: true,
+    });
+    
+    // A zero or malformed break length falls back to the default
+    store_setting(&conn, BREAK_DURATION_KEY, "0").unwrap();
+    assert_eq!(query_pomodoro_settings(&conn).break_seconds, timing().break_seconds);
+    store_setting(&conn, BREAK_DURATION_KEY, "a while").unwrap();
+    assert_eq!(query_pomodoro_settings(&conn).break_seconds, timing().break_seconds);
+    
+    println!("✓ Test: Pomodoro settings passed");
+}
+
+#[test]
+fn test_reclassify_auto_away_interval() {
+    let conn = init_test_db();
+    let now = Local::now();
+    
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 60, 'active', 0)",
+        rusqlite::params![now.format("%Y-%m-%d").to_string(), now.to_rfc3339()],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    
+    let entries = [
+        (Some("coding"), "recorded"),
+        (Some("Away from workspace"), "auto_away"),
+        (None, "pending"),
+    ];
+    let mut ids = Vec::new();
+    for (i, (words, status)) in entries.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?3, ?4, ?5)",
+            rusqlite::params![workblock_id, i as i32 + 1, now.to_rfc3339(), words, status],
+        ).unwrap();
+        ids.push(conn.last_insert_rowid());
+    }
+    
+    // The pending (current) interval offers to reclassify the auto-away one before it
+    let offer = query_reclassify_offer(&conn, ids[2]).unwrap().unwrap();
+    assert_eq!(offer.interval_id, ids[1]);
+    assert_eq!(offer.suggested_words.as_deref(), Some("coding"));
+    // No offer when the previous interval was recorded normally
+    assert!(query_reclassify_offer(&conn, ids[1]).unwrap().is_none());
+    
+    apply_reclassification(&conn, ids[1], " coding ").unwrap();
+    let (words, status, end_time): (String, String, String) = conn.query_row(
+        "SELECT words, status, end_time FROM intervals WHERE id = ?1",
+        [ids[1]],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    ).unwrap();
+    assert_eq!(words, "coding");
+    assert_eq!(status, "recorded");
+    assert_eq!(end_time, now.to_rfc3339());
+    
+    // Only auto-away intervals can be reclassified, and only once
+    assert!(apply_reclassification(&conn, ids[1], "meeting").is_err());
+    assert!(apply_reclassification(&conn, ids[0], "meeting").is_err());
+    assert!(query_reclassify_offer(&conn, ids[2]).unwrap().is_none());
+    
+    println!("✓ Test: Reclassify auto-away interval passed");
+}
+
+#[test]
+fn test_interval_provenance() {
+    let conn = init_test_db();
+    let ended = chrono::Utc::now() - Duration::hours(3);
+    
+    // Only answers given long after the interval ended change the provenance
+    let late = ended + Duration::minutes(AFTER_THE_FACT_MINUTES + 1);
+    let on_time = ended + Duration::minutes(5);
+    assert_eq!(provenance_for_answer(&IntervalStatus::Pending, &IntervalStatus::Recorded, Some(ended), on_time), None);
+    assert_eq!(
+        provenance_for_answer(&IntervalStatus::Pending, &IntervalStatus::Recorded, Some(ended), late),
+        Some(IntervalProvenance::Backfilled)
+    );
+    assert_eq!(
+        provenance_for_answer(&IntervalStatus::Recorded, &IntervalStatus::Recorded, Some(ended), late),
+        Some(IntervalProvenance::Edited)
+    );
+    assert_eq!(provenance_for_answer(&IntervalStatus::Pending, &IntervalStatus::AutoAway, Some(ended), late), None);
+    assert_eq!(provenance_for_answer(&IntervalStatus::Pending, &IntervalStatus::Recorded, None, late), None);
+    
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-02-03', ?1, 60, 'completed', 0)",
+        rusqlite::params![to_timestamp(ended)],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    let mut ids = Vec::new();
+    for (i, (words, status)) in [(Some("coding"), "recorded"), (Some("Away from workspace"), "auto_away"), (None, "pending")].iter().enumerate() {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?3, ?4, ?5)",
+            rusqlite::params![workblock_id, i as i32 + 1, to_timestamp(ended), words, status],
+        ).unwrap();
+        ids.push(conn.last_insert_rowid());
+    }
+    
+    store_interval_words(&conn, ids[0], "code review", IntervalStatus::Recorded, late).unwrap();
+    apply_reclassification(&conn, ids[1], "coding").unwrap();
+    store_interval_words(&conn, ids[2], "testing", IntervalStatus::Recorded, on_time).unwrap();
+    let provenance: Vec<IntervalProvenance> = query_intervals_by_workblock(&conn, workblock_id)
+        .unwrap()
+        .iter()
+        .map(|i| i.provenance)
+        .collect();
+    assert_eq!(provenance, vec![IntervalProvenance::Edited, IntervalProvenance::Backfilled, IntervalProvenance::Live]);
+    
+    // A merged timeline segment is only live if all of its parts are
+    let mut backfilled = timeline_entry(1, 2, "coding", 1);
+    backfilled.provenance = IntervalProvenance::Backfilled;
+    let limits = TimelineLimits { min_segment_minutes: Some(5), max_entries: None };
+    let (merged, _) = limit_timeline(vec![timeline_entry(1, 1, "coding", 15), backfilled], &limits);
+    assert_eq!(merged[0].provenance, IntervalProvenance::Backfilled);
+    
+    println!("✓ Test: Interval provenance passed");
+}
+
+#[test]
+fn test_quick_statuses() {
+    use log15_lib::settings::*;
+    let conn = init_test_db();
+    let now = Local::now();
+    
+    // Defaults until the user sets their own
+    assert_eq!(query_quick_statuses(&conn), None);
+    seed_default_settings(&conn).unwrap();
+    assert_eq!(query_quick_statuses(&conn).unwrap(), DEFAULT_QUICK_STATUSES.to_vec());
+    
+    let statuses = normalize_quick_statuses(&[" Lunch ".to_string(), "".to_string(), "lunch".to_string(), "Doctor".to_string()]).unwrap();
+    assert_eq!(statuses, vec!["Lunch", "Doctor"]);
+    assert!(normalize_quick_statuses(&["x".repeat(MAX_QUICK_STATUS_LENGTH + 1)]).is_err());
+    assert_eq!(find_quick_status(&statuses, " doctor").as_deref(), Some("Doctor"));
+    assert_eq!(find_quick_status(&statuses, "Errand"), None);
+    
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 60, 'active', 0)",
+        rusqlite::params![now.format("%Y-%m-%d").to_string(), now.to_rfc3339()],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    let mut ids = Vec::new();
+    for (i, (words, status)) in [("Away from workspace", "auto_away"), ("coding", "recorded")].iter().enumerate() {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?3, ?4, ?5)",
+            rusqlite::params![workblock_id, i as i32 + 1, now.to_rfc3339(), words, status],
+        ).unwrap();
+        ids.push(conn.last_insert_rowid());
+    }
+    
+    // An auto-away interval takes the status under its own status, keeping its timing
+    apply_quick_status(&conn, ids[0], "Lunch").unwrap();
+    let (words, status, end_time): (String, String, String) = conn.query_row(
+        "SELECT words, status, end_time FROM intervals WHERE id = ?1",
+        [ids[0]],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    ).unwrap();
+    assert_eq!(words, "Lunch");
+    assert_eq!(IntervalStatus::from_str(&status), IntervalStatus::QuickStatus);
+    assert_eq!(end_time, now.to_rfc3339());
+    
+    // Quick statuses aren't activities and never count as away time again
+    assert_eq!(query_recent_activities(&conn, 5).unwrap(), vec!["coding"]);
+    assert!(apply_quick_status(&conn, ids[0], "Errand").is_err());
+    assert!(apply_quick_status(&conn, ids[1], "Errand").is_err());
+    assert!(apply_quick_status(&conn, ids[0], "  ").is_err());
+    
+    println!("✓ Test: Quick statuses passed");
+}
+
+#[test]
+fn test_project_inheritance_and_rollup() {
+    let conn = init_test_db();
+    
+    // Project columns are added to existing tables by the schema migration
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived, project)
+         VALUES ('2024-02-03', '2024-02-03T09:00:00+00:00', 60, 'completed', 0, 'Apollo')",
+        [],
+    ).unwrap();
+    
+    let workblock = Workblock {
+        id: Some(1),
+        date: "2024-02-03".to_string(),
+        start_time: "2024-02-03T09:00:00+00:00".to_string(),
+        end_time: None,
+        duration_minutes: Some(60),
+        status: WorkblockStatus::Completed,
+        is_archived: false,
+        created_at: None,
+        project: Some("Apollo".to_string()),
+        cancel_reason: None,
+        cancel_note: None,
+        title: None,
+    };
+    let mut interval = Interval {
+        id: Some(1),
+        workblock_id: 1,
+        interval_number: 1,
+        start_time: "2024-02-03T09:00:00+00:00".to_string(),
+        end_time: None,
+        words: Some("coding".to_string()),
+        status: IntervalStatus::Recorded,
+        recorded_at: None,
+        project: None,
+        category: None,
+        energy: None,
+        notes: None,
+        provenance: IntervalProvenance::Live,
+        away_cause: None,
+    };
+    assert_eq!(effective_project(&interval, &workblock).as_deref(), Some("Apollo"));
+    interval.project = Some("Gemini".to_string());
+    assert_eq!(effective_project(&interval, &workblock).as_deref(), Some("Gemini"));
+    
+    // A mixed-project workblock rolls up by interval-level project
+    let mut timeline = vec![
+        timeline_entry(1, 1, "coding", 15),
+        timeline_entry(1, 2, "review", 15),
+        timeline_entry(1, 3, "support", 15),
+        timeline_entry(2, 1, "email", 5),
+    ];
+    timeline[0].project = Some("Apollo".to_string());
+    timeline[1].project = Some("Apollo".to_string());
+    timeline[2].project = Some("Gemini".to_string());
+    
+    let rollup = rollup_by_project(&timeline);
+    assert_eq!(rollup.len(), 3);
+    assert_eq!(rollup[0].project.as_deref(), Some("Apollo"));
+    assert_eq!(rollup[0].total_minutes, 30);
+    assert_eq!(rollup[0].interval_count, 2);
+    assert!((rollup[0].percentage - 60.0).abs() < 0.01);
+    assert_eq!(rollup[1].project.as_deref(), Some("Gemini"));
+    assert_eq!(rollup[2].project, None);
+    assert_eq!(rollup[2].total_minutes, 5);
+    
+    println!("✓ Test: Project inheritance and rollup passed");
+}
+
+#[test]
+fn test_category_rules() {
+    use log15_lib::rules::*;
+    
+    let conn = init_test_db();
+    
+    insert_rule(&conn, "standup", RuleMatchType::Contains, "Meetings", 0).unwrap();
+    insert_rule(&conn, r"^(PR|code) review", RuleMatchType::Regex, "Reviews", 0).unwrap();
+    insert_rule(&conn, "review", RuleMatchType::Contains, "Other", 10).unwrap();
+    assert!(insert_rule(&conn, "(unclosed", RuleMatchType::Regex, "Broken", 0).is_err());
+    assert!(insert_rule(&conn, "x", RuleMatchType::Contains, "  ", 0).is_err());
+    
+    let rules = load_compiled_rules(&conn).unwrap();
+    assert_eq!(categorize("Daily Standup", &rules), Some("Meetings"));
+    assert_eq!(categorize("pr review", &rules), Some("Reviews"));
+    assert_eq!(categorize("design review", &rules), Some("Other"));
+    assert_eq!(categorize("coding", &rules), None);
+    
+    // Retroactive application only touches the requested date range
+    for (date, words) in [("2024-02-01", "standup"), ("2024-02-02", "code review"), ("2024-03-01", "standup")] {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+             VALUES (?1, ?1, 60, 'completed', 0)",
+            [date],
+        ).unwrap();
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+             VALUES (?1, 1, ?2, ?3, 'recorded')",
+            rusqlite::params![conn.last_insert_rowid(), date, words],
+        ).unwrap();
+    }
+    assert_eq!(apply_rules_in_range(&conn, "2024-02-01", "2024-02-29").unwrap(), 2);
+    // Re-applying is a no-op
+    assert_eq!(apply_rules_in_range(&conn, "2024-02-01", "2024-02-29").unwrap(), 0);
+    
+    let categories: Vec<Option<String>> = conn
+        .prepare("SELECT category FROM intervals ORDER BY id").unwrap()
+        .query_map([], |row| row.get(0)).unwrap()
+        .map(|c| c.unwrap())
+        .collect();
+    assert_eq!(categories, vec![Some("Meetings".to_string()), Some("Reviews".to_string()), None]);
+    
+    // Previewing a proposed rule reports matches without changing anything
+    let proposed = CategoryRule {
+        id: None,
+        pattern: "standup|review".to_string(),
+        match_type: RuleMatchType::Regex,
+        category: "Meetings".to_string(),
+        priority: 0,
+        enabled: true,
+        created_at: None,
+    };
+    let preview = preview_rule_in_range(&conn, proposed, "2024-01-01", "2024-12-31").unwrap();
+    assert_eq!(preview.total_matches, 3);
+    // Only the "code review" interval and the uncategorized March standup would change
+    assert_eq!(preview.changed_count, 2);
+    assert_eq!(preview.matches[0].date, "2024-03-01");
+    assert_eq!(query_rules(&conn).unwrap().len(), 3);
+    
+    println!("✓ Test: Category rules passed");
+}
+
+#[test]
+fn test_deep_work_detection() {
+    let conn = init_test_db();
+    
+    // Monday 2024-02-05: one workblock with a 3-interval "coding" run, an away break and a short run
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-02-05', '2024-02-05T09:00:00+00:00', 120, 'completed', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    let entries = [
+        ("Coding", "recorded"),
+        ("coding", "recorded"),
+        ("coding ", "recorded"),
+        ("Away from workspace", "auto_away"),
+        ("coding", "recorded"),
+        ("coding", "recorded"),
+        ("email", "recorded"),
+    ];
+    let day_start = chrono::DateTime::parse_from_rfc3339("2024-02-05T09:00:00+00:00").unwrap();
+    for (i, (words, status)) in entries.iter().enumerate() {
+        let start = (day_start + Duration::minutes(i as i64 * 15)).to_rfc3339();
+        let end = (day_start + Duration::minutes(i as i64 * 15 + 15)).to_rfc3339();
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![workblock_id, i as i32 + 1, start, end, words, status],
+        ).unwrap();
+    }
+    
+    let review = query_weekly_review(&conn, "2024-02-05").unwrap();
+    assert_eq!(review.days.len(), 7);
+    assert_eq!(review.end_date, "2024-02-11");
+    assert_eq!(review.deep_work_sessions, 1);
+    assert_eq!(review.deep_work_minutes, 45);
+    assert_eq!(review.days[0].deep_work_sessions, 1);
+    assert_eq!(review.total_workblocks, 1);
+    assert_eq!(review.top_activities[0].word, "coding");
+    assert_eq!(review.top_activities[0].count, 5);
+    
+    assert_eq!(
+        week_start_for(chrono::NaiveDate::from_ymd_opt(2024, 2, 8).unwrap()),
+        chrono::NaiveDate::from_ymd_opt(2024, 2, 5).unwrap()
+    );
+    
+    println!("✓ Test: Deep work detection passed");
+}
+
+#[test]
+fn test_cancel_reasons_in_weekly_review() {
+    let conn = init_test_db();
+    
+    let workblocks = [
+        ("2024-02-05", "cancelled", Some(CancelReason::Meeting), Some("standup ran long")),
+        ("2024-02-06", "cancelled", Some(CancelReason::Meeting), Some("  ")),
+        ("2024-02-07", "cancelled", Some(CancelReason::Distracted), None),
+        ("2024-02-08", "cancelled", None, None),
+        ("2024-02-09", "completed", None, None),
+        ("2024-02-12", "cancelled", Some(CancelReason::Tired), None),  // Next week
+    ];
+    for (date, status, reason, note) in workblocks {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+             VALUES (?1, ?2, 30, ?3, 0)",
+            rusqlite::params![date, format!("{}T09:00:00+00:00", date), status],
+        ).unwrap();
+        store_cancel_reason(&conn, conn.last_insert_rowid(), reason, note).unwrap();
+    }
+    
+    let review = query_weekly_review(&conn, "2024-02-05").unwrap();
+    assert_eq!(review.cancelled_workblocks, 4);
+    assert_eq!(review.cancellation_reasons.len(), 3);
+    let meeting = &review.cancellation_reasons[0];
+    assert_eq!(meeting.reason, Some(CancelReason::Meeting));
+    assert_eq!(meeting.count, 2);
+    // Blank notes are not kept
+    assert_eq!(meeting.notes, vec!["standup ran long".to_string()]);
+    assert!(review.cancellation_reasons.iter().any(|c| c.reason.is_none() && c.count == 1));
+    
+    assert_eq!(CancelReason::from_str(CancelReason::PlansChanged.as_str()), CancelReason::PlansChanged);
+    
+    println!("✓ Test: Cancel reasons in weekly review passed");
+}
+
+#[test]
+fn test_away_causes_in_weekly_review() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-02-06', '2024-02-06T09:00:00+00:00', 60, 'completed', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    
+    // 15-minute intervals: two idle, one timed out, one recorded before causes were kept, one answered
+    let causes = [Some(AwayCause::SystemIdle), Some(AwayCause::PromptTimeout), Some(AwayCause::SystemIdle), None, None];
+    for (i, cause) in causes.iter().enumerate() {
+        let start = chrono::DateTime::parse_from_rfc3339("2024-02-06T09:00:00+00:00").unwrap() + Duration::minutes(15 * i as i64);
+        let answered = i == 4;
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                workblock_id,
+                i as i32 + 1,
+                start.to_rfc3339(),
+                (start + Duration::minutes(15)).to_rfc3339(),
+                if answered { "coding" } else { "Away from workspace" },
+                if answered { "recorded" } else { "auto_away" },
+            ],
+        ).unwrap();
+        if let Some(cause) = cause {
+            store_away_cause(&conn, conn.last_insert_rowid(), *cause).unwrap();
+        }
+    }
+    
+    let review = query_weekly_review(&conn, "2024-02-05").unwrap();
+    assert_eq!(
+        review.away_causes,
+        vec![
+            AwayCauseCount { cause: Some(AwayCause::SystemIdle), intervals: 2, minutes: 30 },
+            AwayCauseCount { cause: Some(AwayCause::PromptTimeout), intervals: 1, minutes: 15 },
+            AwayCauseCount { cause: None, intervals: 1, minutes: 15 },
+        ]
+    );
+    let interval = query_intervals_by_workblock(&conn, workblock_id).unwrap().remove(0);
+    assert_eq!(interval.away_cause, Some(AwayCause::SystemIdle));
+    
+    // Relabelled intervals no longer count as away time, whatever their cause was
+    let first = interval.id.unwrap();
+    apply_quick_status(&conn, first, "Lunch").unwrap();
+    assert_eq!(query_away_causes(&conn, "2024-02-06", "2024-02-07").unwrap()[0].cause, Some(AwayCause::PromptTimeout));
+    assert!(query_away_causes(&conn, "2024-02-07", "2024-02-08").unwrap().is_empty());
+    
+    assert_eq!(AwayCause::from_str(AwayCause::ScreenLock.as_str()), Some(AwayCause::ScreenLock));
+    
+    println!("✓ Test: Away causes in weekly review passed");
+}
+
+#[test]
+fn test_reopen_cancelled_workblock() {
+    let conn = init_test_db();
+    let now = Local::now();
+    
+    // Started 20 minutes ago as a 60-minute block, cancelled 5 minutes ago after 15 minutes
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, end_time, duration_minutes, planned_duration_minutes, status, is_archived, cancel_reason)
+         VALUES (?1, ?2, ?3, 15, 60, 'cancelled', 0, 'meeting')",
+        rusqlite::params![
+            now.format("%Y-%m-%d").to_string(),
+            (now - Duration::minutes(20)).to_rfc3339(),
+            (now - Duration::minutes(5)).to_rfc3339(),
+        ],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    
+    // Outside the resume window
+    assert!(reopen_cancelled_workblock(&conn, workblock_id, 2, now).is_err());
+    // Past the planned end
+    assert!(reopen_cancelled_workblock(&conn, workblock_id, 60, now + Duration::minutes(45)).is_err());
+    
+    let reopened = reopen_cancelled_workblock(&conn, workblock_id, 10, now).unwrap();
+    assert_eq!(reopened.status, WorkblockStatus::Active);
+    assert_eq!(reopened.duration_minutes, Some(60));
+    assert_eq!(reopened.end_time, None);
+    assert_eq!(reopened.cancel_reason, None);
+    
+    // Already active, so it cannot be reopened again
+    assert!(reopen_cancelled_workblock(&conn, workblock_id, 10, now).is_err());
+    
+    println!("✓ Test: Reopen cancelled workblock passed");
+}
+
+#[test]
+fn test_daily_rollover_claimed_once() {
+    let conn = init_test_db();
+    let db_path = conn.path().unwrap().to_string();
+    
+    // Concurrent checks for the same day transition: exactly one wins the claim
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let db_path = db_path.clone();
+            std::thread::spawn(move || {
+                let conn = Connection::open(&db_path).unwrap();
+                claim_rollover(&conn, "2024-02-05").unwrap()
+            })
+        })
+        .collect();
+    let winners = handles.into_iter().map(|h| h.join().unwrap()).filter(|won| *won).count();
+    assert_eq!(winners, 1);
+    assert!(!claim_rollover(&conn, "2024-02-05").unwrap());
+    
+    // A failed rollover releases its claim so the next check retries
+    release_rollover(&conn, "2024-02-05").unwrap();
+    assert!(claim_rollover(&conn, "2024-02-05").unwrap());
+    
+    println!("✓ Test: Daily rollover claimed once passed");
+}
+
+#[test]
+fn test_api_versioning() {
+    use log15_lib::api::*;
+    
+    assert_eq!(resolve_command("v1/get_today_date").unwrap(), "get_today_date_cmd");
+    assert_eq!(resolve_command("v1/start_workblock").unwrap(), "start_workblock");
+    assert!(resolve_command("v2/start_workblock").is_err());
+    assert!(resolve_command("start_workblock").is_err());
+    
+    // Legacy names are only flagged when called directly, not through the facade
+    assert!(deprecation_warning("get_today_date_cmd", None).is_some());
+    assert!(deprecation_warning("get_today_date_cmd", Some(API_VERSION)).is_none());
+    assert!(deprecation_warning("start_workblock", None).is_none());
+    // Deprecated commands are flagged either way
+    let warning = deprecation_warning("get_interval_time_remaining", Some(API_VERSION)).unwrap();
+    assert!(warning.contains("v1/start_timer_ticker"));
+    
+    let manifest = api_manifest();
+    assert_eq!(manifest.version, API_VERSION);
+    assert_eq!(manifest.commands.get("cancel_workblock").map(String::as_str), Some("cancel_workblock_cmd"));
+    
+    println!("✓ Test: API versioning passed");
+}
+
+#[test]
+fn test_workblock_summary() {
+    let conn = init_test_db();
+    let start = Local::now() - Duration::minutes(60);
+    
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 60, 'completed', 0)",
+        rusqlite::params![start.format("%Y-%m-%d").to_string(), start.to_rfc3339()],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    
+    let entries = [
+        ("Coding", "recorded"),
+        ("coding", "recorded"),
+        ("coding", "recorded"),
+        ("email", "recorded"),
+        ("Away from workspace", "auto_away"),
+        ("Lunch", "quick_status"),
+    ];
+    for (i, (words, status)) in entries.iter().enumerate() {
+        let interval_start = start + Duration::minutes(i as i64 * 10);
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                workblock_id,
+                i as i32 + 1,
+                interval_start.to_rfc3339(),
+                (interval_start + Duration::minutes(10)).to_rfc3339(),
+                words,
+                status
+            ],
+        ).unwrap();
+    }
+    
+    let summary = query_workblock_summary(&conn, workblock_id).unwrap();
+    assert_eq!(summary.duration_minutes, 60);
+    assert_eq!(summary.status, "completed");
+    assert_eq!((summary.intervals_recorded, summary.intervals_away, summary.intervals_quick_status), (4, 1, 1));
+    
+    // Away and quick status intervals aren't activities
+    let top: Vec<(&str, i32)> = summary.top_activities.iter().map(|a| (a.words.as_str(), a.total_minutes)).collect();
+    assert_eq!(top, vec![("coding", 30), ("email", 10)]);
+    // Three of the four recorded intervals form one deep work run
+    assert_eq!(summary.focus_score, 75);
+    assert_eq!(summary.note, None);
+    
+    assert!(query_workblock_summary(&conn, workblock_id + 1).is_err());
+    
+    println!("✓ Test: Workblock summary passed");
+}
+
+#[test]
+fn test_interval_energy_rating() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 30, 'active', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+         VALUES (?1, 1, '2024-05-01T09:00:00+00:00', 'coding', 'recorded')",
+        [workblock_id],
+    ).unwrap();
+    let interval_id = conn.last_insert_rowid();
+    
+    store_interval_energy(&conn, interval_id, Some(4)).unwrap();
+    let interval = &query_intervals_by_workblock(&conn, workblock_id).unwrap()[0];
+    assert_eq!(interval.energy, Some(4));
+    
+    // Out-of-range ratings are rejected and leave the old rating alone
+    assert!(store_interval_energy(&conn, interval_id, Some(MAX_ENERGY + 1)).is_err());
+    assert!(store_interval_energy(&conn, interval_id, Some(MIN_ENERGY - 1)).is_err());
+    assert!(store_interval_energy(&conn, interval_id + 1, Some(3)).is_err());
+    assert_eq!(query_intervals_by_workblock(&conn, workblock_id).unwrap()[0].energy, Some(4));
+    
+    store_interval_energy(&conn, interval_id, None).unwrap();
+    assert_eq!(query_intervals_by_workblock(&conn, workblock_id).unwrap()[0].energy, None);
+    
+    assert_eq!(average_energy(&[2, 3, 5]), Some(10.0 / 3.0));
+    assert_eq!(average_energy(&[]), None);
+    
+    println!("✓ Test: Interval energy rating passed");
+}
+
+#[test]
+fn test_interval_notes_and_search() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 30, 'completed', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    for (number, words) in [(1, "coding"), (2, "meeting"), (3, "100% done")] {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+             VALUES (?1, ?2, ?3, ?4, 'recorded')",
+            rusqlite::params![workblock_id, number, format!("2024-05-01T09:{:02}:00+00:00", number * 10), words],
+        ).unwrap();
+    }
+    let intervals = query_intervals_by_workblock(&conn, workblock_id).unwrap();
+    let coding_id = intervals[0].id.unwrap();
+    
+    store_interval_notes(&conn, coding_id, Some("  Chased a Flaky test in the parser.  ")).unwrap();
+    let coding = &query_intervals_by_workblock(&conn, workblock_id).unwrap()[0];
+    assert_eq!(coding.notes.as_deref(), Some("Chased a Flaky test in the parser."));
+    assert_eq!(coding.words.as_deref(), Some("coding"));
+    
+    // Search matches notes as well as words, case-insensitively
+    let found = query_search_intervals(&conn, "flaky", 10).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].interval.id, Some(coding_id));
+    assert_eq!(found[0].date, "2024-05-01");
+    assert_eq!(query_search_intervals(&conn, "MEETING", 10).unwrap().len(), 1);
+    // LIKE wildcards are matched literally
+    assert_eq!(query_search_intervals(&conn, "%", 10).unwrap().len(), 1);
+    assert!(query_search_intervals(&conn, "  ", 10).unwrap().is_empty());
+    
+    // Blank notes clear, oversized notes are rejected
+    assert!(store_interval_notes(&conn, coding_id, Some(&"x".repeat(MAX_NOTES_LENGTH + 1))).is_err());
+    store_interval_notes(&conn, coding_id, Some("   ")).unwrap();
+    assert_eq!(query_intervals_by_workblock(&conn, workblock_id).unwrap()[0].notes, None);
+    assert!(store_interval_notes(&conn, coding_id + 100, Some("lost")).is_err());
+    
+    println!("✓ Test: Interval notes and search passed");
+}
+
+#[test]
+fn test_workblock_title() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 60, 'active', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    assert_eq!(query_workblock_by_id(&conn, workblock_id).unwrap().title, None);
+    
+    store_workblock_title(&conn, workblock_id, Some("  Write Q3 report ")).unwrap();
+    assert_eq!(query_workblock_by_id(&conn, workblock_id).unwrap().title.as_deref(), Some("Write Q3 report"));
+    
+    assert!(store_workblock_title(&conn, workblock_id, Some(&"x".repeat(MAX_WORKBLOCK_TITLE_LENGTH + 1))).is_err());
+    assert!(normalize_workblock_title(Some(&"x".repeat(MAX_WORKBLOCK_TITLE_LENGTH + 1))).is_err());
+    assert_eq!(normalize_workblock_title(Some("   ")).unwrap(), None);
+    assert!(store_workblock_title(&conn, workblock_id + 1, Some("Elsewhere")).is_err());
+    store_workblock_title(&conn, workblock_id, Some("")).unwrap();
+    assert_eq!(query_workblock_by_id(&conn, workblock_id).unwrap().title, None);
+    
+    println!("✓ Test: Workblock title passed");
+}
+
+#[test]
+fn test_word_counts() {
+    use log15_lib::settings::{query_word_cloud_settings, WordCloudSettings};
+    
+    let settings = WordCloudSettings::default();
+    let phrases = ["Code review of the API", "code-review notes", "Wrote docs for the API", "API: 2 bugs", "user's bug"];
+    let counts: Vec<(String, i32)> = count_words(phrases, &settings).into_iter().map(|w| (w.word, w.count)).collect();
+    assert_eq!(
+        counts,
+        vec![
+            ("api".to_string(), 3),
+            ("bug".to_string(), 1),
+            ("bugs".to_string(), 1),
+            ("code".to_string(), 1),
+            ("code-review".to_string(), 1),
+            ("docs".to_string(), 1),
+            ("notes".to_string(), 1),
+            ("review".to_string(), 1),
+            ("user's".to_string(), 1),
+            ("wrote".to_string(), 1),
+        ]
+    );
+    
+    // Custom stop words and a lower minimum length
+    let custom = WordCloudSettings { stop_words: vec!["api".to_string()], min_length: 2 };
+    let counts: Vec<String> = count_words(["API of the 42 PRs"], &custom).into_iter().map(|w| w.word).collect();
+    assert_eq!(counts, vec!["of", "prs", "the"]);
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    assert_eq!(query_word_cloud_settings(&conn), settings);
+    
+    println!("✓ Test: Word counts passed");
+}
+
+#[test]
+fn test_daily_visualization_single_load() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let add_workblock = |date: &str, hour: u32, status: &str, end: &str, answers: &[&str]| {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, end_time, duration_minutes, status) VALUES (?1, ?2, ?3, 60, ?4)",
+            rusqlite::params![date, format!("{}T{:02}:00:00+00:00", date, hour), format!("{}T{}+00:00", date, end), status],
+        ).unwrap();
+        let workblock_id = conn.last_insert_rowid();
+        for (i, words) in answers.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'recorded')",
+                rusqlite::params![
+                    workblock_id,
+                    i as i32 + 1,
+                    format!("{}T{:02}:{:02}:00+00:00", date, hour, i * 15),
+                    format!("{}T{:02}:{:02}:00+00:00", date, hour, i * 15 + 15),
+                    words,
+                ],
+            ).unwrap();
+        }
+        workblock_id
+    };
+    let completed = add_workblock("2024-05-06", 9, "completed", "10:00:00", &["coding", "coding + meeting", "emails"]);
+    // Cancelled at 14:20: the 14:30 interval never happened
+    let cancelled = add_workblock("2024-05-06", 14, "cancelled", "14:20:00", &["design", "review", "late"]);
+    add_workblock("2024-05-07", 9, "completed", "10:00:00", &["other day"]);
+    let split_interval: i64 = conn.query_row(
+        "SELECT id FROM intervals WHERE workblock_id = ?1 AND interval_number = 2", [completed], |row| row.get(0),
+    ).unwrap();
+    store_activity_splits(&conn, split_interval, &[
+        ActivitySplit { words: "coding".to_string(), percentage: 50 },
+        ActivitySplit { words: "meeting".to_string(), percentage: 50 },
+    ]).unwrap();
+    conn.execute(
+        "INSERT INTO interval_attachments (interval_id, file_name, stored_path, size_bytes) VALUES (?1, 'a.png', '/tmp/a.png', 1)",
+        [split_interval],
+    ).unwrap();
+    
+    let data = query_daily_visualization_data(&conn, "2024-05-06").unwrap();
+    assert_eq!(data.workblocks.iter().map(|w| w.id).collect::<Vec<_>>(), vec![completed, cancelled]);
+    
+    // Each workblock's view matches one built from that workblock alone
+    let minutes = |activities: &[ActivityData]| {
+        let mut minutes: Vec<(String, i32)> = activities.iter().map(|a| (a.words.clone(), a.total_minutes)).collect();
+        minutes.sort();
+        minutes
+    };
+    for view in &data.workblocks {
+        let source = VisualizationSource::load_workblock(&conn, view.id).unwrap();
+        let alone = build_workblock_visualization(&conn, &source, &source.workblocks[0]).unwrap();
+        assert_eq!(serde_json::to_value(&view.timeline_data).unwrap(), serde_json::to_value(&alone.timeline_data).unwrap());
+        assert_eq!(minutes(&view.activity_data), minutes(&alone.activity_data));
+    }
+    assert_eq!(data.workblocks[0].timeline_data[1].attachments.len(), 1);
+    let cancelled_view = &data.workblocks[1].timeline_data;
+    assert_eq!(cancelled_view.len(), 2);
+    assert_eq!(cancelled_view[1].workblock_status.as_deref(), Some("cancelled"));
+    
+    // The aggregate covers both workblocks of the day and nothing from the next
+    let aggregate = &data.daily_aggregate;
+    assert_eq!(aggregate.total_workblocks, 2);
+    assert_eq!(aggregate.timeline_data.len(), 5);
+    assert_eq!(
+        minutes(&aggregate.activity_data),
+        vec![
+            ("coding".to_string(), 23),
+            ("design".to_string(), 15),
+            ("emails".to_string(), 15),
+            ("meeting".to_string(), 7),
+            ("review".to_string(), 15),
+        ]
+    );
+    
+    println!("✓ Test: Daily visualization single load passed");
+}
@@ -0,0 +1,91 @@
+// Test the interval math shared by the timer, interval answers and auto-away
+// Run with: cargo test --test interval_schedule_test
+
+use chrono::{Duration, Local, TimeZone, Timelike};
+use log15_lib::db::{create_schema, store_setting};
+use log15_lib::interval_schedule::*;
+use log15_lib::settings::*;
+use rusqlite::Connection;
+
+#[test]
+fn test_total_intervals_and_planned_minutes() {
+    assert_eq!(total_intervals(60, 15 * 60), 4);
+    assert_eq!(total_intervals(50, 15 * 60), 4);  // A partial last interval counts
+    assert_eq!(total_intervals(1, 10), 6);
+    assert_eq!(total_intervals(0, 10), 0);
+
+    assert_eq!(planned_minutes(4, 15 * 60), 60);
+    assert_eq!(planned_minutes(6, 10), 1);
+    assert_eq!(planned_minutes(7, 10), 2);  // Rounded up to whole minutes
+    assert_eq!(planned_minutes(total_intervals(90, interval_seconds()), interval_seconds()), 90);
+
+    println!("✓ Test: Total intervals and planned minutes passed");
+}
+
+#[test]
+fn test_is_last_interval() {
+    let total = total_intervals(60, 15 * 60);
+    assert!(!is_last_interval(3, total));
+    assert!(is_last_interval(4, total));
+    assert!(is_last_interval(5, total));
+
+    println!("✓ Test: Is last interval passed");
+}
+
+#[test]
+fn test_boundaries_and_remaining_time() {
+    let start = Local.with_ymd_and_hms(2024, 5, 6, 9, 7, 30).unwrap();
+    assert_eq!(interval_boundary(start, 2, 15 * 60), start + Duration::minutes(30));
+    assert_eq!(interval_end(start, 15 * 60), start + Duration::minutes(15));
+    assert_eq!(intervals_elapsed(start, start + Duration::minutes(44), 15 * 60), 2);
+    assert_eq!(intervals_elapsed(start, start - Duration::minutes(5), 15 * 60), 0);
+
+    let aligned = align_to_boundary(start, 15 * 60);
+    assert_eq!((aligned.hour(), aligned.minute(), aligned.second()), (9, 0, 0));
+
+    // In interval 2 of 4: this one and two more remain
+    let interval_start = start + Duration::minutes(15);
+    let end = workblock_end(interval_start, 2, 4, 15 * 60);
+    assert_eq!(end, start + Duration::minutes(60));
+    assert_eq!(remaining_seconds(end, interval_start + Duration::minutes(5)), 40 * 60);
+    assert_eq!(remaining_seconds(end, end + Duration::minutes(1)), 0);
+
+    println!("✓ Test: Boundaries and remaining time passed");
+}
+
+#[test]
+fn test_warning_time() {
+    let boundary = Local.with_ymd_and_hms(2024, 5, 6, 9, 15, 0).unwrap();
+    assert_eq!(warning_time(boundary, 60, 15 * 60), Some(boundary - Duration::seconds(60)));
+    assert_eq!(warning_time(boundary, 0, 15 * 60), None);  // Off
+    assert_eq!(warning_time(boundary, 60, 10), None);  // Would come before the interval started
+    assert_eq!(warning_time(boundary, 10, 10), None);
+    assert_eq!(warning_time(boundary, 5, 10), Some(boundary - Duration::seconds(5)));
+    
+    println!("✓ Test: Warning time passed");
+}
+
+#[test]
+fn test_interval_warning_settings_storage() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    assert_eq!(query_interval_warning_settings(&conn), IntervalWarningSettings::default());
+    
+    seed_default_settings(&conn).unwrap();
+    let warning = query_interval_warning_settings(&conn);
+    assert_eq!(warning.seconds_before, DEFAULT_INTERVAL_WARNING_SECONDS);
+    assert!(!warning.notification);
+    
+    store_setting(&conn, INTERVAL_WARNING_SECONDS_KEY, "0").unwrap();
+    store_setting(&conn, INTERVAL_WARNING_NOTIFICATION_KEY, "true").unwrap();
+    assert_eq!(
+        query_interval_warning_settings(&conn),
+        IntervalWarningSettings { seconds_before: 0, notification: true }
+    );
+    
+    // Out of range falls back to the default
+    store_setting(&conn, INTERVAL_WARNING_SECONDS_KEY, &(MAX_INTERVAL_WARNING_SECONDS + 1).to_string()).unwrap();
+    assert_eq!(query_interval_warning_settings(&conn).seconds_before, DEFAULT_INTERVAL_WARNING_SECONDS);
+    
+    println!("✓ Test: Interval warning settings storage passed");
+}
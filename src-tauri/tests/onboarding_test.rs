@@ -0,0 +1,65 @@
+// Test first-run detection, default settings seeding and onboarding progress
+// Run with: cargo test --test onboarding_test
+
+use log15_lib::db::{query_setting, store_setting};
+use log15_lib::onboarding::*;
+use log15_lib::settings::{query_auto_away_settings, AUTO_AWAY_MESSAGE_KEY, DEFAULT_AUTO_AWAY_MESSAGE};
+use rusqlite::Connection;
+
+#[test]
+fn test_first_run_seeds_defaults_once() {
+    let conn = Connection::open_in_memory().unwrap();
+    
+    assert!(initialize_first_run(&conn).unwrap());
+    assert_eq!(query_setting(&conn, AUTO_AWAY_MESSAGE_KEY).unwrap().as_deref(), Some(DEFAULT_AUTO_AWAY_MESSAGE));
+    
+    // Later launches keep the user's choices
+    store_setting(&conn, AUTO_AWAY_MESSAGE_KEY, "Stepped out").unwrap();
+    assert!(!initialize_first_run(&conn).unwrap());
+    assert_eq!(query_auto_away_settings(&conn).message, "Stepped out");
+    
+    let state = query_onboarding_state(&conn).unwrap();
+    assert!(state.first_launch_at.is_some());
+    assert_eq!(state.next_step, Some(OnboardingStep::Welcome));
+    assert!(state.completed_at.is_none());
+    
+    println!("✓ Test: First run seeds defaults once passed");
+}
+
+#[test]
+fn test_onboarding_steps() {
+    let conn = Connection::open_in_memory().unwrap();
+    initialize_first_run(&conn).unwrap();
+    
+    // Steps can be completed out of order and more than once
+    record_onboarding_step(&conn, OnboardingStep::Hotkeys).unwrap();
+    let state = record_onboarding_step(&conn, OnboardingStep::Hotkeys).unwrap();
+    assert_eq!(state.completed_steps, vec![OnboardingStep::Hotkeys]);
+    assert_eq!(state.next_step, Some(OnboardingStep::Welcome));
+    
+    for step in OnboardingStep::ALL {
+        record_onboarding_step(&conn, step).unwrap();
+    }
+    let state = query_onboarding_state(&conn).unwrap();
+    assert_eq!(state.completed_steps, OnboardingStep::ALL.to_vec());
+    assert_eq!(state.next_step, None);
+    assert!(state.completed_at.is_some());
+    
+    println!("✓ Test: Onboarding steps passed");
+}
+
+#[test]
+fn test_existing_install_skips_onboarding() {
+    let conn = Connection::open_in_memory().unwrap();
+    log15_lib::db::create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-02-05', '2024-02-05T09:00:00+00:00', 60, 'completed', 0)",
+        [],
+    ).unwrap();
+    
+    assert!(!initialize_first_run(&conn).unwrap());
+    assert!(query_onboarding_state(&conn).unwrap().completed_at.is_some());
+    
+    println!("✓ Test: Existing install skips onboarding passed");
+}
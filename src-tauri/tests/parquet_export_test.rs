@@ -0,0 +1,44 @@
+// Test Parquet export of intervals and workblocks
+// Run with: cargo test --features parquet-export --test parquet_export_test
+
+#![cfg(feature = "parquet-export")]
+
+use log15_lib::db::create_schema;
+use log15_lib::parquet_export::*;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use rusqlite::{params, Connection};
+
+#[test]
+fn test_export_intervals_and_workblocks() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived, project)
+         VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 30, 'completed', 0, 'log15')",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    for (number, words) in [(1, Some("coding")), (2, None)] {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+             VALUES (?1, ?2, '2024-05-01T09:00:00+00:00', ?3, 'recorded')",
+            params![workblock_id, number, words],
+        ).unwrap();
+    }
+
+    let intervals = query_intervals_batch(&conn).unwrap();
+    assert_eq!(intervals.num_rows(), 2);
+    assert_eq!(intervals.schema().field(9).name(), "project");
+
+    let dir = std::env::temp_dir().join(format!("log15_parquet_test_{}", std::process::id()));
+    let summary = export_to_dir(&conn, &dir).unwrap();
+    assert_eq!((summary.intervals, summary.workblocks), (2, 1));
+
+    let reader = SerializedFileReader::new(std::fs::File::open(dir.join(INTERVALS_FILE)).unwrap()).unwrap();
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+    let reader = SerializedFileReader::new(std::fs::File::open(dir.join(WORKBLOCKS_FILE)).unwrap()).unwrap();
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    println!("✓ Test: Parquet export of intervals and workblocks passed");
+}
@@ -0,0 +1,63 @@
+// Test the queue of prompts waiting behind the one on screen
+// Run with: cargo test --test prompt_queue_test
+
+use log15_lib::window_manager::{PendingPrompts, PromptLabel, PromptQueue};
+use serde_json::json;
+
+#[test]
+fn test_enqueue_keeps_order_without_duplicates() {
+    let mut pending = PendingPrompts::default();
+    assert!(pending.enqueue(8, Some(7)));
+    assert!(pending.enqueue(9, Some(7)));
+    
+    // Already queued, or the one on screen
+    assert!(!pending.enqueue(8, Some(7)));
+    assert!(!pending.enqueue(7, Some(7)));
+    
+    assert_eq!(pending.iter().collect::<Vec<_>>(), vec![8, 9]);
+    assert!(pending.contains(9));
+    assert!(!pending.contains(7));
+    
+    println!("✓ Test: Enqueue keeps order without duplicates passed");
+}
+
+#[test]
+fn test_next_skips_answered_intervals() {
+    let mut pending = PendingPrompts::default();
+    for interval_id in [8, 9, 10] {
+        pending.enqueue(interval_id, Some(7));
+    }
+    
+    // Interval 8 was answered elsewhere (e.g. from the main window) while it waited
+    assert_eq!(pending.next(|interval_id| interval_id != 8), Some(9));
+    assert_eq!(pending.iter().collect::<Vec<_>>(), vec![10]);
+    assert_eq!(pending.next(|_| false), None);
+    assert_eq!(pending.next(|_| true), None);
+    
+    pending.enqueue(11, None);
+    pending.clear();
+    assert_eq!(pending.iter().count(), 0);
+    
+    println!("✓ Test: Next skips answered intervals passed");
+}
+
+#[test]
+fn test_prompt_queue_payload() {
+    let label = |interval_id, interval_number, start: &str, end: Option<&str>| PromptLabel {
+        interval_id,
+        interval_number,
+        start_time: start.to_string(),
+        end_time: end.map(str::to_string),
+    };
+    let queue = PromptQueue {
+        current: Some(label(7, 1, "2026-10-16T09:00:00+00:00", Some("2026-10-16T09:15:00+00:00"))),
+        pending: vec![label(8, 2, "2026-10-16T09:15:00+00:00", Some("2026-10-16T09:30:00+00:00"))],
+    };
+    let value = serde_json::to_value(&queue).unwrap();
+    assert_eq!(value["current"]["interval_id"], json!(7));
+    assert_eq!(value["pending"][0]["end_time"], json!("2026-10-16T09:30:00+00:00"));
+    
+    assert_eq!(serde_json::to_value(PromptQueue::default()).unwrap(), json!({ "current": null, "pending": [] }));
+    
+    println!("✓ Test: Prompt queue payload passed");
+}
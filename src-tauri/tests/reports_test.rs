@@ -0,0 +1,56 @@
+// Test JSON report envelope and request format
+// Run with: cargo test --test reports_test
+
+use log15_lib::reports::{to_envelope, ReportRequest, REPORT_SCHEMA_VERSION};
+use log15_lib::time_audit::TimeAudit;
+
+#[test]
+fn test_report_request_format() {
+    let request: ReportRequest = serde_json::from_str(r#"{"report": "monthly_summary", "year": 2024, "month": 5}"#).unwrap();
+    assert_eq!(request, ReportRequest::MonthlySummary { year: 2024, month: 5 });
+    
+    // The name always matches the serialized tag
+    let requests = vec![
+        ReportRequest::DailyAggregate { date: "2024-05-01".to_string() },
+        ReportRequest::WorkblockSummary { workblock_id: 1 },
+        ReportRequest::WeeklyReview { week_start: "2024-04-29".to_string() },
+        ReportRequest::MonthlySummary { year: 2024, month: 5 },
+        ReportRequest::GoalStatus { date: "2024-05-01".to_string() },
+        ReportRequest::BurnoutIndicator { week_start: "2024-04-29".to_string() },
+        ReportRequest::TimeAudit { date: "2024-05-01".to_string() },
+        ReportRequest::ShareCardStats { date: "2024-05-01".to_string() },
+    ];
+    for request in requests {
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["report"], request.name());
+    }
+    
+    assert!(serde_json::from_str::<ReportRequest>(r#"{"report": "unknown"}"#).is_err());
+    
+    println!("✓ Test: Report request format passed");
+}
+
+#[test]
+fn test_report_envelope() {
+    let request = ReportRequest::TimeAudit { date: "2024-05-01".to_string() };
+    let audit = TimeAudit {
+        date: "2024-05-01".to_string(),
+        tracked_minutes: 60,
+        meeting_minutes: None,
+        active_computer_minutes: Some(90),
+        untracked_computer_minutes: Some(30),
+        untracked_percentage: Some(100.0 / 3.0),
+    };
+    let envelope = to_envelope(&request, &audit, "2024-05-01T18:00:00+02:00").unwrap();
+    
+    let json = serde_json::to_value(&envelope).unwrap();
+    assert_eq!(json["schema_version"], REPORT_SCHEMA_VERSION);
+    assert_eq!(json["report"], "time_audit");
+    assert_eq!(json["generated_at"], "2024-05-01T18:00:00+02:00");
+    assert_eq!(json["request"]["date"], "2024-05-01");
+    assert_eq!(json["data"]["tracked_minutes"], 60);
+    assert_eq!(json["data"]["untracked_computer_minutes"], 30);
+    assert!(json["data"]["meeting_minutes"].is_null());
+    
+    println!("✓ Test: Report envelope passed");
+}
@@ -0,0 +1,52 @@
+// Test the local REST API: its settings and token checks
+// Run with: cargo test --features rest-api --test rest_api_test
+
+use log15_lib::db::{create_schema, store_setting};
+use log15_lib::settings::*;
+use rusqlite::Connection;
+
+#[test]
+fn test_rest_api_settings() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    
+    // Off on port 15015 without a token until it is first enabled
+    seed_default_settings(&conn).unwrap();
+    assert_eq!(query_rest_api_settings(&conn), RestApiSettings::default());
+    assert_eq!(query_rest_api_settings(&conn).port, DEFAULT_REST_API_PORT);
+    
+    store_setting(&conn, REST_API_ENABLED_KEY, "true").unwrap();
+    store_setting(&conn, REST_API_PORT_KEY, "8080").unwrap();
+    store_setting(&conn, REST_API_TOKEN_KEY, "abc").unwrap();
+    assert_eq!(
+        query_rest_api_settings(&conn),
+        RestApiSettings { enabled: true, port: 8080, token: "abc".to_string() }
+    );
+    
+    // Ports outside 1-65535 fall back to the default
+    for port in ["0", "70000", "http"] {
+        store_setting(&conn, REST_API_PORT_KEY, port).unwrap();
+        assert_eq!(query_rest_api_settings(&conn).port, DEFAULT_REST_API_PORT);
+    }
+    
+    println!("✓ Test: REST API settings passed");
+}
+
+#[cfg(feature = "rest-api")]
+#[test]
+fn test_token_check() {
+    use log15_lib::rest_api::is_authorized;
+    
+    assert!(is_authorized(Some("Bearer s3cret"), "s3cret"));
+    assert!(is_authorized(Some("Bearer s3cret "), "s3cret"));
+    assert!(!is_authorized(Some("Bearer s3cre"), "s3cret"));
+    assert!(!is_authorized(Some("Bearer s3cret2"), "s3cret"));
+    assert!(!is_authorized(Some("s3cret"), "s3cret"));
+    assert!(!is_authorized(Some("Basic s3cret"), "s3cret"));
+    assert!(!is_authorized(None, "s3cret"));
+    
+    // Without a token nothing gets in, not even an empty bearer
+    assert!(!is_authorized(Some("Bearer "), ""));
+    
+    println!("✓ Test: Token check passed");
+}
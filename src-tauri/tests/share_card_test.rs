@@ -0,0 +1,44 @@
+// Test the share card stats and PNG rendering
+// Run with: cargo test --test share_card_test
+
+use log15_lib::db::create_schema;
+use log15_lib::share_card::*;
+use rusqlite::{params, Connection};
+
+#[test]
+fn test_share_card() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 45, 'completed', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    for (number, status) in [(1, "recorded"), (2, "recorded"), (3, "auto_away")] {
+        let start = format!("2024-05-01T09:{:02}:00+00:00", (number - 1) * 15);
+        let end = format!("2024-05-01T09:{:02}:00+00:00", number * 15 - 1);
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, end_time, words, status)
+             VALUES (?1, ?2, ?3, ?4, 'private words', ?5)",
+            params![workblock_id, number, start, end, status],
+        ).unwrap();
+    }
+    
+    let stats = query_share_card_stats(&conn, "2024-05-01").unwrap();
+    assert_eq!((stats.tracked_minutes, stats.workblocks, stats.intervals_recorded), (45, 1, 2));
+    assert_eq!(stats.hourly_minutes.len(), 24);
+    assert_eq!(stats.hourly_minutes.iter().sum::<i32>(), 28);
+    
+    let pixels = render_share_card(&stats);
+    assert_eq!(pixels.len(), (CARD_WIDTH * CARD_HEIGHT * 4) as usize);
+    let png = encode_png(&pixels, CARD_WIDTH, CARD_HEIGHT).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    
+    // An empty day still renders
+    let empty = query_share_card_stats(&conn, "2024-05-02").unwrap();
+    assert_eq!(empty.tracked_minutes, 0);
+    assert_eq!(render_share_card(&empty).len(), pixels.len());
+    
+    println!("✓ Test: Share card passed");
+}
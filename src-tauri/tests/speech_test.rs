@@ -0,0 +1,120 @@
+// Test voice answers: reading the recording, tidying transcripts and talking to the transcription API
+// Run with: cargo test --test speech_test
+
+use log15_lib::db::{create_schema, store_setting};
+use log15_lib::settings::{
+    query_transcription_settings, TranscriptionBackend, TranscriptionSettings, TRANSCRIPTION_BACKEND_KEY,
+    TRANSCRIPTION_MODEL_KEY, VOICE_INPUT_ENABLED_KEY,
+};
+use log15_lib::speech::*;
+use rusqlite::Connection;
+use serde_json::json;
+
+fn wav(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&channels.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+    bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn test_parse_wav() {
+    let audio = parse_wav(&wav(WHISPER_SAMPLE_RATE, 1, &[0, 16384, -32768])).unwrap();
+    assert_eq!(audio.sample_rate, WHISPER_SAMPLE_RATE);
+    assert_eq!(audio.channels, 1);
+    assert_eq!(audio.samples, vec![0, 16384, -32768]);
+
+    // Unknown chunks before the data are skipped
+    let mut with_list = wav(WHISPER_SAMPLE_RATE, 1, &[7]);
+    let data_at = with_list.len() - 10;
+    with_list.splice(data_at..data_at, b"LIST\x03\x00\x00\x00abc\x00".iter().copied());
+    assert_eq!(parse_wav(&with_list).unwrap().samples, vec![7]);
+
+    assert!(parse_wav(b"not a wav file").is_err());
+    let mut float_wav = wav(WHISPER_SAMPLE_RATE, 1, &[0]);
+    float_wav[20] = 3; // IEEE float
+    assert!(parse_wav(&float_wav).is_err());
+    assert!(parse_wav(&wav(WHISPER_SAMPLE_RATE, 1, &[])[..36]).is_err());
+
+    println!("✓ Test: Parse WAV passed");
+}
+
+#[test]
+fn test_to_whisper_samples() {
+    // Stereo frames are averaged into mono
+    let stereo = parse_wav(&wav(WHISPER_SAMPLE_RATE, 2, &[16384, 0, -16384, -16384])).unwrap();
+    assert_eq!(to_whisper_samples(&stereo).unwrap(), vec![0.25, -0.5]);
+
+    // Whisper needs 16 kHz; the prompt window records at that rate
+    let cd_quality = parse_wav(&wav(44_100, 1, &[0])).unwrap();
+    assert!(to_whisper_samples(&cd_quality).is_err());
+
+    println!("✓ Test: Whisper samples passed");
+}
+
+#[test]
+fn test_clean_transcript() {
+    assert_eq!(clean_transcript(" Code review.\n").as_deref(), Some("Code review"));
+    assert_eq!(clean_transcript("coding  +\tmeeting!").as_deref(), Some("coding + meeting"));
+    assert_eq!(clean_transcript("  ...  "), None);
+    assert_eq!(clean_transcript(""), None);
+
+    println!("✓ Test: Clean transcript passed");
+}
+
+#[test]
+fn test_transcription_request() {
+    assert_eq!(transcriptions_url("https://api.openai.com/v1/"), "https://api.openai.com/v1/audio/transcriptions");
+    assert_eq!(
+        transcriptions_url("http://localhost:8080/v1/audio/transcriptions"),
+        "http://localhost:8080/v1/audio/transcriptions"
+    );
+
+    let body = build_transcription_body("XYZ", "whisper-1", b"RIFFdata");
+    let text = String::from_utf8_lossy(&body);
+    assert!(text.starts_with("--XYZ\r\n"));
+    assert!(text.contains("name=\"model\"\r\n\r\nwhisper-1\r\n"));
+    assert!(text.contains("filename=\"answer.wav\"\r\nContent-Type: audio/wav\r\n\r\nRIFFdata\r\n--XYZ--\r\n"));
+
+    assert_eq!(parse_transcription_response(&json!({ "text": " Emails." })).unwrap(), " Emails.");
+    let error = parse_transcription_response(&json!({ "error": { "message": "Invalid API key" } })).unwrap_err();
+    assert!(error.contains("Invalid API key"));
+    assert!(parse_transcription_response(&json!({})).is_err());
+
+    println!("✓ Test: Transcription request passed");
+}
+
+#[test]
+fn test_transcription_settings() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+
+    // Off until the user opts in
+    assert_eq!(query_transcription_settings(&conn), TranscriptionSettings::default());
+    assert!(!TranscriptionSettings::default().enabled);
+
+    store_setting(&conn, VOICE_INPUT_ENABLED_KEY, "true").unwrap();
+    store_setting(&conn, TRANSCRIPTION_BACKEND_KEY, "local").unwrap();
+    store_setting(&conn, TRANSCRIPTION_MODEL_KEY, "").unwrap();
+    let settings = query_transcription_settings(&conn);
+    assert!(settings.enabled);
+    assert_eq!(settings.backend, TranscriptionBackend::Local);
+    assert_eq!(settings.model, TranscriptionSettings::default().model);
+
+    println!("✓ Test: Transcription settings passed");
+}
@@ -0,0 +1,33 @@
+// Test startup timing: phases are recorded in order and marked once setup has returned
+// Run with: cargo test --test startup_test
+
+use log15_lib::startup::*;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_startup_timer() {
+    let mut timer = StartupTimer::new();
+    let value = timer.time("database", || {
+        std::thread::sleep(Duration::from_millis(5));
+        42
+    });
+    assert_eq!(value, 42);
+    timer.setup_done();
+    
+    let started = Instant::now();
+    std::thread::sleep(Duration::from_millis(5));
+    timer.record("prompt_window", started);
+    timer.time("tray", || ());
+    
+    let report = timer.finish();
+    let phases: Vec<(&str, bool)> = report.phases.iter().map(|p| (p.name.as_str(), p.background)).collect();
+    assert_eq!(phases, vec![("database", false), ("prompt_window", true), ("tray", true)]);
+    assert!(report.phases[0].millis >= 5 && report.phases[1].millis >= 5);
+    assert!(report.setup_millis >= 5);
+    assert!(report.total_millis >= report.setup_millis + 5);
+    
+    // Nothing is published until the app finishes starting
+    assert_eq!(get_startup_report(), None);
+    
+    println!("✓ Test: Startup timer passed");
+}
@@ -0,0 +1,214 @@
+// Test daily goal progress and streak tracking
+// Run with: cargo test --test stats_test
+
+use log15_lib::db::create_schema;
+use log15_lib::error::Log15Error;
+use log15_lib::stats::*;
+use rusqlite::Connection;
+
+fn add_workblock(conn: &Connection, date: &str, minutes: i32, recorded_intervals: i32) {
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, ?3, 'completed', 0)",
+        rusqlite::params![date, format!("{}T09:00:00+00:00", date), minutes],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    for number in 1..=recorded_intervals {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+             VALUES (?1, ?2, ?3, 'coding', 'recorded')",
+            rusqlite::params![workblock_id, number, format!("{}T09:00:00+00:00", date)],
+        ).unwrap();
+    }
+}
+
+#[test]
+fn test_goal_progress_and_streaks() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    
+    // No goals: nothing to attain and no streak
+    add_workblock(&conn, "2024-02-05", 240, 16);
+    let status = query_goal_status(&conn, "2024-02-05", false).unwrap();
+    assert!(status.goals.is_empty());
+    assert!(!status.all_attained);
+    assert_eq!(status.current_streak, 0);
+    assert!(matches!(query_goal_status(&conn, "2024-02-30", false), Err(Log15Error::InvalidInput(_))));
+    
+    assert!(store_goal(&conn, GoalMetric::TrackedMinutes, 0).is_err());
+    store_goal(&conn, GoalMetric::TrackedMinutes, 120).unwrap();
+    store_goal(&conn, GoalMetric::RecordedIntervals, 8).unwrap();
+    // Setting a goal again replaces its target
+    store_goal(&conn, GoalMetric::TrackedMinutes, 240).unwrap();
+    assert_eq!(query_goals(&conn).unwrap().len(), 2);
+    
+    // 02-05 and 02-06 attain both goals; 02-07 misses the interval goal (split over two workblocks)
+    add_workblock(&conn, "2024-02-06", 120, 4);
+    add_workblock(&conn, "2024-02-06", 120, 4);
+    add_workblock(&conn, "2024-02-07", 300, 2);
+    
+    let status = query_goal_status(&conn, "2024-02-06", false).unwrap();
+    assert!(status.all_attained);
+    assert_eq!(status.current_streak, 2);
+    
+    let status = query_goal_status(&conn, "2024-02-07", false).unwrap();
+    assert!(!status.all_attained);
+    assert_eq!(status.current_streak, 0);
+    let intervals = status.goals.iter().find(|g| g.metric == GoalMetric::RecordedIntervals).unwrap();
+    assert_eq!(intervals.actual, 2);
+    assert_eq!(intervals.percentage, 25.0);
+    
+    // While a day is still in progress an unmet goal keeps the streak from the days before
+    let status = query_goal_status(&conn, "2024-02-07", true).unwrap();
+    assert_eq!(status.current_streak, 2);
+    
+    println!("✓ Test: Goal progress and streaks passed");
+}
+
+#[test]
+fn test_wellbeing_limit() {
+    use chrono::{Duration, Local};
+    use log15_lib::settings::WellbeingSettings;
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let now = Local::now();
+    let today = now.format("%Y-%m-%d").to_string();
+    add_workblock(&conn, &today, 300, 0);
+    add_workblock(&conn, &today, 200, 0);
+    // Still running for 45 minutes: counts the elapsed time, not its planned hour
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 60, 'active', 0)",
+        rusqlite::params![today, (now - Duration::minutes(45)).to_rfc3339()],
+    ).unwrap();
+    assert_eq!(query_tracked_minutes(&conn, &today, now).unwrap(), 545);
+    
+    // Opt-in: nothing happens while the guard is off
+    let mut wellbeing = WellbeingSettings { enabled: false, daily_limit_minutes: 540 };
+    assert!(!wellbeing.exceeded_by(545));
+    assert_eq!(query_wellbeing_alert(&conn, &today, now, &wellbeing).unwrap(), None);
+    
+    wellbeing.enabled = true;
+    let alert = query_wellbeing_alert(&conn, &today, now, &wellbeing).unwrap().unwrap();
+    assert_eq!(alert.tracked_minutes, 545);
+    assert_eq!(alert.daily_limit_minutes, 540);
+    
+    // Exactly at the limit is fine
+    wellbeing.daily_limit_minutes = 545;
+    assert_eq!(query_wellbeing_alert(&conn, &today, now, &wellbeing).unwrap(), None);
+    
+    println!("✓ Test: Wellbeing limit passed");
+}
+
+#[test]
+fn test_burnout_indicator() {
+    use chrono::NaiveDate;
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let week = NaiveDate::from_ymd_opt(2024, 2, 5).unwrap(); // Monday
+    
+    // Nothing tracked: sustainable
+    let empty = query_burnout_indicator(&conn, week).unwrap();
+    assert_eq!(empty.rolling_score, 0);
+    assert_eq!(empty.level, BurnoutLevel::Low);
+    assert_eq!(empty.weekly_scores.len(), BURNOUT_ROLLING_WEEKS as usize);
+    
+    // A 10-hour Monday (over the default 9-hour limit), two hours on Saturday, and three late intervals
+    add_workblock(&conn, "2024-02-05", 600, 3);
+    add_workblock(&conn, "2024-02-10", 120, 1);
+    conn.execute(
+        "UPDATE intervals SET start_time = '2024-02-05T22:15:00+01:00' WHERE interval_number <= 3 AND workblock_id = 1",
+        [],
+    ).unwrap();
+    
+    let indicator = query_burnout_indicator(&conn, week).unwrap();
+    let signals = &indicator.week;
+    assert_eq!(signals.week_start, "2024-02-05");
+    assert_eq!((signals.late_intervals, signals.total_intervals), (3, 4));
+    assert_eq!((signals.weekend_minutes, signals.total_minutes), (120, 720));
+    assert_eq!(signals.over_limit_days, 1);
+    // Late and weekend signals saturate, one of three long days: (1 + 0.83 + 0.33) / 3
+    assert_eq!(signals.score, 72);
+    
+    // The rolling score is diluted by the three quiet weeks before
+    assert_eq!(indicator.weekly_scores, vec![0, 0, 0, 72]);
+    assert_eq!(indicator.rolling_score, 18);
+    assert_eq!(indicator.level, BurnoutLevel::Low);
+    
+    println!("✓ Test: Burnout indicator passed");
+}
+
+#[test]
+fn test_goal_milestones() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    store_goal(&conn, GoalMetric::TrackedMinutes, 120).unwrap();
+    store_goal(&conn, GoalMetric::RecordedIntervals, 8).unwrap();
+    for date in ["2024-02-05", "2024-02-06"] {
+        add_workblock(&conn, date, 120, 8);
+    }
+    add_workblock(&conn, "2024-02-07", 180, 2);
+    
+    // Only the minutes goal is reached on 02-07, which also breaks the streak
+    let status = query_goal_status(&conn, "2024-02-07", false).unwrap();
+    assert_eq!(
+        goal_milestones(&status),
+        vec![Milestone::GoalReached {
+            date: "2024-02-07".to_string(),
+            metric: GoalMetric::TrackedMinutes,
+            target: 120,
+            actual: 180,
+        }]
+    );
+    
+    // A third day in a row with every goal attained is a streak milestone
+    add_workblock(&conn, "2024-02-07", 0, 6);
+    let milestones = goal_milestones(&query_goal_status(&conn, "2024-02-07", false).unwrap());
+    assert_eq!(milestones.len(), 3);
+    assert_eq!(milestones[2], Milestone::StreakMilestone { date: "2024-02-07".to_string(), streak: 3 });
+    assert_eq!(milestones[2].key(), "streak_milestone:2024-02-07:3");
+    
+    // Each milestone is announced once; the record survives a restart because it's in the database
+    assert!(record_milestone(&conn, &milestones[2]).unwrap());
+    assert!(!record_milestone(&conn, &milestones[2]).unwrap());
+    assert!(record_milestone(&conn, &milestones[0]).unwrap());
+    
+    // Payloads are tagged with the event type
+    let budget: Milestone = WellbeingAlert {
+        date: "2024-02-07".to_string(),
+        tracked_minutes: 600,
+        daily_limit_minutes: 480,
+    }
+    .into();
+    let json = serde_json::to_value(&budget).unwrap();
+    assert_eq!(json["event"], "budget_exceeded");
+    assert_eq!(json["tracked_minutes"], 600);
+    
+    println!("✓ Test: Goal milestones passed");
+}
+
+#[test]
+fn test_active_workblock_counts_elapsed_minutes_toward_goal() {
+    use chrono::{Duration, Local};
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    store_goal(&conn, GoalMetric::TrackedMinutes, 60).unwrap();
+    let now = Local::now();
+    let today = now.format("%Y-%m-%d").to_string();
+    // A 120-minute workblock started 10 minutes ago has 10 minutes to show, not its planned length
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (?1, ?2, 120, 'active', 0)",
+        rusqlite::params![today, (now - Duration::minutes(10)).to_rfc3339()],
+    ).unwrap();
+    
+    let status = query_goal_status(&conn, &today, true).unwrap();
+    assert!((10..=11).contains(&status.goals[0].actual));
+    assert!(!status.all_attained);
+    assert!(goal_milestones(&status).is_empty());
+    
+    println!("✓ Test: Active workblock counts elapsed minutes toward goal passed");
+}
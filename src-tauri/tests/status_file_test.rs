@@ -0,0 +1,105 @@
+// Test the status file for status bars: its contents, the current activity and atomic writes
+// Run with: cargo test --test status_file_test
+
+use chrono::{Duration, Local, TimeZone};
+use log15_lib::db::create_schema;
+use log15_lib::interval_schedule::interval_seconds;
+use log15_lib::settings::{query_status_file_settings, StatusFileFormat, StatusFileSettings};
+use log15_lib::status_file::*;
+use log15_lib::status_page::StatusState;
+use log15_lib::timer::TimerState;
+use rusqlite::{params, Connection};
+
+#[test]
+fn test_status_file_contents() {
+    let now = Local.with_ymd_and_hms(2024, 5, 6, 15, 0, 0).unwrap();
+    
+    let idle = build_status_file(StatusState::Available, &TimerState::default(), Some("old".to_string()), 95, now);
+    assert_eq!(idle.activity, None);
+    assert_eq!(idle.interval_seconds_remaining, None);
+    assert_eq!(idle.text, "Idle · 1h 35m today");
+    
+    let running = TimerState {
+        workblock_id: Some(7),
+        current_interval_number: 1,
+        total_intervals: 4,
+        interval_start_time: Some(now - Duration::seconds(interval_seconds() - 452)),
+        is_running: true,
+        ..Default::default()
+    };
+    let focus = build_status_file(StatusState::Focus, &running, Some("writing docs".to_string()), 20, now);
+    assert_eq!(focus.interval_seconds_remaining, Some(452));
+    assert_eq!(focus.text, "writing docs · 7:32 · 20m today");
+    assert_eq!(render_status_file(&focus, StatusFileFormat::Text), "writing docs · 7:32 · 20m today\n");
+    
+    let json: serde_json::Value = serde_json::from_str(&render_status_file(&focus, StatusFileFormat::Json)).unwrap();
+    assert_eq!(json["state"], "focus");
+    assert_eq!(json["activity"], "writing docs");
+    assert_eq!(json["interval_seconds_remaining"], 452);
+    assert_eq!(json["today_minutes"], 20);
+    
+    // Before the first answer the block shows as plain focus
+    assert_eq!(status_line(StatusState::Focus, None, Some(60), 0), "Focus · 1:00 · 0m today");
+    assert_eq!(status_line(StatusState::Break, None, None, 60), "Break · 1h 0m today");
+    
+    println!("✓ Test: Status file contents passed");
+}
+
+#[test]
+fn test_current_activity() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    assert_eq!(query_current_activity(&conn).unwrap(), None);
+    
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, title) VALUES ('2024-05-06', '2024-05-06T09:00:00+00:00', 60, 'active', 'Release notes')",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    assert_eq!(query_current_activity(&conn).unwrap().as_deref(), Some("Release notes"));
+    
+    // The latest answer wins over the title; prompts not yet answered are skipped
+    for (number, words, status) in [(1, Some("drafting"), "recorded"), (2, Some("editing"), "recorded"), (3, None, "pending")] {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status) VALUES (?1, ?2, '2024-05-06T09:00:00+00:00', ?3, ?4)",
+            params![workblock_id, number, words, status],
+        ).unwrap();
+    }
+    assert_eq!(query_current_activity(&conn).unwrap().as_deref(), Some("editing"));
+    
+    conn.execute("UPDATE workblocks SET status = 'completed'", []).unwrap();
+    assert_eq!(query_current_activity(&conn).unwrap(), None);
+    
+    println!("✓ Test: Current activity passed");
+}
+
+#[test]
+fn test_write_status_file() {
+    let dir = std::env::temp_dir().join(format!("log15_status_file_{}", std::process::id()));
+    let path = dir.join("bar").join("status.txt");
+    
+    write_status_file(&path, "first\n").unwrap();
+    write_status_file(&path, "second\n").unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "second\n");
+    // Nothing is left next to it
+    assert_eq!(std::fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    
+    std::fs::remove_dir_all(&dir).unwrap();
+    
+    println!("✓ Test: Write status file passed");
+}
+
+#[test]
+fn test_status_file_settings_defaults() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    assert_eq!(query_status_file_settings(&conn), StatusFileSettings::default());
+    
+    conn.execute("INSERT OR REPLACE INTO settings (key, value) VALUES ('status_file_format', 'text')", []).unwrap();
+    conn.execute("INSERT OR REPLACE INTO settings (key, value) VALUES ('status_file_enabled', 'true')", []).unwrap();
+    let settings = query_status_file_settings(&conn);
+    assert!(settings.enabled);
+    assert_eq!(settings.format, StatusFileFormat::Text);
+    
+    println!("✓ Test: Status file settings defaults passed");
+}
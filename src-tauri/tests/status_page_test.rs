@@ -0,0 +1,66 @@
+// Test the read-only status page: which state wins and how it is worded
+// Run with: cargo test --test status_page_test
+
+use chrono::{Duration, Local, TimeZone};
+use log15_lib::dnd::DndStatus;
+use log15_lib::format::Formatter;
+use log15_lib::interval_schedule::interval_seconds;
+use log15_lib::settings::{Language, LocaleSettings};
+use log15_lib::status_page::*;
+use log15_lib::timer::{BreakInfo, TimerState};
+
+fn no_dnd() -> DndStatus {
+    DndStatus { active: false, until: None, remaining_seconds: 0 }
+}
+
+#[test]
+fn test_status_page_states() {
+    let formatter = Formatter::new(LocaleSettings::default());
+    let now = Local.with_ymd_and_hms(2024, 5, 6, 15, 0, 0).unwrap();
+    
+    let idle = build_status_page(&TimerState::default(), None, &no_dnd(), now, Language::En, &formatter);
+    assert_eq!(idle, StatusPage { state: StatusState::Available, headline: "Available".to_string(), until: None });
+    
+    // A pause outside a workblock
+    let paused_until = now + Duration::minutes(30);
+    let dnd = DndStatus { active: true, until: Some(paused_until.to_rfc3339()), remaining_seconds: 1800 };
+    let paused = build_status_page(&TimerState::default(), None, &dnd, now, Language::En, &formatter);
+    assert_eq!(paused.state, StatusState::Paused);
+    assert_eq!(paused.headline, "Not to be disturbed until 15:30");
+    
+    // A break wins over the pause
+    let break_info = BreakInfo {
+        workblock_id: 1,
+        break_seconds: 300,
+        started_at: now.to_rfc3339(),
+        ends_at: (now + Duration::minutes(5)).to_rfc3339(),
+        next_duration_minutes: 25,
+    };
+    let on_break = build_status_page(&TimerState::default(), Some(&break_info), &dnd, now, Language::En, &formatter);
+    assert_eq!(on_break.state, StatusState::Break);
+    assert_eq!(on_break.headline, "On a break until 15:05");
+    
+    // A running workblock wins over everything; its end doesn't move from tick to tick
+    let started = now - Duration::seconds(interval_seconds() / 2);
+    let running = TimerState {
+        workblock_id: Some(7),
+        current_interval_number: 1,
+        total_intervals: 3,
+        interval_start_time: Some(started),
+        is_running: true,
+        ..Default::default()
+    };
+    let focus = build_status_page(&running, Some(&break_info), &dnd, now, Language::En, &formatter);
+    let end = started + Duration::seconds(interval_seconds() * 3);
+    assert_eq!(focus.state, StatusState::Focus);
+    assert_eq!(focus.until, Some(end.to_rfc3339()));
+    assert_eq!(focus.headline, format!("In a focus block until {}", end.format("%H:%M")));
+    let later = build_status_page(&running, None, &no_dnd(), now + Duration::seconds(1), Language::En, &formatter);
+    assert_eq!(later, focus);
+    
+    // Headlines follow the configured language
+    let german = build_status_page(&TimerState::default(), None, &no_dnd(), now, Language::De, &formatter);
+    assert_eq!(german.headline, "Verfügbar");
+    
+    println!("✓ Test: Status page states passed");
+}
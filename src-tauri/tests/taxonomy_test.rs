@@ -0,0 +1,90 @@
+// Test exporting and importing taxonomy packs (rules, aliases, colors, pinned activities)
+// Run with: cargo test --test taxonomy_test
+
+use log15_lib::db::{create_schema, query_activity_colors, query_alias_map, query_pinned_activities, store_activity_color, ActivityColor};
+use log15_lib::rules::{insert_rule, query_rules, RuleMatchType};
+use log15_lib::taxonomy::*;
+use rusqlite::Connection;
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn
+}
+
+fn sample_pack() -> TaxonomyPack {
+    TaxonomyPack {
+        version: TAXONOMY_PACK_VERSION,
+        exported_at: None,
+        rules: vec![PackRule {
+            pattern: "standup|retro".to_string(),
+            match_type: RuleMatchType::Regex,
+            category: "Meetings".to_string(),
+            priority: 1,
+            enabled: true,
+        }],
+        aliases: vec![PackAlias { alias: "Dev".to_string(), canonical: "Coding".to_string() }],
+        colors: vec![ActivityColor { activity: "coding".to_string(), color: "#3366FF".to_string() }],
+        pinned: vec!["Coding".to_string(), "email".to_string()],
+    }
+}
+
+#[test]
+fn test_taxonomy_pack_round_trip() {
+    let source = setup();
+    insert_rule(&source, "review", RuleMatchType::Contains, "Code Review", 0).unwrap();
+    apply_taxonomy_pack(&source, &sample_pack(), ImportMode::Merge).unwrap();
+
+    // Export to JSON and import on another machine
+    let json = serde_json::to_string(&query_taxonomy_pack(&source).unwrap()).unwrap();
+    let pack: TaxonomyPack = serde_json::from_str(&json).unwrap();
+    assert_eq!(pack.version, TAXONOMY_PACK_VERSION);
+    assert_eq!(pack.rules.len(), 2);
+
+    let target = setup();
+    let summary = apply_taxonomy_pack(&target, &pack, ImportMode::Merge).unwrap();
+    assert_eq!(summary, ImportSummary { rules_added: 2, aliases_set: 1, colors_set: 1, pinned_added: 2 });
+    assert_eq!(query_rules(&target).unwrap().len(), 2);
+    assert_eq!(query_alias_map(&target).unwrap().get("dev").map(String::as_str), Some("coding"));
+    assert_eq!(query_activity_colors(&target).unwrap()[0].color, "#3366ff");
+    assert_eq!(query_pinned_activities(&target).unwrap(), vec!["Coding", "email"]);
+
+    // Importing the same pack again doesn't duplicate rules or pins
+    let summary = apply_taxonomy_pack(&target, &pack, ImportMode::Merge).unwrap();
+    assert_eq!(summary.rules_added, 0);
+    assert_eq!(summary.pinned_added, 0);
+    assert_eq!(query_rules(&target).unwrap().len(), 2);
+
+    println!("✓ Test: Taxonomy pack round trip passed");
+}
+
+#[test]
+fn test_taxonomy_pack_replace_and_validation() {
+    let conn = setup();
+    insert_rule(&conn, "lunch", RuleMatchType::Contains, "Break", 0).unwrap();
+    store_activity_color(&conn, "lunch", "#00ff00").unwrap();
+
+    // Replace drops the existing scheme
+    apply_taxonomy_pack(&conn, &sample_pack(), ImportMode::Replace).unwrap();
+    let rules = query_rules(&conn).unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].category, "Meetings");
+    assert_eq!(query_activity_colors(&conn).unwrap().len(), 1);
+
+    // An invalid entry rejects the whole pack
+    let mut bad = sample_pack();
+    bad.rules[0].category = "Other".to_string();
+    bad.colors.push(ActivityColor { activity: "email".to_string(), color: "blue".to_string() });
+    assert!(apply_taxonomy_pack(&conn, &bad, ImportMode::Replace).is_err());
+    assert_eq!(query_rules(&conn).unwrap()[0].category, "Meetings");
+
+    let mut bad = sample_pack();
+    bad.rules[0].pattern = "(unclosed".to_string();
+    assert!(apply_taxonomy_pack(&conn, &bad, ImportMode::Merge).is_err());
+
+    let mut newer = sample_pack();
+    newer.version = TAXONOMY_PACK_VERSION + 1;
+    assert!(apply_taxonomy_pack(&conn, &newer, ImportMode::Merge).is_err());
+
+    println!("✓ Test: Taxonomy pack replace and validation passed");
+}
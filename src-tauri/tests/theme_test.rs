@@ -0,0 +1,46 @@
+// Test theme preference storage, resolution and OS theme parsing
+// Run with: cargo test --test theme_test
+
+use log15_lib::db::{create_schema, store_setting};
+use log15_lib::settings::{query_theme_preference, ThemePreference, THEME_KEY};
+use log15_lib::theme::*;
+use rusqlite::Connection;
+
+#[test]
+fn test_theme_preference() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    assert_eq!(query_theme_preference(&conn), ThemePreference::System);
+    
+    store_setting(&conn, THEME_KEY, "dark").unwrap();
+    assert_eq!(query_theme_preference(&conn), ThemePreference::Dark);
+    store_setting(&conn, THEME_KEY, "sepia").unwrap();
+    assert_eq!(query_theme_preference(&conn), ThemePreference::System);
+    
+    // Only System follows the OS
+    assert_eq!(resolve_theme(ThemePreference::System, ResolvedTheme::Dark), ResolvedTheme::Dark);
+    assert_eq!(resolve_theme(ThemePreference::Light, ResolvedTheme::Dark), ResolvedTheme::Light);
+    assert_eq!(resolve_theme(ThemePreference::Dark, ResolvedTheme::Light), ResolvedTheme::Dark);
+    
+    let json = serde_json::to_value(ThemeInfo { preference: ThemePreference::System, theme: ResolvedTheme::Dark }).unwrap();
+    assert_eq!(json, serde_json::json!({"preference": "system", "theme": "dark"}));
+    
+    println!("✓ Test: Theme preference passed");
+}
+
+#[test]
+fn test_parse_os_theme() {
+    assert_eq!(parse_apple_interface_style("Dark\n"), ResolvedTheme::Dark);
+    assert_eq!(parse_apple_interface_style(""), ResolvedTheme::Light);
+    
+    let reg = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize\r\n    AppsUseLightTheme    REG_DWORD    0x0\r\n\r\n";
+    assert_eq!(parse_apps_use_light_theme(reg), Some(ResolvedTheme::Dark));
+    assert_eq!(parse_apps_use_light_theme(&reg.replace("0x0", "0x1")), Some(ResolvedTheme::Light));
+    assert_eq!(parse_apps_use_light_theme("ERROR: The system was unable to find the specified registry key"), None);
+    
+    assert_eq!(parse_gnome_color_scheme("'prefer-dark'\n"), Some(ResolvedTheme::Dark));
+    assert_eq!(parse_gnome_color_scheme("'prefer-light'\n"), Some(ResolvedTheme::Light));
+    assert_eq!(parse_gnome_color_scheme("'default'\n"), None);
+    
+    println!("✓ Test: OS theme parsing passed");
+}
@@ -0,0 +1,243 @@
+// Test wall-clock interval scheduling used to recover from system sleep
+// Run with: cargo test --test timer_test
+
+use chrono::{Duration, Local, Timelike};
+use log15_lib::interval_schedule::interval_seconds;
+use log15_lib::timer::{
+    auto_away_wait, clock_aligned_start, compute_tick, continuation_minutes, interval_boundary, intervals_elapsed, plan_workblock, schedule_anchor,
+    store_auto_away, validate_duration_minutes, DurationError, TimerState, MAX_WORKBLOCK_MINUTES,
+};
+use log15_lib::timing::timing;
+
+#[test]
+fn test_wall_clock_reconciliation() {
+    let start = Local::now();
+    
+    // Nothing has ended before the first boundary
+    assert_eq!(intervals_elapsed(start, start), 0);
+    assert_eq!(intervals_elapsed(start, start - Duration::seconds(5)), 0);
+    assert_eq!(intervals_elapsed(start, interval_boundary(start, 1) - Duration::milliseconds(1)), 0);
+    assert_eq!(intervals_elapsed(start, interval_boundary(start, 1)), 1);
+    
+    // Waking up after 4.5 intervals of sleep: four intervals have ended, the fifth is in progress
+    let woke_at = start + Duration::seconds(interval_seconds() * 9 / 2);
+    assert_eq!(intervals_elapsed(start, woke_at), 4);
+    assert!(interval_boundary(start, 4) <= woke_at && woke_at < interval_boundary(start, 5));
+    
+    println!("✓ Test: Wall-clock reconciliation passed");
+}
+
+#[test]
+fn test_clock_aligned_schedule() {
+    let started_at = Local::now().with_nanosecond(0).unwrap();
+    let anchor = schedule_anchor(started_at, true);
+    
+    // The anchor is the clock boundary at or before the start, so the first interval is shortened
+    assert!(anchor <= started_at);
+    assert!(started_at - anchor < Duration::seconds(interval_seconds()));
+    assert_eq!(anchor.num_seconds_from_midnight() as i64 % interval_seconds(), 0);
+    assert!(interval_boundary(anchor, 1) > started_at);
+    assert_eq!(clock_aligned_start(anchor), anchor);
+    
+    // Later intervals tick on clock boundaries
+    assert_eq!(interval_boundary(anchor, 3).num_seconds_from_midnight() as i64 % interval_seconds(), 0);
+    
+    // Without alignment the schedule starts with the workblock
+    assert_eq!(schedule_anchor(started_at, false), started_at);
+    
+    println!("✓ Test: Clock-aligned schedule passed");
+}
+
+#[test]
+fn test_duration_validation() {
+    assert_eq!(
+        validate_duration_minutes(0),
+        Err(DurationError::TooShort { minimum: timing().min_workblock_minutes })
+    );
+    assert!(validate_duration_minutes(-30).is_err());
+    assert_eq!(
+        validate_duration_minutes(MAX_WORKBLOCK_MINUTES + 1),
+        Err(DurationError::TooLong { maximum: MAX_WORKBLOCK_MINUTES })
+    );
+    
+    // Valid durations always cover a whole number of intervals
+    for minutes in [timing().min_workblock_minutes, 20, 60, MAX_WORKBLOCK_MINUTES] {
+        let validated = validate_duration_minutes(minutes).unwrap();
+        assert!(validated >= minutes);
+        assert_eq!(validated as i64 * 60 % interval_seconds(), 0);
+    }
+    
+    println!("✓ Test: Duration validation passed");
+}
+
+#[test]
+fn test_continuation_after_day_start() {
+    let now = Local::now();
+    let interval = Duration::seconds(interval_seconds());
+    let shortest = (timing().min_workblock_minutes as i64 * 60 / interval_seconds().max(1)) as i32;
+    
+    // Only whole intervals carry over, so the continuation never runs past the planned end
+    let planned_end = now + interval * (shortest + 2) + Duration::seconds(59);
+    let minutes = continuation_minutes(planned_end, now).unwrap();
+    assert!(now + Duration::minutes(minutes as i64) <= planned_end);
+    assert_eq!(minutes, validate_duration_minutes(minutes).unwrap());
+    
+    // Less than the shortest workblock left (or already over): the workblock just ends
+    assert_eq!(continuation_minutes(now + interval / 2, now), None);
+    assert_eq!(continuation_minutes(now - interval, now), None);
+    
+    println!("✓ Test: Continuation after day start passed");
+}
+
+#[test]
+fn test_split_workblock_cut_interval_is_not_last() {
+    use log15_lib::db::{create_schema, query_interval_by_id, query_is_last_interval, store_workblock_split};
+    use log15_lib::interval_schedule::total_intervals;
+    
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES ('2024-05-06', '2024-05-06T23:40:00+00:00', 20, 'completed', 0)",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    // The cut interval's number matches the last of the shortened workblock
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, status) VALUES (?1, ?2, '2024-05-06T23:55:00+00:00', 'pending')",
+        rusqlite::params![workblock_id, total_intervals(20, interval_seconds())],
+    ).unwrap();
+    let interval = query_interval_by_id(&conn, conn.last_insert_rowid()).unwrap();
+    assert!(query_is_last_interval(&conn, &interval).unwrap());
+    
+    // Marked as split, answering it doesn't finish the workblock a second time
+    store_workblock_split(&conn, workblock_id).unwrap();
+    assert!(!query_is_last_interval(&conn, &interval).unwrap());
+    
+    println!("✓ Test: Split workblock cut interval is not last passed");
+}
+
+#[test]
+fn test_workblock_plan_preview() {
+    let started_at = Local::now().with_hour(10).unwrap().with_minute(7).unwrap().with_second(30).unwrap()
+        .with_nanosecond(0).unwrap();
+    
+    // An hour of 15-minute intervals, starting with the workblock
+    let plan = plan_workblock(started_at, 60, 15 * 60, false);
+    assert_eq!(plan.intervals.len(), 4);
+    assert_eq!(plan.intervals[0].start_time, started_at.to_rfc3339());
+    assert_eq!(plan.intervals[0].end_time, (started_at + Duration::minutes(15)).to_rfc3339());
+    assert_eq!(plan.intervals[3].end_time, (started_at + Duration::minutes(60)).to_rfc3339());
+    for pair in plan.intervals.windows(2) {
+        assert_eq!(pair[0].end_time, pair[1].start_time);
+    }
+    
+    // Aligned: prompts at 10:15, 10:30, ... with the first interval shortened
+    let plan = plan_workblock(started_at, 60, 15 * 60, true);
+    assert!(plan.aligned_to_clock);
+    assert_eq!(plan.intervals.len(), 4);
+    assert_eq!(plan.intervals[0].start_time, started_at.to_rfc3339());
+    assert_eq!(plan.intervals[0].end_time, started_at.with_minute(15).unwrap().with_second(0).unwrap().to_rfc3339());
+    assert_eq!(plan.intervals[1].end_time, started_at.with_minute(30).unwrap().with_second(0).unwrap().to_rfc3339());
+    
+    // A partial final interval still counts as an interval
+    assert_eq!(plan_workblock(started_at, 50, 15 * 60, false).intervals.len(), 4);
+    
+    println!("✓ Test: Workblock plan preview passed");
+}
+
+#[test]
+fn test_timer_tick_countdown() {
+    let now = Local::now();
+    
+    // Nothing to count down without a running workblock
+    assert_eq!(compute_tick(&TimerState::default(), now), None);
+    
+    // Interval 2 of 4 started 3 seconds ago
+    let mut state = TimerState {
+        workblock_id: Some(7),
+        current_interval_id: Some(12),
+        current_interval_number: 2,
+        interval_start_time: Some(now - Duration::seconds(3)),
+        prompt_shown_time: None,
+        is_running: true,
+        total_intervals: 4,
+        prompted_interval_id: None,
+        snoozed_until: None,
+        auto_away_pending: None,
+    };
+    let tick = compute_tick(&state, now).unwrap();
+    assert_eq!(tick.workblock_id, 7);
+    assert_eq!(tick.interval_number, 2);
+    assert_eq!(tick.interval_remaining_seconds, interval_seconds() - 3);
+    assert_eq!(tick.workblock_remaining_seconds, interval_seconds() * 3 - 3);
+    
+    // Countdowns never go negative while the final prompt is waiting
+    state.current_interval_number = 4;
+    state.interval_start_time = Some(now - Duration::seconds(interval_seconds() + 5));
+    let tick = compute_tick(&state, now).unwrap();
+    assert_eq!(tick.interval_remaining_seconds, 0);
+    assert_eq!(tick.workblock_remaining_seconds, 0);
+    
+    state.is_running = false;
+    assert_eq!(compute_tick(&state, now), None);
+    
+    println!("✓ Test: Timer tick countdown passed");
+}
+
+#[test]
+fn test_timer_selftest_simulation() {
+    use log15_lib::settings::AutoAwaySettings;
+    use log15_lib::timer_selftest::{run_simulated_checks, simulate_workblock, FakeClock};
+    
+    let start = Local::now();
+    let mut clock = FakeClock::new(start);
+    clock.advance(90);
+    assert_eq!(clock.now() - start, Duration::seconds(90));
+    
+    // Every simulated check passes with the default auto-away settings
+    let auto_away = AutoAwaySettings::default();
+    let checks = run_simulated_checks(start, &auto_away);
+    let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["tick_scheduling", "missed_intervals", "auto_away", "completion"]);
+    assert!(checks.iter().all(|c| c.passed), "{:?}", checks);
+    
+    // The synthetic workblock only lives in the connection it was given
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    log15_lib::db::create_schema(&conn).unwrap();
+    let (away, completion) = simulate_workblock(&conn, start, &auto_away).unwrap();
+    assert!(away.is_ok() && completion.is_ok());
+    let (status, away_intervals): (String, i32) = conn.query_row(
+        "SELECT w.status, (SELECT COUNT(*) FROM intervals WHERE workblock_id = w.id AND status = 'auto_away')
+         FROM workblocks w",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).unwrap();
+    assert_eq!(status, "completed");
+    assert_eq!(away_intervals, 1);
+    
+    // The timeout step marks the cause, and leaves an answered interval alone
+    let (answered_id, away_cause): (i64, Option<String>) = conn.query_row(
+        "SELECT (SELECT id FROM intervals WHERE status = 'recorded' LIMIT 1),
+                (SELECT away_cause FROM intervals WHERE status = 'auto_away')",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).unwrap();
+    assert_eq!(away_cause.as_deref(), Some("prompt_timeout"));
+    let at = chrono::Utc::now();
+    assert!(store_auto_away(&conn, answered_id, &auto_away.message, at).unwrap().is_none());
+    
+    println!("✓ Test: Timer self-test simulation passed");
+}
+
+#[test]
+fn test_auto_away_rearm_wait() {
+    let since = Local::now();
+    
+    // A longer timeout keeps the time already waited; a shorter one that already ran out fires at once
+    assert_eq!(auto_away_wait(60, since, since + Duration::seconds(20)), std::time::Duration::from_secs(40));
+    assert_eq!(auto_away_wait(10, since, since + Duration::seconds(20)), std::time::Duration::ZERO);
+    assert_eq!(auto_away_wait(10, since, since - Duration::seconds(5)), std::time::Duration::from_secs(10));
+    
+    println!("✓ Test: Auto-away rearm wait passed");
+}
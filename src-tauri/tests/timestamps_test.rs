@@ -0,0 +1,121 @@
+// Test UTC timestamp storage, normalization of old rows and durations across DST changes
+// Run with: cargo test --test timestamps_test
+
+use chrono::{TimeZone, Utc};
+use log15_lib::db::*;
+use rusqlite::Connection;
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn
+}
+
+fn insert_active_workblock(conn: &Connection, start_time: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status, is_archived)
+         VALUES (substr(?1, 1, 10), ?1, 120, 'active', 0)",
+        [start_time],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+#[test]
+fn test_parse_timestamp() {
+    let expected = Utc.with_ymd_and_hms(2024, 5, 1, 7, 0, 0).unwrap();
+    assert_eq!(parse_timestamp("2024-05-01T09:00:00+02:00"), Some(expected));
+    assert_eq!(parse_timestamp("2024-05-01T07:00:00Z"), Some(expected));
+    assert_eq!(parse_timestamp("2024-05-01 07:00:00"), Some(expected));  // SQLite datetime('now')
+    assert_eq!(parse_timestamp("yesterday"), None);
+    
+    assert_eq!(to_timestamp(expected), "2024-05-01T07:00:00+00:00");
+    assert_eq!(to_timestamp(parse_timestamp("2024-05-01T09:00:00+02:00").unwrap()), "2024-05-01T07:00:00+00:00");
+    
+    println!("✓ Test: Parse timestamp passed");
+}
+
+#[test]
+fn test_workblock_duration_across_dst() {
+    let conn = setup();
+    
+    // Europe spring forward (2024-03-31 02:00 CET -> 03:00 CEST): 01:30 CET to 04:30 CEST is 2 hours
+    let spring = insert_active_workblock(&conn, "2024-03-31T01:30:00+01:00");
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 2, 30, 0).unwrap();
+    let workblock = store_workblock_end(&conn, spring, WorkblockStatus::Completed, end).unwrap();
+    assert_eq!(workblock.duration_minutes, Some(120));
+    assert_eq!(workblock.status, WorkblockStatus::Completed);
+    assert_eq!(workblock.end_time.as_deref(), Some("2024-03-31T02:30:00+00:00"));
+    
+    // Fall back (2024-10-27 03:00 CEST -> 02:00 CET): 01:30 CEST to 02:30 CET is also 2 hours
+    let fall = insert_active_workblock(&conn, "2024-10-27T01:30:00+02:00");
+    let end = Utc.with_ymd_and_hms(2024, 10, 27, 1, 30, 0).unwrap();
+    let workblock = store_workblock_end(&conn, fall, WorkblockStatus::Cancelled, end).unwrap();
+    assert_eq!(workblock.duration_minutes, Some(120));
+    assert_eq!(workblock.status, WorkblockStatus::Cancelled);
+    
+    // A start written by SQLite's datetime('now') is UTC
+    let legacy = insert_active_workblock(&conn, "2024-05-01 07:00:00");
+    let end = Utc.with_ymd_and_hms(2024, 5, 1, 7, 45, 0).unwrap();
+    let workblock = store_workblock_end(&conn, legacy, WorkblockStatus::Completed, end).unwrap();
+    assert_eq!(workblock.duration_minutes, Some(45));
+    
+    println!("✓ Test: Workblock duration across DST passed");
+}
+
+#[test]
+fn test_normalize_timestamps() {
+    let conn = setup();
+    let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+    assert_eq!(version, 1);
+    
+    let workblock_id = insert_active_workblock(&conn, "2024-05-01T09:00:00+02:00");
+    conn.execute(
+        "UPDATE workblocks SET end_time = '2024-05-01 08:00:00', created_at = 'not a time' WHERE id = ?1",
+        [workblock_id],
+    ).unwrap();
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, status, recorded_at)
+         VALUES (?1, 1, '2024-05-01T09:00:00+02:00', 'recorded', '2024-05-01T07:15:00+00:00')",
+        [workblock_id],
+    ).unwrap();
+    
+    assert_eq!(normalize_timestamps(&conn).unwrap(), 3);
+    let (start, end, created): (String, String, String) = conn.query_row(
+        "SELECT start_time, end_time, created_at FROM workblocks WHERE id = ?1",
+        [workblock_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    ).unwrap();
+    assert_eq!(start, "2024-05-01T07:00:00+00:00");
+    assert_eq!(end, "2024-05-01T08:00:00+00:00");
+    assert_eq!(created, "not a time");
+    let interval_start: String = conn.query_row("SELECT start_time FROM intervals", [], |row| row.get(0)).unwrap();
+    assert_eq!(interval_start, "2024-05-01T07:00:00+00:00");
+    
+    // Already normalized values are left alone
+    assert_eq!(normalize_timestamps(&conn).unwrap(), 0);
+    
+    // New rows get UTC RFC 3339 defaults
+    conn.execute("INSERT INTO goals (metric, target) VALUES ('tracked_minutes', 60)", []).unwrap();
+    let created_at: String = conn.query_row("SELECT created_at FROM goals", [], |row| row.get(0)).unwrap();
+    assert!(parse_timestamp(&created_at).is_some());
+    assert!(created_at.ends_with("+00:00"), "{}", created_at);
+    
+    println!("✓ Test: Normalize timestamps passed");
+}
+
+#[test]
+fn test_claim_on_older_schema() {
+    // Databases created by older versions keep SQLite's CURRENT_TIMESTAMP default
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute(
+        "CREATE TABLE daily_rollovers (date TEXT PRIMARY KEY, claimed_at DATETIME DEFAULT CURRENT_TIMESTAMP)",
+        [],
+    ).unwrap();
+    create_schema(&conn).unwrap();
+    
+    assert!(claim_rollover(&conn, "2024-05-01").unwrap());
+    let claimed_at: String = conn.query_row("SELECT claimed_at FROM daily_rollovers", [], |row| row.get(0)).unwrap();
+    assert!(claimed_at.contains('T') && claimed_at.ends_with("+00:00"), "{}", claimed_at);
+    
+    println!("✓ Test: Claim on older schema passed");
+}
@@ -0,0 +1,74 @@
+// Test timing profile selection from the environment and custom overrides
+// Run with: cargo test --test timing_test
+
+use log15_lib::timing::*;
+use std::collections::HashMap;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    move |name| vars.get(name).cloned()
+}
+
+#[test]
+fn test_profile_selection() {
+    assert_eq!(Timing::from_env(env(&[("LOG15_TIMING_PROFILE", "production")])), Timing::PRODUCTION);
+    assert_eq!(Timing::from_env(env(&[("LOG15_TIMING_PROFILE", "fast-test")])), Timing::FAST_TEST);
+    assert_eq!(Timing::PRODUCTION.interval_seconds, 15 * 60);
+    assert_eq!(Timing::FAST_TEST.interval_seconds, 10);
+    
+    // Unset or unknown profiles fall back to what the build was compiled with
+    let default = match TimingProfile::compiled_default() {
+        TimingProfile::FastTest => Timing::FAST_TEST,
+        _ => Timing::PRODUCTION,
+    };
+    assert_eq!(Timing::from_env(env(&[])), default);
+    assert_eq!(Timing::from_env(env(&[("LOG15_TIMING_PROFILE", "turbo")])), default);
+    
+    for profile in [TimingProfile::Production, TimingProfile::FastTest, TimingProfile::Custom] {
+        assert_eq!(TimingProfile::from_str(profile.as_str()), Some(profile));
+    }
+    
+    println!("✓ Test: Profile selection passed");
+}
+
+#[test]
+fn test_custom_profile() {
+    let timing = Timing::from_env(env(&[
+        ("LOG15_TIMING_PROFILE", "custom"),
+        ("LOG15_INTERVAL_SECONDS", "30"),
+        ("LOG15_BREAK_SECONDS", "0"),
+        ("LOG15_SNOOZE_SECONDS", "soon"),
+    ]));
+    assert_eq!(timing.profile, TimingProfile::Custom);
+    assert_eq!(timing.interval_seconds, 30);
+    
+    // Zero or malformed values keep the production default
+    assert_eq!(timing.break_seconds, Timing::PRODUCTION.break_seconds);
+    assert_eq!(timing.snooze_seconds, Timing::PRODUCTION.snooze_seconds);
+    assert_eq!(timing.min_workblock_minutes, Timing::PRODUCTION.min_workblock_minutes);
+    
+    println!("✓ Test: Custom profile passed");
+}
+
+#[test]
+fn test_seeded_timeouts_come_from_production() {
+    use log15_lib::db::{create_schema, query_setting};
+    use log15_lib::settings::{seed_default_settings, AUTO_AWAY_TIMEOUT_KEY, BREAK_DURATION_KEY};
+    
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    seed_default_settings(&conn).unwrap();
+    
+    // A test profile's short timeouts never end up stored; they apply through the fallbacks instead
+    let auto_away = query_setting(&conn, AUTO_AWAY_TIMEOUT_KEY).unwrap();
+    let break_seconds = query_setting(&conn, BREAK_DURATION_KEY).unwrap();
+    if timing().profile == TimingProfile::Production {
+        assert_eq!(auto_away, Some(Timing::PRODUCTION.auto_away_timeout_seconds.to_string()));
+        assert_eq!(break_seconds, Some(Timing::PRODUCTION.break_seconds.to_string()));
+    } else {
+        assert_eq!(auto_away, None);
+        assert_eq!(break_seconds, None);
+    }
+    
+    println!("✓ Test: Seeded timeouts come from production passed");
+}
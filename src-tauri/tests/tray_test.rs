@@ -359,3 +359,199 @@ fn test_tray_state_cancelled_workblocks_included() {
     mock_app.cleanup();
 }
 
+
+#[test]
+fn test_tray_icons_per_state_and_theme() {
+    use log15_lib::tray::TrayIconState;
+    use tauri::{image::Image, Theme};
+    
+    let states = [TrayIconState::Idle, TrayIconState::Active, TrayIconState::SummaryReady];
+    let mut seen: Vec<&[u8]> = Vec::new();
+    for state in states {
+        for theme in [Theme::Light, Theme::Dark] {
+            let png = state.icon_png(theme);
+            let image = Image::from_bytes(png).expect("tray icon should decode");
+            assert_eq!((image.width(), image.height()), (32, 32));
+            assert!(!seen.contains(&png), "{:?}/{:?} reuses another icon", state, theme);
+            seen.push(png);
+        }
+    }
+    
+    println!("✓ Test: Tray icons differ per state and theme");
+}
+
+#[test]
+fn test_tray_countdown_format() {
+    use log15_lib::tray::format_countdown;
+    
+    assert_eq!(format_countdown(750), "12:30");
+    assert_eq!(format_countdown(59), "0:59");
+    assert_eq!(format_countdown(3900), "1:05:00");
+    assert_eq!(format_countdown(-4), "0:00");
+    
+    println!("✓ Test: Tray countdown format passed");
+}
+
+#[test]
+fn test_tray_quick_log_activities() {
+    use log15_lib::db::{create_schema, query_recent_activities};
+    use log15_lib::tray::{parse_quick_log_menu_id, quick_log_menu_id};
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES ('2024-06-03', '2024-06-03T09:00:00+00:00', 60, 'completed')",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    // Oldest first; auto-away and blank entries never show up
+    let entries = [
+        ("coding", "recorded"), ("email", "recorded"), ("Away", "auto_away"), ("Coding ", "recorded"),
+        ("  ", "recorded"), ("review", "recorded"), ("standup", "recorded"), ("lunch", "recorded"), ("docs", "recorded"),
+    ];
+    for (i, (words, status)) in entries.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status) VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![workblock_id, i as i32 + 1, format!("2024-06-03T09:{:02}:00+00:00", i * 5), words, status],
+        ).unwrap();
+    }
+    
+    let recent = query_recent_activities(&conn, 5).unwrap();
+    assert_eq!(recent, vec!["docs", "lunch", "standup", "review", "Coding"]);
+    
+    let id = quick_log_menu_id("deep work");
+    assert_eq!(parse_quick_log_menu_id(&id), Some("deep work"));
+    assert_eq!(parse_quick_log_menu_id("show_window"), None);
+    
+    println!("✓ Test: Tray quick-log activities passed");
+}
+
+#[test]
+fn test_tray_start_durations() {
+    use log15_lib::tray::{format_duration_label, parse_start_menu_id, start_menu_id, tray_start_durations};
+    
+    assert_eq!(tray_start_durations(&[15, 30, 45]), vec![15, 30, 45, 60, 90, 120]);
+    assert_eq!(tray_start_durations(&[]), vec![30, 60, 90, 120]);
+    
+    assert_eq!(parse_start_menu_id(&start_menu_id(45)), Some(45));
+    assert_eq!(parse_start_menu_id("start_workblock"), None);
+    assert_eq!(parse_start_menu_id("start_workblock:abc"), None);
+    
+    assert_eq!(format_duration_label(45), "45 minutes");
+    assert_eq!(format_duration_label(120), "2 h");
+    assert_eq!(format_duration_label(90), "1 h 30 min");
+    
+    println!("✓ Test: Tray start durations passed");
+}
+
+#[test]
+fn test_tray_tooltip_today_totals() {
+    use log15_lib::db::create_schema;
+    use log15_lib::tray::{format_tracked_minutes, query_today_totals, tooltip_text, TodayTotals, TrayIconState};
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    for (start_time, duration, status) in [
+        ("2024-06-03T09:00:00+00:00", 60, "completed"),
+        ("2024-06-03T11:00:00+00:00", 45, "completed"),
+        ("2024-06-03T13:00:00+00:00", 30, "cancelled"),
+    ] {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES ('2024-06-03', ?1, ?2, ?3)",
+            rusqlite::params![start_time, duration, status],
+        ).unwrap();
+    }
+    
+    let totals = query_today_totals(&conn, "2024-06-03", Local::now()).unwrap();
+    assert_eq!(totals, TodayTotals { tracked_minutes: 135, completed_workblocks: 2 });
+    
+    assert_eq!(format_tracked_minutes(225), "3h 45m");
+    assert_eq!(format_tracked_minutes(20), "20m");
+    assert_eq!(
+        tooltip_text(TrayIconState::Idle, totals),
+        "Log15 - No active workblock\n2h 15m tracked today, 2 workblocks completed"
+    );
+    assert_eq!(tooltip_text(TrayIconState::Active, TodayTotals::default()), "Log15 - Workblock in progress");
+    
+    println!("✓ Test: Tray tooltip today totals passed");
+}
+
+#[test]
+fn test_tray_pause_prompts() {
+    use log15_lib::db::{create_schema, store_setting};
+    use log15_lib::dnd::{dnd_status, query_dnd_until, DND_UNTIL_KEY};
+    use log15_lib::settings::Language;
+    use log15_lib::tray::dnd_menu_label;
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    let now = Local::now();
+    
+    // Not paused: nothing stored, or a cleared pause
+    assert_eq!(query_dnd_until(&conn, now), None);
+    store_setting(&conn, DND_UNTIL_KEY, "").unwrap();
+    assert_eq!(query_dnd_until(&conn, now), None);
+    assert_eq!(dnd_menu_label(Language::En, &dnd_status(None, now), 60), "Pause Prompts for 1 h");
+    
+    // Paused for the next 45 minutes
+    let until = now + chrono::Duration::minutes(45);
+    store_setting(&conn, DND_UNTIL_KEY, &until.to_rfc3339()).unwrap();
+    let status = dnd_status(query_dnd_until(&conn, now), now);
+    assert!(status.active);
+    assert_eq!(status.remaining_seconds, 45 * 60);
+    assert_eq!(dnd_menu_label(Language::En, &status, 60), "Resume Prompts (45m left)");
+    
+    // Partial minutes round up so the label never shows 0 while still paused
+    let later = until - chrono::Duration::seconds(30);
+    assert_eq!(dnd_menu_label(Language::En, &dnd_status(Some(until), later), 60), "Resume Prompts (1m left)");
+    
+    // An expired pause counts as none
+    let after = until + chrono::Duration::seconds(1);
+    assert_eq!(query_dnd_until(&conn, after), None);
+    assert!(!dnd_status(Some(until), after).active);
+    
+    println!("✓ Test: Tray pause prompts passed");
+}
+
+#[test]
+fn test_tray_menu_localization() {
+    use log15_lib::db::{create_schema, store_setting};
+    use log15_lib::dnd::dnd_status;
+    use log15_lib::i18n::{duration_label, tr, Text};
+    use log15_lib::settings::{query_language, Language, LANGUAGE_KEY};
+    use log15_lib::tray::dnd_menu_label;
+    
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    assert_eq!(query_language(&conn), Language::En);
+    store_setting(&conn, LANGUAGE_KEY, "de").unwrap();
+    assert_eq!(query_language(&conn), Language::De);
+    // Unknown languages fall back to English
+    store_setting(&conn, LANGUAGE_KEY, "xx").unwrap();
+    assert_eq!(query_language(&conn), Language::En);
+    
+    assert_eq!(tr(Language::En, Text::Quit), "Quit");
+    assert_eq!(tr(Language::De, Text::Quit), "Beenden");
+    assert_eq!(duration_label(Language::Es, 45), "45 minutos");
+    assert_eq!(duration_label(Language::De, 90), "1 h 30 min");
+    
+    let now = Local::now();
+    assert_eq!(dnd_menu_label(Language::Fr, &dnd_status(None, now), 30), "Suspendre les invites pendant 30 minutes");
+    let paused = dnd_status(Some(now + chrono::Duration::minutes(20)), now);
+    assert_eq!(dnd_menu_label(Language::De, &paused, 60), "Abfragen fortsetzen (noch 20m)");
+    
+    // Every text has a translation with the same placeholders as English
+    let texts = [
+        Text::StartWorkblock, Text::CustomDuration, Text::LogCurrentInterval, Text::RepeatLastInterval, Text::NoRecentActivities,
+        Text::PausePromptsFor, Text::ResumePromptsLeft, Text::ViewSummary, Text::ViewLastWords,
+        Text::ShowWindow, Text::HideWindow, Text::Quit, Text::Minutes,
+    ];
+    for language in [Language::De, Language::Fr, Language::Es] {
+        for text in texts {
+            assert!(!tr(language, text).is_empty());
+            assert_eq!(tr(language, text).matches("{}").count(), tr(Language::En, text).matches("{}").count());
+        }
+    }
+    
+    println!("✓ Test: Tray menu localization passed");
+}
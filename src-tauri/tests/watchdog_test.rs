@@ -0,0 +1,71 @@
+// Test the prompt watchdog and the incident log it writes to
+// Run with: cargo test --test watchdog_test
+
+use chrono::{Duration, Local};
+use log15_lib::db::create_schema;
+use log15_lib::diagnostics::{query_incidents, store_incident, MAX_INCIDENTS};
+use log15_lib::watchdog::*;
+use rusqlite::Connection;
+
+#[test]
+fn test_detect_prompt_mismatch() {
+    // Consistent states
+    assert_eq!(detect_mismatch(false, None, None), None);
+    assert_eq!(detect_mismatch(true, Some(7), Some(7)), None);
+    
+    assert_eq!(detect_mismatch(true, Some(7), None), Some(PromptMismatch::OrphanWindow { shown_interval: Some(7) }));
+    assert_eq!(detect_mismatch(false, None, Some(8)), Some(PromptMismatch::MissingWindow { interval_id: 8 }));
+    assert_eq!(
+        detect_mismatch(true, Some(7), Some(8)),
+        Some(PromptMismatch::WrongInterval { shown_interval: Some(7), interval_id: 8 })
+    );
+    
+    println!("✓ Test: Prompt mismatch detection passed");
+}
+
+#[test]
+fn test_watchdog_waits_out_grace_period() {
+    let start = Local::now();
+    let mismatch = Some(PromptMismatch::MissingWindow { interval_id: 8 });
+    let mut watchdog = PromptWatchdog::default();
+    
+    assert_eq!(watchdog.observe(mismatch, start), None);
+    assert_eq!(watchdog.observe(mismatch, start + Duration::seconds(30)), None);
+    assert_eq!(watchdog.observe(mismatch, start + Duration::seconds(WATCHDOG_GRACE_SECONDS)), mismatch);
+    
+    // A repaired mismatch starts a fresh grace period
+    assert_eq!(watchdog.observe(mismatch, start + Duration::seconds(75)), None);
+    
+    // Resolving on its own, or changing into another mismatch, resets the clock
+    let mut watchdog = PromptWatchdog::default();
+    watchdog.observe(mismatch, start);
+    watchdog.observe(None, start + Duration::seconds(30));
+    assert_eq!(watchdog.observe(mismatch, start + Duration::seconds(61)), None);
+    let other = Some(PromptMismatch::OrphanWindow { shown_interval: Some(8) });
+    assert_eq!(watchdog.observe(other, start + Duration::seconds(122)), None);
+    
+    println!("✓ Test: Watchdog waits out grace period passed");
+}
+
+#[test]
+fn test_incident_log() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    
+    store_incident(&conn, "prompt_watchdog", "first").unwrap();
+    store_incident(&conn, "prompt_watchdog", "second").unwrap();
+    let incidents = query_incidents(&conn, 10).unwrap();
+    assert_eq!(incidents.len(), 2);
+    assert_eq!(incidents[0].detail, "second");
+    assert_eq!(incidents[1].source, "prompt_watchdog");
+    
+    // Only the most recent incidents are kept
+    for i in 0..MAX_INCIDENTS {
+        store_incident(&conn, "test", &i.to_string()).unwrap();
+    }
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM diagnostic_incidents", [], |row| row.get(0)).unwrap();
+    assert_eq!(count, MAX_INCIDENTS);
+    assert_eq!(query_incidents(&conn, 1).unwrap()[0].detail, (MAX_INCIDENTS - 1).to_string());
+    
+    println!("✓ Test: Incident log passed");
+}
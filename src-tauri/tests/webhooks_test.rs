@@ -0,0 +1,131 @@
+// Test webhooks: configuration, signing, retry policy, workblock and milestone payloads
+// Run with: cargo test --test webhooks_test
+
+use log15_lib::db::create_schema;
+use log15_lib::error::Log15Error;
+use log15_lib::settings::MilestoneTemplates;
+use log15_lib::stats::{GoalMetric, Milestone};
+use log15_lib::webhooks::*;
+use rusqlite::{params, Connection};
+use std::time::Duration;
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn
+}
+
+#[test]
+fn test_webhook_configuration() {
+    let conn = setup();
+    
+    let hook = insert_webhook(&conn, " https://n8n.example.com/webhook/log15 ", &[WebhookEvent::DayArchived, WebhookEvent::WorkblockStarted], None).unwrap();
+    assert_eq!(hook.url, "https://n8n.example.com/webhook/log15");
+    assert_eq!(hook.events, vec![WebhookEvent::WorkblockStarted, WebhookEvent::DayArchived]);
+    assert_eq!(hook.secret.len(), 64);
+    assert!(hook.enabled);
+    assert_eq!(hook.last_status, None);
+    
+    let other = insert_webhook(&conn, "http://localhost:5678/hook", &[WebhookEvent::WorkblockCompleted], Some("s3cret")).unwrap();
+    assert_eq!(other.secret, "s3cret");
+    assert_ne!(generate_secret(), generate_secret());
+    
+    // Invalid URLs and empty subscriptions are rejected
+    assert!(matches!(insert_webhook(&conn, "ftp://example.com", &[WebhookEvent::DayArchived], None), Err(Log15Error::InvalidInput(_))));
+    assert!(matches!(insert_webhook(&conn, "https://", &[WebhookEvent::DayArchived], None), Err(Log15Error::InvalidInput(_))));
+    assert!(matches!(insert_webhook(&conn, "https://example.com", &[], None), Err(Log15Error::InvalidInput(_))));
+    
+    // Only enabled webhooks subscribed to the event receive it
+    let for_started: Vec<i64> = query_webhooks_for_event(&conn, WebhookEvent::WorkblockStarted).unwrap().iter().map(|w| w.id).collect();
+    assert_eq!(for_started, vec![hook.id]);
+    update_webhook(&conn, hook.id, &hook.url, &[WebhookEvent::WorkblockStarted], false).unwrap();
+    assert!(query_webhooks_for_event(&conn, WebhookEvent::WorkblockStarted).unwrap().is_empty());
+    assert!(matches!(update_webhook(&conn, 999, &hook.url, &[WebhookEvent::DayArchived], true), Err(Log15Error::NotFound(_))));
+    
+    store_delivery_result(&conn, other.id, "503").unwrap();
+    assert_eq!(query_webhook(&conn, other.id).unwrap().last_status.as_deref(), Some("503"));
+    
+    assert!(delete_webhook(&conn, hook.id).unwrap());
+    assert!(!delete_webhook(&conn, hook.id).unwrap());
+    assert_eq!(query_webhooks(&conn).unwrap().len(), 1);
+    
+    println!("✓ Test: Webhook configuration passed");
+}
+
+#[test]
+fn test_signing_and_retries() {
+    // RFC 4231 test case 2
+    assert_eq!(
+        sign_payload("Jefe", "what do ya want for nothing?"),
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+    
+    assert_eq!(retry_delay(1), Duration::from_secs(RETRY_BASE_DELAY_SECONDS));
+    assert_eq!(retry_delay(3), Duration::from_secs(RETRY_BASE_DELAY_SECONDS * 4));
+    assert!(should_retry(500) && should_retry(503) && should_retry(429) && should_retry(408));
+    assert!(!should_retry(200) && !should_retry(204) && !should_retry(401) && !should_retry(404));
+    
+    assert_eq!(WebhookEvent::from_str("day_archived"), Some(WebhookEvent::DayArchived));
+    assert_eq!(WebhookEvent::from_str("day-archived"), None);
+    
+    println!("✓ Test: Signing and retries passed");
+}
+
+#[test]
+fn test_workblock_event_data() {
+    let conn = setup();
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES ('2024-05-01', '2024-05-01T09:00:00+00:00', 60, 'completed')",
+        [],
+    ).unwrap();
+    let workblock_id = conn.last_insert_rowid();
+    conn.execute(
+        "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+         VALUES (?1, 1, '2024-05-01T09:00:00+00:00', 'wrote tests', 'recorded')",
+        params![workblock_id],
+    ).unwrap();
+    
+    let started = workblock_event_data(&conn, WebhookEvent::WorkblockStarted, workblock_id).unwrap();
+    assert_eq!(started["workblock"]["id"], workblock_id);
+    assert!(started.get("intervals").is_none());
+    
+    let completed = workblock_event_data(&conn, WebhookEvent::WorkblockCompleted, workblock_id).unwrap();
+    assert_eq!(completed["intervals"][0]["words"], "wrote tests");
+    
+    let payload = WebhookPayload { event: WebhookEvent::WorkblockCompleted, occurred_at: "2024-05-01T10:00:00+00:00".to_string(), data: completed };
+    let body = serde_json::to_string(&payload).unwrap();
+    assert!(body.starts_with("{\"event\":\"workblock_completed\""));
+    
+    println!("✓ Test: Workblock event data passed");
+}
+
+#[test]
+fn test_milestone_event_data() {
+    let templates = MilestoneTemplates::default();
+    let goal = Milestone::GoalReached { date: "2024-05-01".to_string(), metric: GoalMetric::TrackedMinutes, target: 240, actual: 255 };
+    assert_eq!(milestone_event(&goal), WebhookEvent::GoalReached);
+    let data = milestone_event_data(&goal, &templates);
+    assert_eq!(data["message"], "Goal reached on 2024-05-01: 255 of 240 tracked minutes");
+    assert_eq!(data["milestone"]["target"], 240);
+    
+    let budget = Milestone::BudgetExceeded { date: "2024-05-01".to_string(), tracked_minutes: 545, daily_limit_minutes: 480 };
+    assert_eq!(milestone_event(&budget), WebhookEvent::BudgetExceeded);
+    assert_eq!(milestone_event_data(&budget, &templates)["message"], "Daily limit exceeded on 2024-05-01: 545 of 480 minutes tracked");
+    
+    let streak = Milestone::StreakMilestone { date: "2024-05-01".to_string(), streak: 7 };
+    assert_eq!(milestone_event(&streak), WebhookEvent::StreakMilestone);
+    assert_eq!(milestone_event_data(&streak, &templates)["message"], "7-day goal streak on 2024-05-01");
+    
+    // Messages follow the user's templates; unknown placeholders are left alone
+    let templates = MilestoneTemplates { streak_milestone: ":fire: {streak} days in a row {unknown}".to_string(), ..templates };
+    assert_eq!(milestone_event_data(&streak, &templates)["message"], ":fire: 7 days in a row {unknown}");
+    assert_eq!(render_milestone_message("{metric}: {actual}/{target}", &goal), "tracked minutes: 255/240");
+    
+    // Milestone webhooks are configured like any other
+    let conn = setup();
+    let hook = insert_webhook(&conn, "https://hooks.zapier.com/log15", &[WebhookEvent::StreakMilestone], None).unwrap();
+    assert_eq!(hook.events, vec![WebhookEvent::StreakMilestone]);
+    assert_eq!(WebhookEvent::from_str("budget_exceeded"), Some(WebhookEvent::BudgetExceeded));
+    
+    println!("✓ Test: Milestone event data passed");
+}
@@ -0,0 +1,73 @@
+// Test saving and restoring the main window layout
+// Run with: cargo test --test window_state_test
+
+use log15_lib::db::{create_schema, store_setting};
+use log15_lib::window_state::*;
+use rusqlite::Connection;
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    conn
+}
+
+#[test]
+fn test_window_state_round_trip() {
+    let conn = setup();
+    assert_eq!(query_window_state(&conn), None);
+
+    let state = WindowState { width: 1024.0, height: 768.0, x: 120.0, y: 80.0 };
+    store_window_state(&conn, &state).unwrap();
+    assert_eq!(query_window_state(&conn), Some(state));
+
+    // Too small or malformed layouts fall back to the defaults
+    store_window_state(&conn, &WindowState { width: 120.0, height: 40.0, x: 0.0, y: 0.0 }).unwrap();
+    assert_eq!(query_window_state(&conn), None);
+    store_setting(&conn, MAIN_WINDOW_STATE_KEY, "not json").unwrap();
+    assert_eq!(query_window_state(&conn), None);
+
+    // Layout saves aren't setting changes worth a history entry
+    let history: i64 = conn
+        .query_row("SELECT COUNT(*) FROM settings_history WHERE key = ?1", [MAIN_WINDOW_STATE_KEY], |row| row.get(0))
+        .unwrap();
+    assert_eq!(history, 0);
+
+    println!("✓ Test: Window state round trip passed");
+}
+
+#[test]
+fn test_window_state_on_screen() {
+    let primary = MonitorRect { x: 0.0, y: 0.0, width: 1440.0, height: 900.0 };
+    let secondary = MonitorRect { x: 1440.0, y: 0.0, width: 1920.0, height: 1080.0 };
+    let at = |x: f64, y: f64| WindowState { width: 800.0, height: 600.0, x, y };
+
+    assert!(is_on_screen(&at(100.0, 100.0), &[primary]));
+    // Saved on a monitor that is no longer connected
+    assert!(!is_on_screen(&at(2000.0, 100.0), &[primary]));
+    assert!(is_on_screen(&at(2000.0, 100.0), &[primary, secondary]));
+    // Mostly off the left edge still leaves the title bar reachable
+    assert!(is_on_screen(&at(-700.0, 100.0), &[primary]));
+    assert!(!is_on_screen(&at(-790.0, 100.0), &[primary]));
+    // Title bar above the top of every monitor
+    assert!(!is_on_screen(&at(100.0, -20.0), &[primary]));
+    assert!(!is_on_screen(&at(100.0, 100.0), &[]));
+
+    println!("✓ Test: Window state on screen passed");
+}
+
+#[test]
+fn test_last_viewed_date() {
+    let conn = setup();
+    assert_eq!(query_last_viewed_date(&conn), None);
+
+    store_setting(&conn, LAST_VIEWED_DATE_KEY, "2024-06-03").unwrap();
+    assert_eq!(query_last_viewed_date(&conn).as_deref(), Some("2024-06-03"));
+
+    // Cleared or invalid dates restore nothing
+    store_setting(&conn, LAST_VIEWED_DATE_KEY, "").unwrap();
+    assert_eq!(query_last_viewed_date(&conn), None);
+    store_setting(&conn, LAST_VIEWED_DATE_KEY, "June 3rd").unwrap();
+    assert_eq!(query_last_viewed_date(&conn), None);
+
+    println!("✓ Test: Last viewed date passed");
+}
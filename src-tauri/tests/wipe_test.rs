@@ -0,0 +1,58 @@
+// Test wiping all data: every table is emptied and the app starts over as on a first launch
+// Run with: cargo test --test wipe_test
+
+use log15_lib::db::{create_schema, query_setting, store_setting};
+use log15_lib::onboarding::initialize_first_run;
+use log15_lib::settings::{AUTO_AWAY_MESSAGE_KEY, DEFAULT_AUTO_AWAY_MESSAGE};
+use log15_lib::wipe::*;
+use rusqlite::{params, Connection};
+
+fn count(conn: &Connection, table: &str) -> i64 {
+    conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap()
+}
+
+#[test]
+fn test_wipe_database() {
+    let conn = Connection::open_in_memory().unwrap();
+    create_schema(&conn).unwrap();
+    initialize_first_run(&conn).unwrap();
+    store_setting(&conn, AUTO_AWAY_MESSAGE_KEY, "Stepped out").unwrap();
+    for date in ["2024-05-01", "2024-05-02"] {
+        conn.execute(
+            "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES (?1, ?1, 60, 'completed')",
+            params![date],
+        ).unwrap();
+        conn.execute(
+            "INSERT INTO intervals (workblock_id, interval_number, start_time, words, status)
+             VALUES (?1, 1, ?2, 'Writing', 'recorded')",
+            params![conn.last_insert_rowid(), date],
+        ).unwrap();
+        conn.execute(
+            "INSERT INTO interval_activities (interval_id, position, words, percentage) VALUES (?1, 0, 'Writing', 100)",
+            params![conn.last_insert_rowid()],
+        ).unwrap();
+        conn.execute("INSERT INTO daily_archives (date) VALUES (?1)", params![date]).unwrap();
+    }
+    conn.execute("INSERT INTO goals (metric, target) VALUES ('tracked_minutes', 240)", []).unwrap();
+    let settings = count(&conn, "settings") as usize;
+    
+    let summary = wipe_database(&conn).unwrap();
+    assert_eq!(summary.workblocks_deleted, 2);
+    assert_eq!(summary.intervals_deleted, 2);
+    assert_eq!(summary.archives_deleted, 2);
+    assert_eq!(summary.settings_deleted, settings);
+    for table in ["workblocks", "intervals", "interval_activities", "daily_archives", "settings", "goals"] {
+        assert_eq!(count(&conn, table), 0, "{} not emptied", table);
+    }
+    
+    // The next launch seeds the defaults again, and ids start over
+    assert!(initialize_first_run(&conn).unwrap());
+    assert_eq!(query_setting(&conn, AUTO_AWAY_MESSAGE_KEY).unwrap().as_deref(), Some(DEFAULT_AUTO_AWAY_MESSAGE));
+    conn.execute(
+        "INSERT INTO workblocks (date, start_time, duration_minutes, status) VALUES ('2024-05-03', '2024-05-03', 60, 'active')",
+        [],
+    ).unwrap();
+    assert_eq!(conn.last_insert_rowid(), 1);
+    
+    println!("✓ Test: Wipe database passed");
+}
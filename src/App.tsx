@@ -1,23 +1,33 @@
 import { useState, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { listenAcked } from "./api/events";
 import PromptPage from "./pages/PromptPage";
+import BreakPage from "./pages/BreakPage";
+import SummaryPage from "./pages/SummaryPage";
+import StatusPage from "./pages/StatusPage";
 import WorkblockControl from "./components/WorkblockControl";
 import SummaryView from "./components/SummaryView";
 import ArchiveView from "./components/ArchiveView";
+import type { IntervalEndingSoon, WellbeingAlert, WipeSummary } from "./types/workblock";
 import "./App.css";
 
 function App() {
     const [currentView, setCurrentView] = useState<string>("main");
+    const [lastViewedDate, setLastViewedDate] = useState<string | null>(null);
     // Use a ref to track handled intervals across renders
     const handledIntervalsRef = useRef<Set<number>>(new Set());
+    // The window type check runs more than once; restore the last session only the first time
+    const restoredSessionRef = useRef(false);
 
     // Check window type on mount (for prompt window detection)
     useEffect(() => {
         let unlistenPromise: Promise<() => void> | null = null;
         let unlistenStart: Promise<() => void> | null = null;
-        let unlistenSummary: Promise<() => void> | null = null;
         let unlistenLastWords: Promise<() => void> | null = null;
+        let unlistenWellbeing: Promise<() => void> | null = null;
+        let unlistenEndingSoon: Promise<() => void> | null = null;
+        let unlistenWiped: Promise<() => void> | null = null;
 
         const checkWindowType = async () => {
             try {
@@ -31,6 +41,18 @@ function App() {
                     setCurrentView("prompt");
                     return;
                 }
+                if (label === "break") {
+                    setCurrentView("break");
+                    return;
+                }
+                if (label === "summary") {
+                    setCurrentView("summary-window");
+                    return;
+                }
+                if (label === "status") {
+                    setCurrentView("status");
+                    return;
+                }
             } catch (error) {
                 console.error("[APP] Error in window type check:", error);
             }
@@ -42,15 +64,39 @@ function App() {
                 setCurrentView("prompt");
                 return;
             }
+            if (hash.startsWith("#/break")) {
+                setCurrentView("break");
+                return;
+            }
+            if (hash.startsWith("#/summary")) {
+                setCurrentView("summary-window");
+                return;
+            }
+            if (hash.startsWith("#/status")) {
+                setCurrentView("status");
+                return;
+            }
 
             // If we get here, we're in the main window
             console.log("[APP] Main window detected, setting up listeners");
 
+            // Reopen the day that was being viewed when the app last closed
+            if (!restoredSessionRef.current) {
+                restoredSessionRef.current = true;
+                invoke<string | null>("get_last_viewed_date")
+                    .then((date) => {
+                        if (date) {
+                            setLastViewedDate(date);
+                            setCurrentView("archive");
+                        }
+                    })
+                    .catch(console.error);
+            }
+
             // Listen for interval-complete event to show prompt window
             const setupListeners = async () => {
-                const unlisten = await listen("interval-complete", async (event: any) => {
-                    console.log("[FRONTEND] Received interval-complete event:", event.payload);
-                    const payload = event.payload as { interval_id?: number; interval_number?: number };
+                const unlisten = await listenAcked<{ interval_id?: number; interval_number?: number }>("interval-complete", async (payload) => {
+                    console.log("[FRONTEND] Received interval-complete event:", payload);
                     if (payload.interval_id) {
                         // Prevent duplicate handling of the same interval
                         if (handledIntervalsRef.current.has(payload.interval_id)) {
@@ -65,8 +111,9 @@ function App() {
                             console.log("[FRONTEND] show_prompt_window_cmd succeeded");
                         } catch (error) {
                             console.error("[FRONTEND] Failed to show prompt window:", error);
-                            // Remove from set on error so we can retry
+                            // Remove from set on error so we can retry (the event stays unacknowledged)
                             handledIntervalsRef.current.delete(payload.interval_id);
+                            throw error;
                         }
                     } else {
                         console.warn("[FRONTEND] interval-complete event missing interval_id");
@@ -80,19 +127,51 @@ function App() {
                 unlistenPromise = Promise.resolve(unlisten);
             });
 
+            // Show a system notification; false if notifications aren't available or allowed
+            const notify = async (body: string) => {
+                if (!("Notification" in window)) {
+                    return false;
+                }
+                const permission = Notification.permission === "default"
+                    ? await Notification.requestPermission()
+                    : Notification.permission;
+                if (permission !== "granted") {
+                    return false;
+                }
+                new Notification("Log15", { body });
+                return true;
+            };
+
+            // Suggest stopping once today's tracking passes the wellbeing limit (opt-in)
+            unlistenWellbeing = listen<WellbeingAlert>("wellbeing-limit-exceeded", async (event) => {
+                const hours = (event.payload.tracked_minutes / 60).toFixed(1);
+                const body = `You've tracked ${hours} hours today. Consider stopping for the day.`;
+                if (!(await notify(body))) {
+                    alert(body);
+                }
+            });
+
+            // Heads-up before the prompt appears; no alert fallback, that would steal focus itself
+            unlistenEndingSoon = listen<IntervalEndingSoon>("interval-ending-soon", async (event) => {
+                if (event.payload.notification) {
+                    await notify(`Interval ${event.payload.interval_number} ends in ${event.payload.seconds_left} seconds.`);
+                }
+            });
+
             // Listen for tray navigation events
             unlistenStart = listen("tray-start-workblock", () => {
                 setCurrentView("main");
             });
 
-            unlistenSummary = listen("tray-view-summary", () => {
-                setCurrentView("summary");
-            });
-
             unlistenLastWords = listen("tray-view-last-words", () => {
                 setCurrentView("main");
                 // TODO: Show last words
             });
+
+            // Everything was deleted; start over from a clean state (onboarding shows again)
+            unlistenWiped = listen<WipeSummary>("data-wiped", () => {
+                window.location.reload();
+            });
         };
 
         // Check immediately
@@ -111,8 +190,10 @@ function App() {
             clearTimeout(timeoutId2);
             unlistenPromise?.then((fn) => fn());
             unlistenStart?.then((fn) => fn());
-            unlistenSummary?.then((fn) => fn());
             unlistenLastWords?.then((fn) => fn());
+            unlistenWellbeing?.then((fn) => fn());
+            unlistenEndingSoon?.then((fn) => fn());
+            unlistenWiped?.then((fn) => fn());
         };
     }, []);
 
@@ -120,6 +201,18 @@ function App() {
         return <PromptPage />;
     }
 
+    if (currentView === "break") {
+        return <BreakPage />;
+    }
+
+    if (currentView === "summary-window") {
+        return <SummaryPage />;
+    }
+
+    if (currentView === "status") {
+        return <StatusPage />;
+    }
+
     if (currentView === "summary") {
         return (
             <main className="container">
@@ -131,7 +224,13 @@ function App() {
     if (currentView === "archive") {
         return (
             <main className="container">
-                <ArchiveView onBack={() => setCurrentView("main")} />
+                <ArchiveView
+                    initialDate={lastViewedDate}
+                    onBack={() => {
+                        setLastViewedDate(null);
+                        setCurrentView("main");
+                    }}
+                />
             </main>
         );
     }
@@ -0,0 +1,15 @@
+import type { Log15Error, Log15ErrorCode } from "../types/workblock";
+
+// Commands reject with { code, message }; older code paths and plugins may still reject with a string.
+
+export function isLog15Error(error: unknown): error is Log15Error {
+    return typeof error === "object" && error !== null && "code" in error && "message" in error;
+}
+
+export function errorCode(error: unknown): Log15ErrorCode | null {
+    return isLog15Error(error) ? error.code : null;
+}
+
+export function errorMessage(error: unknown): string {
+    return isLog15Error(error) ? error.message : String(error);
+}
@@ -0,0 +1,39 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
+import type { EventName, EventPayloads } from "../types/workblock";
+
+// Lifecycle-critical events (interval-complete, workblock-complete, missed-intervals) arrive as
+// { seq, payload } and are re-sent on window focus until acknowledged.
+
+export interface AckedEvent<T> {
+    seq: number;
+    payload: T;
+}
+
+// Sequence numbers handled by this webview; a replay after a reload is handled once more on purpose
+const handled = new Set<number>();
+
+export function listenAcked<T>(event: string, handler: (payload: T) => void | Promise<void>): Promise<UnlistenFn> {
+    return listen<AckedEvent<T>>(event, async ({ payload: { seq, payload } }) => {
+        if (handled.has(seq)) {
+            return;
+        }
+        handled.add(seq);
+        try {
+            await handler(payload);
+            await invoke("ack_event", { seq });
+        } catch (error) {
+            // Left unacknowledged so the backend replays it
+            handled.delete(seq);
+            console.error(`[FRONTEND] Failed to handle ${event}:`, error);
+        }
+    });
+}
+
+// Listen for an event with its payload typed from the catalog (get_event_schema lists the names)
+export function listenEvent<E extends EventName>(
+    event: E,
+    handler: (payload: EventPayloads[E]) => void,
+): Promise<UnlistenFn> {
+    return listen<EventPayloads[E]>(event, ({ payload }) => handler(payload));
+}
@@ -0,0 +1,33 @@
+import { invoke, InvokeArgs } from "@tauri-apps/api/core";
+
+// Versioned facade over the backend commands: call invokeApi("v1/<name>", args) instead of
+// invoke("<name>") so command names can change without breaking older callers.
+
+export const API_VERSION = 1;
+const API_VERSION_HEADER = "log15-api-version";
+
+export interface DeprecatedCommand {
+    command: string;
+    replacement?: string; // Versioned name to use instead, if any
+}
+
+export interface ApiManifest {
+    version: number;
+    commands: Record<string, string>; // Versioned name -> registered command, where they differ
+    deprecated: DeprecatedCommand[];
+}
+
+let manifest: Promise<ApiManifest> | null = null;
+
+export function getApiManifest(): Promise<ApiManifest> {
+    manifest ??= invoke<ApiManifest>("get_api_manifest");
+    return manifest;
+}
+
+export async function invokeApi<T>(path: `v${number}/${string}`, args?: InvokeArgs): Promise<T> {
+    const [version, name] = path.split("/", 2);
+    const { commands } = await getApiManifest();
+    return invoke<T>(commands[name] ?? name, args, {
+        headers: { [API_VERSION_HEADER]: version.slice(1) },
+    });
+}
@@ -32,7 +32,7 @@ export default function ActivityChart({ activityData, title = "Activity Breakdow
         name: activity.words || "Unknown",
         minutes: activity.total_minutes,
         percentage: activity.percentage,
-        color: COLORS[index % COLORS.length],
+        color: activity.color ?? COLORS[index % COLORS.length],
     }));
 
     return (
@@ -1,18 +1,39 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { DailyArchive } from "../types/workblock";
+import { listen } from "@tauri-apps/api/event";
+import type { ArchiveUpdated, DailyArchive } from "../types/workblock";
 import SummaryView from "./SummaryView";
 import "./ArchiveView.css";
 
-export default function ArchiveView({ onBack }: { onBack?: () => void }) {
+interface ArchiveViewProps {
+    onBack?: () => void;
+    initialDate?: string | null; // Day to open right away (restored from the last session)
+}
+
+export default function ArchiveView({ onBack, initialDate }: ArchiveViewProps) {
     const [archivedDates, setArchivedDates] = useState<DailyArchive[]>([]);
-    const [selectedDate, setSelectedDate] = useState<string | null>(null);
+    const [selectedDate, setSelectedDateState] = useState<string | null>(initialDate ?? null);
     const [loading, setLoading] = useState(true);
 
     useEffect(() => {
         loadArchivedDates();
+
+        // Refresh the list whenever a day is archived or regenerated
+        const unlisten = listen<ArchiveUpdated>("archive-updated", () => {
+            loadArchivedDates();
+        });
+
+        return () => {
+            unlisten.then((fn) => fn());
+        };
     }, []);
 
+    // Remember the viewed day so the next launch reopens it
+    const setSelectedDate = (date: string | null) => {
+        setSelectedDateState(date);
+        invoke("set_last_viewed_date", { date }).catch(console.error);
+    };
+
     const loadArchivedDates = async () => {
         setLoading(true);
         try {
@@ -1,6 +1,16 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { errorMessage } from "../api/errors";
+import type {
+    ActivitySplit,
+    PromptLabel,
+    PromptQueue,
+    PromptQuickPicks,
+    ReclassifyOffer,
+    TranscriptionSettings,
+} from "../types/workblock";
 import "./PromptWindow.css";
 
 interface PromptWindowProps {
@@ -8,12 +18,114 @@ interface PromptWindowProps {
 }
 
 const CHECKMARK_DURATION_MS = 2000; // 2 seconds
+const ENERGY_LEVELS = [1, 2, 3, 4, 5]; // 1 = drained, 5 = energized
+const VOICE_SAMPLE_RATE = 16000; // What whisper expects (speech.rs)
+const MAX_RECORDING_MS = 15000; // An answer is a few words
+
+// "coding + meeting" splits the interval evenly; "coding 60% + meeting 40%" sets the shares.
+// Returns null for a single activity.
+function parseActivitySplit(words: string): ActivitySplit[] | null {
+    const parts = words.split("+").map((part) => part.trim()).filter((part) => part);
+    if (parts.length < 2) {
+        return null;
+    }
+    const parsed = parts.map((part) => {
+        const match = part.match(/^(.*?)\s*(\d{1,2})\s*%$/);
+        return match ? { words: match[1].trim(), percentage: Number(match[2]) } : { words: part, percentage: null };
+    });
+    if (parsed.every((p) => p.percentage !== null)) {
+        return parsed as ActivitySplit[];
+    }
+    // Even shares; the remainder goes to the first activities
+    const share = Math.floor(100 / parsed.length);
+    return parsed.map((p, i) => ({ words: p.words, percentage: share + (i < 100 % parsed.length ? 1 : 0) }));
+}
+
+const formatTime = (iso: string) => new Date(iso).toLocaleTimeString([], { hour: "2-digit", minute: "2-digit" });
+
+// "10:15–10:30", or just the start while the interval has no end yet
+function formatTimeRange(label: PromptLabel): string {
+    return label.end_time ? `${formatTime(label.start_time)}–${formatTime(label.end_time)}` : formatTime(label.start_time);
+}
+
+interface Recording {
+    stream: MediaStream;
+    context: AudioContext;
+    processor: ScriptProcessorNode;
+    chunks: Float32Array[];
+}
+
+// Record the microphone as mono samples at whisper's rate
+async function startRecording(): Promise<Recording> {
+    const stream = await navigator.mediaDevices.getUserMedia({ audio: true });
+    const context = new AudioContext({ sampleRate: VOICE_SAMPLE_RATE });
+    const source = context.createMediaStreamSource(stream);
+    const processor = context.createScriptProcessor(4096, 1, 1);
+    const chunks: Float32Array[] = [];
+    processor.onaudioprocess = (e) => chunks.push(new Float32Array(e.inputBuffer.getChannelData(0)));
+    source.connect(processor);
+    processor.connect(context.destination);
+    return { stream, context, processor, chunks };
+}
+
+// Stop recording and encode it as a 16-bit PCM WAV
+async function stopRecording(recording: Recording): Promise<Uint8Array> {
+    recording.processor.disconnect();
+    recording.stream.getTracks().forEach((track) => track.stop());
+    await recording.context.close();
+
+    const length = recording.chunks.reduce((sum, chunk) => sum + chunk.length, 0);
+    const view = new DataView(new ArrayBuffer(44 + length * 2));
+    const writeText = (at: number, text: string) => [...text].forEach((c, i) => view.setUint8(at + i, c.charCodeAt(0)));
+    writeText(0, "RIFF");
+    view.setUint32(4, 36 + length * 2, true);
+    writeText(8, "WAVE");
+    writeText(12, "fmt ");
+    view.setUint32(16, 16, true);
+    view.setUint16(20, 1, true); // PCM
+    view.setUint16(22, 1, true); // Mono
+    view.setUint32(24, VOICE_SAMPLE_RATE, true);
+    view.setUint32(28, VOICE_SAMPLE_RATE * 2, true);
+    view.setUint16(32, 2, true);
+    view.setUint16(34, 16, true);
+    writeText(36, "data");
+    view.setUint32(40, length * 2, true);
+    let at = 44;
+    for (const chunk of recording.chunks) {
+        for (const sample of chunk) {
+            view.setInt16(at, Math.max(-1, Math.min(1, sample)) * 0x7fff, true);
+            at += 2;
+        }
+    }
+    return new Uint8Array(view.buffer);
+}
+
+// Last words first, then today's top activities and pins, without repeating a phrase
+function quickPickWords(picks: PromptQuickPicks): string[] {
+    const candidates = [...(picks.last_words ? [picks.last_words] : []), ...picks.top_today, ...picks.pinned];
+    return candidates.filter(
+        (words, i) => candidates.findIndex((other) => other.toLowerCase() === words.toLowerCase()) === i
+    );
+}
 
 export default function PromptWindow({ intervalId }: PromptWindowProps) {
     const [words, setWords] = useState("");
+    const [energy, setEnergy] = useState<number | null>(null);
     const [showCheckmark, setShowCheckmark] = useState(false);
     const [isVisible, setIsVisible] = useState(false);
-    const [showSummaryReady, setShowSummaryReady] = useState(false);
+    const [reclassifyOffer, setReclassifyOffer] = useState<ReclassifyOffer | null>(null);
+    const [quickStatuses, setQuickStatuses] = useState<string[]>([]);
+    // A passive prompt shows dimmed without focus until the hotkey or a click focuses it
+    const [passive, setPassive] = useState(false);
+    const [focused, setFocused] = useState(false);
+    // Set when more than one interval awaits an answer; each prompt is labelled with its time range
+    const [queue, setQueue] = useState<PromptQueue | null>(null);
+    const [quickPicks, setQuickPicks] = useState<string[]>([]);
+    const [voiceEnabled, setVoiceEnabled] = useState(false);
+    const [recording, setRecording] = useState<Recording | null>(null);
+    const recordingRef = useRef<Recording | null>(null);
+    const [transcribing, setTranscribing] = useState(false);
+    const [voiceError, setVoiceError] = useState<string | null>(null);
 
     useEffect(() => {
         console.log("[PROMPT_WINDOW] intervalId changed:", intervalId);
@@ -22,11 +134,46 @@ export default function PromptWindow({ intervalId }: PromptWindowProps) {
             setIsVisible(true);
             // Reset state when new interval comes in
             setShowCheckmark(false);
-            setShowSummaryReady(false);
             setWords("");
+            setEnergy(null);
+            setReclassifyOffer(null);
+            setVoiceError(null);
+
+            // Was the previous interval lost to auto-away?
+            invoke<ReclassifyOffer | null>("get_reclassify_offer_cmd", { intervalId })
+                .then(setReclassifyOffer)
+                .catch(console.error);
+            invoke<string[]>("get_quick_statuses")
+                .then(setQuickStatuses)
+                .catch(console.error);
+            invoke<boolean>("get_passive_prompt")
+                .then(setPassive)
+                .catch(console.error);
+            invoke<PromptQueue>("get_prompt_queue")
+                .then(setQueue)
+                .catch(console.error);
+            invoke<PromptQuickPicks>("get_prompt_quick_picks")
+                .then((picks) => setQuickPicks(quickPickWords(picks)))
+                .catch(console.error);
+            invoke<TranscriptionSettings>("get_transcription_settings")
+                .then((settings) => setVoiceEnabled(settings.enabled))
+                .catch(console.error);
         }
     }, [intervalId]);
 
+    useEffect(() => {
+        const unlistenFocus = getCurrentWindow().onFocusChanged(({ payload: isFocused }) => {
+            setFocused(isFocused);
+            if (isFocused) {
+                document.getElementById("words-input")?.focus();
+            }
+        });
+
+        return () => {
+            unlistenFocus.then((fn) => fn());
+        };
+    }, []);
+
     useEffect(() => {
         // Listen for hide event
         const unlisten = listen("prompt-hide", () => {
@@ -42,23 +189,20 @@ export default function PromptWindow({ intervalId }: PromptWindowProps) {
             invoke("hide_prompt_window_cmd").catch(console.error);
         });
 
-        // Listen for show summary ready event
-        const unlistenSummary = listen("show-summary-ready", () => {
-            setShowSummaryReady(true);
-            setShowCheckmark(false);
-            setWords("");
+        const unlistenQueue = listen<PromptQueue>("prompt-queue", (event) => {
+            setQueue(event.payload);
         });
 
-        // Listen for close summary event
-        const unlistenClose = listen("close-summary", () => {
-            handleFadeOut();
+        // Listen for reclassify offer (fallback if the window loaded before the offer was fetched)
+        const unlistenReclassify = listen<ReclassifyOffer>("reclassify-offer", (event) => {
+            setReclassifyOffer(event.payload);
         });
 
         return () => {
             unlisten.then((fn) => fn());
             unlistenAutoAway.then((fn) => fn());
-            unlistenSummary.then((fn) => fn());
-            unlistenClose.then((fn) => fn());
+            unlistenReclassify.then((fn) => fn());
+            unlistenQueue.then((fn) => fn());
         };
     }, []);
 
@@ -70,8 +214,9 @@ export default function PromptWindow({ intervalId }: PromptWindowProps) {
         }, 300); // Wait for fade-out animation
     };
 
-    const handleSubmit = async () => {
-        if (!intervalId || !words.trim()) {
+    // A quick pick submits its words directly
+    const handleSubmit = async (answer: string = words) => {
+        if (!intervalId || !answer.trim()) {
             return;
         }
 
@@ -80,34 +225,121 @@ export default function PromptWindow({ intervalId }: PromptWindowProps) {
 
         // Submit words
         try {
-            const result = await invoke<{ is_last_interval: boolean }>("submit_interval_words", {
+            // After the last interval the backend opens the summary window
+            await invoke("submit_interval_words", {
                 intervalId: intervalId,
-                words: words.trim(),
+                words: answer.trim(),
+                energy,
+                activities: parseActivitySplit(answer),
             });
 
-            // If this is the last interval, show summary ready after checkmark duration
-            if (result.is_last_interval) {
-                setTimeout(() => {
-                    setShowSummaryReady(true);
-                    setShowCheckmark(false);
-                }, CHECKMARK_DURATION_MS);
-            } else {
-                // For non-last intervals, close window after checkmark duration
-                setTimeout(() => {
-                    invoke("hide_prompt_window_cmd").catch(console.error);
-                }, CHECKMARK_DURATION_MS);
-            }
+            // Close window after checkmark duration
+            setTimeout(() => {
+                invoke("hide_prompt_window_cmd").catch(console.error);
+            }, CHECKMARK_DURATION_MS);
         } catch (error) {
             console.error("Failed to submit words:", error);
             setShowCheckmark(false);
         }
     };
 
-    const handleCloseSummary = async () => {
+    const finishVoice = async (active: Recording) => {
+        if (recordingRef.current !== active) {
+            return; // Already stopped
+        }
+        recordingRef.current = null;
+        setRecording(null);
+        setTranscribing(true);
+        try {
+            const audio = await stopRecording(active);
+            // Sent as a raw body: a JSON number array would be several times the recording's size
+            const result = await invoke<{ transcript: string }>("transcribe_and_submit", audio, {
+                headers: { "Interval-Id": String(intervalId) },
+            });
+            setWords(result.transcript);
+            setShowCheckmark(true);
+            setTimeout(() => {
+                invoke("hide_prompt_window_cmd").catch(console.error);
+            }, CHECKMARK_DURATION_MS);
+        } catch (error) {
+            console.error("Failed to answer by voice:", error);
+            setVoiceError(errorMessage(error));
+        }
+        setTranscribing(false);
+    };
+
+    // Click to start speaking, click again (or wait) to answer with what was said
+    const handleVoice = async () => {
+        if (!intervalId || transcribing) {
+            return;
+        }
+        if (recording) {
+            await finishVoice(recording);
+            return;
+        }
+
+        setVoiceError(null);
+        try {
+            const started = await startRecording();
+            recordingRef.current = started;
+            setRecording(started);
+            setTimeout(() => finishVoice(started), MAX_RECORDING_MS);
+        } catch (error) {
+            console.error("Failed to start recording:", error);
+            setVoiceError("Microphone unavailable");
+        }
+    };
+
+    const handleReclassify = async () => {
+        if (!reclassifyOffer?.suggested_words) {
+            return;
+        }
+
         try {
-            await invoke("hide_prompt_window_cmd");
+            await invoke("reclassify_interval_cmd", {
+                intervalId: reclassifyOffer.interval_id,
+                words: reclassifyOffer.suggested_words,
+            });
         } catch (error) {
-            console.error("Failed to close summary window:", error);
+            console.error("Failed to reclassify interval:", error);
+        }
+        setReclassifyOffer(null);
+    };
+
+    // Label the auto-away interval with where the user was
+    const handleQuickStatus = async (status: string) => {
+        if (!reclassifyOffer) {
+            return;
+        }
+
+        try {
+            await invoke("submit_quick_status", {
+                intervalId: reclassifyOffer.interval_id,
+                status,
+            });
+        } catch (error) {
+            console.error("Failed to set quick status:", error);
+        }
+        setReclassifyOffer(null);
+    };
+
+    const handleSnooze = async () => {
+        if (!intervalId) {
+            return;
+        }
+
+        try {
+            // Backend hides the window and re-shows it once the snooze is over
+            await invoke("snooze_prompt", { intervalId });
+        } catch (error) {
+            console.error("Failed to snooze prompt:", error);
+        }
+    };
+
+    // The passive window can't take focus by itself, so a click asks the backend for it
+    const handleFocusRequest = () => {
+        if (passive && !focused) {
+            invoke("focus_prompt_window").catch(console.error);
         }
     };
 
@@ -118,22 +350,44 @@ export default function PromptWindow({ intervalId }: PromptWindowProps) {
     };
 
     return (
-        <div className={`prompt-container ${isVisible ? "fade-in" : "fade-out"}`}>
-            {showSummaryReady ? (
-                <div className="summary-ready-content">
-                    <div className="summary-icon">📊</div>
-                    <h3 className="summary-title">Summary Ready!</h3>
-                    <p className="summary-message">Your workblock summary is ready to view.</p>
-                    <button onClick={handleCloseSummary} className="close-summary-button">
-                        Close
-                    </button>
-                </div>
-            ) : showCheckmark ? (
+        <div
+            className={`prompt-container ${isVisible ? "fade-in" : "fade-out"} ${passive && !focused ? "dimmed" : ""}`}
+            onMouseDown={handleFocusRequest}
+        >
+            {showCheckmark ? (
                 <div className="checkmark-container">
                     <div className="checkmark"></div>
                 </div>
             ) : intervalId ? (
                 <div className="prompt-content">
+                    {reclassifyOffer?.suggested_words && (
+                        <div className="reclassify-offer">
+                            Last interval: still {reclassifyOffer.suggested_words}?
+                            <button onClick={handleReclassify} className="reclassify-button">
+                                Yes
+                            </button>
+                        </div>
+                    )}
+                    {reclassifyOffer && quickStatuses.length > 0 && (
+                        <div className="quick-statuses">
+                            Away for:
+                            {quickStatuses.map((status) => (
+                                <button
+                                    key={status}
+                                    onClick={() => handleQuickStatus(status)}
+                                    className="quick-status-button"
+                                >
+                                    {status}
+                                </button>
+                            ))}
+                        </div>
+                    )}
+                    {queue?.current?.interval_id === intervalId && (queue.current.end_time || queue.pending.length > 0) && (
+                        <div className="prompt-queue">
+                            {formatTimeRange(queue.current)}
+                            {queue.pending.length > 0 && ` · ${queue.pending.length} more waiting`}
+                        </div>
+                    )}
                     <div className="prompt-label">
                         What did you do? (1-2 words)
                     </div>
@@ -143,11 +397,47 @@ export default function PromptWindow({ intervalId }: PromptWindowProps) {
                         value={words}
                         onChange={(e) => setWords(e.target.value)}
                         onKeyPress={handleKeyPress}
-                        placeholder="e.g., coding, meeting"
+                        placeholder="e.g., coding, or coding + meeting"
                         className="words-input"
                         autoFocus
                         maxLength={50}
                     />
+                    {voiceEnabled && (
+                        <div className="voice-input">
+                            <button
+                                onClick={handleVoice}
+                                disabled={transcribing}
+                                className={`voice-button ${recording ? "recording" : ""}`}
+                            >
+                                {transcribing ? "Listening back..." : recording ? "Done speaking" : "Speak"}
+                            </button>
+                            {voiceError && <span className="voice-error">{voiceError}</span>}
+                        </div>
+                    )}
+                    {quickPicks.length > 0 && (
+                        <div className="quick-picks">
+                            {quickPicks.map((pick) => (
+                                <button key={pick} onClick={() => handleSubmit(pick)} className="quick-pick-button">
+                                    {pick}
+                                </button>
+                            ))}
+                        </div>
+                    )}
+                    <div className="energy-rating">
+                        Energy:
+                        {ENERGY_LEVELS.map((level) => (
+                            <button
+                                key={level}
+                                onClick={() => setEnergy(energy === level ? null : level)}
+                                className={`energy-button ${energy === level ? "selected" : ""}`}
+                            >
+                                {level}
+                            </button>
+                        ))}
+                    </div>
+                    <button onClick={handleSnooze} className="snooze-button">
+                        Give me a minute
+                    </button>
                 </div>
             ) : (
                 <div className="prompt-content">
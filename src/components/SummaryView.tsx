@@ -1,11 +1,14 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type {
     Workblock,
     DailyVisualizationData,
     WorkblockVisualization,
     DailyAggregate,
     DailyArchive,
+    ArchiveUpdated,
+    DailyAggregateDelta,
 } from "../types/workblock";
 import TimelineChart from "./TimelineChart";
 import ActivityChart from "./ActivityChart";
@@ -15,9 +18,42 @@ import "./SummaryView.css";
 interface SummaryViewProps {
     onBack?: () => void;
     date?: string; // If provided, show archive for this date
+    data?: DailyVisualizationData; // Today's data pushed by the backend (summary window); skips fetching it
 }
 
-export default function SummaryView({ onBack, date }: SummaryViewProps) {
+// Patch a day's aggregate with a "daily-aggregate-updated" delta
+function applyAggregateDelta(aggregate: DailyAggregate, delta: DailyAggregateDelta): DailyAggregate {
+    const key = (entry: { workblock_id: number; interval_number: number }) => `${entry.workblock_id}:${entry.interval_number}`;
+    const replacedEntries = new Set([...delta.timeline_removed, ...delta.timeline_upserted].map(key));
+    const timeline_data = [
+        ...aggregate.timeline_data.filter((entry) => !replacedEntries.has(key(entry))),
+        ...delta.timeline_upserted,
+    ].sort((a, b) => a.start_time.localeCompare(b.start_time));
+
+    const replacedActivities = new Set([...delta.activities_removed, ...delta.activities_upserted.map((a) => a.words)]);
+    const activity_data = [
+        ...aggregate.activity_data.filter((activity) => !replacedActivities.has(activity.words)),
+        ...delta.activities_upserted,
+    ];
+
+    return {
+        ...aggregate,
+        total_workblocks: delta.total_workblocks,
+        total_minutes: delta.total_minutes,
+        over_daily_limit: delta.over_daily_limit,
+        after_the_fact_minutes: delta.after_the_fact_minutes,
+        timeline_data,
+        activity_data,
+        word_frequency: delta.word_frequency ?? aggregate.word_frequency,
+        workblock_boundaries: delta.workblock_boundaries ?? aggregate.workblock_boundaries,
+        project_data: delta.project_data ?? aggregate.project_data,
+        deep_work_sessions: delta.deep_work_sessions ?? aggregate.deep_work_sessions,
+        energy_timeline: delta.energy_timeline ?? aggregate.energy_timeline,
+        away_causes: delta.away_causes ?? aggregate.away_causes,
+    };
+}
+
+export default function SummaryView({ onBack, date, data }: SummaryViewProps) {
     const [activeTab, setActiveTab] = useState<string>("aggregate");
     const [vizData, setVizData] = useState<DailyVisualizationData | null>(null);
     const [workblocks, setWorkblocks] = useState<Workblock[]>([]);
@@ -28,7 +64,30 @@ export default function SummaryView({ onBack, date }: SummaryViewProps) {
 
     useEffect(() => {
         loadSummaryData();
-    }, [date]);
+
+        // Reload if the archive for the displayed date was rebuilt
+        const unlisten = listen<ArchiveUpdated>("archive-updated", async (event) => {
+            const targetDate = date || (await invoke<string>("get_today_date_cmd"));
+            if (event.payload.date === targetDate) {
+                loadSummaryData();
+            }
+        });
+
+        // Today's aggregate changed: patch it rather than refetching the day
+        const unlistenAggregate = listen<DailyAggregateDelta>("daily-aggregate-updated", async (event) => {
+            const targetDate = date || (await invoke<string>("get_today_date_cmd"));
+            if (event.payload.date === targetDate) {
+                setVizData((current) =>
+                    current && { ...current, daily_aggregate: applyAggregateDelta(current.daily_aggregate, event.payload) }
+                );
+            }
+        });
+
+        return () => {
+            unlisten.then((fn) => fn());
+            unlistenAggregate.then((fn) => fn());
+        };
+    }, [date, data]);
 
     const loadSummaryData = async () => {
         setLoading(true);
@@ -59,8 +118,8 @@ export default function SummaryView({ onBack, date }: SummaryViewProps) {
             } else {
                 // Load current day data
                 setIsArchived(false);
-                const vizDataJson = await invoke<string>("get_daily_visualization_data_cmd", { date: targetDate });
-                const parsed = JSON.parse(vizDataJson) as DailyVisualizationData;
+                const parsed =
+                    data ?? (await invoke<DailyVisualizationData>("get_daily_visualization_data_cmd", { date: targetDate }));
                 setVizData(parsed);
 
                 // Get workblocks for the date
@@ -238,6 +297,10 @@ export default function SummaryView({ onBack, date }: SummaryViewProps) {
                                     wordFrequency={workblock.word_frequency}
                                     title={`Workblock #${workblockNumber} Word Frequency`}
                                 />
+                                <WordFrequencyChart
+                                    wordFrequency={workblock.word_counts ?? []}
+                                    title={`Workblock #${workblockNumber} Words`}
+                                />
                             </div>
                         );
                     })()
@@ -156,6 +156,8 @@ export default function TimelineChart({ timelineData, title = "Timeline", workbl
                         // If this interval is cancelled, show "cancelled" as the words
                         const displayWords =
                             interval.workblock_status === "cancelled" ? "cancelled" : interval.words || "Pending";
+                        // Backfilled, imported or edited answers are shown muted and italic
+                        const afterTheFact = interval.provenance !== undefined && interval.provenance !== "live";
 
                         return (
                             <div
@@ -181,7 +183,10 @@ export default function TimelineChart({ timelineData, title = "Timeline", workbl
                                         fontSize: "12px",
                                         color: "white",
                                         fontWeight: interval.workblock_status === "cancelled" ? 600 : 400,
+                                        fontStyle: afterTheFact ? "italic" : "normal",
+                                        opacity: afterTheFact ? 0.7 : 1,
                                     }}
+                                    title={afterTheFact ? `Recorded after the fact (${interval.provenance})` : undefined}
                                 >
                                     {displayWords}
                                 </div>
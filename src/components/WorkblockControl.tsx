@@ -1,7 +1,18 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import type { Workblock, TimerState } from "../types/workblock";
+import { errorCode, errorMessage } from "../api/errors";
+import { listenAcked } from "../api/events";
+import type { Workblock, TimerState, TimerTick, CancelReason, MissedIntervals, WorkblockPlan, DayRolledOver } from "../types/workblock";
+
+const CANCEL_REASONS: { value: CancelReason; label: string }[] = [
+    { value: "interrupted", label: "Interrupted" },
+    { value: "meeting", label: "Meeting" },
+    { value: "distracted", label: "Distracted" },
+    { value: "tired", label: "Tired" },
+    { value: "plans_changed", label: "Plans changed" },
+    { value: "other", label: "Other" },
+];
 
 interface WorkblockControlProps {
     onNavigateToSummary?: () => void;
@@ -13,31 +24,77 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
     const [timerState, setTimerState] = useState<TimerState | null>(null);
     const [hours, setHours] = useState<number>(1); // Default 1 hour
     const [minutes, setMinutes] = useState<number>(0); // Default 0 minutes
+    const [title, setTitle] = useState("");
     const [timeRemaining, setTimeRemaining] = useState<number | null>(null);
     const [loading, setLoading] = useState(false);
     const [showInfoOverlay, setShowInfoOverlay] = useState(false);
+    const [cancelReason, setCancelReason] = useState<CancelReason | "">("");
+    const [cancelNote, setCancelNote] = useState("");
+    const [lastCancelledId, setLastCancelledId] = useState<number | null>(null);
+    const [durationPresets, setDurationPresets] = useState<number[]>([]);
+    const [plan, setPlan] = useState<WorkblockPlan | null>(null);
+    const lastTickIntervalRef = useRef<number | null>(null);
 
     // Calculate total duration in minutes
     const duration = hours * 60 + minutes;
 
+    // Preview the prompt times for the selected duration
+    useEffect(() => {
+        if (activeWorkblock || duration <= 0) {
+            setPlan(null);
+            return;
+        }
+        invoke<WorkblockPlan>("preview_workblock_plan", { durationMinutes: duration })
+            .then(setPlan)
+            .catch(() => setPlan(null));
+    }, [duration, activeWorkblock]);
+
     // Load active workblock on mount
     useEffect(() => {
         loadActiveWorkblock();
         loadTimerState();
+        invoke<number[]>("get_duration_presets")
+            .then(setDurationPresets)
+            .catch((error) => console.error("Failed to load duration presets:", error));
 
-        // Set up interval to update timer state
-        const interval = setInterval(() => {
-            loadTimerState();
-        }, 1000); // Update every second
+        // The backend pushes a countdown every second while we are subscribed
+        invoke("start_timer_ticker").catch((error) => console.error("Failed to start timer ticker:", error));
 
         // Listen for workblock events
         const setupListeners = async () => {
-            const unlistenComplete = await listen("workblock-complete", () => {
+            const unlistenTick = await listen<TimerTick>("timer-tick", (event) => {
+                setTimeRemaining(event.payload.interval_remaining_seconds);
+                // Reload the full state only when a new interval begins
+                if (lastTickIntervalRef.current !== event.payload.interval_number) {
+                    lastTickIntervalRef.current = event.payload.interval_number;
+                    loadTimerState();
+                }
+            });
+
+            const unlistenComplete = await listenAcked<number>("workblock-complete", () => {
+                loadActiveWorkblock();
+                loadTimerState();
+            });
+
+            const unlistenMissed = await listenAcked<MissedIntervals>("missed-intervals", (missed) => {
+                console.log(
+                    `Intervals ${missed.first_interval_number}-${missed.last_interval_number} were missed while the system was asleep`
+                );
+                loadTimerState();
+            });
+
+            // A workblock running into the new day continues as a new one
+            const unlistenRollover = await listen<DayRolledOver>("day-rolled-over", () => {
                 loadActiveWorkblock();
                 loadTimerState();
             });
 
-            return unlistenComplete;
+            return () => {
+                unlistenTick();
+                unlistenComplete();
+                unlistenMissed();
+                unlistenRollover();
+            };
         };
 
         let unlistenPromise: Promise<() => void> | null = null;
@@ -46,7 +103,7 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
         });
 
         return () => {
-            clearInterval(interval);
+            invoke("stop_timer_ticker").catch(() => {});
             unlistenPromise?.then((fn) => fn());
         };
     }, []);
@@ -73,17 +130,67 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
         }
     };
 
-    const handleStartWorkblock = async () => {
+    const handleStartWorkblock = async (durationMinutes: number = duration) => {
         setLoading(true);
         try {
             const workblock = await invoke<Workblock>("start_workblock", {
-                durationMinutes: duration,
+                durationMinutes,
+                title: title.trim() || null,
             });
             setActiveWorkblock(workblock);
+            setTitle("");
             await loadTimerState();
         } catch (error) {
             console.error("Failed to start workblock:", error);
-            alert(`Failed to start workblock: ${error}`);
+            if (errorCode(error) === "already_running") {
+                // Started elsewhere (tray, hotkey, another window): show that one instead
+                await loadTimerState();
+                return;
+            }
+            alert(`Failed to start workblock: ${errorMessage(error)}`);
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const handleResumeWorkblock = async () => {
+        if (lastCancelledId === null) {
+            return;
+        }
+
+        setLoading(true);
+        try {
+            const workblock = await invoke<Workblock>("resume_cancelled_workblock", {
+                workblockId: lastCancelledId,
+            });
+            setActiveWorkblock(workblock);
+            setLastCancelledId(null);
+            await loadTimerState();
+        } catch (error) {
+            console.error("Failed to resume workblock:", error);
+            alert(`Failed to resume workblock: ${errorMessage(error)}`);
+            setLastCancelledId(null);
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const handleAdjustDuration = async (deltaMinutes: number) => {
+        if (!activeWorkblock?.id) {
+            return;
+        }
+
+        setLoading(true);
+        try {
+            const workblock = await invoke<Workblock>("adjust_workblock_duration", {
+                workblockId: activeWorkblock.id,
+                newDurationMinutes: (activeWorkblock.duration_minutes || 0) + deltaMinutes,
+            });
+            setActiveWorkblock(workblock);
+            await loadTimerState();
+        } catch (error) {
+            console.error("Failed to adjust workblock:", error);
+            alert(`Failed to adjust workblock: ${errorMessage(error)}`);
         } finally {
             setLoading(false);
         }
@@ -105,14 +212,19 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
 
             await invoke("cancel_workblock_cmd", {
                 workblockId: activeWorkblock.id,
+                reason: cancelReason || null,
+                note: cancelNote.trim() || null,
             });
+            setCancelReason("");
+            setCancelNote("");
+            setLastCancelledId(activeWorkblock.id);
 
             // Explicitly reload state from backend to ensure UI updates
             await loadActiveWorkblock();
             await loadTimerState();
         } catch (error) {
             console.error("Failed to cancel workblock:", error);
-            alert(`Failed to cancel workblock: ${error}`);
+            alert(`Failed to cancel workblock: ${errorMessage(error)}`);
         } finally {
             setLoading(false);
         }
@@ -195,9 +307,18 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
                     }}
                 >
                     <h2>Active Workblock</h2>
+                    {activeWorkblock.title && <p style={{ fontStyle: "italic" }}>{activeWorkblock.title}</p>}
                     <p>
                         <strong>Duration:</strong> {formatDuration(activeWorkblock.duration_minutes || 0)}
                     </p>
+                    <div style={{ display: "flex", gap: "10px", justifyContent: "center" }}>
+                        <button onClick={() => handleAdjustDuration(-15)} disabled={loading}>
+                            −15 min
+                        </button>
+                        <button onClick={() => handleAdjustDuration(30)} disabled={loading}>
+                            +30 min
+                        </button>
+                    </div>
                     <p>
                         <strong>Started:</strong> {new Date(activeWorkblock.start_time).toLocaleTimeString()}
                     </p>
@@ -216,6 +337,27 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
                     )}
 
                     <div style={{ marginTop: "20px", display: "flex", gap: "10px", justifyContent: "center" }}>
+                        <select
+                            value={cancelReason}
+                            onChange={(e) => setCancelReason(e.target.value as CancelReason | "")}
+                            disabled={loading}
+                            style={{ padding: "8px" }}
+                        >
+                            <option value="">Reason (optional)</option>
+                            {CANCEL_REASONS.map((r) => (
+                                <option key={r.value} value={r.value}>
+                                    {r.label}
+                                </option>
+                            ))}
+                        </select>
+                        <input
+                            type="text"
+                            value={cancelNote}
+                            onChange={(e) => setCancelNote(e.target.value)}
+                            placeholder="Note (optional)"
+                            disabled={loading}
+                            style={{ padding: "8px" }}
+                        />
                         <button
                             onClick={handleCancelWorkblock}
                             disabled={loading}
@@ -245,6 +387,14 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
                 >
                     <h2>Start New Workblock</h2>
                     <div style={{ marginTop: "15px", display: "flex", flexDirection: "column", alignItems: "center" }}>
+                        <input
+                            type="text"
+                            value={title}
+                            onChange={(e) => setTitle(e.target.value)}
+                            placeholder="What is this block for? (optional)"
+                            maxLength={100}
+                            style={{ padding: "8px", marginBottom: "15px", width: "260px" }}
+                        />
                         <label style={{ display: "block", marginBottom: "10px" }}>Duration:</label>
                         <div style={{ display: "flex", gap: "15px", alignItems: "center" }}>
                             <div style={{ display: "flex", flexDirection: "column", gap: "5px" }}>
@@ -289,8 +439,27 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
                             </div>
                         </div>
                     </div>
+                    {durationPresets.length > 0 && (
+                        <div style={{ marginTop: "15px", display: "flex", gap: "8px", justifyContent: "center" }}>
+                            {durationPresets.map((preset) => (
+                                <button key={preset} onClick={() => handleStartWorkblock(preset)} disabled={loading}>
+                                    {formatDuration(preset)}
+                                </button>
+                            ))}
+                        </div>
+                    )}
+                    {plan && plan.intervals.length > 0 && (
+                        <div style={{ marginTop: "10px", fontSize: "13px", color: "#666" }}>
+                            You'll be prompted at{" "}
+                            {plan.intervals
+                                .slice(0, 4)
+                                .map((i) => new Date(i.end_time).toLocaleTimeString([], { hour: "2-digit", minute: "2-digit", second: "2-digit" }))
+                                .join(", ")}
+                            {plan.intervals.length > 4 ? `, … (${plan.intervals.length} prompts)` : ""}
+                        </div>
+                    )}
                     <button
-                        onClick={handleStartWorkblock}
+                        onClick={() => handleStartWorkblock()}
                         disabled={loading}
                         style={{
                             marginTop: "20px",
@@ -305,6 +474,25 @@ export default function WorkblockControl({ onNavigateToSummary, onNavigateToArch
                     >
                         {loading ? "Starting..." : "Start Workblock"}
                     </button>
+                    {lastCancelledId !== null && (
+                        <button
+                            onClick={handleResumeWorkblock}
+                            disabled={loading}
+                            style={{
+                                marginTop: "10px",
+                                marginLeft: "10px",
+                                padding: "12px 24px",
+                                backgroundColor: "#4a90e2",
+                                color: "white",
+                                border: "none",
+                                borderRadius: "5px",
+                                fontSize: "16px",
+                                cursor: loading ? "not-allowed" : "pointer",
+                            }}
+                        >
+                            Resume Cancelled Workblock
+                        </button>
+                    )}
                 </div>
             )}
 
@@ -1,6 +1,17 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import App from "./App";
+import type { ThemeInfo } from "./types/workblock";
+
+// Every window (main, prompt, summary, break) mirrors the backend's resolved theme
+function applyTheme({ theme }: ThemeInfo) {
+  document.documentElement.dataset.theme = theme;
+  document.documentElement.style.colorScheme = theme;
+}
+invoke<ThemeInfo>("get_theme").then(applyTheme).catch(console.error);
+listen<ThemeInfo>("theme-changed", (event) => applyTheme(event.payload));
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
@@ -0,0 +1,79 @@
+// Break overlay shown between workblocks in Pomodoro mode
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { BreakInfo } from "../types/workblock";
+
+export default function BreakPage() {
+    const [breakInfo, setBreakInfo] = useState<BreakInfo | null>(null);
+    const [secondsLeft, setSecondsLeft] = useState<number>(0);
+
+    useEffect(() => {
+        const loadBreak = async () => {
+            try {
+                setBreakInfo(await invoke<BreakInfo | null>("get_break_state"));
+            } catch (error) {
+                console.error("[BREAK_PAGE] Failed to load break state:", error);
+            }
+        };
+        loadBreak();
+
+        // The overlay window is reused when a new break starts before it was closed
+        const unlistenStarted = listen<BreakInfo>("break-started", (event) => {
+            setBreakInfo(event.payload);
+        });
+        const unlistenReset = listen("break-overlay-reset", () => {
+            loadBreak();
+        });
+
+        return () => {
+            unlistenStarted.then((fn) => fn());
+            unlistenReset.then((fn) => fn());
+        };
+    }, []);
+
+    useEffect(() => {
+        if (!breakInfo) return;
+        const update = () => {
+            const remaining = Math.ceil((new Date(breakInfo.ends_at).getTime() - Date.now()) / 1000);
+            setSecondsLeft(Math.max(0, remaining));
+        };
+        update();
+        const timer = setInterval(update, 1000);
+        return () => clearInterval(timer);
+    }, [breakInfo]);
+
+    const handleSkip = async () => {
+        try {
+            // Backend closes this window and emits break-ended
+            await invoke("skip_break");
+        } catch (error) {
+            console.error("[BREAK_PAGE] Failed to skip break:", error);
+        }
+    };
+
+    const minutes = Math.floor(secondsLeft / 60);
+    const seconds = secondsLeft % 60;
+
+    return (
+        <div
+            style={{
+                width: "100vw",
+                height: "100vh",
+                display: "flex",
+                flexDirection: "column",
+                alignItems: "center",
+                justifyContent: "center",
+                gap: "12px",
+                backgroundColor: "rgba(30, 30, 30, 0.92)",
+                color: "#fff",
+            }}
+        >
+            <h2 style={{ margin: 0, fontSize: "20px" }}>Take a break</h2>
+            <div style={{ fontSize: "36px", fontWeight: 600 }}>
+                {minutes}:{seconds.toString().padStart(2, "0")}
+            </div>
+            <button onClick={handleSkip}>Skip break</button>
+        </div>
+    );
+}
@@ -21,7 +21,8 @@ export default function PromptPage() {
     useEffect(() => {
         console.log("[PROMPT_PAGE] Component mounted! Setting up event listeners");
 
-        // Listen for interval ID from backend (fallback - URL should have it already)
+        // Listen for interval ID from backend (the window is reused across intervals;
+        // only a window created on demand has it in its URL)
         const setupListeners = async () => {
             const unlisten = await listen<number>("prompt-interval-id", (event) => {
                 console.log("[PROMPT_PAGE] Received prompt-interval-id event:", event.payload);
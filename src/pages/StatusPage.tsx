@@ -0,0 +1,52 @@
+// Read-only status for a second monitor or an office door display; never shows entry text
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { StatusPage as Status, StatusState } from "../types/workblock";
+
+const STATE_COLORS: Record<StatusState, string> = {
+    available: "#2e7d32",
+    focus: "#c62828",
+    break: "#f9a825",
+    paused: "#6a1b9a",
+};
+
+export default function StatusPage() {
+    const [status, setStatus] = useState<Status | null>(null);
+
+    useEffect(() => {
+        invoke<Status>("get_status_page")
+            .then(setStatus)
+            .catch((error) => console.error("[STATUS_PAGE] Failed to load status:", error));
+
+        // The ticker pushes "status-page" whenever a block, break or pause starts or ends
+        invoke("start_timer_ticker").catch((error) => console.error("[STATUS_PAGE] Failed to start timer ticker:", error));
+        const unlisten = listen<Status>("status-page", (event) => {
+            setStatus(event.payload);
+        });
+
+        return () => {
+            unlisten.then((fn) => fn());
+            invoke("stop_timer_ticker").catch(() => {});
+        };
+    }, []);
+
+    return (
+        <div
+            style={{
+                width: "100vw",
+                height: "100vh",
+                display: "flex",
+                alignItems: "center",
+                justifyContent: "center",
+                padding: "24px",
+                boxSizing: "border-box",
+                backgroundColor: status ? STATE_COLORS[status.state] : "#333",
+                color: "#fff",
+                textAlign: "center",
+            }}
+        >
+            <div style={{ fontSize: "min(8vw, 48px)", fontWeight: 600 }}>{status?.headline ?? ""}</div>
+        </div>
+    );
+}
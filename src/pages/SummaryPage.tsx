@@ -0,0 +1,94 @@
+// Summary page for the dedicated summary window
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../api/errors";
+import { listenEvent } from "../api/events";
+import SummaryView from "../components/SummaryView";
+import type { AiSummary, DailyVisualizationData, WorkblockSummary } from "../types/workblock";
+
+export default function SummaryPage() {
+    // The window URL carries the date and, after a workblock, its id; later shows send them as events
+    const getInitialParam = (name: string): string | null => {
+        const match = window.location.hash.match(new RegExp(`[?&]${name}=([^&]+)`));
+        return match ? decodeURIComponent(match[1]) : null;
+    };
+
+    const [date, setDate] = useState<string | undefined>(getInitialParam("date") ?? undefined);
+    const [data, setData] = useState<DailyVisualizationData | undefined>(undefined);
+    const [workblockId, setWorkblockId] = useState<number | null>(() => {
+        const id = parseInt(getInitialParam("workblockId") ?? "", 10);
+        return isNaN(id) ? null : id;
+    });
+    const [workblockSummary, setWorkblockSummary] = useState<WorkblockSummary | null>(null);
+    const [aiSummary, setAiSummary] = useState<string | null>(null);
+    const [aiEnabled, setAiEnabled] = useState(false);
+    const [aiStatus, setAiStatus] = useState<string | null>(null);
+
+    useEffect(() => {
+        const unlisten = listenEvent("summary-data", (payload) => {
+            setDate(payload.date);
+            setData(payload.data);
+            setWorkblockId(payload.workblock_id);
+            setAiSummary(payload.ai_summary);
+            setAiEnabled(payload.ai_enabled);
+            setAiStatus(null);
+        });
+
+        return () => {
+            unlisten.then((fn) => fn());
+        };
+    }, []);
+
+    useEffect(() => {
+        if (workblockId === null) {
+            setWorkblockSummary(null);
+            return;
+        }
+        invoke<WorkblockSummary>("get_workblock_summary", { workblockId })
+            .then(setWorkblockSummary)
+            .catch(console.error);
+    }, [workblockId]);
+
+    const summarizeDay = () => {
+        if (!date) return;
+        setAiStatus("Summarizing…");
+        invoke<AiSummary>("generate_ai_summary", { date })
+            .then((result) => {
+                setAiSummary(result.summary);
+                setAiStatus(null);
+            })
+            .catch((e) => setAiStatus(errorMessage(e)));
+    };
+
+    return (
+        <main className="container">
+            {workblockSummary && (
+                <div className="workblock-summary">
+                    <h3>Workblock complete</h3>
+                    <div>
+                        {workblockSummary.duration_minutes} min · {workblockSummary.intervals_recorded} recorded
+                        {workblockSummary.intervals_away + workblockSummary.intervals_quick_status > 0 &&
+                            ` · ${workblockSummary.intervals_away + workblockSummary.intervals_quick_status} away`}
+                        {` · Focus ${workblockSummary.focus_score}%`}
+                    </div>
+                    {workblockSummary.top_activities.length > 0 && (
+                        <div>Top: {workblockSummary.top_activities.map((a) => a.words).join(", ")}</div>
+                    )}
+                    {workblockSummary.note && <div className="workblock-summary-note">{workblockSummary.note}</div>}
+                </div>
+            )}
+            {(aiSummary || aiEnabled) && (
+                <div className="ai-summary">
+                    {aiSummary && <p>{aiSummary}</p>}
+                    {aiEnabled && (
+                        <button onClick={summarizeDay} disabled={aiStatus === "Summarizing…"}>
+                            {aiSummary ? "Summarize again" : "Summarize my day"}
+                        </button>
+                    )}
+                    {aiStatus && <div className="ai-summary-status">{aiStatus}</div>}
+                </div>
+            )}
+            <SummaryView date={date} data={data} />
+        </main>
+    );
+}
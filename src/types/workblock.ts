@@ -9,8 +9,14 @@ export interface Workblock {
     status: "active" | "completed" | "cancelled";
     is_archived?: boolean;
     created_at?: string;
+    project?: string; // Inherited by intervals without their own project
+    cancel_reason?: CancelReason; // Only set on cancelled workblocks
+    cancel_note?: string;
+    title?: string; // Intention set when starting the block
 }
 
+export type CancelReason = 'interrupted' | 'meeting' | 'distracted' | 'tired' | 'plans_changed' | 'other';
+
 export interface Interval {
     id?: number;
     workblock_id: number;
@@ -18,8 +24,35 @@ export interface Interval {
     start_time: string;
     end_time?: string;
     words?: string;
-    status: "pending" | "recorded" | "auto_away";
+    status: "pending" | "recorded" | "auto_away" | "quick_status";
     recorded_at?: string;
+    project?: string; // Per-interval override; unset inherits the workblock's project
+    category?: string; // Assigned by the first matching category rule
+    energy?: number; // 1 (drained) to 5 (energized), if rated
+    notes?: string; // Longer free-form notes; never used for activity grouping
+    provenance?: IntervalProvenance;
+    away_cause?: AwayCause | null; // Why it was recorded as away; kept if it is relabelled later
+}
+
+// How an interval's words got there; anything but "live" was recorded after the fact
+export type IntervalProvenance = 'live' | 'backfilled' | 'imported' | 'edited';
+
+// Why an interval was recorded as away instead of being answered
+export type AwayCause = 'prompt_timeout' | 'system_idle' | 'screen_lock' | 'prompts_paused' | 'system_sleep' | 'cancelled';
+
+export interface AwayCauseCount {
+    cause: AwayCause | null; // null for intervals recorded before causes were kept
+    intervals: number;
+    minutes: number;
+}
+
+export interface IntervalAttachment {
+    id?: number;
+    interval_id: number;
+    file_name: string;
+    stored_path: string; // Copy inside the app's managed attachments directory
+    size_bytes: number;
+    created_at?: string;
 }
 
 export interface TimerState {
@@ -29,6 +62,68 @@ export interface TimerState {
     interval_start_time: string | null;
     prompt_shown_time: string | null;
     is_running: boolean;
+    total_intervals: number; // Can change while running (extend/shorten)
+    prompted_interval_id: number | null; // Interval whose prompt went out most recently
+    snoozed_until: string | null;
+    auto_away_pending: AutoAwayCountdown | null; // Running auto-away countdown
+}
+
+export interface AutoAwayCountdown {
+    interval_id: number;
+    since: string;
+}
+
+// Payload of the "settings-changed" event
+export interface SettingChanged {
+    key: string;
+    value: string;
+}
+
+export type StatusState = 'available' | 'focus' | 'break' | 'paused';
+
+// Returned by get_status_page and the payload of "status-page"; contains no entry text
+export interface StatusPage {
+    state: StatusState;
+    headline: string; // e.g. "In a focus block until 15:30", in the configured language
+    until: string | null; // RFC 3339; null while available
+}
+
+// Payload of the "timer-tick" event (every second while subscribed via start_timer_ticker)
+export interface TimerTick {
+    workblock_id: number;
+    interval_number: number;
+    total_intervals: number;
+    interval_remaining_seconds: number;
+    workblock_remaining_seconds: number;
+}
+
+// Payload of the "missed-intervals" event (intervals that ended while the system was asleep)
+export interface MissedIntervals {
+    workblock_id: number;
+    interval_ids: number[];
+    first_interval_number: number;
+    last_interval_number: number;
+}
+
+export interface PomodoroSettings {
+    enabled: boolean; // Run a break timer after each completed workblock
+    break_seconds: number;
+    auto_start_next: boolean; // Start another workblock of the same length when the break ends
+}
+
+// Payload of the "break-started" event; also returned by get_break_state
+export interface BreakInfo {
+    workblock_id: number; // The workblock the break follows
+    break_seconds: number;
+    started_at: string;
+    ends_at: string;
+    next_duration_minutes: number;
+}
+
+// Payload of the "break-ended" event
+export interface BreakEnded {
+    workblock_id: number;
+    skipped: boolean; // Ended early (skipped, or a workblock was started during the break)
 }
 
 // Visualization data types
@@ -39,6 +134,10 @@ export interface TimelineData {
     words?: string;
     duration_minutes: number;
     workblock_status?: string;
+    attachments?: IntervalAttachment[];
+    project?: string; // Effective project (interval override or workblock project)
+    notes?: string;
+    provenance?: IntervalProvenance;
 }
 
 export interface AggregateTimelineData {
@@ -49,6 +148,27 @@ export interface AggregateTimelineData {
     words?: string;
     duration_minutes: number;
     workblock_status?: string;
+    attachments?: IntervalAttachment[];
+    project?: string; // Effective project (interval override or workblock project)
+    notes?: string;
+    workblock_title?: string;
+    provenance?: IntervalProvenance;
+}
+
+export interface ProjectData {
+    project?: string; // Unset = intervals without any project
+    total_minutes: number;
+    interval_count: number;
+    percentage: number;
+}
+
+export interface DeepWorkSession {
+    workblock_id: number;
+    activity: string; // Normalized activity shared by every interval in the session
+    start_time: string;
+    end_time?: string;
+    interval_count: number;
+    duration_minutes: number;
 }
 
 export interface WorkblockBoundary {
@@ -62,6 +182,22 @@ export interface ActivityData {
     words: string;
     total_minutes: number;
     percentage: number;
+    average_energy: number | null; // Mean rating of the activity's rated intervals
+    color: string | null; // From the activity color registry; null in older archives
+}
+
+// Compact end-of-workblock summary for the summary-ready window
+export interface WorkblockSummary {
+    workblock_id: number;
+    date: string;
+    status: string;
+    duration_minutes: number;
+    intervals_recorded: number;
+    intervals_away: number;
+    intervals_quick_status: number;
+    top_activities: ActivityData[];
+    focus_score: number; // 0-100
+    note: string | null;
 }
 
 export interface WordFrequency {
@@ -73,7 +209,25 @@ export interface WorkblockVisualization {
     id: number;
     timeline_data: TimelineData[];
     activity_data: ActivityData[];
-    word_frequency: WordFrequency[];
+    word_frequency: WordFrequency[]; // Whole phrases
+    word_counts?: WordFrequency[]; // Single words without stop words, most frequent first
+    apps_used: AppUsage[];
+    energy_timeline: EnergyPoint[];
+}
+
+// A rated interval (energy 1 = drained to 5 = energized)
+export interface EnergyPoint {
+    workblock_id: number;
+    interval_number: number;
+    start_time: string;
+    energy: number;
+}
+
+// Focused application time across a workblock (empty unless activity capture is enabled)
+export interface AppUsage {
+    app_name: string;
+    seconds: number;
+    percentage: number;
 }
 
 export interface DailyAggregate {
@@ -83,6 +237,43 @@ export interface DailyAggregate {
     activity_data: ActivityData[];
     word_frequency: WordFrequency[];
     workblock_boundaries?: WorkblockBoundary[]; // Optional for backward compatibility with old archived data
+    is_truncated?: boolean; // Set when timeline_data was capped by TimelineLimits.max_entries
+    project_data?: ProjectData[]; // Time per interval-level project
+    deep_work_sessions?: DeepWorkSession[];
+    over_daily_limit?: boolean; // Tracked time passed the wellbeing limit (only when the guard is on)
+    energy_timeline: EnergyPoint[];
+    after_the_fact_minutes?: number; // Timeline minutes that were backfilled, imported or edited
+    away_causes?: AwayCauseCount[]; // Why intervals were recorded as away, most minutes first
+}
+
+export interface TimelineEntryKey {
+    workblock_id: number;
+    interval_number: number;
+}
+
+// Payload of "daily-aggregate-updated": how a cached day's aggregate changed
+export interface DailyAggregateDelta {
+    date: string;
+    total_workblocks: number;
+    total_minutes: number;
+    over_daily_limit: boolean;
+    after_the_fact_minutes: number;
+    timeline_upserted: AggregateTimelineData[]; // New or changed entries
+    timeline_removed: TimelineEntryKey[];
+    activities_upserted: ActivityData[];
+    activities_removed: string[];
+    // Replaced whole; null when unchanged
+    word_frequency: WordFrequency[] | null;
+    workblock_boundaries: WorkblockBoundary[] | null;
+    project_data: ProjectData[] | null;
+    deep_work_sessions: DeepWorkSession[] | null;
+    energy_timeline: EnergyPoint[] | null;
+    away_causes: AwayCauseCount[] | null;
+}
+
+export interface TimelineLimits {
+    min_segment_minutes?: number; // Merge segments shorter than this into the previous one
+    max_entries?: number;
 }
 
 export interface DailyVisualizationData {
@@ -97,4 +288,676 @@ export interface DailyArchive {
     total_minutes: number;
     visualization_data?: string; // JSON string
     archived_at?: string;
+    goal_streak: number; // Streak of days with every goal attained, ending on this day
+    goals_attained?: boolean; // Unset when no goals were set
+    ai_summary?: string | null; // Written by generate_ai_summary
+}
+
+// DailyArchive without visualization_data (see get_archive_visualization)
+export type ArchiveSummary = Omit<DailyArchive, 'id' | 'visualization_data'>;
+
+export interface ArchiveHistoryPage {
+    archives: ArchiveSummary[]; // Newest first
+    total_count: number;
+    has_more: boolean;
+}
+
+export type GoalMetric = 'tracked_minutes' | 'recorded_intervals';
+
+export interface Goal {
+    metric: GoalMetric;
+    target: number;
+    created_at?: string;
+}
+
+export interface GoalProgress {
+    metric: GoalMetric;
+    target: number;
+    actual: number;
+    attained: boolean;
+    percentage: number; // Capped at 100
+}
+
+export interface DailyGoalStatus {
+    date: string;
+    goals: GoalProgress[];
+    all_attained: boolean;
+    current_streak: number;
+}
+
+export interface HeatmapDay {
+    date: string;
+    total_minutes: number;
+    total_workblocks: number;
+    untracked: boolean; // Past day with nothing recorded (no workblocks, or only idle ones)
+}
+
+export interface MonthlySummary {
+    year: number;
+    month: number;
+    heatmap: HeatmapDay[]; // One entry per calendar day, including empty days
+    total_workblocks: number;
+    total_minutes: number;
+    top_activities: WordFrequency[];
+    average_workblock_minutes: number;
+}
+
+export interface ActivitySuggestion {
+    words: string; // Most recently used spelling of the phrase
+    count: number;
+    last_used: string;
+    score: number;
+}
+
+export interface ActivityAlias {
+    alias: string; // Normalized raw phrase
+    canonical: string;
+    created_at?: string;
+}
+
+export interface ActivityColor {
+    activity: string; // Normalized activity
+    color: string; // "#rrggbb"
+}
+
+export type SuggestionSource = 'pinned' | 'recent' | 'attachment';
+
+export interface PromptSuggestion {
+    words: string;
+    sources: SuggestionSource[]; // Every source that offered this phrase
+    score: number;
+}
+
+// get_prompt_quick_picks: one-click answers for the prompt
+export interface PromptQuickPicks {
+    last_words?: string; // Words of the most recently recorded interval
+    top_today: string[]; // Today's most-logged activities, most intervals first
+    pinned: string[];
+}
+
+export interface AutoAwaySettings {
+    timeout_seconds: number;
+    message: string; // Recorded as the interval words on auto-away
+}
+
+// Payload of the "archive-updated" event
+export interface ArchiveUpdated {
+    date: string;
+    total_workblocks: number;
+    total_minutes: number;
+}
+
+export interface ReclassifyOffer {
+    interval_id: number; // The auto-away interval that can be rewritten
+    interval_number: number;
+    suggested_words?: string; // Last words recorded before the away interval
+}
+
+// A prompt labelled with the time range its interval covered
+export interface PromptLabel {
+    interval_id: number;
+    interval_number: number;
+    start_time: string;
+    end_time?: string;
+}
+
+// Payload of "prompt-queue" and get_prompt_queue
+export interface PromptQueue {
+    current?: PromptLabel;
+    pending: PromptLabel[]; // Waiting behind the current prompt, oldest first
+}
+
+export interface CategoryRule {
+    id?: number;
+    pattern: string;
+    match_type: "contains" | "regex"; // Both case-insensitive
+    category: string;
+    priority: number; // Lower runs first; the first matching rule wins
+    enabled: boolean;
+    created_at?: string;
+}
+
+export interface TaxonomyPack {
+    version: number;
+    exported_at?: string;
+    rules: Omit<CategoryRule, "id" | "created_at">[];
+    aliases: Omit<ActivityAlias, "created_at">[];
+    colors: ActivityColor[];
+    pinned: string[];
+}
+
+export type TaxonomyImportMode = "merge" | "replace";
+
+export interface TaxonomyImportSummary {
+    rules_added: number;
+    aliases_set: number;
+    colors_set: number;
+    pinned_added: number;
+}
+
+export interface RuleMatch {
+    interval_id: number;
+    workblock_id: number;
+    date: string;
+    words: string;
+    current_category?: string;
+}
+
+export interface RulePreview {
+    total_matches: number;
+    changed_count: number; // Matches whose category would actually change
+    matches: RuleMatch[]; // Most recent first, capped
+}
+
+export interface WeeklyReviewDay {
+    date: string;
+    total_minutes: number;
+    total_workblocks: number;
+    deep_work_sessions: number;
+    deep_work_minutes: number;
+}
+
+export interface WeeklyReview {
+    start_date: string; // Inclusive
+    end_date: string; // Inclusive
+    days: WeeklyReviewDay[]; // Seven entries, including empty days
+    total_workblocks: number;
+    total_minutes: number;
+    deep_work_sessions: number;
+    deep_work_minutes: number;
+    top_activities: WordFrequency[];
+    cancelled_workblocks: number;
+    cancellation_reasons: CancelReasonCount[]; // Most frequent first
+    burnout: BurnoutIndicator;
+    context?: ContextGroup[]; // Empty unless context snapshots are on
+    away_causes?: AwayCauseCount[];
+}
+
+// Workspace when an interval was answered (opt-in); null parts couldn't be detected
+export interface ContextSnapshot {
+    active_app: string | null;
+    monitor_count: number | null;
+    on_battery: boolean | null;
+    captured_at: string;
+}
+
+// Intervals answered in one context over a week
+export interface ContextGroup {
+    dimension: 'power' | 'monitors' | 'app';
+    value: string; // e.g. "battery"/"plugged_in", "2", "Code"
+    intervals: number;
+    average_energy: number | null; // null when none were rated
+}
+
+export type BurnoutLevel = 'low' | 'moderate' | 'high';
+
+// Signs of unsustainable habits in one week
+export interface BurnoutSignals {
+    week_start: string;
+    late_intervals: number; // Started in the late evening or early morning
+    total_intervals: number;
+    weekend_minutes: number;
+    total_minutes: number;
+    over_limit_days: number; // Days tracked past the wellbeing daily limit
+    score: number; // 0 (sustainable) to 100
+}
+
+export interface BurnoutIndicator {
+    week: BurnoutSignals; // The reviewed week
+    rolling_score: number; // Mean over the reviewed week and the weeks before it
+    level: BurnoutLevel;
+    weekly_scores: number[]; // Oldest first
+}
+
+export interface CancelReasonCount {
+    reason?: CancelReason; // Unset = cancelled without giving a reason
+    count: number;
+    notes: string[]; // Most recent first
+}
+
+export interface HotkeySettings {
+    open_prompt: string; // e.g. "CommandOrControl+Shift+L"; empty disables
+    toggle_workblock: string;
+    workblock_minutes: number; // Length of workblocks started from the hotkey
+    repeat_last: string; // Answers the open prompt with the previous interval's words
+}
+
+export type OnboardingStep = 'welcome' | 'durations' | 'auto_away' | 'hotkeys' | 'first_workblock';
+
+export interface OnboardingState {
+    first_launch_at?: string;
+    completed_steps: OnboardingStep[]; // In flow order
+    next_step?: OnboardingStep; // Unset once every step is done
+    completed_at?: string;
+}
+
+// Result of run_readonly_query (data explorer)
+export interface QueryResult {
+    columns: string[];
+    rows: (string | number | null)[][]; // One value per column, in column order
+    truncated: boolean; // More rows matched than the row limit
+}
+
+export interface PlannedInterval {
+    interval_number: number;
+    start_time: string;
+    end_time: string; // When the prompt for this interval appears
+}
+
+// Returned by preview_workblock_plan before a workblock is started
+export interface WorkblockPlan {
+    duration_minutes: number;
+    interval_seconds: number;
+    aligned_to_clock: boolean;
+    intervals: PlannedInterval[];
+}
+
+// Returned by get_settings_history (newest first)
+export interface SettingChange {
+    key: string;
+    old_value?: string; // Unset = the setting had no value before
+    new_value: string;
+    changed_at: string;
+}
+
+export interface WellbeingSettings {
+    enabled: boolean; // Opt-in
+    daily_limit_minutes: number;
+}
+
+// Payload of the "wellbeing-limit-exceeded" event (at most once per day)
+export interface WellbeingAlert {
+    date: string;
+    tracked_minutes: number;
+    daily_limit_minutes: number;
+}
+
+// Heads-up before each interval ends (get/set_interval_warning_settings)
+export interface IntervalWarningSettings {
+    seconds_before: number; // 0 = off
+    notification: boolean; // Also show a system notification
+}
+
+// Payload of the "interval-ending-soon" event
+export interface IntervalEndingSoon {
+    workblock_id: number;
+    interval_id: number | null;
+    interval_number: number;
+    ends_at: string;
+    seconds_left: number;
+    notification: boolean;
+}
+
+// What the tray title counts down (macOS shows it next to the icon)
+export type TrayCountdownMode = 'off' | 'interval' | 'workblock';
+
+// Language of backend-built UI such as the tray menu
+export type Language = 'en' | 'de' | 'fr' | 'es';
+
+export type ThemePreference = 'system' | 'dark' | 'light';
+
+// Result of get_theme and payload of the "theme-changed" event
+export interface ThemeInfo {
+    preference: ThemePreference;
+    theme: 'light' | 'dark'; // What the windows actually show
+}
+
+// Prompts paused from the tray or settings (emitted as "dnd-changed")
+export interface DndStatus {
+  active: boolean;
+  until: string | null;
+  remaining_seconds: number;
+}
+
+// How numbers, dates and times are written in exports
+export interface LocaleSettings {
+    decimal_separator: 'dot' | 'comma';
+    time_format: 'h24' | 'h12';
+    date_order: 'ymd' | 'dmy' | 'mdy';
+}
+
+// Opt-in sampling of the focused application during workblocks
+export interface ActivityCaptureSettings {
+  enabled: boolean;
+  window_titles: boolean;
+  sample_seconds: number;
+}
+
+export interface IntervalAppUsage {
+  app_name: string;
+  window_title: string | null;
+  seconds: number;
+}
+
+// Daily reconciliation from get_time_audit; null means the source wasn't available
+export interface TimeAudit {
+  date: string;
+  tracked_minutes: number;
+  meeting_minutes: number | null;
+  active_computer_minutes: number | null;
+  untracked_computer_minutes: number | null;
+  untracked_percentage: number | null;
+}
+
+// Result of export_compliance_csv
+export interface ComplianceExportSummary {
+  path: string;
+  workblocks: number;
+  days: number;
+  total_minutes: number;
+  sha256: string; // Also the file's last line
+}
+
+// A database snapshot from list_backups / create_backup_now
+export interface BackupInfo {
+  path: string;
+  file_name: string;
+  created_at: string;
+  reason: 'startup' | 'migration' | 'manual' | 'pre_restore' | 'pre_import';
+  size_bytes: number;
+}
+
+export type ImportMode = 'replace' | 'merge';
+
+// Returned by import_database
+export interface MergeResult {
+  workblocks_imported: number;
+  intervals_imported: number;
+  duplicates_skipped: number;
+  active_skipped: number;
+  dates: string[];
+}
+
+export interface ImportSummary {
+  mode: ImportMode;
+  merge: MergeResult | null;  // null for replace
+  rebuilt_archives: string[];
+  backup: BackupInfo;         // the database as it was before the import
+}
+
+// Result of wipe_all_data, also the payload of the "data-wiped" event
+export interface WipeSummary {
+  workblocks_deleted: number;
+  intervals_deleted: number;
+  archives_deleted: number;
+  settings_deleted: number;
+  attachments_deleted: number;
+  backups_deleted: number;  // 0 unless backups were included
+}
+
+// Parameters for get_report / export_report_json
+export type ReportRequest =
+  | { report: "daily_aggregate"; date: string }
+  | { report: "workblock_summary"; workblock_id: number }
+  | { report: "weekly_review"; week_start: string }
+  | { report: "monthly_summary"; year: number; month: number }
+  | { report: "goal_status"; date: string }
+  | { report: "burnout_indicator"; week_start: string }
+  | { report: "time_audit"; date: string }
+  | { report: "share_card_stats"; date: string };
+
+// Versioned JSON report envelope; `data` is the report struct for `report`
+export interface ReportEnvelope {
+  schema_version: number;
+  report: ReportRequest["report"];
+  generated_at: string;
+  request: ReportRequest;
+  data: unknown;
+}
+
+// Result of export_parquet (only in builds with the `parquet-export` feature)
+export interface ParquetExportSummary {
+  intervals_path: string;
+  workblocks_path: string;
+  intervals: number;
+  workblocks: number;
+}
+
+// A problem the app detected and repaired on its own (see get_diagnostics)
+export interface Incident {
+  id: number;
+  source: string;
+  detail: string;
+  occurred_at: string;
+}
+
+// Returned by run_timer_selftest
+export interface SelftestCheck {
+  name: 'tick_scheduling' | 'missed_intervals' | 'auto_away' | 'completion' | 'wall_clock_wait';
+  passed: boolean;
+  detail: string;
+}
+
+export interface SelftestReport {
+  passed: boolean;
+  checks: SelftestCheck[];
+  ran_at: string;
+  elapsed_ms: number;
+}
+
+// An interval whose words or notes matched search_intervals
+export interface IntervalSearchResult {
+  date: string;
+  interval: Interval;
+}
+
+// Payload of the "milestone" event
+export type Milestone =
+  | { event: 'goal_reached'; date: string; metric: 'tracked_minutes' | 'recorded_intervals'; target: number; actual: number }
+  | { event: 'budget_exceeded'; date: string; tracked_minutes: number; daily_limit_minutes: number }
+  | { event: 'streak_milestone'; date: string; streak: number };
+
+// Headline stats drawn on the share card (generate_share_card)
+export interface ShareCardStats {
+  date: string;
+  tracked_minutes: number;
+  workblocks: number;
+  intervals_recorded: number;
+  hourly_minutes: number[]; // 24 entries by local hour
+}
+
+// Events a webhook can subscribe to
+export type WebhookEvent =
+  | 'workblock_started'
+  | 'workblock_completed'
+  | 'day_archived'
+  | 'goal_reached'
+  | 'budget_exceeded'
+  | 'streak_milestone';
+
+// A URL notified with signed JSON POSTs (X-Log15-Signature: sha256=<HMAC of the body>)
+export interface Webhook {
+  id: number;
+  url: string;
+  events: WebhookEvent[];
+  secret: string;
+  enabled: boolean;
+  created_at: string;
+  last_status: string | null; // HTTP status or transport error of the last delivery
+  last_delivery_at: string | null;
+}
+
+// Messages sent with milestone webhooks; {date}, {metric}, {target}, {actual}, {tracked_minutes},
+// {daily_limit_minutes} and {streak} are filled in. A blank template goes back to its default.
+export interface MilestoneTemplates {
+  goal_reached: string;
+  budget_exceeded: string;
+  streak_milestone: string;
+}
+
+// Local REST API on 127.0.0.1 (requests need "Authorization: Bearer <token>")
+export interface RestApiSettings {
+  enabled: boolean;
+  port: number;
+  token: string; // Empty until the API is first enabled
+}
+
+// A row deleted after the cursor of a change set
+export interface DeletedRow {
+  table_name: 'workblocks' | 'intervals' | 'daily_archives';
+  row_id: number;
+  deleted_at: string;
+}
+
+// Everything created, modified or deleted since a cursor (get_changes_since)
+export interface ChangeSet {
+  since: string;
+  until: string; // Pass as `since` on the next call
+  workblocks: Workblock[];
+  intervals: Interval[];
+  archives: DailyArchive[];
+  deleted: DeletedRow[];
+}
+
+// File kept current for status bars (Polybar, xbar, Stream Deck)
+export interface StatusFileSettings {
+  enabled: boolean;
+  path: string; // Absolute; empty writes status.json or status.txt in the app data directory
+  format: 'json' | 'text';
+}
+
+// Contents of the status file in the json format (get_status_file_preview)
+export interface StatusFile {
+  state: StatusState;
+  activity: string | null;
+  interval_seconds_remaining: number | null;
+  today_minutes: number;
+  text: string; // The line written in the text format
+}
+
+// Result of export_to_daily_note
+export interface DailyNoteExport {
+  path: string;
+  replaced: boolean; // An earlier log15 section was replaced rather than appended
+  timeline_entries: number;
+  total_minutes: number;
+}
+
+// Payload of the "ready" event (also returned by get_startup_report)
+export interface StartupReport {
+  phases: { name: string; millis: number; background: boolean }[];
+  setup_millis: number; // Until the main window could be shown
+  total_millis: number; // Until every background step finished
+}
+
+// Model used for end-of-day summaries (only interval words are sent)
+export interface AiSettings {
+  enabled: boolean;
+  endpoint: string; // OpenAI-compatible base URL; /chat/completions is appended
+  model: string;
+  api_key: string;
+}
+
+// Voice answers in the prompt (transcribe_and_submit)
+export interface TranscriptionSettings {
+  enabled: boolean;
+  backend: "local" | "api";
+  endpoint: string; // OpenAI-compatible base URL; /audio/transcriptions is appended
+  model: string;
+  api_key: string;
+  whisper_model_path: string; // ggml model file for the local backend
+}
+
+// Result of generate_ai_summary
+export interface AiSummary {
+  date: string;
+  summary: string;
+}
+
+// How entries are split into words for WorkblockVisualization.word_counts
+export interface WordCloudSettings {
+  stop_words: string[];
+  min_length: number;
+}
+
+// Payload of "day-rolled-over": a new day began while the app was running
+export interface DayRolledOver {
+  previous_date: string;
+  date: string;
+  archived_date: string | null; // null when the previous day was quiet or already archived
+  ended_workblock_id: number | null; // Workblock that ran into the new day and ended at its start
+  continued_workblock_id: number | null; // Workblock carrying on with the rest of its planned time
+}
+
+// Payload of "interval-complete"
+export interface IntervalComplete {
+  workblock_id: number;
+  interval_id: number;
+  interval_number: number;
+}
+
+// Payload of "summary-data"
+export interface SummaryWindowData {
+  date: string;
+  workblock_id: number | null;
+  data: DailyVisualizationData;
+  ai_summary: string | null;
+  ai_enabled: boolean;
+}
+
+// Every backend event and its payload; mirrors AppEvent in src-tauri/src/events.rs
+export interface EventPayloads {
+  "interval-complete": IntervalComplete;
+  "missed-intervals": MissedIntervals;
+  "workblock-complete": number; // Workblock id
+  "workblock-resumed": number; // Workblock id
+  "workblock-adjusted": Workblock;
+  "auto-away": number; // Interval id
+  "idle-auto-away": number; // Interval id
+  "prompt-hide": null;
+  "prompt-interval-id": number;
+  "prompt-queue": PromptQueue;
+  "reclassify-offer": ReclassifyOffer;
+  "summary-data": SummaryWindowData;
+  "timer-tick": TimerTick;
+  "status-page": StatusPage;
+  "break-started": BreakInfo;
+  "break-ended": BreakEnded;
+  "break-overlay-reset": number; // Break length in seconds
+  "wellbeing-limit-exceeded": WellbeingAlert;
+  "interval-ending-soon": IntervalEndingSoon;
+  milestone: Milestone;
+  "archive-updated": ArchiveUpdated;
+  "daily-aggregate-updated": DailyAggregateDelta;
+  "day-rolled-over": DayRolledOver;
+  "dnd-changed": DndStatus;
+  "settings-changed": SettingChanged;
+  "language-changed": Language;
+  "theme-changed": ThemeInfo;
+  "data-wiped": WipeSummary;
+  "tray-start-workblock": null;
+  "tray-view-last-words": null;
+  ready: StartupReport;
+}
+
+export type EventName = keyof EventPayloads;
+
+// Entry of get_event_schema
+export interface EventSchema {
+  name: EventName;
+  payload: string; // Type name above, or number/null
+  acked: boolean; // Arrives as { seq, payload }; use listenAcked
+  description: string;
+}
+
+export type Log15ErrorCode =
+  | "no_active_workblock"
+  | "already_running"
+  | "not_found"
+  | "invalid_input"
+  | "database_locked" // Another connection held the lock; retrying usually works
+  | "database"
+  | "window"
+  | "other";
+
+// What a failed command rejects with
+export interface Log15Error {
+  code: Log15ErrorCode;
+  message: string;
+}
+
+// One of several activities an interval was split between (submit_interval_words `activities`)
+export interface ActivitySplit {
+  words: string;
+  percentage: number; // An interval's splits add up to 100
 }